@@ -0,0 +1,195 @@
+//! Buffer manipulation API for plugins
+//!
+//! When one of a plugin's commands runs, the plugin receives the active buffer
+//! as a [`BufferApi`] through its [`CommandContext`](crate::plugin::CommandContext).
+//!
+//! # Positions
+//!
+//! Positions are zero-based. Columns count characters (not bytes) and a line
+//! does not include its line break. A position at the end of a line is valid
+//! for insertion. Ranges are half-open: `end` is not included.
+//!
+//! # Undo
+//!
+//! All edits made by one plugin command form a single undo step.
+//!
+//! # Versioning
+//!
+//! [`BUFFER_API_VERSION`] is incremented whenever methods are added to
+//! [`BufferApi`]. New methods come with default implementations, so a plugin
+//! built against an older version keeps working; a plugin relying on a newer
+//! method can check [`BufferApi::version`] first.
+//!
+//! # Example
+//!
+//! ```rust
+//! use termide_plugin_api::buffer::{BufferApi, BufferError, TextPosition};
+//!
+//! /// Inserts a comment marker at the start of the cursor line
+//! fn comment_line(buffer: &mut dyn BufferApi) -> Result<(), BufferError> {
+//!     let cursor = buffer.cursor();
+//!     buffer.insert(TextPosition::new(cursor.line, 0), "// ")?;
+//!     buffer.set_cursor(TextPosition::new(cursor.line, cursor.column + 3))
+//! }
+//! ```
+
+use std::path::Path;
+use thiserror::Error;
+
+/// Version of the [`BufferApi`] trait implemented by this crate
+pub const BUFFER_API_VERSION: u32 = 1;
+
+/// A position in a buffer (zero-based line and character column)
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::buffer::TextPosition;
+///
+/// let position = TextPosition::new(2, 4);
+/// assert!(TextPosition::new(1, 10) < position);
+/// assert_eq!(position.to_string(), "3:5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TextPosition {
+    /// Zero-based line index
+    pub line: usize,
+    /// Zero-based column, in characters
+    pub column: usize,
+}
+
+impl TextPosition {
+    /// Creates a position from a zero-based line and column
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl std::fmt::Display for TextPosition {
+    /// Formats the position one-based, as shown in the status bar (`line:column`)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column + 1)
+    }
+}
+
+/// A half-open range of text between two positions
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::buffer::{TextPosition, TextRange};
+///
+/// // The bounds are ordered, whichever comes first
+/// let range = TextRange::new(TextPosition::new(1, 0), TextPosition::new(0, 3));
+/// assert_eq!(range.start, TextPosition::new(0, 3));
+/// assert!(!range.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextRange {
+    /// First position in the range
+    pub start: TextPosition,
+    /// Position just after the range
+    pub end: TextPosition,
+}
+
+impl TextRange {
+    /// Creates a range between two positions in either order
+    pub fn new(a: TextPosition, b: TextPosition) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    /// Returns `true` if the range contains no text
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Error type for buffer operations
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::buffer::{BufferError, TextPosition};
+///
+/// let err = BufferError::InvalidPosition(TextPosition::new(9, 0));
+/// assert_eq!(err.to_string(), "position 10:1 is outside the buffer");
+/// ```
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum BufferError {
+    /// The position is past the end of its line or of the buffer
+    #[error("position {0} is outside the buffer")]
+    InvalidPosition(TextPosition),
+
+    /// The buffer does not allow editing
+    #[error("buffer is read-only")]
+    ReadOnly,
+}
+
+/// Access to the active buffer for plugin commands
+///
+/// Implemented by the editor; plugins only use it. See the
+/// [module documentation](self) for the position conventions.
+pub trait BufferApi {
+    /// Returns the version of this trait the editor implements
+    fn version(&self) -> u32 {
+        BUFFER_API_VERSION
+    }
+
+    /// Returns the number of lines (at least 1)
+    fn line_count(&self) -> usize;
+
+    /// Returns the text of `line` without its line break
+    fn line(&self, line: usize) -> Option<String>;
+
+    /// Returns the text in `range`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidPosition`] if a bound is outside the buffer.
+    fn text(&self, range: TextRange) -> Result<String, BufferError>;
+
+    /// Inserts `text` at `position`, returning the position just after it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is outside the buffer or the buffer
+    /// is read-only.
+    fn insert(&mut self, position: TextPosition, text: &str) -> Result<TextPosition, BufferError>;
+
+    /// Deletes the text in `range`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a bound is outside the buffer or the buffer is
+    /// read-only.
+    fn delete(&mut self, range: TextRange) -> Result<(), BufferError>;
+
+    /// Returns the cursor position
+    fn cursor(&self) -> TextPosition;
+
+    /// Moves the cursor
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidPosition`] if the position is outside the buffer.
+    fn set_cursor(&mut self, position: TextPosition) -> Result<(), BufferError>;
+
+    /// Returns the selected range, if any text is selected
+    fn selection(&self) -> Option<TextRange>;
+
+    /// Selects `range` and moves the cursor to its end, or clears the selection
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BufferError::InvalidPosition`] if a bound is outside the buffer.
+    fn set_selection(&mut self, range: Option<TextRange>) -> Result<(), BufferError>;
+
+    /// Returns the path of the file the buffer was loaded from or saved to
+    fn file_path(&self) -> Option<&Path>;
+
+    /// Returns `true` if the buffer has unsaved changes
+    fn is_dirty(&self) -> bool;
+}
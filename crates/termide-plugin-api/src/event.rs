@@ -0,0 +1,181 @@
+//! Editor events plugins can subscribe to
+//!
+//! The editor publishes an [`EditorEvent`] when a buffer is opened, saved or
+//! changed, when the mode changes and when the configuration is reloaded. A
+//! plugin subscribes by returning an [`EventSubscriber`] from
+//! [`Plugin::event_subscriber`](crate::plugin::Plugin::event_subscriber).
+//!
+//! # Ordering
+//!
+//! Events are delivered in the order they were published, after the command
+//! that caused them has finished. Each event reaches every subscribed plugin,
+//! in the order the plugins were loaded, before the next event is delivered.
+//!
+//! # Reentrancy
+//!
+//! Handlers never run nested. Events caused by a handler, such as the
+//! [`BufferChanged`](EditorEvent::BufferChanged) of its own edits, are queued
+//! behind the event being delivered and are not delivered back to the plugin
+//! whose handler caused them. The editor stops delivering events caused by
+//! handlers after a fixed number per command, so plugins reacting to each
+//! other's events cannot loop forever.
+//!
+//! # Example
+//!
+//! ```rust
+//! use termide_plugin_api::event::{EditorEvent, EventKind, EventSubscriber};
+//! use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+//!
+//! /// Counts saves in a status line segment
+//! struct SaveCounter {
+//!     saves: usize,
+//! }
+//!
+//! impl Plugin for SaveCounter {
+//!     fn name(&self) -> &str {
+//!         "save-counter"
+//!     }
+//!
+//!     fn execute(&mut self, command: &str, _context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+//!         Err(PluginError::UnknownCommand(command.to_string()))
+//!     }
+//!
+//!     fn event_subscriber(&mut self) -> Option<&mut dyn EventSubscriber> {
+//!         Some(self)
+//!     }
+//! }
+//!
+//! impl EventSubscriber for SaveCounter {
+//!     fn subscriptions(&self) -> Vec<EventKind> {
+//!         vec![EventKind::BufferSaved]
+//!     }
+//!
+//!     fn on_event(&mut self, event: &EditorEvent, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+//!         if let EditorEvent::BufferSaved { .. } = event {
+//!             self.saves += 1;
+//!             context.set_status_segment("saves", Some(format!("{} saves", self.saves)));
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//! ```
+
+use std::path::PathBuf;
+
+use crate::input::EditorMode;
+use crate::plugin::{CommandContext, PluginError};
+
+/// Something that happened in the editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorEvent {
+    /// A file was opened in a new buffer
+    BufferOpened { path: PathBuf },
+    /// A buffer was written; `path` is `None` for buffers saved to standard output
+    BufferSaved { path: Option<PathBuf> },
+    /// A command changed the text of a buffer
+    ///
+    /// Published once per command and buffer, however many edits the command
+    /// made.
+    BufferChanged { path: Option<PathBuf> },
+    /// The editing mode changed
+    ModeChanged { from: EditorMode, to: EditorMode },
+    /// The configuration files were reloaded
+    ConfigReloaded,
+}
+
+impl EditorEvent {
+    /// Returns the kind of the event
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide_plugin_api::event::{EditorEvent, EventKind};
+    ///
+    /// assert_eq!(EditorEvent::ConfigReloaded.kind(), EventKind::ConfigReloaded);
+    /// ```
+    pub fn kind(&self) -> EventKind {
+        match self {
+            EditorEvent::BufferOpened { .. } => EventKind::BufferOpened,
+            EditorEvent::BufferSaved { .. } => EventKind::BufferSaved,
+            EditorEvent::BufferChanged { .. } => EventKind::BufferChanged,
+            EditorEvent::ModeChanged { .. } => EventKind::ModeChanged,
+            EditorEvent::ConfigReloaded => EventKind::ConfigReloaded,
+        }
+    }
+}
+
+/// The kind of an [`EditorEvent`], used to subscribe to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EventKind {
+    /// [`EditorEvent::BufferOpened`]
+    BufferOpened,
+    /// [`EditorEvent::BufferSaved`]
+    BufferSaved,
+    /// [`EditorEvent::BufferChanged`]
+    BufferChanged,
+    /// [`EditorEvent::ModeChanged`]
+    ModeChanged,
+    /// [`EditorEvent::ConfigReloaded`]
+    ConfigReloaded,
+}
+
+impl EventKind {
+    /// Every event kind, in declaration order
+    pub const ALL: [EventKind; 5] = [
+        EventKind::BufferOpened,
+        EventKind::BufferSaved,
+        EventKind::BufferChanged,
+        EventKind::ModeChanged,
+        EventKind::ConfigReloaded,
+    ];
+
+    /// Returns the name of the event kind, e.g. `"buffer_saved"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide_plugin_api::event::EventKind;
+    ///
+    /// assert_eq!(EventKind::BufferSaved.as_str(), "buffer_saved");
+    /// assert_eq!(EventKind::from_name("mode_changed"), Some(EventKind::ModeChanged));
+    /// assert_eq!(EventKind::from_name("cursor_moved"), None);
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::BufferOpened => "buffer_opened",
+            EventKind::BufferSaved => "buffer_saved",
+            EventKind::BufferChanged => "buffer_changed",
+            EventKind::ModeChanged => "mode_changed",
+            EventKind::ConfigReloaded => "config_reloaded",
+        }
+    }
+
+    /// Returns the event kind named `name`, see [`as_str`](Self::as_str)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == name)
+    }
+}
+
+/// Receives the editor events a plugin subscribed to
+///
+/// Handlers run on the editor thread like commands: buffer edits made through
+/// the context are a single undo step, UI requests are applied when the
+/// handler returns, and errors and panics are isolated by the host. See the
+/// [module documentation](self) for the delivery order.
+pub trait EventSubscriber {
+    /// Returns the kinds of events to deliver to [`on_event`](Self::on_event)
+    ///
+    /// Read once when the plugin is loaded.
+    fn subscriptions(&self) -> Vec<EventKind>;
+
+    /// Handles an event of one of the subscribed kinds
+    ///
+    /// `context` gives access to the active buffer, which need not be the
+    /// buffer a buffer event is about; compare the event's path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::Failed`] if the handler could not complete; the
+    /// message is shown to the user and later events are still delivered.
+    fn on_event(&mut self, event: &EditorEvent, context: &mut CommandContext<'_>) -> Result<(), PluginError>;
+}
@@ -65,13 +65,38 @@
 //!
 //! # Editor Modes
 //!
-//! TermIDE has three editor modes:
+//! TermIDE has three core editor modes:
 //! - **Insert**: Characters typed are inserted into the buffer
 //! - **Normal**: Navigation and command mode (vim-style)
 //! - **Prompt**: User is being prompted for input
 //!
+//! Plugins can add their own modes, e.g. a blame viewer or a REPL, with
+//! [`PluginInputExtension::register_mode`]. Plugin modes are namespaced like
+//! commands (`"git.blame"`) and shown in the status line by their label. Keys
+//! without a binding in a plugin mode are ignored, as in Normal mode.
+//!
+//! ```rust
+//! use termide_plugin_api::input::{PluginBindingBuilder, PluginInputExtension, PluginMode};
+//!
+//! fn setup(registry: &mut impl PluginInputExtension) -> Result<(), Box<dyn std::error::Error>> {
+//!     registry.register_mode(PluginMode::new("git", "blame").with_label("BLAME"))?;
+//!
+//!     // Only active in the plugin's mode
+//!     registry.register_keybinding(
+//!         PluginBindingBuilder::new("git")
+//!             .bind("q", "close_blame")
+//!             .in_mode("blame")
+//!             .build()?
+//!     )?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Commands enter a mode with
+//! [`CommandContext::set_mode`](crate::plugin::CommandContext::set_mode).
+//!
 //! Bindings can be:
-//! - **Global**: Active in Insert and Normal modes (not Prompt)
+//! - **Global**: Active in every mode except Prompt
 //! - **Mode-specific**: Active only in specified mode(s)
 //!
 //! # Command Namespacing
@@ -87,7 +112,8 @@ use thiserror::Error;
 
 /// Editor mode enumeration for plugin use
 ///
-/// Represents the editor modes that plugins can specify for keybindings.
+/// Represents the editor modes that plugins can specify for keybindings: the
+/// core modes, and modes registered by plugins.
 /// This is a simplified representation for the plugin API - the actual editor
 /// uses a more detailed internal representation.
 ///
@@ -101,8 +127,11 @@ use thiserror::Error;
 ///
 /// let mode = EditorMode::from_str("normal").unwrap();
 /// assert_eq!(mode, EditorMode::Normal);
+///
+/// let mode = EditorMode::from_str("git.blame").unwrap();
+/// assert_eq!(mode, EditorMode::Plugin("git.blame".to_string()));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EditorMode {
     /// Insert mode - characters are inserted at the cursor
     Insert,
@@ -110,6 +139,8 @@ pub enum EditorMode {
     Normal,
     /// Prompt mode - user is being prompted for input
     Prompt,
+    /// A mode registered by a plugin, by its namespaced name (e.g. `"git.blame"`)
+    Plugin(String),
 }
 
 impl EditorMode {
@@ -123,18 +154,21 @@ impl EditorMode {
     /// assert_eq!(EditorMode::Insert.as_str(), "insert");
     /// assert_eq!(EditorMode::Normal.as_str(), "normal");
     /// assert_eq!(EditorMode::Prompt.as_str(), "prompt");
+    /// assert_eq!(EditorMode::Plugin("git.blame".to_string()).as_str(), "git.blame");
     /// ```
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             EditorMode::Insert => "insert",
             EditorMode::Normal => "normal",
             EditorMode::Prompt => "prompt",
+            EditorMode::Plugin(name) => name,
         }
     }
 
     /// Parses a mode string into an `EditorMode`
     ///
-    /// Case-insensitive and trims whitespace.
+    /// Core mode names are case-insensitive; names of the form
+    /// `plugin.mode` are plugin modes. Trims whitespace.
     ///
     /// # Examples
     ///
@@ -145,15 +179,71 @@ impl EditorMode {
     /// assert_eq!(EditorMode::from_str("Normal").unwrap(), EditorMode::Normal);
     /// assert_eq!(EditorMode::from_str(" PROMPT ").unwrap(), EditorMode::Prompt);
     /// assert!(EditorMode::from_str("invalid").is_err());
+    /// assert!(EditorMode::from_str("git.").is_err());
     /// ```
     pub fn from_str(s: &str) -> Result<Self, BindingError> {
         match s.trim().to_lowercase().as_str() {
             "insert" => Ok(EditorMode::Insert),
             "normal" => Ok(EditorMode::Normal),
             "prompt" => Ok(EditorMode::Prompt),
-            _ => Err(BindingError::InvalidMode(s.to_string())),
+            _ => match s.trim().split_once('.') {
+                Some((plugin, name)) if is_valid_name(plugin) && is_valid_name(name) => {
+                    Ok(EditorMode::Plugin(s.trim().to_string()))
+                }
+                _ => Err(BindingError::InvalidMode(s.to_string())),
+            },
+        }
+    }
+}
+
+/// Returns `true` if `name` is a valid plugin or mode name: letters, digits,
+/// `_` and `-`
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// A mode defined by a plugin
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::input::{EditorMode, PluginMode};
+///
+/// let mode = PluginMode::new("repl", "eval");
+/// assert_eq!(mode.name, "repl.eval");
+/// assert_eq!(mode.label, "EVAL");
+/// assert_eq!(mode.mode(), EditorMode::Plugin("repl.eval".to_string()));
+///
+/// let mode = PluginMode::new("git", "blame").with_label("BLAME (git)");
+/// assert_eq!(mode.label, "BLAME (git)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginMode {
+    /// Namespaced mode name, e.g. `"git.blame"`
+    pub name: String,
+    /// Status line label, e.g. `"BLAME"`
+    pub label: String,
+}
+
+impl PluginMode {
+    /// Creates the mode `name` of `plugin`, labeled with the uppercased name
+    pub fn new(plugin: &str, name: &str) -> Self {
+        Self {
+            name: format!("{}.{}", plugin, name),
+            label: name.to_uppercase(),
         }
     }
+
+    /// Sets the status line label
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Returns the mode as an [`EditorMode`], for bindings and mode changes
+    pub fn mode(&self) -> EditorMode {
+        EditorMode::Plugin(self.name.clone())
+    }
 }
 
 /// Context specifying when a plugin keybinding is active
@@ -165,7 +255,7 @@ impl EditorMode {
 /// ```
 /// use termide_plugin_api::input::{BindingContext, EditorMode};
 ///
-/// // Global binding (Insert and Normal, not Prompt)
+/// // Global binding (every mode except Prompt)
 /// let ctx = BindingContext::Global;
 ///
 /// // Single mode
@@ -176,7 +266,7 @@ impl EditorMode {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BindingContext {
-    /// Active in all modes except Prompt (Insert and Normal)
+    /// Active in all modes except Prompt
     Global,
     /// Active only in the specified mode
     Mode(EditorMode),
@@ -231,9 +321,16 @@ pub enum BindingError {
     InvalidSequence(String, String),
 
     /// Invalid mode name
-    #[error("invalid mode '{0}': valid modes are 'insert', 'normal', 'prompt'")]
+    #[error("invalid mode '{0}': valid modes are 'insert', 'normal', 'prompt' and registered plugin modes")]
     InvalidMode(String),
 
+    /// The mode is not in the namespace of the plugin registering it
+    #[error("mode '{mode}' does not belong to plugin '{plugin}'")]
+    ForeignMode {
+        mode: String,
+        plugin: String,
+    },
+
     /// Binding conflicts with an existing plugin binding
     #[error("binding conflict: key sequence '{sequence}' is already bound to '{existing_command}' in plugin '{plugin}'")]
     Conflict {
@@ -242,6 +339,13 @@ pub enum BindingError {
         plugin: String,
     },
 
+    /// The binding runs a command of another plugin
+    #[error("command '{command}' does not belong to plugin '{plugin}'")]
+    ForeignCommand {
+        command: String,
+        plugin: String,
+    },
+
     /// Builder validation failed (missing required fields)
     #[error("binding builder validation failed: {0}")]
     BuilderValidation(String),
@@ -372,7 +476,9 @@ impl PluginBindingBuilder {
     ///
     /// # Arguments
     ///
-    /// * `mode` - Mode name: "insert", "normal", or "prompt" (case-insensitive)
+    /// * `mode` - Mode name: "insert", "normal", or "prompt" (case-insensitive),
+    ///   or the name of a plugin mode. Plugin mode names without a dot are
+    ///   namespaced with the plugin name, like commands.
     ///
     /// # Examples
     ///
@@ -390,7 +496,7 @@ impl PluginBindingBuilder {
         // Store as string for now, will parse in build()
         let mode_str = mode.as_ref().to_string();
         self.context = BindingContext::Mode(
-            EditorMode::from_str(&mode_str).unwrap_or(EditorMode::Normal)
+            self.parse_mode(&mode_str).unwrap_or(EditorMode::Normal)
         );
         self
     }
@@ -399,7 +505,8 @@ impl PluginBindingBuilder {
     ///
     /// # Arguments
     ///
-    /// * `modes` - Slice of mode names: "insert", "normal", "prompt" (case-insensitive)
+    /// * `modes` - Slice of mode names: "insert", "normal", "prompt" (case-insensitive),
+    ///   or plugin mode names as in [`in_mode`](Self::in_mode)
     ///
     /// # Examples
     ///
@@ -415,7 +522,7 @@ impl PluginBindingBuilder {
     pub fn in_modes(mut self, modes: &[impl AsRef<str>]) -> Self {
         let parsed_modes: Vec<EditorMode> = modes
             .iter()
-            .filter_map(|m| EditorMode::from_str(m.as_ref()).ok())
+            .filter_map(|m| self.parse_mode(m.as_ref()))
             .collect();
 
         self.context = if parsed_modes.is_empty() {
@@ -426,7 +533,14 @@ impl PluginBindingBuilder {
         self
     }
 
-    /// Sets the binding to be global (active in Insert and Normal modes, not Prompt)
+    /// Parses a mode name, namespacing plugin mode names without a dot
+    fn parse_mode(&self, mode: &str) -> Option<EditorMode> {
+        EditorMode::from_str(mode)
+            .or_else(|_| EditorMode::from_str(&format!("{}.{}", self.plugin_name, mode.trim())))
+            .ok()
+    }
+
+    /// Sets the binding to be global (active in every mode except Prompt)
     ///
     /// This is the default context if no mode methods are called.
     ///
@@ -571,4 +685,16 @@ pub trait PluginInputExtension {
     /// }
     /// ```
     fn register_keybinding(&mut self, binding: PluginBinding) -> Result<(), BindingError>;
+
+    /// Registers a plugin mode, which bindings can then target
+    ///
+    /// Register modes before the bindings that use them. Registering a mode
+    /// again updates its label.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BindingError::InvalidMode`] if the name is not of the form
+    /// `plugin.mode`, and [`BindingError::ForeignMode`] if it is in another
+    /// plugin's namespace.
+    fn register_mode(&mut self, mode: PluginMode) -> Result<(), BindingError>;
 }
@@ -39,6 +39,47 @@
 //! }
 //! ```
 //!
+//! ### Custom Modes (v0.1.0)
+//!
+//! Besides the core Insert, Normal and Prompt modes, plugins register their
+//! own modes with a status line label, bind keys in them and switch to them
+//! from commands. See the [`input`] module.
+//!
+//! ### Plugin Entry Point (v0.1.0)
+//!
+//! Plugins implement the [`Plugin`] trait. The editor's plugin host calls
+//! [`Plugin::register`] with its keybinding registry when the plugin is loaded
+//! and dispatches the plugin's commands to [`Plugin::execute`]. See the
+//! [`plugin`] module for a complete example.
+//!
+//! ### Buffer Manipulation (buffer API v1)
+//!
+//! Running commands access the active buffer through the [`BufferApi`] trait:
+//! reading lines and ranges, inserting and deleting text, moving the cursor and
+//! selection, and reading the file path and dirty state. See the [`buffer`]
+//! module.
+//!
+//! ### UI Extension Points (v0.1.0)
+//!
+//! Commands post status messages with a [`Severity`], add status line
+//! segments and open picker popups through their [`CommandContext`]. The
+//! picked item is delivered to [`Plugin::picker_result`]. See the [`ui`]
+//! module.
+//!
+//! ### Editor Events (v0.1.0)
+//!
+//! Plugins subscribe to buffer, mode and config events by implementing
+//! [`EventSubscriber`] and returning it from [`Plugin::event_subscriber`].
+//! Events are delivered in publication order, to plugins in load order, and
+//! handlers never run nested. See the [`event`] module.
+//!
+//! ### Manifests and Versioning (v0.1.0)
+//!
+//! Plugins describe themselves with a [`PluginManifest`]: name, version, the
+//! [`API_VERSION`] they were written against, commands, default bindings and
+//! required capabilities. The editor refuses plugins written against an
+//! incompatible API version. See the [`manifest`] module.
+//!
 //! ## Plugin Command Format
 //!
 //! Plugin commands use a namespaced format to avoid conflicts:
@@ -59,8 +100,6 @@
 //! ## Future Capabilities
 //!
 //! Future versions will add:
-//! - UI extension points (status line, command palette)
-//! - Buffer manipulation APIs
 //! - LSP integration helpers
 //! - Custom syntax highlighting
 //!
@@ -73,10 +112,20 @@
 //!
 //! See the `examples/` directory for complete plugin implementations.
 
+pub mod buffer;
+pub mod event;
 pub mod input;
+pub mod manifest;
+pub mod plugin;
+pub mod ui;
 
 // Re-export commonly used types at the crate root for convenience
-pub use input::{PluginInputExtension, PluginBinding, PluginBindingBuilder, BindingError};
+pub use input::{PluginInputExtension, PluginBinding, PluginBindingBuilder, PluginMode, BindingError};
+pub use buffer::{BufferApi, BufferError, TextPosition, TextRange, BUFFER_API_VERSION};
+pub use event::{EditorEvent, EventKind, EventSubscriber};
+pub use manifest::{ManifestError, PluginManifest, API_VERSION};
+pub use plugin::{CommandContext, Plugin, PluginError};
+pub use ui::{Picker, PickerSelection, Severity, UiRequest};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,261 @@
+//! Plugin manifests and API versioning
+//!
+//! A [`PluginManifest`] describes a plugin: its name and version, the version
+//! of this API it was written against, its commands, its default keybindings
+//! and the capabilities it needs. The editor checks the manifest before
+//! loading the plugin and refuses plugins written against an incompatible
+//! API version.
+//!
+//! # Compatibility
+//!
+//! API versions follow semantic versioning. A plugin written against
+//! `api_version` runs on an editor providing [`API_VERSION`] if the major
+//! versions are equal and the editor's version is not older. While the major
+//! version is 0, the minor versions must be equal as well, since minor
+//! releases may break plugins.
+//!
+//! # Example
+//!
+//! ```rust
+//! use termide_plugin_api::manifest::{BindingManifest, CommandManifest, PluginManifest};
+//!
+//! let mut manifest = PluginManifest::new("shout", "1.2.0");
+//! manifest.commands.push(CommandManifest::new("upper", "Uppercase the cursor line"));
+//! manifest.bindings.push(BindingManifest::new("Ctrl+Alt+u", "upper"));
+//! manifest.capabilities.push("buffer-edit".to_string());
+//!
+//! assert!(manifest.validate().is_ok());
+//! ```
+
+use std::fmt;
+
+use thiserror::Error;
+
+/// Version of this API, which plugins name in [`PluginManifest::api_version`]
+pub const API_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Error for manifests the editor cannot load
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::manifest::ManifestError;
+///
+/// let err = ManifestError::IncompatibleApi { plugin: "2.0".to_string(), host: "0.1.0".to_string() };
+/// assert_eq!(err.to_string(), "written for plugin API 2.0, but this editor provides 0.1.0");
+/// ```
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ManifestError {
+    /// A version is not `major.minor.patch` (or `major.minor` for API versions)
+    #[error("invalid version '{0}': expected 'major.minor.patch'")]
+    InvalidVersion(String),
+
+    /// The plugin was written against an API version the editor doesn't provide
+    #[error("written for plugin API {plugin}, but this editor provides {host}")]
+    IncompatibleApi { plugin: String, host: String },
+
+    /// A command name is empty or contains characters other than letters,
+    /// digits, `_` and `-`
+    #[error("invalid command name '{0}'")]
+    InvalidCommand(String),
+
+    /// A default binding runs a command the manifest doesn't declare
+    #[error("binding '{keys}' runs undeclared command '{command}'")]
+    UndeclaredCommand { keys: String, command: String },
+}
+
+/// A `major.minor.patch` version
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::manifest::Version;
+///
+/// let host = Version::parse("0.1.2").unwrap();
+/// assert!(Version::parse("0.1").unwrap().is_compatible_with(host));
+/// assert!(!Version::parse("0.2.0").unwrap().is_compatible_with(host));
+/// assert!(!Version::parse("0.1.3").unwrap().is_compatible_with(host));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parses `major.minor.patch`; the patch version may be left out
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::InvalidVersion`] for anything else.
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        let invalid = || ManifestError::InvalidVersion(s.to_string());
+        let mut parts = s.trim().split('.').map(|part| {
+            if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            part.parse::<u64>().map_err(|_| invalid())
+        });
+
+        let major = parts.next().ok_or_else(invalid)??;
+        let minor = parts.next().ok_or_else(invalid)??;
+        let patch = parts.next().transpose()?.unwrap_or(0);
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    /// Returns the version of this API, [`API_VERSION`]
+    pub fn current() -> Self {
+        Self::parse(API_VERSION).expect("the crate version is valid")
+    }
+
+    /// Returns `true` if a plugin written against this API version runs on
+    /// an editor providing `host`, see the [module documentation](self)
+    pub fn is_compatible_with(&self, host: Version) -> bool {
+        self.major == host.major && (self.major > 0 || self.minor == host.minor) && *self <= host
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A command declared in a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandManifest {
+    /// Command name without the plugin namespace, e.g. `"format"`
+    pub name: String,
+    /// One-line description shown to users
+    pub description: String,
+}
+
+impl CommandManifest {
+    /// Creates a command declaration
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// A default keybinding declared in a manifest
+///
+/// The editor registers it with plugin priority when the plugin is loaded,
+/// so user bindings override it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingManifest {
+    /// Key sequence, e.g. `"Ctrl+Alt+u"`
+    pub keys: String,
+    /// Name of one of the manifest's commands
+    pub command: String,
+    /// Mode name; bindings without a mode are global
+    pub mode: Option<String>,
+}
+
+impl BindingManifest {
+    /// Creates a global binding of `keys` to `command`
+    pub fn new(keys: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            keys: keys.into(),
+            command: command.into(),
+            mode: None,
+        }
+    }
+
+    /// Restricts the binding to the mode named `mode`
+    pub fn in_mode(mut self, mode: impl Into<String>) -> Self {
+        self.mode = Some(mode.into());
+        self
+    }
+}
+
+/// Description of a plugin, checked by the editor before loading it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifest {
+    /// Plugin name, which must match [`Plugin::name`](crate::plugin::Plugin::name)
+    pub name: String,
+    /// Version of the plugin itself
+    pub version: String,
+    /// Version of this API the plugin was written against
+    pub api_version: String,
+    /// Commands the plugin provides
+    pub commands: Vec<CommandManifest>,
+    /// Keybindings registered for the plugin by default
+    pub bindings: Vec<BindingManifest>,
+    /// Names of the capabilities the plugin needs, e.g. `"buffer-edit"`
+    pub capabilities: Vec<String>,
+}
+
+impl PluginManifest {
+    /// Creates a manifest with no commands, bindings or capabilities,
+    /// written against the current [`API_VERSION`]
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            api_version: API_VERSION.to_string(),
+            commands: Vec::new(),
+            bindings: Vec::new(),
+            capabilities: Vec::new(),
+        }
+    }
+
+    /// Checks the manifest against the API version of this crate
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a version is invalid, the API version is
+    /// incompatible, a command name is invalid, or a binding runs a command
+    /// the manifest doesn't declare.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide_plugin_api::manifest::{ManifestError, PluginManifest};
+    ///
+    /// let mut manifest = PluginManifest::new("old", "1.0.0");
+    /// manifest.api_version = "99.0".to_string();
+    /// assert!(matches!(manifest.validate(), Err(ManifestError::IncompatibleApi { .. })));
+    /// ```
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        Version::parse(&self.version)?;
+        let host = Version::current();
+        if !Version::parse(&self.api_version)?.is_compatible_with(host) {
+            return Err(ManifestError::IncompatibleApi {
+                plugin: self.api_version.clone(),
+                host: host.to_string(),
+            });
+        }
+
+        for command in &self.commands {
+            let name = &command.name;
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+                return Err(ManifestError::InvalidCommand(name.clone()));
+            }
+        }
+        for binding in &self.bindings {
+            if self.command(&binding.command).is_none() {
+                return Err(ManifestError::UndeclaredCommand {
+                    keys: binding.keys.clone(),
+                    command: binding.command.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the declared command named `name`
+    pub fn command(&self, name: &str) -> Option<&CommandManifest> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+}
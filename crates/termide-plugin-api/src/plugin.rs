@@ -0,0 +1,238 @@
+//! Plugin entry points
+//!
+//! A plugin implements the [`Plugin`] trait. The editor's plugin host calls
+//! [`Plugin::register`] once when the plugin is loaded, then
+//! [`Plugin::execute`] whenever one of the plugin's commands is triggered by a
+//! keybinding. Commands reach the editor through their [`CommandContext`]:
+//! the active buffer, and [UI requests](crate::ui) such as status messages
+//! and pickers. Plugins that react to editor events also implement
+//! [`EventSubscriber`](crate::event::EventSubscriber).
+//!
+//! # Example
+//!
+//! ```rust
+//! use termide_plugin_api::input::{BindingError, PluginBindingBuilder, PluginInputExtension};
+//! use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+//!
+//! /// Inserts how many times it has been run at the cursor
+//! struct Counter {
+//!     count: usize,
+//! }
+//!
+//! impl Plugin for Counter {
+//!     fn name(&self) -> &str {
+//!         "counter"
+//!     }
+//!
+//!     fn register(&mut self, registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+//!         registry.register_keybinding(
+//!             PluginBindingBuilder::new("counter").bind("Ctrl+K", "insert").build()?,
+//!         )
+//!     }
+//!
+//!     fn execute(&mut self, command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+//!         match command {
+//!             "insert" => {
+//!                 self.count += 1;
+//!                 let buffer = context.buffer();
+//!                 let end = buffer.insert(buffer.cursor(), &self.count.to_string())?;
+//!                 buffer.set_cursor(end)?;
+//!                 Ok(())
+//!             }
+//!             other => Err(PluginError::UnknownCommand(other.to_string())),
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::buffer::{BufferApi, BufferError};
+use crate::event::EventSubscriber;
+use crate::input::{BindingError, EditorMode, PluginInputExtension};
+use crate::manifest::PluginManifest;
+use crate::ui::{Picker, PickerSelection, Severity, UiRequest};
+use thiserror::Error;
+
+/// Error returned by a plugin
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::plugin::PluginError;
+///
+/// let err = PluginError::UnknownCommand("reset".to_string());
+/// assert_eq!(err.to_string(), "unknown command 'reset'");
+///
+/// let err = PluginError::Failed("no selection".to_string());
+/// assert_eq!(err.to_string(), "no selection");
+/// ```
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    /// The plugin has no command with this name
+    #[error("unknown command '{0}'")]
+    UnknownCommand(String),
+
+    /// The command ran but could not complete; the message is shown to the user
+    #[error("{0}")]
+    Failed(String),
+
+    /// Registering a keybinding failed
+    #[error(transparent)]
+    Binding(#[from] BindingError),
+
+    /// A buffer operation failed
+    #[error(transparent)]
+    Buffer(#[from] BufferError),
+}
+
+/// The editor as seen by a running plugin command
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::buffer::BufferApi;
+/// use termide_plugin_api::plugin::CommandContext;
+/// use termide_plugin_api::ui::{Severity, UiRequest};
+///
+/// fn run(buffer: &mut dyn BufferApi) -> Vec<UiRequest> {
+///     let mut context = CommandContext::new(buffer);
+///     context.show_message(Severity::Warning, "no matches");
+///     context.take_ui_requests()
+/// }
+/// ```
+pub struct CommandContext<'a> {
+    buffer: &'a mut dyn BufferApi,
+    ui_requests: Vec<UiRequest>,
+}
+
+impl<'a> CommandContext<'a> {
+    /// Creates a context giving access to `buffer`
+    ///
+    /// Called by the editor's plugin host, and by plugin tests with a mock buffer.
+    pub fn new(buffer: &'a mut dyn BufferApi) -> Self {
+        Self {
+            buffer,
+            ui_requests: Vec::new(),
+        }
+    }
+
+    /// Returns the active buffer
+    pub fn buffer(&mut self) -> &mut dyn BufferApi {
+        &mut *self.buffer
+    }
+
+    /// Shows `message` in the status bar, colored by `severity`
+    ///
+    /// The last message of a command wins. If the command returns an error,
+    /// the error is shown instead.
+    pub fn show_message(&mut self, severity: Severity, message: impl Into<String>) {
+        self.ui_requests.push(UiRequest::Message {
+            severity,
+            text: message.into(),
+        });
+    }
+
+    /// Shows `text` in the plugin's status line segment `key`, or removes the
+    /// segment if `text` is `None`
+    ///
+    /// Segments stay until the plugin changes them. They are shown after the
+    /// cursor position, ordered by plugin and key.
+    pub fn set_status_segment(&mut self, key: impl Into<String>, text: Option<String>) {
+        self.ui_requests.push(UiRequest::StatusSegment {
+            key: key.into(),
+            text,
+        });
+    }
+
+    /// Opens `picker` once the command returns
+    ///
+    /// The result is delivered to [`Plugin::picker_result`]. Only one picker
+    /// is open at a time; a later request replaces an earlier one.
+    pub fn open_picker(&mut self, picker: Picker) {
+        self.ui_requests.push(UiRequest::OpenPicker(picker));
+    }
+
+    /// Switches the editor to `mode` once the command returns
+    ///
+    /// `mode` is Insert, Normal or a registered plugin mode; prompts are
+    /// opened with [`open_picker`](Self::open_picker) instead.
+    pub fn set_mode(&mut self, mode: EditorMode) {
+        self.ui_requests.push(UiRequest::SetMode(mode));
+    }
+
+    /// Removes and returns the UI requests made so far, in order
+    ///
+    /// Called by the editor's plugin host after the command returns.
+    pub fn take_ui_requests(&mut self) -> Vec<UiRequest> {
+        std::mem::take(&mut self.ui_requests)
+    }
+}
+
+/// An editor plugin
+///
+/// Plugins run on the editor thread. The host isolates them: an error or a
+/// panic in a plugin is reported in the status bar instead of crashing the
+/// editor, and a plugin that panics is disabled for the rest of the session.
+pub trait Plugin {
+    /// Returns the plugin name, which is also the namespace of its commands
+    ///
+    /// Names must be unique among loaded plugins.
+    fn name(&self) -> &str;
+
+    /// Returns the plugin's manifest, if it has one
+    ///
+    /// The host validates the manifest before calling
+    /// [`register`](Self::register): a plugin written against an incompatible
+    /// API version, or whose manifest names another plugin, is not loaded.
+    /// The manifest's default bindings are registered for the plugin. See the
+    /// [`manifest`](crate::manifest) module.
+    fn manifest(&self) -> Option<PluginManifest> {
+        None
+    }
+
+    /// Registers the plugin's keybindings
+    ///
+    /// Called once when the plugin is loaded. Bindings may only run the
+    /// plugin's own commands (`<name>.<command>`). If this returns an error,
+    /// the bindings registered so far are removed and the plugin is not loaded.
+    fn register(&mut self, registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+        let _ = registry;
+        Ok(())
+    }
+
+    /// Runs one of the plugin's commands
+    ///
+    /// `command` is the command name without the plugin namespace, e.g.
+    /// `"format"` for a binding to `my-plugin.format`. All buffer edits made
+    /// through `context` are a single undo step.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::UnknownCommand`] for commands the plugin doesn't
+    /// have, or [`PluginError::Failed`] if the command could not complete.
+    fn execute(&mut self, command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError>;
+
+    /// Receives the result of a picker opened with [`CommandContext::open_picker`]
+    ///
+    /// `picker` is the [`Picker::id`]; `selection` is `None` if the user closed
+    /// the picker without picking an item. Buffer edits and UI requests work as
+    /// in [`execute`](Self::execute).
+    fn picker_result(
+        &mut self,
+        picker: &str,
+        selection: Option<PickerSelection>,
+        context: &mut CommandContext<'_>,
+    ) -> Result<(), PluginError> {
+        let _ = (picker, selection, context);
+        Ok(())
+    }
+
+    /// Returns the plugin's event handler, if it subscribes to editor events
+    ///
+    /// Plugins that handle events implement [`EventSubscriber`] and return
+    /// `Some(self)`; see the [`event`](crate::event) module. The host reads
+    /// the subscriptions when the plugin is loaded and calls this again for
+    /// every event it delivers.
+    fn event_subscriber(&mut self) -> Option<&mut dyn EventSubscriber> {
+        None
+    }
+}
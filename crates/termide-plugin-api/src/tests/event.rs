@@ -0,0 +1,32 @@
+//! Unit tests for the editor event API
+
+use crate::event::*;
+use crate::input::EditorMode;
+use std::path::PathBuf;
+
+#[test]
+fn test_event_kind_names_round_trip() {
+    for kind in EventKind::ALL {
+        assert_eq!(EventKind::from_name(kind.as_str()), Some(kind));
+    }
+    assert_eq!(EventKind::from_name("Buffer_Saved"), None);
+}
+
+#[test]
+fn test_event_kinds() {
+    let path = Some(PathBuf::from("a.txt"));
+    let events = [
+        EditorEvent::BufferOpened {
+            path: PathBuf::from("a.txt"),
+        },
+        EditorEvent::BufferSaved { path: path.clone() },
+        EditorEvent::BufferChanged { path },
+        EditorEvent::ModeChanged {
+            from: EditorMode::Insert,
+            to: EditorMode::Normal,
+        },
+        EditorEvent::ConfigReloaded,
+    ];
+    let kinds: Vec<EventKind> = events.iter().map(EditorEvent::kind).collect();
+    assert_eq!(kinds, EventKind::ALL.to_vec());
+}
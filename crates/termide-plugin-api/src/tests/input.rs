@@ -14,6 +14,44 @@ fn test_mode_parsing_case_insensitive() {
     assert_eq!(EditorMode::from_str("PROMPT").unwrap(), EditorMode::Prompt);
 }
 
+#[test]
+fn test_mode_parsing_plugin_modes() {
+    assert_eq!(
+        EditorMode::from_str("git.Blame").unwrap(),
+        EditorMode::Plugin("git.Blame".to_string())
+    );
+    assert_eq!(EditorMode::Plugin("git.blame".to_string()).as_str(), "git.blame");
+    assert!(EditorMode::from_str("blame").is_err());
+    assert!(EditorMode::from_str("git.").is_err());
+    assert!(EditorMode::from_str("git.bl ame").is_err());
+}
+
+#[test]
+fn test_plugin_mode_is_namespaced() {
+    let mode = PluginMode::new("git", "blame");
+    assert_eq!(mode.name, "git.blame");
+    assert_eq!(mode.label, "BLAME");
+    assert_eq!(mode.mode(), EditorMode::Plugin("git.blame".to_string()));
+    assert_eq!(mode.with_label("Blame").label, "Blame");
+}
+
+#[test]
+fn test_builder_namespaces_plugin_modes() {
+    let binding = PluginBindingBuilder::new("git")
+        .bind("q", "close")
+        .in_modes(&["blame", "normal", "repl.eval"])
+        .build()
+        .unwrap();
+    assert_eq!(
+        binding.context,
+        BindingContext::Modes(vec![
+            EditorMode::Plugin("git.blame".to_string()),
+            EditorMode::Normal,
+            EditorMode::Plugin("repl.eval".to_string()),
+        ])
+    );
+}
+
 #[test]
 fn test_mode_parsing_whitespace_trimming() {
     assert_eq!(
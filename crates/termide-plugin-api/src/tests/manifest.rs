@@ -0,0 +1,114 @@
+//! Unit tests for plugin manifests and API versioning
+
+use crate::manifest::*;
+
+fn version(s: &str) -> Version {
+    Version::parse(s).unwrap()
+}
+
+#[test]
+fn test_version_parse() {
+    assert_eq!(
+        version("1.2.3"),
+        Version {
+            major: 1,
+            minor: 2,
+            patch: 3
+        }
+    );
+    assert_eq!(version("0.4"), version("0.4.0"));
+    assert_eq!(version("1.2.3").to_string(), "1.2.3");
+
+    for invalid in ["", "1", "1.", "1.2.3.4", "1.x", "-1.0", "v1.0", "1.+2"] {
+        assert_eq!(
+            Version::parse(invalid),
+            Err(ManifestError::InvalidVersion(invalid.to_string())),
+            "{:?}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_current_version_matches_api_version() {
+    assert_eq!(Version::current(), version(API_VERSION));
+}
+
+#[test]
+fn test_version_compatibility() {
+    // Pre-1.0: minor versions must match
+    assert!(version("0.3.0").is_compatible_with(version("0.3.2")));
+    assert!(!version("0.3.0").is_compatible_with(version("0.4.0")));
+    assert!(!version("0.4.0").is_compatible_with(version("0.3.9")));
+
+    // From 1.0: newer minor versions of the editor still run older plugins
+    assert!(version("1.2.0").is_compatible_with(version("1.5.1")));
+    assert!(!version("1.5.0").is_compatible_with(version("1.2.0")));
+    assert!(!version("1.0.0").is_compatible_with(version("2.0.0")));
+
+    // Plugins written against a newer patch release may use its fixes
+    assert!(!version("1.2.3").is_compatible_with(version("1.2.2")));
+}
+
+#[test]
+fn test_validate_accepts_current_api() {
+    let mut manifest = PluginManifest::new("fmt", "1.0.0");
+    manifest.commands.push(CommandManifest::new("format-all", "Format"));
+    manifest
+        .bindings
+        .push(BindingManifest::new("Ctrl+Alt+f", "format-all").in_mode("normal"));
+    assert_eq!(manifest.validate(), Ok(()));
+    assert_eq!(manifest.command("format-all").unwrap().description, "Format");
+    assert_eq!(manifest.bindings[0].mode.as_deref(), Some("normal"));
+}
+
+#[test]
+fn test_validate_rejects_incompatible_api() {
+    let host = Version::current();
+    let mut manifest = PluginManifest::new("fmt", "1.0.0");
+    manifest.api_version = format!("{}.{}", host.major + 1, host.minor);
+
+    assert_eq!(
+        manifest.validate(),
+        Err(ManifestError::IncompatibleApi {
+            plugin: manifest.api_version.clone(),
+            host: host.to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_validate_rejects_invalid_versions() {
+    let mut manifest = PluginManifest::new("fmt", "latest");
+    assert_eq!(
+        manifest.validate(),
+        Err(ManifestError::InvalidVersion("latest".to_string()))
+    );
+
+    manifest.version = "1.0.0".to_string();
+    manifest.api_version = "any".to_string();
+    assert_eq!(
+        manifest.validate(),
+        Err(ManifestError::InvalidVersion("any".to_string()))
+    );
+}
+
+#[test]
+fn test_validate_rejects_bad_commands_and_bindings() {
+    let mut manifest = PluginManifest::new("fmt", "1.0.0");
+    manifest.commands.push(CommandManifest::new("fmt.all", ""));
+    assert_eq!(
+        manifest.validate(),
+        Err(ManifestError::InvalidCommand("fmt.all".to_string()))
+    );
+
+    manifest.commands[0].name = "all".to_string();
+    manifest.bindings.push(BindingManifest::new("Ctrl+Alt+f", "some"));
+    assert_eq!(
+        manifest.validate(),
+        Err(ManifestError::UndeclaredCommand {
+            keys: "Ctrl+Alt+f".to_string(),
+            command: "some".to_string(),
+        })
+    );
+}
@@ -1,6 +1,10 @@
 //! Unit tests for termide-plugin-api
 //!
 //! Tests are organized by module:
+//! - event.rs: Tests for editor event API
 //! - input.rs: Tests for input extension API
+//! - manifest.rs: Tests for plugin manifests and API versioning
 
+mod event;
 mod input;
+mod manifest;
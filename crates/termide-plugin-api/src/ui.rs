@@ -0,0 +1,101 @@
+//! UI extension points for plugins
+//!
+//! A running command asks for UI changes through its
+//! [`CommandContext`](crate::plugin::CommandContext). The editor applies them
+//! once the command returns, even if it returns an error:
+//!
+//! - **Messages**: a status message with a [`Severity`], which decides its color
+//! - **Status segments**: short texts shown in the status line until the
+//!   plugin clears them
+//! - **Pickers**: a popup list the user filters and picks from; the result is
+//!   delivered to [`Plugin::picker_result`](crate::plugin::Plugin::picker_result)
+//! - **Modes**: switching to another mode, such as one the plugin registered
+//!
+//! # Example
+//!
+//! ```rust
+//! use termide_plugin_api::plugin::{CommandContext, PluginError};
+//! use termide_plugin_api::ui::{Picker, Severity};
+//!
+//! fn choose_branch(context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+//!     let branches = vec!["main".to_string(), "develop".to_string()];
+//!     context.show_message(Severity::Info, "Pick a branch");
+//!     context.set_status_segment("branch", Some("main".to_string()));
+//!     context.open_picker(Picker::new("checkout", "Branch", branches));
+//!     Ok(())
+//! }
+//! ```
+
+use crate::input::EditorMode;
+
+/// How important a status message is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Severity {
+    /// Neutral information
+    #[default]
+    Info,
+    /// An operation completed
+    Success,
+    /// Something needs attention but nothing failed
+    Warning,
+    /// An operation failed
+    Error,
+}
+
+/// A list of items for the user to pick from
+///
+/// The editor shows the items in a popup above the status bar. Typing filters
+/// them; `Enter` picks the highlighted item and `Esc` closes the popup.
+///
+/// # Examples
+///
+/// ```
+/// use termide_plugin_api::ui::Picker;
+///
+/// let picker = Picker::new("open", "Recent file", vec!["src/main.rs".to_string()]);
+/// assert_eq!(picker.id, "open");
+/// assert_eq!(picker.items.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picker {
+    /// Identifies the picker in [`PickerSelection`] results
+    pub id: String,
+    /// Title shown in front of the filter input
+    pub title: String,
+    /// Items to pick from, in display order
+    pub items: Vec<String>,
+}
+
+impl Picker {
+    /// Creates a picker
+    pub fn new(id: impl Into<String>, title: impl Into<String>, items: Vec<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            items,
+        }
+    }
+}
+
+/// The item the user picked from a [`Picker`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerSelection {
+    /// Index of the item in [`Picker::items`]
+    pub index: usize,
+    /// Text of the item
+    pub item: String,
+}
+
+/// A UI change requested by a running command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UiRequest {
+    /// Show `text` in the status bar
+    Message { severity: Severity, text: String },
+    /// Show `text` in the status line segment `key` of the plugin, or remove
+    /// the segment if `text` is `None`
+    StatusSegment { key: String, text: Option<String> },
+    /// Open a picker popup
+    OpenPicker(Picker),
+    /// Switch the editor to a core mode or a registered plugin mode
+    SetMode(EditorMode),
+}
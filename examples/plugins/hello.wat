@@ -0,0 +1,94 @@
+;; Example termide plugin in WebAssembly text format
+;;
+;; Copy this file to the plugins directory (`~/.config/termide/plugins` on
+;; Linux) to load it as the plugin `hello`; compiled `.wasm` files work too.
+;;
+;; - `Ctrl+Alt+h` runs `hello.greet`, which inserts a greeting at the cursor
+;; - `Ctrl+Alt+d` runs `hello.duplicate_line`, which copies the cursor line
+;;
+;; It needs the default capabilities: buffer-read, buffer-edit and ui. Its
+;; manifest, `hello.toml`, lists them with the plugin's version and commands.
+(module
+  (import "termide" "bind" (func $bind (param i32 i32 i32 i32 i32 i32) (result i32)))
+  (import "termide" "command" (func $command (param i32 i32) (result i32)))
+  (import "termide" "fail" (func $fail (param i32 i32)))
+  (import "termide" "line" (func $line (param i32 i32 i32) (result i32)))
+  (import "termide" "cursor_line" (func $cursor_line (result i32)))
+  (import "termide" "cursor_column" (func $cursor_column (result i32)))
+  (import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))
+  (import "termide" "set_cursor" (func $set_cursor (param i32 i32) (result i32)))
+  (import "termide" "message" (func $message (param i32 i32 i32)))
+
+  (memory (export "memory") 1)
+
+  ;; Strings, as offset and length
+  (data (i32.const 0) "Ctrl+Alt+h")         ;; 0, 10
+  (data (i32.const 16) "greet")             ;; 16, 5
+  (data (i32.const 32) "Ctrl+Alt+d")        ;; 32, 10
+  (data (i32.const 48) "duplicate_line")    ;; 48, 14
+  (data (i32.const 64) "Hello from WASM!")  ;; 64, 16
+  (data (i32.const 96) "Greeted")           ;; 96, 7
+  (data (i32.const 112) "unknown command")  ;; 112, 15
+  (data (i32.const 128) "line too long")    ;; 128, 13
+
+  ;; The command name is read to 256..512, lines to 1024..5120
+
+  (func (export "termide_register")
+    (drop (call $bind (i32.const 0) (i32.const 10) (i32.const 16) (i32.const 5) (i32.const 0) (i32.const 0)))
+    (drop (call $bind (i32.const 32) (i32.const 10) (i32.const 48) (i32.const 14) (i32.const 0) (i32.const 0))))
+
+  (func (export "termide_execute") (result i32)
+    (local $len i32)
+    (local.set $len (call $command (i32.const 256) (i32.const 256)))
+    (if (call $is_command (local.get $len) (i32.const 16) (i32.const 5))
+      (then (return (call $greet))))
+    (if (call $is_command (local.get $len) (i32.const 48) (i32.const 14))
+      (then (return (call $duplicate_line))))
+    (call $fail (i32.const 112) (i32.const 15))
+    (i32.const 1))
+
+  ;; Returns 1 if the command name of length `len` equals the string at `name`
+  (func $is_command (param $len i32) (param $name i32) (param $name_len i32) (result i32)
+    (local $i i32)
+    (if (i32.ne (local.get $len) (local.get $name_len))
+      (then (return (i32.const 0))))
+    (block $done
+      (loop $next
+        (br_if $done (i32.eq (local.get $i) (local.get $len)))
+        (if (i32.ne
+              (i32.load8_u (i32.add (i32.const 256) (local.get $i)))
+              (i32.load8_u (i32.add (local.get $name) (local.get $i))))
+          (then (return (i32.const 0))))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $next)))
+    (i32.const 1))
+
+  ;; Inserts a greeting at the cursor and moves the cursor after it
+  (func $greet (result i32)
+    (local $row i32)
+    (local $column i32)
+    (local.set $row (call $cursor_line))
+    (local.set $column (call $cursor_column))
+    (if (call $insert (local.get $row) (local.get $column) (i32.const 64) (i32.const 16))
+      (then (return (i32.const 1))))
+    (drop (call $set_cursor (local.get $row) (i32.add (local.get $column) (i32.const 16))))
+    (call $message (i32.const 1) (i32.const 96) (i32.const 7))
+    (i32.const 0))
+
+  ;; Inserts a copy of the cursor line above it and keeps the cursor on the
+  ;; original line
+  (func $duplicate_line (result i32)
+    (local $row i32)
+    (local $len i32)
+    (local.set $row (call $cursor_line))
+    (local.set $len (call $line (local.get $row) (i32.const 1024) (i32.const 4095)))
+    (if (i32.gt_s (local.get $len) (i32.const 4095))
+      (then
+        (call $fail (i32.const 128) (i32.const 13))
+        (return (i32.const 1))))
+    (i32.store8 (i32.add (i32.const 1024) (local.get $len)) (i32.const 10))
+    (if (call $insert (local.get $row) (i32.const 0) (i32.const 1024) (i32.add (local.get $len) (i32.const 1)))
+      (then (return (i32.const 1))))
+    (drop (call $set_cursor (i32.add (local.get $row) (i32.const 1)) (call $cursor_column)))
+    (i32.const 0))
+)
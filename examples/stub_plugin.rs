@@ -0,0 +1,208 @@
+//! Minimal process plugin speaking the termide JSON-RPC protocol
+//!
+//! Used by the process plugin integration tests, and a starting point for
+//! plugins in other languages: it only needs JSON and standard I/O. See
+//! `termide::plugin::protocol` for the messages.
+//!
+//! Commands:
+//! - `upper`: uppercases the cursor line
+//! - `pid`: inserts the process id at the cursor
+//! - `last_event`: inserts the name of the last event received
+//! - `pick`: opens a picker; the picked item is shown as a message
+//! - `view`, `leave`: enter its `view` mode and go back to Normal mode; `q`
+//!   runs `leave` in the `view` mode
+//! - `crash`, `hang`, `garbage`, `chatter`, `deaf`: misbehave, to test
+//!   restarts; `chatter` sends messages forever without answering, and `deaf`
+//!   answers and then stops reading its input
+//!
+//! Run it with `--no-bindings` to register no keybindings, and with
+//! `--manifest` to describe itself with a manifest.
+
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+struct Stub {
+    lines: io::Lines<io::StdinLock<'static>>,
+    next_id: u64,
+    last_event: String,
+    deaf: bool,
+}
+
+impl Stub {
+    fn send(&self, message: Value) {
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{}", message).unwrap();
+        stdout.flush().unwrap();
+    }
+
+    fn read(&mut self) -> Option<Value> {
+        let line = self.lines.next()?.ok()?;
+        Some(serde_json::from_str(&line).expect("the editor sends valid JSON"))
+    }
+
+    /// Sends a request to the editor and returns its result or error
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = format!("stub-{}", self.next_id);
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        let response = self.read().expect("the editor answers requests");
+        match response.get("error") {
+            Some(error) => Err(error["message"].as_str().unwrap_or_default().to_string()),
+            None => Ok(response["result"].clone()),
+        }
+    }
+
+    fn notify(&self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn insert_at_cursor(&mut self, text: &str) -> Result<Value, String> {
+        let cursor = self.request("buffer.cursor", Value::Null)?;
+        self.request("buffer.insert", json!({ "position": cursor, "text": text }))
+    }
+
+    fn execute(&mut self, command: &str) -> Result<Value, (i64, String)> {
+        let failed = |message: String| (-32000, message);
+        match command {
+            "upper" => {
+                let cursor = self.request("buffer.cursor", Value::Null).map_err(failed)?;
+                let line = cursor["line"].clone();
+                let text = self
+                    .request("buffer.line", json!({ "line": line }))
+                    .map_err(failed)?;
+                let text = text.as_str().unwrap_or_default();
+                let range = json!({
+                    "start": { "line": line, "column": 0 },
+                    "end": { "line": line, "column": text.chars().count() },
+                });
+                self.request("buffer.delete", range).map_err(failed)?;
+                let start = json!({ "line": line, "column": 0 });
+                let upper = text.to_uppercase();
+                self.request("buffer.insert", json!({ "position": start, "text": upper }))
+                    .map_err(failed)?;
+                self.request("buffer.set_cursor", cursor).map_err(failed)?;
+                self.notify(
+                    "ui.message",
+                    json!({ "severity": "success", "text": "Uppercased" }),
+                );
+                Ok(Value::Null)
+            }
+            "pid" => {
+                self.insert_at_cursor(&std::process::id().to_string())
+                    .map_err(failed)?;
+                Ok(Value::Null)
+            }
+            "last_event" => {
+                let event = self.last_event.clone();
+                self.insert_at_cursor(&event).map_err(failed)?;
+                Ok(Value::Null)
+            }
+            "pick" => {
+                let picker = json!({ "id": "color", "title": "Color", "items": ["red", "green"] });
+                self.request("ui.open_picker", picker).map_err(failed)?;
+                Ok(Value::Null)
+            }
+            "view" | "leave" => {
+                let mode = if command == "view" { "view" } else { "normal" };
+                self.request("ui.set_mode", json!({ "mode": mode }))
+                    .map_err(failed)?;
+                Ok(Value::Null)
+            }
+            "crash" => std::process::exit(1),
+            "hang" => loop {
+                thread::sleep(Duration::from_secs(60));
+            },
+            "chatter" => loop {
+                self.notify("ui.message", json!({ "severity": "info", "text": "Busy" }));
+                thread::sleep(Duration::from_millis(50));
+            },
+            "deaf" => {
+                self.deaf = true;
+                Ok(Value::Null)
+            }
+            "garbage" => {
+                println!("this is not JSON");
+                Ok(Value::Null)
+            }
+            other => Err((-32001, format!("unknown command '{}'", other))),
+        }
+    }
+
+    fn handle(&mut self, message: Value) {
+        let params = &message["params"];
+        let result = match message["method"].as_str().unwrap_or_default() {
+            "initialize" => {
+                let bindings = if std::env::args().any(|arg| arg == "--no-bindings") {
+                    json!([])
+                } else {
+                    json!([
+                        { "keys": "Ctrl+Alt+u", "command": "upper" },
+                        { "keys": "Ctrl+Alt+p", "command": "pid", "mode": "insert" },
+                        { "keys": "q", "command": "leave", "mode": "view" },
+                    ])
+                };
+                let mut result = json!({
+                    "bindings": bindings,
+                    "modes": [{ "name": "view", "label": "VIEW" }],
+                    "events": ["buffer_saved"],
+                });
+                if std::env::args().any(|arg| arg == "--manifest") {
+                    result["manifest"] = json!({
+                        "name": "stub",
+                        "version": "0.3.0",
+                        "api_version": "0.1",
+                        "commands": [
+                            { "name": "upper", "description": "Uppercase the cursor line" },
+                            { "name": "pid", "description": "Insert the process id" },
+                        ],
+                        "capabilities": ["buffer-read", "buffer-edit"],
+                    });
+                }
+                Ok(result)
+            }
+            "execute" => self.execute(params["command"].as_str().unwrap_or_default()),
+            "picker_result" => {
+                let text = match params["selection"]["item"].as_str() {
+                    Some(item) => format!("Picked {}", item),
+                    None => "Nothing picked".to_string(),
+                };
+                self.notify("ui.message", json!({ "severity": "info", "text": text }));
+                Ok(Value::Null)
+            }
+            "event" => {
+                self.last_event = params["name"].as_str().unwrap_or_default().to_string();
+                return;
+            }
+            other => Err((-32601, format!("unknown method '{}'", other))),
+        };
+
+        let id = message["id"].clone();
+        match result {
+            Ok(result) => self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err((code, text)) => self.send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": text },
+            })),
+        }
+    }
+}
+
+fn main() {
+    let mut stub = Stub {
+        lines: io::stdin().lock().lines(),
+        next_id: 1,
+        last_event: String::new(),
+        deaf: false,
+    };
+    while let Some(message) = stub.read() {
+        stub.handle(message);
+        if stub.deaf {
+            loop {
+                thread::sleep(Duration::from_secs(60));
+            }
+        }
+    }
+}
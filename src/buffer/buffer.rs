@@ -3,6 +3,7 @@
 use ropey::Rope;
 use std::path::PathBuf;
 
+use super::history::UndoHistory;
 use super::Position;
 use super::Selection;
 
@@ -46,6 +47,12 @@ pub struct Buffer {
     dirty: bool,
     /// Current text selection (transient, not persisted to disk)
     selection: Option<Selection>,
+    /// Flag indicating edits to this buffer should be rejected
+    read_only: bool,
+    /// Snapshots for undo and redo
+    history: UndoHistory,
+    /// Number of changes made to the text, see [`revision`](Self::revision)
+    revision: u64,
 }
 
 impl Buffer {
@@ -66,6 +73,9 @@ impl Buffer {
             file_path: None,
             dirty: false,
             selection: None,
+            read_only: false,
+            history: UndoHistory::default(),
+            revision: 0,
         }
     }
 
@@ -86,6 +96,9 @@ impl Buffer {
             file_path: None,
             dirty: false,
             selection: None,
+            read_only: false,
+            history: UndoHistory::default(),
+            revision: 0,
         }
     }
 
@@ -107,14 +120,92 @@ impl Buffer {
         if let Some(char_idx) = self.position_to_char_idx(pos) {
             // Check if position is valid (within bounds or at end)
             if char_idx <= self.rope.len_chars() {
+                self.history.record(&self.rope);
                 self.rope.insert_char(char_idx, ch);
-                self.dirty = true;
+                self.mark_changed();
                 return true;
             }
         }
         false
     }
 
+    /// Inserts text at the specified position
+    ///
+    /// Returns the position just after the inserted text, or `None` if the
+    /// position was invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::{Buffer, Position};
+    ///
+    /// let mut buffer = Buffer::from_str("ad");
+    /// assert_eq!(buffer.insert_text("b\nc", Position::new(0, 1)), Some(Position::new(1, 1)));
+    /// assert_eq!(buffer.content(), "ab\ncd");
+    /// assert_eq!(buffer.insert_text("x", Position::new(5, 0)), None);
+    /// ```
+    pub fn insert_text(&mut self, text: &str, pos: Position) -> Option<Position> {
+        let char_idx = self.position_to_char_idx(pos)?;
+        if !text.is_empty() {
+            self.history.record(&self.rope);
+            self.rope.insert(char_idx, text);
+            self.mark_changed();
+        }
+        Some(self.char_idx_to_position(char_idx + text.chars().count()))
+    }
+
+    /// Returns the text between two positions (`end` excluded)
+    ///
+    /// Returns `None` if either position is invalid or `end` comes before `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::{Buffer, Position};
+    ///
+    /// let buffer = Buffer::from_str("Hello\nWorld");
+    /// assert_eq!(buffer.text_range(Position::new(0, 3), Position::new(1, 2)), Some("lo\nWo".to_string()));
+    /// assert_eq!(buffer.text_range(Position::new(1, 0), Position::new(0, 0)), None);
+    /// ```
+    pub fn text_range(&self, start: Position, end: Position) -> Option<String> {
+        let start_idx = self.position_to_char_idx(start)?;
+        let end_idx = self.position_to_char_idx(end)?;
+        if start_idx > end_idx {
+            return None;
+        }
+        Some(self.rope.slice(start_idx..end_idx).to_string())
+    }
+
+    /// Deletes the text between two positions (`end` excluded)
+    ///
+    /// Returns `false` if either position is invalid or `end` comes before `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::{Buffer, Position};
+    ///
+    /// let mut buffer = Buffer::from_str("Hello\nWorld");
+    /// assert!(buffer.delete_range(Position::new(0, 3), Position::new(1, 2)));
+    /// assert_eq!(buffer.content(), "Helrld");
+    /// ```
+    pub fn delete_range(&mut self, start: Position, end: Position) -> bool {
+        let (Some(start_idx), Some(end_idx)) =
+            (self.position_to_char_idx(start), self.position_to_char_idx(end))
+        else {
+            return false;
+        };
+        if start_idx > end_idx {
+            return false;
+        }
+        if start_idx < end_idx {
+            self.history.record(&self.rope);
+            self.rope.remove(start_idx..end_idx);
+            self.mark_changed();
+        }
+        true
+    }
+
     /// Deletes the character at the specified position
     ///
     /// Returns `true` if deletion was successful, `false` if position was invalid or no character exists.
@@ -132,8 +223,9 @@ impl Buffer {
         if let Some(char_idx) = self.position_to_char_idx(pos) {
             // Check if there's a character to delete
             if char_idx < self.rope.len_chars() {
+                self.history.record(&self.rope);
                 self.rope.remove(char_idx..char_idx + 1);
-                self.dirty = true;
+                self.mark_changed();
                 return true;
             }
         }
@@ -284,6 +376,28 @@ impl Buffer {
         self.rope.to_string()
     }
 
+    /// Replaces the whole buffer content
+    ///
+    /// The file path is kept, the selection is cleared and the buffer is marked dirty.
+    /// Used when save hooks (formatters, whitespace trimming) rewrite the content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::Buffer;
+    ///
+    /// let mut buffer = Buffer::from_str("old");
+    /// buffer.set_content("new\n");
+    /// assert_eq!(buffer.content(), "new\n");
+    /// assert!(buffer.is_dirty());
+    /// ```
+    pub fn set_content(&mut self, content: &str) {
+        self.history.record(&self.rope);
+        self.rope = Rope::from_str(content);
+        self.selection = None;
+        self.mark_changed();
+    }
+
     /// Returns the number of lines in the buffer
     ///
     /// Note: An empty buffer has 1 line, a buffer with one newline has 2 lines.
@@ -367,6 +481,44 @@ impl Buffer {
         self.dirty = true;
     }
 
+    /// Returns a counter that increases with every change to the text
+    ///
+    /// Unlike the dirty flag, it is not reset by saving, so comparing two
+    /// revisions tells whether the text changed in between.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::{Buffer, Position};
+    ///
+    /// let mut buffer = Buffer::new();
+    /// let before = buffer.revision();
+    /// buffer.insert_char('x', Position::origin());
+    /// buffer.clear_dirty();
+    /// assert_ne!(buffer.revision(), before);
+    /// ```
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Marks the text as changed
+    fn mark_changed(&mut self) {
+        self.dirty = true;
+        self.revision += 1;
+    }
+
+    /// Returns `true` if the buffer is marked read-only
+    ///
+    /// The flag is advisory: the editor checks it before running edit commands.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Marks the buffer as read-only or editable
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
     /// Gets the file path associated with this buffer
     pub fn file_path(&self) -> Option<&PathBuf> {
         self.file_path.as_ref()
@@ -441,6 +593,12 @@ impl Buffer {
         Some(line_start + pos.column)
     }
 
+    /// Converts a character index in the rope to a Position
+    fn char_idx_to_position(&self, char_idx: usize) -> Position {
+        let line = self.rope.char_to_line(char_idx);
+        Position::new(line, char_idx - self.rope.line_to_char(line))
+    }
+
     /// Returns the total number of characters in the buffer
     pub fn len_chars(&self) -> usize {
         self.rope.len_chars()
@@ -587,9 +745,6 @@ impl Buffer {
     /// Returns `true` if text was deleted, `false` if there was no selection
     /// or the operation failed. Sets the dirty flag if text was deleted.
     ///
-    /// Note: Currently does not integrate with undo/redo system as it hasn't
-    /// been implemented yet. Undo integration will be added in a future task.
-    ///
     /// # Examples
     ///
     /// ```
@@ -640,8 +795,9 @@ impl Buffer {
         }
 
         // Delete the selected range
+        self.history.record(&self.rope);
         self.rope.remove(start_idx..end_idx);
-        self.dirty = true;
+        self.mark_changed();
 
         // Selection is already cleared (we used take())
         true
@@ -667,6 +823,84 @@ impl Buffer {
         let last_column = self.line_len(last_line).unwrap_or(0);
         Position::new(last_line, last_column)
     }
+
+    /// Reverts the most recent undo step
+    ///
+    /// Returns `false` if there is nothing to undo. Clears the selection and
+    /// marks the buffer dirty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::{Buffer, Position};
+    ///
+    /// let mut buffer = Buffer::from_str("ab");
+    /// buffer.insert_char('c', Position::new(0, 2));
+    /// assert!(buffer.undo());
+    /// assert_eq!(buffer.content(), "ab");
+    /// assert!(buffer.redo());
+    /// assert_eq!(buffer.content(), "abc");
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        let undone = self.history.undo(&mut self.rope);
+        if undone {
+            self.selection = None;
+            self.mark_changed();
+        }
+        undone
+    }
+
+    /// Reapplies the most recently undone step
+    ///
+    /// Returns `false` if there is nothing to redo. Any new edit discards the
+    /// redo steps.
+    pub fn redo(&mut self) -> bool {
+        let redone = self.history.redo(&mut self.rope);
+        if redone {
+            self.selection = None;
+            self.mark_changed();
+        }
+        redone
+    }
+
+    /// Returns `true` if there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Returns `true` if there is an undone edit to redo
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Starts grouping edits into a single undo step
+    ///
+    /// Every edit until the matching [`end_undo_group`](Self::end_undo_group)
+    /// is undone at once. Groups may nest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::{Buffer, Position};
+    ///
+    /// let mut buffer = Buffer::new();
+    /// buffer.begin_undo_group();
+    /// buffer.insert_char('h', Position::new(0, 0));
+    /// buffer.insert_char('i', Position::new(0, 1));
+    /// buffer.end_undo_group();
+    ///
+    /// assert!(buffer.undo());
+    /// assert_eq!(buffer.content(), "");
+    /// assert!(!buffer.can_undo());
+    /// ```
+    pub fn begin_undo_group(&mut self) {
+        self.history.begin_group();
+    }
+
+    /// Ends a group started with [`begin_undo_group`](Self::begin_undo_group)
+    pub fn end_undo_group(&mut self) {
+        self.history.end_group();
+    }
 }
 
 impl Default for Buffer {
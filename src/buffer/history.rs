@@ -0,0 +1,90 @@
+//! Undo history for text buffers
+
+use ropey::Rope;
+
+/// Maximum number of undo steps kept per buffer
+const MAX_UNDO_STEPS: usize = 1000;
+
+/// Snapshot-based undo and redo stacks
+///
+/// Each undo step is the rope as it was before an edit. Cloning a `Rope` shares
+/// its unchanged nodes, so a snapshot costs O(log n) rather than a full copy.
+///
+/// Edits made between [`begin_group`](Self::begin_group) and
+/// [`end_group`](Self::end_group) form a single step. Groups nest; the step
+/// ends with the outermost group.
+#[derive(Debug, Clone, Default)]
+pub(super) struct UndoHistory {
+    /// Snapshots to restore on undo, most recent last
+    undo: Vec<Rope>,
+    /// Snapshots to restore on redo, most recent last
+    redo: Vec<Rope>,
+    /// Number of open groups
+    group_depth: usize,
+    /// Whether the open group already recorded its snapshot
+    group_recorded: bool,
+}
+
+impl UndoHistory {
+    /// Records `before`, the text prior to an edit, unless the open group already has
+    pub(super) fn record(&mut self, before: &Rope) {
+        if self.group_depth > 0 {
+            if self.group_recorded {
+                return;
+            }
+            self.group_recorded = true;
+        }
+        if self.undo.len() == MAX_UNDO_STEPS {
+            self.undo.remove(0);
+        }
+        self.undo.push(before.clone());
+        self.redo.clear();
+    }
+
+    /// Starts a group of edits that undo together
+    pub(super) fn begin_group(&mut self) {
+        if self.group_depth == 0 {
+            self.group_recorded = false;
+        }
+        self.group_depth += 1;
+    }
+
+    /// Ends a group started with [`begin_group`](Self::begin_group)
+    pub(super) fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+    }
+
+    /// Swaps `current` with the previous snapshot, returning `false` if there is none
+    pub(super) fn undo(&mut self, current: &mut Rope) -> bool {
+        match self.undo.pop() {
+            Some(previous) => {
+                self.redo.push(std::mem::replace(current, previous));
+                self.group_recorded = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swaps `current` with the next snapshot, returning `false` if there is none
+    pub(super) fn redo(&mut self, current: &mut Rope) -> bool {
+        match self.redo.pop() {
+            Some(next) => {
+                self.undo.push(std::mem::replace(current, next));
+                self.group_recorded = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if there is an edit to undo
+    pub(super) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is an undone edit to redo
+    pub(super) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
@@ -21,6 +21,7 @@
 //! ```
 
 mod buffer;
+mod history;
 mod position;
 mod selection;
 
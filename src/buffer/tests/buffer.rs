@@ -869,3 +869,133 @@ fn test_selection_not_in_clone() {
     assert!(cloned.has_selection());
     assert_eq!(cloned.selected_text(), Some("Hel".to_string()));
 }
+
+// ============================================================================
+// Undo/Redo Tests
+// ============================================================================
+
+#[test]
+fn test_undo_redo_single_edits() {
+    let mut buffer = Buffer::from_str("ab");
+    assert!(!buffer.can_undo());
+    assert!(!buffer.undo());
+
+    buffer.insert_char('c', Position::new(0, 2));
+    buffer.delete_char_at(Position::new(0, 0));
+    assert_eq!(buffer.content(), "bc");
+
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "abc");
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "ab");
+    assert!(!buffer.can_undo());
+    assert!(buffer.can_redo());
+
+    assert!(buffer.redo());
+    assert_eq!(buffer.content(), "abc");
+    assert!(buffer.is_dirty());
+}
+
+#[test]
+fn test_new_edit_discards_redo() {
+    let mut buffer = Buffer::new();
+    buffer.insert_char('a', Position::origin());
+    buffer.undo();
+    buffer.insert_char('b', Position::origin());
+
+    assert!(!buffer.can_redo());
+    assert!(!buffer.redo());
+    assert_eq!(buffer.content(), "b");
+}
+
+#[test]
+fn test_undo_group_is_one_step() {
+    let mut buffer = Buffer::from_str("x");
+    buffer.begin_undo_group();
+    buffer.insert_char('a', Position::new(0, 1));
+    // Nested groups end with the outermost one
+    buffer.begin_undo_group();
+    buffer.insert_char('b', Position::new(0, 2));
+    buffer.end_undo_group();
+    buffer.set_content("replaced");
+    buffer.end_undo_group();
+    buffer.insert_char('!', Position::new(0, 8));
+
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "replaced");
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "x");
+    assert!(!buffer.can_undo());
+
+    assert!(buffer.redo());
+    assert_eq!(buffer.content(), "replaced");
+}
+
+#[test]
+fn test_empty_undo_group_records_nothing() {
+    let mut buffer = Buffer::from_str("x");
+    buffer.begin_undo_group();
+    buffer.end_undo_group();
+    assert!(!buffer.can_undo());
+}
+
+#[test]
+fn test_revision_counts_changes() {
+    let mut buffer = Buffer::from_str("ab");
+    let start = buffer.revision();
+
+    buffer.insert_char('c', Position::new(0, 2));
+    buffer.clear_dirty();
+    assert_eq!(buffer.revision(), start + 1);
+
+    // Rejected edits don't change the text
+    buffer.insert_char('d', Position::new(5, 0));
+    assert_eq!(buffer.revision(), start + 1);
+
+    assert!(buffer.undo());
+    assert_eq!(buffer.revision(), start + 2);
+}
+
+#[test]
+fn test_insert_text_multiline() {
+    let mut buffer = Buffer::from_str("one\nfour");
+    let end = buffer.insert_text("two\nthree\n", Position::new(1, 0));
+    assert_eq!(end, Some(Position::new(3, 0)));
+    assert_eq!(buffer.content(), "one\ntwo\nthree\nfour");
+    assert!(buffer.is_dirty());
+
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "one\nfour");
+}
+
+#[test]
+fn test_insert_empty_text_records_nothing() {
+    let mut buffer = Buffer::from_str("abc");
+    assert_eq!(buffer.insert_text("", Position::new(0, 3)), Some(Position::new(0, 3)));
+    assert!(!buffer.is_dirty());
+    assert!(!buffer.can_undo());
+}
+
+#[test]
+fn test_text_range() {
+    let buffer = Buffer::from_str("Hello\nWorld");
+    assert_eq!(buffer.text_range(Position::new(0, 0), Position::new(0, 5)), Some("Hello".to_string()));
+    assert_eq!(buffer.text_range(Position::new(0, 5), Position::new(1, 0)), Some("\n".to_string()));
+    assert_eq!(buffer.text_range(Position::new(1, 1), Position::new(1, 1)), Some(String::new()));
+    assert_eq!(buffer.text_range(Position::new(0, 0), Position::new(2, 0)), None);
+}
+
+#[test]
+fn test_delete_range() {
+    let mut buffer = Buffer::from_str("Hello\nWorld");
+    assert!(buffer.delete_range(Position::new(0, 5), Position::new(1, 0)));
+    assert_eq!(buffer.content(), "HelloWorld");
+
+    // Invalid and reversed ranges leave the buffer unchanged
+    assert!(!buffer.delete_range(Position::new(0, 0), Position::new(3, 0)));
+    assert!(!buffer.delete_range(Position::new(0, 4), Position::new(0, 2)));
+    assert_eq!(buffer.content(), "HelloWorld");
+
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "Hello\nWorld");
+}
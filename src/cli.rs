@@ -0,0 +1,340 @@
+//! Command-line argument parsing for the termide binary
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use termide::buffer::Position;
+use termide::ui::Theme;
+
+/// Long options, used for "did you mean" suggestions
+const OPTIONS: &[&str] = &[
+    "--config",
+    "--help",
+    "--list-plugins",
+    "--readonly",
+    "--show-config",
+    "--stdout",
+    "--theme",
+    "--version",
+];
+
+/// What the binary should do after parsing the command line
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliAction {
+    /// Start the editor
+    Run(CliArgs),
+    /// Print the usage text and exit
+    Help,
+    /// Print the version and exit
+    Version,
+}
+
+/// Parsed command-line arguments
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    /// Files to open, in order; empty for a new empty buffer
+    pub files: Vec<FileArg>,
+    /// Read the first buffer from standard input (`-`)
+    pub stdin: bool,
+    /// Write the saved unnamed buffer to standard output on exit (`--stdout`)
+    pub stdout: bool,
+    /// Open the buffers read-only (`--readonly`)
+    pub readonly: bool,
+    /// Config file overriding the default location (`--config <path>`)
+    pub config: Option<PathBuf>,
+    /// Theme name (`--theme <name>`), already validated
+    pub theme: Option<String>,
+    /// Print the resolved settings and the layer that set each one, then exit
+    /// (`--show-config`)
+    pub show_config: bool,
+    /// Print the plugins, their versions and commands, then exit
+    /// (`--list-plugins`)
+    pub list_plugins: bool,
+}
+
+/// A file to open and where to place the cursor in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileArg {
+    /// Path of the file
+    pub path: PathBuf,
+    /// 1-based line to jump to (`usize::MAX` for the last line)
+    pub line: Option<usize>,
+    /// 1-based column to jump to
+    pub column: Option<usize>,
+}
+
+impl FileArg {
+    /// Creates a file argument without a jump target
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Returns the 0-based cursor position for this file
+    ///
+    /// The position may be past the end of the file; callers clamp it once the
+    /// file is loaded.
+    pub fn cursor(&self) -> Position {
+        Position::new(
+            self.line.map_or(0, |line| line.saturating_sub(1)),
+            self.column.map_or(0, |column| column.saturating_sub(1)),
+        )
+    }
+}
+
+/// Parse command-line arguments
+///
+/// Supported syntax:
+/// - `file...` - open one or more files (one buffer each)
+/// - `file:line[:col]` - open a file at a position, unless a file with that
+///   literal name exists
+/// - `+line file` - open the following file at `line` (`+` alone means the last line)
+/// - `-` - read the first buffer from standard input
+/// - `--` - treat all remaining arguments as file names
+/// - `-R`, `--readonly`, `--stdout`, `--config <path>`, `--theme <name>`,
+///   `--show-config`, `--list-plugins`, `-h`, `--help`, `-V`, `--version`
+///
+/// # Errors
+///
+/// Returns an error naming the offending argument, with a suggestion or the
+/// usage text, for unknown options, missing or invalid option values, and
+/// invalid line numbers.
+pub fn parse_args(args: &[String]) -> Result<CliAction> {
+    let program = args.first().map(String::as_str).unwrap_or("termide");
+    let mut cli_args = CliArgs::default();
+    let mut pending_line: Option<usize> = None;
+    let mut only_files = false;
+    let mut iter = args.iter().skip(1);
+
+    while let Some(arg) = iter.next() {
+        if only_files {
+            cli_args
+                .files
+                .push(file_arg(arg, pending_line.take(), false));
+            continue;
+        }
+
+        // Split `--option=value` into its parts
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if arg.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+
+        match name {
+            "--help" | "--version" | "--readonly" | "--stdout" | "--show-config"
+            | "--list-plugins"
+                if inline_value.is_some() =>
+            {
+                bail!(
+                    "Option '{}' does not take a value\n\n{}",
+                    name,
+                    usage(program)
+                );
+            }
+            "-h" | "--help" => return Ok(CliAction::Help),
+            "-V" | "--version" => return Ok(CliAction::Version),
+            "-R" | "--readonly" => cli_args.readonly = true,
+            "--stdout" => cli_args.stdout = true,
+            "--show-config" => cli_args.show_config = true,
+            "--list-plugins" => cli_args.list_plugins = true,
+            "--config" => {
+                let value = option_value(name, inline_value, &mut iter, program)?;
+                cli_args.config = Some(PathBuf::from(value));
+            }
+            "--theme" => {
+                let value = option_value(name, inline_value, &mut iter, program)?;
+                if Theme::by_name(&value).is_none() {
+                    bail!(
+                        "Invalid theme '{}'. Available themes: {}",
+                        value,
+                        Theme::NAMES.join(", ")
+                    );
+                }
+                cli_args.theme = Some(value.to_lowercase());
+            }
+            "--" => only_files = true,
+            "-" => {
+                if cli_args.stdin {
+                    bail!("Standard input ('-') can only be read once");
+                }
+                cli_args.stdin = true;
+            }
+            _ if arg.starts_with('+') => {
+                pending_line = Some(parse_jump_line(arg)?);
+            }
+            _ if arg.starts_with('-') => {
+                let mut message = format!("Unknown option '{}'", name);
+                if let Some(suggestion) = suggest_option(name) {
+                    message.push_str(&format!(". Did you mean '{}'?", suggestion));
+                }
+                bail!("{}\n\n{}", message, usage(program));
+            }
+            _ => cli_args
+                .files
+                .push(file_arg(arg, pending_line.take(), true)),
+        }
+    }
+
+    if pending_line.is_some() {
+        bail!(
+            "'+line' must be followed by a file name\n\n{}",
+            usage(program)
+        );
+    }
+
+    Ok(CliAction::Run(cli_args))
+}
+
+/// Returns the value of an option given as `--name=value` or `--name value`
+fn option_value<'a>(
+    name: &str,
+    inline_value: Option<String>,
+    iter: &mut impl Iterator<Item = &'a String>,
+    program: &str,
+) -> Result<String> {
+    let value = match inline_value {
+        Some(value) => value,
+        None => match iter.next() {
+            Some(value) if !value.starts_with("--") => value.clone(),
+            _ => bail!("Option '{}' requires a value\n\n{}", name, usage(program)),
+        },
+    };
+
+    if value.is_empty() {
+        bail!("Option '{}' requires a value\n\n{}", name, usage(program));
+    }
+    Ok(value)
+}
+
+/// Parses `+N` (jump to line N) or `+` (jump to the last line)
+fn parse_jump_line(arg: &str) -> Result<usize> {
+    let number = &arg[1..];
+    if number.is_empty() {
+        return Ok(usize::MAX);
+    }
+
+    match number.parse::<usize>() {
+        Ok(line) if line > 0 => Ok(line),
+        _ => bail!(
+            "Invalid line number '{}': expected a positive number after '+'",
+            arg
+        ),
+    }
+}
+
+/// Builds a file argument, splitting `file:line[:col]` when allowed
+///
+/// A suffix is only treated as a position if it is numeric and no file with
+/// the literal name exists, so names containing colons still open normally.
+fn file_arg(arg: &str, line: Option<usize>, allow_suffix: bool) -> FileArg {
+    if allow_suffix && !Path::new(arg).exists() {
+        if let Some(parsed) = split_position_suffix(arg) {
+            return parsed;
+        }
+    }
+
+    FileArg {
+        line,
+        ..FileArg::new(arg)
+    }
+}
+
+/// Splits `file:line` or `file:line:col` into a [`FileArg`]
+fn split_position_suffix(arg: &str) -> Option<FileArg> {
+    let parse = |s: &str| s.parse::<usize>().ok().filter(|n| *n > 0);
+
+    let (rest, last) = arg.rsplit_once(':')?;
+    let last = parse(last)?;
+
+    if let Some((path, line)) = rest.rsplit_once(':') {
+        if let Some(line) = parse(line) {
+            if !path.is_empty() {
+                return Some(FileArg {
+                    path: PathBuf::from(path),
+                    line: Some(line),
+                    column: Some(last),
+                });
+            }
+        }
+    }
+
+    if rest.is_empty() {
+        return None;
+    }
+    Some(FileArg {
+        path: PathBuf::from(rest),
+        line: Some(last),
+        column: None,
+    })
+}
+
+/// Returns the known option closest to `arg`, if it is a likely typo
+fn suggest_option(arg: &str) -> Option<&'static str> {
+    let arg = if arg.starts_with("--") {
+        arg.to_string()
+    } else {
+        // `-readonly` is most likely a mistyped `--readonly`
+        format!("-{}", arg)
+    };
+
+    OPTIONS
+        .iter()
+        .map(|option| (edit_distance(&arg, option), *option))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, option)| option)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Version string printed by `--version`
+pub fn version() -> String {
+    format!("termide {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Usage text printed by `--help` and for invalid arguments
+pub fn usage(program: &str) -> String {
+    format!(
+        "Usage: {} [options] [+line] [file[:line[:col]]]... [-]
+
+Arguments:
+  file                 File to open or create (several files open several buffers)
+  file:line[:col]      Open the file with the cursor at line (and column)
+  +line                Open the next file at line ('+' alone: last line)
+  -                    Read the text to edit from standard input
+                       (with stdout redirected, saving writes it to stdout on exit)
+
+Options:
+  -R, --readonly       Open files read-only
+      --stdout         Write the saved unnamed buffer to standard output on exit
+      --config <path>  Use this config file instead of the default location
+      --theme <name>   Color theme ({})
+      --show-config    Print the settings for the first file and where each
+                       one was set (default, user, project or language config)
+      --list-plugins   Print the plugins with their versions and commands, and
+                       the plugins that could not be loaded
+  -h, --help           Print this help and exit
+  -V, --version        Print the version and exit
+      --               Treat all following arguments as file names",
+        program,
+        Theme::NAMES.join(", ")
+    )
+}
@@ -1,7 +1,9 @@
 //! Editor mode enumeration
 
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Error returned when parsing an invalid mode string
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,7 +16,7 @@ impl fmt::Display for ParseModeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "unknown mode '{}'. Valid modes are: 'insert', 'normal', 'prompt'",
+            "unknown mode '{}'. Valid modes are: 'insert', 'normal', 'prompt' and registered plugin modes",
             self.invalid
         )
     }
@@ -24,11 +26,14 @@ impl std::error::Error for ParseModeError {}
 
 /// Represents the current editing mode of the editor
 ///
-/// The editor supports three modes:
+/// The editor has three core modes:
 /// - `Insert`: Characters typed are inserted into the buffer
 /// - `Normal`: Navigation and commands (vi-like behavior)
 /// - `Prompt`: Prompting the user for input (e.g., filename)
 ///
+/// Plugins add their own modes, which the keybinding registry keeps track of
+/// (see [`KeyBindingRegistry::plugin_mode`](crate::input::registry::KeyBindingRegistry::plugin_mode)).
+///
 /// # Examples
 ///
 /// ```
@@ -43,7 +48,7 @@ impl std::error::Error for ParseModeError {}
 /// let mode = EditorMode::Prompt;
 /// assert_eq!(mode.to_string(), "PROMPT");
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EditorMode {
     /// Insert mode - characters are inserted at the cursor
     Insert,
@@ -51,10 +56,35 @@ pub enum EditorMode {
     Normal,
     /// Prompt mode - user is being prompted for input
     Prompt,
+    /// A mode registered by a plugin - keys without a binding are ignored
+    Plugin(PluginMode),
 }
 
 impl EditorMode {
-    /// Returns the string representation of the mode for display
+    /// Every core mode, in declaration order
+    pub const ALL: [EditorMode; 3] = [EditorMode::Insert, EditorMode::Normal, EditorMode::Prompt];
+
+    /// Returns the name of the mode, as used in config files
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::{EditorMode, PluginMode};
+    ///
+    /// assert_eq!(EditorMode::Normal.name(), "normal");
+    /// let blame = PluginMode::new("git.blame", "BLAME");
+    /// assert_eq!(EditorMode::Plugin(blame).name(), "git.blame");
+    /// ```
+    pub fn name(&self) -> &str {
+        match self {
+            EditorMode::Insert => "insert",
+            EditorMode::Normal => "normal",
+            EditorMode::Prompt => "prompt",
+            EditorMode::Plugin(mode) => mode.name(),
+        }
+    }
+
+    /// Returns the status line label of the mode
     ///
     /// # Examples
     ///
@@ -65,11 +95,93 @@ impl EditorMode {
     /// assert_eq!(EditorMode::Normal.to_string(), "NORMAL");
     /// assert_eq!(EditorMode::Prompt.to_string(), "PROMPT");
     /// ```
-    pub fn to_string(&self) -> &'static str {
+    pub fn to_string(&self) -> &str {
         match self {
             EditorMode::Insert => "INSERT",
             EditorMode::Normal => "NORMAL",
             EditorMode::Prompt => "PROMPT",
+            EditorMode::Plugin(mode) => mode.label(),
+        }
+    }
+}
+
+/// A mode registered by a plugin
+///
+/// Modes are named `plugin.mode`, like plugin commands, and compare equal by
+/// name. The keybinding registry keeps the registered modes; look them up
+/// there rather than creating handles for unknown names.
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::{EditorMode, PluginMode};
+///
+/// let eval = PluginMode::new("repl.eval", "REPL");
+/// assert_eq!(eval.plugin(), "repl");
+/// assert_eq!(EditorMode::Plugin(eval).to_string(), "REPL");
+/// ```
+#[derive(Clone)]
+pub struct PluginMode {
+    name: Arc<str>,
+    label: Arc<str>,
+}
+
+impl PluginMode {
+    /// Creates the mode `name` with the status line label `label`
+    pub fn new(name: &str, label: &str) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+        }
+    }
+
+    /// Returns the namespaced name, e.g. `"git.blame"`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the plugin owning the mode
+    pub fn plugin(&self) -> &str {
+        let name: &str = &self.name;
+        name.split_once('.').map_or(name, |(plugin, _)| plugin)
+    }
+
+    /// Returns the status line label, e.g. `"BLAME"`
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl PartialEq for PluginMode {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for PluginMode {}
+
+impl Hash for PluginMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl fmt::Debug for PluginMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PluginMode({:?})", self.name)
+    }
+}
+
+impl From<EditorMode> for termide_plugin_api::input::EditorMode {
+    /// Converts the mode to the plugin API mode of the same name
+    fn from(mode: EditorMode) -> Self {
+        match mode {
+            EditorMode::Insert => termide_plugin_api::input::EditorMode::Insert,
+            EditorMode::Normal => termide_plugin_api::input::EditorMode::Normal,
+            EditorMode::Prompt => termide_plugin_api::input::EditorMode::Prompt,
+            EditorMode::Plugin(mode) => {
+                termide_plugin_api::input::EditorMode::Plugin(mode.name().to_string())
+            }
         }
     }
 }
@@ -93,6 +205,8 @@ impl FromStr for EditorMode {
     /// - `"prompt"` → `EditorMode::Prompt`
     ///
     /// Parsing is case-insensitive and leading/trailing whitespace is trimmed.
+    /// Plugin modes are parsed by the keybinding registry, which knows the
+    /// registered ones.
     ///
     /// # Examples
     ///
@@ -1,18 +1,28 @@
 //! Editor state management
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use termide_plugin_api::event::EditorEvent;
+use termide_plugin_api::ui::Severity;
 
 use crate::buffer::{Buffer, Position};
-use crate::file_io::{read_file, write_file};
+use crate::file_io::{
+    apply_save_hooks, is_writable, read_file, save_with_options, write_file_elevated, SaveHooks,
+    SaveOptions,
+};
 
-use super::EditorMode;
+use super::{
+    EditorConfig, EditorMode, EditorSettings, EventBus, KeyHints, PromptKind, PromptList,
+};
 
-/// Central editor state managing buffer, mode, and UI state
+/// Central editor state managing buffers, mode, and UI state
 ///
-/// `EditorState` is the core component that ties together the buffer,
+/// `EditorState` is the core component that ties together the open buffers,
 /// editing mode, status messages, and application lifecycle management.
+/// One buffer is active at a time; [`buffer`](Self::buffer) and the editing
+/// methods always operate on the active buffer.
 ///
 /// # Examples
 ///
@@ -37,20 +47,59 @@ use super::EditorMode;
 /// ```
 #[derive(Debug)]
 pub struct EditorState {
-    /// The text buffer
-    buffer: Buffer,
+    /// Open buffers, in the order they were opened (never empty)
+    buffers: Vec<Buffer>,
+    /// Last cursor position of each buffer, restored when switching back to it
+    cursors: Vec<Position>,
+    /// Index of the active buffer
+    active: usize,
     /// Current editing mode
     mode: EditorMode,
     /// Status message to display to user
     status_message: Option<String>,
+    /// Severity of the status message, which decides its color
+    status_severity: Severity,
+    /// Status line segments added by plugins, keyed by plugin and segment key
+    status_segments: BTreeMap<(String, String), String>,
+    /// Continuations of a pending multi-key sequence, shown in the which-key popup
+    key_hints: Option<KeyHints>,
+    /// Register of the macro being recorded, shown in the status bar
+    recording_macro: Option<char>,
     /// Flag indicating the editor should quit
     should_quit: bool,
+    /// Set once the unsaved-changes warning is shown, so the next quit request
+    /// discards the changes
+    quit_pending: bool,
     /// Current prompt input text (used in Prompt mode)
     prompt_input: String,
     /// Prompt message to display (used in Prompt mode)
     prompt_message: String,
+    /// What the prompt input is for
+    prompt_kind: PromptKind,
+    /// Items to pick from, for prompts that show a list
+    prompt_list: Option<PromptList>,
+    /// Completions being cycled through with Tab, and the one shown
+    prompt_completions: Option<(Vec<String>, usize)>,
     /// Previous mode before entering Prompt mode
     previous_mode: EditorMode,
+    /// Backup and save hook options applied on save
+    save_options: SaveOptions,
+    /// General settings from the `[editor]` config section
+    settings: EditorSettings,
+    /// Set when a save through the sudo helper has been requested
+    sudo_save_requested: bool,
+    /// Name of a theme the renderer should switch to
+    theme_request: Option<String>,
+    /// Whether saving a buffer without a file path hands it to standard output
+    pipe_output: bool,
+    /// Content saved for standard output, written when the editor exits
+    piped_content: Option<String>,
+    /// Events waiting to be delivered to plugins
+    events: EventBus,
+    /// Number of open undo groups
+    undo_group_depth: usize,
+    /// Revision of each buffer when the outermost undo group started
+    group_revisions: Vec<u64>,
 }
 
 impl EditorState {
@@ -68,13 +117,32 @@ impl EditorState {
     /// ```
     pub fn new() -> Self {
         Self {
-            buffer: Buffer::new(),
+            buffers: vec![Buffer::new()],
+            cursors: vec![Position::origin()],
+            active: 0,
             mode: EditorMode::Insert,
             status_message: None,
+            status_severity: Severity::default(),
+            status_segments: BTreeMap::new(),
+            key_hints: None,
+            recording_macro: None,
             should_quit: false,
+            quit_pending: false,
             prompt_input: String::new(),
             prompt_message: String::new(),
+            prompt_kind: PromptKind::default(),
+            prompt_list: None,
+            prompt_completions: None,
             previous_mode: EditorMode::Insert,
+            save_options: SaveOptions::default(),
+            settings: EditorSettings::default(),
+            sudo_save_requested: false,
+            theme_request: None,
+            pipe_output: false,
+            piped_content: None,
+            events: EventBus::new(),
+            undo_group_depth: 0,
+            group_revisions: Vec::new(),
         }
     }
 
@@ -104,44 +172,137 @@ impl EditorState {
     /// # }
     /// ```
     pub fn from_file(path: &Path) -> Result<Self> {
-        let mut buffer = if path.exists() {
-            let content = read_file(path)
-                .with_context(|| format!("Failed to load file: {}", path.display()))?;
-            Buffer::from_str(&content)
-        } else {
-            Buffer::new()
-        };
+        let (buffer, warning) = load_buffer(path)?;
 
-        buffer.set_file_path(path.to_path_buf());
+        let mut state = Self::new();
+        state.buffers = vec![buffer];
+        state.publish_event(EditorEvent::BufferOpened {
+            path: path.to_path_buf(),
+        });
+        if let Some(warning) = warning {
+            state.set_status(Severity::Warning, warning);
+        }
+        Ok(state)
+    }
 
-        Ok(Self {
-            buffer,
-            mode: EditorMode::Insert,
-            status_message: None,
-            should_quit: false,
-            prompt_input: String::new(),
-            prompt_message: String::new(),
-            previous_mode: EditorMode::Insert,
-        })
+    /// Opens another file in a new buffer without switching to it
+    ///
+    /// `cursor` is the position the cursor is placed at when the buffer is
+    /// first activated (clamped to the buffer content).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use termide::editor::EditorState;
+    /// use termide::buffer::Position;
+    ///
+    /// # fn main() -> Result<(), anyhow::Error> {
+    /// let mut state = EditorState::from_file(Path::new("a.txt"))?;
+    /// state.open_file(Path::new("b.txt"), Position::origin())?;
+    /// assert_eq!(state.buffer_count(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_file(&mut self, path: &Path, cursor: Position) -> Result<()> {
+        let (buffer, warning) = load_buffer(path)?;
+        self.add_buffer(buffer, cursor);
+        self.publish_event(EditorEvent::BufferOpened {
+            path: path.to_path_buf(),
+        });
+        if let Some(warning) = warning {
+            self.set_status(Severity::Warning, warning);
+        }
+        Ok(())
+    }
+
+    /// Adds an already loaded buffer (e.g. text read from stdin) without switching to it
+    pub fn add_buffer(&mut self, buffer: Buffer, cursor: Position) {
+        let cursor = buffer.clamp_position(cursor);
+        self.buffers.push(buffer);
+        self.cursors.push(cursor);
+    }
+
+    /// Returns all open buffers in the order they were opened
+    pub fn buffers(&self) -> &[Buffer] {
+        &self.buffers
     }
 
-    /// Returns a reference to the buffer
+    /// Returns mutable references to all open buffers
+    pub fn buffers_mut(&mut self) -> &mut [Buffer] {
+        &mut self.buffers
+    }
+
+    /// Returns the number of open buffers
+    pub fn buffer_count(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Returns the index of the active buffer
+    pub fn active_buffer_index(&self) -> usize {
+        self.active
+    }
+
+    /// Makes the buffer at `index` active
+    ///
+    /// `cursor` is the current cursor position, remembered for the buffer being
+    /// left. Returns the cursor position to use in the newly active buffer.
+    /// An out-of-range index leaves the active buffer unchanged.
+    pub fn switch_buffer(&mut self, index: usize, cursor: Position) -> Position {
+        if index >= self.buffers.len() {
+            return cursor;
+        }
+
+        self.cursors[self.active] = cursor;
+        self.active = index;
+        self.buffers[index].clamp_position(self.cursors[index])
+    }
+
+    /// Activates the next buffer (wrapping around), see [`switch_buffer`](Self::switch_buffer)
+    pub fn next_buffer(&mut self, cursor: Position) -> Position {
+        let index = (self.active + 1) % self.buffers.len();
+        self.switch_buffer(index, cursor)
+    }
+
+    /// Activates the previous buffer (wrapping around), see [`switch_buffer`](Self::switch_buffer)
+    pub fn previous_buffer(&mut self, cursor: Position) -> Position {
+        let index = (self.active + self.buffers.len() - 1) % self.buffers.len();
+        self.switch_buffer(index, cursor)
+    }
+
+    /// Returns `true` if the active buffer rejects edits
+    pub fn is_read_only(&self) -> bool {
+        self.buffer().is_read_only()
+    }
+
+    /// Marks the active buffer as read-only (e.g. from the `--readonly` flag) or editable
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.buffer_mut().set_read_only(read_only);
+    }
+
+    /// Returns a reference to the active buffer
     pub fn buffer(&self) -> &Buffer {
-        &self.buffer
+        &self.buffers[self.active]
     }
 
-    /// Returns a mutable reference to the buffer
+    /// Returns a mutable reference to the active buffer
     pub fn buffer_mut(&mut self) -> &mut Buffer {
-        &mut self.buffer
+        &mut self.buffers[self.active]
     }
 
     /// Returns the current editing mode
     pub fn mode(&self) -> EditorMode {
-        self.mode
+        self.mode.clone()
     }
 
     /// Sets the editing mode
     ///
+    /// Publishes [`EditorEvent::ModeChanged`] if the mode changes.
+    ///
     /// # Examples
     ///
     /// ```
@@ -154,32 +315,184 @@ impl EditorState {
     /// assert_eq!(state.mode(), EditorMode::Normal);
     /// ```
     pub fn set_mode(&mut self, mode: EditorMode) {
+        if mode != self.mode {
+            self.publish_event(EditorEvent::ModeChanged {
+                from: self.mode.clone().into(),
+                to: mode.clone().into(),
+            });
+        }
         self.mode = mode;
     }
 
+    /// Publishes `event` to plugins subscribed to it
+    ///
+    /// Events are queued until the event loop hands them to the plugin host
+    /// with [`take_events`](Self::take_events).
+    pub fn publish_event(&mut self, event: EditorEvent) {
+        self.events.publish(event);
+    }
+
+    /// Removes and returns the events published so far, oldest first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::{EditorMode, EditorState};
+    /// use termide_plugin_api::event::EditorEvent;
+    /// use termide_plugin_api::input::EditorMode as ApiMode;
+    ///
+    /// let mut state = EditorState::new();
+    /// state.set_mode(EditorMode::Normal);
+    ///
+    /// assert_eq!(
+    ///     state.take_events(),
+    ///     vec![EditorEvent::ModeChanged { from: ApiMode::Insert, to: ApiMode::Normal }]
+    /// );
+    /// assert!(state.take_events().is_empty());
+    /// ```
+    pub fn take_events(&mut self) -> Vec<EditorEvent> {
+        self.events.drain()
+    }
+
     /// Returns the current status message, if any
     pub fn status_message(&self) -> Option<&str> {
         self.status_message.as_deref()
     }
 
-    /// Sets a status message to display to the user
+    /// Returns the severity of the current status message
+    pub fn status_severity(&self) -> Severity {
+        self.status_severity
+    }
+
+    /// Sets a status message reporting a success
+    ///
+    /// Shorthand for [`set_status`](Self::set_status) with
+    /// [`Severity::Success`]; other messages set their severity explicitly.
     ///
     /// # Examples
     ///
     /// ```
     /// use termide::editor::EditorState;
+    /// use termide_plugin_api::ui::Severity;
     ///
     /// let mut state = EditorState::new();
     /// state.set_status_message("File saved successfully".to_string());
     /// assert_eq!(state.status_message(), Some("File saved successfully"));
+    /// assert_eq!(state.status_severity(), Severity::Success);
     /// ```
     pub fn set_status_message(&mut self, message: String) {
+        self.set_status(Severity::Success, message);
+    }
+
+    /// Sets a status message with an explicit severity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::EditorState;
+    /// use termide_plugin_api::ui::Severity;
+    ///
+    /// let mut state = EditorState::new();
+    /// state.set_status(Severity::Warning, "3 lints".to_string());
+    /// assert_eq!(state.status_message(), Some("3 lints"));
+    /// assert_eq!(state.status_severity(), Severity::Warning);
+    /// ```
+    pub fn set_status(&mut self, severity: Severity, message: String) {
         self.status_message = Some(message);
+        self.status_severity = severity;
     }
 
     /// Clears the status message
     pub fn clear_status_message(&mut self) {
         self.status_message = None;
+        self.status_severity = Severity::default();
+    }
+
+    /// Returns the status line segments, ordered by owner and key
+    pub fn status_segments(&self) -> impl Iterator<Item = &str> {
+        self.status_segments.values().map(String::as_str)
+    }
+
+    /// Sets the status line segment `key` of `owner`, or removes it if `text` is `None`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::EditorState;
+    ///
+    /// let mut state = EditorState::new();
+    /// state.set_status_segment("git", "branch", Some("main".to_string()));
+    /// state.set_status_segment("lint", "count", Some("2 warnings".to_string()));
+    /// assert_eq!(state.status_segments().collect::<Vec<_>>(), vec!["main", "2 warnings"]);
+    ///
+    /// state.set_status_segment("git", "branch", None);
+    /// assert_eq!(state.status_segments().count(), 1);
+    /// ```
+    pub fn set_status_segment(&mut self, owner: &str, key: &str, text: Option<String>) {
+        let id = (owner.to_string(), key.to_string());
+        match text {
+            Some(text) => {
+                self.status_segments.insert(id, text);
+            }
+            None => {
+                self.status_segments.remove(&id);
+            }
+        }
+    }
+
+    /// Returns the continuations of the pending key sequence, if the popup is shown
+    pub fn key_hints(&self) -> Option<&KeyHints> {
+        self.key_hints.as_ref()
+    }
+
+    /// Shows (`Some`) or hides (`None`) the which-key popup
+    pub fn set_key_hints(&mut self, hints: Option<KeyHints>) {
+        self.key_hints = hints;
+    }
+
+    /// Returns the register of the macro being recorded
+    pub fn recording_macro(&self) -> Option<char> {
+        self.recording_macro
+    }
+
+    /// Shows (`Some`) or hides (`None`) the macro recording indicator
+    pub fn set_recording_macro(&mut self, register: Option<char>) {
+        self.recording_macro = register;
+    }
+
+    /// Starts grouping the edits of every open buffer into one undo step per buffer
+    ///
+    /// See [`Buffer::begin_undo_group`]; must be paired with
+    /// [`end_undo_group`](Self::end_undo_group).
+    pub fn begin_undo_group(&mut self) {
+        if self.undo_group_depth == 0 {
+            self.group_revisions = self.buffers.iter().map(Buffer::revision).collect();
+        }
+        self.undo_group_depth += 1;
+        self.buffers.iter_mut().for_each(Buffer::begin_undo_group);
+    }
+
+    /// Ends a group started with [`begin_undo_group`](Self::begin_undo_group)
+    ///
+    /// Ending the outermost group publishes [`EditorEvent::BufferChanged`]
+    /// once for each buffer whose text changed during the group. Buffers
+    /// opened during the group are not reported.
+    pub fn end_undo_group(&mut self) {
+        self.buffers.iter_mut().for_each(Buffer::end_undo_group);
+        self.undo_group_depth = self.undo_group_depth.saturating_sub(1);
+        if self.undo_group_depth > 0 {
+            return;
+        }
+
+        let revisions = std::mem::take(&mut self.group_revisions);
+        for (buffer, revision) in self.buffers.iter().zip(revisions) {
+            if buffer.revision() != revision {
+                self.quit_pending = false;
+                self.events.publish(EditorEvent::BufferChanged {
+                    path: buffer.file_path().cloned(),
+                });
+            }
+        }
     }
 
     /// Returns whether the editor should quit
@@ -189,8 +502,11 @@ impl EditorState {
 
     /// Sets the quit flag
     ///
-    /// This method checks if the buffer has unsaved changes and returns
-    /// whether the quit should proceed.
+    /// This method checks if any buffer has unsaved changes and returns
+    /// whether the quit should proceed. The first request with unsaved changes
+    /// shows a warning; a second request right after it quits anyway. Any other
+    /// command or edit in between cancels the pending quit (see
+    /// [`cancel_quit`](Self::cancel_quit)).
     ///
     /// Returns `true` if quit should proceed, `false` if there are unsaved changes
     /// that need to be handled.
@@ -213,23 +529,36 @@ impl EditorState {
     ///
     /// // Has changes, first attempt warns
     /// assert!(!state.request_quit());
-    /// assert_eq!(state.status_message(), Some("Warning: Unsaved changes! Press Ctrl+Q again to force quit."));
+    /// assert_eq!(state.status_message(), Some("Unsaved changes! Press Ctrl+Q again to force quit."));
     ///
     /// // Second attempt forces quit
     /// assert!(state.request_quit());
     /// assert!(state.should_quit());
     /// ```
     pub fn request_quit(&mut self) -> bool {
-        if self.buffer.is_dirty() {
-            // If already warned (status message exists), force quit
-            if self.status_message.is_some() {
+        let dirty_count = self.buffers.iter().filter(|b| b.is_dirty()).count();
+        if dirty_count > 0 {
+            if self.quit_pending {
+                // Already warned, force quit
                 self.should_quit = true;
                 true
-            } else {
+            } else if dirty_count == 1 {
                 // First attempt - warn user
-                self.set_status_message(
-                    "Warning: Unsaved changes! Press Ctrl+Q again to force quit.".to_string(),
+                self.set_status(
+                    Severity::Warning,
+                    "Unsaved changes! Press Ctrl+Q again to force quit.".to_string(),
                 );
+                self.quit_pending = true;
+                false
+            } else {
+                self.set_status(
+                    Severity::Warning,
+                    format!(
+                        "{} buffers have unsaved changes! Press Ctrl+Q again to force quit.",
+                        dirty_count
+                    ),
+                );
+                self.quit_pending = true;
                 false
             }
         } else {
@@ -239,6 +568,14 @@ impl EditorState {
         }
     }
 
+    /// Cancels a quit confirmed by the unsaved-changes warning
+    ///
+    /// Called for every command other than quit, so that a later quit request
+    /// warns again.
+    pub fn cancel_quit(&mut self) {
+        self.quit_pending = false;
+    }
+
     /// Forces quit without checking for unsaved changes
     pub fn force_quit(&mut self) {
         self.should_quit = true;
@@ -261,19 +598,19 @@ impl EditorState {
     /// assert_eq!(state.buffer().content(), "A");
     /// ```
     pub fn handle_char_insert(&mut self, ch: char, pos: Position) -> bool {
-        self.buffer.insert_char(ch, pos)
+        self.buffer_mut().insert_char(ch, pos)
     }
 
     /// Deletes a character at the specified position
     ///
     /// Returns `true` if deletion was successful.
     pub fn handle_char_delete(&mut self, pos: Position) -> bool {
-        self.buffer.delete_char_at(pos)
+        self.buffer_mut().delete_char_at(pos)
     }
 
     /// Sets the file path for the buffer
     pub fn set_file_path(&mut self, path: &Path) {
-        self.buffer.set_file_path(path.to_path_buf());
+        self.buffer_mut().set_file_path(path.to_path_buf());
     }
 
     /// Returns the current prompt input text
@@ -286,52 +623,218 @@ impl EditorState {
         &self.prompt_message
     }
 
+    /// Returns what the prompt input is for
+    pub fn prompt_kind(&self) -> &PromptKind {
+        &self.prompt_kind
+    }
+
+    /// Returns the items of the prompt, if it shows a list
+    pub fn prompt_list(&self) -> Option<&PromptList> {
+        self.prompt_list.as_ref()
+    }
+
     /// Enters prompt mode with the given message
     ///
     /// Saves the current mode and switches to Prompt mode, displaying the given message.
     pub fn enter_prompt(&mut self, message: String) {
-        self.previous_mode = self.mode;
-        self.mode = EditorMode::Prompt;
+        self.previous_mode = self.mode.clone();
+        self.set_mode(EditorMode::Prompt);
         self.prompt_message = message;
         self.prompt_input.clear();
+        self.prompt_kind = PromptKind::SaveAs;
+        self.prompt_list = None;
+        self.prompt_completions = None;
+    }
+
+    /// Enters prompt mode for input of the given kind
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::{EditorState, PromptKind};
+    ///
+    /// let mut state = EditorState::new();
+    /// state.enter_prompt_for(PromptKind::CommandLine, ":".to_string());
+    /// assert_eq!(state.prompt_kind(), &PromptKind::CommandLine);
+    /// assert!(state.prompt_list().is_none());
+    /// ```
+    pub fn enter_prompt_for(&mut self, kind: PromptKind, message: String) {
+        self.enter_prompt(message);
+        self.prompt_kind = kind;
+    }
+
+    /// Enters prompt mode with a list of `items` to pick from
+    ///
+    /// `items` is a [`PromptList`], or the item texts. Typing filters the items, [`prompt_select_next`](Self::prompt_select_next)
+    /// and [`prompt_select_previous`](Self::prompt_select_previous) move the
+    /// highlight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::{EditorMode, EditorState, PromptKind};
+    ///
+    /// let mut state = EditorState::new();
+    /// let kind = PromptKind::PluginPicker { plugin: "git".to_string(), id: "branch".to_string() };
+    /// state.enter_list_prompt(kind, "Branch: ".to_string(), vec!["main".to_string(), "dev".to_string()]);
+    /// state.prompt_insert_char('d');
+    ///
+    /// assert_eq!(state.mode(), EditorMode::Prompt);
+    /// assert_eq!(state.prompt_selection(), Some((1, "dev".to_string())));
+    /// ```
+    pub fn enter_list_prompt(&mut self, kind: PromptKind, message: String, items: impl Into<PromptList>) {
+        self.enter_prompt(message);
+        self.prompt_kind = kind;
+        self.prompt_list = Some(items.into());
     }
 
     /// Appends a character to the prompt input
     pub fn prompt_insert_char(&mut self, ch: char) {
         self.prompt_input.push(ch);
+        self.prompt_completions = None;
+        self.filter_prompt_list();
     }
 
     /// Deletes the last character from the prompt input
     pub fn prompt_delete_char(&mut self) {
         self.prompt_input.pop();
+        self.prompt_completions = None;
+        self.filter_prompt_list();
+    }
+
+    /// Replaces the prompt input with a completion
+    ///
+    /// The first call asks `completions` for the completions of the input and
+    /// shows the first; further calls show the next one, wrapping around,
+    /// until the input is edited. Returns the number of completions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::{EditorState, PromptKind};
+    ///
+    /// let mut state = EditorState::new();
+    /// state.enter_prompt_for(PromptKind::CommandLine, ":".to_string());
+    /// state.prompt_insert_char('w');
+    ///
+    /// let complete = |input: &str| vec![format!("{}q", input), format!("{}rite", input)];
+    /// assert_eq!(state.prompt_complete(complete), 2);
+    /// assert_eq!(state.prompt_input(), "wq");
+    /// state.prompt_complete(complete);
+    /// assert_eq!(state.prompt_input(), "write");
+    /// state.prompt_complete(complete);
+    /// assert_eq!(state.prompt_input(), "wq");
+    /// ```
+    pub fn prompt_complete(&mut self, completions: impl FnOnce(&str) -> Vec<String>) -> usize {
+        let (candidates, index) = match self.prompt_completions.take() {
+            Some((candidates, index)) => {
+                let next = (index + 1) % candidates.len();
+                (candidates, next)
+            }
+            None => (completions(&self.prompt_input), 0),
+        };
+        let Some(completion) = candidates.get(index) else {
+            return 0;
+        };
+
+        self.prompt_input = completion.clone();
+        self.filter_prompt_list();
+        let count = candidates.len();
+        // A single completion is final, so the next Tab completes it further
+        if count > 1 {
+            self.prompt_completions = Some((candidates, index));
+        }
+        count
+    }
+
+    /// Highlights the next item of the prompt list
+    pub fn prompt_select_next(&mut self) {
+        if let Some(list) = &mut self.prompt_list {
+            list.select_next();
+        }
+    }
+
+    /// Highlights the previous item of the prompt list
+    pub fn prompt_select_previous(&mut self) {
+        if let Some(list) = &mut self.prompt_list {
+            list.select_previous();
+        }
+    }
+
+    /// Returns the highlighted item of the prompt list with its index
+    pub fn prompt_selection(&self) -> Option<(usize, String)> {
+        let (index, item) = self.prompt_list.as_ref()?.selected()?;
+        Some((index, item.to_string()))
+    }
+
+    fn filter_prompt_list(&mut self) {
+        if let Some(list) = &mut self.prompt_list {
+            list.filter(&self.prompt_input);
+        }
     }
 
     /// Accepts the prompt input and returns to the previous mode
     ///
     /// Returns the prompt input text.
     pub fn accept_prompt(&mut self) -> String {
-        self.mode = self.previous_mode;
+        self.set_mode(self.previous_mode.clone());
         let input = self.prompt_input.clone();
         self.prompt_input.clear();
         self.prompt_message.clear();
+        self.prompt_kind = PromptKind::SaveAs;
+        self.prompt_list = None;
+        self.prompt_completions = None;
         input
     }
 
     /// Cancels the prompt and returns to the previous mode
     pub fn cancel_prompt(&mut self) {
-        self.mode = self.previous_mode;
+        self.set_mode(self.previous_mode.clone());
         self.prompt_input.clear();
         self.prompt_message.clear();
+        self.prompt_kind = PromptKind::SaveAs;
+        self.prompt_list = None;
+        self.prompt_completions = None;
+    }
+
+    /// Returns the options applied when saving
+    pub fn save_options(&self) -> &SaveOptions {
+        &self.save_options
+    }
+
+    /// Sets the backup and save hook options (typically from the config file)
+    pub fn set_save_options(&mut self, options: SaveOptions) {
+        self.save_options = options;
+    }
+
+    /// Returns the general editor settings
+    pub fn settings(&self) -> &EditorSettings {
+        &self.settings
+    }
+
+    /// Replaces the general editor settings (typically from the config file)
+    ///
+    /// The new settings apply to the next edit and the next rendered frame.
+    pub fn set_settings(&mut self, settings: EditorSettings) {
+        self.settings = settings;
     }
 
     /// Saves the buffer to its associated file
     ///
     /// If no file path is associated, enters Prompt mode to ask for a filename.
     /// Returns `true` if the file was saved, `false` if a filename prompt was started.
+    /// Read-only buffers are never written. In pipe mode (see
+    /// [`set_pipe_output`](Self::set_pipe_output)) a buffer without a file path is
+    /// saved for standard output instead of prompting for a filename.
+    ///
+    /// Configured save hooks run first and their result replaces the buffer content.
+    /// Non-fatal hook failures (such as a failing formatter) are shown as a warning
+    /// in the status line; the unformatted content is still saved.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written (permissions, disk full, etc.)
+    /// Returns an error if the buffer is read-only or the file cannot be written
+    /// (permissions, disk full, etc.)
     ///
     /// # Examples
     ///
@@ -352,40 +855,219 @@ impl EditorState {
     /// # }
     /// ```
     pub fn save(&mut self) -> Result<bool> {
-        if self.buffer.file_path().is_none() {
+        if self.is_read_only() {
+            bail!("Buffer is read-only. Use 'buffer.toggle_readonly' to allow editing.");
+        }
+
+        if self.buffer().file_path().is_none() && self.pipe_output {
+            self.piped_content = Some(self.buffer().content());
+            self.buffer_mut().clear_dirty();
+            self.publish_event(EditorEvent::BufferSaved { path: None });
+            self.set_status_message(
+                "Saved successfully (written to standard output on exit)".to_string(),
+            );
+            return Ok(true);
+        }
+
+        if self.buffer().file_path().is_none() {
             // No file path - enter prompt mode to ask for filename
             self.enter_prompt("Save as: ".to_string());
             return Ok(false);
         }
 
-        let path = self.buffer.file_path().unwrap();
-
-        write_file(path, &self.buffer.content())
-            .with_context(|| format!("Failed to save file: {}", path.display()))?;
-
-        self.buffer.clear_dirty();
-        self.set_status_message("Saved successfully".to_string());
+        let path = self.buffer().file_path().unwrap().clone();
+        self.write_to(&path)?;
 
         Ok(true)
     }
 
     /// Saves the buffer to a specific file path
     ///
-    /// Sets the buffer's file path and writes the content.
+    /// Sets the buffer's file path and writes the content. Like
+    /// [`save`](Self::save), this never writes a read-only buffer, not even to
+    /// another file.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written (permissions, disk full, etc.)
+    /// Returns an error if the buffer is read-only or the file cannot be
+    /// written (permissions, disk full, etc.)
     pub fn save_as(&mut self, path: &Path) -> Result<()> {
-        write_file(path, &self.buffer.content())
+        if self.is_read_only() {
+            bail!("Buffer is read-only. Use 'buffer.toggle_readonly' to allow editing.");
+        }
+        self.write_to(path)?;
+        self.buffer_mut().set_file_path(path.to_path_buf());
+
+        Ok(())
+    }
+
+    /// Enables or disables pipe mode
+    ///
+    /// In pipe mode, saving a buffer without a file path (such as text read from
+    /// standard input) keeps a copy of its content instead of asking for a
+    /// filename. The caller writes it to standard output on exit using
+    /// [`take_piped_content`](Self::take_piped_content).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::EditorState;
+    /// use termide::buffer::Position;
+    ///
+    /// let mut state = EditorState::new();
+    /// state.set_pipe_output(true);
+    /// state.handle_char_insert('A', Position::origin());
+    ///
+    /// assert!(state.save().unwrap());
+    /// assert_eq!(state.take_piped_content(), Some("A".to_string()));
+    /// ```
+    pub fn set_pipe_output(&mut self, enabled: bool) {
+        self.pipe_output = enabled;
+    }
+
+    /// Returns `true` if pipe mode is enabled
+    pub fn is_pipe_output(&self) -> bool {
+        self.pipe_output
+    }
+
+    /// Returns and clears the content last saved for standard output
+    ///
+    /// Returns `None` if nothing was saved in pipe mode, in which case nothing
+    /// should be written to standard output.
+    pub fn take_piped_content(&mut self) -> Option<String> {
+        self.piped_content.take()
+    }
+
+    /// Requests a save through the sudo helper
+    ///
+    /// The helper needs the terminal (e.g. for a password prompt), so the save is
+    /// carried out by the event loop, which suspends the UI and calls
+    /// [`save_with_sudo`](Self::save_with_sudo).
+    pub fn request_sudo_save(&mut self) {
+        self.sudo_save_requested = true;
+    }
+
+    /// Returns and clears the pending sudo save request
+    pub fn take_sudo_save_request(&mut self) -> bool {
+        std::mem::take(&mut self.sudo_save_requested)
+    }
+
+    /// Requests a switch to the named color theme
+    ///
+    /// The theme belongs to the renderer, so the switch is carried out by the
+    /// event loop.
+    pub fn request_theme(&mut self, name: String) {
+        self.theme_request = Some(name);
+    }
+
+    /// Returns and clears the pending theme switch
+    pub fn take_theme_request(&mut self) -> Option<String> {
+        self.theme_request.take()
+    }
+
+    /// Saves the buffer by piping it to the configured sudo helper
+    ///
+    /// Save hooks run as for a regular save. No backup is made, since the backup
+    /// location is usually not writable either. The read-only flag does not block
+    /// this command: it is the explicit way to write a protected file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer has no file path or the helper fails.
+    pub fn save_with_sudo(&mut self) -> Result<()> {
+        let Some(path) = self.buffer().file_path().cloned() else {
+            bail!("Buffer has no file name. Save it normally first.");
+        };
+
+        let content = self.buffer().content();
+        let (new_content, warnings) = apply_save_hooks(&path, &content, &self.save_hooks_for(&path));
+        write_file_elevated(&path, &new_content, &self.save_options.sudo_command)
             .with_context(|| format!("Failed to save file: {}", path.display()))?;
 
-        self.buffer.set_file_path(path.to_path_buf());
-        self.buffer.clear_dirty();
-        self.set_status_message("Saved successfully".to_string());
+        if new_content != content {
+            self.buffer_mut().set_content(&new_content);
+        }
+        self.buffer_mut().clear_dirty();
+        self.publish_event(EditorEvent::BufferSaved { path: Some(path) });
+
+        if warnings.is_empty() {
+            self.set_status_message("Saved successfully with elevated privileges".to_string());
+        } else {
+            self.set_status(Severity::Warning, warnings.join("; "));
+        }
 
         Ok(())
     }
+
+    /// Returns the save hooks for `path`: the configured hooks with the
+    /// `.editorconfig` properties for `path` applied on top
+    fn save_hooks_for(&self, path: &Path) -> SaveHooks {
+        let mut hooks = self.save_options.hooks.clone();
+        EditorConfig::for_file(path).apply_save_hooks(&mut hooks);
+        hooks
+    }
+
+    /// Writes the buffer to `path` using the save options and reports the outcome
+    fn write_to(&mut self, path: &Path) -> Result<()> {
+        let content = self.buffer().content();
+        let options = SaveOptions {
+            hooks: self.save_hooks_for(path),
+            ..self.save_options.clone()
+        };
+        let report = save_with_options(path, &content, &options).with_context(|| {
+            if path.exists() && !is_writable(path) {
+                format!(
+                    "Failed to save file: {}. Use 'file.save_sudo' to save with elevated privileges",
+                    path.display()
+                )
+            } else {
+                format!("Failed to save file: {}", path.display())
+            }
+        })?;
+
+        if report.content != content {
+            self.buffer_mut().set_content(&report.content);
+        }
+        self.buffer_mut().clear_dirty();
+        self.publish_event(EditorEvent::BufferSaved {
+            path: Some(path.to_path_buf()),
+        });
+
+        if report.warnings.is_empty() {
+            self.set_status_message("Saved successfully".to_string());
+        } else {
+            self.set_status(Severity::Warning, report.warnings.join("; "));
+        }
+
+        Ok(())
+    }
+}
+
+/// Loads `path` into a new buffer, detecting files that are not writable
+///
+/// Returns the buffer and a warning to show when it was opened read-only.
+fn load_buffer(path: &Path) -> Result<(Buffer, Option<String>)> {
+    let mut buffer = if path.exists() {
+        let content = read_file(path)
+            .with_context(|| format!("Failed to load file: {}", path.display()))?;
+        Buffer::from_str(&content)
+    } else {
+        Buffer::new()
+    };
+
+    buffer.set_file_path(path.to_path_buf());
+
+    // Files we cannot write open read-only instead of failing later on save
+    let read_only = path.exists() && !is_writable(path);
+    buffer.set_read_only(read_only);
+    let warning = read_only.then(|| {
+        format!(
+            "'{}' is not writable, opened read-only",
+            path.display()
+        )
+    });
+
+    Ok((buffer, warning))
 }
 
 impl Default for EditorState {
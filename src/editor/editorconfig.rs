@@ -0,0 +1,465 @@
+//! EditorConfig (`.editorconfig`) support
+//!
+//! The properties for a file are collected from every `.editorconfig` file in
+//! its directory and the directories above it, stopping at a file that sets
+//! `root = true`. Files closer to the edited file, and later sections within a
+//! file, take precedence. See <https://editorconfig.org> for the format.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::EditorSettings;
+use crate::file_io::{LineEnding, SaveHooks};
+
+/// File name of an EditorConfig file
+const EDITORCONFIG_FILE_NAME: &str = ".editorconfig";
+
+/// Indentation style requested by `indent_style`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    /// Indent with tab characters
+    Tab,
+    /// Indent with spaces
+    Space,
+}
+
+/// The EditorConfig properties that apply to one file
+///
+/// Properties that are not set, set to `unset`, or have an invalid value are
+/// `None` and leave the corresponding termide setting alone.
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::{EditorConfig, EditorSettings, IndentStyle};
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(
+///     dir.path().join(".editorconfig"),
+///     "root = true\n\n[*.rs]\nindent_style = space\nindent_size = 2\n",
+/// )
+/// .unwrap();
+///
+/// let config = EditorConfig::for_file(&dir.path().join("main.rs"));
+/// assert_eq!(config.indent_style, Some(IndentStyle::Space));
+///
+/// let mut settings = EditorSettings::default();
+/// config.apply_settings(&mut settings);
+/// assert!(settings.expand_tabs);
+/// assert_eq!(settings.tab_width, 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorConfig {
+    /// `indent_style`
+    pub indent_style: Option<IndentStyle>,
+    /// `indent_size`, with `indent_size = tab` resolved to `tab_width`
+    pub indent_size: Option<usize>,
+    /// `tab_width`
+    pub tab_width: Option<usize>,
+    /// `end_of_line`
+    pub end_of_line: Option<LineEnding>,
+    /// `charset`, lowercased
+    pub charset: Option<String>,
+    /// `trim_trailing_whitespace`
+    pub trim_trailing_whitespace: Option<bool>,
+    /// `insert_final_newline`
+    pub insert_final_newline: Option<bool>,
+}
+
+impl EditorConfig {
+    /// Collects the properties for `path` from the `.editorconfig` files above it
+    ///
+    /// Unreadable `.editorconfig` files are skipped.
+    pub fn for_file(path: &Path) -> Self {
+        let Ok(path) = std::path::absolute(path) else {
+            return Self::default();
+        };
+
+        // Nearest file first; stop after a root file
+        let mut files = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            let Ok(content) = fs::read_to_string(dir.join(EDITORCONFIG_FILE_NAME)) else {
+                continue;
+            };
+            let file = parse(&content);
+            let is_root = file.root;
+            files.push((dir.to_path_buf(), file));
+            if is_root {
+                break;
+            }
+        }
+
+        // Apply from the outermost file inwards so closer files win
+        let mut properties = HashMap::new();
+        for (dir, file) in files.iter().rev() {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let relative: Vec<char> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+                .chars()
+                .collect();
+            for section in &file.sections {
+                if section.glob.matches(&relative) {
+                    for (key, value) in &section.properties {
+                        properties.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        Self::from_properties(&properties)
+    }
+
+    /// Builds the configuration from lowercased `key = value` properties
+    fn from_properties(properties: &HashMap<String, String>) -> Self {
+        let get = |key: &str| {
+            properties
+                .get(key)
+                .map(String::as_str)
+                .filter(|value| *value != "unset")
+        };
+        let width = |key: &str| get(key).and_then(|value| value.parse::<usize>().ok());
+        let flag = |key: &str| match get(key) {
+            Some("true") => Some(true),
+            Some("false") => Some(false),
+            _ => None,
+        };
+
+        let tab_width = width("tab_width");
+        let indent_size = match get("indent_size") {
+            Some("tab") => tab_width,
+            _ => width("indent_size"),
+        };
+
+        Self {
+            indent_style: match get("indent_style") {
+                Some("tab") => Some(IndentStyle::Tab),
+                Some("space") => Some(IndentStyle::Space),
+                _ => None,
+            },
+            indent_size,
+            tab_width: tab_width.or(indent_size),
+            end_of_line: match get("end_of_line") {
+                Some("lf") => Some(LineEnding::Lf),
+                Some("crlf") => Some(LineEnding::Crlf),
+                Some("cr") => Some(LineEnding::Cr),
+                _ => None,
+            },
+            charset: get("charset").map(str::to_string),
+            trim_trailing_whitespace: flag("trim_trailing_whitespace"),
+            insert_final_newline: flag("insert_final_newline"),
+        }
+    }
+
+    /// Applies the indentation properties to the editing settings
+    ///
+    /// `indent_style` sets `expand_tabs`. The width used for indentation
+    /// (`indent_size` when indenting with spaces, `tab_width` otherwise) sets
+    /// `tab_width`. Returns the names of the settings that were changed.
+    pub fn apply_settings(&self, settings: &mut EditorSettings) -> Vec<&'static str> {
+        let mut applied = Vec::new();
+
+        if let Some(style) = self.indent_style {
+            settings.expand_tabs = style == IndentStyle::Space;
+            applied.push("expand_tabs");
+        }
+
+        let width = match self.indent_style {
+            Some(IndentStyle::Space) => self.indent_size.or(self.tab_width),
+            _ => self.tab_width.or(self.indent_size),
+        };
+        if let Some(width) =
+            width.filter(|width| (1..=EditorSettings::MAX_TAB_WIDTH).contains(width))
+        {
+            settings.tab_width = width;
+            applied.push("tab_width");
+        }
+
+        applied
+    }
+
+    /// Applies the save-related properties to the save hooks
+    ///
+    /// `charset = utf-8-bom` writes a byte order mark and `charset = utf-8`
+    /// removes one. Other charsets are not supported: files are always saved
+    /// as UTF-8.
+    pub fn apply_save_hooks(&self, hooks: &mut SaveHooks) {
+        if let Some(trim) = self.trim_trailing_whitespace {
+            hooks.trim_trailing_whitespace = trim;
+        }
+        if let Some(insert) = self.insert_final_newline {
+            hooks.ensure_final_newline = insert;
+        }
+        if let Some(ending) = self.end_of_line {
+            hooks.line_ending = Some(ending);
+        }
+        match self.charset.as_deref() {
+            Some("utf-8") => hooks.byte_order_mark = Some(false),
+            Some("utf-8-bom") => hooks.byte_order_mark = Some(true),
+            _ => {}
+        }
+    }
+}
+
+/// A parsed `.editorconfig` file
+#[derive(Debug, Default)]
+struct EditorConfigFile {
+    /// `root = true` in the preamble
+    root: bool,
+    /// Sections in file order
+    sections: Vec<Section>,
+}
+
+/// A `[glob]` section and its properties
+#[derive(Debug)]
+struct Section {
+    glob: Glob,
+    properties: Vec<(String, String)>,
+}
+
+/// Parses the INI-style content of an `.editorconfig` file
+///
+/// Keys and values are lowercased. Comment lines start with `#` or `;`;
+/// malformed lines are ignored.
+fn parse(content: &str) -> EditorConfigFile {
+    let mut file = EditorConfigFile::default();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(header) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            file.sections.push(Section {
+                glob: Glob::new(header),
+                properties: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim().to_lowercase();
+        match file.sections.last_mut() {
+            Some(section) => section.properties.push((key, value)),
+            None if key == "root" => file.root = value == "true",
+            None => {}
+        }
+    }
+
+    file
+}
+
+/// A section glob, matched against paths relative to the `.editorconfig` file
+#[derive(Debug)]
+struct Glob {
+    tokens: Vec<Token>,
+}
+
+/// Element of a parsed glob
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A character matching itself
+    Literal(char),
+    /// `?`: any character except `/`
+    AnyChar,
+    /// `*`: any run of characters except `/`
+    Star,
+    /// `**`: any run of characters
+    DoubleStar,
+    /// `**/`: nothing, or any path ending in `/`
+    AnyDirectories,
+    /// `[...]` or `[!...]`: one character from (or not from) the ranges
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    /// `{a,b}`: one of the alternatives
+    Alternatives(Vec<Vec<Token>>),
+    /// `{n1..n2}`: an integer between the bounds
+    Range(i64, i64),
+}
+
+impl Glob {
+    /// Parses a section header
+    ///
+    /// A glob without `/` matches file names in any directory; otherwise it is
+    /// anchored at the directory of the `.editorconfig` file.
+    fn new(pattern: &str) -> Self {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut pos = 0;
+        let mut tokens = parse_glob(&chars, &mut pos, false);
+
+        if pattern.contains('/') {
+            if tokens.first() == Some(&Token::Literal('/')) {
+                tokens.remove(0);
+            }
+        } else {
+            tokens.insert(0, Token::AnyDirectories);
+        }
+
+        Self { tokens }
+    }
+
+    /// Returns `true` if the relative path `path` matches
+    fn matches(&self, path: &[char]) -> bool {
+        match_tokens(&self.tokens, path, &|rest| rest.is_empty())
+    }
+}
+
+/// Parses glob characters from `pos`, stopping at `,` or `}` inside braces
+fn parse_glob(chars: &[char], pos: &mut usize, in_braces: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    while let Some(&ch) = chars.get(*pos) {
+        if in_braces && (ch == ',' || ch == '}') {
+            break;
+        }
+        *pos += 1;
+
+        match ch {
+            '\\' => {
+                if let Some(&escaped) = chars.get(*pos) {
+                    tokens.push(Token::Literal(escaped));
+                    *pos += 1;
+                }
+            }
+            '?' => tokens.push(Token::AnyChar),
+            '*' if chars.get(*pos) == Some(&'*') => {
+                *pos += 1;
+                if chars.get(*pos) == Some(&'/') {
+                    *pos += 1;
+                    tokens.push(Token::AnyDirectories);
+                } else {
+                    tokens.push(Token::DoubleStar);
+                }
+            }
+            '*' => tokens.push(Token::Star),
+            '[' => match parse_class(chars, *pos) {
+                Some((token, end)) => {
+                    tokens.push(token);
+                    *pos = end;
+                }
+                None => tokens.push(Token::Literal('[')),
+            },
+            '{' => match parse_braces(chars, *pos) {
+                Some((token, end)) => {
+                    tokens.push(token);
+                    *pos = end;
+                }
+                None => tokens.push(Token::Literal('{')),
+            },
+            _ => tokens.push(Token::Literal(ch)),
+        }
+    }
+
+    tokens
+}
+
+/// Parses a `[...]` class whose content starts at `start`
+///
+/// Returns the token and the position after `]`, or `None` if the class is
+/// not closed (the `[` is then a literal character).
+fn parse_class(chars: &[char], start: usize) -> Option<(Token, usize)> {
+    let close = start + chars[start..].iter().position(|&ch| ch == ']')?;
+    let mut content = &chars[start..close];
+    if content.contains(&'/') {
+        return None;
+    }
+
+    let negated = matches!(content.first(), Some('!') | Some('^'));
+    if negated {
+        content = &content[1..];
+    }
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < content.len() {
+        if i + 2 < content.len() && content[i + 1] == '-' {
+            ranges.push((content[i], content[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((content[i], content[i]));
+            i += 1;
+        }
+    }
+
+    Some((Token::Class { negated, ranges }, close + 1))
+}
+
+/// Parses a `{...}` group whose content starts at `start`
+///
+/// Returns the token and the position after `}`, or `None` if the group is
+/// not closed (the `{` is then a literal character).
+fn parse_braces(chars: &[char], start: usize) -> Option<(Token, usize)> {
+    // `{n1..n2}` integer range
+    let close = start + chars[start..].iter().position(|&ch| ch == '}')?;
+    let content: String = chars[start..close].iter().collect();
+    if let Some((low, high)) = content.split_once("..") {
+        if let (Ok(low), Ok(high)) = (low.parse(), high.parse()) {
+            return Some((Token::Range(low, high), close + 1));
+        }
+    }
+
+    let mut alternatives = Vec::new();
+    let mut pos = start;
+    loop {
+        alternatives.push(parse_glob(chars, &mut pos, true));
+        match chars.get(pos) {
+            Some(',') => pos += 1,
+            Some('}') => return Some((Token::Alternatives(alternatives), pos + 1)),
+            _ => return None,
+        }
+    }
+}
+
+/// Matches `tokens` against the start of `input`, then calls `next` with the
+/// rest of the input
+fn match_tokens(tokens: &[Token], input: &[char], next: &dyn Fn(&[char]) -> bool) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return next(input);
+    };
+    let continue_with = |remaining: &[char]| match_tokens(rest, remaining, next);
+
+    match token {
+        Token::Literal(ch) => input.first() == Some(ch) && continue_with(&input[1..]),
+        Token::AnyChar => input.first().is_some_and(|&ch| ch != '/') && continue_with(&input[1..]),
+        Token::Star => {
+            let limit = input
+                .iter()
+                .position(|&ch| ch == '/')
+                .unwrap_or(input.len());
+            (0..=limit).any(|len| continue_with(&input[len..]))
+        }
+        Token::DoubleStar => (0..=input.len()).any(|len| continue_with(&input[len..])),
+        Token::AnyDirectories => (0..=input.len())
+            .filter(|&len| len == 0 || input[len - 1] == '/')
+            .any(|len| continue_with(&input[len..])),
+        Token::Class { negated, ranges } => input.first().is_some_and(|&ch| {
+            let in_class = ranges.iter().any(|&(low, high)| (low..=high).contains(&ch));
+            ch != '/' && in_class != *negated && continue_with(&input[1..])
+        }),
+        Token::Alternatives(alternatives) => alternatives
+            .iter()
+            .any(|alternative| match_tokens(alternative, input, &continue_with)),
+        Token::Range(low, high) => (1..=input.len()).any(|len| {
+            let number: String = input[..len].iter().collect();
+            number
+                .parse::<i64>()
+                .is_ok_and(|n| (*low.min(high)..=*low.max(high)).contains(&n))
+                && !number.starts_with('+')
+                && continue_with(&input[len..])
+        }),
+    }
+}
@@ -0,0 +1,59 @@
+//! Queue of editor events waiting to be delivered to plugins
+
+use std::collections::VecDeque;
+
+use termide_plugin_api::event::EditorEvent;
+
+/// Editor events in the order they were published
+///
+/// The editor state publishes to the bus as things happen; the event loop
+/// drains it after each command and hands the events to the plugin host,
+/// which delivers them to subscribed plugins. Queueing the events instead of
+/// calling plugins directly keeps plugin code out of the middle of editor
+/// operations.
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::EventBus;
+/// use termide_plugin_api::event::EditorEvent;
+///
+/// let mut bus = EventBus::new();
+/// bus.publish(EditorEvent::ConfigReloaded);
+/// bus.publish(EditorEvent::BufferChanged { path: None });
+///
+/// let events = bus.drain();
+/// assert_eq!(events[0], EditorEvent::ConfigReloaded);
+/// assert!(bus.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventBus {
+    queue: VecDeque<EditorEvent>,
+}
+
+impl EventBus {
+    /// Creates an empty bus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `event` behind the events published before it
+    pub fn publish(&mut self, event: EditorEvent) {
+        self.queue.push_back(event);
+    }
+
+    /// Removes and returns all queued events, oldest first
+    pub fn drain(&mut self) -> Vec<EditorEvent> {
+        self.queue.drain(..).collect()
+    }
+
+    /// Returns `true` if no event is queued
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the number of queued events
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+}
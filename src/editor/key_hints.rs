@@ -0,0 +1,38 @@
+//! Continuations of a pending key sequence, shown in the which-key popup
+
+/// One way to complete the pending key sequence
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyHint {
+    /// Keys still to be pressed, e.g. `d` after `g` for the sequence `g d`
+    pub keys: String,
+    /// Name of the command the completed sequence runs
+    pub command: String,
+}
+
+/// The keys typed so far and every binding they can still complete
+///
+/// Built by the input handler while a multi-key sequence is pending and
+/// displayed by the renderer above the status bar.
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::{EditorState, KeyHint, KeyHints};
+///
+/// let mut state = EditorState::new();
+/// state.set_key_hints(Some(KeyHints {
+///     pending: "g".to_string(),
+///     hints: vec![KeyHint {
+///         keys: "d".to_string(),
+///         command: "lsp.goto_definition".to_string(),
+///     }],
+/// }));
+/// assert_eq!(state.key_hints().unwrap().hints.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyHints {
+    /// Keys typed so far, e.g. `g`
+    pub pending: String,
+    /// Possible continuations, sorted by keys
+    pub hints: Vec<KeyHint>,
+}
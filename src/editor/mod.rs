@@ -1,7 +1,8 @@
 //! Editor module - core editor state and mode management
 //!
 //! This module provides the central `EditorState` that manages the overall editor state
-//! including the current buffer, editing mode, status messages, and application lifecycle.
+//! including the current buffer, editing mode, status messages, and application lifecycle,
+//! along with the general [`EditorSettings`] configured in the `[editor]` config section.
 //!
 //! # Examples
 //!
@@ -30,10 +31,20 @@
 //! ```
 
 mod editor_mode;
+mod editorconfig;
 mod editor_state;
+mod event_bus;
+mod key_hints;
+mod prompt;
+mod settings;
 
-pub use editor_mode::EditorMode;
+pub use editor_mode::{EditorMode, ParseModeError, PluginMode};
 pub use editor_state::EditorState;
+pub use editorconfig::{EditorConfig, IndentStyle};
+pub use event_bus::EventBus;
+pub use key_hints::{KeyHint, KeyHints};
+pub use prompt::{PromptKind, PromptList};
+pub use settings::EditorSettings;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,202 @@
+//! Prompt kinds and the item list shown by picker prompts
+
+/// What the input of the prompt is used for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum PromptKind {
+    /// File name for saving a buffer that has no path
+    #[default]
+    SaveAs,
+    /// Picker opened by a plugin; the picked item is delivered to the plugin
+    PluginPicker {
+        /// Name of the plugin that opened the picker
+        plugin: String,
+        /// Picker id chosen by the plugin
+        id: String,
+    },
+    /// Command palette; the picked item is the config name of the command to run
+    CommandPalette,
+    /// Ex-style command line opened with `:`; the input is a command such as `w` or `%s/a/b/`
+    CommandLine,
+}
+
+/// Items a prompt lets the user pick from, filtered by the prompt input
+///
+/// Items match when the characters of the input appear in them in order,
+/// ignoring case (`fsv` matches `file.save`). The matches are sorted best
+/// first: consecutive characters and characters at the start of a word
+/// (after `.`, `_`, `/` and the like) score higher, gaps score lower, and
+/// equally good matches keep their order. One of the matching items is
+/// highlighted; accepting the prompt picks it.
+///
+/// Each item may have a detail, such as the keys bound to a command, shown
+/// next to it but not matched against.
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::PromptList;
+///
+/// let mut list = PromptList::new(vec!["main".to_string(), "develop".to_string(), "docs".to_string()]);
+/// list.filter("D");
+/// assert_eq!(list.len(), 2);
+/// list.select_next();
+/// assert_eq!(list.selected(), Some((2, "docs")));
+///
+/// list.filter("dvl");
+/// assert_eq!(list.selected(), Some((1, "develop")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PromptList {
+    items: Vec<String>,
+    /// Detail shown next to each item; empty for none
+    details: Vec<String>,
+    /// Indices of the items matching the input, in display order
+    matches: Vec<usize>,
+    /// Position of the highlighted item in `matches`
+    selected: usize,
+}
+
+impl PromptList {
+    /// Creates a list with all `items` matching
+    pub fn new(items: Vec<String>) -> Self {
+        let matches = (0..items.len()).collect();
+        Self {
+            details: vec![String::new(); items.len()],
+            items,
+            matches,
+            selected: 0,
+        }
+    }
+
+    /// Sets the details shown next to the items, in item order
+    ///
+    /// Items without a detail (`details` is shorter, or the detail is empty)
+    /// are shown alone.
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        for (slot, detail) in self.details.iter_mut().zip(details) {
+            *slot = detail;
+        }
+        self
+    }
+
+    /// Returns the detail of the item at `index` in the full list
+    pub fn detail(&self, index: usize) -> Option<&str> {
+        self.details
+            .get(index)
+            .map(String::as_str)
+            .filter(|detail| !detail.is_empty())
+    }
+
+    /// Keeps the items fuzzy-matching `query`, best first, and highlights the first of them
+    pub fn filter(&mut self, query: &str) {
+        let query: Vec<char> = query.to_lowercase().chars().collect();
+        let mut scored: Vec<(i64, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| Some((fuzzy_score(item, &query)?, index)))
+            .collect();
+        // Stable, so equal scores keep the item order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+    }
+
+    /// Returns the number of matching items
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Returns `true` if no item matches
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+
+    /// Returns the matching items with their index in the full list
+    pub fn matches(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.matches
+            .iter()
+            .map(|&index| (index, self.items[index].as_str()))
+    }
+
+    /// Returns the position of the highlighted item among the matches
+    pub fn selected_position(&self) -> Option<usize> {
+        (!self.matches.is_empty()).then_some(self.selected)
+    }
+
+    /// Returns the highlighted item with its index in the full list
+    pub fn selected(&self) -> Option<(usize, &str)> {
+        let index = *self.matches.get(self.selected)?;
+        Some((index, self.items[index].as_str()))
+    }
+
+    /// Highlights the next matching item, wrapping around at the end
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+        }
+    }
+
+    /// Highlights the previous matching item, wrapping around at the start
+    pub fn select_previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+}
+
+impl From<Vec<String>> for PromptList {
+    fn from(items: Vec<String>) -> Self {
+        Self::new(items)
+    }
+}
+
+/// Scores how well the lowercase `query` matches `item`, or `None` if its
+/// characters don't all appear in `item` in order
+///
+/// Each start position of the first query character is tried, matching the
+/// rest as early as possible, and the best score is kept.
+fn fuzzy_score(item: &str, query: &[char]) -> Option<i64> {
+    const MATCH: i64 = 16;
+    const CONSECUTIVE: i64 = 12;
+    const WORD_START: i64 = 10;
+
+    let Some(&first) = query.first() else {
+        return Some(0);
+    };
+    let item: Vec<char> = item.chars().flat_map(char::to_lowercase).collect();
+    let word_start = |at: usize| at == 0 || !item[at - 1].is_alphanumeric();
+
+    let mut best = None;
+    for start in (0..item.len()).filter(|&at| item[at] == first) {
+        // Matches further from the start of the item score a little lower
+        let mut score = MATCH - start.min(MATCH as usize) as i64 / 4;
+        if word_start(start) {
+            score += WORD_START;
+        }
+        let mut previous = start;
+        let mut complete = true;
+        for &ch in &query[1..] {
+            let Some(at) = (previous + 1..item.len()).find(|&at| item[at] == ch) else {
+                complete = false;
+                break;
+            };
+            score += MATCH;
+            if at == previous + 1 {
+                score += CONSECUTIVE;
+            } else {
+                score -= (at - previous - 1) as i64;
+                if word_start(at) {
+                    score += WORD_START;
+                }
+            }
+            previous = at;
+        }
+        if !complete {
+            // Later starts leave fewer characters to match the rest
+            break;
+        }
+        best = best.max(Some(score));
+    }
+    best
+}
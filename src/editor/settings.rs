@@ -0,0 +1,140 @@
+//! General editor settings
+
+use std::time::Duration;
+
+use super::EditorMode;
+
+/// General editor settings, configured in the `[editor]` section of the config file
+///
+/// Settings are read by the editor and renderer on every use, so replacing
+/// them with [`EditorState::set_settings`](super::EditorState::set_settings)
+/// (e.g. on config hot reload) takes effect immediately.
+///
+/// # Configuration
+///
+/// ```toml
+/// [editor]
+/// tab_width = 4
+/// expand_tabs = true
+/// default_mode = "normal"
+/// scroll_margin = 3
+/// sequence_timeout_ms = 800
+/// which_key_delay_ms = 300
+/// theme = "light"
+/// line_numbers = true
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::EditorSettings;
+///
+/// let settings = EditorSettings::default();
+/// assert_eq!(settings.tab_width, 4);
+/// assert_eq!(settings.tab_text(0), "\t");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorSettings {
+    /// Width of a tab stop in columns
+    pub tab_width: usize,
+    /// Insert spaces instead of a tab character when Tab is pressed
+    pub expand_tabs: bool,
+    /// Mode the editor starts in
+    pub default_mode: EditorMode,
+    /// Lines kept visible above and below the cursor when scrolling
+    pub scroll_margin: usize,
+    /// Time after which an incomplete multi-key sequence is discarded
+    pub sequence_timeout: Duration,
+    /// Time a multi-key sequence must be pending before its continuations are shown
+    pub which_key_delay: Duration,
+    /// Name of the color theme
+    pub theme: String,
+    /// Show line numbers in a gutter left of the text
+    pub line_numbers: bool,
+}
+
+impl EditorSettings {
+    /// Largest accepted `tab_width`
+    pub const MAX_TAB_WIDTH: usize = 16;
+
+    /// Setting names as written in the configuration file
+    pub const KEYS: &'static [&'static str] = &[
+        "tab_width",
+        "expand_tabs",
+        "default_mode",
+        "scroll_margin",
+        "sequence_timeout_ms",
+        "which_key_delay_ms",
+        "theme",
+        "line_numbers",
+    ];
+
+    /// Returns the value of the setting `key` formatted as in the configuration
+    /// file, or `None` for an unknown key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::EditorSettings;
+    ///
+    /// let settings = EditorSettings::default();
+    /// assert_eq!(settings.value("tab_width").as_deref(), Some("4"));
+    /// assert_eq!(settings.value("theme").as_deref(), Some("\"dark\""));
+    /// assert_eq!(settings.value("unknown"), None);
+    /// ```
+    pub fn value(&self, key: &str) -> Option<String> {
+        let value = match key {
+            "tab_width" => self.tab_width.to_string(),
+            "expand_tabs" => self.expand_tabs.to_string(),
+            "default_mode" => format!("\"{}\"", self.default_mode.to_string().to_lowercase()),
+            "scroll_margin" => self.scroll_margin.to_string(),
+            "sequence_timeout_ms" => self.sequence_timeout.as_millis().to_string(),
+            "which_key_delay_ms" => self.which_key_delay.as_millis().to_string(),
+            "theme" => format!("\"{}\"", self.theme),
+            "line_numbers" => self.line_numbers.to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Returns the text inserted by the Tab key at `column`
+    ///
+    /// With `expand_tabs`, this is the number of spaces needed to reach the next
+    /// tab stop; otherwise a single tab character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::EditorSettings;
+    ///
+    /// let settings = EditorSettings {
+    ///     expand_tabs: true,
+    ///     ..EditorSettings::default()
+    /// };
+    /// assert_eq!(settings.tab_text(0), "    ");
+    /// assert_eq!(settings.tab_text(6), "  ");
+    /// ```
+    pub fn tab_text(&self, column: usize) -> String {
+        if self.expand_tabs {
+            let width = self.tab_width.max(1);
+            " ".repeat(width - column % width)
+        } else {
+            "\t".to_string()
+        }
+    }
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            tab_width: 4,
+            expand_tabs: false,
+            default_mode: EditorMode::Insert,
+            scroll_margin: 0,
+            sequence_timeout: Duration::from_millis(1000),
+            which_key_delay: Duration::from_millis(300),
+            theme: "dark".to_string(),
+            line_numbers: false,
+        }
+    }
+}
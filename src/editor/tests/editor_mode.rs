@@ -1,6 +1,8 @@
 //! Unit tests for EditorMode
 
-use crate::editor::EditorMode;
+use crate::editor::{EditorMode, PluginMode};
+use std::str::FromStr;
+use termide_plugin_api::input::EditorMode as ApiMode;
 
 #[test]
 fn test_editor_mode_default() {
@@ -14,3 +16,37 @@ fn test_editor_mode_to_string() {
     assert_eq!(EditorMode::Normal.to_string(), "NORMAL");
     assert_eq!(EditorMode::Prompt.to_string(), "PROMPT");
 }
+
+#[test]
+fn test_plugin_mode_handle() {
+    let blame = PluginMode::new("git.blame", "BLAME");
+    assert_eq!(blame.name(), "git.blame");
+    assert_eq!(blame.plugin(), "git");
+    assert_eq!(blame.label(), "BLAME");
+
+    let mode = EditorMode::Plugin(blame);
+    assert_eq!(mode.name(), "git.blame");
+    assert_eq!(mode.to_string(), "BLAME");
+    assert_ne!(mode, EditorMode::Normal);
+}
+
+#[test]
+fn test_plugin_modes_compare_by_name() {
+    let repl = PluginMode::new("repl.eval", "REPL");
+    assert_eq!(repl, PluginMode::new("repl.eval", "EVAL"));
+    assert_ne!(repl, PluginMode::new("repl.other", "REPL"));
+}
+
+#[test]
+fn test_editor_mode_from_str_rejects_plugin_modes() {
+    // Only the keybinding registry knows the registered plugin modes
+    let err = EditorMode::from_str("git.blame").unwrap_err();
+    assert_eq!(err.invalid, "git.blame");
+}
+
+#[test]
+fn test_editor_mode_to_plugin_api() {
+    let view = EditorMode::Plugin(PluginMode::new("viewer.view", "VIEW"));
+    assert_eq!(ApiMode::from(view), ApiMode::Plugin("viewer.view".to_string()));
+    assert_eq!(ApiMode::from(EditorMode::Normal), ApiMode::Normal);
+}
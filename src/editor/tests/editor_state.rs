@@ -2,8 +2,9 @@
 
 use std::path::PathBuf;
 
-use crate::buffer::Position;
-use crate::editor::{EditorMode, EditorState};
+use crate::buffer::{Buffer, Position};
+use crate::editor::{EditorMode, EditorState, PromptKind};
+use termide_plugin_api::ui::Severity;
 
 #[test]
 fn test_editor_state_new() {
@@ -87,8 +88,9 @@ fn test_request_quit_dirty_buffer_requires_confirmation() {
     assert!(!state.should_quit());
     assert_eq!(
         state.status_message(),
-        Some("Warning: Unsaved changes! Press Ctrl+Q again to force quit.")
+        Some("Unsaved changes! Press Ctrl+Q again to force quit.")
     );
+    assert_eq!(state.status_severity(), Severity::Warning);
 
     // Second attempt - should quit
     assert!(state.request_quit());
@@ -219,3 +221,338 @@ fn test_cancel_prompt() {
     assert_eq!(state.prompt_input(), "");
     assert_eq!(state.prompt_message(), "");
 }
+
+#[test]
+fn test_prompt_complete_cycles_until_edited() {
+    let mut state = EditorState::new();
+    state.enter_prompt_for(PromptKind::CommandLine, ":".to_string());
+    state.prompt_insert_char('q');
+
+    let complete = |input: &str| match input {
+        "q" => vec!["quit".to_string(), "quit!".to_string()],
+        "quit!x" => vec!["quit!xyz".to_string()],
+        _ => Vec::new(),
+    };
+    assert_eq!(state.prompt_complete(complete), 2);
+    assert_eq!(state.prompt_input(), "quit");
+    assert_eq!(state.prompt_complete(complete), 2);
+    assert_eq!(state.prompt_input(), "quit!");
+
+    // Editing starts over from the new input
+    state.prompt_insert_char('x');
+    assert_eq!(state.prompt_complete(complete), 1);
+    assert_eq!(state.prompt_input(), "quit!xyz");
+    assert_eq!(state.prompt_complete(complete), 0);
+    assert_eq!(state.prompt_input(), "quit!xyz");
+}
+
+#[test]
+fn test_list_prompt_filters_and_selects() {
+    let mut state = EditorState::new();
+    state.set_mode(EditorMode::Normal);
+    let kind = PromptKind::PluginPicker {
+        plugin: "files".to_string(),
+        id: "recent".to_string(),
+    };
+    let items = vec!["main.rs".to_string(), "lib.rs".to_string(), "Cargo.toml".to_string()];
+    state.enter_list_prompt(kind.clone(), "Open: ".to_string(), items);
+    assert_eq!(state.prompt_kind(), &kind);
+
+    state.prompt_insert_char('r');
+    state.prompt_insert_char('s');
+    state.prompt_select_next();
+    assert_eq!(state.prompt_selection(), Some((1, "lib.rs".to_string())));
+
+    // Deleting input widens the filter again
+    state.prompt_delete_char();
+    state.prompt_delete_char();
+    assert_eq!(state.prompt_list().map(|list| list.len()), Some(3));
+
+    state.accept_prompt();
+    assert_eq!(state.mode(), EditorMode::Normal);
+    assert_eq!(state.prompt_kind(), &PromptKind::SaveAs);
+    assert!(state.prompt_list().is_none());
+}
+
+#[test]
+fn test_plain_prompt_has_no_list() {
+    let mut state = EditorState::new();
+    state.enter_list_prompt(PromptKind::SaveAs, "Pick: ".to_string(), vec!["a".to_string()]);
+    state.cancel_prompt();
+    state.enter_prompt("Save as: ".to_string());
+
+    state.prompt_select_next();
+    assert!(state.prompt_list().is_none());
+    assert_eq!(state.prompt_selection(), None);
+}
+
+#[test]
+fn test_status_severity_is_explicit() {
+    let mut state = EditorState::new();
+    state.set_status(Severity::Error, "lint failed".to_string());
+    assert_eq!(state.status_message(), Some("lint failed"));
+    assert_eq!(state.status_severity(), Severity::Error);
+
+    // Prefixes are just text
+    state.set_status_message("Error: disk full".to_string());
+    assert_eq!(state.status_severity(), Severity::Success);
+
+    state.set_status(Severity::Warning, "trailing whitespace".to_string());
+    state.clear_status_message();
+    assert_eq!(state.status_message(), None);
+    assert_eq!(state.status_severity(), Severity::default());
+}
+
+#[test]
+fn test_status_segments_are_ordered_by_owner_and_key() {
+    let mut state = EditorState::new();
+    state.set_status_segment("lint", "warnings", Some("W:1".to_string()));
+    state.set_status_segment("git", "branch", Some("main".to_string()));
+    state.set_status_segment("git", "ahead", Some("+2".to_string()));
+    assert_eq!(state.status_segments().collect::<Vec<_>>(), vec!["+2", "main", "W:1"]);
+
+    state.set_status_segment("git", "ahead", Some("+3".to_string()));
+    state.set_status_segment("lint", "warnings", None);
+    assert_eq!(state.status_segments().collect::<Vec<_>>(), vec!["+3", "main"]);
+}
+
+#[test]
+fn test_read_only_default_false() {
+    let state = EditorState::new();
+    assert!(!state.is_read_only());
+}
+
+#[test]
+fn test_set_read_only() {
+    let mut state = EditorState::new();
+    state.set_read_only(true);
+    assert!(state.is_read_only());
+
+    state.set_read_only(false);
+    assert!(!state.is_read_only());
+}
+
+#[test]
+fn test_save_rejected_when_read_only() {
+    let mut state = EditorState::new();
+    state.set_file_path(&PathBuf::from("/nonexistent/readonly.txt"));
+    state.set_read_only(true);
+
+    let result = state.save();
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("read-only"));
+    // Read-only buffers never fall through to the filename prompt
+    assert_eq!(state.mode(), EditorMode::Insert);
+}
+
+#[test]
+fn test_save_as_rejected_when_read_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("copy.txt");
+    let mut state = EditorState::new();
+    state.set_read_only(true);
+
+    let result = state.save_as(&path);
+    assert!(result.unwrap_err().to_string().contains("read-only"));
+    assert!(!path.exists());
+    assert_eq!(state.buffer().file_path(), None);
+}
+
+#[test]
+fn test_sudo_save_request_is_taken_once() {
+    let mut state = EditorState::new();
+    assert!(!state.take_sudo_save_request());
+
+    state.request_sudo_save();
+    assert!(state.take_sudo_save_request());
+    assert!(!state.take_sudo_save_request());
+}
+
+#[test]
+fn test_save_with_sudo_requires_file_path() {
+    let mut state = EditorState::new();
+    let result = state.save_with_sudo();
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("no file name"));
+}
+
+#[test]
+fn test_editor_state_starts_with_one_buffer() {
+    let state = EditorState::new();
+    assert_eq!(state.buffer_count(), 1);
+    assert_eq!(state.active_buffer_index(), 0);
+}
+
+#[test]
+fn test_add_buffer_does_not_switch() {
+    let mut state = EditorState::new();
+    state.add_buffer(Buffer::from_str("second"), Position::origin());
+
+    assert_eq!(state.buffer_count(), 2);
+    assert_eq!(state.active_buffer_index(), 0);
+    assert_eq!(state.buffer().content(), "");
+}
+
+#[test]
+fn test_switch_buffer_remembers_cursor_positions() {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("first buffer");
+    state.add_buffer(Buffer::from_str("second\nbuffer"), Position::new(1, 3));
+
+    // The initial cursor of the new buffer is used on first activation
+    let cursor = state.next_buffer(Position::new(0, 6));
+    assert_eq!(state.active_buffer_index(), 1);
+    assert_eq!(cursor, Position::new(1, 3));
+    assert_eq!(state.buffer().content(), "second\nbuffer");
+
+    // Wraps around back to the first buffer and its saved cursor
+    let cursor = state.next_buffer(Position::new(0, 2));
+    assert_eq!(state.active_buffer_index(), 0);
+    assert_eq!(cursor, Position::new(0, 6));
+
+    let cursor = state.previous_buffer(cursor);
+    assert_eq!(state.active_buffer_index(), 1);
+    assert_eq!(cursor, Position::new(0, 2));
+}
+
+#[test]
+fn test_switch_buffer_out_of_range_is_ignored() {
+    let mut state = EditorState::new();
+    let cursor = state.switch_buffer(3, Position::new(0, 0));
+    assert_eq!(state.active_buffer_index(), 0);
+    assert_eq!(cursor, Position::origin());
+}
+
+#[test]
+fn test_add_buffer_clamps_cursor() {
+    let mut state = EditorState::new();
+    state.add_buffer(Buffer::from_str("ab\ncd"), Position::new(usize::MAX, 10));
+
+    let cursor = state.next_buffer(Position::origin());
+    assert_eq!(cursor, Position::new(1, 2));
+}
+
+#[test]
+fn test_read_only_is_per_buffer() {
+    let mut state = EditorState::new();
+    state.add_buffer(Buffer::new(), Position::origin());
+    state.set_read_only(true);
+
+    assert!(state.is_read_only());
+    state.next_buffer(Position::origin());
+    assert!(!state.is_read_only());
+}
+
+#[test]
+fn test_quit_warns_about_all_dirty_buffers() {
+    let mut state = EditorState::new();
+    state.handle_char_insert('a', Position::origin());
+    let mut other = Buffer::new();
+    other.insert_char('b', Position::origin());
+    state.add_buffer(other, Position::origin());
+
+    assert!(!state.request_quit());
+    assert!(state.status_message().unwrap().contains("2 buffers have unsaved changes"));
+    assert!(state.request_quit());
+}
+
+#[test]
+fn test_status_message_does_not_confirm_quit() {
+    let mut state = EditorState::new();
+    state.handle_char_insert('a', Position::origin());
+    state.set_status_message("Saved successfully".to_string());
+
+    assert!(!state.request_quit());
+    assert!(!state.should_quit());
+
+    // Cancelling the pending quit makes the next request warn again
+    state.cancel_quit();
+    assert!(!state.request_quit());
+    assert!(state.request_quit());
+}
+
+#[test]
+fn test_quit_checks_inactive_buffers() {
+    let mut state = EditorState::new();
+    let mut other = Buffer::new();
+    other.insert_char('b', Position::origin());
+    state.add_buffer(other, Position::origin());
+
+    assert!(!state.request_quit());
+    assert!(!state.should_quit());
+}
+
+#[test]
+fn test_pipe_mode_save_keeps_content_for_stdout() {
+    let mut state = EditorState::new();
+    state.set_pipe_output(true);
+    state.handle_char_insert('x', Position::origin());
+
+    assert!(state.save().unwrap());
+    assert!(!state.buffer().is_dirty());
+    assert_eq!(state.mode(), EditorMode::Insert);
+    assert!(state.status_message().unwrap().contains("standard output"));
+
+    // Later edits are only written if saved again
+    state.handle_char_insert('y', Position::new(0, 1));
+    assert_eq!(state.take_piped_content(), Some("x".to_string()));
+    assert_eq!(state.take_piped_content(), None);
+}
+
+#[test]
+fn test_pipe_mode_off_prompts_for_filename() {
+    let mut state = EditorState::new();
+    assert!(!state.is_pipe_output());
+
+    assert!(!state.save().unwrap());
+    assert_eq!(state.mode(), EditorMode::Prompt);
+    assert_eq!(state.take_piped_content(), None);
+}
+
+#[test]
+fn test_pipe_mode_named_buffer_saves_to_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("named.txt");
+    let mut state = EditorState::new();
+    state.set_file_path(&path);
+    state.set_pipe_output(true);
+    state.handle_char_insert('z', Position::origin());
+
+    assert!(state.save().unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "z");
+    assert_eq!(state.take_piped_content(), None);
+}
+
+#[test]
+fn test_set_settings() {
+    let mut state = EditorState::new();
+    assert_eq!(state.settings(), &crate::editor::EditorSettings::default());
+
+    let settings = crate::editor::EditorSettings {
+        tab_width: 2,
+        line_numbers: true,
+        ..Default::default()
+    };
+    state.set_settings(settings.clone());
+    assert_eq!(state.settings(), &settings);
+}
+
+#[test]
+fn test_save_applies_editorconfig() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join(".editorconfig"),
+        "root = true\n[*.txt]\nend_of_line = crlf\ninsert_final_newline = true\n",
+    )
+    .unwrap();
+    let path = dir.path().join("notes.txt");
+    let mut state = EditorState::new();
+    state.set_file_path(&path);
+    state.buffer_mut().set_content("a\nb");
+
+    assert!(state.save().unwrap());
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\r\nb\r\n");
+    assert_eq!(state.buffer().content(), "a\r\nb\r\n");
+    assert!(!state.buffer().is_dirty());
+}
@@ -0,0 +1,283 @@
+//! Unit tests for EditorConfig
+
+use std::fs;
+
+use tempfile::TempDir;
+
+use crate::editor::{EditorConfig, EditorSettings, IndentStyle};
+use crate::file_io::{LineEnding, SaveHooks};
+
+/// Writes `content` to `.editorconfig` in `dir` (relative to the temp dir)
+fn write_editorconfig(root: &TempDir, dir: &str, content: &str) {
+    let dir = root.path().join(dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(".editorconfig"), content).unwrap();
+}
+
+/// Returns the properties for `file` (relative to the temp dir)
+fn config_for(root: &TempDir, file: &str) -> EditorConfig {
+    EditorConfig::for_file(&root.path().join(file))
+}
+
+/// Returns `true` if a section with `glob` applies to `file`
+fn glob_matches(glob: &str, file: &str) -> bool {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", &format!("root = true\n[{}]\nindent_style = tab\n", glob));
+    config_for(&root, file).indent_style.is_some()
+}
+
+#[test]
+fn test_reads_all_properties() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(
+        &root,
+        "",
+        "root = true\n\n\
+         [*]\n\
+         indent_style = space\n\
+         indent_size = 2\n\
+         tab_width = 8\n\
+         end_of_line = crlf\n\
+         charset = utf-8-bom\n\
+         trim_trailing_whitespace = true\n\
+         insert_final_newline = false\n",
+    );
+
+    let config = config_for(&root, "main.rs");
+
+    assert_eq!(
+        config,
+        EditorConfig {
+            indent_style: Some(IndentStyle::Space),
+            indent_size: Some(2),
+            tab_width: Some(8),
+            end_of_line: Some(LineEnding::Crlf),
+            charset: Some("utf-8-bom".to_string()),
+            trim_trailing_whitespace: Some(true),
+            insert_final_newline: Some(false),
+        }
+    );
+}
+
+#[test]
+fn test_no_editorconfig_is_empty() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", "root = true\n");
+
+    assert_eq!(config_for(&root, "main.rs"), EditorConfig::default());
+}
+
+#[test]
+fn test_values_are_case_insensitive_and_comments_ignored() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(
+        &root,
+        "",
+        "# comment\nroot = TRUE\n; another\n[*]\nIndent_Style = Tab\nend_of_line = LF\n",
+    );
+
+    let config = config_for(&root, "a.txt");
+
+    assert_eq!(config.indent_style, Some(IndentStyle::Tab));
+    assert_eq!(config.end_of_line, Some(LineEnding::Lf));
+}
+
+#[test]
+fn test_nearer_file_and_later_section_win() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", "root = true\n[*]\nindent_size = 4\nend_of_line = lf\n");
+    write_editorconfig(
+        &root,
+        "sub",
+        "[*]\nindent_size = 3\n[*.py]\nindent_size = 2\n",
+    );
+
+    assert_eq!(config_for(&root, "a.py").indent_size, Some(4));
+    assert_eq!(config_for(&root, "sub/a.rs").indent_size, Some(3));
+    assert_eq!(config_for(&root, "sub/a.py").indent_size, Some(2));
+    // Properties not overridden are inherited from the outer file
+    assert_eq!(config_for(&root, "sub/a.py").end_of_line, Some(LineEnding::Lf));
+}
+
+#[test]
+fn test_root_stops_search() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", "[*]\nindent_style = tab\n");
+    write_editorconfig(&root, "project", "root = true\n[*]\nindent_size = 2\n");
+
+    let config = config_for(&root, "project/a.rs");
+
+    assert_eq!(config.indent_style, None);
+    assert_eq!(config.indent_size, Some(2));
+}
+
+#[test]
+fn test_unset_removes_property() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", "root = true\n[*]\nindent_size = 2\n[*.md]\nindent_size = unset\n");
+
+    assert_eq!(config_for(&root, "a.rs").indent_size, Some(2));
+    assert_eq!(config_for(&root, "a.md").indent_size, None);
+}
+
+#[test]
+fn test_indent_size_tab_uses_tab_width() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", "root = true\n[*]\nindent_size = tab\ntab_width = 6\n");
+
+    let config = config_for(&root, "a.c");
+
+    assert_eq!(config.indent_size, Some(6));
+    assert_eq!(config.tab_width, Some(6));
+}
+
+#[test]
+fn test_tab_width_defaults_to_indent_size() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(&root, "", "root = true\n[*]\nindent_size = 3\n");
+
+    assert_eq!(config_for(&root, "a.c").tab_width, Some(3));
+}
+
+#[test]
+fn test_invalid_values_are_ignored() {
+    let root = TempDir::new().unwrap();
+    write_editorconfig(
+        &root,
+        "",
+        "root = true\n[*]\nindent_style = both\nindent_size = wide\nend_of_line = nel\ninsert_final_newline = yes\n",
+    );
+
+    assert_eq!(config_for(&root, "a").indent_style, None);
+    assert_eq!(config_for(&root, "a").indent_size, None);
+    assert_eq!(config_for(&root, "a").end_of_line, None);
+    assert_eq!(config_for(&root, "a").insert_final_newline, None);
+}
+
+#[test]
+fn test_glob_star_and_basename() {
+    assert!(glob_matches("*", "a.rs"));
+    assert!(glob_matches("*.rs", "src/deep/a.rs"));
+    assert!(!glob_matches("*.rs", "a.rs.bak"));
+    assert!(glob_matches("Makefile", "sub/Makefile"));
+    assert!(glob_matches("a?c.txt", "abc.txt"));
+    assert!(!glob_matches("a?c.txt", "ac.txt"));
+}
+
+#[test]
+fn test_glob_with_slash_is_anchored() {
+    assert!(glob_matches("src/*.rs", "src/main.rs"));
+    assert!(glob_matches("/src/*.rs", "src/main.rs"));
+    assert!(!glob_matches("src/*.rs", "src/a/b.rs"));
+    assert!(!glob_matches("src/*.rs", "other/src/main.rs"));
+}
+
+#[test]
+fn test_glob_double_star() {
+    assert!(glob_matches("src/**.rs", "src/a/b/c.rs"));
+    assert!(glob_matches("lib/**/test.rs", "lib/test.rs"));
+    assert!(glob_matches("lib/**/test.rs", "lib/a/b/test.rs"));
+    assert!(!glob_matches("lib/**/test.rs", "lib/atest.rs"));
+}
+
+#[test]
+fn test_glob_classes() {
+    assert!(glob_matches("[mM]akefile", "makefile"));
+    assert!(glob_matches("[mM]akefile", "Makefile"));
+    assert!(glob_matches("file[0-9].txt", "file7.txt"));
+    assert!(!glob_matches("file[!0-9].txt", "file7.txt"));
+    assert!(glob_matches("file[!0-9].txt", "filex.txt"));
+}
+
+#[test]
+fn test_glob_braces() {
+    assert!(glob_matches("*.{js,ts}", "index.ts"));
+    assert!(glob_matches("*.{js,ts}", "index.js"));
+    assert!(!glob_matches("*.{js,ts}", "index.rs"));
+    assert!(glob_matches("{package.json,.travis.yml}", ".travis.yml"));
+    assert!(glob_matches("*.{c,{h,hpp}}", "a.hpp"));
+}
+
+#[test]
+fn test_glob_numeric_range() {
+    assert!(glob_matches("file{1..3}.txt", "file2.txt"));
+    assert!(!glob_matches("file{1..3}.txt", "file4.txt"));
+    assert!(glob_matches("v{-5..10}", "v-2"));
+    assert!(!glob_matches("v{1..3}", "va"));
+}
+
+#[test]
+fn test_glob_escape_and_unclosed() {
+    assert!(glob_matches("a\\*b", "a*b"));
+    assert!(!glob_matches("a\\*b", "axb"));
+    assert!(glob_matches("[abc", "[abc"));
+}
+
+#[test]
+fn test_apply_settings_uses_indent_width_for_style() {
+    let mut settings = EditorSettings::default();
+    let spaces = EditorConfig {
+        indent_style: Some(IndentStyle::Space),
+        indent_size: Some(2),
+        tab_width: Some(8),
+        ..EditorConfig::default()
+    };
+
+    assert_eq!(spaces.apply_settings(&mut settings), vec!["expand_tabs", "tab_width"]);
+    assert!(settings.expand_tabs);
+    assert_eq!(settings.tab_width, 2);
+
+    let tabs = EditorConfig {
+        indent_style: Some(IndentStyle::Tab),
+        ..spaces
+    };
+    tabs.apply_settings(&mut settings);
+    assert!(!settings.expand_tabs);
+    assert_eq!(settings.tab_width, 8);
+}
+
+#[test]
+fn test_apply_settings_ignores_out_of_range_width() {
+    let mut settings = EditorSettings::default();
+    let config = EditorConfig {
+        indent_size: Some(40),
+        ..EditorConfig::default()
+    };
+
+    assert!(config.apply_settings(&mut settings).is_empty());
+    assert_eq!(settings.tab_width, 4);
+}
+
+#[test]
+fn test_apply_save_hooks() {
+    let mut hooks = SaveHooks {
+        trim_trailing_whitespace: true,
+        ..SaveHooks::default()
+    };
+    let config = EditorConfig {
+        trim_trailing_whitespace: Some(false),
+        insert_final_newline: Some(true),
+        end_of_line: Some(LineEnding::Crlf),
+        charset: Some("utf-8".to_string()),
+        ..EditorConfig::default()
+    };
+
+    config.apply_save_hooks(&mut hooks);
+
+    assert!(!hooks.trim_trailing_whitespace);
+    assert!(hooks.ensure_final_newline);
+    assert_eq!(hooks.line_ending, Some(LineEnding::Crlf));
+    assert_eq!(hooks.byte_order_mark, Some(false));
+}
+
+#[test]
+fn test_apply_save_hooks_ignores_unsupported_charset() {
+    let mut hooks = SaveHooks::default();
+    EditorConfig {
+        charset: Some("latin1".to_string()),
+        ..EditorConfig::default()
+    }
+    .apply_save_hooks(&mut hooks);
+
+    assert_eq!(hooks, SaveHooks::default());
+}
@@ -0,0 +1,159 @@
+//! Unit tests for EventBus and the events EditorState publishes
+
+use std::fs;
+
+use crate::buffer::Position;
+use crate::editor::{EditorMode, EditorState, EventBus};
+use tempfile::TempDir;
+use termide_plugin_api::event::EditorEvent;
+use termide_plugin_api::input::EditorMode as ApiMode;
+
+#[test]
+fn test_bus_keeps_publication_order() {
+    let mut bus = EventBus::new();
+    bus.publish(EditorEvent::ConfigReloaded);
+    bus.publish(EditorEvent::BufferChanged { path: None });
+    assert_eq!(bus.len(), 2);
+
+    assert_eq!(
+        bus.drain(),
+        vec![
+            EditorEvent::ConfigReloaded,
+            EditorEvent::BufferChanged { path: None }
+        ]
+    );
+    assert!(bus.is_empty());
+}
+
+#[test]
+fn test_set_mode_publishes_only_changes() {
+    let mut state = EditorState::new();
+    state.set_mode(EditorMode::Insert);
+    assert!(state.take_events().is_empty());
+
+    state.set_mode(EditorMode::Normal);
+    assert_eq!(
+        state.take_events(),
+        vec![EditorEvent::ModeChanged {
+            from: ApiMode::Insert,
+            to: ApiMode::Normal
+        }]
+    );
+}
+
+#[test]
+fn test_prompts_publish_mode_changes() {
+    let mut state = EditorState::new();
+    state.set_mode(EditorMode::Normal);
+    state.take_events();
+
+    state.enter_prompt("Save as: ".to_string());
+    state.cancel_prompt();
+    assert_eq!(
+        state.take_events(),
+        vec![
+            EditorEvent::ModeChanged {
+                from: ApiMode::Normal,
+                to: ApiMode::Prompt
+            },
+            EditorEvent::ModeChanged {
+                from: ApiMode::Prompt,
+                to: ApiMode::Normal
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_undo_group_publishes_one_change_per_buffer() {
+    let mut state = EditorState::new();
+    state.begin_undo_group();
+    state.handle_char_insert('a', Position::origin());
+    state.begin_undo_group();
+    state.handle_char_insert('b', Position::new(0, 1));
+    state.end_undo_group();
+    assert!(state.take_events().is_empty());
+    state.end_undo_group();
+
+    assert_eq!(
+        state.take_events(),
+        vec![EditorEvent::BufferChanged { path: None }]
+    );
+}
+
+#[test]
+fn test_undo_group_without_edits_publishes_nothing() {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("text");
+    state.begin_undo_group();
+    state.end_undo_group();
+    assert!(state.take_events().is_empty());
+}
+
+#[test]
+fn test_undo_in_group_publishes_change() {
+    let mut state = EditorState::new();
+    state.handle_char_insert('a', Position::origin());
+    state.begin_undo_group();
+    state.buffer_mut().undo();
+    state.end_undo_group();
+    assert_eq!(
+        state.take_events(),
+        vec![EditorEvent::BufferChanged { path: None }]
+    );
+}
+
+#[test]
+fn test_open_and_save_publish_events() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.txt");
+    let second = dir.path().join("second.txt");
+    fs::write(&first, "one").unwrap();
+
+    let mut state = EditorState::from_file(&first).unwrap();
+    state.open_file(&second, Position::origin()).unwrap();
+    state.save().unwrap();
+
+    assert_eq!(
+        state.take_events(),
+        vec![
+            EditorEvent::BufferOpened {
+                path: first.clone()
+            },
+            EditorEvent::BufferOpened { path: second },
+            EditorEvent::BufferSaved { path: Some(first) },
+        ]
+    );
+}
+
+#[test]
+fn test_save_as_publishes_new_path() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("new.txt");
+    let mut state = EditorState::new();
+
+    state.save_as(&path).unwrap();
+    assert_eq!(
+        state.take_events(),
+        vec![EditorEvent::BufferSaved { path: Some(path) }]
+    );
+}
+
+#[test]
+fn test_failed_save_publishes_nothing() {
+    let dir = TempDir::new().unwrap();
+    let mut state = EditorState::new();
+    assert!(state.save_as(&dir.path().join("missing/new.txt")).is_err());
+    assert!(state.take_events().is_empty());
+}
+
+#[test]
+fn test_piped_save_has_no_path() {
+    let mut state = EditorState::new();
+    state.set_pipe_output(true);
+    assert!(state.save().unwrap());
+    assert_eq!(
+        state.take_events(),
+        vec![EditorEvent::BufferSaved { path: None }]
+    );
+}
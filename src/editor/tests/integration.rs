@@ -6,6 +6,8 @@ use tempfile::TempDir;
 
 use crate::buffer::Position;
 use crate::editor::EditorState;
+use crate::file_io::{BackupMode, SaveOptions};
+use termide_plugin_api::ui::Severity;
 
 #[test]
 fn test_from_file_existing() {
@@ -143,3 +145,120 @@ fn test_load_edit_save_preserves_content() {
     let content = fs::read_to_string(&path).unwrap();
     assert_eq!(content, "Line 1\nXLine 2\nLine 3");
 }
+
+#[test]
+fn test_save_with_hooks_updates_buffer() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("hooks.txt");
+
+    let mut state = EditorState::from_file(&path).unwrap();
+    let mut options = SaveOptions::default();
+    options.hooks.trim_trailing_whitespace = true;
+    options.hooks.ensure_final_newline = true;
+    state.set_save_options(options);
+
+    state.handle_char_insert('A', Position::new(0, 0));
+    state.handle_char_insert(' ', Position::new(0, 1));
+    state.save().unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "A\n");
+    assert_eq!(state.buffer().content(), "A\n");
+    assert!(!state.buffer().is_dirty());
+    assert_eq!(state.status_message(), Some("Saved successfully"));
+}
+
+#[test]
+fn test_save_with_backup_keeps_previous_version() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("backup.txt");
+    fs::write(&path, "old").unwrap();
+
+    let mut state = EditorState::from_file(&path).unwrap();
+    let mut options = SaveOptions::default();
+    options.backup.mode = BackupMode::Simple;
+    state.set_save_options(options);
+
+    state.handle_char_insert('X', Position::new(0, 0));
+    state.save().unwrap();
+
+    assert_eq!(fs::read_to_string(dir.path().join("backup.txt~")).unwrap(), "old");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Xold");
+}
+
+#[test]
+fn test_save_formatter_failure_reported_as_warning() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("format.rs");
+
+    let mut state = EditorState::from_file(&path).unwrap();
+    let mut options = SaveOptions::default();
+    options
+        .hooks
+        .formatters
+        .insert("rs".to_string(), vec!["termide-no-such-formatter".to_string()]);
+    state.set_save_options(options);
+
+    state.handle_char_insert('x', Position::new(0, 0));
+    state.save().unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "x");
+    assert!(!state.buffer().is_dirty());
+    assert!(state.status_message().is_some());
+    assert_eq!(state.status_severity(), Severity::Warning);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_with_sudo_uses_helper_command() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("protected.txt");
+    fs::write(&path, "old").unwrap();
+
+    let mut state = EditorState::from_file(&path).unwrap();
+    // Stand-in for `sudo tee`: the target path is passed as $0
+    state.set_save_options(SaveOptions {
+        sudo_command: vec!["sh".to_string(), "-c".to_string(), "cat > \"$0\"".to_string()],
+        ..Default::default()
+    });
+
+    state.handle_char_insert('X', Position::new(0, 0));
+    // Explicit sudo saves are allowed even for read-only buffers
+    state.set_read_only(true);
+    state.save_with_sudo().unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Xold");
+    assert!(!state.buffer().is_dirty());
+    assert_eq!(
+        state.status_message(),
+        Some("Saved successfully with elevated privileges")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_with_sudo_helper_failure_keeps_buffer_dirty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("protected.txt");
+
+    let mut state = EditorState::from_file(&path).unwrap();
+    state.set_save_options(SaveOptions {
+        sudo_command: vec!["false".to_string()],
+        ..Default::default()
+    });
+
+    state.handle_char_insert('X', Position::new(0, 0));
+    assert!(state.save_with_sudo().is_err());
+    assert!(state.buffer().is_dirty());
+    assert_eq!(state.buffer().content(), "X");
+}
+
+#[test]
+fn test_from_file_writable_is_not_read_only() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("writable.txt");
+    fs::write(&path, "content").unwrap();
+
+    let state = EditorState::from_file(&path).unwrap();
+    assert!(!state.is_read_only());
+    assert_eq!(state.status_message(), None);
+}
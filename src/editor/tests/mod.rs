@@ -3,8 +3,16 @@
 //! Tests are organized by struct:
 //! - editor_mode.rs: Tests for EditorMode enum
 //! - editor_state.rs: Tests for EditorState struct
+//! - editorconfig.rs: Tests for EditorConfig parsing and glob matching
+//! - event_bus.rs: Tests for EventBus and the events EditorState publishes
+//! - prompt.rs: Tests for PromptList
+//! - settings.rs: Tests for EditorSettings struct
 //! - integration.rs: Integration tests for file operations
 
 mod editor_mode;
 mod editor_state;
+mod editorconfig;
+mod event_bus;
 mod integration;
+mod prompt;
+mod settings;
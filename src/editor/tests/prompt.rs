@@ -0,0 +1,100 @@
+//! Unit tests for PromptList
+
+use crate::editor::PromptList;
+
+fn items() -> Vec<String> {
+    vec!["Cargo.toml".to_string(), "src/main.rs".to_string(), "src/lib.rs".to_string()]
+}
+
+#[test]
+fn test_new_list_matches_everything() {
+    let list = PromptList::new(items());
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.selected(), Some((0, "Cargo.toml")));
+}
+
+#[test]
+fn test_filter_ignores_case_and_keeps_indices() {
+    let mut list = PromptList::new(items());
+    list.filter("SRC/");
+    assert_eq!(list.matches().collect::<Vec<_>>(), vec![(1, "src/main.rs"), (2, "src/lib.rs")]);
+
+    list.filter("toml");
+    assert_eq!(list.selected(), Some((0, "Cargo.toml")));
+}
+
+#[test]
+fn test_filter_resets_highlight() {
+    let mut list = PromptList::new(items());
+    list.select_next();
+    list.select_next();
+    list.filter("rs");
+    assert_eq!(list.selected_position(), Some(0));
+}
+
+#[test]
+fn test_selection_wraps_around() {
+    let mut list = PromptList::new(items());
+    list.select_previous();
+    assert_eq!(list.selected(), Some((2, "src/lib.rs")));
+    list.select_next();
+    assert_eq!(list.selected(), Some((0, "Cargo.toml")));
+}
+
+#[test]
+fn test_empty_list_has_no_selection() {
+    let mut list = PromptList::new(items());
+    list.filter("xyz");
+    list.select_next();
+    assert!(list.is_empty());
+    assert_eq!(list.selected_position(), None);
+    assert_eq!(list.selected(), None);
+}
+
+#[test]
+fn test_filter_matches_characters_in_order() {
+    let mut list = PromptList::new(items());
+    list.filter("smr");
+    assert_eq!(list.matches().collect::<Vec<_>>(), vec![(1, "src/main.rs")]);
+
+    list.filter("rsm");
+    assert!(list.is_empty());
+}
+
+#[test]
+fn test_filter_ranks_best_match_first() {
+    let names = ["buffers", "save_else", "file.save_sudo", "file.save", "select.all"];
+    let mut list = PromptList::new(names.iter().map(|name| name.to_string()).collect());
+
+    // Word starts beat characters inside words
+    list.filter("fs");
+    assert_eq!(list.selected(), Some((2, "file.save_sudo")));
+    assert_eq!(list.matches().last(), Some((0, "buffers")));
+
+    // Consecutive characters beat gaps
+    list.filter("sel");
+    assert_eq!(list.selected(), Some((4, "select.all")));
+    assert_eq!(list.len(), 2);
+
+    // Equal scores keep the item order
+    list.filter("save");
+    assert_eq!(
+        list.matches().map(|(index, _)| index).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+
+    list.filter("");
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.selected(), Some((0, "buffers")));
+}
+
+#[test]
+fn test_details_are_not_matched() {
+    let mut list = PromptList::new(items()).with_details(vec!["Ctrl+t".to_string(), String::new()]);
+    assert_eq!(list.detail(0), Some("Ctrl+t"));
+    assert_eq!(list.detail(1), None);
+    assert_eq!(list.detail(2), None);
+
+    list.filter("ctrl");
+    assert!(list.is_empty());
+}
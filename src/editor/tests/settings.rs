@@ -0,0 +1,63 @@
+//! Unit tests for EditorSettings
+
+use std::time::Duration;
+
+use crate::editor::{EditorMode, EditorSettings};
+
+#[test]
+fn test_default_settings_match_previous_behaviour() {
+    let settings = EditorSettings::default();
+    assert_eq!(settings.tab_width, 4);
+    assert!(!settings.expand_tabs);
+    assert_eq!(settings.default_mode, EditorMode::Insert);
+    assert_eq!(settings.scroll_margin, 0);
+    assert_eq!(settings.sequence_timeout, Duration::from_millis(1000));
+    assert_eq!(settings.theme, "dark");
+    assert!(!settings.line_numbers);
+}
+
+#[test]
+fn test_tab_text_inserts_tab_character_by_default() {
+    let settings = EditorSettings::default();
+    assert_eq!(settings.tab_text(0), "\t");
+    assert_eq!(settings.tab_text(7), "\t");
+}
+
+#[test]
+fn test_tab_text_expands_to_next_tab_stop() {
+    let settings = EditorSettings {
+        expand_tabs: true,
+        tab_width: 8,
+        ..EditorSettings::default()
+    };
+    assert_eq!(settings.tab_text(0), " ".repeat(8));
+    assert_eq!(settings.tab_text(3), " ".repeat(5));
+    assert_eq!(settings.tab_text(8), " ".repeat(8));
+}
+
+#[test]
+fn test_tab_text_with_zero_width_inserts_one_space() {
+    let settings = EditorSettings {
+        expand_tabs: true,
+        tab_width: 0,
+        ..EditorSettings::default()
+    };
+    assert_eq!(settings.tab_text(5), " ");
+}
+
+#[test]
+fn test_value_formats_every_key() {
+    let settings = EditorSettings {
+        default_mode: EditorMode::Normal,
+        sequence_timeout: Duration::from_millis(800),
+        ..EditorSettings::default()
+    };
+
+    for key in EditorSettings::KEYS {
+        assert!(settings.value(key).is_some(), "missing value for {}", key);
+    }
+    assert_eq!(settings.value("default_mode").as_deref(), Some("\"normal\""));
+    assert_eq!(settings.value("sequence_timeout_ms").as_deref(), Some("800"));
+    assert_eq!(settings.value("expand_tabs").as_deref(), Some("false"));
+    assert_eq!(settings.value("nonsense"), None);
+}
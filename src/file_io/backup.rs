@@ -0,0 +1,253 @@
+//! Backup creation for files that are about to be overwritten
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Strategy used to keep the previous version of a file on save
+///
+/// # Examples
+///
+/// ```toml
+/// [save.backup]
+/// mode = "timestamped"   # "none", "simple" or "timestamped"
+/// directory = "~/.local/state/termide/backups"
+/// keep = 10
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupMode {
+    /// No backup is made (default)
+    #[default]
+    None,
+    /// A single `file~` copy next to the file, replaced on every save
+    Simple,
+    /// A timestamped copy per save, pruned to the configured retention count
+    Timestamped,
+}
+
+/// Backup configuration applied before a file is overwritten
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::{BackupMode, BackupOptions};
+///
+/// let options = BackupOptions::default();
+/// assert_eq!(options.mode, BackupMode::None);
+/// assert_eq!(options.keep, 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BackupOptions {
+    /// Which backup strategy to use
+    pub mode: BackupMode,
+    /// Directory for backups (`~` is expanded). Defaults to the file's own directory.
+    pub directory: Option<PathBuf>,
+    /// Number of timestamped backups to retain per file (0 keeps all of them)
+    pub keep: usize,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            mode: BackupMode::None,
+            directory: None,
+            keep: 5,
+        }
+    }
+}
+
+/// Copies the current on-disk version of `path` according to `options`.
+///
+/// Nothing is copied when backups are disabled or when the file does not exist
+/// yet (there is no previous version to keep).
+///
+/// # Returns
+///
+/// * `Ok(Some(path))` - Path of the backup that was written
+/// * `Ok(None)` - No backup was necessary
+///
+/// # Errors
+///
+/// Returns an error if the backup directory cannot be created or the copy fails.
+/// Callers should not overwrite the original file in that case.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use termide::file_io::{create_backup, BackupMode, BackupOptions};
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let options = BackupOptions { mode: BackupMode::Simple, ..Default::default() };
+/// let backup = create_backup(Path::new("notes.txt"), &options)?;
+/// assert_eq!(backup.as_deref(), Some(Path::new("notes.txt~")));
+/// # Ok(())
+/// # }
+/// ```
+pub fn create_backup(path: &Path, options: &BackupOptions) -> Result<Option<PathBuf>> {
+    if options.mode == BackupMode::None || !path.is_file() {
+        return Ok(None);
+    }
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+
+    let (directory, prefix) = match &options.directory {
+        Some(dir) => {
+            // Backups from different directories share one folder, so the prefix
+            // encodes the full path (vim-style) to keep them apart
+            let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+            let encoded = absolute.to_string_lossy().replace(['/', '\\', ':'], "%");
+            (expand_tilde(dir), encoded)
+        }
+        None => (
+            path.parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."))
+                .to_path_buf(),
+            file_name.to_string(),
+        ),
+    };
+
+    fs::create_dir_all(&directory).context(format!(
+        "Failed to create backup directory '{}'.",
+        directory.display()
+    ))?;
+
+    let backup_path = match options.mode {
+        BackupMode::Simple => directory.join(format!("{}~", prefix)),
+        _ => unique_timestamped_path(&directory, &prefix, &format_timestamp(SystemTime::now())),
+    };
+
+    fs::copy(path, &backup_path).context(format!(
+        "Failed to back up '{}' to '{}'. The file was not saved.",
+        path.display(),
+        backup_path.display()
+    ))?;
+
+    if options.mode == BackupMode::Timestamped && options.keep > 0 {
+        prune_backups(&directory, &prefix, options.keep);
+    }
+
+    Ok(Some(backup_path))
+}
+
+/// Removes the oldest timestamped backups so that at most `keep` remain
+///
+/// Pruning is best-effort: a backup that cannot be removed is left in place.
+fn prune_backups(directory: &Path, prefix: &str, keep: usize) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    let mut backups: Vec<((String, u32), PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter_map(|p| {
+            let key = p
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(prefix))
+                .and_then(|rest| rest.strip_prefix('.'))
+                .and_then(|rest| rest.strip_suffix('~'))
+                .and_then(parse_backup_stamp)?;
+            Some((key, p))
+        })
+        .collect();
+
+    // Timestamps sort lexicographically in chronological order; the counter
+    // orders backups made within the same millisecond
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(keep);
+    for (_, old) in backups.into_iter().take(excess) {
+        let _ = fs::remove_file(old);
+    }
+}
+
+/// Returns a timestamped backup path that does not exist yet
+///
+/// Saves within the same millisecond share a timestamp, so later ones get a
+/// counter suffix (`file.<timestamp>.1~`) instead of replacing the earlier backup.
+fn unique_timestamped_path(directory: &Path, prefix: &str, timestamp: &str) -> PathBuf {
+    let first = directory.join(format!("{}.{}~", prefix, timestamp));
+    if !first.exists() {
+        return first;
+    }
+    (1u32..)
+        .map(|n| directory.join(format!("{}.{}.{}~", prefix, timestamp, n)))
+        .find(|path| !path.exists())
+        .expect("some counter is unused")
+}
+
+/// Splits a backup stamp (`<timestamp>` or `<timestamp>.<counter>`) into its
+/// timestamp and counter, or returns `None` if it is not one
+fn parse_backup_stamp(stamp: &str) -> Option<(String, u32)> {
+    let (timestamp, counter) = match stamp.get(23..) {
+        Some("") => (stamp, 0),
+        Some(rest) => (&stamp[..23], rest.strip_prefix('.')?.parse().ok()?),
+        None => return None,
+    };
+    is_timestamp(timestamp).then(|| (timestamp.to_string(), counter))
+}
+
+/// Formats a time as a sortable UTC timestamp, e.g. `2026-10-18T15-36-00.123`
+///
+/// Colons are avoided so the result is a valid file name on every platform.
+fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let secs_of_day = secs % 86_400;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}.{:03}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Returns `true` if `s` has the shape produced by `format_timestamp`
+fn is_timestamp(s: &str) -> bool {
+    s.len() == 23
+        && s.chars().enumerate().all(|(i, c)| match i {
+            4 | 7 | 13 | 16 => c == '-',
+            10 => c == 'T',
+            19 => c == '.',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+/// Converts days since the Unix epoch to a (year, month, day) civil date
+///
+/// Based on Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Expands a leading `~` to the user's home directory
+fn expand_tilde(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
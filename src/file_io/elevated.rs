@@ -0,0 +1,110 @@
+//! Writing files through an external (privileged) helper process
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Default helper used to write files the current user cannot write
+///
+/// The target path is appended as the final argument and the content is
+/// passed on standard input, so the command behaves like `sudo tee <file>`.
+pub const DEFAULT_SUDO_COMMAND: &[&str] = &["sudo", "tee"];
+
+/// Returns `true` if the current user can write to `path`
+///
+/// Existing files are checked by opening them for writing (without truncating).
+/// For files that do not exist yet, the parent directory must exist and must not
+/// be marked read-only.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use termide::file_io::is_writable;
+///
+/// if !is_writable(Path::new("/etc/hosts")) {
+///     println!("Opening read-only");
+/// }
+/// ```
+pub fn is_writable(path: &Path) -> bool {
+    if path.exists() {
+        return fs::OpenOptions::new().write(true).open(path).is_ok();
+    }
+
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::metadata(parent)
+        .map(|m| m.is_dir() && !m.permissions().readonly())
+        .unwrap_or(false)
+}
+
+/// Writes `content` to `path` by piping it to an external helper command
+///
+/// The helper is started as `command[0] command[1..] <path>` with `content` on its
+/// standard input. Its standard output is discarded; standard error and the
+/// terminal are inherited so it can prompt for a password. The caller is
+/// responsible for releasing the terminal while the helper runs.
+///
+/// Because the helper writes the existing file in place, ownership, permissions
+/// and links of the file are left untouched.
+///
+/// # Errors
+///
+/// Returns an error if the command is empty, cannot be started, or exits
+/// unsuccessfully (for example when authentication fails).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use termide::file_io::write_file_elevated;
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let command = vec!["sudo".to_string(), "tee".to_string()];
+/// write_file_elevated(Path::new("/etc/motd"), "Welcome\n", &command)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_file_elevated(path: &Path, content: &str, command: &[String]) -> Result<()> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("Sudo command is empty. Set 'save.sudo_command' in the config file.");
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .context(format!(
+            "Failed to start '{}' to save '{}'.",
+            program,
+            path.display()
+        ))?;
+
+    {
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        // A helper that exits early (e.g. failed authentication) closes the pipe;
+        // the exit status below reports that case
+        let _ = stdin.write_all(content.as_bytes());
+    }
+
+    let status = child
+        .wait()
+        .context(format!("Failed to wait for '{}'.", program))?;
+
+    if !status.success() {
+        bail!(
+            "'{}' failed to save '{}' ({}).",
+            program,
+            path.display(),
+            status
+        );
+    }
+
+    Ok(())
+}
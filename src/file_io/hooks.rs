@@ -0,0 +1,268 @@
+//! Content transformations applied to a buffer before it is written
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Line terminator written on save
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// `\n` (Unix)
+    Lf,
+    /// `\r\n` (Windows)
+    Crlf,
+    /// `\r` (classic Mac OS)
+    Cr,
+}
+
+impl LineEnding {
+    /// Returns the characters that end a line
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+}
+
+/// Hooks that rewrite file content on save
+///
+/// Hooks run in a fixed order: the external formatter first, then trailing
+/// whitespace trimming, the final newline check, line ending conversion and
+/// finally the byte order mark.
+///
+/// # Examples
+///
+/// ```toml
+/// [save]
+/// trim_trailing_whitespace = true
+/// ensure_final_newline = true
+/// line_ending = "lf"
+///
+/// [save.formatters]
+/// rs = ["rustfmt", "--edition", "2021"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SaveHooks {
+    /// Remove spaces and tabs at the end of every line
+    pub trim_trailing_whitespace: bool,
+    /// Make sure non-empty content ends with a newline
+    pub ensure_final_newline: bool,
+    /// Convert every line ending to this one; `None` keeps them as they are
+    pub line_ending: Option<LineEnding>,
+    /// Add (`true`) or remove (`false`) a UTF-8 byte order mark; `None` keeps
+    /// the content as it is
+    pub byte_order_mark: Option<bool>,
+    /// Formatter command lines keyed by file extension (without the dot)
+    pub formatters: HashMap<String, Vec<String>>,
+}
+
+impl SaveHooks {
+    /// Returns the formatter command configured for `path`, if any
+    pub fn formatter_for(&self, path: &Path) -> Option<&[String]> {
+        let extension = path.extension()?.to_str()?;
+        self.formatters
+            .get(extension)
+            .map(Vec::as_slice)
+            .filter(|command| !command.is_empty())
+    }
+}
+
+/// Removes trailing spaces and tabs from every line, keeping line endings intact
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::trim_trailing_whitespace;
+///
+/// assert_eq!(trim_trailing_whitespace("a  \nb\t\r\n"), "a\nb\r\n");
+/// ```
+pub fn trim_trailing_whitespace(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let (body, ending) = match line.strip_suffix("\r\n") {
+            Some(body) => (body, "\r\n"),
+            None => match line.strip_suffix('\n') {
+                Some(body) => (body, "\n"),
+                None => (line, ""),
+            },
+        };
+        result.push_str(body.trim_end_matches([' ', '\t']));
+        result.push_str(ending);
+    }
+    result
+}
+
+/// Appends a newline to non-empty content that does not already end with one
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::ensure_final_newline;
+///
+/// assert_eq!(ensure_final_newline("text"), "text\n");
+/// assert_eq!(ensure_final_newline("text\n"), "text\n");
+/// assert_eq!(ensure_final_newline(""), "");
+/// ```
+pub fn ensure_final_newline(content: &str) -> String {
+    if content.is_empty() || content.ends_with('\n') {
+        content.to_string()
+    } else {
+        format!("{}\n", content)
+    }
+}
+
+/// Converts every line ending in `content` (`\n`, `\r\n` or `\r`) to `ending`
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::{convert_line_endings, LineEnding};
+///
+/// assert_eq!(convert_line_endings("a\nb\r\nc\r", LineEnding::Crlf), "a\r\nb\r\nc\r\n");
+/// assert_eq!(convert_line_endings("a\r\nb", LineEnding::Lf), "a\nb");
+/// ```
+pub fn convert_line_endings(content: &str, ending: LineEnding) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                result.push_str(ending.as_str());
+            }
+            '\n' => result.push_str(ending.as_str()),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+/// Adds or removes the UTF-8 byte order mark at the start of `content`
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::set_byte_order_mark;
+///
+/// assert_eq!(set_byte_order_mark("text", true), "\u{feff}text");
+/// assert_eq!(set_byte_order_mark("\u{feff}text", true), "\u{feff}text");
+/// assert_eq!(set_byte_order_mark("\u{feff}text", false), "text");
+/// ```
+pub fn set_byte_order_mark(content: &str, enabled: bool) -> String {
+    let body = content.strip_prefix('\u{feff}').unwrap_or(content);
+    if enabled {
+        format!("\u{feff}{}", body)
+    } else {
+        body.to_string()
+    }
+}
+
+/// How long a formatter may run before it is killed
+const FORMATTER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pipes `content` through an external command and returns its standard output
+///
+/// The first element of `command` is the program, the rest are its arguments.
+/// A formatter that runs longer than 10 seconds is killed.
+///
+/// # Errors
+///
+/// Returns an error if the program cannot be started, times out, exits
+/// unsuccessfully (stderr is included in the message), or produces output
+/// that is not UTF-8.
+///
+/// # Examples
+///
+/// ```no_run
+/// use termide::file_io::run_formatter;
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let command = vec!["rustfmt".to_string()];
+/// let formatted = run_formatter(&command, "fn main(){}")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn run_formatter(command: &[String], content: &str) -> Result<String> {
+    run_formatter_with_timeout(command, content, FORMATTER_TIMEOUT)
+}
+
+/// Like [`run_formatter`], but kills the formatter after `timeout`
+///
+/// # Errors
+///
+/// See [`run_formatter`].
+pub fn run_formatter_with_timeout(
+    command: &[String],
+    content: &str,
+    timeout: Duration,
+) -> Result<String> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("Formatter command is empty.");
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!("Failed to start formatter '{}'.", program))?;
+
+    // Feed stdin and drain stdout/stderr from separate threads so a formatter
+    // that writes output before reading all of its input cannot deadlock
+    // against us, and so the deadline below can be checked while it runs
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = content.to_string();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let stdout = read_to_end_in_thread(child.stdout.take().expect("stdout is piped"));
+    let stderr = read_to_end_in_thread(child.stderr.take().expect("stderr is piped"));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        let status = child
+            .try_wait()
+            .context(format!("Failed to wait for formatter '{}'.", program))?;
+        if let Some(status) = status {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            // The I/O threads finish on their own once the pipes close
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("Formatter '{}' timed out after {:?}.", program, timeout);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        let detail = stderr.lines().next().unwrap_or("").trim();
+        if detail.is_empty() {
+            bail!("Formatter '{}' failed ({}).", program, status);
+        }
+        bail!("Formatter '{}' failed: {}", program, detail);
+    }
+
+    String::from_utf8(stdout).context(format!("Formatter '{}' produced invalid UTF-8.", program))
+}
+
+/// Reads `source` to the end on a new thread, returning whatever was read
+fn read_to_end_in_thread(mut source: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = source.read_to_end(&mut bytes);
+        bytes
+    })
+}
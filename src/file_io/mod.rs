@@ -1,7 +1,13 @@
 //! File I/O module - file system operations
 //!
 //! This module handles reading and writing text files with atomic writes
-//! and proper error handling.
+//! and proper error handling. Writes follow symlinks, keep hard links and
+//! preserve file ownership where possible. Saving can optionally back up the
+//! previous version of a file and run save hooks (whitespace trimming, final
+//! newline, external formatters) before the content is written. Files the user
+//! cannot write can be saved through an external helper such as `sudo tee`.
+//! Text can also be read from standard input and written to standard output
+//! when the editor is used in a pipeline.
 //!
 //! # Examples
 //!
@@ -21,11 +27,24 @@
 //! # }
 //! ```
 
+mod backup;
+mod elevated;
+mod hooks;
+mod pipe;
 mod read;
+mod save;
 mod write;
 
 #[cfg(test)]
 mod tests;
 
+pub use backup::{create_backup, BackupMode, BackupOptions};
+pub use elevated::{is_writable, write_file_elevated, DEFAULT_SUDO_COMMAND};
+pub use hooks::{
+    convert_line_endings, ensure_final_newline, run_formatter, run_formatter_with_timeout,
+    set_byte_order_mark, trim_trailing_whitespace, LineEnding, SaveHooks,
+};
+pub use pipe::{read_pipe, write_pipe};
 pub use read::read_file;
-pub use write::write_file;
+pub use save::{apply_save_hooks, save_with_options, SaveOptions};
+pub use write::{write_file, write_file_detailed, WriteStrategy};
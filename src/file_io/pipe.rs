@@ -0,0 +1,64 @@
+//! Reading text from standard input and writing it to standard output
+
+use anyhow::{Context, Result};
+use std::io::{Read, Write};
+
+/// Reads all of `reader` and returns it as a UTF-8 encoded string
+///
+/// Used for text piped into the editor (`cmd | termide -`). Reading stops at
+/// end of input, so the caller must only use this for pipes and files, never
+/// for an interactive terminal.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or the input is not valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::read_pipe;
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let content = read_pipe("piped text\n".as_bytes())?;
+/// assert_eq!(content, "piped text\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_pipe(mut reader: impl Read) -> Result<String> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .context("Failed to read from standard input.")?;
+
+    String::from_utf8(bytes)
+        .context("Standard input contains invalid UTF-8. Only UTF-8 encoded text is supported.")
+}
+
+/// Writes `content` to `writer` and flushes it
+///
+/// Used to hand the edited text to the next command in a pipeline
+/// (`cmd | termide - | other`).
+///
+/// # Errors
+///
+/// Returns an error if writing fails, e.g. because the reading end of the
+/// pipe was closed.
+///
+/// # Examples
+///
+/// ```
+/// use termide::file_io::write_pipe;
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let mut output = Vec::new();
+/// write_pipe(&mut output, "edited\n")?;
+/// assert_eq!(output, b"edited\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_pipe(mut writer: impl Write, content: &str) -> Result<()> {
+    writer
+        .write_all(content.as_bytes())
+        .and_then(|()| writer.flush())
+        .context("Failed to write to standard output.")
+}
@@ -0,0 +1,148 @@
+//! Saving with backups and save hooks
+
+use super::backup::{create_backup, BackupOptions};
+use super::elevated::DEFAULT_SUDO_COMMAND;
+use super::hooks::{
+    convert_line_endings, ensure_final_newline, run_formatter, set_byte_order_mark,
+    trim_trailing_whitespace, SaveHooks,
+};
+use super::write::write_file_detailed;
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Options controlling what happens when a buffer is saved
+///
+/// Corresponds to the `[save]` section of the configuration file.
+///
+/// # Examples
+///
+/// ```toml
+/// [save]
+/// trim_trailing_whitespace = true
+/// ensure_final_newline = true
+///
+/// [save.formatters]
+/// rs = ["rustfmt"]
+///
+/// [save.backup]
+/// mode = "simple"
+/// ```
+///
+/// The helper used to save files without write permission can be changed:
+///
+/// ```toml
+/// [save]
+/// sudo_command = ["doas", "tee"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SaveOptions {
+    /// Backup strategy for the previous version of the file
+    pub backup: BackupOptions,
+    /// Content hooks run before writing
+    #[serde(flatten)]
+    pub hooks: SaveHooks,
+    /// Helper used by "save with sudo"; the file path is appended as last argument
+    pub sudo_command: Vec<String>,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            backup: BackupOptions::default(),
+            hooks: SaveHooks::default(),
+            sudo_command: DEFAULT_SUDO_COMMAND.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Outcome of a successful [`save_with_options`] call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveReport {
+    /// The content that was written (after hooks ran)
+    pub content: String,
+    /// Path of the backup that was created, if any
+    pub backup: Option<PathBuf>,
+    /// Non-fatal problems, such as a failing formatter or lost file ownership
+    pub warnings: Vec<String>,
+}
+
+/// Applies save hooks to `content` for a file at `path`
+///
+/// A formatter failure never discards content: the unformatted text is kept and
+/// the failure is returned as a warning.
+///
+/// # Returns
+///
+/// The transformed content and a list of warnings.
+pub fn apply_save_hooks(path: &Path, content: &str, hooks: &SaveHooks) -> (String, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut result = content.to_string();
+
+    if let Some(command) = hooks.formatter_for(path) {
+        match run_formatter(command, &result) {
+            Ok(formatted) => result = formatted,
+            Err(e) => warnings.push(format!("{:#} Saved without formatting.", e)),
+        }
+    }
+
+    if hooks.trim_trailing_whitespace {
+        result = trim_trailing_whitespace(&result);
+    }
+
+    if hooks.ensure_final_newline {
+        result = ensure_final_newline(&result);
+    }
+
+    if let Some(ending) = hooks.line_ending {
+        result = convert_line_endings(&result, ending);
+    }
+
+    if let Some(enabled) = hooks.byte_order_mark {
+        result = set_byte_order_mark(&result, enabled);
+    }
+
+    (result, warnings)
+}
+
+/// Saves `content` to `path`, running save hooks and creating a backup first
+///
+/// The steps are:
+/// 1. Run save hooks (formatter, whitespace trimming, final newline, line
+///    endings, byte order mark)
+/// 2. Back up the existing file according to the backup options
+/// 3. Write the new content with [`write_file_detailed`]
+///
+/// # Errors
+///
+/// Returns an error if the backup or the write fails. The file on disk is left
+/// untouched when the backup cannot be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use termide::file_io::{save_with_options, SaveOptions};
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let mut options = SaveOptions::default();
+/// options.hooks.ensure_final_newline = true;
+///
+/// let report = save_with_options(Path::new("notes.txt"), "hello", &options)?;
+/// assert_eq!(report.content, "hello\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn save_with_options(path: &Path, content: &str, options: &SaveOptions) -> Result<SaveReport> {
+    let (content, mut warnings) = apply_save_hooks(path, content, &options.hooks);
+    let backup = create_backup(path, &options.backup)?;
+    let written = write_file_detailed(path, &content)?;
+    warnings.extend(written.warnings);
+
+    Ok(SaveReport {
+        content,
+        backup,
+        warnings,
+    })
+}
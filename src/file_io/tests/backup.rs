@@ -0,0 +1,169 @@
+//! Unit tests for backup creation
+
+use crate::file_io::{create_backup, BackupMode, BackupOptions};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_backup_disabled_by_default() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    fs::write(&path, "original").unwrap();
+
+    let backup = create_backup(&path, &BackupOptions::default()).unwrap();
+
+    assert!(backup.is_none());
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_simple_backup_next_to_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    fs::write(&path, "original").unwrap();
+
+    let options = BackupOptions {
+        mode: BackupMode::Simple,
+        ..Default::default()
+    };
+    let backup = create_backup(&path, &options).unwrap().unwrap();
+
+    assert_eq!(backup, dir.path().join("test.txt~"));
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "original");
+}
+
+#[test]
+fn test_simple_backup_is_replaced() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    let options = BackupOptions {
+        mode: BackupMode::Simple,
+        ..Default::default()
+    };
+
+    fs::write(&path, "first").unwrap();
+    create_backup(&path, &options).unwrap();
+    fs::write(&path, "second").unwrap();
+    let backup = create_backup(&path, &options).unwrap().unwrap();
+
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "second");
+}
+
+#[test]
+fn test_no_backup_for_new_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("new.txt");
+    let options = BackupOptions {
+        mode: BackupMode::Simple,
+        ..Default::default()
+    };
+
+    assert!(create_backup(&path, &options).unwrap().is_none());
+}
+
+#[test]
+fn test_timestamped_backup_in_directory() {
+    let dir = TempDir::new().unwrap();
+    let backups = dir.path().join("backups");
+    let path = dir.path().join("test.txt");
+    fs::write(&path, "original").unwrap();
+
+    let options = BackupOptions {
+        mode: BackupMode::Timestamped,
+        directory: Some(backups.clone()),
+        keep: 5,
+    };
+    let backup = create_backup(&path, &options).unwrap().unwrap();
+
+    assert!(backup.starts_with(&backups));
+    let name = backup.file_name().unwrap().to_str().unwrap();
+    assert!(name.contains("test.txt."));
+    assert!(name.ends_with('~'));
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "original");
+}
+
+#[test]
+fn test_timestamped_backup_retention() {
+    let dir = TempDir::new().unwrap();
+    let backups = dir.path().join("backups");
+    let path = dir.path().join("test.txt");
+    let options = BackupOptions {
+        mode: BackupMode::Timestamped,
+        directory: Some(backups.clone()),
+        keep: 2,
+    };
+
+    for i in 0..4 {
+        fs::write(&path, format!("version {}", i)).unwrap();
+        create_backup(&path, &options).unwrap();
+        // Timestamps have millisecond resolution
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    let mut remaining: Vec<String> = fs::read_dir(&backups)
+        .unwrap()
+        .map(|e| fs::read_to_string(e.unwrap().path()).unwrap())
+        .collect();
+    remaining.sort();
+
+    assert_eq!(remaining, vec!["version 2", "version 3"]);
+}
+
+#[test]
+fn test_timestamped_backups_in_same_millisecond_are_kept() {
+    let dir = TempDir::new().unwrap();
+    let backups = dir.path().join("backups");
+    let path = dir.path().join("test.txt");
+    let options = BackupOptions {
+        mode: BackupMode::Timestamped,
+        directory: Some(backups.clone()),
+        keep: 2,
+    };
+
+    // No pause between saves, so timestamps collide
+    let mut written = Vec::new();
+    for i in 0..3 {
+        fs::write(&path, format!("version {}", i)).unwrap();
+        written.push(create_backup(&path, &options).unwrap().unwrap());
+    }
+
+    written.dedup();
+    assert_eq!(written.len(), 3, "every save gets its own backup");
+    let mut remaining: Vec<String> = fs::read_dir(&backups)
+        .unwrap()
+        .map(|e| fs::read_to_string(e.unwrap().path()).unwrap())
+        .collect();
+    remaining.sort();
+    assert_eq!(remaining, vec!["version 1", "version 2"]);
+}
+
+#[test]
+fn test_retention_ignores_other_files() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    let unrelated = dir.path().join("test.txt.notes~");
+    fs::write(&unrelated, "keep me").unwrap();
+
+    let options = BackupOptions {
+        mode: BackupMode::Timestamped,
+        directory: None,
+        keep: 1,
+    };
+    for _ in 0..2 {
+        fs::write(&path, "content").unwrap();
+        create_backup(&path, &options).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    assert!(unrelated.exists());
+}
+
+#[test]
+fn test_backup_options_from_toml() {
+    let options: BackupOptions =
+        toml::from_str("mode = \"timestamped\"\ndirectory = \"/tmp/backups\"\nkeep = 3").unwrap();
+
+    assert_eq!(options.mode, BackupMode::Timestamped);
+    assert_eq!(options.directory.unwrap().to_str(), Some("/tmp/backups"));
+    assert_eq!(options.keep, 3);
+}
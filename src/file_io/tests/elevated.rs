@@ -0,0 +1,72 @@
+//! Unit tests for writability checks and writing through a helper process
+
+use crate::file_io::{is_writable, write_file_elevated, SaveOptions, DEFAULT_SUDO_COMMAND};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_is_writable_existing_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    fs::write(&path, "content").unwrap();
+
+    assert!(is_writable(&path));
+    // Checking must not truncate the file
+    assert_eq!(fs::read_to_string(&path).unwrap(), "content");
+}
+
+#[test]
+fn test_is_writable_new_file_in_existing_directory() {
+    let dir = TempDir::new().unwrap();
+    assert!(is_writable(&dir.path().join("new.txt")));
+}
+
+#[test]
+fn test_is_writable_missing_directory() {
+    let dir = TempDir::new().unwrap();
+    assert!(!is_writable(&dir.path().join("missing").join("new.txt")));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_file_elevated_passes_path_and_content() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("target.txt");
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "cat > \"$0\"".to_string(),
+    ];
+
+    write_file_elevated(&path, "Hello\n", &command).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Hello\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_file_elevated_reports_helper_failure() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("target.txt");
+    let command = vec!["false".to_string()];
+
+    let err = write_file_elevated(&path, "Hello", &command).unwrap_err();
+
+    assert!(err.to_string().contains("'false' failed to save"));
+}
+
+#[test]
+fn test_write_file_elevated_empty_command() {
+    let dir = TempDir::new().unwrap();
+    let err = write_file_elevated(&dir.path().join("x"), "", &[]).unwrap_err();
+    assert!(err.to_string().contains("save.sudo_command"));
+}
+
+#[test]
+fn test_default_sudo_command() {
+    let options = SaveOptions::default();
+    assert_eq!(options.sudo_command, DEFAULT_SUDO_COMMAND);
+
+    let options: SaveOptions = toml::from_str("sudo_command = [\"doas\", \"tee\"]").unwrap();
+    assert_eq!(options.sudo_command, vec!["doas", "tee"]);
+}
@@ -0,0 +1,107 @@
+//! Unit tests for save hooks
+
+use crate::file_io::{
+    convert_line_endings, ensure_final_newline, run_formatter, run_formatter_with_timeout,
+    set_byte_order_mark, trim_trailing_whitespace, LineEnding, SaveHooks,
+};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_trim_trailing_whitespace() {
+    assert_eq!(trim_trailing_whitespace("a  \nb\t\nc"), "a\nb\nc");
+}
+
+#[test]
+fn test_trim_trailing_whitespace_keeps_crlf() {
+    assert_eq!(trim_trailing_whitespace("a \r\nb\r\n"), "a\r\nb\r\n");
+}
+
+#[test]
+fn test_trim_trailing_whitespace_keeps_leading() {
+    assert_eq!(
+        trim_trailing_whitespace("    indented   \n"),
+        "    indented\n"
+    );
+}
+
+#[test]
+fn test_ensure_final_newline() {
+    assert_eq!(ensure_final_newline("text"), "text\n");
+    assert_eq!(ensure_final_newline("text\n"), "text\n");
+    assert_eq!(ensure_final_newline(""), "");
+}
+
+#[test]
+fn test_formatter_for_extension() {
+    let mut hooks = SaveHooks::default();
+    hooks
+        .formatters
+        .insert("rs".to_string(), vec!["rustfmt".to_string()]);
+    hooks.formatters.insert("txt".to_string(), vec![]);
+
+    assert!(hooks.formatter_for(Path::new("main.rs")).is_some());
+    assert!(hooks.formatter_for(Path::new("notes.txt")).is_none());
+    assert!(hooks.formatter_for(Path::new("Makefile")).is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_formatter_pipes_content() {
+    let command = vec!["tr".to_string(), "a-z".to_string(), "A-Z".to_string()];
+    assert_eq!(run_formatter(&command, "hello\n").unwrap(), "HELLO\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_formatter_reports_failure() {
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "echo 'syntax error' >&2; exit 1".to_string(),
+    ];
+    let err = run_formatter(&command, "input").unwrap_err();
+    assert!(format!("{:#}", err).contains("syntax error"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_formatter_times_out() {
+    let command = vec!["sleep".to_string(), "30".to_string()];
+    let started = Instant::now();
+    let err =
+        run_formatter_with_timeout(&command, "input", Duration::from_millis(200)).unwrap_err();
+    assert!(format!("{:#}", err).contains("timed out"));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_run_formatter_missing_program() {
+    let command = vec!["termide-no-such-formatter".to_string()];
+    assert!(run_formatter(&command, "input").is_err());
+}
+
+#[test]
+fn test_convert_line_endings() {
+    let mixed = "a\nb\r\nc\rd";
+    assert_eq!(convert_line_endings(mixed, LineEnding::Lf), "a\nb\nc\nd");
+    assert_eq!(convert_line_endings(mixed, LineEnding::Crlf), "a\r\nb\r\nc\r\nd");
+    assert_eq!(convert_line_endings(mixed, LineEnding::Cr), "a\rb\rc\rd");
+    assert_eq!(convert_line_endings("\r\n\r\n", LineEnding::Lf), "\n\n");
+}
+
+#[test]
+fn test_set_byte_order_mark() {
+    assert_eq!(set_byte_order_mark("", true), "\u{feff}");
+    assert_eq!(set_byte_order_mark("text", false), "text");
+    assert_eq!(set_byte_order_mark("\u{feff}\u{feff}x", false), "\u{feff}x");
+}
+
+#[test]
+fn test_line_ending_from_toml() {
+    let hooks: SaveHooks = toml::from_str("line_ending = \"crlf\"\nbyte_order_mark = false").unwrap();
+    assert_eq!(hooks.line_ending, Some(LineEnding::Crlf));
+    assert_eq!(hooks.byte_order_mark, Some(false));
+
+    assert!(toml::from_str::<SaveHooks>("line_ending = \"dos\"").is_err());
+}
@@ -3,8 +3,18 @@
 //! Tests are organized by functionality:
 //! - read.rs: Tests for read_file function
 //! - write.rs: Tests for write_file function
+//! - backup.rs: Tests for backup creation and retention
+//! - elevated.rs: Tests for writability checks and helper-process writes
+//! - hooks.rs: Tests for save hooks and external formatters
+//! - pipe.rs: Tests for reading piped input and writing piped output
+//! - save.rs: Tests for saving with backups and hooks
 //! - integration.rs: Integration tests for read-write workflows
 
+mod backup;
+mod elevated;
+mod hooks;
 mod integration;
+mod pipe;
 mod read;
+mod save;
 mod write;
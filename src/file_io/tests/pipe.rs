@@ -0,0 +1,52 @@
+//! Unit tests for pipe reading and writing
+
+use crate::file_io::{read_pipe, write_pipe};
+use std::io::{self, Write};
+
+#[test]
+fn test_read_pipe_returns_all_input() {
+    let input = "line 1\nline 2\n";
+    assert_eq!(read_pipe(input.as_bytes()).unwrap(), input);
+}
+
+#[test]
+fn test_read_pipe_empty_input() {
+    assert_eq!(read_pipe(io::empty()).unwrap(), "");
+}
+
+#[test]
+fn test_read_pipe_rejects_invalid_utf8() {
+    let result = read_pipe(&[0x66, 0x6f, 0xff, 0xfe][..]);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("invalid UTF-8"));
+}
+
+#[test]
+fn test_write_pipe_writes_content() {
+    let mut output = Vec::new();
+    write_pipe(&mut output, "Hello 世界\n").unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "Hello 世界\n");
+}
+
+/// Writer whose reading end has gone away
+struct ClosedPipe;
+
+impl Write for ClosedPipe {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_pipe_reports_closed_pipe() {
+    let result = write_pipe(ClosedPipe, "text");
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Failed to write to standard output"));
+}
@@ -0,0 +1,117 @@
+//! Unit tests for saving with backups and hooks
+
+use crate::file_io::{save_with_options, BackupMode, LineEnding, SaveOptions};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_save_default_options_writes_content_unchanged() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+
+    let report = save_with_options(&path, "text  ", &SaveOptions::default()).unwrap();
+
+    assert_eq!(report.content, "text  ");
+    assert!(report.backup.is_none());
+    assert!(report.warnings.is_empty());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "text  ");
+}
+
+#[test]
+fn test_save_applies_hooks() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    let mut options = SaveOptions::default();
+    options.hooks.trim_trailing_whitespace = true;
+    options.hooks.ensure_final_newline = true;
+
+    let report = save_with_options(&path, "a  \nb\t", &options).unwrap();
+
+    assert_eq!(report.content, "a\nb\n");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+}
+
+#[test]
+fn test_save_creates_backup_of_previous_version() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    fs::write(&path, "old").unwrap();
+    let mut options = SaveOptions::default();
+    options.backup.mode = BackupMode::Simple;
+
+    let report = save_with_options(&path, "new", &options).unwrap();
+
+    let backup = report.backup.unwrap();
+    assert_eq!(fs::read_to_string(backup).unwrap(), "old");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+}
+
+#[test]
+fn test_save_formatter_failure_keeps_content() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.rs");
+    let mut options = SaveOptions::default();
+    options.hooks.formatters.insert(
+        "rs".to_string(),
+        vec!["termide-no-such-formatter".to_string()],
+    );
+
+    let report = save_with_options(&path, "fn main(){}", &options).unwrap();
+
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("termide-no-such-formatter"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "fn main(){}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_runs_formatter_before_other_hooks() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    let mut options = SaveOptions::default();
+    options.hooks.ensure_final_newline = true;
+    options.hooks.formatters.insert(
+        "txt".to_string(),
+        vec!["tr".to_string(), "a-z".to_string(), "A-Z".to_string()],
+    );
+
+    let report = save_with_options(&path, "hello", &options).unwrap();
+
+    assert_eq!(report.content, "HELLO\n");
+}
+
+#[test]
+fn test_save_converts_line_endings_after_other_hooks() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.txt");
+    let mut options = SaveOptions::default();
+    options.hooks.ensure_final_newline = true;
+    options.hooks.line_ending = Some(LineEnding::Crlf);
+    options.hooks.byte_order_mark = Some(true);
+
+    let report = save_with_options(&path, "a\nb", &options).unwrap();
+
+    assert_eq!(report.content, "\u{feff}a\r\nb\r\n");
+    assert_eq!(fs::read(&path).unwrap(), b"\xef\xbb\xbfa\r\nb\r\n");
+}
+
+#[test]
+fn test_save_options_from_toml() {
+    let options: SaveOptions = toml::from_str(
+        r#"
+        trim_trailing_whitespace = true
+
+        [formatters]
+        rs = ["rustfmt", "--edition", "2021"]
+
+        [backup]
+        mode = "simple"
+        "#,
+    )
+    .unwrap();
+
+    assert!(options.hooks.trim_trailing_whitespace);
+    assert!(!options.hooks.ensure_final_newline);
+    assert_eq!(options.hooks.formatters["rs"].len(), 3);
+    assert_eq!(options.backup.mode, BackupMode::Simple);
+}
@@ -1,7 +1,9 @@
 //! Unit tests for write_file function
 
-use crate::file_io::write_file;
+use crate::file_io::write::{write_file_with, TempFile};
+use crate::file_io::{write_file, write_file_detailed, WriteStrategy};
 use std::fs;
+use std::io::{self, Write};
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -201,3 +203,179 @@ fn test_write_atomic_behavior() {
     let content = fs::read_to_string(&path).unwrap();
     assert_eq!(content, "Updated");
 }
+
+#[cfg(unix)]
+#[test]
+fn test_write_through_symlink_updates_target() {
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("target.txt");
+    let link = dir.path().join("link.txt");
+    fs::write(&target, "Initial").unwrap();
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    let report = write_file_detailed(&link, "Updated").unwrap();
+
+    assert_eq!(report.target, target);
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "Updated");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_through_relative_symlink_chain() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    let target = dir.path().join("sub").join("target.txt");
+    fs::write(&target, "Initial").unwrap();
+    std::os::unix::fs::symlink("sub/target.txt", dir.path().join("first")).unwrap();
+    std::os::unix::fs::symlink("first", dir.path().join("second")).unwrap();
+
+    write_file(&dir.path().join("second"), "Updated").unwrap();
+
+    assert_eq!(fs::read_to_string(&target).unwrap(), "Updated");
+    assert!(fs::symlink_metadata(dir.path().join("first"))
+        .unwrap()
+        .file_type()
+        .is_symlink());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_through_dangling_symlink_creates_target() {
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("missing.txt");
+    let link = dir.path().join("link.txt");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    write_file(&link, "Created").unwrap();
+
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "Created");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_symlink_loop_is_error() {
+    let dir = TempDir::new().unwrap();
+    let a = dir.path().join("a");
+    let b = dir.path().join("b");
+    std::os::unix::fs::symlink(&b, &a).unwrap();
+    std::os::unix::fs::symlink(&a, &b).unwrap();
+
+    let result = write_file(&a, "Test");
+
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("symbolic links"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_preserves_hard_links() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("original.txt");
+    let other = dir.path().join("hardlink.txt");
+    fs::write(&path, "Initial").unwrap();
+    fs::hard_link(&path, &other).unwrap();
+    let inode = fs::metadata(&path).unwrap().ino();
+
+    let report = write_file_detailed(&path, "Updated").unwrap();
+
+    assert_eq!(report.strategy, WriteStrategy::InPlace);
+    assert_eq!(fs::metadata(&path).unwrap().ino(), inode);
+    assert_eq!(fs::read_to_string(&other).unwrap(), "Updated");
+}
+
+#[test]
+fn test_write_temp_file_unavailable_leaves_original() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("fallback.txt");
+    fs::write(&path, "Initial content that is longer").unwrap();
+
+    // Occupy the temporary file name with a directory so creating it fails
+    let temp_path = dir
+        .path()
+        .join(format!(".fallback.txt.tmp.{}", std::process::id()));
+    fs::create_dir(&temp_path).unwrap();
+
+    assert!(write_file_detailed(&path, "Updated").is_err());
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "Initial content that is longer"
+    );
+}
+
+/// Temporary file that fails like a full disk once anything is written
+struct FullDisk(fs::File);
+
+impl Write for FullDisk {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::StorageFull, "No space left on device"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TempFile for FullDisk {
+    fn sync_all(&self) -> io::Result<()> {
+        self.0.sync_all()
+    }
+}
+
+#[test]
+fn test_write_full_disk_leaves_original() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("full.txt");
+    fs::write(&path, "Original").unwrap();
+
+    let result = write_file_with(&path, "Updated", |temp| fs::File::create(temp).map(FullDisk));
+
+    let err = result.unwrap_err();
+    assert!(format!("{:#}", err).contains("No space left on device"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Original");
+    // The partial temporary file is cleaned up
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_write_new_file_uses_atomic_strategy() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("new.txt");
+
+    let report = write_file_detailed(&path, "Content").unwrap();
+
+    assert_eq!(report.strategy, WriteStrategy::Atomic);
+    assert_eq!(report.target, path);
+    assert!(report.warnings.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_preserves_ownership() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("owned.txt");
+    fs::write(&path, "Initial").unwrap();
+
+    // Handing a file to another user requires root
+    if std::os::unix::fs::chown(&path, Some(12345), Some(12345)).is_err() {
+        return;
+    }
+
+    let report = write_file_detailed(&path, "Updated").unwrap();
+
+    let metadata = fs::metadata(&path).unwrap();
+    assert_eq!(report.strategy, WriteStrategy::Atomic);
+    assert_eq!((metadata.uid(), metadata.gid()), (12345, 12345));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Updated");
+}
@@ -1,21 +1,51 @@
 //! File writing functionality with atomic operations
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use std::fs;
-use std::io::Write;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Maximum number of symlinks followed before giving up (matches Linux `MAXSYMLINKS`)
+const MAX_SYMLINK_DEPTH: usize = 40;
+
+/// How the content reached the disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStrategy {
+    /// Written to a temporary file that was renamed over the target
+    Atomic,
+    /// The existing file was truncated and rewritten in place
+    ///
+    /// Used for files with multiple hard links and when the atomic rename is
+    /// not possible, so that the file's inode (and its links/ownership) survive.
+    InPlace,
+}
+
+/// Details about a completed write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteReport {
+    /// The file that was actually written (symlinks resolved)
+    pub target: PathBuf,
+    /// How the content was written
+    pub strategy: WriteStrategy,
+    /// Non-fatal problems, such as ownership that could not be preserved
+    pub warnings: Vec<String>,
+}
 
 /// Writes content to a file using an atomic write strategy.
 ///
 /// This function uses a temporary file and rename strategy to ensure atomic writes,
-/// preventing data corruption if the write operation fails. File permissions are
-/// preserved when overwriting existing files.
+/// preventing data corruption if the write operation fails. File permissions and
+/// ownership are preserved when overwriting existing files, symlinks are followed
+/// so the link target is updated, and hard links are kept intact.
 ///
 /// The atomic write strategy:
 /// 1. Write content to a temporary file in the same directory
 /// 2. If write succeeds, rename the temporary file to the target path
 /// 3. The rename operation is atomic on POSIX systems
 ///
+/// See [`write_file_detailed`] for the fallbacks used when this is not possible.
+///
 /// # Arguments
 ///
 /// * `path` - Path to the file to write
@@ -47,24 +77,206 @@ use std::path::Path;
 /// # }
 /// ```
 pub fn write_file(path: &Path, content: &str) -> Result<()> {
-    // Get the parent directory for the temp file
-    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    write_file_detailed(path, content).map(|_| ())
+}
+
+/// Writes content to a file and reports how it was written.
+///
+/// Behaves like [`write_file`], with these rules for existing files:
+/// - Symlinks are resolved and the final target is written; the link is kept
+/// - Files with more than one hard link are truncated and rewritten in place,
+///   since renaming would detach this path from the other links
+/// - If the temporary file cannot be given the original owner or renamed over
+///   the target, the file is rewritten in place instead
+/// - If the temporary file cannot be created, written or synced (e.g. the disk
+///   is full), the error is returned and the existing file is left untouched;
+///   rewriting it in place would truncate it and most likely fail the same way
+/// - If ownership cannot be preserved at all, the file is still saved and a
+///   warning is returned in the report
+///
+/// # Errors
+///
+/// Returns an error if the content could not be written by any strategy.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use termide::file_io::{write_file_detailed, WriteStrategy};
+///
+/// # fn main() -> Result<(), anyhow::Error> {
+/// let report = write_file_detailed(Path::new("link-to-notes.txt"), "Hello")?;
+/// println!("Wrote {}", report.target.display());
+/// assert!(matches!(report.strategy, WriteStrategy::Atomic | WriteStrategy::InPlace));
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_file_detailed(path: &Path, content: &str) -> Result<WriteReport> {
+    write_file_with(path, content, |temp| fs::File::create(temp))
+}
+
+/// A file that the temporary copy is written to
+///
+/// Implemented by [`fs::File`]; tests substitute writers that fail.
+pub(crate) trait TempFile: Write {
+    /// Flushes the written data to disk
+    fn sync_all(&self) -> io::Result<()>;
+}
+
+impl TempFile for fs::File {
+    fn sync_all(&self) -> io::Result<()> {
+        fs::File::sync_all(self)
+    }
+}
+
+/// [`write_file_detailed`] with `create` opening the temporary file
+pub(crate) fn write_file_with<F: TempFile>(
+    path: &Path,
+    content: &str,
+    create: impl Fn(&Path) -> io::Result<F>,
+) -> Result<WriteReport> {
+    let target = resolve_symlinks(path)?;
+
+    // Get file metadata to preserve permissions and ownership (if file exists)
+    let existing_metadata = fs::metadata(&target).ok();
+
+    let mut report = WriteReport {
+        target: target.clone(),
+        strategy: WriteStrategy::Atomic,
+        warnings: Vec::new(),
+    };
+
+    let Some(metadata) = existing_metadata else {
+        // New file: nothing to preserve
+        let temp_path = write_temp_file(&target, content, &create)?;
+        finish_atomic(&temp_path, &target, None)?;
+        return Ok(report);
+    };
+
+    if link_count(&metadata) > 1 {
+        write_in_place(&target, content)?;
+        report.strategy = WriteStrategy::InPlace;
+        return Ok(report);
+    }
+
+    // Until the temporary file is complete the target is untouched, so its
+    // errors are returned as they are
+    let temp_path = write_temp_file(&target, content, &create)?;
+
+    match finish_atomic(&temp_path, &target, Some(&metadata)) {
+        Ok(()) => Ok(report),
+        Err(atomic_error) => match write_in_place(&target, content) {
+            Ok(()) => {
+                report.strategy = WriteStrategy::InPlace;
+                Ok(report)
+            }
+            Err(_) if atomic_error.is::<OwnershipError>() => {
+                // Neither preserving ownership nor writing in place is possible,
+                // save anyway rather than losing the user's changes
+                write_atomic_without_owner(&target, content, &metadata, &create)?;
+                report.warnings.push(format!(
+                    "Could not preserve the owner of '{}': {}",
+                    target.display(),
+                    atomic_error
+                ));
+                Ok(report)
+            }
+            Err(_) => Err(atomic_error),
+        },
+    }
+}
+
+/// Follows symlinks at `path` until a non-link (or missing) path is reached
+///
+/// Unlike `fs::canonicalize`, this works for dangling links so saving through a
+/// link to a file that does not exist yet creates the target.
+fn resolve_symlinks(path: &Path) -> Result<PathBuf> {
+    let mut current = path.to_path_buf();
+
+    for _ in 0..MAX_SYMLINK_DEPTH {
+        match fs::symlink_metadata(&current) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                let link = fs::read_link(&current)
+                    .context(format!("Failed to read symlink '{}'.", current.display()))?;
+                current = match current.parent() {
+                    Some(parent) if link.is_relative() => parent.join(link),
+                    _ => link,
+                };
+            }
+            _ => return Ok(current),
+        }
+    }
+
+    bail!(
+        "Failed to save file '{}': too many levels of symbolic links.",
+        path.display()
+    )
+}
+
+/// Error raised when the temporary file cannot be given the original owner
+#[derive(Debug, Error)]
+#[error("changing ownership failed ({0})")]
+struct OwnershipError(std::io::Error);
+
+/// Renames a complete temporary file over `path`, first copying permissions
+/// and ownership from `existing` when given
+///
+/// The temporary file is removed if this fails.
+fn finish_atomic(temp_path: &Path, path: &Path, existing: Option<&fs::Metadata>) -> Result<()> {
+    if let Some(metadata) = existing {
+        if let Err(e) = copy_metadata(temp_path, metadata) {
+            let _ = fs::remove_file(temp_path);
+            return Err(e);
+        }
+    }
+
+    rename_over(temp_path, path)
+}
+
+/// Writes via a temporary file and rename, copying only the permissions
+fn write_atomic_without_owner<F: TempFile>(
+    path: &Path,
+    content: &str,
+    existing: &fs::Metadata,
+    create: impl Fn(&Path) -> io::Result<F>,
+) -> Result<()> {
+    let temp_path = write_temp_file(path, content, create)?;
+
+    #[cfg(unix)]
+    if let Err(e) = fs::set_permissions(&temp_path, existing.permissions()) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(anyhow::Error::new(e).context(format!(
+            "Failed to set permissions on temporary file for '{}'.",
+            path.display()
+        )));
+    }
+    #[cfg(not(unix))]
+    let _ = existing;
 
-    // Get file metadata to preserve permissions (if file exists)
-    let existing_metadata = fs::metadata(path).ok();
+    rename_over(&temp_path, path)
+}
+
+/// Creates `.<name>.tmp.<pid>` next to `path` containing `content`
+fn write_temp_file<F: TempFile>(
+    path: &Path,
+    content: &str,
+    create: impl Fn(&Path) -> io::Result<F>,
+) -> Result<PathBuf> {
+    // Get the parent directory for the temp file
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
 
     // Create a temporary file in the same directory
     // Format: .<original_name>.tmp.<process_id>
-    let file_name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("file");
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
     let temp_name = format!(".{}.tmp.{}", file_name, std::process::id());
     let temp_path = parent.join(temp_name);
 
     // Write content to temporary file
-    {
-        let mut temp_file = fs::File::create(&temp_path).context(format!(
+    let result = (|| -> Result<()> {
+        let mut temp_file = create(&temp_path).context(format!(
             "Failed to create temporary file for '{}'. Check that you have write permission in the directory.",
             path.display()
         ))?;
@@ -78,25 +290,80 @@ pub fn write_file(path: &Path, content: &str) -> Result<()> {
         temp_file.sync_all().context(format!(
             "Failed to sync file data to disk for '{}'.",
             path.display()
-        ))?;
+        ))
+    })();
+
+    if result.is_err() && temp_path.exists() {
+        let _ = fs::remove_file(&temp_path);
     }
 
-    // Preserve file permissions if the original file existed
+    result.map(|()| temp_path)
+}
+
+/// Preserves permissions and (on Unix) ownership of the original file
+fn copy_metadata(temp_path: &Path, metadata: &fs::Metadata) -> Result<()> {
     #[cfg(unix)]
-    if let Some(metadata) = existing_metadata {
-        let permissions = metadata.permissions();
-        fs::set_permissions(&temp_path, permissions).context(format!(
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_metadata = fs::metadata(temp_path)?;
+        if temp_metadata.uid() != metadata.uid() || temp_metadata.gid() != metadata.gid() {
+            std::os::unix::fs::chown(temp_path, Some(metadata.uid()), Some(metadata.gid()))
+                .map_err(OwnershipError)?;
+        }
+
+        // Set permissions after chown, which may clear setuid/setgid bits
+        fs::set_permissions(temp_path, metadata.permissions()).context(format!(
             "Failed to set permissions on temporary file for '{}'.",
-            path.display()
+            temp_path.display()
         ))?;
     }
+    #[cfg(not(unix))]
+    let _ = (temp_path, metadata);
 
-    // Atomically rename temporary file to target path
-    fs::rename(&temp_path, path).context(format!(
-        "Failed to save file '{}'. The temporary file has been left at '{}'.",
-        path.display(),
-        temp_path.display()
+    Ok(())
+}
+
+/// Atomically renames the temporary file to the target path
+fn rename_over(temp_path: &Path, path: &Path) -> Result<()> {
+    fs::rename(temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(temp_path);
+        anyhow::Error::new(e).context(format!("Failed to save file '{}'.", path.display()))
+    })
+}
+
+/// Truncates the existing file and writes `content` into the same inode
+fn write_in_place(path: &Path, content: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .context(format!(
+            "Failed to open '{}' for writing. Check that you have write permission.",
+            path.display()
+        ))?;
+
+    file.write_all(content.as_bytes()).context(format!(
+        "Failed to write content to '{}'. Check available disk space.",
+        path.display()
     ))?;
 
-    Ok(())
+    file.sync_all().context(format!(
+        "Failed to sync file data to disk for '{}'.",
+        path.display()
+    ))
+}
+
+/// Returns the number of hard links to the file (always 1 on non-Unix platforms)
+fn link_count(metadata: &fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.nlink()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        1
+    }
 }
@@ -11,6 +11,8 @@
 //! - **Normal mode bindings**: Mode switching ('i'), navigation
 //! - **Prompt mode bindings**: Prompt input handling
 //! - **Common navigation**: Arrow keys shared across multiple modes
+//! - **Presets**: Optional vim, emacs and vscode flavoured bindings layered over
+//!   the defaults, selected with [`KeymapOptions`]
 //!
 //! # Usage
 //!
@@ -28,8 +30,63 @@ use crate::input::keybinding::{
     BindingContext, KeyBinding, KeyPattern, KeySequence, Priority, PRIMARY_MODIFIER,
 };
 use crate::input::registry::{BindingError, KeyBindingRegistry};
-use crate::input::{Direction, EditorCommand};
+use crate::input::{Direction, EditorCommand, DEFAULT_MACRO_REGISTER};
 use crossterm::event::{KeyCode, KeyModifiers};
+use serde::Deserialize;
+use std::fmt;
+
+/// A bundle of bindings modelled on another editor
+///
+/// Preset bindings are registered at `Priority::Default` together with the
+/// built-in defaults, replacing any default binding they clash with, so user
+/// bindings still override them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    /// Only the built-in defaults
+    #[default]
+    Default,
+    /// `h j k l` style navigation in Normal mode
+    Vim,
+    /// `Ctrl+f`/`Ctrl+b` style navigation and `Ctrl+x` prefixed file commands
+    Emacs,
+    /// Shift-selection and clipboard shortcuts
+    Vscode,
+}
+
+impl fmt::Display for KeymapPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            KeymapPreset::Default => "default",
+            KeymapPreset::Vim => "vim",
+            KeymapPreset::Emacs => "emacs",
+            KeymapPreset::Vscode => "vscode",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Options for the built-in keymap, configured in the `[keymap]` config section
+///
+/// # Configuration
+///
+/// ```toml
+/// [keymap]
+/// preset = "emacs"         # default, vim, emacs or vscode
+/// disable_defaults = true  # start from an empty keymap
+/// ```
+///
+/// With `disable_defaults`, only the Prompt mode bindings (needed to answer
+/// the save prompt) and the preset bindings are registered; everything else
+/// must be bound in the `[[keybindings]]` list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeymapOptions {
+    /// Preset layered over the defaults
+    pub preset: KeymapPreset,
+    /// Leave out the built-in Insert and Normal mode bindings
+    pub disable_defaults: bool,
+}
 
 /// Registers all default keybindings into the provided registry
 ///
@@ -58,27 +115,200 @@ use crossterm::event::{KeyCode, KeyModifiers};
 /// assert!(registry.len() > 0);
 /// ```
 pub fn register_default_bindings(registry: &mut KeyBindingRegistry) -> Result<(), BindingError> {
-    // Register global bindings (active in Insert and Normal modes)
-    for binding in global_bindings() {
-        registry.register(binding)?;
-    }
+    register_keymap(registry, KeymapOptions::default())
+}
 
-    // Register Insert mode specific bindings
-    for binding in insert_mode_bindings() {
+/// Registers the default bindings adjusted by the `[keymap]` options
+///
+/// Preset bindings replace every default binding for the same sequence, or for
+/// a sequence that one of them starts with or extends, when the two are active
+/// in a common mode. This keeps presets such as emacs' `Ctrl+x Ctrl+s` from
+/// being made unreachable by a default binding for `Ctrl+x`.
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::bindings::{register_keymap, KeymapOptions, KeymapPreset};
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide::input::keybinding::KeyPattern;
+/// use termide::input::{Direction, EditorCommand};
+/// use termide::editor::EditorMode;
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// let mut registry = KeyBindingRegistry::default();
+/// let options = KeymapOptions { preset: KeymapPreset::Vim, ..KeymapOptions::default() };
+/// register_keymap(&mut registry, options).unwrap();
+///
+/// registry.add_to_sequence(KeyPattern::new(KeyCode::Char('j'), KeyModifiers::NONE));
+/// assert_eq!(
+///     registry.find_match(&EditorMode::Normal),
+///     Some(&EditorCommand::MoveCursor(Direction::Down))
+/// );
+/// ```
+pub fn register_keymap(
+    registry: &mut KeyBindingRegistry,
+    options: KeymapOptions,
+) -> Result<(), BindingError> {
+    for binding in keymap_bindings(options) {
         registry.register(binding)?;
     }
 
-    // Register Normal mode specific bindings
-    for binding in normal_mode_bindings() {
-        registry.register(binding)?;
-    }
+    Ok(())
+}
 
-    // Register Prompt mode specific bindings
-    for binding in prompt_mode_bindings() {
-        registry.register(binding)?;
+/// Returns the bindings registered by [`register_keymap`] for `options`
+pub fn keymap_bindings(options: KeymapOptions) -> Vec<KeyBinding> {
+    let mut defaults = Vec::new();
+    if !options.disable_defaults {
+        // Global bindings (active in Insert and Normal modes)
+        defaults.extend(global_bindings());
+        // Insert mode specific bindings
+        defaults.extend(insert_mode_bindings());
+        // Normal mode specific bindings
+        defaults.extend(normal_mode_bindings());
     }
+    // Prompt mode bindings are always needed to answer the save prompt
+    defaults.extend(prompt_mode_bindings());
 
-    Ok(())
+    let preset = preset_bindings(options.preset);
+    defaults.retain(|default| !preset.iter().any(|binding| clashes(default, binding)));
+    defaults.extend(preset);
+    defaults
+}
+
+/// Returns `true` if two bindings are active in a common mode and one sequence
+/// equals or starts with the other
+fn clashes(a: &KeyBinding, b: &KeyBinding) -> bool {
+    let related = a.sequence() == b.sequence()
+        || a.sequence().is_prefix_of(b.sequence())
+        || b.sequence().is_prefix_of(a.sequence());
+    related
+        && EditorMode::ALL
+            .iter()
+            .any(|mode| a.context().is_active(mode) && b.context().is_active(mode))
+}
+
+/// Keys and command of a preset binding
+type PresetEntry = (Vec<(KeyCode, KeyModifiers)>, EditorCommand);
+
+/// Returns the bindings of a keymap preset (none for [`KeymapPreset::Default`])
+///
+/// # Bindings
+///
+/// - **vim** (Normal mode): `h j k l` move, `0`/`$` line start/end, `x`/`X`
+///   delete forward/backward, `Ctrl+f`/`Ctrl+b` page down/up, `g t`/`g T`
+///   next/previous buffer
+/// - **emacs** (Insert and Normal mode): `Ctrl+f b n p` move, `Ctrl+a`/`Ctrl+e`
+///   line start/end, `Ctrl+d` delete forward, `Ctrl+v`/`Alt+v` page down/up,
+///   `Ctrl+x Ctrl+s` save, `Ctrl+x Ctrl+c` quit, `Ctrl+x Right`/`Ctrl+x Left`
+///   next/previous buffer
+/// - **vscode** (Insert and Normal mode): `Shift+arrows`, `Shift+Home`/`Shift+End`
+///   select, `Ctrl+a` select all, `Ctrl+c`/`Ctrl+x`/`Ctrl+v` copy/cut/paste,
+///   `Ctrl+Tab`/`Ctrl+Shift+Tab` next/previous buffer
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::bindings::{preset_bindings, KeymapPreset};
+///
+/// assert!(preset_bindings(KeymapPreset::Default).is_empty());
+/// assert!(!preset_bindings(KeymapPreset::Emacs).is_empty());
+/// ```
+pub fn preset_bindings(preset: KeymapPreset) -> Vec<KeyBinding> {
+    let ctrl = KeyModifiers::CONTROL;
+    let none = KeyModifiers::NONE;
+    let shift = KeyModifiers::SHIFT;
+
+    let (context, entries): (BindingContext, Vec<PresetEntry>) =
+        match preset {
+            KeymapPreset::Default => return Vec::new(),
+            KeymapPreset::Vim => (
+                BindingContext::Mode(EditorMode::Normal),
+                vec![
+                    (vec![(KeyCode::Char('h'), none)], EditorCommand::MoveCursor(Direction::Left)),
+                    (vec![(KeyCode::Char('j'), none)], EditorCommand::MoveCursor(Direction::Down)),
+                    (vec![(KeyCode::Char('k'), none)], EditorCommand::MoveCursor(Direction::Up)),
+                    (vec![(KeyCode::Char('l'), none)], EditorCommand::MoveCursor(Direction::Right)),
+                    (vec![(KeyCode::Char('0'), none)], EditorCommand::MoveToLineStart),
+                    (vec![(KeyCode::Char('$'), none)], EditorCommand::MoveToLineEnd),
+                    (vec![(KeyCode::Char('x'), none)], EditorCommand::DeleteForward),
+                    (vec![(KeyCode::Char('X'), shift)], EditorCommand::DeleteChar),
+                    (vec![(KeyCode::Char('f'), ctrl)], EditorCommand::PageDown),
+                    (vec![(KeyCode::Char('b'), ctrl)], EditorCommand::PageUp),
+                    (
+                        vec![(KeyCode::Char('g'), none), (KeyCode::Char('t'), none)],
+                        EditorCommand::NextBuffer,
+                    ),
+                    (
+                        vec![(KeyCode::Char('g'), none), (KeyCode::Char('T'), shift)],
+                        EditorCommand::PreviousBuffer,
+                    ),
+                ],
+            ),
+            KeymapPreset::Emacs => (
+                BindingContext::Global,
+                vec![
+                    (vec![(KeyCode::Char('f'), ctrl)], EditorCommand::MoveCursor(Direction::Right)),
+                    (vec![(KeyCode::Char('b'), ctrl)], EditorCommand::MoveCursor(Direction::Left)),
+                    (vec![(KeyCode::Char('n'), ctrl)], EditorCommand::MoveCursor(Direction::Down)),
+                    (vec![(KeyCode::Char('p'), ctrl)], EditorCommand::MoveCursor(Direction::Up)),
+                    (vec![(KeyCode::Char('a'), ctrl)], EditorCommand::MoveToLineStart),
+                    (vec![(KeyCode::Char('e'), ctrl)], EditorCommand::MoveToLineEnd),
+                    (vec![(KeyCode::Char('d'), ctrl)], EditorCommand::DeleteForward),
+                    (vec![(KeyCode::Char('v'), ctrl)], EditorCommand::PageDown),
+                    (vec![(KeyCode::Char('v'), KeyModifiers::ALT)], EditorCommand::PageUp),
+                    (
+                        vec![(KeyCode::Char('x'), ctrl), (KeyCode::Char('s'), ctrl)],
+                        EditorCommand::Save,
+                    ),
+                    (
+                        vec![(KeyCode::Char('x'), ctrl), (KeyCode::Char('c'), ctrl)],
+                        EditorCommand::Quit,
+                    ),
+                    (
+                        vec![(KeyCode::Char('x'), ctrl), (KeyCode::Right, none)],
+                        EditorCommand::NextBuffer,
+                    ),
+                    (
+                        vec![(KeyCode::Char('x'), ctrl), (KeyCode::Left, none)],
+                        EditorCommand::PreviousBuffer,
+                    ),
+                ],
+            ),
+            KeymapPreset::Vscode => (
+                BindingContext::Global,
+                vec![
+                    (vec![(KeyCode::Left, shift)], EditorCommand::SelectLeft),
+                    (vec![(KeyCode::Right, shift)], EditorCommand::SelectRight),
+                    (vec![(KeyCode::Up, shift)], EditorCommand::SelectUp),
+                    (vec![(KeyCode::Down, shift)], EditorCommand::SelectDown),
+                    (vec![(KeyCode::Home, shift)], EditorCommand::SelectLineStart),
+                    (vec![(KeyCode::End, shift)], EditorCommand::SelectLineEnd),
+                    (vec![(KeyCode::Char('a'), PRIMARY_MODIFIER)], EditorCommand::SelectAll),
+                    (vec![(KeyCode::Char('c'), PRIMARY_MODIFIER)], EditorCommand::Copy),
+                    (vec![(KeyCode::Char('x'), PRIMARY_MODIFIER)], EditorCommand::Cut),
+                    (vec![(KeyCode::Char('v'), PRIMARY_MODIFIER)], EditorCommand::Paste),
+                    (vec![(KeyCode::Tab, ctrl)], EditorCommand::NextBuffer),
+                    (vec![(KeyCode::BackTab, ctrl | shift)], EditorCommand::PreviousBuffer),
+                ],
+            ),
+        };
+
+    entries
+        .into_iter()
+        .map(|(keys, command)| {
+            let patterns = keys
+                .into_iter()
+                .map(|(code, modifiers)| KeyPattern::new(code, modifiers))
+                .collect();
+            KeyBinding::new(
+                KeySequence::new(patterns).expect("preset sequences are not empty"),
+                command,
+                context.clone(),
+                Priority::Default,
+            )
+        })
+        .collect()
 }
 
 /// Returns global keybindings active in Insert and Normal modes
@@ -90,6 +320,15 @@ pub fn register_default_bindings(registry: &mut KeyBindingRegistry) -> Result<()
 ///
 /// - `Ctrl+S` / `Cmd+S` → Save file
 /// - `Ctrl+Q` / `Cmd+Q` → Quit editor
+/// - `Ctrl+PageDown` / `Cmd+PageDown` → Next buffer
+/// - `Ctrl+PageUp` / `Cmd+PageUp` → Previous buffer
+/// - `F1` → List keybindings
+/// - `Shift+F1` → Describe the next key sequence
+/// - `F2` / `Ctrl+Shift+P` → Open the command palette
+/// - `Ctrl+Z` / `Cmd+Z` → Undo
+/// - `Ctrl+Y` / `Cmd+Y` → Redo
+/// - `F3` → Start or stop recording a macro into register `q`
+/// - `F4` → Play the macro in register `q`
 ///
 /// # Examples
 ///
@@ -97,7 +336,7 @@ pub fn register_default_bindings(registry: &mut KeyBindingRegistry) -> Result<()
 /// use termide::input::bindings::global_bindings;
 ///
 /// let bindings = global_bindings();
-/// assert_eq!(bindings.len(), 4);
+/// assert_eq!(bindings.len(), 14);
 /// ```
 pub fn global_bindings() -> Vec<KeyBinding> {
     vec![
@@ -145,6 +384,84 @@ pub fn global_bindings() -> Vec<KeyBinding> {
             BindingContext::Global,
             Priority::Default,
         ),
+        // Switch buffers (Ctrl+PageDown / Ctrl+PageUp, Cmd on macOS)
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::PageDown, PRIMARY_MODIFIER)])
+                .expect("Ctrl+PageDown is valid"),
+            EditorCommand::NextBuffer,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::PageUp, PRIMARY_MODIFIER)])
+                .expect("Ctrl+PageUp is valid"),
+            EditorCommand::PreviousBuffer,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        // Keybinding introspection
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::F(1), KeyModifiers::NONE)])
+                .expect("F1 is valid"),
+            EditorCommand::ListKeybindings,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::F(1), KeyModifiers::SHIFT)])
+                .expect("Shift+F1 is valid"),
+            EditorCommand::DescribeKey,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        // Command palette
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::F(2), KeyModifiers::NONE)])
+                .expect("F2 is valid"),
+            EditorCommand::CommandPalette,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Char('P'), PRIMARY_MODIFIER)])
+                .expect("Ctrl+Shift+P is valid"),
+            EditorCommand::CommandPalette,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        // Undo and redo
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Char('z'), PRIMARY_MODIFIER)])
+                .expect("Ctrl+Z is valid"),
+            EditorCommand::Undo,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Char('y'), PRIMARY_MODIFIER)])
+                .expect("Ctrl+Y is valid"),
+            EditorCommand::Redo,
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        // Macros
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::F(3), KeyModifiers::NONE)])
+                .expect("F3 is valid"),
+            EditorCommand::ToggleMacroRecording(DEFAULT_MACRO_REGISTER),
+            BindingContext::Global,
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::F(4), KeyModifiers::NONE)])
+                .expect("F4 is valid"),
+            EditorCommand::PlayMacro {
+                register: DEFAULT_MACRO_REGISTER,
+                count: 1,
+            },
+            BindingContext::Global,
+            Priority::Default,
+        ),
     ]
 }
 
@@ -243,6 +560,7 @@ pub fn insert_mode_bindings() -> Vec<KeyBinding> {
 /// # Bindings
 ///
 /// - `i` → Switch to Insert mode
+/// - `:` → Open the command line
 /// - Arrow keys → Move cursor (shared with Insert mode)
 /// - `Delete` → Delete character at cursor (shared with Insert mode)
 /// - `Home` → Move to start of line (shared with Insert mode)
@@ -273,6 +591,15 @@ pub fn normal_mode_bindings() -> Vec<KeyBinding> {
         Priority::Default,
     ));
 
+    // ':' key - open the command line
+    bindings.push(KeyBinding::new(
+        KeySequence::new(vec![KeyPattern::new(KeyCode::Char(':'), KeyModifiers::NONE)])
+            .expect(": is valid"),
+        EditorCommand::CommandLine,
+        BindingContext::Mode(EditorMode::Normal),
+        Priority::Default,
+    ));
+
     // Arrow key navigation is already added via insert_mode_bindings
     // with Modes([Insert, Normal]) context, so we don't duplicate it here
     //
@@ -291,6 +618,8 @@ pub fn normal_mode_bindings() -> Vec<KeyBinding> {
 ///
 /// - Printable characters → Insert into prompt
 /// - `Backspace` → Delete character from prompt
+/// - `Down`/`Ctrl+N`, `Up`/`Ctrl+P` → Move the highlight in a prompt list
+/// - `Tab` → Complete the input of the command line and file name prompts
 /// - `Enter` → Accept prompt input
 /// - `Esc` → Cancel prompt
 ///
@@ -315,6 +644,43 @@ pub fn prompt_mode_bindings() -> Vec<KeyBinding> {
             BindingContext::Mode(EditorMode::Prompt),
             Priority::Default,
         ),
+        // Down/Ctrl+N and Up/Ctrl+P - move the highlight in a prompt list
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Down, KeyModifiers::NONE)])
+                .expect("Down is valid"),
+            EditorCommand::PromptNext,
+            BindingContext::Mode(EditorMode::Prompt),
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Char('n'), KeyModifiers::CONTROL)])
+                .expect("Ctrl+N is valid"),
+            EditorCommand::PromptNext,
+            BindingContext::Mode(EditorMode::Prompt),
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Up, KeyModifiers::NONE)])
+                .expect("Up is valid"),
+            EditorCommand::PromptPrevious,
+            BindingContext::Mode(EditorMode::Prompt),
+            Priority::Default,
+        ),
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Char('p'), KeyModifiers::CONTROL)])
+                .expect("Ctrl+P is valid"),
+            EditorCommand::PromptPrevious,
+            BindingContext::Mode(EditorMode::Prompt),
+            Priority::Default,
+        ),
+        // Tab - complete the prompt input
+        KeyBinding::new(
+            KeySequence::new(vec![KeyPattern::new(KeyCode::Tab, KeyModifiers::NONE)])
+                .expect("Tab is valid"),
+            EditorCommand::PromptComplete,
+            BindingContext::Mode(EditorMode::Prompt),
+            Priority::Default,
+        ),
         // Enter - accept prompt input
         KeyBinding::new(
             KeySequence::new(vec![KeyPattern::new(KeyCode::Enter, KeyModifiers::NONE)])
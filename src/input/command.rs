@@ -1,7 +1,10 @@
 //! Editor command enumeration
 
 use crate::editor::EditorMode;
+use crate::ui::Theme;
+use super::user_commands::is_valid_user_command_name;
 use super::Direction;
+use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -18,9 +21,12 @@ use thiserror::Error;
 /// - [`DeleteChar`](Self::DeleteChar) - Delete character before cursor (Backspace)
 /// - [`DeleteForward`](Self::DeleteForward) - Delete character at cursor (Delete key)
 /// - [`InsertTab`](Self::InsertTab) - Insert tab character or spaces
+/// - [`InsertText`](Self::InsertText) - Insert a snippet (parameterized)
 ///
 /// ## Navigation Commands
 /// - [`MoveCursor`](Self::MoveCursor) - Move cursor in cardinal directions
+/// - [`MoveCursorBy`](Self::MoveCursorBy) - Move cursor several steps (parameterized)
+/// - [`GotoLine`](Self::GotoLine) - Jump to a line (parameterized)
 /// - [`MoveToLineStart`](Self::MoveToLineStart) - Move to start of line (Home)
 /// - [`MoveToLineEnd`](Self::MoveToLineEnd) - Move to end of line (End)
 /// - [`PageUp`](Self::PageUp) - Scroll up by viewport height
@@ -28,11 +34,34 @@ use thiserror::Error;
 ///
 /// ## File Operations
 /// - [`Save`](Self::Save) - Save current buffer to file (Ctrl+S)
+/// - [`SaveWithSudo`](Self::SaveWithSudo) - Save through an elevated helper process
+/// - [`ToggleReadOnly`](Self::ToggleReadOnly) - Toggle the buffer's read-only flag
+/// - [`NextBuffer`](Self::NextBuffer) - Switch to the next open buffer (Ctrl+PageDown)
+/// - [`PreviousBuffer`](Self::PreviousBuffer) - Switch to the previous open buffer (Ctrl+PageUp)
 /// - [`Quit`](Self::Quit) - Quit the editor (Ctrl+Q)
+/// - [`ForceQuit`](Self::ForceQuit) - Quit, discarding unsaved changes
+///
+/// ## Discovery
+/// - [`ListKeybindings`](Self::ListKeybindings) - List every keybinding (F1)
+/// - [`DescribeKey`](Self::DescribeKey) - Describe the next key sequence (Shift+F1)
+/// - [`CommandPalette`](Self::CommandPalette) - Pick a command to run (F2)
+/// - [`CommandLine`](Self::CommandLine) - Type an ex command such as `:w` (`:`)
 ///
 /// ## Mode Switching
 /// - [`ChangeMode`](Self::ChangeMode) - Switch between Insert/Normal/Prompt modes
 ///
+/// ## Composite Commands
+/// - [`Chain`](Self::Chain) - Run several commands in order
+/// - [`UserCommand`](Self::UserCommand) - Run a named command from the config
+///
+/// ## History and Macros
+/// - [`Undo`](Self::Undo) / [`Redo`](Self::Redo) - Undo and redo edits
+/// - [`ToggleMacroRecording`](Self::ToggleMacroRecording) - Record a macro into a register
+/// - [`PlayMacro`](Self::PlayMacro) - Replay a recorded macro
+///
+/// ## Appearance
+/// - [`SetTheme`](Self::SetTheme) - Switch color theme (parameterized)
+///
 /// ## Selection Commands (Not Yet Implemented)
 /// - [`SelectLeft`](Self::SelectLeft), [`SelectRight`](Self::SelectRight)
 /// - [`SelectUp`](Self::SelectUp), [`SelectDown`](Self::SelectDown)
@@ -45,6 +74,8 @@ use thiserror::Error;
 /// ## Prompt Commands
 /// - [`PromptInsertChar`](Self::PromptInsertChar) - Insert character in prompt
 /// - [`PromptDeleteChar`](Self::PromptDeleteChar) - Delete character from prompt
+/// - [`PromptNext`](Self::PromptNext) / [`PromptPrevious`](Self::PromptPrevious) - Move the highlight in a prompt list
+/// - [`PromptComplete`](Self::PromptComplete) - Complete the prompt input (Tab)
 /// - [`AcceptPrompt`](Self::AcceptPrompt) - Accept prompt input (Enter)
 /// - [`CancelPrompt`](Self::CancelPrompt) - Cancel prompt (Esc)
 ///
@@ -115,6 +146,21 @@ pub enum EditorCommand {
     /// boundaries correctly (e.g., moving up from first line stays at first line).
     MoveCursor(Direction),
 
+    /// Move the cursor several steps in a direction
+    ///
+    /// **Available in**: Any mode, when bound in the config file
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Bound as `command = "move.down"` with `args = { count = 10 }`. A count of 1
+    /// parses to [`MoveCursor`](Self::MoveCursor).
+    MoveCursorBy {
+        /// Direction to move in
+        direction: Direction,
+        /// Number of steps, at least 2
+        count: usize,
+    },
+
     /// Move cursor to the start of the current line (column 0)
     ///
     /// **Available in**: Insert mode, Normal mode
@@ -258,6 +304,158 @@ pub enum EditorCommand {
     /// Sets the buffer's dirty flag to false after successful save.
     Save,
 
+    /// Save the current buffer through an elevated helper process
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Writes the buffer by piping it to an external helper (`sudo tee <file>` by
+    /// default, configurable via `save.sudo_command`). The terminal is handed back
+    /// to the helper while it runs so it can ask for a password.
+    SaveWithSudo,
+
+    /// Toggle the read-only flag of the current buffer
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Read-only buffers reject edit commands. Files opened with `--readonly` or
+    /// without write permission start out read-only.
+    ToggleReadOnly,
+
+    /// Switch to the next open buffer
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: `Ctrl+PageDown` (or `Cmd+PageDown` on macOS)
+    ///
+    /// Buffers are opened from the command line (`termide a.txt b.txt`). Wraps
+    /// around after the last buffer; each buffer remembers its cursor position.
+    NextBuffer,
+
+    /// Switch to the previous open buffer
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: `Ctrl+PageUp` (or `Cmd+PageUp` on macOS)
+    ///
+    /// Wraps around to the last buffer when the first one is active.
+    PreviousBuffer,
+
+    /// List every keybinding in a read-only buffer
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: `F1`
+    ///
+    /// Shows each binding's sequence, command, context and priority, followed by
+    /// bindings shadowed by higher-priority ones and bindings made unreachable by
+    /// a shorter binding for their prefix.
+    ListKeybindings,
+
+    /// Describe what the next key sequence does in the current mode
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: `Shift+F1`
+    ///
+    /// The keys pressed next are not executed; the status bar shows the command
+    /// they resolve to instead.
+    DescribeKey,
+
+    /// Open the command palette
+    ///
+    /// **Available in**: Insert mode, Normal mode (Global binding)
+    ///
+    /// **Default Keybinding**: `F2`, `Ctrl+Shift+P`
+    ///
+    /// Lists the built-in, user and plugin commands with the keys bound to
+    /// them, filtered as you type; accepting the prompt runs the highlighted
+    /// command.
+    CommandPalette,
+
+    /// Do nothing
+    ///
+    /// **Available in**: All modes
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Binding a sequence to `noop` (or using `unbind = true` in the config file)
+    /// disables it: the key is consumed without effect, hiding any lower-priority
+    /// binding for the same sequence.
+    Noop,
+
+    /// Insert a snippet of text at the cursor
+    ///
+    /// **Available in**: Any mode, when bound in the config file
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Bound as `command = "insert_text"` with `args = { text = "..." }`. The
+    /// cursor ends up after the inserted text.
+    InsertText(String),
+
+    /// Move the cursor to the start of a line (1-based)
+    ///
+    /// **Available in**: Any mode, when bound in the config file
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Bound as `command = "goto_line"` with `args = { line = 100 }`. Lines past
+    /// the end of the buffer go to the last line.
+    GotoLine(usize),
+
+    /// Switch to a color theme by name
+    ///
+    /// **Available in**: Any mode, when bound in the config file
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Bound as `command = "theme.set"` with `args = { name = "light" }`. The
+    /// theme applies until the config is reloaded.
+    SetTheme(String),
+
+    /// Undo the most recent edit
+    ///
+    /// **Available in**: Insert and Normal modes
+    ///
+    /// **Default Keybinding**: `Ctrl+Z`
+    Undo,
+
+    /// Redo the most recently undone edit
+    ///
+    /// **Available in**: Insert and Normal modes
+    ///
+    /// **Default Keybinding**: `Ctrl+Y`
+    Redo,
+
+    /// Start recording a macro into a register, or stop the current recording
+    ///
+    /// **Available in**: Insert and Normal modes
+    ///
+    /// **Default Keybinding**: `F3` (register `q`)
+    ///
+    /// Records the commands produced by key presses, not the raw keys. Bound as
+    /// `command = "macro.record"` with an optional `args = { register = "a" }`.
+    ToggleMacroRecording(char),
+
+    /// Play the macro in a register
+    ///
+    /// **Available in**: Insert and Normal modes
+    ///
+    /// **Default Keybinding**: `F4` (register `q`)
+    ///
+    /// Bound as `command = "macro.play"` with optional `register` and `count`
+    /// arguments. All repetitions together form a single undo step.
+    PlayMacro {
+        /// Register holding the macro
+        register: char,
+        /// Number of times to play it
+        count: usize,
+    },
+
     /// Quit the editor
     ///
     /// **Available in**: Insert mode, Normal mode (Global binding)
@@ -268,6 +466,15 @@ pub enum EditorCommand {
     /// should prompt for confirmation before quitting to prevent data loss.
     Quit,
 
+    /// Quit the editor, discarding unsaved changes
+    ///
+    /// **Available in**: Any mode
+    ///
+    /// **Default Keybinding**: None (`:q!` on the command line)
+    ///
+    /// Quits at once, without the unsaved changes warning of [`Quit`](Self::Quit).
+    ForceQuit,
+
     /// Change the editor mode
     ///
     /// **Available in**: Mode-dependent
@@ -301,6 +508,26 @@ pub enum EditorCommand {
     /// user to correct mistakes while entering prompt input.
     PromptDeleteChar,
 
+    /// Highlight the next item of a prompt list
+    ///
+    /// **Available in**: Prompt mode
+    ///
+    /// **Default Keybindings**: `Down`, `Ctrl+N` (in Prompt mode)
+    ///
+    /// Moves the highlight in prompts that show a list of items, such as
+    /// plugin pickers. Wraps around at the end of the list.
+    PromptNext,
+
+    /// Highlight the previous item of a prompt list
+    ///
+    /// **Available in**: Prompt mode
+    ///
+    /// **Default Keybindings**: `Up`, `Ctrl+P` (in Prompt mode)
+    ///
+    /// Moves the highlight in prompts that show a list of items. Wraps around
+    /// at the start of the list.
+    PromptPrevious,
+
     /// Accept the prompt input (Enter key)
     ///
     /// **Available in**: Prompt mode
@@ -321,6 +548,27 @@ pub enum EditorCommand {
     /// mode without processing the prompt input.
     CancelPrompt,
 
+    /// Complete the prompt input (Tab key)
+    ///
+    /// **Available in**: Prompt mode
+    ///
+    /// **Default Keybinding**: `Tab` (in Prompt mode)
+    ///
+    /// Replaces the input of the command line and file name prompts with the
+    /// first completion; pressing Tab again cycles through the others.
+    PromptComplete,
+
+    /// Open the ex-style command line
+    ///
+    /// **Available in**: Normal mode
+    ///
+    /// **Default Keybinding**: `:` (in Normal mode)
+    ///
+    /// Opens a prompt for commands such as `:w`, `:q!`, `:e file`, `:42`,
+    /// `:%s/old/new/g` and `:set tab_width=2`; see
+    /// [`command_line`](crate::input::command_line) for the syntax.
+    CommandLine,
+
     /// Execute a plugin command
     ///
     /// **Available in**: Mode-dependent (based on plugin binding context)
@@ -329,6 +577,7 @@ pub enum EditorCommand {
     ///
     /// Plugin commands are namespaced to prevent conflicts between plugins.
     /// The plugin name and command name are separated by a dot (e.g., `rust_analyzer.format`).
+    /// The plugin host runs the command through the plugin's `Plugin::execute`.
     ///
     /// # Examples
     ///
@@ -352,6 +601,175 @@ pub enum EditorCommand {
         /// The command name within the plugin's namespace
         command_name: String,
     },
+
+    /// Run several commands in order, stopping at the first failure
+    ///
+    /// **Available in**: Any mode, when bound in the config file
+    ///
+    /// **Default Keybinding**: None
+    ///
+    /// Bound with a `commands` list instead of `command`, e.g.
+    /// `commands = ["file.save", "quit"]`.
+    Chain(Vec<EditorCommand>),
+
+    /// Run a named command defined in the `[commands]` config section
+    ///
+    /// **Available in**: Any mode, when bound in the config file
+    ///
+    /// **Format**: `user.name`
+    ///
+    /// The name is looked up when the command runs, see
+    /// [`UserCommands`](crate::input::user_commands::UserCommands).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::EditorCommand;
+    /// use std::str::FromStr;
+    ///
+    /// let cmd = EditorCommand::from_str("user.save_and_quit").unwrap();
+    /// assert_eq!(cmd, EditorCommand::UserCommand("save_and_quit".to_string()));
+    /// ```
+    UserCommand(String),
+}
+
+impl EditorCommand {
+    /// Returns `true` if this command modifies the buffer content
+    ///
+    /// Used to reject edits in read-only buffers. Commands that run other
+    /// commands (`Chain`, `PlayMacro`, `UserCommand`) are not edits
+    /// themselves: each command they run is checked in turn, so a chain stops
+    /// at its first edit. Plugin commands are not edits either, since plugins
+    /// edit through their buffer API, which rejects writes to read-only
+    /// buffers. Saving is not an edit; read-only buffers are never written,
+    /// see [`EditorState::save_as`](crate::editor::EditorState::save_as).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::{EditorCommand, Direction};
+    ///
+    /// assert!(EditorCommand::InsertChar('a').is_edit());
+    /// assert!(EditorCommand::Paste.is_edit());
+    /// assert!(!EditorCommand::MoveCursor(Direction::Up).is_edit());
+    /// assert!(!EditorCommand::Save.is_edit());
+    /// assert!(!EditorCommand::Chain(vec![EditorCommand::Paste]).is_edit());
+    /// ```
+    pub fn is_edit(&self) -> bool {
+        matches!(
+            self,
+            EditorCommand::InsertChar(_)
+                | EditorCommand::DeleteChar
+                | EditorCommand::DeleteForward
+                | EditorCommand::InsertTab
+                | EditorCommand::InsertText(_)
+                | EditorCommand::Undo
+                | EditorCommand::Redo
+                | EditorCommand::Cut
+                | EditorCommand::Paste
+        )
+    }
+}
+
+impl fmt::Display for EditorCommand {
+    /// Formats the command by its canonical config file name, e.g. `file.save`
+    ///
+    /// Character insertion has no config file name and is shown as
+    /// `insert_char 'x'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::{EditorCommand, Direction};
+    ///
+    /// assert_eq!(EditorCommand::Save.to_string(), "file.save");
+    /// assert_eq!(EditorCommand::MoveCursor(Direction::Up).to_string(), "move.up");
+    /// assert_eq!(EditorCommand::InsertChar('x').to_string(), "insert_char 'x'");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EditorCommand::InsertChar(ch) => return write!(f, "insert_char {:?}", ch),
+            EditorCommand::DeleteChar => "delete_char",
+            EditorCommand::DeleteForward => "delete_forward",
+            EditorCommand::MoveCursor(Direction::Up) => "move.up",
+            EditorCommand::MoveCursor(Direction::Down) => "move.down",
+            EditorCommand::MoveCursor(Direction::Left) => "move.left",
+            EditorCommand::MoveCursor(Direction::Right) => "move.right",
+            EditorCommand::MoveCursorBy { direction, count } => {
+                let name = EditorCommand::MoveCursor(*direction).to_string();
+                return write!(f, "{} {{ count = {} }}", name, count);
+            }
+            EditorCommand::InsertText(text) => {
+                return write!(f, "insert_text {{ text = {} }}", toml::Value::from(text.as_str()))
+            }
+            EditorCommand::GotoLine(line) => return write!(f, "goto_line {{ line = {} }}", line),
+            EditorCommand::Undo => "edit.undo",
+            EditorCommand::Redo => "edit.redo",
+            EditorCommand::ToggleMacroRecording(register) => {
+                return write!(f, "macro.record {{ register = \"{}\" }}", register)
+            }
+            EditorCommand::PlayMacro { register, count } => {
+                return write!(f, "macro.play {{ register = \"{}\", count = {} }}", register, count)
+            }
+            EditorCommand::SetTheme(name) => {
+                return write!(f, "theme.set {{ name = {} }}", toml::Value::from(name.as_str()))
+            }
+            EditorCommand::MoveToLineStart => "move.line_start",
+            EditorCommand::MoveToLineEnd => "move.line_end",
+            EditorCommand::PageUp => "page.up",
+            EditorCommand::PageDown => "page.down",
+            EditorCommand::InsertTab => "insert_tab",
+            EditorCommand::SelectLeft => "select.left",
+            EditorCommand::SelectRight => "select.right",
+            EditorCommand::SelectUp => "select.up",
+            EditorCommand::SelectDown => "select.down",
+            EditorCommand::SelectLineStart => "select.line_start",
+            EditorCommand::SelectLineEnd => "select.line_end",
+            EditorCommand::SelectAll => "select.all",
+            EditorCommand::Copy => "copy",
+            EditorCommand::Cut => "cut",
+            EditorCommand::Paste => "paste",
+            EditorCommand::Save => "file.save",
+            EditorCommand::SaveWithSudo => "file.save_sudo",
+            EditorCommand::ToggleReadOnly => "buffer.toggle_readonly",
+            EditorCommand::NextBuffer => "buffer.next",
+            EditorCommand::PreviousBuffer => "buffer.prev",
+            EditorCommand::ListKeybindings => "keybindings.list",
+            EditorCommand::DescribeKey => "keybindings.describe_key",
+            EditorCommand::CommandPalette => "palette.open",
+            EditorCommand::Noop => "noop",
+            EditorCommand::Quit => "quit",
+            EditorCommand::ForceQuit => "quit.force",
+            EditorCommand::CommandLine => "command_line.open",
+            EditorCommand::ChangeMode(EditorMode::Insert) => "mode.insert",
+            EditorCommand::ChangeMode(EditorMode::Normal) => "mode.normal",
+            EditorCommand::ChangeMode(EditorMode::Prompt) => "mode.prompt",
+            EditorCommand::ChangeMode(EditorMode::Plugin(mode)) => {
+                return write!(f, "mode.{}", mode.name())
+            }
+            EditorCommand::PromptInsertChar(ch) => {
+                return write!(f, "prompt.insert_char {:?}", ch)
+            }
+            EditorCommand::PromptDeleteChar => "prompt.delete_char",
+            EditorCommand::PromptNext => "prompt.next",
+            EditorCommand::PromptPrevious => "prompt.previous",
+            EditorCommand::PromptComplete => "prompt.complete",
+            EditorCommand::AcceptPrompt => "prompt.accept",
+            EditorCommand::CancelPrompt => "prompt.cancel",
+            EditorCommand::PluginCommand {
+                plugin_name,
+                command_name,
+            } => return write!(f, "{}.{}", plugin_name, command_name),
+            EditorCommand::Chain(commands) => {
+                let names: Vec<String> = commands.iter().map(ToString::to_string).collect();
+                return write!(f, "[{}]", names.join(", "));
+            }
+            EditorCommand::UserCommand(name) => {
+                return write!(f, "user.{}", name)
+            }
+        };
+        write!(f, "{}", name)
+    }
 }
 
 /// Error type for parsing editor commands from strings
@@ -389,14 +807,21 @@ impl FromStr for EditorCommand {
     /// # Format
     ///
     /// Commands use a consistent naming convention with dot notation for namespacing:
-    /// - File operations: `file.save`, `quit`
+    /// - File operations: `file.save`, `file.save_sudo`, `buffer.toggle_readonly`, `quit`,
+    ///   `quit.force`
     /// - Navigation: `move.up`, `move.down`, `move.left`, `move.right`
-    /// - Mode switching: `mode.insert`, `mode.normal`, `mode.prompt`
+    /// - Mode switching: `mode.insert`, `mode.normal`, `mode.prompt` (plugin modes,
+    ///   `mode.<plugin>.<mode>`, are parsed by
+    ///   [`KeyBindingRegistry::parse_command`](crate::input::registry::KeyBindingRegistry::parse_command))
     /// - Editing: `delete_char`
-    /// - Prompt operations: `prompt.insert_char`, `prompt.delete_char`, `prompt.accept`, `prompt.cancel`
+    /// - Prompt operations: `prompt.insert_char`, `prompt.delete_char`, `prompt.next`,
+    ///   `prompt.previous`, `prompt.complete`, `prompt.accept`, `prompt.cancel`
     ///
     /// Command names are case-insensitive for better user experience.
     ///
+    /// Commands that take arguments (`insert_text`, `goto_line`, `theme.set`) can
+    /// only be built with [`EditorCommand::with_args`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -435,74 +860,394 @@ impl FromStr for EditorCommand {
     /// - An unknown command name is provided
     /// - Invalid parameters are provided for parameterized commands
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let trimmed = s.trim().to_lowercase();
+        EditorCommand::with_args(s, &toml::Table::new())
+    }
+}
 
-        if trimmed.is_empty() {
-            return Err(CommandParseError::EmptyCommand);
-        }
+/// Canonical names of the commands that can run without arguments
+///
+/// Every name parses with [`EditorCommand::from_str`]. The command palette
+/// lists the commands by these names.
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::{EditorCommand, COMMAND_NAMES};
+///
+/// for name in COMMAND_NAMES {
+///     assert!(name.parse::<EditorCommand>().is_ok(), "{}", name);
+/// }
+/// ```
+pub const COMMAND_NAMES: &[&str] = &[
+    "delete_char",
+    "delete_forward",
+    "insert_tab",
+    "move.up",
+    "move.down",
+    "move.left",
+    "move.right",
+    "move.line_start",
+    "move.line_end",
+    "page.up",
+    "page.down",
+    "file.save",
+    "file.save_sudo",
+    "buffer.toggle_readonly",
+    "buffer.next",
+    "buffer.prev",
+    "quit",
+    "quit.force",
+    "keybindings.list",
+    "keybindings.describe_key",
+    "palette.open",
+    "command_line.open",
+    "mode.insert",
+    "mode.normal",
+    "mode.prompt",
+    "edit.undo",
+    "edit.redo",
+    "macro.record",
+    "macro.play",
+    "select.left",
+    "select.right",
+    "select.up",
+    "select.down",
+    "select.line_start",
+    "select.line_end",
+    "select.all",
+    "copy",
+    "cut",
+    "paste",
+    "prompt.delete_char",
+    "prompt.next",
+    "prompt.previous",
+    "prompt.complete",
+    "prompt.accept",
+    "prompt.cancel",
+    "noop",
+];
+
+/// Largest accepted `count` argument
+pub(crate) const MAX_COUNT: usize = 10_000;
 
-        match trimmed.as_str() {
-            // File operations
-            "file.save" | "save" => Ok(EditorCommand::Save),
-            "quit" | "exit" => Ok(EditorCommand::Quit),
-
-            // Editing operations
-            "delete_char" | "delete" | "backspace" => Ok(EditorCommand::DeleteChar),
-            "delete_forward" | "delete.forward" | "del" => Ok(EditorCommand::DeleteForward),
-
-            // Navigation commands
-            "move.up" | "move_up" | "up" => Ok(EditorCommand::MoveCursor(Direction::Up)),
-            "move.down" | "move_down" | "down" => Ok(EditorCommand::MoveCursor(Direction::Down)),
-            "move.left" | "move_left" | "left" => Ok(EditorCommand::MoveCursor(Direction::Left)),
-            "move.right" | "move_right" | "right" => Ok(EditorCommand::MoveCursor(Direction::Right)),
-            "move.line_start" | "move_line_start" | "line_start" | "home" => {
-                Ok(EditorCommand::MoveToLineStart)
+/// Register used by the macro commands when no `register` argument is given
+pub const DEFAULT_MACRO_REGISTER: char = 'q';
+
+impl EditorCommand {
+    /// Parses a command name together with the `args` table of a keybinding
+    ///
+    /// # Parameterized Commands
+    ///
+    /// - `move.up`/`move.down`/`move.left`/`move.right`: optional `count` (1 to 10000)
+    /// - `insert_text` (alias `snippet.insert`): required `text`
+    /// - `goto_line`: required `line` (1-based)
+    /// - `theme.set`: required `name` of a built-in theme
+    /// - `insert_char`/`prompt.insert_char`: required single-character `char`
+    /// - `macro.record`: optional `register` (a letter or digit, default `q`)
+    /// - `macro.play`: optional `register` and `count` (1 to 10000)
+    ///
+    /// Other commands take no arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::{EditorCommand, Direction};
+    ///
+    /// let args: toml::Table = toml::from_str("count = 10").unwrap();
+    /// assert_eq!(
+    ///     EditorCommand::with_args("move.down", &args).unwrap(),
+    ///     EditorCommand::MoveCursorBy { direction: Direction::Down, count: 10 }
+    /// );
+    ///
+    /// let args: toml::Table = toml::from_str("line = 0").unwrap();
+    /// assert!(EditorCommand::with_args("goto_line", &args).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `CommandParseError::InvalidParameter` for missing, mistyped,
+    /// out-of-range or unexpected arguments, besides the errors of
+    /// [`from_str`](std::str::FromStr::from_str).
+    pub fn with_args(name: &str, args: &toml::Table) -> Result<Self, CommandParseError> {
+        let trimmed = name.trim().to_lowercase();
+        let mut args = CommandArgs::new(&trimmed, args);
+
+        let command = match trimmed.as_str() {
+            "insert_text" | "snippet.insert" => EditorCommand::InsertText(args.string("text")?),
+            "goto_line" | "goto.line" => match args.integer("line", 1..=i64::MAX)? {
+                Some(line) => EditorCommand::GotoLine(line as usize),
+                None => return Err(args.invalid("line", "missing required argument".to_string())),
+            },
+            "theme.set" | "set_theme" => {
+                let theme = args.string("name")?;
+                if Theme::by_name(&theme).is_none() {
+                    return Err(args.invalid(
+                        "name",
+                        format!("unknown theme '{}' (available: {})", theme, Theme::NAMES.join(", ")),
+                    ));
+                }
+                EditorCommand::SetTheme(theme.to_lowercase())
             }
-            "move.line_end" | "move_line_end" | "line_end" | "end" => {
-                Ok(EditorCommand::MoveToLineEnd)
+            "insert_char" => EditorCommand::InsertChar(args.character("char")?),
+            "prompt.insert_char" => EditorCommand::PromptInsertChar(args.character("char")?),
+            "macro.record" | "record_macro" => EditorCommand::ToggleMacroRecording(args.register()?),
+            "macro.play" | "play_macro" => EditorCommand::PlayMacro {
+                register: args.register()?,
+                count: args.integer("count", 1..=MAX_COUNT as i64)?.unwrap_or(1) as usize,
+            },
+            _ => match parse_command_name(trimmed.clone())? {
+                EditorCommand::MoveCursor(direction) => match args.integer("count", 1..=MAX_COUNT as i64)? {
+                    Some(count) if count > 1 => EditorCommand::MoveCursorBy {
+                        direction,
+                        count: count as usize,
+                    },
+                    _ => EditorCommand::MoveCursor(direction),
+                },
+                command => command,
+            },
+        };
+
+        args.finish()?;
+        Ok(command)
+    }
+
+    /// Returns a command name and `args` table that parse back into this command
+    ///
+    /// Returns `None` for [`Chain`](Self::Chain), which has no single name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::{EditorCommand, Direction};
+    ///
+    /// let cmd = EditorCommand::MoveCursorBy { direction: Direction::Down, count: 3 };
+    /// let (name, args) = cmd.to_name_and_args().unwrap();
+    /// assert_eq!(name, "move.down");
+    /// assert_eq!(EditorCommand::with_args(&name, &args).unwrap(), cmd);
+    /// ```
+    pub fn to_name_and_args(&self) -> Option<(String, toml::Table)> {
+        let mut args = toml::Table::new();
+        let name = match self {
+            EditorCommand::InsertChar(ch) => {
+                args.insert("char".to_string(), ch.to_string().into());
+                "insert_char".to_string()
+            }
+            EditorCommand::PromptInsertChar(ch) => {
+                args.insert("char".to_string(), ch.to_string().into());
+                "prompt.insert_char".to_string()
+            }
+            EditorCommand::MoveCursorBy { direction, count } => {
+                args.insert("count".to_string(), (*count as i64).into());
+                EditorCommand::MoveCursor(*direction).to_string()
+            }
+            EditorCommand::InsertText(text) => {
+                args.insert("text".to_string(), text.as_str().into());
+                "insert_text".to_string()
             }
-            "page.up" | "page_up" | "pageup" => Ok(EditorCommand::PageUp),
-            "page.down" | "page_down" | "pagedown" => Ok(EditorCommand::PageDown),
-            "insert_tab" | "tab" => Ok(EditorCommand::InsertTab),
-
-            // Selection commands (future features - placeholders)
-            "select.left" | "select_left" => Ok(EditorCommand::SelectLeft),
-            "select.right" | "select_right" => Ok(EditorCommand::SelectRight),
-            "select.up" | "select_up" => Ok(EditorCommand::SelectUp),
-            "select.down" | "select_down" => Ok(EditorCommand::SelectDown),
-            "select.line_start" | "select_line_start" => Ok(EditorCommand::SelectLineStart),
-            "select.line_end" | "select_line_end" => Ok(EditorCommand::SelectLineEnd),
-            "select.all" | "select_all" => Ok(EditorCommand::SelectAll),
-
-            // Clipboard commands (future features - placeholders)
-            "copy" => Ok(EditorCommand::Copy),
-            "cut" => Ok(EditorCommand::Cut),
-            "paste" => Ok(EditorCommand::Paste),
-
-            // Mode switching commands
-            "mode.insert" | "insert_mode" | "insert" => {
-                Ok(EditorCommand::ChangeMode(EditorMode::Insert))
+            EditorCommand::GotoLine(line) => {
+                args.insert("line".to_string(), (*line as i64).into());
+                "goto_line".to_string()
             }
-            "mode.normal" | "normal_mode" | "normal" => {
-                Ok(EditorCommand::ChangeMode(EditorMode::Normal))
+            EditorCommand::SetTheme(name) => {
+                args.insert("name".to_string(), name.as_str().into());
+                "theme.set".to_string()
             }
-            "mode.prompt" | "prompt_mode" => {
-                Ok(EditorCommand::ChangeMode(EditorMode::Prompt))
+            EditorCommand::ToggleMacroRecording(register) => {
+                args.insert("register".to_string(), register.to_string().into());
+                "macro.record".to_string()
             }
+            EditorCommand::PlayMacro { register, count } => {
+                args.insert("register".to_string(), register.to_string().into());
+                args.insert("count".to_string(), (*count as i64).into());
+                "macro.play".to_string()
+            }
+            EditorCommand::Chain(_) => return None,
+            command => command.to_string(),
+        };
+        Some((name, args))
+    }
+}
+
+/// Typed access to the `args` table of a keybinding, tracking which keys were used
+struct CommandArgs<'a> {
+    command: &'a str,
+    args: &'a toml::Table,
+    used: Vec<&'static str>,
+}
+
+impl<'a> CommandArgs<'a> {
+    fn new(command: &'a str, args: &'a toml::Table) -> Self {
+        Self {
+            command,
+            args,
+            used: Vec::new(),
+        }
+    }
+
+    /// Builds an `InvalidParameter` error for `param`
+    fn invalid(&self, param: &str, reason: String) -> CommandParseError {
+        CommandParseError::InvalidParameter {
+            command: self.command.to_string(),
+            param: param.to_string(),
+            reason,
+        }
+    }
+
+    /// Returns the required string argument `param`
+    fn string(&mut self, param: &'static str) -> Result<String, CommandParseError> {
+        self.used.push(param);
+        match self.args.get(param) {
+            Some(toml::Value::String(value)) => Ok(value.clone()),
+            Some(other) => Err(self.invalid(param, format!("expected a string, got {}", other.type_str()))),
+            None => Err(self.invalid(param, "missing required argument".to_string())),
+        }
+    }
+
+    /// Returns the required single-character argument `param`
+    fn character(&mut self, param: &'static str) -> Result<char, CommandParseError> {
+        let value = self.string(param)?;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Ok(ch),
+            _ => Err(self.invalid(param, format!("expected a single character, got '{}'", value))),
+        }
+    }
 
-            // Prompt operations
-            "prompt.accept" | "accept_prompt" | "accept" => Ok(EditorCommand::AcceptPrompt),
-            "prompt.cancel" | "cancel_prompt" | "cancel" => Ok(EditorCommand::CancelPrompt),
-            "prompt.delete_char" | "prompt_delete" => Ok(EditorCommand::PromptDeleteChar),
+    /// Returns the optional macro `register` argument, `q` by default
+    fn register(&mut self) -> Result<char, CommandParseError> {
+        if !self.args.contains_key("register") {
+            self.used.push("register");
+            return Ok(DEFAULT_MACRO_REGISTER);
+        }
+        match self.character("register")? {
+            ch if ch.is_ascii_alphanumeric() => Ok(ch.to_ascii_lowercase()),
+            ch => Err(self.invalid("register", format!("expected a letter or digit, got '{}'", ch))),
+        }
+    }
 
-            // Unknown command - try parsing as plugin command if it contains a dot
-            _ => {
-                // Check if this looks like a plugin command (contains exactly one dot)
-                if trimmed.contains('.') {
-                    parse_plugin_command(&trimmed)
+    /// Returns the integer argument `param` if present, checked against `range`
+    fn integer(
+        &mut self,
+        param: &'static str,
+        range: std::ops::RangeInclusive<i64>,
+    ) -> Result<Option<i64>, CommandParseError> {
+        self.used.push(param);
+        match self.args.get(param) {
+            Some(toml::Value::Integer(value)) if range.contains(value) => Ok(Some(*value)),
+            Some(toml::Value::Integer(value)) => Err(self.invalid(
+                param,
+                if *range.end() == i64::MAX {
+                    format!("must be at least {} (got {})", range.start(), value)
+                } else {
+                    format!("must be between {} and {} (got {})", range.start(), range.end(), value)
+                },
+            )),
+            Some(other) => Err(self.invalid(param, format!("expected an integer, got {}", other.type_str()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Rejects arguments the command does not take
+    fn finish(self) -> Result<(), CommandParseError> {
+        match self.args.keys().find(|key| !self.used.contains(&key.as_str())) {
+            Some(key) => Err(self.invalid(key, "unexpected argument".to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses a command name without arguments (already trimmed and lowercased)
+fn parse_command_name(trimmed: String) -> Result<EditorCommand, CommandParseError> {
+    if trimmed.is_empty() {
+        return Err(CommandParseError::EmptyCommand);
+    }
+
+    match trimmed.as_str() {
+        // File operations
+        "file.save" | "save" => Ok(EditorCommand::Save),
+        "file.save_sudo" | "save_sudo" | "sudo_save" => Ok(EditorCommand::SaveWithSudo),
+        "buffer.toggle_readonly" | "toggle_readonly" => Ok(EditorCommand::ToggleReadOnly),
+        "buffer.next" | "next_buffer" | "bnext" => Ok(EditorCommand::NextBuffer),
+        "buffer.prev" | "buffer.previous" | "prev_buffer" | "previous_buffer" | "bprev" => {
+            Ok(EditorCommand::PreviousBuffer)
+        }
+        "keybindings.list" | "list_keybindings" => Ok(EditorCommand::ListKeybindings),
+        "keybindings.describe_key" | "describe_key" => Ok(EditorCommand::DescribeKey),
+        "palette.open" | "command_palette" => Ok(EditorCommand::CommandPalette),
+        "noop" | "nop" => Ok(EditorCommand::Noop),
+        "edit.undo" | "undo" => Ok(EditorCommand::Undo),
+        "edit.redo" | "redo" => Ok(EditorCommand::Redo),
+        "quit" | "exit" => Ok(EditorCommand::Quit),
+        "quit.force" | "force_quit" => Ok(EditorCommand::ForceQuit),
+        "command_line.open" | "command_line" | "ex" => Ok(EditorCommand::CommandLine),
+
+        // Editing operations
+        "delete_char" | "delete" | "backspace" => Ok(EditorCommand::DeleteChar),
+        "delete_forward" | "delete.forward" | "del" => Ok(EditorCommand::DeleteForward),
+
+        // Navigation commands
+        "move.up" | "move_up" | "up" => Ok(EditorCommand::MoveCursor(Direction::Up)),
+        "move.down" | "move_down" | "down" => Ok(EditorCommand::MoveCursor(Direction::Down)),
+        "move.left" | "move_left" | "left" => Ok(EditorCommand::MoveCursor(Direction::Left)),
+        "move.right" | "move_right" | "right" => Ok(EditorCommand::MoveCursor(Direction::Right)),
+        "move.line_start" | "move_line_start" | "line_start" | "home" => {
+            Ok(EditorCommand::MoveToLineStart)
+        }
+        "move.line_end" | "move_line_end" | "line_end" | "end" => {
+            Ok(EditorCommand::MoveToLineEnd)
+        }
+        "page.up" | "page_up" | "pageup" => Ok(EditorCommand::PageUp),
+        "page.down" | "page_down" | "pagedown" => Ok(EditorCommand::PageDown),
+        "insert_tab" | "tab" => Ok(EditorCommand::InsertTab),
+
+        // Selection commands (future features - placeholders)
+        "select.left" | "select_left" => Ok(EditorCommand::SelectLeft),
+        "select.right" | "select_right" => Ok(EditorCommand::SelectRight),
+        "select.up" | "select_up" => Ok(EditorCommand::SelectUp),
+        "select.down" | "select_down" => Ok(EditorCommand::SelectDown),
+        "select.line_start" | "select_line_start" => Ok(EditorCommand::SelectLineStart),
+        "select.line_end" | "select_line_end" => Ok(EditorCommand::SelectLineEnd),
+        "select.all" | "select_all" => Ok(EditorCommand::SelectAll),
+
+        // Clipboard commands (future features - placeholders)
+        "copy" => Ok(EditorCommand::Copy),
+        "cut" => Ok(EditorCommand::Cut),
+        "paste" => Ok(EditorCommand::Paste),
+
+        // Mode switching commands
+        "mode.insert" | "insert_mode" | "insert" => {
+            Ok(EditorCommand::ChangeMode(EditorMode::Insert))
+        }
+        "mode.normal" | "normal_mode" | "normal" => {
+            Ok(EditorCommand::ChangeMode(EditorMode::Normal))
+        }
+        "mode.prompt" | "prompt_mode" => {
+            Ok(EditorCommand::ChangeMode(EditorMode::Prompt))
+        }
+
+        // Prompt operations
+        "prompt.accept" | "accept_prompt" | "accept" => Ok(EditorCommand::AcceptPrompt),
+        "prompt.cancel" | "cancel_prompt" | "cancel" => Ok(EditorCommand::CancelPrompt),
+        "prompt.delete_char" | "prompt_delete" => Ok(EditorCommand::PromptDeleteChar),
+        "prompt.next" | "prompt_next" => Ok(EditorCommand::PromptNext),
+        "prompt.previous" | "prompt.prev" | "prompt_previous" => Ok(EditorCommand::PromptPrevious),
+        "prompt.complete" | "prompt_complete" => Ok(EditorCommand::PromptComplete),
+
+        // Unknown command - try parsing as plugin command if it contains a dot
+        _ => {
+            if let Some(name) = trimmed.strip_prefix("user.") {
+                return if is_valid_user_command_name(name) {
+                    Ok(EditorCommand::UserCommand(name.to_string()))
                 } else {
                     Err(CommandParseError::UnknownCommand(trimmed))
-                }
+                };
+            }
+
+            // Check if this looks like a plugin command (contains exactly one dot)
+            if trimmed.contains('.') {
+                parse_plugin_command(&trimmed)
+            } else {
+                Err(CommandParseError::UnknownCommand(trimmed))
             }
         }
     }
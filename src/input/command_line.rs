@@ -0,0 +1,590 @@
+//! Ex-style command line
+//!
+//! `:` in Normal mode opens a prompt for short typed commands:
+//!
+//! | Command | Effect |
+//! |---------|--------|
+//! | `:w` / `:write` | Save the buffer |
+//! | `:w file` | Save the buffer as `file` |
+//! | `:q` / `:quit` | Quit, warning about unsaved changes |
+//! | `:q!` / `:quit!` | Quit, discarding unsaved changes |
+//! | `:wq` / `:x` | Save, then quit |
+//! | `:e file` / `:edit file` | Open `file`, or switch to it if it is open |
+//! | `:42`, `:$` | Go to line 42, or the last line |
+//! | `:[range]s/old/new/[gi]` | Replace `old` with `new` |
+//! | `:set key=value` | Change a setting of the `[editor]` config section |
+//! | `:set key` | Show a setting |
+//!
+//! # Ranges
+//!
+//! A substitution applies to the cursor line unless a range is given: `%`
+//! for every line, or one or two addresses separated by a comma, each a line
+//! number, `.` for the cursor line or `$` for the last line (`:1,.s/a/b/`).
+//!
+//! # Substitutions
+//!
+//! The pattern is matched literally. Any character other than a letter, a
+//! digit, `\` or whitespace can delimit it instead of `/` (`:s#/usr#/opt#`),
+//! and `\` escapes the delimiter inside the pattern and the replacement. By
+//! default only the first match on each line is replaced; the `g` flag
+//! replaces all of them and `i` ignores case. The closing delimiter may be
+//! left out when no flags follow.
+//!
+//! # Completion
+//!
+//! [`complete`] returns the completions for the Tab key: command names, file
+//! paths after `:w` and `:e`, and setting names after `:set`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::buffer::{Buffer, Position};
+use crate::editor::EditorSettings;
+
+/// Command names offered by [`complete`], in alphabetical order
+pub const EX_COMMANDS: &[&str] = &[
+    "edit",
+    "quit",
+    "quit!",
+    "set",
+    "substitute",
+    "wq",
+    "write",
+    "xit",
+];
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExCommand {
+    /// `:w [file]` - save the buffer, as `file` if given
+    Write(Option<PathBuf>),
+    /// `:q` - quit, warning about unsaved changes
+    Quit,
+    /// `:q!` - quit, discarding unsaved changes
+    ForceQuit,
+    /// `:wq` / `:x` - save the buffer, then quit
+    WriteQuit,
+    /// `:e file` - open `file`
+    Edit(PathBuf),
+    /// `:<line>` - go to a line
+    Goto(Address),
+    /// `:[range]s/old/new/[flags]` - replace text
+    Substitute(Substitution),
+    /// `:set key[=value]` - change a setting, or show it without a value
+    Set {
+        /// Setting name, as in the `[editor]` config section
+        key: String,
+        /// New value; plain words that are not TOML values are strings
+        value: Option<toml::Value>,
+    },
+}
+
+/// A line address on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Address {
+    /// A 1-based line number
+    Line(usize),
+    /// `.`, the cursor line
+    Current,
+    /// `$`, the last line
+    Last,
+}
+
+impl Address {
+    /// Returns the 0-based line, clamped to the `line_count` lines of the buffer
+    pub fn resolve(self, cursor_line: usize, line_count: usize) -> usize {
+        let last = line_count.saturating_sub(1);
+        match self {
+            Address::Line(line) => line.saturating_sub(1).min(last),
+            Address::Current => cursor_line.min(last),
+            Address::Last => last,
+        }
+    }
+}
+
+/// The lines a substitution applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineRange {
+    /// No range: the cursor line
+    #[default]
+    Current,
+    /// `%`: every line
+    All,
+    /// `a` or `a,b`: the lines from `a` to `b`
+    Lines(Address, Address),
+}
+
+impl LineRange {
+    /// Returns the first and last 0-based line of the range
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExError::BackwardsRange`] if the range ends before it starts.
+    pub fn resolve(self, cursor_line: usize, line_count: usize) -> Result<(usize, usize), ExError> {
+        let (start, end) = match self {
+            LineRange::Current => (Address::Current, Address::Current),
+            LineRange::All => (Address::Line(1), Address::Last),
+            LineRange::Lines(start, end) => (start, end),
+        };
+        let (start, end) = (
+            start.resolve(cursor_line, line_count),
+            end.resolve(cursor_line, line_count),
+        );
+        if end < start {
+            return Err(ExError::BackwardsRange);
+        }
+        Ok((start, end))
+    }
+}
+
+/// A `:s` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    /// Lines to replace text in
+    pub range: LineRange,
+    /// Text to find, matched literally
+    pub pattern: String,
+    /// Text to put in its place
+    pub replacement: String,
+    /// Replace every match on a line, not just the first (`g`)
+    pub global: bool,
+    /// Match regardless of case (`i`)
+    pub ignore_case: bool,
+}
+
+impl Substitution {
+    /// Replaces the matches in the lines `start..=end` of `buffer`
+    ///
+    /// Returns the number of replacements and of lines changed. The
+    /// replacements are undone together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::buffer::Buffer;
+    /// use termide::input::command_line::{parse, ExCommand};
+    ///
+    /// let mut buffer = Buffer::from_str("a-a\nb-a\n");
+    /// let ExCommand::Substitute(substitution) = parse("%s/a/x/g").unwrap() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(substitution.apply(&mut buffer, 0, 2), (3, 2));
+    /// assert_eq!(buffer.content(), "x-x\nb-x\n");
+    /// ```
+    pub fn apply(&self, buffer: &mut Buffer, start: usize, end: usize) -> (usize, usize) {
+        let mut replaced = 0;
+        let mut lines = 0;
+
+        buffer.begin_undo_group();
+        for line in start..=end.min(buffer.line_count().saturating_sub(1)) {
+            let Some(text) = buffer.get_line(line) else {
+                continue;
+            };
+            let text = text.strip_suffix('\n').unwrap_or(&text);
+            let (new_text, count) = self.replace_in(text);
+            if count == 0 {
+                continue;
+            }
+
+            let line_start = Position::new(line, 0);
+            let line_end = Position::new(line, text.chars().count());
+            buffer.delete_range(line_start, line_end);
+            buffer.insert_text(&new_text, line_start);
+            replaced += count;
+            lines += 1;
+        }
+        buffer.end_undo_group();
+
+        (replaced, lines)
+    }
+
+    /// Returns `text` with the matches replaced, and the number of replacements
+    fn replace_in(&self, text: &str) -> (String, usize) {
+        if self.pattern.is_empty() {
+            return (text.to_string(), 0);
+        }
+        let find = |from: usize| -> Option<usize> {
+            if self.ignore_case {
+                find_ignoring_case(&text[from..], &self.pattern).map(|at| from + at)
+            } else {
+                text[from..].find(&self.pattern).map(|at| from + at)
+            }
+        };
+
+        let mut result = String::with_capacity(text.len());
+        let mut count = 0;
+        let mut from = 0;
+        while let Some(at) = find(from) {
+            let matched_len = if self.ignore_case {
+                matched_len_ignoring_case(&text[at..], &self.pattern)
+            } else {
+                self.pattern.len()
+            };
+            result.push_str(&text[from..at]);
+            result.push_str(&self.replacement);
+            from = at + matched_len;
+            count += 1;
+            if !self.global {
+                break;
+            }
+        }
+        result.push_str(&text[from..]);
+        (result, count)
+    }
+}
+
+/// Returns the byte offset of the first case-insensitive match of `pattern` in `text`
+fn find_ignoring_case(text: &str, pattern: &str) -> Option<usize> {
+    text.char_indices()
+        .map(|(at, _)| at)
+        .find(|&at| matched_len_ignoring_case(&text[at..], pattern) > 0)
+}
+
+/// Returns the byte length of the case-insensitive match of `pattern` at the
+/// start of `text`, or 0 if there is none
+fn matched_len_ignoring_case(text: &str, pattern: &str) -> usize {
+    let mut text_chars = text.char_indices();
+    for expected in pattern.chars() {
+        match text_chars.next() {
+            Some((_, ch)) if ch.to_lowercase().eq(expected.to_lowercase()) => {}
+            _ => return 0,
+        }
+    }
+    text_chars.next().map_or(text.len(), |(at, _)| at)
+}
+
+/// Error type for command lines that cannot be parsed or carried out
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ExError {
+    /// The command name is not known
+    #[error("Not an editor command: {0}")]
+    UnknownCommand(String),
+
+    /// The command needs an argument
+    #[error("Argument required: {0}")]
+    MissingArgument(&'static str),
+
+    /// The command doesn't take the argument it was given
+    #[error("Trailing characters: {0}")]
+    TrailingCharacters(String),
+
+    /// An address is not a line number, `.` or `$`
+    #[error("Invalid range: {0}")]
+    InvalidRange(String),
+
+    /// The range ends before it starts
+    #[error("Backwards range given")]
+    BackwardsRange,
+
+    /// The substitution is malformed
+    #[error("Invalid substitution: {0}")]
+    InvalidSubstitution(String),
+}
+
+/// Parses a command line, without the leading `:`
+///
+/// # Errors
+///
+/// Returns an [`ExError`] describing the first problem found.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use termide::input::command_line::{parse, Address, ExCommand, ExError};
+///
+/// assert_eq!(parse("w notes.txt"), Ok(ExCommand::Write(Some(PathBuf::from("notes.txt")))));
+/// assert_eq!(parse("q!"), Ok(ExCommand::ForceQuit));
+/// assert_eq!(parse("12"), Ok(ExCommand::Goto(Address::Line(12))));
+/// assert_eq!(parse("frobnicate"), Err(ExError::UnknownCommand("frobnicate".to_string())));
+/// ```
+pub fn parse(input: &str) -> Result<ExCommand, ExError> {
+    let input = input.trim().trim_start_matches(':').trim_start();
+    let (range, rest) = parse_range(input)?;
+    let rest = rest.trim_start();
+
+    // The command name runs up to the first character that is not a letter
+    let name_len = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+    let (name, args) = rest.split_at(name_len);
+
+    if name.is_empty() {
+        return match (range, args) {
+            (Some(LineRange::Lines(address, _)), "") => Ok(ExCommand::Goto(address)),
+            (Some(LineRange::All), "") => Ok(ExCommand::Goto(Address::Last)),
+            (None, "") => Err(ExError::MissingArgument("command")),
+            _ => Err(ExError::UnknownCommand(rest.to_string())),
+        };
+    }
+
+    if matches!(name, "s" | "substitute") {
+        return parse_substitution(range.unwrap_or_default(), args).map(ExCommand::Substitute);
+    }
+    if range.is_some() {
+        return Err(ExError::InvalidRange(format!("'{}' takes no range", name)));
+    }
+
+    let (force, args) = match args.strip_prefix('!') {
+        Some(args) => (true, args),
+        None => (false, args),
+    };
+    let known = [
+        "w", "write", "q", "quit", "wq", "x", "xit", "e", "edit", "se", "set",
+    ];
+    if !known.contains(&name) {
+        return Err(ExError::UnknownCommand(
+            rest.split_whitespace().next().unwrap_or(name).to_string(),
+        ));
+    }
+    if !args.is_empty() && !args.starts_with(char::is_whitespace) {
+        return Err(ExError::TrailingCharacters(args.to_string()));
+    }
+    let arg = Some(args.trim()).filter(|arg| !arg.is_empty());
+
+    let command = match (name, force) {
+        ("w" | "write", false) => ExCommand::Write(arg.map(PathBuf::from)),
+        ("q" | "quit", false) => no_argument(arg, ExCommand::Quit)?,
+        ("q" | "quit", true) => no_argument(arg, ExCommand::ForceQuit)?,
+        ("wq" | "x" | "xit", false) => no_argument(arg, ExCommand::WriteQuit)?,
+        ("e" | "edit", false) => ExCommand::Edit(PathBuf::from(
+            arg.ok_or(ExError::MissingArgument("file name"))?,
+        )),
+        ("se" | "set", false) => parse_set(arg.ok_or(ExError::MissingArgument("setting"))?)?,
+        _ if force => return Err(ExError::UnknownCommand(format!("{}!", name))),
+        _ => return Err(ExError::UnknownCommand(name.to_string())),
+    };
+    Ok(command)
+}
+
+/// Returns `command`, or an error if an argument was given
+fn no_argument(arg: Option<&str>, command: ExCommand) -> Result<ExCommand, ExError> {
+    match arg {
+        Some(arg) => Err(ExError::TrailingCharacters(arg.to_string())),
+        None => Ok(command),
+    }
+}
+
+/// Splits a leading range off `input`
+fn parse_range(input: &str) -> Result<(Option<LineRange>, &str), ExError> {
+    if let Some(rest) = input.strip_prefix('%') {
+        return Ok((Some(LineRange::All), rest));
+    }
+
+    let Some((start, rest)) = parse_address(input)? else {
+        return Ok((None, input));
+    };
+    let Some(rest) = rest.strip_prefix(',') else {
+        return Ok((Some(LineRange::Lines(start, start)), rest));
+    };
+    match parse_address(rest)? {
+        Some((end, rest)) => Ok((Some(LineRange::Lines(start, end)), rest)),
+        None => Err(ExError::InvalidRange(input.to_string())),
+    }
+}
+
+/// Splits a leading address off `input`
+fn parse_address(input: &str) -> Result<Option<(Address, &str)>, ExError> {
+    if let Some(rest) = input.strip_prefix('.') {
+        return Ok(Some((Address::Current, rest)));
+    }
+    if let Some(rest) = input.strip_prefix('$') {
+        return Ok(Some((Address::Last, rest)));
+    }
+
+    let digits = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    if digits == 0 {
+        return Ok(None);
+    }
+    match input[..digits].parse::<usize>() {
+        Ok(line) if line > 0 => Ok(Some((Address::Line(line), &input[digits..]))),
+        _ => Err(ExError::InvalidRange(input[..digits].to_string())),
+    }
+}
+
+/// Parses the `/old/new/flags` part of a substitution
+fn parse_substitution(range: LineRange, args: &str) -> Result<Substitution, ExError> {
+    let mut chars = args.chars();
+    let delimiter = match chars.next() {
+        Some(c) if !(c.is_alphanumeric() || c == '\\' || c.is_whitespace()) => c,
+        Some(_) => {
+            return Err(ExError::InvalidSubstitution(format!(
+                "bad delimiter in '{}'",
+                args
+            )))
+        }
+        None => return Err(ExError::MissingArgument("pattern")),
+    };
+
+    let (pattern, rest) = split_delimited(chars.as_str(), delimiter);
+    if pattern.is_empty() {
+        return Err(ExError::InvalidSubstitution("empty pattern".to_string()));
+    }
+    let (replacement, flags) = match rest {
+        Some(rest) => split_delimited(rest, delimiter),
+        None => (String::new(), None),
+    };
+
+    let mut substitution = Substitution {
+        range,
+        pattern,
+        replacement,
+        global: false,
+        ignore_case: false,
+    };
+    for flag in flags.unwrap_or_default().trim().chars() {
+        match flag {
+            'g' => substitution.global = true,
+            'i' => substitution.ignore_case = true,
+            other => {
+                return Err(ExError::InvalidSubstitution(format!(
+                    "unknown flag '{}'",
+                    other
+                )))
+            }
+        }
+    }
+    Ok(substitution)
+}
+
+/// Reads up to an unescaped `delimiter`, returning the text read and what
+/// follows the delimiter (`None` if there is none)
+fn split_delimited(input: &str, delimiter: char) -> (String, Option<&str>) {
+    let mut text = String::new();
+    let mut chars = input.char_indices();
+    while let Some((at, ch)) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some((_, next)) if next == delimiter => text.push(next),
+                Some((_, next)) => {
+                    text.push('\\');
+                    text.push(next);
+                }
+                None => text.push('\\'),
+            },
+            ch if ch == delimiter => return (text, Some(&input[at + ch.len_utf8()..])),
+            ch => text.push(ch),
+        }
+    }
+    (text, None)
+}
+
+/// Parses the argument of `:set`
+fn parse_set(arg: &str) -> Result<ExCommand, ExError> {
+    let Some((key, value)) = arg.split_once('=') else {
+        return Ok(ExCommand::Set {
+            key: arg.to_string(),
+            value: None,
+        });
+    };
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return Err(ExError::TrailingCharacters(arg.to_string()));
+    }
+
+    // Quotes are optional around strings: `:set theme=light`
+    let value = toml::from_str::<toml::Table>(&format!("value = {}", value))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+    Ok(ExCommand::Set {
+        key: key.to_string(),
+        value: Some(value),
+    })
+}
+
+/// Returns the completions of `input` for the Tab key, each a whole command line
+///
+/// Command names are completed first; after `:w` and `:e` the file name is
+/// completed relative to `dir`, and after `:set` the setting name.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use termide::input::command_line::complete;
+///
+/// assert_eq!(complete("wr", Path::new(".")), vec!["write"]);
+/// assert_eq!(complete("set tab", Path::new(".")), vec!["set tab_width="]);
+/// ```
+pub fn complete(input: &str, dir: &Path) -> Vec<String> {
+    let input = input.trim_start();
+    let Some((name, arg)) = input.split_once(char::is_whitespace) else {
+        return EX_COMMANDS
+            .iter()
+            .filter(|command| command.starts_with(input))
+            .map(|command| command.to_string())
+            .collect();
+    };
+
+    let arg = arg.trim_start();
+    match name {
+        "w" | "write" | "e" | "edit" => complete_path(arg, dir)
+            .into_iter()
+            .map(|path| format!("{} {}", name, path))
+            .collect(),
+        "se" | "set" => EditorSettings::KEYS
+            .iter()
+            .filter(|key| key.starts_with(arg))
+            .map(|key| format!("{} {}=", name, key))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the paths starting with `partial`, relative to `dir` unless
+/// `partial` is absolute, in alphabetical order
+///
+/// Directories end with `/`. Hidden files are only offered once their name
+/// is started with a `.`.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs;
+/// use termide::input::command_line::complete_path;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// fs::create_dir(dir.path().join("src")).unwrap();
+/// fs::write(dir.path().join("src/main.rs"), "").unwrap();
+///
+/// assert_eq!(complete_path("s", dir.path()), vec!["src/"]);
+/// assert_eq!(complete_path("src/m", dir.path()), vec!["src/main.rs"]);
+/// ```
+pub fn complete_path(partial: &str, dir: &Path) -> Vec<String> {
+    let (parent, prefix) = match partial.rfind('/') {
+        Some(at) => partial.split_at(at + 1),
+        None => ("", partial),
+    };
+    let search = if Path::new(parent).is_absolute() {
+        PathBuf::from(parent)
+    } else {
+        dir.join(parent)
+    };
+    let Ok(entries) = fs::read_dir(&search) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir()) || entry.path().is_dir();
+            Some(format!(
+                "{}{}{}",
+                parent,
+                name,
+                if is_dir { "/" } else { "" }
+            ))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
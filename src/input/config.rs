@@ -18,6 +18,62 @@
 //! sequence = "d d"
 //! command = "delete_char"
 //! mode = "normal"  # mode-specific binding
+//!
+//! [[keybindings]]
+//! sequence = "Ctrl+Q"
+//! unbind = true  # disable a default binding (same as command = "noop")
+//!
+//! [[keybindings]]
+//! sequence = "Ctrl+J"
+//! command = "move.down"
+//! args = { count = 10 }  # parameterized command
+//!
+//! [[keybindings]]
+//! sequence = "Ctrl+Alt+S"
+//! commands = ["file.save", "quit"]  # runs both, stops at the first failure
+//!
+//! [[keybindings]]
+//! sequence = "KpEnter"  # keypad keys, media keys and F1-F24 have names too
+//! command = "file.save"
+//!
+//! [[keybindings]]
+//! sequence = "Release+F5"  # key release; needs keyboard enhancement support
+//! command = "file.save"
+//! ```
+//!
+//! Named commands are defined in the `[commands]` section, bound as
+//! `user.<name>` and loaded with [`load_user_commands`]:
+//!
+//! ```toml
+//! [commands]
+//! save_and_quit = ["file.save", "quit"]
+//! ```
+//!
+//! The built-in keymap itself is adjusted in the `[keymap]` section, see
+//! [`KeymapOptions`]:
+//!
+//! ```toml
+//! [keymap]
+//! preset = "vim"
+//! disable_defaults = false
+//! ```
+//!
+//! General settings live in the `[editor]` section and are loaded with
+//! [`load_editor_settings`]:
+//!
+//! ```toml
+//! [editor]
+//! tab_width = 4
+//! expand_tabs = true
+//! line_numbers = true
+//! ```
+//!
+//! Sandboxed plugins are granted capabilities in the `[plugins]` section, see
+//! [`PluginGrants`](crate::plugin::PluginGrants):
+//!
+//! ```toml
+//! [plugins.word-count]
+//! capabilities = ["buffer-read", "ui"]
 //! ```
 //!
 //! # Features
@@ -43,16 +99,31 @@
 //! }
 //! ```
 
-use crate::editor::EditorMode;
+use crate::editor::{EditorMode, EditorSettings};
+use crate::file_io::SaveOptions;
+use crate::input::bindings::KeymapOptions;
 use crate::input::keybinding::{BindingContext, KeyBinding, KeySequence, Priority};
 use crate::input::registry::{BindingError, KeyBindingRegistry};
+use crate::input::user_commands::{is_valid_user_command_name, UserCommands};
 use crate::input::{CommandParseError, EditorCommand, ParseError};
-use serde::Deserialize;
+use crate::ui::Theme;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Accepted range for `editor.sequence_timeout_ms`
+const SEQUENCE_TIMEOUT_RANGE_MS: std::ops::RangeInclusive<i64> = 50..=10_000;
+
+/// Accepted range for `editor.which_key_delay_ms`
+const WHICH_KEY_DELAY_RANGE_MS: std::ops::RangeInclusive<i64> = 0..=10_000;
+
+/// Largest accepted `editor.scroll_margin`
+const MAX_SCROLL_MARGIN: i64 = 100;
+
 /// Result of loading keybindings with potential warnings
 #[derive(Debug)]
 pub struct LoadResult {
@@ -62,6 +133,17 @@ pub struct LoadResult {
     pub warnings: Vec<String>,
 }
 
+/// Result of loading the `[editor]` settings with potential warnings
+///
+/// Invalid or unknown settings produce a warning and keep their default value.
+#[derive(Debug)]
+pub struct SettingsLoadResult {
+    /// The validated settings
+    pub settings: EditorSettings,
+    /// Warning messages for settings that couldn't be applied
+    pub warnings: Vec<String>,
+}
+
 /// Error type for configuration loading
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -106,12 +188,16 @@ pub enum ConfigError {
         index: usize,
         source: BindingError,
     },
+
+    /// Invalid definition in the `[commands]` section
+    #[error("invalid user command '{name}': {reason}")]
+    InvalidUserCommand { name: String, reason: String },
 }
 
 /// Root configuration structure
 ///
 /// This struct represents the complete TOML configuration file structure.
-/// Currently, it only contains keybindings, but can be extended in the future
+/// It contains keybindings and save behaviour, and can be extended in the future
 /// to include other configuration options (theme, window size, etc.).
 ///
 /// # Examples
@@ -125,12 +211,52 @@ pub enum ConfigError {
 /// sequence = "d d"
 /// command = "delete_char"
 /// mode = "normal"
+///
+/// [save]
+/// trim_trailing_whitespace = true
+/// ensure_final_newline = true
+///
+/// [save.backup]
+/// mode = "simple"
+///
+/// [editor]
+/// tab_width = 2
+/// expand_tabs = true
+///
+/// [keymap]
+/// preset = "vim"
 /// ```
 #[derive(Debug, Deserialize)]
 pub struct KeybindingConfig {
     /// List of user-defined keybindings
     #[serde(default)]
     pub keybindings: Vec<UserBinding>,
+
+    /// Preset and default binding options, `None` when the section is absent
+    #[serde(default)]
+    pub keymap: Option<KeymapOptions>,
+
+    /// Named user commands, see [`load_user_commands`]
+    #[serde(default)]
+    pub commands: BTreeMap<String, Vec<CommandStep>>,
+
+    /// Backup and save hook options, `None` when the section is absent
+    #[serde(default)]
+    pub save: Option<SaveOptions>,
+
+    /// General editor settings, validated by [`parse_editor_settings`]
+    #[serde(default)]
+    pub editor: toml::Table,
+
+    /// Per-language overrides of the `[editor]` settings, keyed by language
+    /// name or file extension (e.g. `[language.rust]`)
+    #[serde(default)]
+    pub language: HashMap<String, toml::Table>,
+
+    /// Capabilities of sandboxed plugins, keyed by plugin name, see
+    /// [`PluginGrants`](crate::plugin::PluginGrants)
+    #[serde(default)]
+    pub plugins: toml::Table,
 }
 
 /// User-defined keybinding from configuration file
@@ -144,6 +270,9 @@ pub struct KeybindingConfig {
 /// - `sequence`: Key sequence string (e.g., "Ctrl+S", "d d")
 /// - `command`: Command name (e.g., "file.save", "delete_char")
 /// - `mode`: Optional mode restriction (e.g., "insert", "normal", "prompt")
+/// - `unbind`: Disable the sequence instead of binding a command
+/// - `args`: Arguments of a parameterized command, see [`EditorCommand::with_args`]
+/// - `commands`: Chain of commands to run instead of a single `command`
 ///
 /// # Examples
 ///
@@ -164,16 +293,151 @@ pub struct KeybindingConfig {
 /// sequence = "d d"
 /// command = "delete_char"
 /// mode = "normal"
+///
+/// # Disable the default quit binding
+/// [[keybindings]]
+/// sequence = "Ctrl+Q"
+/// unbind = true
+///
+/// # Parameterized commands
+/// [[keybindings]]
+/// sequence = "g g"
+/// command = "goto_line"
+/// args = { line = 1 }
+/// mode = "normal"
+///
+/// [[keybindings]]
+/// sequence = "Ctrl+Alt+D"
+/// command = "insert_text"
+/// args = { text = "TODO: " }
+///
+/// # Command chain
+/// [[keybindings]]
+/// sequence = "Ctrl+Alt+S"
+/// commands = ["file.save", { command = "theme.set", args = { name = "light" } }]
 /// ```
 #[derive(Debug, Deserialize)]
 pub struct UserBinding {
     /// Key sequence string (e.g., "Ctrl+S", "d d")
     pub sequence: String,
-    /// Command name (e.g., "file.save", "delete_char")
+    /// Command name (e.g., "file.save", "delete_char"), may be omitted with `unbind`
+    #[serde(default)]
     pub command: String,
     /// Optional mode restriction (e.g., "insert", "normal", "prompt")
     #[serde(default)]
     pub mode: Option<String>,
+    /// Bind the sequence to `noop`, hiding lower-priority bindings for it
+    #[serde(default)]
+    pub unbind: bool,
+    /// Arguments for parameterized commands (e.g., `{ count = 10 }`)
+    #[serde(default)]
+    pub args: toml::Table,
+    /// Commands run in order instead of `command`
+    #[serde(default)]
+    pub commands: Vec<CommandStep>,
+}
+
+/// One command of a chain: a command name, a name with arguments, or a
+/// nested chain
+///
+/// # Examples
+///
+/// ```toml
+/// commands = ["file.save", { command = "move.down", args = { count = 10 } }]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CommandStep {
+    /// Command without arguments (e.g., "file.save")
+    Name(String),
+    /// Command with an `args` table
+    WithArgs {
+        /// Command name (e.g., "move.down")
+        command: String,
+        /// Arguments, see [`EditorCommand::with_args`]
+        #[serde(default)]
+        args: toml::Table,
+    },
+    /// Nested chain that stops at its first failure
+    Chain {
+        /// Steps of the chain
+        commands: Vec<CommandStep>,
+    },
+}
+
+impl CommandStep {
+    /// Converts a command into the step that parses back into it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::config::CommandStep;
+    /// use termide::input::EditorCommand;
+    ///
+    /// let step = CommandStep::from_command(&EditorCommand::GotoLine(3));
+    /// assert_eq!(step.parse().unwrap(), EditorCommand::GotoLine(3));
+    /// ```
+    pub fn from_command(command: &EditorCommand) -> Self {
+        if let EditorCommand::Chain(commands) = command {
+            return CommandStep::Chain {
+                commands: commands.iter().map(CommandStep::from_command).collect(),
+            };
+        }
+        let (name, args) = command.to_name_and_args().expect("only chains lack a name");
+        if args.is_empty() {
+            CommandStep::Name(name)
+        } else {
+            CommandStep::WithArgs { command: name, args }
+        }
+    }
+
+    /// Returns the command name, or `chain` for a nested chain
+    pub fn name(&self) -> &str {
+        match self {
+            CommandStep::Name(name) | CommandStep::WithArgs { command: name, .. } => name,
+            CommandStep::Chain { .. } => "chain",
+        }
+    }
+
+    /// Parses the step into an `EditorCommand`
+    pub fn parse(&self) -> Result<EditorCommand, CommandParseError> {
+        match self {
+            CommandStep::Name(name) => EditorCommand::from_str(name),
+            CommandStep::WithArgs { command, args } => EditorCommand::with_args(command, args),
+            CommandStep::Chain { commands } => commands
+                .iter()
+                .map(CommandStep::parse)
+                .collect::<Result<_, _>>()
+                .map(EditorCommand::Chain),
+        }
+    }
+}
+
+/// Parses a step, resolving plugin modes with `registry`
+fn parse_step(step: &CommandStep, registry: &KeyBindingRegistry) -> Result<EditorCommand, CommandParseError> {
+    match step {
+        CommandStep::Name(name) => registry.parse_command(name),
+        CommandStep::Chain { commands } => commands
+            .iter()
+            .map(|step| parse_step(step, registry))
+            .collect::<Result<_, _>>()
+            .map(EditorCommand::Chain),
+        CommandStep::WithArgs { .. } => step.parse(),
+    }
+}
+
+/// Parses the steps of a chain, failing on the first invalid one
+fn parse_steps(
+    steps: &[CommandStep],
+    registry: &KeyBindingRegistry,
+) -> Result<Vec<EditorCommand>, (String, String)> {
+    steps
+        .iter()
+        .map(|step| {
+            parse_step(step, registry)
+                .map_err(|e| (step.name().to_string(), format_command_error(&e)))
+        })
+        .collect()
 }
 
 /// Load user keybindings from a TOML configuration file
@@ -225,25 +489,28 @@ pub struct UserBinding {
 ///
 /// Each binding is validated for:
 /// - Valid key sequence syntax (uses `KeySequence::from_str`)
-/// - Valid command name (uses `EditorCommand::from_str`)
-/// - Valid mode name if specified (case-insensitive)
+/// - Valid command name (uses `EditorCommand::from_str`, plus the plugin
+///   modes of `registry` for `mode.<plugin>.<mode>`)
+/// - Valid mode name if specified (case-insensitive for core modes, or a plugin
+///   mode of `registry`)
 /// - No conflicts at `Priority::User` level
 pub fn load_user_keybindings(
     registry: &mut KeyBindingRegistry,
     path: &Path,
 ) -> Result<LoadResult, ConfigError> {
-    // Read the file
-    let contents = fs::read_to_string(path).map_err(|source| ConfigError::ReadError {
-        path: path.display().to_string(),
-        source,
-    })?;
+    load_keybindings(registry, path, Priority::User)
+}
 
-    // Parse TOML
-    let config: KeybindingConfig =
-        toml::from_str(&contents).map_err(|source| ConfigError::TomlParseError {
-            path: path.display().to_string(),
-            source,
-        })?;
+/// Load keybindings from a configuration file and register them at `priority`
+///
+/// Shared by [`load_user_keybindings`] and project configuration loading; see
+/// [`load_user_keybindings`] for the error handling rules.
+pub fn load_keybindings(
+    registry: &mut KeyBindingRegistry,
+    path: &Path,
+    priority: Priority,
+) -> Result<LoadResult, ConfigError> {
+    let config = read_config(path)?;
 
     // Load each binding, collecting successes and warnings
     let mut loaded_count = 0;
@@ -252,7 +519,7 @@ pub fn load_user_keybindings(
     for (index, user_binding) in config.keybindings.iter().enumerate() {
         let binding_num = index + 1; // 1-indexed for user-friendly messages
 
-        match load_single_binding(user_binding, binding_num) {
+        match load_single_binding(user_binding, binding_num, priority, registry) {
             Ok(binding) => {
                 // Try to register the binding
                 match registry.register(binding) {
@@ -287,6 +554,8 @@ pub fn load_user_keybindings(
 ///
 /// * `user_binding` - The user binding from TOML config
 /// * `index` - Binding number (1-indexed) for error messages
+/// * `priority` - Priority the binding is registered with
+/// * `registry` - Registry whose plugin modes the binding may use
 ///
 /// # Returns
 ///
@@ -295,6 +564,8 @@ pub fn load_user_keybindings(
 fn load_single_binding(
     user_binding: &UserBinding,
     index: usize,
+    priority: Priority,
+    registry: &KeyBindingRegistry,
 ) -> Result<KeyBinding, ConfigError> {
     // Parse key sequence
     let sequence = KeySequence::from_str(&user_binding.sequence).map_err(|e| {
@@ -305,29 +576,62 @@ fn load_single_binding(
         }
     })?;
 
-    // Parse command
-    let command = EditorCommand::from_str(&user_binding.command).map_err(|e| {
-        ConfigError::InvalidCommand {
-            index,
-            command: user_binding.command.clone(),
-            reason: format_command_error(&e),
+    // Parse command; an unbound sequence runs `noop`
+    let command = if user_binding.unbind {
+        match EditorCommand::from_str(&user_binding.command) {
+            Err(CommandParseError::EmptyCommand) | Ok(EditorCommand::Noop) => EditorCommand::Noop,
+            _ => {
+                return Err(ConfigError::InvalidCommand {
+                    index,
+                    command: user_binding.command.clone(),
+                    reason: "'unbind = true' cannot be combined with a command".to_string(),
+                })
+            }
         }
-    })?;
+    } else if !user_binding.commands.is_empty() {
+        if !user_binding.command.is_empty() || !user_binding.args.is_empty() {
+            return Err(ConfigError::InvalidCommand {
+                index,
+                command: user_binding.command.clone(),
+                reason: "'commands' cannot be combined with 'command' or 'args'".to_string(),
+            });
+        }
+        let commands = parse_steps(&user_binding.commands, registry).map_err(|(command, reason)| {
+            ConfigError::InvalidCommand {
+                index,
+                command,
+                reason,
+            }
+        })?;
+        EditorCommand::Chain(commands)
+    } else {
+        let command = if user_binding.args.is_empty() {
+            registry.parse_command(&user_binding.command)
+        } else {
+            EditorCommand::with_args(&user_binding.command, &user_binding.args)
+        };
+        command.map_err(|e| {
+            ConfigError::InvalidCommand {
+                index,
+                command: user_binding.command.clone(),
+                reason: format_command_error(&e),
+            }
+        })?
+    };
 
     // Parse mode if specified
     let context = if let Some(mode_str) = &user_binding.mode {
-        let mode = parse_mode(mode_str).map_err(|err_msg| ConfigError::InvalidMode {
+        let mode = registry.parse_mode(mode_str).map_err(|e| ConfigError::InvalidMode {
             index,
             mode: mode_str.clone(),
-            reason: err_msg,
+            reason: e.to_string(),
         })?;
         BindingContext::Mode(mode)
     } else {
         BindingContext::Global
     };
 
-    // Create binding with User priority
-    Ok(KeyBinding::new(sequence, command, context, Priority::User))
+    Ok(KeyBinding::new(sequence, command, context, priority))
 }
 
 /// Parse a mode string (case-insensitive with whitespace trimming)
@@ -457,6 +761,256 @@ pub fn reload_user_keybindings(
     Ok((removed, result))
 }
 
+/// Load the `[save]` section of a configuration file
+///
+/// Returns `None` when the section is absent, so that a project configuration
+/// without it keeps the user's save options.
+///
+/// # Arguments
+///
+/// * `path` - Path to the TOML configuration file
+///
+/// # Errors
+///
+/// Returns `ConfigError::ReadError` or `ConfigError::TomlParseError` if the file
+/// cannot be read or parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use termide::input::config::{get_config_path, load_save_options};
+///
+/// if let Some(path) = get_config_path() {
+///     let options = load_save_options(&path).ok().flatten().unwrap_or_default();
+///     println!("Backup mode: {:?}", options.backup.mode);
+/// }
+/// ```
+pub fn load_save_options(path: &Path) -> Result<Option<SaveOptions>, ConfigError> {
+    Ok(read_config(path)?.save)
+}
+
+/// Load the `[keymap]` section of a configuration file
+///
+/// Returns `None` when the section is absent, so that a project configuration
+/// without it keeps the user's keymap.
+///
+/// # Errors
+///
+/// Returns `ConfigError::ReadError` or `ConfigError::TomlParseError` if the file
+/// cannot be read or parsed, including unknown presets and keys.
+pub fn load_keymap_options(path: &Path) -> Result<Option<KeymapOptions>, ConfigError> {
+    Ok(read_config(path)?.keymap)
+}
+
+/// Load the `[commands]` section of a configuration file into `commands`
+///
+/// Definitions replace earlier ones of the same name, so loading the user
+/// config before the project config lets the project override commands.
+/// Invalid definitions are skipped with a warning. `mode.<plugin>.<mode>`
+/// steps may enter the plugin modes of `registry`.
+///
+/// # Errors
+///
+/// Returns `ConfigError::ReadError` or `ConfigError::TomlParseError` if the file
+/// cannot be read or parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use termide::input::config::{get_config_path, load_user_commands};
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide::input::user_commands::UserCommands;
+///
+/// let registry = KeyBindingRegistry::default();
+/// let mut commands = UserCommands::new();
+/// if let Some(path) = get_config_path() {
+///     for warning in load_user_commands(&path, &mut commands, &registry).unwrap_or_default() {
+///         eprintln!("{}", warning);
+///     }
+/// }
+/// ```
+pub fn load_user_commands(
+    path: &Path,
+    commands: &mut UserCommands,
+    registry: &KeyBindingRegistry,
+) -> Result<Vec<String>, ConfigError> {
+    let mut warnings = Vec::new();
+
+    for (name, steps) in &read_config(path)?.commands {
+        let reason = if !is_valid_user_command_name(name) {
+            "names may only contain letters, digits, '_' and '-'".to_string()
+        } else if steps.is_empty() {
+            "the command list is empty".to_string()
+        } else {
+            match parse_steps(steps, registry) {
+                Ok(steps) => {
+                    commands.insert(name, steps);
+                    continue;
+                }
+                Err((command, reason)) => format!("command '{}': {}", command, reason),
+            }
+        };
+        warnings.push(
+            ConfigError::InvalidUserCommand {
+                name: name.clone(),
+                reason,
+            }
+            .to_string(),
+        );
+    }
+
+    Ok(warnings)
+}
+
+/// Load the `[editor]` section of a configuration file
+///
+/// Returns default settings when the section is absent. Each setting is
+/// validated separately; invalid values and unknown keys produce warnings and
+/// keep their defaults (see [`parse_editor_settings`]).
+///
+/// # Errors
+///
+/// Returns `ConfigError::ReadError` or `ConfigError::TomlParseError` if the file
+/// cannot be read or parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use termide::input::config::{get_config_path, load_editor_settings};
+///
+/// if let Some(path) = get_config_path() {
+///     if let Ok(result) = load_editor_settings(&path) {
+///         println!("Tab width: {}", result.settings.tab_width);
+///         for warning in result.warnings {
+///             eprintln!("{}", warning);
+///         }
+///     }
+/// }
+/// ```
+pub fn load_editor_settings(path: &Path) -> Result<SettingsLoadResult, ConfigError> {
+    Ok(parse_editor_settings(&read_config(path)?.editor))
+}
+
+/// Validate the contents of an `[editor]` section
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::config::parse_editor_settings;
+///
+/// let table: toml::Table = toml::from_str("tab_width = 2\nline_numbers = \"yes\"").unwrap();
+/// let result = parse_editor_settings(&table);
+///
+/// assert_eq!(result.settings.tab_width, 2);
+/// assert!(!result.settings.line_numbers);
+/// assert_eq!(result.warnings.len(), 1);
+/// ```
+pub fn parse_editor_settings(table: &toml::Table) -> SettingsLoadResult {
+    let mut settings = EditorSettings::default();
+    let mut warnings = Vec::new();
+    apply_editor_settings(&mut settings, table, "editor", &mut warnings);
+
+    SettingsLoadResult { settings, warnings }
+}
+
+/// Apply the settings in `table` on top of `settings`
+///
+/// Invalid values and unknown keys are reported in `warnings` as
+/// `<section>.<key>: <reason>` and leave the current value untouched.
+/// Returns the keys that were applied.
+pub fn apply_editor_settings(
+    settings: &mut EditorSettings,
+    table: &toml::Table,
+    section: &str,
+    warnings: &mut Vec<String>,
+) -> Vec<String> {
+    let mut applied = Vec::new();
+
+    for (key, value) in table {
+        let result = match key.as_str() {
+            "tab_width" => integer_setting(value, 1..=EditorSettings::MAX_TAB_WIDTH as i64)
+                .map(|n| settings.tab_width = n as usize),
+            "expand_tabs" => bool_setting(value).map(|b| settings.expand_tabs = b),
+            "default_mode" => string_setting(value).and_then(|s| match parse_mode(s) {
+                Ok(EditorMode::Prompt) => Err("prompt mode cannot be the default mode".to_string()),
+                Ok(mode) => {
+                    settings.default_mode = mode;
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            }),
+            "scroll_margin" => integer_setting(value, 0..=MAX_SCROLL_MARGIN)
+                .map(|n| settings.scroll_margin = n as usize),
+            "sequence_timeout_ms" => integer_setting(value, SEQUENCE_TIMEOUT_RANGE_MS)
+                .map(|n| settings.sequence_timeout = Duration::from_millis(n as u64)),
+            "which_key_delay_ms" => integer_setting(value, WHICH_KEY_DELAY_RANGE_MS)
+                .map(|n| settings.which_key_delay = Duration::from_millis(n as u64)),
+            "theme" => string_setting(value).and_then(|name| {
+                if Theme::by_name(name).is_some() {
+                    settings.theme = name.to_lowercase();
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "unknown theme '{}' (available: {})",
+                        name,
+                        Theme::NAMES.join(", ")
+                    ))
+                }
+            }),
+            "line_numbers" => bool_setting(value).map(|b| settings.line_numbers = b),
+            _ => Err("unknown setting".to_string()),
+        };
+
+        match result {
+            Ok(()) => applied.push(key.clone()),
+            Err(reason) => warnings.push(format!("{}.{}: {}", section, key, reason)),
+        }
+    }
+
+    applied
+}
+
+/// Reads an integer setting within `range`
+fn integer_setting(value: &toml::Value, range: std::ops::RangeInclusive<i64>) -> Result<i64, String> {
+    match value.as_integer() {
+        Some(n) if range.contains(&n) => Ok(n),
+        Some(n) => Err(format!(
+            "must be between {} and {} (got {})",
+            range.start(),
+            range.end(),
+            n
+        )),
+        None => Err(format!("expected an integer, got {}", value.type_str())),
+    }
+}
+
+/// Reads a boolean setting
+fn bool_setting(value: &toml::Value) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("expected true or false, got {}", value.type_str()))
+}
+
+/// Reads a string setting
+fn string_setting(value: &toml::Value) -> Result<&str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("expected a string, got {}", value.type_str()))
+}
+
+/// Read and parse a configuration file
+pub fn read_config(path: &Path) -> Result<KeybindingConfig, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::ReadError {
+        path: path.display().to_string(),
+        source,
+    })?;
+
+    toml::from_str(&contents).map_err(|source| ConfigError::TomlParseError {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
 /// Get the platform-specific path to the user's keybinding configuration file
 ///
 /// This function returns the standard configuration file path for the current platform:
@@ -0,0 +1,354 @@
+//! Layered configuration: defaults, user, project and language settings
+//!
+//! Settings are resolved from several layers, each overriding the ones before
+//! it:
+//!
+//! 1. Built-in defaults
+//! 2. The user configuration file (`~/.config/termide/config.toml`)
+//! 3. The project configuration file (`.termide.toml`), found by walking up
+//!    from the directory of the opened file
+//! 4. `[language.<name>]` sections for the language of the opened file, from
+//!    the user file first and then the project file
+//! 5. `.editorconfig` indentation properties for the opened file, applied with
+//!    [`ResolvedSettings::apply_editorconfig`]
+//!
+//! ```toml
+//! # .termide.toml
+//! [editor]
+//! expand_tabs = true
+//!
+//! [language.rust]
+//! tab_width = 4
+//!
+//! [language.go]
+//! expand_tabs = false
+//!
+//! [[keybindings]]
+//! sequence = "Ctrl+B"
+//! command = "file.save"
+//! ```
+//!
+//! Project keybindings are registered with `Priority::Project`, so they
+//! override the user's bindings for files inside the project. The `[save]`
+//! section is only read from the user configuration file.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use termide::input::config::get_config_path;
+//! use termide::input::config_layers::{language_for_path, resolve_settings, ConfigPaths};
+//! use std::path::Path;
+//!
+//! let file = Path::new("src/main.rs");
+//! let paths = ConfigPaths::discover(get_config_path(), Some(file));
+//! let resolved = resolve_settings(&paths, language_for_path(file).as_deref())?;
+//!
+//! for line in resolved.describe() {
+//!     println!("{}", line);
+//! }
+//! # Ok::<(), termide::input::config::ConfigError>(())
+//! ```
+
+use crate::editor::{EditorConfig, EditorSettings};
+use crate::input::config::{
+    apply_editor_settings, load_keybindings, read_config, ConfigError, KeybindingConfig, LoadResult,
+};
+use crate::input::keybinding::Priority;
+use crate::input::registry::KeyBindingRegistry;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// File name of a project configuration file
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".termide.toml";
+
+/// File extensions (and whole file names) mapped to language names used in
+/// `[language.<name>]` sections
+const LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "rust"),
+    ("py", "python"),
+    ("js", "javascript"),
+    ("mjs", "javascript"),
+    ("ts", "typescript"),
+    ("tsx", "typescript"),
+    ("go", "go"),
+    ("c", "c"),
+    ("h", "c"),
+    ("cc", "cpp"),
+    ("cpp", "cpp"),
+    ("cxx", "cpp"),
+    ("hpp", "cpp"),
+    ("java", "java"),
+    ("rb", "ruby"),
+    ("sh", "shell"),
+    ("bash", "shell"),
+    ("md", "markdown"),
+    ("toml", "toml"),
+    ("json", "json"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("html", "html"),
+    ("css", "css"),
+    ("Makefile", "make"),
+    ("makefile", "make"),
+];
+
+/// A configuration layer, listed in order of increasing precedence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// Built-in default value
+    Default,
+    /// The user configuration file
+    User,
+    /// The project configuration file
+    Project,
+    /// A `[language.<name>]` section of the user or project configuration file
+    Language {
+        /// Language name of the section
+        language: String,
+        /// `true` if the section is in the project configuration file
+        project: bool,
+    },
+    /// `.editorconfig` files
+    EditorConfig,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLayer::Default => write!(f, "default"),
+            ConfigLayer::User => write!(f, "user config"),
+            ConfigLayer::Project => write!(f, "project config"),
+            ConfigLayer::Language { language, project } => write!(
+                f,
+                "{} config [language.{}]",
+                if *project { "project" } else { "user" },
+                language
+            ),
+            ConfigLayer::EditorConfig => write!(f, ".editorconfig"),
+        }
+    }
+}
+
+/// Configuration files that apply to a file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigPaths {
+    /// The user configuration file, if it exists
+    pub user: Option<PathBuf>,
+    /// The nearest project configuration file, if any
+    pub project: Option<PathBuf>,
+}
+
+impl ConfigPaths {
+    /// Finds the configuration files for `file`
+    ///
+    /// `user` is kept only if the file exists. The project configuration is
+    /// searched from the directory containing `file`, or from the current
+    /// directory for unnamed buffers.
+    pub fn discover(user: Option<PathBuf>, file: Option<&Path>) -> Self {
+        let start = file
+            .and_then(|file| std::path::absolute(file).ok())
+            .and_then(|file| file.parent().map(Path::to_path_buf))
+            .or_else(|| std::env::current_dir().ok());
+
+        Self {
+            user: user.filter(|path| path.is_file()),
+            project: start.and_then(|dir| find_project_config(&dir)),
+        }
+    }
+}
+
+/// Finds the nearest `.termide.toml` in `start_dir` or one of its ancestors
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::config_layers::find_project_config;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(dir.path().join(".termide.toml"), "").unwrap();
+/// std::fs::create_dir(dir.path().join("src")).unwrap();
+///
+/// let found = find_project_config(&dir.path().join("src")).unwrap();
+/// assert_eq!(found, dir.path().join(".termide.toml"));
+/// ```
+pub fn find_project_config(start_dir: &Path) -> Option<PathBuf> {
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Returns the language name used for `[language.<name>]` sections of `path`
+///
+/// Known extensions map to a language name (`rs` → `rust`); other extensions
+/// are used as the name themselves.
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::config_layers::language_for_path;
+/// use std::path::Path;
+///
+/// assert_eq!(language_for_path(Path::new("src/main.rs")).as_deref(), Some("rust"));
+/// assert_eq!(language_for_path(Path::new("notes.adoc")).as_deref(), Some("adoc"));
+/// assert_eq!(language_for_path(Path::new("README")), None);
+/// ```
+pub fn language_for_path(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    if let Some((_, language)) = LANGUAGES.iter().find(|(name, _)| *name == file_name) {
+        return Some(language.to_string());
+    }
+
+    let extension = path.extension()?.to_str()?;
+    let language = LANGUAGES
+        .iter()
+        .find(|(ext, _)| ext.eq_ignore_ascii_case(extension))
+        .map_or_else(
+            || extension.to_lowercase(),
+            |(_, language)| language.to_string(),
+        );
+    Some(language)
+}
+
+/// Editor settings resolved across all layers
+#[derive(Debug)]
+pub struct ResolvedSettings {
+    /// The effective settings
+    pub settings: EditorSettings,
+    /// Layer that set each setting; settings not listed keep their default
+    pub sources: HashMap<String, ConfigLayer>,
+    /// Warning messages for settings that couldn't be applied
+    pub warnings: Vec<String>,
+}
+
+impl ResolvedSettings {
+    /// Returns the layer that set `key`
+    pub fn source(&self, key: &str) -> &ConfigLayer {
+        const DEFAULT: &ConfigLayer = &ConfigLayer::Default;
+        self.sources.get(key).unwrap_or(DEFAULT)
+    }
+
+    /// Applies the `.editorconfig` properties on top of the resolved settings
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::{EditorConfig, IndentStyle};
+    /// use termide::input::config_layers::{resolve_layers, ConfigLayer};
+    ///
+    /// let mut resolved = resolve_layers(None, None, None);
+    /// resolved.apply_editorconfig(&EditorConfig {
+    ///     indent_style: Some(IndentStyle::Space),
+    ///     ..EditorConfig::default()
+    /// });
+    ///
+    /// assert!(resolved.settings.expand_tabs);
+    /// assert_eq!(resolved.source("expand_tabs"), &ConfigLayer::EditorConfig);
+    /// ```
+    pub fn apply_editorconfig(&mut self, config: &EditorConfig) {
+        for key in config.apply_settings(&mut self.settings) {
+            self.sources.insert(key.to_string(), ConfigLayer::EditorConfig);
+        }
+    }
+
+    /// Describes every setting as `key = value (layer)`
+    pub fn describe(&self) -> Vec<String> {
+        EditorSettings::KEYS
+            .iter()
+            .filter_map(|key| {
+                let value = self.settings.value(key)?;
+                Some(format!("{} = {} ({})", key, value, self.source(key)))
+            })
+            .collect()
+    }
+}
+
+/// Resolves the editor settings from all configuration layers
+///
+/// `language` selects the `[language.<name>]` sections, see
+/// [`language_for_path`].
+///
+/// # Errors
+///
+/// Returns `ConfigError::ReadError` or `ConfigError::TomlParseError` if one of
+/// the configuration files cannot be read or parsed.
+pub fn resolve_settings(
+    paths: &ConfigPaths,
+    language: Option<&str>,
+) -> Result<ResolvedSettings, ConfigError> {
+    let user = paths.user.as_deref().map(read_config).transpose()?;
+    let project = paths.project.as_deref().map(read_config).transpose()?;
+    Ok(resolve_layers(user.as_ref(), project.as_ref(), language))
+}
+
+/// Resolves the editor settings from already parsed configuration files
+pub fn resolve_layers(
+    user: Option<&KeybindingConfig>,
+    project: Option<&KeybindingConfig>,
+    language: Option<&str>,
+) -> ResolvedSettings {
+    let mut resolved = ResolvedSettings {
+        settings: EditorSettings::default(),
+        sources: HashMap::new(),
+        warnings: Vec::new(),
+    };
+
+    let mut layers: Vec<(&toml::Table, String, ConfigLayer)> = Vec::new();
+    if let Some(config) = user {
+        layers.push((&config.editor, "editor".to_string(), ConfigLayer::User));
+    }
+    if let Some(config) = project {
+        layers.push((&config.editor, "editor".to_string(), ConfigLayer::Project));
+    }
+    if let Some(language) = language {
+        for (config, is_project) in [(user, false), (project, true)] {
+            if let Some(table) = config.and_then(|config| config.language.get(language)) {
+                let layer = ConfigLayer::Language {
+                    language: language.to_string(),
+                    project: is_project,
+                };
+                layers.push((table, format!("language.{}", language), layer));
+            }
+        }
+    }
+
+    for (table, section, layer) in layers {
+        let applied = apply_editor_settings(
+            &mut resolved.settings,
+            table,
+            &section,
+            &mut resolved.warnings,
+        );
+        for key in applied {
+            resolved.sources.insert(key, layer.clone());
+        }
+    }
+
+    resolved
+}
+
+/// Replace the project keybindings with those from `path`
+///
+/// Removes all bindings registered with `Priority::Project`, then loads the
+/// bindings of the project configuration file, if any.
+///
+/// # Errors
+///
+/// Returns an error if the project configuration file cannot be read or parsed.
+/// The previous project bindings are removed either way.
+pub fn reload_project_keybindings(
+    registry: &mut KeyBindingRegistry,
+    path: Option<&Path>,
+) -> Result<(usize, LoadResult), ConfigError> {
+    let removed = registry.unregister_by_priority(Priority::Project);
+    let result = match path {
+        Some(path) => load_keybindings(registry, path, Priority::Project)?,
+        None => LoadResult {
+            loaded: 0,
+            warnings: Vec::new(),
+        },
+    };
+
+    Ok((removed, result))
+}
@@ -37,7 +37,7 @@
 //!
 //! // Process a key event
 //! let event = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
-//! let result = handler.process_key_event(event, EditorMode::Normal);
+//! let result = handler.process_key_event(event, &EditorMode::Normal);
 //!
 //! match result {
 //!     MatchResult::Matched(cmd) => {
@@ -50,6 +50,9 @@
 //!     MatchResult::NoMatch => {
 //!         // Fall back to default behavior
 //!     }
+//!     MatchResult::Ignored => {
+//!         // Key release nothing is bound to
+//!     }
 //! }
 //! ```
 //!
@@ -78,12 +81,12 @@
 //!
 //! // First 'd' - partial match
 //! let event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
-//! let result = handler.process_key_event(event, EditorMode::Normal);
+//! let result = handler.process_key_event(event, &EditorMode::Normal);
 //! assert!(matches!(result, MatchResult::Partial));
 //!
 //! // Second 'd' - complete match
 //! let event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
-//! let result = handler.process_key_event(event, EditorMode::Normal);
+//! let result = handler.process_key_event(event, &EditorMode::Normal);
 //! assert!(matches!(result, MatchResult::Matched(_)));
 //! ```
 //!
@@ -112,30 +115,34 @@
 //!
 //! // Type 'd' in Normal mode - partial match
 //! let event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
-//! let result = handler.process_key_event(event, EditorMode::Normal);
+//! let result = handler.process_key_event(event, &EditorMode::Normal);
 //! assert!(matches!(result, MatchResult::Partial));
 //!
 //! // Switch to Insert mode - buffer should be cleared
 //! handler.on_mode_change();
 //!
 //! // Type 'd' again in Insert mode - should be NoMatch, not continuing the sequence
-//! let result = handler.process_key_event(event, EditorMode::Insert);
+//! let result = handler.process_key_event(event, &EditorMode::Insert);
 //! assert!(matches!(result, MatchResult::NoMatch));
 //! ```
 
-use crate::editor::EditorMode;
-use crate::input::keybinding::{KeyBinding, KeyPattern};
+use crate::editor::{EditorMode, KeyHint, KeyHints};
+use crate::input::introspection::describe_key;
+use crate::input::keybinding::{KeyBinding, KeyPattern, KeySequence};
 use crate::input::registry::{BindingError, KeyBindingRegistry};
+use crate::input::macros::Macros;
+use crate::input::user_commands::UserCommands;
 use crate::input::EditorCommand;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, KeyEventKind};
 use std::time::Duration;
 
 /// Result of matching a key event against registered bindings
 ///
-/// This enum represents the three possible outcomes when processing a key event:
+/// This enum represents the possible outcomes when processing a key event:
 /// - `Matched`: A complete binding matched, execute the command
 /// - `Partial`: An incomplete multi-key sequence matched, wait for next key
 /// - `NoMatch`: No binding matches, fall back to default behavior
+/// - `Ignored`: A key release that no binding uses, do nothing
 ///
 /// # Examples
 ///
@@ -188,6 +195,12 @@ pub enum MatchResult {
     /// The sequence buffer is automatically cleared. The main loop should
     /// fall back to default key handling behavior.
     NoMatch,
+
+    /// The event is a key release and no binding uses it
+    ///
+    /// The sequence buffer is left untouched, so releases between the keys of
+    /// a multi-key sequence don't interrupt it.
+    Ignored,
 }
 
 /// High-level input handler for processing keyboard events
@@ -199,6 +212,7 @@ pub enum MatchResult {
 /// # Fields
 ///
 /// - `registry`: The underlying keybinding registry
+/// - `describing`: Keys collected for "describe key", if a description is pending
 ///
 /// # Examples
 ///
@@ -216,6 +230,12 @@ pub enum MatchResult {
 pub struct InputHandler {
     /// The underlying keybinding registry
     registry: KeyBindingRegistry,
+    /// Keys typed since `describe_next_sequence`, `None` when not describing
+    describing: Option<Vec<KeyPattern>>,
+    /// Named commands from the `[commands]` config sections
+    user_commands: UserCommands,
+    /// Macro registers and the recording in progress
+    macros: Macros,
 }
 
 impl InputHandler {
@@ -231,6 +251,9 @@ impl InputHandler {
     pub fn new() -> Self {
         Self {
             registry: KeyBindingRegistry::default(),
+            describing: None,
+            user_commands: UserCommands::new(),
+            macros: Macros::new(),
         }
     }
 
@@ -255,6 +278,9 @@ impl InputHandler {
     pub fn with_timeout(timeout: Duration) -> Self {
         Self {
             registry: KeyBindingRegistry::new(timeout),
+            describing: None,
+            user_commands: UserCommands::new(),
+            macros: Macros::new(),
         }
     }
 
@@ -316,12 +342,105 @@ impl InputHandler {
         &mut self.registry
     }
 
+    /// Returns the underlying registry for queries such as listing bindings
+    pub fn registry(&self) -> &KeyBindingRegistry {
+        &self.registry
+    }
+
+    /// Returns the named commands that `user.<name>` bindings run
+    pub fn user_commands(&self) -> &UserCommands {
+        &self.user_commands
+    }
+
+    /// Replaces the named commands, e.g. after a config reload
+    pub fn set_user_commands(&mut self, user_commands: UserCommands) {
+        self.user_commands = user_commands;
+    }
+
+    /// Returns the macro registers
+    pub fn macros(&self) -> &Macros {
+        &self.macros
+    }
+
+    /// Returns the macro registers for recording and playback
+    pub fn macros_mut(&mut self) -> &mut Macros {
+        &mut self.macros
+    }
+
+    /// Replaces the macro registers, e.g. with ones loaded from disk
+    pub fn set_macros(&mut self, macros: Macros) {
+        self.macros = macros;
+    }
+
+    /// Starts "describe key": the next key sequence is described instead of executed
+    ///
+    /// Feed the following key events to [`describe_key_event`](Self::describe_key_event)
+    /// while [`is_describing`](Self::is_describing) returns `true`.
+    pub fn describe_next_sequence(&mut self) {
+        self.registry.clear_sequence();
+        self.describing = Some(Vec::new());
+    }
+
+    /// Returns `true` while waiting for the key sequence to describe
+    pub fn is_describing(&self) -> bool {
+        self.describing.is_some()
+    }
+
+    /// Adds a key to the sequence being described
+    ///
+    /// Returns `None` while the keys so far only start longer bindings, otherwise
+    /// ends the description and returns what the sequence resolves to in `mode`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::input_handler::InputHandler;
+    /// use termide::input::bindings::register_default_bindings;
+    /// use termide::editor::EditorMode;
+    /// use crossterm::event::{KeyEvent, KeyCode, KeyModifiers};
+    ///
+    /// let mut handler = InputHandler::new();
+    /// register_default_bindings(handler.registry_mut()).unwrap();
+    ///
+    /// handler.describe_next_sequence();
+    /// let event = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+    /// let text = handler.describe_key_event(event, &EditorMode::Normal).unwrap();
+    /// assert!(text.starts_with("Ctrl+q runs quit"));
+    /// assert!(!handler.is_describing());
+    /// ```
+    pub fn describe_key_event(&mut self, event: KeyEvent, mode: &EditorMode) -> Option<String> {
+        let pattern = self.key_pattern(&event)?;
+        let keys = self.describing.get_or_insert_with(Vec::new);
+        keys.push(pattern);
+
+        let description = self.registry.describe_sequence(keys, mode);
+        if description.binding.is_none() && !description.continuations.is_empty() {
+            return None;
+        }
+
+        let text = describe_key(keys, mode, &description);
+        self.describing = None;
+        Some(text)
+    }
+
+    /// Changes the timeout for incomplete multi-key sequences
+    ///
+    /// Used to apply the `editor.sequence_timeout_ms` setting, including on
+    /// config hot reload.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.registry.set_timeout(timeout);
+    }
+
     /// Processes a key event and returns the match result
     ///
     /// This method converts the Crossterm `KeyEvent` to a `KeyPattern`, adds it to
     /// the sequence buffer, and checks for matches. The sequence buffer is automatically
     /// cleared for complete matches and non-matches, but preserved for partial matches.
     ///
+    /// Key repeats and keypad keys are treated as plain presses unless a binding
+    /// uses them explicitly, so holding a key repeats its command and keypad
+    /// digits type digits. Key releases are ignored unless a binding uses them.
+    ///
     /// # Arguments
     ///
     /// * `event` - The Crossterm key event to process
@@ -332,6 +451,7 @@ impl InputHandler {
     /// - `MatchResult::Matched(command)` if a complete binding matched
     /// - `MatchResult::Partial` if an incomplete sequence matched
     /// - `MatchResult::NoMatch` if no binding matches
+    /// - `MatchResult::Ignored` if the event is an unbound key release
     ///
     /// # Examples
     ///
@@ -357,7 +477,7 @@ impl InputHandler {
     ///
     /// // Process 'i' key in Normal mode
     /// let event = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
-    /// let result = handler.process_key_event(event, EditorMode::Normal);
+    /// let result = handler.process_key_event(event, &EditorMode::Normal);
     ///
     /// match result {
     ///     MatchResult::Matched(cmd) => {
@@ -366,9 +486,11 @@ impl InputHandler {
     ///     _ => panic!("Expected match"),
     /// }
     /// ```
-    pub fn process_key_event(&mut self, event: KeyEvent, mode: EditorMode) -> MatchResult {
+    pub fn process_key_event(&mut self, event: KeyEvent, mode: &EditorMode) -> MatchResult {
         // Convert KeyEvent to KeyPattern
-        let pattern = KeyPattern::new(event.code, event.modifiers);
+        let Some(pattern) = self.key_pattern(&event) else {
+            return MatchResult::Ignored;
+        };
 
         // Add to sequence buffer
         self.registry.add_to_sequence(pattern);
@@ -389,6 +511,94 @@ impl InputHandler {
         }
     }
 
+    /// Converts `event` to the pattern looked up in the registry
+    ///
+    /// Returns `None` for key releases no binding uses. Repeats and keypad keys
+    /// fall back to plain presses unless a binding uses them.
+    fn key_pattern(&self, event: &KeyEvent) -> Option<KeyPattern> {
+        let mut pattern = KeyPattern::from_event(event);
+        if pattern.keypad && !self.registry.uses_key(&pattern) {
+            pattern.keypad = false;
+        }
+
+        match pattern.kind {
+            KeyEventKind::Press => Some(pattern),
+            _ if self.registry.uses_key(&pattern) => Some(pattern),
+            KeyEventKind::Repeat => Some(pattern.with_kind(KeyEventKind::Press)),
+            KeyEventKind::Release => None,
+        }
+    }
+
+    /// Returns the continuations of the pending key sequence for the which-key popup
+    ///
+    /// Returns `None` until a sequence has been pending for at least `delay`, and
+    /// when no binding active in `mode` continues it. Each continuation lists the
+    /// remaining keys and the command of the binding that would fire; bindings
+    /// shadowed by a higher-priority binding for the same sequence and unbound
+    /// (`noop`) sequences are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::input_handler::{InputHandler, MatchResult};
+    /// use termide::input::keybinding::{KeyPattern, KeySequence, KeyBinding, BindingContext, Priority};
+    /// use termide::input::EditorCommand;
+    /// use termide::editor::EditorMode;
+    /// use crossterm::event::{KeyEvent, KeyCode, KeyModifiers};
+    /// use std::time::Duration;
+    ///
+    /// let mut handler = InputHandler::new();
+    /// let d = KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE);
+    /// handler.register_binding(KeyBinding::new(
+    ///     KeySequence::new(vec![d, d]).unwrap(),
+    ///     EditorCommand::DeleteChar,
+    ///     BindingContext::Mode(EditorMode::Normal),
+    ///     Priority::Default,
+    /// )).unwrap();
+    ///
+    /// let event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+    /// assert_eq!(handler.process_key_event(event, &EditorMode::Normal), MatchResult::Partial);
+    ///
+    /// let hints = handler.key_hints(&EditorMode::Normal, Duration::ZERO).unwrap();
+    /// assert_eq!(hints.pending, "d");
+    /// assert_eq!(hints.hints[0].keys, "d");
+    /// assert_eq!(hints.hints[0].command, "delete_char");
+    /// ```
+    pub fn key_hints(&self, mode: &EditorMode, delay: Duration) -> Option<KeyHints> {
+        if self.registry.pending_duration()? < delay {
+            return None;
+        }
+
+        let pending = self.registry.pending_sequence();
+        let description = self.registry.describe_sequence(pending, mode);
+        let mut hints: Vec<KeyHint> = Vec::new();
+        let mut seen: Vec<&KeySequence> = Vec::new();
+        for binding in description.continuations {
+            // Bindings come in lookup order, so the first one per sequence fires
+            if seen.contains(&binding.sequence()) {
+                continue;
+            }
+            seen.push(binding.sequence());
+            // Unbound sequences are not worth offering
+            if binding.command() == &EditorCommand::Noop {
+                continue;
+            }
+            hints.push(KeyHint {
+                keys: format_keys(&binding.sequence().patterns()[pending.len()..]),
+                command: binding.command().to_string(),
+            });
+        }
+        if hints.is_empty() {
+            return None;
+        }
+        hints.sort_by(|a, b| a.keys.cmp(&b.keys));
+
+        Some(KeyHints {
+            pending: format_keys(pending),
+            hints,
+        })
+    }
+
     /// Handles mode changes by clearing the sequence buffer
     ///
     /// This method should be called whenever the editor mode changes to prevent
@@ -421,14 +631,14 @@ impl InputHandler {
     ///
     /// // Type 'd' - partial match
     /// let event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
-    /// let result = handler.process_key_event(event, EditorMode::Normal);
+    /// let result = handler.process_key_event(event, &EditorMode::Normal);
     /// assert!(matches!(result, MatchResult::Partial));
     ///
     /// // Mode change - clear buffer
     /// handler.on_mode_change();
     ///
     /// // Type 'd' again - should not complete the sequence
-    /// let result = handler.process_key_event(event, EditorMode::Insert);
+    /// let result = handler.process_key_event(event, &EditorMode::Insert);
     /// assert!(matches!(result, MatchResult::NoMatch));
     /// ```
     pub fn on_mode_change(&mut self) {
@@ -473,6 +683,15 @@ impl InputHandler {
     }
 }
 
+/// Formats key patterns in config file syntax, separated by spaces
+fn format_keys(patterns: &[KeyPattern]) -> String {
+    patterns
+        .iter()
+        .map(KeyPattern::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl Default for InputHandler {
     /// Creates a new input handler with default timeout (1000ms)
     ///
@@ -0,0 +1,218 @@
+//! Human-readable keybinding reports
+//!
+//! Formats the queries of [`KeyBindingRegistry`] for display: the full binding
+//! table shown by `keybindings.list` and the one-line answer shown by
+//! `keybindings.describe_key`.
+
+use crate::buffer::Buffer;
+use crate::editor::EditorMode;
+use crate::input::keybinding::{KeyBinding, KeyPattern};
+use crate::input::registry::{KeyBindingRegistry, SequenceDescription};
+use crate::input::EditorCommand;
+
+/// First word of the report made by [`keybinding_report`]
+const REPORT_HEADING: &str = "Keybindings (";
+
+/// Returns `true` if `buffer` shows a report made by [`keybinding_report`]
+///
+/// Reports are read-only buffers without a file, starting with the report
+/// heading, so the report can be refreshed instead of opened again.
+///
+/// # Examples
+///
+/// ```
+/// use termide::buffer::Buffer;
+/// use termide::input::introspection::{is_keybinding_report, keybinding_report};
+/// use termide::input::registry::KeyBindingRegistry;
+///
+/// let mut report = Buffer::from_str(&keybinding_report(&KeyBindingRegistry::default()));
+/// assert!(!is_keybinding_report(&report));
+/// report.set_read_only(true);
+/// assert!(is_keybinding_report(&report));
+/// ```
+pub fn is_keybinding_report(buffer: &Buffer) -> bool {
+    buffer.is_read_only()
+        && buffer.file_path().is_none()
+        && buffer.get_line(0).is_some_and(|line| line.starts_with(REPORT_HEADING))
+}
+
+/// Formats every binding plus the shadowed bindings and prefix conflicts
+///
+/// Bindings are listed in lookup order, so for any sequence the first row that
+/// is active in a mode is the one that fires there.
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide::input::bindings::register_default_bindings;
+/// use termide::input::introspection::keybinding_report;
+///
+/// let mut registry = KeyBindingRegistry::default();
+/// register_default_bindings(&mut registry).unwrap();
+///
+/// let report = keybinding_report(&registry);
+/// assert!(report.contains("file.save"));
+/// assert!(report.contains("Shadowed bindings"));
+/// ```
+pub fn keybinding_report(registry: &KeyBindingRegistry) -> String {
+    let header = ["SEQUENCE", "COMMAND", "CONTEXT", "PRIORITY"];
+    let rows: Vec<[String; 4]> = registry
+        .bindings()
+        .iter()
+        .map(|binding| {
+            [
+                binding.sequence().to_string(),
+                binding.command().to_string(),
+                binding.context().to_string(),
+                binding.priority().to_string(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |cells: [&str; 4]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.trim_end().to_string()
+    };
+
+    let mut lines = vec![format!("{}{})", REPORT_HEADING, rows.len()), String::new()];
+    lines.push(format_row(header));
+    for row in &rows {
+        lines.push(format_row([&row[0], &row[1], &row[2], &row[3]]));
+    }
+
+    lines.push(String::new());
+    lines.push("Shadowed bindings".to_string());
+    let shadowed = registry.shadowed_bindings();
+    if shadowed.is_empty() {
+        lines.push("  none".to_string());
+    }
+    for entry in shadowed {
+        lines.push(format!(
+            "  {} is overridden by {} in {}",
+            describe_binding(entry.binding),
+            describe_binding(entry.by),
+            format_modes(&entry.modes)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("Prefix conflicts".to_string());
+    let conflicts = registry.prefix_conflicts();
+    if conflicts.is_empty() {
+        lines.push("  none".to_string());
+    }
+    for entry in conflicts {
+        lines.push(format!(
+            "  {} is unreachable: {} fires first in {}",
+            describe_binding(entry.unreachable),
+            describe_binding(entry.prefix),
+            format_modes(&entry.modes)
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Formats what a key sequence resolves to in `mode`, for the status bar
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide::input::bindings::register_default_bindings;
+/// use termide::input::introspection::describe_key;
+/// use termide::input::keybinding::KeyPattern;
+/// use termide::editor::EditorMode;
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// let mut registry = KeyBindingRegistry::default();
+/// register_default_bindings(&mut registry).unwrap();
+///
+/// let keys = [KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL)];
+/// let description = registry.describe_sequence(&keys, &EditorMode::Insert);
+/// assert_eq!(
+///     describe_key(&keys, &EditorMode::Insert, &description),
+///     "Ctrl+s runs file.save in insert mode (global, default)"
+/// );
+/// ```
+pub fn describe_key(
+    sequence: &[KeyPattern],
+    mode: &EditorMode,
+    description: &SequenceDescription<'_>,
+) -> String {
+    let keys = sequence
+        .iter()
+        .map(KeyPattern::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mode = mode.to_string().to_lowercase();
+
+    match description.binding {
+        Some(binding) => {
+            let action = match binding.command() {
+                EditorCommand::Noop => "is unbound".to_string(),
+                command => format!("runs {}", command),
+            };
+            let mut text = format!(
+                "{} {} in {} mode ({}, {})",
+                keys,
+                action,
+                mode,
+                binding.context(),
+                binding.priority()
+            );
+            if !description.shadowed.is_empty() {
+                let shadowed = description
+                    .shadowed
+                    .iter()
+                    .map(|binding| format!("{} ({})", binding.command(), binding.priority()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                text.push_str(&format!("; overrides {}", shadowed));
+            }
+            text
+        }
+        None if !description.continuations.is_empty() => {
+            let continuations = description
+                .continuations
+                .iter()
+                .map(|binding| format!("{} → {}", binding.sequence(), binding.command()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{} starts {} in {} mode", keys, continuations, mode)
+        }
+        None => format!("{} is not bound in {} mode", keys, mode),
+    }
+}
+
+/// Formats a binding as `sequence → command (context, priority)`
+fn describe_binding(binding: &KeyBinding) -> String {
+    format!(
+        "{} → {} ({}, {})",
+        binding.sequence(),
+        binding.command(),
+        binding.context(),
+        binding.priority()
+    )
+}
+
+/// Formats a list of modes as lowercase names, e.g. `insert, normal`
+fn format_modes(modes: &[EditorMode]) -> String {
+    modes
+        .iter()
+        .map(EditorMode::name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -23,6 +23,7 @@
 //! # Priority System
 //!
 //! When multiple bindings match the same key sequence, priority determines which wins:
+//! - `Priority::Project` (30): Project configuration (`.termide.toml`)
 //! - `Priority::User` (20): User customizations
 //! - `Priority::Plugin` (10): Plugin-defined bindings
 //! - `Priority::Default` (0): Built-in editor bindings
@@ -88,13 +89,14 @@
 //! );
 //!
 //! // Check if binding is active in Normal mode
-//! assert!(insert_binding.context().is_active(EditorMode::Normal));
-//! assert!(!insert_binding.context().is_active(EditorMode::Insert));
+//! assert!(insert_binding.context().is_active(&EditorMode::Normal));
+//! assert!(!insert_binding.context().is_active(&EditorMode::Insert));
 //! ```
 
 use crate::editor::EditorMode;
 use crate::input::EditorCommand;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MediaKeyCode};
+use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -134,6 +136,11 @@ pub const PRIMARY_MODIFIER: KeyModifiers = KeyModifiers::CONTROL;
 ///
 /// - `code`: The key code (character, function key, arrow, etc.)
 /// - `modifiers`: Required modifiers (NONE, CONTROL, SHIFT, ALT, SUPER, or combinations)
+/// - `kind`: The event kind (press, repeat or release); `new` creates press patterns
+/// - `keypad`: Whether the key must come from the numeric keypad
+///
+/// Repeat and release events and keypad keys are only reported by terminals
+/// supporting the keyboard enhancement protocol.
 ///
 /// # Examples
 ///
@@ -163,6 +170,10 @@ pub struct KeyPattern {
     pub code: KeyCode,
     /// Required modifiers (must match exactly)
     pub modifiers: KeyModifiers,
+    /// Required event kind
+    pub kind: KeyEventKind,
+    /// Whether the key must be on the keypad
+    pub keypad: bool,
 }
 
 impl KeyPattern {
@@ -184,13 +195,75 @@ impl KeyPattern {
     /// let cut = KeyPattern::new(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::SHIFT);
     /// ```
     pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
-        Self { code, modifiers }
+        Self {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            keypad: false,
+        }
+    }
+
+    /// Returns this pattern for key events of `kind` instead of presses
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::keybinding::KeyPattern;
+    /// use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+    ///
+    /// let pattern = KeyPattern::new(KeyCode::Char(' '), KeyModifiers::NONE)
+    ///     .with_kind(KeyEventKind::Release);
+    /// let release = KeyEvent::new_with_kind_and_state(
+    ///     KeyCode::Char(' '),
+    ///     KeyModifiers::NONE,
+    ///     KeyEventKind::Release,
+    ///     KeyEventState::NONE,
+    /// );
+    /// assert!(pattern.matches(&release));
+    /// assert!(!pattern.matches(&KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE)));
+    /// ```
+    pub fn with_kind(self, kind: KeyEventKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// Returns this pattern for the keypad variant of the key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::keybinding::KeyPattern;
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// let kp_enter = KeyPattern::new(KeyCode::Enter, KeyModifiers::NONE).on_keypad();
+    /// assert!(kp_enter.keypad);
+    /// assert_eq!(kp_enter.to_string(), "KpEnter");
+    /// ```
+    pub fn on_keypad(self) -> Self {
+        Self { keypad: true, ..self }
+    }
+
+    /// Converts a key event into the pattern it matches exactly
+    ///
+    /// Terminals report Shift+Tab as `BackTab` with the Shift modifier; the
+    /// modifier is dropped so that `BackTab` alone matches it.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers;
+        if event.code == KeyCode::BackTab {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self {
+            code: event.code,
+            modifiers,
+            kind: event.kind,
+            keypad: event.state.contains(KeyEventState::KEYPAD),
+        }
     }
 
     /// Checks if this pattern matches a key event with exact modifier matching
     ///
-    /// Returns `true` only if both the key code and modifiers match exactly.
-    /// Modifier matching is strict: Ctrl matches only Ctrl, not Ctrl+Shift.
+    /// Returns `true` only if the key code, modifiers, event kind and keypad
+    /// flag all match. Modifier matching is strict: Ctrl matches only Ctrl, not
+    /// Ctrl+Shift.
     ///
     /// # Arguments
     ///
@@ -221,7 +294,74 @@ impl KeyPattern {
     /// assert!(!pattern.matches(&event4));
     /// ```
     pub fn matches(&self, event: &KeyEvent) -> bool {
-        self.code == event.code && self.modifiers == event.modifiers
+        *self == Self::from_event(event)
+    }
+}
+
+impl fmt::Display for KeyPattern {
+    /// Formats the pattern in the config file syntax, e.g. `Ctrl+S`, `Shift+F1`
+    /// or `Release+KpEnter`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            KeyEventKind::Press => {}
+            KeyEventKind::Repeat => write!(f, "Repeat+")?,
+            KeyEventKind::Release => write!(f, "Release+")?,
+        }
+
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+            (KeyModifiers::SUPER, "Super"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+
+        if self.keypad {
+            write!(f, "Kp")?;
+            if let Some(name) = KEYPAD_SYMBOLS
+                .iter()
+                .find(|(_, ch)| self.code == KeyCode::Char(*ch))
+                .map(|(name, _)| name)
+            {
+                return write!(f, "{}", name);
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(ch) => write!(f, "{}", ch),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::Insert => write!(f, "Insert"),
+            KeyCode::BackTab => write!(f, "BackTab"),
+            KeyCode::Menu => write!(f, "Menu"),
+            KeyCode::CapsLock => write!(f, "CapsLock"),
+            KeyCode::ScrollLock => write!(f, "ScrollLock"),
+            KeyCode::NumLock => write!(f, "NumLock"),
+            KeyCode::PrintScreen => write!(f, "PrintScreen"),
+            KeyCode::Pause => write!(f, "Pause"),
+            KeyCode::KeypadBegin if self.keypad => write!(f, "Begin"),
+            KeyCode::Media(media) => match MEDIA_KEYS.iter().find(|(_, code)| *code == media) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{:?}", media),
+            },
+            other => write!(f, "{:?}", other),
+        }
     }
 }
 
@@ -343,6 +483,30 @@ impl KeySequence {
         self.patterns.is_empty()
     }
 
+    /// Returns the key patterns in order
+    pub fn patterns(&self) -> &[KeyPattern] {
+        &self.patterns
+    }
+
+    /// Returns `true` if this sequence is shorter than `other` and `other`
+    /// starts with it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::keybinding::KeySequence;
+    /// use std::str::FromStr;
+    ///
+    /// let g = KeySequence::from_str("g").unwrap();
+    /// let gd = KeySequence::from_str("g d").unwrap();
+    /// assert!(g.is_prefix_of(&gd));
+    /// assert!(!gd.is_prefix_of(&g));
+    /// assert!(!g.is_prefix_of(&g));
+    /// ```
+    pub fn is_prefix_of(&self, other: &KeySequence) -> bool {
+        other.is_partial_match(&self.patterns)
+    }
+
     /// Checks if this sequence exactly matches the given buffer
     ///
     /// Returns `true` only if the buffer contains the exact same patterns
@@ -450,6 +614,19 @@ impl KeySequence {
     }
 }
 
+impl fmt::Display for KeySequence {
+    /// Formats the sequence in the config file syntax, e.g. `g d`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, pattern) in self.patterns.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", pattern)?;
+        }
+        Ok(())
+    }
+}
+
 /// Error type for parsing key sequences and patterns from strings
 ///
 /// This error type provides detailed information about what went wrong during
@@ -465,7 +642,7 @@ pub enum ParseError {
     EmptyPattern,
 
     /// An unknown modifier name was encountered
-    #[error("unknown modifier '{0}': valid modifiers are Ctrl, Shift, Alt, Super")]
+    #[error("unknown modifier '{0}': valid modifiers are Ctrl, Shift, Alt, Super, Repeat, Release")]
     UnknownModifier(String),
 
     /// An unknown key name was encountered
@@ -496,12 +673,25 @@ impl FromStr for KeySequence {
     /// - Modifiers are case-insensitive: `"ctrl+s"` == `"Ctrl+S"` == `"CTRL+S"`
     /// - Valid modifiers: `Ctrl`, `Shift`, `Alt`, `Super`
     ///
+    /// ## Event Kinds
+    ///
+    /// Patterns match key presses. The `Repeat` and `Release` prefixes match
+    /// auto-repeat and key release events instead: `"Release+Space"`. These are
+    /// only reported by terminals supporting the keyboard enhancement protocol.
+    ///
     /// ## Special Keys
     ///
     /// Special key names are case-insensitive:
     /// - Navigation: `Up`, `Down`, `Left`, `Right`, `Home`, `End`, `PageUp`, `PageDown`
-    /// - Editing: `Enter`, `Backspace`, `Delete`, `Tab`, `Space`, `Esc`
-    /// - Function keys: `F1` through `F12`
+    /// - Editing: `Enter`, `Backspace`, `Delete`, `Insert`, `Tab`, `BackTab`, `Space`, `Esc`
+    /// - Function keys: `F1` through `F24`
+    /// - Other keys: `Menu`, `CapsLock`, `ScrollLock`, `NumLock`, `PrintScreen`, `Pause`
+    /// - Media keys: `MediaPlay`, `MediaPause`, `MediaPlayPause`, `MediaStop`,
+    ///   `MediaNext`, `MediaPrev`, `MediaFastForward`, `MediaRewind`, `MediaRecord`,
+    ///   `VolumeUp`, `VolumeDown`, `Mute`
+    /// - Keypad keys: `Kp` followed by a digit, `Enter`, `Plus`, `Minus`, `Multiply`,
+    ///   `Divide`, `Decimal`, `Equal`, `Comma`, `Begin` or a navigation key,
+    ///   e.g. `Kp5`, `KpEnter`, `KpPlus`
     ///
     /// ## Multi-Key Sequences
     ///
@@ -587,17 +777,28 @@ fn parse_key_pattern(s: &str) -> Result<KeyPattern, ParseError> {
     let key_str = parts[parts.len() - 1];
     let modifier_strs = &parts[..parts.len() - 1];
 
-    // Parse modifiers
+    // Parse modifiers and event kind
     let mut modifiers = KeyModifiers::NONE;
+    let mut kind = KeyEventKind::Press;
     for modifier_str in modifier_strs {
-        let modifier = parse_modifier(modifier_str)?;
-        modifiers |= modifier;
+        match modifier_str.to_lowercase().as_str() {
+            "repeat" => kind = KeyEventKind::Repeat,
+            "release" => kind = KeyEventKind::Release,
+            _ => modifiers |= parse_modifier(modifier_str)?,
+        }
     }
 
-    // Parse key
-    let key_code = parse_key_code(key_str)?;
+    // Parse key, which may be on the keypad
+    let pattern = match key_str.get(..2) {
+        Some(prefix) if key_str.len() > 2 && prefix.eq_ignore_ascii_case("kp") => {
+            let code = parse_keypad_key(&key_str[2..])
+                .ok_or_else(|| ParseError::UnknownKey(key_str.to_string()))?;
+            KeyPattern::new(code, modifiers).on_keypad()
+        }
+        _ => KeyPattern::new(parse_key_code(key_str)?, modifiers),
+    };
 
-    Ok(KeyPattern::new(key_code, modifiers))
+    Ok(pattern.with_kind(kind))
 }
 
 /// Parses a modifier string like "Ctrl", "Shift", "Alt"
@@ -629,13 +830,26 @@ fn parse_key_code(s: &str) -> Result<KeyCode, ParseError> {
         "end" => return Ok(KeyCode::End),
         "pageup" | "pgup" => return Ok(KeyCode::PageUp),
         "pagedown" | "pgdown" => return Ok(KeyCode::PageDown),
+        "insert" | "ins" => return Ok(KeyCode::Insert),
+        "backtab" => return Ok(KeyCode::BackTab),
+        "menu" => return Ok(KeyCode::Menu),
+        "capslock" => return Ok(KeyCode::CapsLock),
+        "scrolllock" => return Ok(KeyCode::ScrollLock),
+        "numlock" => return Ok(KeyCode::NumLock),
+        "printscreen" | "print" => return Ok(KeyCode::PrintScreen),
+        "pause" => return Ok(KeyCode::Pause),
         _ => {}
     }
 
+    // Media keys
+    if let Some((_, media)) = MEDIA_KEYS.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)) {
+        return Ok(KeyCode::Media(*media));
+    }
+
     // Function keys
     if s.to_lowercase().starts_with('f') {
         if let Ok(num) = s[1..].parse::<u8>() {
-            if (1..=12).contains(&num) {
+            if (1..=24).contains(&num) {
                 return Ok(KeyCode::F(num));
             }
         }
@@ -651,6 +865,59 @@ fn parse_key_code(s: &str) -> Result<KeyCode, ParseError> {
     Err(ParseError::UnknownKey(s.to_string()))
 }
 
+/// Names of the media keys, as written in key sequences
+const MEDIA_KEYS: [(&str, MediaKeyCode); 12] = [
+    ("MediaPlay", MediaKeyCode::Play),
+    ("MediaPause", MediaKeyCode::Pause),
+    ("MediaPlayPause", MediaKeyCode::PlayPause),
+    ("MediaStop", MediaKeyCode::Stop),
+    ("MediaNext", MediaKeyCode::TrackNext),
+    ("MediaPrev", MediaKeyCode::TrackPrevious),
+    ("MediaFastForward", MediaKeyCode::FastForward),
+    ("MediaRewind", MediaKeyCode::Rewind),
+    ("MediaRecord", MediaKeyCode::Record),
+    ("VolumeUp", MediaKeyCode::RaiseVolume),
+    ("VolumeDown", MediaKeyCode::LowerVolume),
+    ("Mute", MediaKeyCode::MuteVolume),
+];
+
+/// Names of the keypad operator keys, written after the `Kp` prefix
+const KEYPAD_SYMBOLS: [(&str, char); 7] = [
+    ("Plus", '+'),
+    ("Minus", '-'),
+    ("Multiply", '*'),
+    ("Divide", '/'),
+    ("Decimal", '.'),
+    ("Equal", '='),
+    ("Comma", ','),
+];
+
+/// Parses the name of a keypad key after its `Kp` prefix, e.g. "5" or "Enter"
+fn parse_keypad_key(s: &str) -> Option<KeyCode> {
+    if let Some((_, ch)) = KEYPAD_SYMBOLS.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)) {
+        return Some(KeyCode::Char(*ch));
+    }
+    if s.eq_ignore_ascii_case("begin") {
+        return Some(KeyCode::KeypadBegin);
+    }
+
+    match parse_key_code(s).ok()? {
+        KeyCode::Char(ch) if ch.is_ascii_digit() => Some(KeyCode::Char(ch)),
+        code @ (KeyCode::Enter
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Insert
+        | KeyCode::Delete) => Some(code),
+        _ => None,
+    }
+}
+
 /// Context specifying when a keybinding is active
 ///
 /// Bindings can be global (active in most modes), mode-specific, or plugin-specific.
@@ -671,30 +938,30 @@ fn parse_key_code(s: &str) -> Result<KeyCode, ParseError> {
 ///
 /// // Global binding - active in Insert and Normal, not in Prompt
 /// let global = BindingContext::Global;
-/// assert!(global.is_active(EditorMode::Normal));
-/// assert!(global.is_active(EditorMode::Insert));
-/// assert!(!global.is_active(EditorMode::Prompt)); // Prompt mode excluded
+/// assert!(global.is_active(&EditorMode::Normal));
+/// assert!(global.is_active(&EditorMode::Insert));
+/// assert!(!global.is_active(&EditorMode::Prompt)); // Prompt mode excluded
 ///
 /// // Mode-specific binding
 /// let normal_only = BindingContext::Mode(EditorMode::Normal);
-/// assert!(normal_only.is_active(EditorMode::Normal));
-/// assert!(!normal_only.is_active(EditorMode::Insert));
+/// assert!(normal_only.is_active(&EditorMode::Normal));
+/// assert!(!normal_only.is_active(&EditorMode::Insert));
 ///
 /// // Multi-mode binding
 /// let editing_modes = BindingContext::Modes(vec![
 ///     EditorMode::Insert,
 ///     EditorMode::Normal,
 /// ]);
-/// assert!(editing_modes.is_active(EditorMode::Insert));
-/// assert!(editing_modes.is_active(EditorMode::Normal));
-/// assert!(!editing_modes.is_active(EditorMode::Prompt));
+/// assert!(editing_modes.is_active(&EditorMode::Insert));
+/// assert!(editing_modes.is_active(&EditorMode::Normal));
+/// assert!(!editing_modes.is_active(&EditorMode::Prompt));
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BindingContext {
     /// Active in all modes except Prompt
     ///
     /// Global bindings are for universal shortcuts like Ctrl+S (save) that should
-    /// work in both Insert and Normal modes, but not when prompting for input.
+    /// work in Insert, Normal and plugin modes, but not when prompting for input.
     Global,
 
     /// Active only in the specified mode
@@ -712,7 +979,8 @@ pub enum BindingContext {
     ///
     /// Plugins can register bindings with an optional list of modes. If modes is
     /// `None`, the plugin binding is active in all modes. If modes is `Some(vec)`,
-    /// it's only active in those modes.
+    /// it's only active in those modes, which may include the plugin's own
+    /// [`EditorMode::Plugin`] modes.
     Plugin {
         /// Name of the plugin registering this binding
         name: String,
@@ -736,55 +1004,79 @@ impl BindingContext {
     ///
     /// // Global context excludes Prompt mode
     /// let ctx = BindingContext::Global;
-    /// assert!(ctx.is_active(EditorMode::Normal));
-    /// assert!(ctx.is_active(EditorMode::Insert));
-    /// assert!(!ctx.is_active(EditorMode::Prompt));
+    /// assert!(ctx.is_active(&EditorMode::Normal));
+    /// assert!(ctx.is_active(&EditorMode::Insert));
+    /// assert!(!ctx.is_active(&EditorMode::Prompt));
     ///
     /// // Mode-specific context
     /// let ctx = BindingContext::Mode(EditorMode::Normal);
-    /// assert!(ctx.is_active(EditorMode::Normal));
-    /// assert!(!ctx.is_active(EditorMode::Insert));
+    /// assert!(ctx.is_active(&EditorMode::Normal));
+    /// assert!(!ctx.is_active(&EditorMode::Insert));
     ///
     /// // Multi-mode context
     /// let ctx = BindingContext::Modes(vec![EditorMode::Insert, EditorMode::Normal]);
-    /// assert!(ctx.is_active(EditorMode::Insert));
-    /// assert!(ctx.is_active(EditorMode::Normal));
-    /// assert!(!ctx.is_active(EditorMode::Prompt));
+    /// assert!(ctx.is_active(&EditorMode::Insert));
+    /// assert!(ctx.is_active(&EditorMode::Normal));
+    /// assert!(!ctx.is_active(&EditorMode::Prompt));
     ///
     /// // Plugin context without mode filter (all modes)
     /// let ctx = BindingContext::Plugin {
     ///     name: "lsp".to_string(),
     ///     modes: None,
     /// };
-    /// assert!(ctx.is_active(EditorMode::Normal));
-    /// assert!(ctx.is_active(EditorMode::Insert));
-    /// assert!(ctx.is_active(EditorMode::Prompt));
+    /// assert!(ctx.is_active(&EditorMode::Normal));
+    /// assert!(ctx.is_active(&EditorMode::Insert));
+    /// assert!(ctx.is_active(&EditorMode::Prompt));
     ///
     /// // Plugin context with mode filter
     /// let ctx = BindingContext::Plugin {
     ///     name: "lsp".to_string(),
     ///     modes: Some(vec![EditorMode::Normal, EditorMode::Insert]),
     /// };
-    /// assert!(ctx.is_active(EditorMode::Normal));
-    /// assert!(ctx.is_active(EditorMode::Insert));
-    /// assert!(!ctx.is_active(EditorMode::Prompt));
+    /// assert!(ctx.is_active(&EditorMode::Normal));
+    /// assert!(ctx.is_active(&EditorMode::Insert));
+    /// assert!(!ctx.is_active(&EditorMode::Prompt));
     /// ```
-    pub fn is_active(&self, current_mode: EditorMode) -> bool {
+    pub fn is_active(&self, current_mode: &EditorMode) -> bool {
         match self {
             BindingContext::Global => {
                 // Global bindings exclude Prompt mode
-                current_mode != EditorMode::Prompt
+                *current_mode != EditorMode::Prompt
             }
-            BindingContext::Mode(mode) => *mode == current_mode,
-            BindingContext::Modes(modes) => modes.contains(&current_mode),
+            BindingContext::Mode(mode) => mode == current_mode,
+            BindingContext::Modes(modes) => modes.contains(current_mode),
             BindingContext::Plugin { modes, .. } => match modes {
                 None => true, // Active in all modes
-                Some(mode_list) => mode_list.contains(&current_mode),
+                Some(mode_list) => mode_list.contains(current_mode),
             },
         }
     }
 }
 
+impl fmt::Display for BindingContext {
+    /// Formats the context as the modes it is active in, e.g. `global` or `normal`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modes = |modes: &[EditorMode]| {
+            modes
+                .iter()
+                .map(EditorMode::name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match self {
+            BindingContext::Global => write!(f, "global"),
+            BindingContext::Mode(mode) => write!(f, "{}", mode.name()),
+            BindingContext::Modes(list) => write!(f, "{}", modes(list)),
+            BindingContext::Plugin { name, modes: None } => write!(f, "plugin {}: all modes", name),
+            BindingContext::Plugin {
+                name,
+                modes: Some(list),
+            } => write!(f, "plugin {}: {}", name, modes(list)),
+        }
+    }
+}
+
 /// Priority for keybinding conflict resolution
 ///
 /// When multiple bindings match the same key sequence in the same context,
@@ -796,6 +1088,7 @@ impl BindingContext {
 /// - `Default` (0): Built-in editor bindings
 /// - `Plugin` (10): Plugin-defined bindings
 /// - `User` (20): User customizations
+/// - `Project` (30): Bindings from a project's `.termide.toml`
 ///
 /// Higher numeric values have higher priority.
 ///
@@ -805,6 +1098,7 @@ impl BindingContext {
 /// use termide::input::keybinding::Priority;
 ///
 /// // Priority ordering
+/// assert!(Priority::Project > Priority::User);
 /// assert!(Priority::User > Priority::Plugin);
 /// assert!(Priority::Plugin > Priority::Default);
 ///
@@ -812,6 +1106,7 @@ impl BindingContext {
 /// assert_eq!(Priority::Default as u8, 0);
 /// assert_eq!(Priority::Plugin as u8, 10);
 /// assert_eq!(Priority::User as u8, 20);
+/// assert_eq!(Priority::Project as u8, 30);
 /// ```
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -822,6 +1117,20 @@ pub enum Priority {
     Plugin = 10,
     /// User customizations (priority 20)
     User = 20,
+    /// Project configuration, overriding the user's bindings (priority 30)
+    Project = 30,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Priority::Default => "default",
+            Priority::Plugin => "plugin",
+            Priority::User => "user",
+            Priority::Project => "project",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 /// A complete keybinding that maps a sequence to a command in a specific context
@@ -852,8 +1161,8 @@ pub enum Priority {
 ///
 /// // Access binding properties
 /// assert_eq!(insert_binding.priority(), Priority::Default);
-/// assert!(insert_binding.context().is_active(EditorMode::Normal));
-/// assert!(!insert_binding.context().is_active(EditorMode::Insert));
+/// assert!(insert_binding.context().is_active(&EditorMode::Normal));
+/// assert!(!insert_binding.context().is_active(&EditorMode::Insert));
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct KeyBinding {
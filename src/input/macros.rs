@@ -0,0 +1,239 @@
+//! Keyboard macro recording and playback
+//!
+//! A macro is the list of [`EditorCommand`]s produced while recording, not the
+//! raw keys, so it replays the same way regardless of the keymap in use.
+//! Macros live in registers named by a letter or digit and are persisted in a
+//! TOML file:
+//!
+//! ```toml
+//! [registers]
+//! q = ["move.line_start", { command = "insert_text", args = { text = "// " } }, "move.down"]
+//! ```
+
+use crate::input::command::MAX_COUNT;
+use crate::input::config::{CommandStep, ConfigError};
+use crate::input::EditorCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// On-disk layout of the macro file
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct MacroFile {
+    /// Commands of each register
+    #[serde(default)]
+    registers: BTreeMap<String, Vec<CommandStep>>,
+}
+
+/// Macro registers plus the recording and playback in progress
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::macros::Macros;
+/// use termide::input::EditorCommand;
+///
+/// let mut macros = Macros::new();
+/// macros.start_recording('q');
+/// macros.record(&EditorCommand::InsertChar('x'));
+/// assert_eq!(macros.stop_recording(), Some(('q', 1)));
+/// assert_eq!(macros.get('q'), Some(&[EditorCommand::InsertChar('x')][..]));
+/// ```
+#[derive(Debug, Default)]
+pub struct Macros {
+    /// Recorded commands by register
+    registers: BTreeMap<char, Vec<EditorCommand>>,
+    /// Register and commands of the recording in progress
+    recording: Option<(char, Vec<EditorCommand>)>,
+    /// Registers currently being played, innermost last
+    playing: Vec<char>,
+    /// File the registers are saved to after each recording
+    path: Option<PathBuf>,
+}
+
+impl Macros {
+    /// Creates empty registers that are not persisted
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the registers saved at `path` and persists future recordings there
+    ///
+    /// A missing file gives empty registers. Entries that no longer parse
+    /// (e.g. after a command was renamed) are skipped with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::ReadError` or `ConfigError::TomlParseError` if the
+    /// file exists but cannot be read or parsed.
+    pub fn load(path: PathBuf) -> Result<(Self, Vec<String>), ConfigError> {
+        let mut macros = Self {
+            path: Some(path.clone()),
+            ..Self::default()
+        };
+        if !path.exists() {
+            return Ok((macros, Vec::new()));
+        }
+
+        let content = fs::read_to_string(&path).map_err(|source| ConfigError::ReadError {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let file: MacroFile = toml::from_str(&content).map_err(|source| ConfigError::TomlParseError {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        let mut warnings = Vec::new();
+        for (name, steps) in file.registers {
+            let register = match register_name(&name) {
+                Some(register) => register,
+                None => {
+                    warnings.push(format!("macro register '{}': expected a letter or digit", name));
+                    continue;
+                }
+            };
+            match steps.iter().map(CommandStep::parse).collect() {
+                Ok(commands) => {
+                    macros.registers.insert(register, commands);
+                }
+                Err(e) => warnings.push(format!("macro register '{}': {}", name, e)),
+            }
+        }
+
+        Ok((macros, warnings))
+    }
+
+    /// Writes the registers to the file given to [`load`](Self::load)
+    ///
+    /// Does nothing for registers created with [`new`](Self::new).
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = MacroFile {
+            registers: self
+                .registers
+                .iter()
+                .map(|(register, commands)| {
+                    (register.to_string(), commands.iter().map(CommandStep::from_command).collect())
+                })
+                .collect(),
+        };
+        let content = toml::to_string(&file).map_err(std::io::Error::other)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    }
+
+    /// Starts recording into `register`, discarding a recording in progress
+    pub fn start_recording(&mut self, register: char) {
+        self.recording = Some((register, Vec::new()));
+    }
+
+    /// Stops recording and stores the commands in their register
+    ///
+    /// Returns the register and the number of recorded commands, or `None` if
+    /// nothing was being recorded.
+    pub fn stop_recording(&mut self) -> Option<(char, usize)> {
+        let (register, commands) = self.recording.take()?;
+        let count = commands.len();
+        self.registers.insert(register, commands);
+        Some((register, count))
+    }
+
+    /// Returns the register being recorded into
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    /// Appends `command` to the recording in progress
+    ///
+    /// Recording commands themselves are never recorded.
+    pub fn record(&mut self, command: &EditorCommand) {
+        if let Some((_, commands)) = &mut self.recording {
+            if !matches!(command, EditorCommand::ToggleMacroRecording(_)) {
+                commands.push(command.clone());
+            }
+        }
+    }
+
+    /// Returns the commands in `register`
+    pub fn get(&self, register: char) -> Option<&[EditorCommand]> {
+        self.registers.get(&register).map(Vec::as_slice)
+    }
+
+    /// Stores `commands` in `register`
+    pub fn set(&mut self, register: char, commands: Vec<EditorCommand>) {
+        self.registers.insert(register, commands);
+    }
+
+    /// Marks `register` as playing
+    ///
+    /// Returns `false` if it is already playing, i.e. the macro plays itself.
+    pub fn start_playback(&mut self, register: char) -> bool {
+        if self.playing.contains(&register) {
+            return false;
+        }
+        self.playing.push(register);
+        true
+    }
+
+    /// Ends the innermost playback started with [`start_playback`](Self::start_playback)
+    pub fn finish_playback(&mut self) {
+        self.playing.pop();
+    }
+}
+
+/// Returns `commands` repeated `count` times, for playing a macro `count` times
+///
+/// # Errors
+///
+/// Returns a message if `count` exceeds the limit of the `count` argument
+/// (10000); commands built from config are checked when parsed, but
+/// [`EditorCommand::PlayMacro`] can also be built in code.
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::macros::repeat_commands;
+/// use termide::input::EditorCommand;
+///
+/// let commands = [EditorCommand::InsertChar('x')];
+/// assert_eq!(repeat_commands(&commands, 2).unwrap().len(), 2);
+/// assert!(repeat_commands(&commands, usize::MAX).is_err());
+/// ```
+pub fn repeat_commands(commands: &[EditorCommand], count: usize) -> Result<Vec<EditorCommand>, String> {
+    let total = commands.len().checked_mul(count).filter(|_| count <= MAX_COUNT);
+    let Some(total) = total else {
+        return Err(format!("Macro count {} exceeds the limit of {}", count, MAX_COUNT));
+    };
+
+    let mut repeated = Vec::with_capacity(total);
+    for _ in 0..count {
+        repeated.extend_from_slice(commands);
+    }
+    Ok(repeated)
+}
+
+/// Parses a register name from the macro file
+fn register_name(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_alphanumeric() => Some(ch.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
+/// Returns the path of the macro file
+///
+/// # Platform Behavior
+///
+/// - **Linux**: `$XDG_DATA_HOME/termide/macros.toml` or `~/.local/share/termide/macros.toml`
+/// - **macOS**: `~/Library/Application Support/termide/macros.toml`
+/// - **Windows**: `%APPDATA%\termide\macros.toml`
+pub fn get_macros_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|path| path.join("termide").join("macros.toml"))
+}
@@ -9,6 +9,11 @@
 //! - `InputHandler`: Main entry point for processing keyboard events
 //! - `KeyBindingRegistry`: Stores and matches keybindings with priority ordering
 //! - `EditorCommand`: Commands that can be executed by the editor
+//! - `introspection`: Keybinding list and "describe key" reports
+//! - `user_commands`: Named command chains from the `[commands]` config section
+//! - `macros`: Keyboard macro registers, recording and persistence
+//! - `palette`: Entries of the command palette
+//! - `command_line`: Parsing and completion of `:` commands
 //! - Default bindings: Registered automatically at startup
 //!
 //! # Key Mappings
@@ -24,6 +29,7 @@
 //!
 //! ## Normal Mode
 //! - `i` → Switch to Insert mode
+//! - `:` → Open the command line
 //! - Arrow keys → Move cursor
 //! - `Ctrl+S` → Save file
 //! - `Ctrl+Q` → Quit editor
@@ -31,6 +37,7 @@
 //! ## Prompt Mode
 //! - Printable characters → Insert into prompt
 //! - `Backspace` → Delete from prompt
+//! - `Tab` → Complete the command or file name
 //! - `Enter` → Accept prompt
 //! - `Esc` → Cancel prompt
 //!
@@ -45,7 +52,7 @@
 //! // Create and use the input handler
 //! let mut handler = InputHandler::new();
 //! let event = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
-//! let result = handler.process_key_event(event, EditorMode::Normal);
+//! let result = handler.process_key_event(event, &EditorMode::Normal);
 //!
 //! match result {
 //!     MatchResult::Matched(cmd) => {
@@ -58,19 +65,28 @@
 //!     MatchResult::NoMatch => {
 //!         // No binding found, use default behavior
 //!     }
+//!     MatchResult::Ignored => {
+//!         // Key release nothing is bound to
+//!     }
 //! }
 //! ```
 
 pub mod bindings;
 mod command;
+pub mod command_line;
 pub mod config;
+pub mod config_layers;
 mod direction;
 pub mod input_handler;
+pub mod introspection;
 pub mod keybinding;
+pub mod macros;
+pub mod palette;
 pub mod registry;
+pub mod user_commands;
 pub mod watcher;
 
-pub use command::{EditorCommand, CommandParseError};
+pub use command::{EditorCommand, CommandParseError, COMMAND_NAMES, DEFAULT_MACRO_REGISTER};
 pub use direction::Direction;
 pub use keybinding::ParseError;
 
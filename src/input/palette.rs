@@ -0,0 +1,107 @@
+//! Command palette entries
+//!
+//! The command palette lists the commands that run without arguments: the
+//! built-in commands by their [`COMMAND_NAMES`], the user commands from the
+//! `[commands]` config section, and the plugin commands declared in manifests
+//! or bound to keys. Each entry shows the key sequences that run it in the
+//! mode the palette was opened from.
+//!
+//! The prompt commands and the palette itself are left out, since the
+//! palette is a prompt already.
+
+use crate::editor::{EditorMode, PromptList};
+use crate::input::registry::KeyBindingRegistry;
+use crate::input::user_commands::UserCommands;
+use crate::input::{EditorCommand, COMMAND_NAMES};
+
+/// A command listed in the command palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    /// Config name of the command, which parses back into it
+    pub name: String,
+    /// The command run when the entry is picked
+    pub command: EditorCommand,
+    /// Key sequences bound to the command, e.g. `["Ctrl+S"]`
+    pub keys: Vec<String>,
+}
+
+/// Returns the palette entries for `mode`
+///
+/// Built-in commands come first, then user commands in alphabetical order,
+/// then plugin commands: those in `plugin_commands` followed by the ones
+/// only known from their bindings.
+///
+/// # Examples
+///
+/// ```
+/// use termide::editor::EditorMode;
+/// use termide::input::bindings::register_default_bindings;
+/// use termide::input::palette::palette_entries;
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide::input::user_commands::UserCommands;
+///
+/// let mut registry = KeyBindingRegistry::default();
+/// register_default_bindings(&mut registry).unwrap();
+///
+/// let entries = palette_entries(&registry, &UserCommands::new(), &[], &EditorMode::Insert);
+/// let save = entries.iter().find(|entry| entry.name == "file.save").unwrap();
+/// assert!(save.keys.contains(&"Ctrl+s".to_string()) || save.keys.contains(&"Cmd+s".to_string()));
+/// assert!(entries.iter().all(|entry| !entry.name.starts_with("prompt.")));
+/// ```
+pub fn palette_entries(
+    registry: &KeyBindingRegistry,
+    user_commands: &UserCommands,
+    plugin_commands: &[EditorCommand],
+    mode: &EditorMode,
+) -> Vec<PaletteEntry> {
+    let builtin = COMMAND_NAMES
+        .iter()
+        .filter(|name| is_listed(name))
+        .filter_map(|name| Some((name.to_string(), name.parse::<EditorCommand>().ok()?)));
+    let user = user_commands
+        .names()
+        .map(|name| EditorCommand::UserCommand(name.to_string()));
+    let bound_plugin = registry
+        .bindings()
+        .iter()
+        .map(|binding| binding.command())
+        .filter(|command| matches!(command, EditorCommand::PluginCommand { .. }))
+        .cloned();
+    // Other commands are displayed by their config name
+    let named = user
+        .chain(plugin_commands.iter().cloned())
+        .chain(bound_plugin)
+        .map(|command| (command.to_string(), command));
+
+    let mut entries: Vec<PaletteEntry> = Vec::new();
+    for (name, command) in builtin.chain(named) {
+        if entries.iter().any(|entry| entry.command == command) {
+            continue;
+        }
+        let mut keys: Vec<String> = Vec::new();
+        for sequence in registry.sequences_for(&command, mode) {
+            let sequence = sequence.to_string();
+            if !keys.contains(&sequence) {
+                keys.push(sequence);
+            }
+        }
+        entries.push(PaletteEntry {
+            name,
+            command,
+            keys,
+        });
+    }
+    entries
+}
+
+/// Returns the prompt list showing `entries`, with their keys as details
+pub fn palette_list(entries: &[PaletteEntry]) -> PromptList {
+    let names = entries.iter().map(|entry| entry.name.clone()).collect();
+    let keys = entries.iter().map(|entry| entry.keys.join(", ")).collect();
+    PromptList::new(names).with_details(keys)
+}
+
+/// Returns `true` if the built-in command `name` belongs in the palette
+fn is_listed(name: &str) -> bool {
+    !(name.starts_with("prompt.") || matches!(name, "mode.prompt" | "palette.open" | "noop"))
+}
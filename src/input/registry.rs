@@ -111,12 +111,13 @@
 //! // User binding shadows the default binding
 //! ```
 
-use crate::editor::EditorMode;
+use crate::editor::{EditorMode, ParseModeError, PluginMode};
+use crate::input::command::CommandParseError;
 use crate::input::keybinding::{BindingContext, KeyBinding, KeyPattern, KeySequence, Priority};
 use crate::input::EditorCommand;
 use std::str::FromStr;
 use std::time::{Duration, Instant};
-use termide_plugin_api::input::{PluginBinding, PluginInputExtension};
+use termide_plugin_api::input::{PluginBinding, PluginInputExtension, PluginMode as ApiPluginMode};
 use thiserror::Error;
 
 /// Errors that can occur during keybinding registration or management
@@ -142,6 +143,45 @@ pub enum BindingError {
     InvalidContext(String),
 }
 
+/// What a key sequence resolves to in one mode
+///
+/// Returned by [`KeyBindingRegistry::describe_sequence`] to answer "what does this
+/// key do here?".
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceDescription<'a> {
+    /// The binding that fires for the sequence, if any
+    pub binding: Option<&'a KeyBinding>,
+    /// Active bindings for the same sequence that lose to `binding`
+    pub shadowed: Vec<&'a KeyBinding>,
+    /// Active bindings that the sequence is a proper prefix of
+    pub continuations: Vec<&'a KeyBinding>,
+}
+
+/// A binding that never fires because another binding for the same sequence wins
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowedBinding<'a> {
+    /// The binding that is overridden
+    pub binding: &'a KeyBinding,
+    /// The binding that fires instead
+    pub by: &'a KeyBinding,
+    /// Modes in which `by` wins over `binding`
+    pub modes: Vec<EditorMode>,
+}
+
+/// A multi-key binding that cannot be typed because a shorter binding fires first
+///
+/// With `g` and `g d` both bound, pressing `g` executes the `g` binding
+/// immediately, so `g d` is unreachable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefixConflict<'a> {
+    /// The binding for the prefix, which fires as soon as it is typed
+    pub prefix: &'a KeyBinding,
+    /// The longer binding that can never complete
+    pub unreachable: &'a KeyBinding,
+    /// Modes in which the conflict occurs
+    pub modes: Vec<EditorMode>,
+}
+
 /// Central registry for storing and managing keybindings
 ///
 /// The registry maintains bindings in priority-sorted order (descending) for efficient
@@ -174,6 +214,8 @@ pub struct KeyBindingRegistry {
     last_key_time: Instant,
     /// Timeout duration for sequence completion
     timeout: Duration,
+    /// Modes registered by plugins
+    modes: Vec<PluginMode>,
 }
 
 impl KeyBindingRegistry {
@@ -201,6 +243,7 @@ impl KeyBindingRegistry {
             sequence_buffer: Vec::new(),
             last_key_time: Instant::now(),
             timeout,
+            modes: Vec::new(),
         }
     }
 
@@ -371,6 +414,149 @@ impl KeyBindingRegistry {
         original_len - self.bindings.len()
     }
 
+    /// Removes all plugin-priority bindings to commands of `plugin_name`
+    ///
+    /// Returns the number of bindings removed. Used to undo the registration
+    /// of a plugin that failed to load.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide_plugin_api::input::{PluginInputExtension, PluginBindingBuilder};
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// let binding = PluginBindingBuilder::new("lint").bind("Ctrl+L", "run").build().unwrap();
+    /// registry.register_keybinding(binding).unwrap();
+    ///
+    /// assert_eq!(registry.unregister_plugin("other"), 0);
+    /// assert_eq!(registry.unregister_plugin("lint"), 1);
+    /// assert!(registry.is_empty());
+    /// ```
+    pub fn unregister_plugin(&mut self, plugin_name: &str) -> usize {
+        self.modes.retain(|mode| mode.plugin() != plugin_name);
+        let original_len = self.bindings.len();
+        self.bindings.retain(|binding| {
+            binding.priority() != Priority::Plugin
+                || !matches!(
+                    binding.command(),
+                    EditorCommand::PluginCommand { plugin_name: name, .. } if name == plugin_name
+                )
+        });
+        original_len - self.bindings.len()
+    }
+
+    /// Returns the plugin mode named `name`, if a plugin registered it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide_plugin_api::input::{PluginInputExtension, PluginMode};
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// registry.register_mode(PluginMode::new("repl", "eval").with_label("REPL")).unwrap();
+    ///
+    /// let eval = registry.plugin_mode("repl.eval").unwrap();
+    /// assert_eq!(eval.label(), "REPL");
+    /// assert!(registry.plugin_mode("repl.other").is_none());
+    /// ```
+    pub fn plugin_mode(&self, name: &str) -> Option<PluginMode> {
+        self.modes.iter().find(|mode| mode.name() == name).cloned()
+    }
+
+    /// Returns every core mode followed by the modes plugins have registered
+    pub fn modes(&self) -> Vec<EditorMode> {
+        EditorMode::ALL
+            .into_iter()
+            .chain(self.modes.iter().cloned().map(EditorMode::Plugin))
+            .collect()
+    }
+
+    /// Parses a core mode name or the name of a registered plugin mode
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for names that are neither.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::editor::EditorMode;
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide_plugin_api::input::{PluginInputExtension, PluginMode};
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// registry.register_mode(PluginMode::new("git", "blame")).unwrap();
+    ///
+    /// assert_eq!(registry.parse_mode("NORMAL"), Ok(EditorMode::Normal));
+    /// assert_eq!(registry.parse_mode(" git.blame ").unwrap().name(), "git.blame");
+    /// assert!(registry.parse_mode("git.other").is_err());
+    /// ```
+    pub fn parse_mode(&self, s: &str) -> Result<EditorMode, ParseModeError> {
+        EditorMode::from_str(s).or_else(|e| {
+            self.plugin_mode(s.trim())
+                .map(EditorMode::Plugin)
+                .ok_or(e)
+        })
+    }
+
+    /// Parses a command, including `mode.<plugin>.<mode>` for the registered
+    /// plugin modes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error as [`EditorCommand::from_str`].
+    pub fn parse_command(&self, s: &str) -> Result<EditorCommand, CommandParseError> {
+        match s.trim().strip_prefix("mode.").and_then(|name| self.plugin_mode(name)) {
+            Some(mode) => Ok(EditorCommand::ChangeMode(EditorMode::Plugin(mode))),
+            None => EditorCommand::from_str(s),
+        }
+    }
+
+    /// Converts a plugin API mode, which fails for unregistered plugin modes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the mode if it is an unregistered plugin mode.
+    pub fn resolve_mode(
+        &self,
+        mode: termide_plugin_api::input::EditorMode,
+    ) -> Result<EditorMode, ParseModeError> {
+        match mode {
+            termide_plugin_api::input::EditorMode::Insert => Ok(EditorMode::Insert),
+            termide_plugin_api::input::EditorMode::Normal => Ok(EditorMode::Normal),
+            termide_plugin_api::input::EditorMode::Prompt => Ok(EditorMode::Prompt),
+            termide_plugin_api::input::EditorMode::Plugin(name) => self
+                .plugin_mode(&name)
+                .map(EditorMode::Plugin)
+                .ok_or(ParseModeError { invalid: name }),
+        }
+    }
+
+    /// Returns the timeout after which incomplete sequences are cleared
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Changes the timeout for incomplete sequences
+    ///
+    /// Takes effect for the sequence currently being typed as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use std::time::Duration;
+    ///
+    /// let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    /// registry.set_timeout(Duration::from_millis(500));
+    /// assert_eq!(registry.timeout(), Duration::from_millis(500));
+    /// ```
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
     /// Returns the number of registered bindings
     ///
     /// # Examples
@@ -401,6 +587,255 @@ impl KeyBindingRegistry {
         self.bindings.is_empty()
     }
 
+    /// Returns all registered bindings in lookup order (highest priority first)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide::input::bindings::register_default_bindings;
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// register_default_bindings(&mut registry).unwrap();
+    /// assert_eq!(registry.bindings().len(), registry.len());
+    /// ```
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// Returns `true` if any binding's sequence contains `key`
+    ///
+    /// Used to tell whether key releases, repeats or keypad keys need to be
+    /// distinguished from plain presses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide::input::bindings::register_default_bindings;
+    /// use termide::input::keybinding::KeyPattern;
+    /// use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// register_default_bindings(&mut registry).unwrap();
+    /// let esc = KeyPattern::new(KeyCode::Esc, KeyModifiers::NONE);
+    /// assert!(registry.uses_key(&esc));
+    /// assert!(!registry.uses_key(&esc.with_kind(KeyEventKind::Release)));
+    /// ```
+    pub fn uses_key(&self, key: &KeyPattern) -> bool {
+        self.bindings
+            .iter()
+            .any(|binding| binding.sequence().patterns().contains(key))
+    }
+
+    /// Describes what a key sequence resolves to in the given mode
+    ///
+    /// Reports the binding that fires for exactly `sequence`, the active bindings it
+    /// shadows, and the longer bindings that `sequence` is the start of. Does not
+    /// touch the sequence buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide::input::bindings::register_default_bindings;
+    /// use termide::input::keybinding::KeyPattern;
+    /// use termide::input::EditorCommand;
+    /// use termide::editor::EditorMode;
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// register_default_bindings(&mut registry).unwrap();
+    ///
+    /// let ctrl_s = KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    /// let description = registry.describe_sequence(&[ctrl_s], &EditorMode::Insert);
+    /// assert_eq!(description.binding.map(|b| b.command()), Some(&EditorCommand::Save));
+    /// ```
+    pub fn describe_sequence(&self, sequence: &[KeyPattern], mode: &EditorMode) -> SequenceDescription<'_> {
+        let mut exact = self
+            .active_bindings(mode)
+            .filter(|binding| binding.sequence().matches(sequence));
+        let binding = exact.next();
+        let shadowed = exact.collect();
+        let continuations = self
+            .active_bindings(mode)
+            .filter(|binding| binding.sequence().is_partial_match(sequence))
+            .collect();
+
+        SequenceDescription {
+            binding,
+            shadowed,
+            continuations,
+        }
+    }
+
+    /// Returns the sequences that run `command` in `mode`, in lookup order
+    ///
+    /// Sequences taken over by another binding in `mode` are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide::input::bindings::register_default_bindings;
+    /// use termide::input::EditorCommand;
+    /// use termide::editor::EditorMode;
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// register_default_bindings(&mut registry).unwrap();
+    ///
+    /// let sequences = registry.sequences_for(&EditorCommand::DescribeKey, &EditorMode::Normal);
+    /// assert_eq!(sequences[0].to_string(), "Shift+F1");
+    /// assert!(registry.sequences_for(&EditorCommand::DescribeKey, &EditorMode::Prompt).is_empty());
+    /// ```
+    pub fn sequences_for(&self, command: &EditorCommand, mode: &EditorMode) -> Vec<&KeySequence> {
+        self.active_bindings(mode)
+            .filter(|binding| binding.command() == command)
+            .filter(|binding| {
+                self.active_bindings(mode)
+                    .find(|other| other.sequence() == binding.sequence())
+                    .is_some_and(|winner| std::ptr::eq(winner, *binding))
+            })
+            .map(KeyBinding::sequence)
+            .collect()
+    }
+
+    /// Returns the bindings that are overridden by another binding for the same sequence
+    ///
+    /// A binding is shadowed in a mode when it is active there but a binding that
+    /// comes earlier in lookup order (higher priority, or registered first at the
+    /// same priority) matches the same sequence. Bindings that are only shadowed in
+    /// some of their modes are reported with just those modes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide::input::keybinding::{KeyBinding, KeyPattern, KeySequence, BindingContext, Priority};
+    /// use termide::input::EditorCommand;
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// let sequence = KeySequence::new(vec![
+    ///     KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+    /// ]).unwrap();
+    /// registry.register(KeyBinding::new(
+    ///     sequence.clone(), EditorCommand::Save, BindingContext::Global, Priority::Default,
+    /// )).unwrap();
+    /// registry.register(KeyBinding::new(
+    ///     sequence, EditorCommand::Quit, BindingContext::Global, Priority::User,
+    /// )).unwrap();
+    ///
+    /// let shadowed = registry.shadowed_bindings();
+    /// assert_eq!(shadowed.len(), 1);
+    /// assert_eq!(shadowed[0].binding.command(), &EditorCommand::Save);
+    /// assert_eq!(shadowed[0].by.command(), &EditorCommand::Quit);
+    /// ```
+    pub fn shadowed_bindings(&self) -> Vec<ShadowedBinding<'_>> {
+        let mut shadowed: Vec<ShadowedBinding<'_>> = Vec::new();
+
+        for binding in &self.bindings {
+            for mode in self.modes() {
+                if !binding.context().is_active(&mode) {
+                    continue;
+                }
+                let winner = self
+                    .active_bindings(&mode)
+                    .find(|other| other.sequence() == binding.sequence())
+                    .expect("an active binding always matches its own sequence");
+                if std::ptr::eq(winner, binding) {
+                    continue;
+                }
+                match shadowed
+                    .iter_mut()
+                    .find(|entry| std::ptr::eq(entry.binding, binding) && std::ptr::eq(entry.by, winner))
+                {
+                    Some(entry) => entry.modes.push(mode),
+                    None => shadowed.push(ShadowedBinding {
+                        binding,
+                        by: winner,
+                        modes: vec![mode],
+                    }),
+                }
+            }
+        }
+
+        shadowed
+    }
+
+    /// Returns multi-key bindings that are unreachable because a prefix is bound
+    ///
+    /// A complete match fires as soon as it is typed, so when `g` is bound in a
+    /// mode, no longer sequence starting with `g` can be entered in that mode. The
+    /// reported prefix is the binding that actually fires: the shortest bound
+    /// prefix, resolved by lookup order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::input::registry::KeyBindingRegistry;
+    /// use termide::input::keybinding::{KeyBinding, KeyPattern, KeySequence, BindingContext, Priority};
+    /// use termide::input::EditorCommand;
+    /// use termide::editor::EditorMode;
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// let g = KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE);
+    /// let d = KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE);
+    /// let normal = BindingContext::Mode(EditorMode::Normal);
+    ///
+    /// let mut registry = KeyBindingRegistry::default();
+    /// registry.register(KeyBinding::new(
+    ///     KeySequence::new(vec![g]).unwrap(), EditorCommand::PageUp, normal.clone(), Priority::User,
+    /// )).unwrap();
+    /// registry.register(KeyBinding::new(
+    ///     KeySequence::new(vec![g, d]).unwrap(), EditorCommand::PageDown, normal, Priority::Default,
+    /// )).unwrap();
+    ///
+    /// let conflicts = registry.prefix_conflicts();
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert_eq!(conflicts[0].unreachable.command(), &EditorCommand::PageDown);
+    /// assert_eq!(conflicts[0].modes, vec![EditorMode::Normal]);
+    /// ```
+    pub fn prefix_conflicts(&self) -> Vec<PrefixConflict<'_>> {
+        let mut conflicts: Vec<PrefixConflict<'_>> = Vec::new();
+
+        for binding in self.bindings.iter().filter(|binding| binding.sequence().len() > 1) {
+            let patterns = binding.sequence().patterns();
+            for mode in self.modes() {
+                if !binding.context().is_active(&mode) {
+                    continue;
+                }
+                let prefix = (1..patterns.len()).find_map(|len| {
+                    self.active_bindings(&mode)
+                        .find(|other| other.sequence().matches(&patterns[..len]))
+                });
+                let Some(prefix) = prefix else {
+                    continue;
+                };
+                match conflicts.iter_mut().find(|entry| {
+                    std::ptr::eq(entry.prefix, prefix) && std::ptr::eq(entry.unreachable, binding)
+                }) {
+                    Some(entry) => entry.modes.push(mode),
+                    None => conflicts.push(PrefixConflict {
+                        prefix,
+                        unreachable: binding,
+                        modes: vec![mode],
+                    }),
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Bindings active in `mode`, in lookup order
+    fn active_bindings(&self, mode: &EditorMode) -> impl Iterator<Item = &KeyBinding> {
+        let mode = mode.clone();
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.context().is_active(&mode))
+    }
+
     /// Adds a key pattern to the sequence buffer
     ///
     /// This method is called for each key press to accumulate patterns for multi-key
@@ -474,14 +909,14 @@ impl KeyBindingRegistry {
     ///
     /// // Add first 'd' - no match yet
     /// registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    /// assert_eq!(registry.find_match(EditorMode::Normal), None);
+    /// assert_eq!(registry.find_match(&EditorMode::Normal), None);
     ///
     /// // Add second 'd' - now we have a match
     /// registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    /// assert!(registry.find_match(EditorMode::Normal).is_some());
+    /// assert!(registry.find_match(&EditorMode::Normal).is_some());
     /// ```
     #[inline]
-    pub fn find_match(&self, current_mode: EditorMode) -> Option<&EditorCommand> {
+    pub fn find_match(&self, current_mode: &EditorMode) -> Option<&EditorCommand> {
         // Filter bindings by active context, then find first exact match
         // Since bindings are priority-sorted, first match is highest priority
         self.bindings
@@ -532,20 +967,36 @@ impl KeyBindingRegistry {
     ///
     /// // Add first 'd' - this is a partial match
     /// registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    /// assert!(registry.is_partial_match(EditorMode::Normal));
+    /// assert!(registry.is_partial_match(&EditorMode::Normal));
     ///
     /// // Add second 'd' - no longer partial (it's complete)
     /// registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    /// assert!(!registry.is_partial_match(EditorMode::Normal));
+    /// assert!(!registry.is_partial_match(&EditorMode::Normal));
     /// ```
     #[inline]
-    pub fn is_partial_match(&self, current_mode: EditorMode) -> bool {
+    pub fn is_partial_match(&self, current_mode: &EditorMode) -> bool {
         self.bindings
             .iter()
             .filter(|binding| binding.context().is_active(current_mode))
             .any(|binding| binding.sequence().is_partial_match(&self.sequence_buffer))
     }
 
+    /// Returns the keys typed so far of an incomplete multi-key sequence
+    pub fn pending_sequence(&self) -> &[KeyPattern] {
+        &self.sequence_buffer
+    }
+
+    /// Returns how long the current sequence has been waiting for its next key
+    ///
+    /// Returns `None` when no sequence is pending.
+    pub fn pending_duration(&self) -> Option<Duration> {
+        if self.sequence_buffer.is_empty() {
+            None
+        } else {
+            Some(self.last_key_time.elapsed())
+        }
+    }
+
     /// Checks if the sequence buffer has timed out and clears it if so
     ///
     /// This method should be called periodically to prevent incomplete sequences
@@ -653,6 +1104,27 @@ impl Default for KeyBindingRegistry {
 ///
 /// registry.register_keybinding(binding).unwrap();
 /// ```
+///
+/// Bindings in plugin modes are registered with a [`BindingContext::Plugin`]
+/// context naming the plugin that owns the mode:
+///
+/// ```
+/// use termide::editor::EditorMode;
+/// use termide::input::keybinding::BindingContext;
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide_plugin_api::input::{PluginBindingBuilder, PluginInputExtension, PluginMode as ApiPluginMode};
+///
+/// let mut registry = KeyBindingRegistry::default();
+/// registry.register_mode(ApiPluginMode::new("doc-blame", "blame")).unwrap();
+/// let binding = PluginBindingBuilder::new("doc-blame").bind("q", "close").in_mode("blame").build().unwrap();
+/// registry.register_keybinding(binding).unwrap();
+///
+/// let blame = EditorMode::Plugin(registry.plugin_mode("doc-blame.blame").unwrap());
+/// assert_eq!(
+///     registry.bindings()[0].context(),
+///     &BindingContext::Plugin { name: "doc-blame".to_string(), modes: Some(vec![blame]) }
+/// );
+/// ```
 impl PluginInputExtension for KeyBindingRegistry {
     fn register_keybinding(
         &mut self,
@@ -693,7 +1165,7 @@ impl PluginInputExtension for KeyBindingRegistry {
         }
 
         // 4. Convert BindingContext from plugin API to core type
-        let context = convert_plugin_context_to_core(binding.context);
+        let context = convert_plugin_context_to_core(self, binding.context)?;
 
         // 5. Create KeyBinding with Plugin priority
         let key_binding = KeyBinding::new(sequence, command, context, Priority::Plugin);
@@ -747,36 +1219,78 @@ impl PluginInputExtension for KeyBindingRegistry {
             }
         })
     }
+
+    fn register_mode(
+        &mut self,
+        mode: ApiPluginMode,
+    ) -> Result<(), termide_plugin_api::input::BindingError> {
+        // Only namespaced names are plugin modes
+        match termide_plugin_api::input::EditorMode::from_str(&mode.name) {
+            Ok(termide_plugin_api::input::EditorMode::Plugin(name)) => {
+                let mode = PluginMode::new(&name, &mode.label);
+                match self.modes.iter_mut().find(|known| **known == mode) {
+                    Some(known) => *known = mode,
+                    None => self.modes.push(mode),
+                }
+                Ok(())
+            }
+            _ => Err(termide_plugin_api::input::BindingError::InvalidMode(mode.name)),
+        }
+    }
 }
 
 /// Converts a plugin API BindingContext to the core BindingContext
 ///
 /// This helper function maps between the plugin API types and internal core types.
+/// Contexts naming plugin modes become [`BindingContext::Plugin`] contexts of
+/// the plugin owning the first of them.
+///
+/// # Errors
+///
+/// Returns [`BindingError::InvalidMode`](termide_plugin_api::input::BindingError::InvalidMode)
+/// for plugin modes that are not registered.
 fn convert_plugin_context_to_core(
+    registry: &KeyBindingRegistry,
     plugin_context: termide_plugin_api::input::BindingContext,
-) -> BindingContext {
-    match plugin_context {
+) -> Result<BindingContext, termide_plugin_api::input::BindingError> {
+    let context = match plugin_context {
         termide_plugin_api::input::BindingContext::Global => BindingContext::Global,
         termide_plugin_api::input::BindingContext::Mode(mode) => {
-            BindingContext::Mode(convert_plugin_mode_to_core(mode))
+            BindingContext::Mode(convert_plugin_mode_to_core(registry, mode)?)
         }
         termide_plugin_api::input::BindingContext::Modes(modes) => {
             let core_modes = modes
                 .into_iter()
-                .map(convert_plugin_mode_to_core)
-                .collect();
+                .map(|mode| convert_plugin_mode_to_core(registry, mode))
+                .collect::<Result<_, _>>()?;
             BindingContext::Modes(core_modes)
         }
-    }
+    };
+
+    let modes = match context {
+        BindingContext::Mode(ref mode) => vec![mode.clone()],
+        BindingContext::Modes(ref modes) => modes.clone(),
+        _ => return Ok(context),
+    };
+    let owner = modes.iter().find_map(|mode| match mode {
+        EditorMode::Plugin(mode) => Some(mode.plugin()),
+        _ => None,
+    });
+    Ok(match owner {
+        Some(plugin) => BindingContext::Plugin {
+            name: plugin.to_string(),
+            modes: Some(modes),
+        },
+        None => context,
+    })
 }
 
 /// Converts a plugin API EditorMode to the core EditorMode
 fn convert_plugin_mode_to_core(
+    registry: &KeyBindingRegistry,
     plugin_mode: termide_plugin_api::input::EditorMode,
-) -> EditorMode {
-    match plugin_mode {
-        termide_plugin_api::input::EditorMode::Insert => EditorMode::Insert,
-        termide_plugin_api::input::EditorMode::Normal => EditorMode::Normal,
-        termide_plugin_api::input::EditorMode::Prompt => EditorMode::Prompt,
-    }
+) -> Result<EditorMode, termide_plugin_api::input::BindingError> {
+    registry
+        .resolve_mode(plugin_mode)
+        .map_err(|e| termide_plugin_api::input::BindingError::InvalidMode(e.invalid))
 }
@@ -2,10 +2,12 @@
 
 use crate::editor::EditorMode;
 use crate::input::bindings::{
-    arrow_key_navigation, global_bindings, insert_mode_bindings, normal_mode_bindings,
-    prompt_mode_bindings, register_default_bindings,
+    arrow_key_navigation, global_bindings, insert_mode_bindings, keymap_bindings,
+    normal_mode_bindings, preset_bindings, prompt_mode_bindings, register_default_bindings,
+    register_keymap, KeymapOptions, KeymapPreset,
 };
-use crate::input::keybinding::{BindingContext, Priority};
+use crate::input::keybinding::{BindingContext, KeyPattern, Priority};
+use crossterm::event::{KeyCode, KeyModifiers};
 use crate::input::registry::KeyBindingRegistry;
 use crate::input::{Direction, EditorCommand};
 use std::time::Duration;
@@ -28,9 +30,9 @@ fn test_global_bindings_active_in_correct_modes() {
         assert_eq!(binding.context(), &BindingContext::Global);
         assert_eq!(binding.priority(), Priority::Default);
         // Global bindings should be active in Insert and Normal, not in Prompt
-        assert!(binding.context().is_active(EditorMode::Insert));
-        assert!(binding.context().is_active(EditorMode::Normal));
-        assert!(!binding.context().is_active(EditorMode::Prompt));
+        assert!(binding.context().is_active(&EditorMode::Insert));
+        assert!(binding.context().is_active(&EditorMode::Normal));
+        assert!(!binding.context().is_active(&EditorMode::Prompt));
     }
 }
 
@@ -78,9 +80,9 @@ fn test_normal_mode_i_only_active_in_normal() {
         .find(|b| matches!(b.command(), EditorCommand::ChangeMode(EditorMode::Insert)))
         .expect("'i' binding should exist");
 
-    assert!(i_binding.context().is_active(EditorMode::Normal));
-    assert!(!i_binding.context().is_active(EditorMode::Insert));
-    assert!(!i_binding.context().is_active(EditorMode::Prompt));
+    assert!(i_binding.context().is_active(&EditorMode::Normal));
+    assert!(!i_binding.context().is_active(&EditorMode::Insert));
+    assert!(!i_binding.context().is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -101,9 +103,9 @@ fn test_prompt_bindings_only_active_in_prompt() {
     let bindings = prompt_mode_bindings();
 
     for binding in bindings {
-        assert!(!binding.context().is_active(EditorMode::Insert));
-        assert!(!binding.context().is_active(EditorMode::Normal));
-        assert!(binding.context().is_active(EditorMode::Prompt));
+        assert!(!binding.context().is_active(&EditorMode::Insert));
+        assert!(!binding.context().is_active(&EditorMode::Normal));
+        assert!(binding.context().is_active(&EditorMode::Prompt));
     }
 }
 
@@ -159,9 +161,9 @@ fn test_arrow_keys_active_in_insert_and_normal() {
         .expect("Arrow key binding should exist");
 
     // Should be active in both Insert and Normal modes
-    assert!(arrow_binding.context().is_active(EditorMode::Insert));
-    assert!(arrow_binding.context().is_active(EditorMode::Normal));
-    assert!(!arrow_binding.context().is_active(EditorMode::Prompt));
+    assert!(arrow_binding.context().is_active(&EditorMode::Insert));
+    assert!(arrow_binding.context().is_active(&EditorMode::Normal));
+    assert!(!arrow_binding.context().is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -176,3 +178,86 @@ fn test_all_default_bindings_use_default_priority() {
         assert_eq!(binding.priority(), Priority::Default, "All default bindings should have Priority::Default");
     }
 }
+
+const PRESETS: [KeymapPreset; 4] = [
+    KeymapPreset::Default,
+    KeymapPreset::Vim,
+    KeymapPreset::Emacs,
+    KeymapPreset::Vscode,
+];
+
+#[test]
+fn test_every_keymap_registers_without_conflicts() {
+    for preset in PRESETS {
+        for disable_defaults in [false, true] {
+            let options = KeymapOptions { preset, disable_defaults };
+            let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+            register_keymap(&mut registry, options).unwrap();
+
+            assert!(registry.shadowed_bindings().is_empty(), "{:?}", options);
+            assert!(registry.prefix_conflicts().is_empty(), "{:?}", options);
+            assert!(registry.bindings().iter().all(|b| b.priority() == Priority::Default));
+        }
+    }
+}
+
+#[test]
+fn test_default_keymap_matches_default_bindings() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    register_default_bindings(&mut registry).unwrap();
+
+    assert_eq!(registry.bindings().len(), keymap_bindings(KeymapOptions::default()).len());
+}
+
+#[test]
+fn test_disable_defaults_keeps_prompt_and_preset_bindings() {
+    let bindings = keymap_bindings(KeymapOptions {
+        preset: KeymapPreset::Vim,
+        disable_defaults: true,
+    });
+
+    assert_eq!(
+        bindings.len(),
+        prompt_mode_bindings().len() + preset_bindings(KeymapPreset::Vim).len()
+    );
+    assert!(!bindings.iter().any(|b| b.command() == &EditorCommand::Save));
+    assert!(bindings.iter().any(|b| b.command() == &EditorCommand::AcceptPrompt));
+}
+
+#[test]
+fn test_emacs_preset_replaces_clashing_defaults() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    register_keymap(
+        &mut registry,
+        KeymapOptions {
+            preset: KeymapPreset::Emacs,
+            disable_defaults: false,
+        },
+    )
+    .unwrap();
+
+    // Ctrl+x Ctrl+s saves, and the default Ctrl+S still works
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    assert!(registry.is_partial_match(&EditorMode::Insert));
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    assert_eq!(registry.find_match(&EditorMode::Insert), Some(&EditorCommand::Save));
+    registry.clear_sequence();
+
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('f'), KeyModifiers::CONTROL));
+    assert_eq!(
+        registry.find_match(&EditorMode::Normal),
+        Some(&EditorCommand::MoveCursor(Direction::Right))
+    );
+}
+
+#[test]
+fn test_keymap_preset_deserializes_lowercase_names() {
+    let options: KeymapOptions = toml::from_str("preset = \"vscode\"").unwrap();
+    assert_eq!(options.preset, KeymapPreset::Vscode);
+    assert!(!options.disable_defaults);
+    assert_eq!(options.preset.to_string(), "vscode");
+
+    assert!(toml::from_str::<KeymapOptions>("preset = \"nano\"").is_err());
+    assert!(toml::from_str::<KeymapOptions>("presets = \"vim\"").is_err());
+}
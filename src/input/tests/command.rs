@@ -1,7 +1,7 @@
 //! Unit tests for EditorCommand string parsing
 
 use crate::editor::EditorMode;
-use crate::input::{CommandParseError, Direction, EditorCommand};
+use crate::input::{CommandParseError, Direction, EditorCommand, COMMAND_NAMES};
 use std::str::FromStr;
 
 // ============================================================================
@@ -18,6 +18,65 @@ fn test_parse_save_command() {
     assert_eq!(cmd, EditorCommand::Save);
 }
 
+#[test]
+fn test_parse_save_with_sudo_command() {
+    let cmd = EditorCommand::from_str("file.save_sudo").unwrap();
+    assert_eq!(cmd, EditorCommand::SaveWithSudo);
+
+    // Alternative forms
+    assert_eq!(EditorCommand::from_str("save_sudo").unwrap(), EditorCommand::SaveWithSudo);
+    assert_eq!(EditorCommand::from_str("sudo_save").unwrap(), EditorCommand::SaveWithSudo);
+}
+
+#[test]
+fn test_parse_toggle_readonly_command() {
+    let cmd = EditorCommand::from_str("buffer.toggle_readonly").unwrap();
+    assert_eq!(cmd, EditorCommand::ToggleReadOnly);
+
+    let cmd = EditorCommand::from_str("toggle_readonly").unwrap();
+    assert_eq!(cmd, EditorCommand::ToggleReadOnly);
+}
+
+#[test]
+fn test_parse_buffer_switch_commands() {
+    assert_eq!(EditorCommand::from_str("buffer.next").unwrap(), EditorCommand::NextBuffer);
+    assert_eq!(EditorCommand::from_str("bnext").unwrap(), EditorCommand::NextBuffer);
+    assert_eq!(EditorCommand::from_str("buffer.prev").unwrap(), EditorCommand::PreviousBuffer);
+    assert_eq!(
+        EditorCommand::from_str("previous_buffer").unwrap(),
+        EditorCommand::PreviousBuffer
+    );
+}
+
+#[test]
+fn test_is_edit() {
+    let edits = vec![
+        EditorCommand::InsertChar('a'),
+        EditorCommand::DeleteChar,
+        EditorCommand::DeleteForward,
+        EditorCommand::InsertTab,
+        EditorCommand::Cut,
+        EditorCommand::Paste,
+    ];
+    for cmd in edits {
+        assert!(cmd.is_edit(), "{:?} should be an edit", cmd);
+    }
+
+    let non_edits = vec![
+        EditorCommand::MoveCursor(Direction::Up),
+        EditorCommand::Save,
+        EditorCommand::SaveWithSudo,
+        EditorCommand::ToggleReadOnly,
+        EditorCommand::NextBuffer,
+        EditorCommand::Copy,
+        EditorCommand::ChangeMode(EditorMode::Normal),
+        EditorCommand::PromptInsertChar('a'),
+    ];
+    for cmd in non_edits {
+        assert!(!cmd.is_edit(), "{:?} should not be an edit", cmd);
+    }
+}
+
 #[test]
 fn test_parse_quit_command() {
     let cmd = EditorCommand::from_str("quit").unwrap();
@@ -839,3 +898,300 @@ fn test_plugin_command_examples() {
         }
     }
 }
+
+// ============================================================================
+// EditorCommand Display Tests
+// ============================================================================
+
+#[test]
+fn test_display_round_trips_canonical_names() {
+    let names = [
+        "delete_char",
+        "delete_forward",
+        "move.up",
+        "move.down",
+        "move.left",
+        "move.right",
+        "move.line_start",
+        "move.line_end",
+        "page.up",
+        "page.down",
+        "insert_tab",
+        "select.all",
+        "copy",
+        "file.save",
+        "file.save_sudo",
+        "buffer.toggle_readonly",
+        "buffer.next",
+        "buffer.prev",
+        "keybindings.list",
+        "keybindings.describe_key",
+        "noop",
+        "quit",
+        "mode.insert",
+        "mode.normal",
+        "prompt.next",
+        "prompt.previous",
+        "prompt.accept",
+        "prompt.cancel",
+        "lsp.goto_definition",
+    ];
+
+    for name in names {
+        let cmd = EditorCommand::from_str(name).unwrap();
+        assert_eq!(cmd.to_string(), name, "display of {:?}", cmd);
+    }
+}
+
+#[test]
+fn test_display_aliases_use_canonical_name() {
+    assert_eq!(EditorCommand::from_str("save").unwrap().to_string(), "file.save");
+    assert_eq!(EditorCommand::from_str("describe_key").unwrap().to_string(), "keybindings.describe_key");
+    assert_eq!(EditorCommand::InsertChar('a').to_string(), "insert_char 'a'");
+    assert_eq!(EditorCommand::InsertChar('\n').to_string(), "insert_char '\\n'");
+}
+
+#[test]
+fn test_display_parameterized_commands() {
+    let cmd = EditorCommand::MoveCursorBy { direction: Direction::Down, count: 10 };
+    assert_eq!(cmd.to_string(), "move.down { count = 10 }");
+    assert_eq!(EditorCommand::InsertText("// TODO".to_string()).to_string(), "insert_text { text = \"// TODO\" }");
+    assert_eq!(EditorCommand::GotoLine(100).to_string(), "goto_line { line = 100 }");
+    assert_eq!(EditorCommand::SetTheme("light".to_string()).to_string(), "theme.set { name = \"light\" }");
+}
+
+// ============================================================================
+// Parameterized Command Tests
+// ============================================================================
+
+fn args(toml: &str) -> toml::Table {
+    toml::from_str(toml).unwrap()
+}
+
+#[test]
+fn test_with_args_move_count() {
+    assert_eq!(
+        EditorCommand::with_args("move.down", &args("count = 10")).unwrap(),
+        EditorCommand::MoveCursorBy { direction: Direction::Down, count: 10 }
+    );
+    // A single step is the plain command
+    assert_eq!(
+        EditorCommand::with_args("Move.Up", &args("count = 1")).unwrap(),
+        EditorCommand::MoveCursor(Direction::Up)
+    );
+    assert_eq!(
+        EditorCommand::with_args("move.left", &args("")).unwrap(),
+        EditorCommand::MoveCursor(Direction::Left)
+    );
+}
+
+#[test]
+fn test_with_args_insert_text_goto_line_and_theme() {
+    assert_eq!(
+        EditorCommand::with_args("insert_text", &args("text = \"fn main() {}\"")).unwrap(),
+        EditorCommand::InsertText("fn main() {}".to_string())
+    );
+    assert_eq!(
+        EditorCommand::with_args("snippet.insert", &args("text = \"x\"")).unwrap(),
+        EditorCommand::InsertText("x".to_string())
+    );
+    assert_eq!(
+        EditorCommand::with_args("goto_line", &args("line = 100")).unwrap(),
+        EditorCommand::GotoLine(100)
+    );
+    assert_eq!(
+        EditorCommand::with_args("theme.set", &args("name = \"Light\"")).unwrap(),
+        EditorCommand::SetTheme("light".to_string())
+    );
+}
+
+#[test]
+fn test_with_args_without_args_matches_from_str() {
+    for name in ["file.save", "mode.normal", "lsp.goto_definition", "noop"] {
+        assert_eq!(EditorCommand::with_args(name, &args("")), EditorCommand::from_str(name));
+    }
+    assert_eq!(EditorCommand::with_args("  ", &args("")), Err(CommandParseError::EmptyCommand));
+}
+
+fn invalid(command: &str, param: &str, reason: &str) -> CommandParseError {
+    CommandParseError::InvalidParameter {
+        command: command.to_string(),
+        param: param.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[test]
+fn test_with_args_missing_required_argument() {
+    assert_eq!(
+        EditorCommand::from_str("goto_line"),
+        Err(invalid("goto_line", "line", "missing required argument"))
+    );
+    assert_eq!(
+        EditorCommand::with_args("insert_text", &args("")),
+        Err(invalid("insert_text", "text", "missing required argument"))
+    );
+}
+
+#[test]
+fn test_with_args_type_errors() {
+    assert_eq!(
+        EditorCommand::with_args("move.down", &args("count = \"10\"")),
+        Err(invalid("move.down", "count", "expected an integer, got string"))
+    );
+    assert_eq!(
+        EditorCommand::with_args("insert_text", &args("text = 5")),
+        Err(invalid("insert_text", "text", "expected a string, got integer"))
+    );
+}
+
+#[test]
+fn test_with_args_range_errors() {
+    assert_eq!(
+        EditorCommand::with_args("move.down", &args("count = 0")),
+        Err(invalid("move.down", "count", "must be between 1 and 10000 (got 0)"))
+    );
+    assert_eq!(
+        EditorCommand::with_args("goto_line", &args("line = 0")),
+        Err(invalid("goto_line", "line", "must be at least 1 (got 0)"))
+    );
+}
+
+#[test]
+fn test_with_args_unknown_theme() {
+    assert_eq!(
+        EditorCommand::with_args("theme.set", &args("name = \"solarized\"")),
+        Err(invalid("theme.set", "name", "unknown theme 'solarized' (available: dark, light)"))
+    );
+}
+
+#[test]
+fn test_with_args_unexpected_argument() {
+    assert_eq!(
+        EditorCommand::with_args("file.save", &args("count = 2")),
+        Err(invalid("file.save", "count", "unexpected argument"))
+    );
+    assert_eq!(
+        EditorCommand::with_args("move.down", &args("count = 2\nlines = 3")),
+        Err(invalid("move.down", "lines", "unexpected argument"))
+    );
+}
+
+#[test]
+fn test_parameterized_commands_is_edit() {
+    assert!(EditorCommand::InsertText("x".to_string()).is_edit());
+    assert!(!EditorCommand::GotoLine(1).is_edit());
+    assert!(!EditorCommand::SetTheme("dark".to_string()).is_edit());
+    assert!(!EditorCommand::MoveCursorBy { direction: Direction::Up, count: 2 }.is_edit());
+}
+
+// ============================================================================
+// Composite Command Tests
+// ============================================================================
+
+#[test]
+fn test_parse_user_command() {
+    assert_eq!(
+        EditorCommand::from_str("User.Save_And_Quit").unwrap(),
+        EditorCommand::UserCommand("save_and_quit".to_string())
+    );
+    assert_eq!(
+        EditorCommand::from_str("user.save and quit"),
+        Err(CommandParseError::UnknownCommand("user.save and quit".to_string()))
+    );
+}
+
+#[test]
+fn test_display_composite_commands() {
+    assert_eq!(EditorCommand::UserCommand("save_all".to_string()).to_string(), "user.save_all");
+    let chain = EditorCommand::Chain(vec![
+        EditorCommand::Save,
+        EditorCommand::MoveCursorBy { direction: Direction::Down, count: 2 },
+        EditorCommand::Quit,
+    ]);
+    assert_eq!(chain.to_string(), "[file.save, move.down { count = 2 }, quit]");
+    assert!(!chain.is_edit());
+}
+
+// ============================================================================
+// Undo and Macro Command Tests
+// ============================================================================
+
+#[test]
+fn test_parse_undo_redo() {
+    assert_eq!(EditorCommand::from_str("edit.undo").unwrap(), EditorCommand::Undo);
+    assert_eq!(EditorCommand::from_str("redo").unwrap(), EditorCommand::Redo);
+    assert!(EditorCommand::Undo.is_edit());
+}
+
+#[test]
+fn test_parse_macro_commands() {
+    assert_eq!(
+        EditorCommand::from_str("macro.record").unwrap(),
+        EditorCommand::ToggleMacroRecording('q')
+    );
+    assert_eq!(
+        EditorCommand::with_args("macro.record", &args("register = \"A\"")).unwrap(),
+        EditorCommand::ToggleMacroRecording('a')
+    );
+    assert_eq!(
+        EditorCommand::with_args("play_macro", &args("register = \"3\"\ncount = 5")).unwrap(),
+        EditorCommand::PlayMacro { register: '3', count: 5 }
+    );
+    assert_eq!(
+        EditorCommand::with_args("macro.play", &args("register = \"ab\"")),
+        Err(invalid("macro.play", "register", "expected a single character, got 'ab'"))
+    );
+    assert_eq!(
+        EditorCommand::with_args("macro.play", &args("register = \"@\"")),
+        Err(invalid("macro.play", "register", "expected a letter or digit, got '@'"))
+    );
+}
+
+#[test]
+fn test_parse_insert_char_with_argument() {
+    assert_eq!(
+        EditorCommand::with_args("insert_char", &args("char = \"\\n\"")).unwrap(),
+        EditorCommand::InsertChar('\n')
+    );
+    assert_eq!(
+        EditorCommand::with_args("prompt.insert_char", &args("char = \"é\"")).unwrap(),
+        EditorCommand::PromptInsertChar('é')
+    );
+    assert!(EditorCommand::from_str("insert_char").is_err());
+}
+
+#[test]
+fn test_to_name_and_args_round_trips() {
+    let commands = [
+        EditorCommand::InsertChar('x'),
+        EditorCommand::PromptInsertChar('/'),
+        EditorCommand::MoveCursor(Direction::Left),
+        EditorCommand::MoveCursorBy { direction: Direction::Up, count: 4 },
+        EditorCommand::InsertText("two\nlines".to_string()),
+        EditorCommand::GotoLine(7),
+        EditorCommand::SetTheme("dark".to_string()),
+        EditorCommand::ToggleMacroRecording('w'),
+        EditorCommand::PlayMacro { register: 'w', count: 3 },
+        EditorCommand::UserCommand("save_all".to_string()),
+        EditorCommand::Undo,
+        EditorCommand::Save,
+    ];
+
+    for command in commands {
+        let (name, args) = command.to_name_and_args().unwrap();
+        assert_eq!(EditorCommand::with_args(&name, &args).unwrap(), command, "{}", name);
+    }
+    assert_eq!(EditorCommand::Chain(vec![EditorCommand::Save]).to_name_and_args(), None);
+}
+
+#[test]
+fn test_command_names_parse_and_display_by_name() {
+    for name in COMMAND_NAMES {
+        let command = EditorCommand::from_str(name).unwrap();
+        let (display, _) = command.to_name_and_args().unwrap();
+        assert_eq!(&display, name);
+    }
+    assert_eq!(EditorCommand::from_str("command_palette"), Ok(EditorCommand::CommandPalette));
+    assert_eq!(EditorCommand::CommandPalette.to_string(), "palette.open");
+}
@@ -0,0 +1,192 @@
+//! Unit tests for parsing and completing `:` commands
+
+use crate::buffer::Buffer;
+use crate::input::command_line::{
+    complete, complete_path, parse, Address, ExCommand, ExError, LineRange, Substitution,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn substitution(input: &str) -> Substitution {
+    match parse(input) {
+        Ok(ExCommand::Substitute(substitution)) => substitution,
+        other => panic!("{} parsed as {:?}", input, other),
+    }
+}
+
+#[test]
+fn test_parse_file_commands() {
+    assert_eq!(parse("w"), Ok(ExCommand::Write(None)));
+    assert_eq!(
+        parse(":write  out.txt "),
+        Ok(ExCommand::Write(Some(PathBuf::from("out.txt"))))
+    );
+    assert_eq!(parse("q"), Ok(ExCommand::Quit));
+    assert_eq!(parse("quit!"), Ok(ExCommand::ForceQuit));
+    assert_eq!(parse("wq"), Ok(ExCommand::WriteQuit));
+    assert_eq!(parse("x"), Ok(ExCommand::WriteQuit));
+    assert_eq!(
+        parse("e src/main.rs"),
+        Ok(ExCommand::Edit(PathBuf::from("src/main.rs")))
+    );
+}
+
+#[test]
+fn test_parse_rejects_bad_commands() {
+    assert_eq!(parse("e"), Err(ExError::MissingArgument("file name")));
+    assert_eq!(parse(""), Err(ExError::MissingArgument("command")));
+    assert_eq!(
+        parse("q now"),
+        Err(ExError::TrailingCharacters("now".to_string()))
+    );
+    assert_eq!(parse("qa"), Err(ExError::UnknownCommand("qa".to_string())));
+    assert_eq!(parse("w!"), Err(ExError::UnknownCommand("w!".to_string())));
+    assert!(matches!(parse("3q"), Err(ExError::InvalidRange(_))));
+    assert!(matches!(parse("0"), Err(ExError::InvalidRange(_))));
+}
+
+#[test]
+fn test_parse_goto_line() {
+    assert_eq!(parse("42"), Ok(ExCommand::Goto(Address::Line(42))));
+    assert_eq!(parse("$"), Ok(ExCommand::Goto(Address::Last)));
+    assert_eq!(parse("."), Ok(ExCommand::Goto(Address::Current)));
+}
+
+#[test]
+fn test_parse_substitution_ranges_and_flags() {
+    let all = substitution("%s/foo/bar/gi");
+    assert_eq!(all.range, LineRange::All);
+    assert_eq!(
+        (all.pattern.as_str(), all.replacement.as_str()),
+        ("foo", "bar")
+    );
+    assert!(all.global && all.ignore_case);
+
+    let lines = substitution("2,$substitute/a/b");
+    assert_eq!(
+        lines.range,
+        LineRange::Lines(Address::Line(2), Address::Last)
+    );
+    assert!(!lines.global && !lines.ignore_case);
+
+    let current = substitution("s#/usr#/opt\\##");
+    assert_eq!(current.range, LineRange::Current);
+    assert_eq!(
+        (current.pattern.as_str(), current.replacement.as_str()),
+        ("/usr", "/opt#")
+    );
+
+    let deleted = substitution(".s/x");
+    assert_eq!(
+        deleted.range,
+        LineRange::Lines(Address::Current, Address::Current)
+    );
+    assert_eq!(deleted.replacement, "");
+}
+
+#[test]
+fn test_parse_rejects_bad_substitutions() {
+    assert_eq!(parse("s"), Err(ExError::MissingArgument("pattern")));
+    assert!(matches!(
+        parse("s//x/"),
+        Err(ExError::InvalidSubstitution(_))
+    ));
+    assert!(matches!(
+        parse("s/a/b/q"),
+        Err(ExError::InvalidSubstitution(_))
+    ));
+    assert!(matches!(parse("sa/b/"), Err(ExError::UnknownCommand(_))));
+}
+
+#[test]
+fn test_range_resolves_against_buffer() {
+    assert_eq!(LineRange::Current.resolve(3, 10), Ok((3, 3)));
+    assert_eq!(LineRange::All.resolve(3, 10), Ok((0, 9)));
+    assert_eq!(
+        LineRange::Lines(Address::Line(2), Address::Line(99)).resolve(0, 10),
+        Ok((1, 9))
+    );
+    assert_eq!(
+        LineRange::Lines(Address::Last, Address::Current).resolve(0, 10),
+        Err(ExError::BackwardsRange)
+    );
+}
+
+#[test]
+fn test_substitution_replaces_in_range() {
+    let mut buffer = Buffer::from_str("foo foo\nfoo\nFOO foo\n");
+
+    assert_eq!(substitution("s/foo/bar/").apply(&mut buffer, 0, 1), (2, 2));
+    assert_eq!(buffer.content(), "bar foo\nbar\nFOO foo\n");
+
+    assert_eq!(
+        substitution("s/foo/baz/gi").apply(&mut buffer, 2, 2),
+        (2, 1)
+    );
+    assert_eq!(buffer.content(), "bar foo\nbar\nbaz baz\n");
+
+    // All lines of one substitution are undone together
+    assert!(buffer.undo());
+    assert_eq!(buffer.content(), "bar foo\nbar\nFOO foo\n");
+}
+
+#[test]
+fn test_substitution_without_match_leaves_buffer_clean() {
+    let mut buffer = Buffer::from_str("hello\n");
+    assert_eq!(substitution("s/bye/x/g").apply(&mut buffer, 0, 0), (0, 0));
+    assert!(!buffer.is_dirty());
+}
+
+#[test]
+fn test_parse_set() {
+    assert_eq!(
+        parse("set tab_width=2"),
+        Ok(ExCommand::Set {
+            key: "tab_width".to_string(),
+            value: Some(toml::Value::Integer(2)),
+        })
+    );
+    assert_eq!(
+        parse("set theme=light"),
+        Ok(ExCommand::Set {
+            key: "theme".to_string(),
+            value: Some(toml::Value::String("light".to_string())),
+        })
+    );
+    assert_eq!(
+        parse("se expand_tabs"),
+        Ok(ExCommand::Set {
+            key: "expand_tabs".to_string(),
+            value: None,
+        })
+    );
+    assert_eq!(parse("set"), Err(ExError::MissingArgument("setting")));
+}
+
+#[test]
+fn test_complete_command_names() {
+    let dir = Path::new(".");
+    assert_eq!(complete("q", dir), vec!["quit", "quit!"]);
+    assert_eq!(complete("w", dir), vec!["wq", "write"]);
+    assert!(complete("z", dir).is_empty());
+    assert_eq!(complete("set line", dir), vec!["set line_numbers="]);
+    assert!(complete("q foo", dir).is_empty());
+}
+
+#[test]
+fn test_complete_file_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("notes")).unwrap();
+    fs::write(dir.path().join("notes/todo.md"), "").unwrap();
+    fs::write(dir.path().join("new.txt"), "").unwrap();
+    fs::write(dir.path().join(".hidden"), "").unwrap();
+
+    assert_eq!(complete("e n", dir.path()), vec!["e new.txt", "e notes/"]);
+    assert_eq!(
+        complete("write notes/", dir.path()),
+        vec!["write notes/todo.md"]
+    );
+    assert_eq!(complete_path("", dir.path()), vec!["new.txt", "notes/"]);
+    assert_eq!(complete_path(".h", dir.path()), vec![".hidden"]);
+    assert!(complete_path("missing/", dir.path()).is_empty());
+}
@@ -1,11 +1,21 @@
 //! Unit tests for config module
 
 use crate::editor::EditorMode;
-use crate::input::config::{load_user_keybindings, parse_mode, ConfigError};
+use crate::file_io::BackupMode;
+use crate::input::bindings::{register_default_bindings, KeymapOptions, KeymapPreset};
+use crate::input::config::{
+    load_editor_settings, load_keymap_options, load_save_options, load_user_commands,
+    load_user_keybindings, parse_editor_settings, parse_mode, ConfigError,
+};
+use crate::input::keybinding::KeyPattern;
 use crate::input::registry::KeyBindingRegistry;
+use crate::input::user_commands::UserCommands;
+use crate::input::EditorCommand;
+use crossterm::event::{KeyCode, KeyModifiers};
 use std::io::Write;
 use std::path::Path;
 use std::time::Duration;
+use termide_plugin_api::input::{PluginInputExtension, PluginMode};
 use tempfile::NamedTempFile;
 
 /// Helper to create a temporary config file with content
@@ -89,6 +99,45 @@ fn test_load_mode_specific_binding() {
     assert_eq!(result.unwrap().loaded, 1);
 }
 
+#[test]
+fn test_load_bindings_for_registered_plugin_modes() {
+    let config_content = r#"
+        [[keybindings]]
+        sequence = "b"
+        command = "mode.git.blame"
+        mode = "normal"
+
+        [[keybindings]]
+        sequence = "q"
+        command = "mode.normal"
+        mode = "git.blame"
+
+        [[keybindings]]
+        sequence = "x"
+        command = "mode.git.other"
+    "#;
+    let temp_file = create_temp_config(config_content);
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry.register_mode(PluginMode::new("git", "blame")).unwrap();
+    let blame = EditorMode::Plugin(registry.plugin_mode("git.blame").unwrap());
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+    assert_eq!(result.loaded, 2);
+    assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('b'), KeyModifiers::NONE));
+    assert_eq!(
+        registry.find_match(&EditorMode::Normal),
+        Some(&EditorCommand::ChangeMode(blame.clone()))
+    );
+    registry.clear_sequence();
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('q'), KeyModifiers::NONE));
+    assert_eq!(
+        registry.find_match(&blame),
+        Some(&EditorCommand::ChangeMode(EditorMode::Normal))
+    );
+}
+
 #[test]
 fn test_load_multiple_bindings() {
     let config_content = r#"
@@ -388,3 +437,400 @@ fn test_all_modes() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap().loaded, 3);
 }
+
+#[test]
+fn test_load_save_options() {
+    let config_content = r#"
+        [[keybindings]]
+        sequence = "Ctrl+S"
+        command = "file.save"
+
+        [save]
+        trim_trailing_whitespace = true
+        ensure_final_newline = true
+
+        [save.formatters]
+        rs = ["rustfmt"]
+
+        [save.backup]
+        mode = "timestamped"
+        keep = 3
+    "#;
+    let temp_file = create_temp_config(config_content);
+
+    let options = load_save_options(temp_file.path()).unwrap().unwrap();
+    assert!(options.hooks.trim_trailing_whitespace);
+    assert!(options.hooks.ensure_final_newline);
+    assert_eq!(options.hooks.formatters["rs"], vec!["rustfmt".to_string()]);
+    assert_eq!(options.backup.mode, BackupMode::Timestamped);
+    assert_eq!(options.backup.keep, 3);
+
+    // Keybindings in the same file still load
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    assert_eq!(load_user_keybindings(&mut registry, temp_file.path()).unwrap().loaded, 1);
+}
+
+#[test]
+fn test_load_save_options_none_when_missing() {
+    let temp_file = create_temp_config("");
+    assert_eq!(load_save_options(temp_file.path()).unwrap(), None);
+
+    // An empty section gives the defaults
+    let temp_file = create_temp_config("[save]
+");
+    let options = load_save_options(temp_file.path()).unwrap().unwrap();
+    assert_eq!(options.backup.mode, BackupMode::None);
+    assert!(!options.hooks.trim_trailing_whitespace);
+    assert!(options.hooks.formatters.is_empty());
+}
+
+#[test]
+fn test_load_save_options_invalid_backup_mode() {
+    let temp_file = create_temp_config("[save.backup]\nmode = \"sometimes\"\n");
+
+    let result = load_save_options(temp_file.path());
+    assert!(matches!(result, Err(ConfigError::TomlParseError { .. })));
+}
+
+fn parse_settings(toml_str: &str) -> crate::input::config::SettingsLoadResult {
+    let table: toml::Table = toml::from_str(toml_str).unwrap();
+    parse_editor_settings(&table)
+}
+
+#[test]
+fn test_load_editor_settings() {
+    let temp_file = create_temp_config(
+        r#"
+[[keybindings]]
+sequence = "Ctrl+S"
+command = "file.save"
+
+[editor]
+tab_width = 2
+expand_tabs = true
+default_mode = "Normal"
+scroll_margin = 3
+sequence_timeout_ms = 600
+which_key_delay_ms = 0
+theme = "light"
+line_numbers = true
+"#,
+    );
+
+    let result = load_editor_settings(temp_file.path()).unwrap();
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    let settings = result.settings;
+    assert_eq!(settings.tab_width, 2);
+    assert!(settings.expand_tabs);
+    assert_eq!(settings.default_mode, EditorMode::Normal);
+    assert_eq!(settings.scroll_margin, 3);
+    assert_eq!(settings.sequence_timeout, Duration::from_millis(600));
+    assert_eq!(settings.which_key_delay, Duration::ZERO);
+    assert_eq!(settings.theme, "light");
+    assert!(settings.line_numbers);
+}
+
+#[test]
+fn test_load_editor_settings_defaults_when_missing() {
+    let temp_file = create_temp_config("");
+
+    let result = load_editor_settings(temp_file.path()).unwrap();
+    assert!(result.warnings.is_empty());
+    assert_eq!(result.settings, crate::editor::EditorSettings::default());
+}
+
+#[test]
+fn test_editor_settings_invalid_values_keep_defaults() {
+    let result = parse_settings(
+        r#"
+tab_width = 0
+expand_tabs = "yes"
+scroll_margin = -1
+sequence_timeout_ms = 5
+which_key_delay_ms = 20000
+line_numbers = true
+"#,
+    );
+
+    let defaults = crate::editor::EditorSettings::default();
+    assert_eq!(result.settings.tab_width, defaults.tab_width);
+    assert_eq!(result.settings.expand_tabs, defaults.expand_tabs);
+    assert_eq!(result.settings.scroll_margin, defaults.scroll_margin);
+    assert_eq!(result.settings.sequence_timeout, defaults.sequence_timeout);
+    assert_eq!(result.settings.which_key_delay, defaults.which_key_delay);
+    // Valid settings next to invalid ones still apply
+    assert!(result.settings.line_numbers);
+
+    assert_eq!(result.warnings.len(), 5);
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w == "editor.tab_width: must be between 1 and 16 (got 0)"));
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w == "editor.expand_tabs: expected true or false, got string"));
+}
+
+#[test]
+fn test_editor_settings_unknown_key_and_theme() {
+    let result = parse_settings("tabwidth = 4\ntheme = \"neon\"\n");
+
+    assert_eq!(result.settings.theme, "dark");
+    assert_eq!(result.warnings.len(), 2);
+    assert!(result
+        .warnings
+        .contains(&"editor.tabwidth: unknown setting".to_string()));
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.starts_with("editor.theme: unknown theme 'neon'")));
+}
+
+#[test]
+fn test_editor_settings_rejects_prompt_default_mode() {
+    let result = parse_settings("default_mode = \"prompt\"\n");
+    assert_eq!(result.settings.default_mode, EditorMode::Insert);
+    assert_eq!(result.warnings.len(), 1);
+
+    let result = parse_settings("default_mode = \"visual\"\n");
+    assert_eq!(result.warnings.len(), 1);
+    assert!(result.warnings[0].starts_with("editor.default_mode:"));
+}
+
+#[test]
+fn test_editor_settings_wrong_section_type_is_parse_error() {
+    let temp_file = create_temp_config("editor = 5\n");
+
+    let result = load_editor_settings(temp_file.path());
+    assert!(matches!(result, Err(ConfigError::TomlParseError { .. })));
+}
+
+#[test]
+fn test_unbind_disables_default_binding() {
+    let temp_file = create_temp_config(
+        r#"
+[[keybindings]]
+sequence = "Ctrl+q"
+unbind = true
+
+[[keybindings]]
+sequence = "Ctrl+s"
+command = "noop"
+mode = "normal"
+"#,
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    register_default_bindings(&mut registry).unwrap();
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+    assert_eq!(result.loaded, 2);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('q'), KeyModifiers::CONTROL));
+    assert_eq!(registry.find_match(&EditorMode::Insert), Some(&EditorCommand::Noop));
+    registry.clear_sequence();
+
+    // Unbinding in one mode leaves the others alone
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    assert_eq!(registry.find_match(&EditorMode::Normal), Some(&EditorCommand::Noop));
+    assert_eq!(registry.find_match(&EditorMode::Insert), Some(&EditorCommand::Save));
+}
+
+#[test]
+fn test_unbind_with_command_is_rejected() {
+    let temp_file = create_temp_config(
+        "[[keybindings]]\nsequence = \"Ctrl+q\"\ncommand = \"file.save\"\nunbind = true\n",
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+
+    assert_eq!(result.loaded, 0);
+    assert_eq!(
+        result.warnings,
+        vec!["invalid command 'file.save' in keybinding #1: 'unbind = true' cannot be combined with a command"]
+    );
+}
+
+#[test]
+fn test_missing_command_without_unbind_is_rejected() {
+    let temp_file = create_temp_config("[[keybindings]]\nsequence = \"Ctrl+q\"\n");
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+
+    assert_eq!(result.loaded, 0);
+    assert!(result.warnings[0].contains("command cannot be empty"));
+}
+
+#[test]
+fn test_load_keymap_options() {
+    let temp_file = create_temp_config("[keymap]\npreset = \"emacs\"\ndisable_defaults = true\n");
+    assert_eq!(
+        load_keymap_options(temp_file.path()).unwrap(),
+        Some(KeymapOptions {
+            preset: KeymapPreset::Emacs,
+            disable_defaults: true,
+        })
+    );
+
+    let temp_file = create_temp_config("[editor]\ntab_width = 2\n");
+    assert_eq!(load_keymap_options(temp_file.path()).unwrap(), None);
+
+    let temp_file = create_temp_config("[keymap]\npreset = \"nano\"\n");
+    assert!(matches!(
+        load_keymap_options(temp_file.path()),
+        Err(ConfigError::TomlParseError { .. })
+    ));
+}
+
+#[test]
+fn test_parameterized_binding_args() {
+    let temp_file = create_temp_config(
+        r#"
+[[keybindings]]
+sequence = "Ctrl+j"
+command = "move.down"
+args = { count = 10 }
+
+[[keybindings]]
+sequence = "g g"
+command = "goto_line"
+args = { line = 1 }
+mode = "normal"
+"#,
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+    assert_eq!(result.loaded, 2);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    assert_eq!(
+        registry.find_match(&EditorMode::Insert),
+        Some(&EditorCommand::MoveCursorBy { direction: crate::input::Direction::Down, count: 10 })
+    );
+}
+
+#[test]
+fn test_invalid_binding_args_are_reported() {
+    let temp_file = create_temp_config(
+        r#"
+[[keybindings]]
+sequence = "Ctrl+j"
+command = "move.down"
+args = { count = "ten" }
+
+[[keybindings]]
+sequence = "Ctrl+t"
+command = "theme.set"
+args = { name = "neon" }
+
+[[keybindings]]
+sequence = "Ctrl+g"
+command = "goto_line"
+"#,
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+
+    assert_eq!(result.loaded, 0);
+    assert_eq!(
+        result.warnings,
+        vec![
+            "invalid command 'move.down' in keybinding #1: invalid parameter 'count' for command 'move.down': expected an integer, got string",
+            "invalid command 'theme.set' in keybinding #2: invalid parameter 'name' for command 'theme.set': unknown theme 'neon' (available: dark, light)",
+            "invalid command 'goto_line' in keybinding #3: invalid parameter 'line' for command 'goto_line': missing required argument",
+        ]
+    );
+}
+
+#[test]
+fn test_binding_command_chain() {
+    let temp_file = create_temp_config(
+        r#"
+[[keybindings]]
+sequence = "Ctrl+j"
+commands = ["file.save", { command = "move.down", args = { count = 3 } }, "user.done"]
+"#,
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+    assert_eq!(result.loaded, 1);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('j'), KeyModifiers::CONTROL));
+    assert_eq!(
+        registry.find_match(&EditorMode::Insert),
+        Some(&EditorCommand::Chain(vec![
+            EditorCommand::Save,
+            EditorCommand::MoveCursorBy { direction: crate::input::Direction::Down, count: 3 },
+            EditorCommand::UserCommand("done".to_string()),
+        ]))
+    );
+}
+
+#[test]
+fn test_invalid_command_chains_are_reported() {
+    let temp_file = create_temp_config(
+        r#"
+[[keybindings]]
+sequence = "Ctrl+j"
+commands = ["file.save", "explode"]
+
+[[keybindings]]
+sequence = "Ctrl+k"
+command = "quit"
+commands = ["file.save"]
+"#,
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    let result = load_user_keybindings(&mut registry, temp_file.path()).unwrap();
+
+    assert_eq!(result.loaded, 0);
+    assert_eq!(
+        result.warnings,
+        vec![
+            "invalid command 'explode' in keybinding #1: unknown command 'explode'",
+            "invalid command 'quit' in keybinding #2: 'commands' cannot be combined with 'command' or 'args'",
+        ]
+    );
+}
+
+#[test]
+fn test_load_user_commands() {
+    let temp_file = create_temp_config(
+        r#"
+[commands]
+save_and_quit = ["file.save", "quit"]
+scroll = [{ command = "move.down", args = { count = 10 } }]
+"bad name" = ["quit"]
+empty = []
+broken = ["file.save", { command = "goto_line" }]
+"#,
+    );
+    let mut commands = UserCommands::new();
+    commands.insert("scroll", vec![EditorCommand::Quit]);
+
+    let warnings = load_user_commands(temp_file.path(), &mut commands, &KeyBindingRegistry::default()).unwrap();
+
+    assert_eq!(
+        warnings,
+        vec![
+            "invalid user command 'bad name': names may only contain letters, digits, '_' and '-'",
+            "invalid user command 'broken': command 'goto_line': invalid parameter 'line' for command 'goto_line': missing required argument",
+            "invalid user command 'empty': the command list is empty",
+        ]
+    );
+    assert_eq!(commands.names().collect::<Vec<_>>(), vec!["save_and_quit", "scroll"]);
+    assert_eq!(
+        commands.get("scroll"),
+        Some(&[EditorCommand::MoveCursorBy { direction: crate::input::Direction::Down, count: 10 }][..])
+    );
+}
@@ -0,0 +1,273 @@
+//! Unit tests for config_layers module
+
+use crate::editor::{EditorConfig, EditorMode};
+use crate::input::config::{load_user_keybindings, read_config};
+use crate::input::config_layers::{
+    find_project_config, language_for_path, reload_project_keybindings, resolve_layers,
+    resolve_settings, ConfigLayer, ConfigPaths, PROJECT_CONFIG_FILE_NAME,
+};
+use crate::input::keybinding::{KeyPattern, Priority};
+use crate::input::registry::KeyBindingRegistry;
+use crate::input::EditorCommand;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const USER_CONFIG: &str = r#"
+[editor]
+tab_width = 8
+line_numbers = true
+
+[language.rust]
+tab_width = 2
+expand_tabs = true
+"#;
+
+const PROJECT_CONFIG: &str = r#"
+[editor]
+tab_width = 3
+scroll_margin = 5
+
+[language.rust]
+expand_tabs = false
+"#;
+
+/// Creates a project directory with a `.termide.toml` and a user config file
+fn create_layers(user: &str, project: &str) -> (TempDir, ConfigPaths) {
+    let dir = TempDir::new().unwrap();
+    let user_path = dir.path().join("config.toml");
+    let project_path = dir.path().join("project").join(PROJECT_CONFIG_FILE_NAME);
+    fs::create_dir_all(dir.path().join("project/src")).unwrap();
+    fs::write(&user_path, user).unwrap();
+    fs::write(&project_path, project).unwrap();
+
+    let paths = ConfigPaths {
+        user: Some(user_path),
+        project: Some(project_path),
+    };
+    (dir, paths)
+}
+
+#[test]
+fn test_find_project_config_walks_up() {
+    let (dir, paths) = create_layers("", "");
+    let nested = dir.path().join("project/src");
+
+    assert_eq!(find_project_config(&nested), paths.project);
+    assert_eq!(find_project_config(&dir.path().join("project")), paths.project);
+}
+
+#[test]
+fn test_find_project_config_picks_nearest() {
+    let (dir, _paths) = create_layers("", "");
+    let nested_config = dir.path().join("project/src").join(PROJECT_CONFIG_FILE_NAME);
+    fs::write(&nested_config, "").unwrap();
+
+    assert_eq!(
+        find_project_config(&dir.path().join("project/src")),
+        Some(nested_config)
+    );
+}
+
+#[test]
+fn test_find_project_config_ignores_directories() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join(PROJECT_CONFIG_FILE_NAME)).unwrap();
+
+    assert_ne!(
+        find_project_config(dir.path()),
+        Some(dir.path().join(PROJECT_CONFIG_FILE_NAME))
+    );
+}
+
+#[test]
+fn test_discover_uses_file_directory_and_drops_missing_user_config() {
+    let (dir, paths) = create_layers("", "");
+    let file = dir.path().join("project/src/main.rs");
+    let missing_user = dir.path().join("missing.toml");
+
+    let discovered = ConfigPaths::discover(Some(missing_user), Some(&file));
+
+    assert_eq!(discovered.user, None);
+    assert_eq!(discovered.project, paths.project);
+}
+
+#[test]
+fn test_language_for_path() {
+    assert_eq!(language_for_path(Path::new("main.rs")).as_deref(), Some("rust"));
+    assert_eq!(language_for_path(Path::new("a/b/script.PY")).as_deref(), Some("python"));
+    assert_eq!(language_for_path(Path::new("config.yml")).as_deref(), Some("yaml"));
+    assert_eq!(language_for_path(Path::new("Makefile")).as_deref(), Some("make"));
+    assert_eq!(language_for_path(Path::new("doc.ADOC")).as_deref(), Some("adoc"));
+    assert_eq!(language_for_path(Path::new("LICENSE")), None);
+}
+
+#[test]
+fn test_resolve_precedence_default_user_project_language() {
+    let (_dir, paths) = create_layers(USER_CONFIG, PROJECT_CONFIG);
+
+    let resolved = resolve_settings(&paths, Some("rust")).unwrap();
+
+    // User [language.rust] beats project [editor]
+    assert_eq!(resolved.settings.tab_width, 2);
+    // Project [language.rust] beats user [language.rust]
+    assert!(!resolved.settings.expand_tabs);
+    assert_eq!(resolved.settings.scroll_margin, 5);
+    assert!(resolved.settings.line_numbers);
+    assert_eq!(resolved.settings.default_mode, EditorMode::Insert);
+    assert!(resolved.warnings.is_empty());
+}
+
+#[test]
+fn test_resolve_records_source_layers() {
+    let (_dir, paths) = create_layers(USER_CONFIG, PROJECT_CONFIG);
+
+    let resolved = resolve_settings(&paths, Some("rust")).unwrap();
+
+    assert_eq!(
+        resolved.source("tab_width"),
+        &ConfigLayer::Language {
+            language: "rust".to_string(),
+            project: false
+        }
+    );
+    assert_eq!(
+        resolved.source("expand_tabs"),
+        &ConfigLayer::Language {
+            language: "rust".to_string(),
+            project: true
+        }
+    );
+    assert_eq!(resolved.source("scroll_margin"), &ConfigLayer::Project);
+    assert_eq!(resolved.source("line_numbers"), &ConfigLayer::User);
+    assert_eq!(resolved.source("theme"), &ConfigLayer::Default);
+}
+
+#[test]
+fn test_resolve_without_language_ignores_language_sections() {
+    let (_dir, paths) = create_layers(USER_CONFIG, PROJECT_CONFIG);
+
+    let resolved = resolve_settings(&paths, Some("go")).unwrap();
+
+    assert_eq!(resolved.settings.tab_width, 3);
+    assert_eq!(resolved.source("tab_width"), &ConfigLayer::Project);
+    assert!(!resolved.settings.expand_tabs);
+    assert_eq!(resolved.source("expand_tabs"), &ConfigLayer::Default);
+}
+
+#[test]
+fn test_resolve_without_files_uses_defaults() {
+    let resolved = resolve_settings(&ConfigPaths::default(), Some("rust")).unwrap();
+
+    assert_eq!(resolved.settings, Default::default());
+    assert!(resolved.sources.is_empty());
+}
+
+#[test]
+fn test_resolve_invalid_value_keeps_lower_layer() {
+    let (_dir, paths) = create_layers(USER_CONFIG, "[language.rust]\ntab_width = 0\n");
+
+    let resolved = resolve_settings(&paths, Some("rust")).unwrap();
+
+    assert_eq!(resolved.settings.tab_width, 2);
+    assert_eq!(resolved.warnings.len(), 1);
+    assert!(resolved.warnings[0].starts_with("language.rust.tab_width:"));
+}
+
+#[test]
+fn test_resolve_reports_unreadable_project_config() {
+    let (_dir, paths) = create_layers(USER_CONFIG, "[editor\n");
+
+    let result = resolve_settings(&paths, None);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_resolve_layers_from_parsed_configs() {
+    let (_dir, paths) = create_layers(USER_CONFIG, PROJECT_CONFIG);
+    let user = read_config(paths.user.as_deref().unwrap()).unwrap();
+
+    let resolved = resolve_layers(Some(&user), None, Some("rust"));
+
+    assert_eq!(resolved.settings.tab_width, 2);
+    assert!(resolved.settings.expand_tabs);
+}
+
+#[test]
+fn test_describe_lists_values_and_layers() {
+    let (_dir, paths) = create_layers(USER_CONFIG, PROJECT_CONFIG);
+
+    let lines = resolve_settings(&paths, Some("rust")).unwrap().describe();
+
+    assert!(lines.contains(&"tab_width = 2 (user config [language.rust])".to_string()));
+    assert!(lines.contains(&"expand_tabs = false (project config [language.rust])".to_string()));
+    assert!(lines.contains(&"scroll_margin = 5 (project config)".to_string()));
+    assert!(lines.contains(&"line_numbers = true (user config)".to_string()));
+    assert!(lines.contains(&"theme = \"dark\" (default)".to_string()));
+    assert!(lines.contains(&"default_mode = \"insert\" (default)".to_string()));
+}
+
+#[test]
+fn test_project_keybindings_override_user_keybindings() {
+    let (_dir, paths) = create_layers(
+        "[[keybindings]]\nsequence = \"Ctrl+b\"\ncommand = \"file.save\"\n",
+        "[[keybindings]]\nsequence = \"Ctrl+b\"\ncommand = \"quit\"\n",
+    );
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    load_user_keybindings(&mut registry, paths.user.as_deref().unwrap()).unwrap();
+
+    let (removed, result) =
+        reload_project_keybindings(&mut registry, paths.project.as_deref()).unwrap();
+    assert_eq!(removed, 0);
+    assert_eq!(result.loaded, 1);
+
+    registry.add_to_sequence(KeyPattern::new(KeyCode::Char('b'), KeyModifiers::CONTROL));
+    assert_eq!(registry.find_match(&EditorMode::Insert), Some(&EditorCommand::Quit));
+
+    // Leaving the project removes its bindings and the user binding applies again
+    let (removed, result) = reload_project_keybindings(&mut registry, None).unwrap();
+    assert_eq!(removed, 1);
+    assert_eq!(result.loaded, 0);
+    assert_eq!(registry.unregister_by_priority(Priority::Project), 0);
+    assert_eq!(registry.find_match(&EditorMode::Insert), Some(&EditorCommand::Save));
+}
+
+#[test]
+fn test_config_layer_display() {
+    assert_eq!(ConfigLayer::Default.to_string(), "default");
+    assert_eq!(ConfigLayer::User.to_string(), "user config");
+    assert_eq!(ConfigLayer::Project.to_string(), "project config");
+    assert_eq!(
+        ConfigLayer::Language {
+            language: "go".to_string(),
+            project: true
+        }
+        .to_string(),
+        "project config [language.go]"
+    );
+}
+
+#[test]
+fn test_editorconfig_overrides_configured_settings() {
+    let (dir, paths) = create_layers(USER_CONFIG, PROJECT_CONFIG);
+    fs::write(
+        dir.path().join("project/.editorconfig"),
+        "root = true\n[*.rs]\nindent_style = space\nindent_size = 4\n",
+    )
+    .unwrap();
+
+    let mut resolved = resolve_settings(&paths, Some("rust")).unwrap();
+    resolved.apply_editorconfig(&EditorConfig::for_file(&dir.path().join("project/src/lib.rs")));
+
+    assert!(resolved.settings.expand_tabs);
+    assert_eq!(resolved.settings.tab_width, 4);
+    assert_eq!(resolved.source("tab_width"), &ConfigLayer::EditorConfig);
+    assert!(resolved
+        .describe()
+        .contains(&"expand_tabs = true (.editorconfig)".to_string()));
+    // Settings without an .editorconfig property keep their layer
+    assert_eq!(resolved.source("scroll_margin"), &ConfigLayer::Project);
+}
@@ -46,7 +46,7 @@ fn test_global_ctrl_s_save_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Save)),
@@ -59,7 +59,7 @@ fn test_global_ctrl_s_save_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Save)),
@@ -73,7 +73,7 @@ fn test_global_ctrl_shift_s_save_in_insert_mode() {
 
     // Uppercase 'S' represents Shift+S
     let event = key_event(KeyCode::Char('S'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Save)),
@@ -86,7 +86,7 @@ fn test_global_ctrl_shift_s_save_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('S'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Save)),
@@ -99,7 +99,7 @@ fn test_global_ctrl_q_quit_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('q'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Quit)),
@@ -112,7 +112,7 @@ fn test_global_ctrl_q_quit_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('q'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Quit)),
@@ -125,7 +125,7 @@ fn test_global_ctrl_shift_q_quit_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('Q'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Quit)),
@@ -138,7 +138,7 @@ fn test_global_ctrl_shift_q_quit_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('Q'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::Quit)),
@@ -152,7 +152,7 @@ fn test_global_shortcuts_not_active_in_prompt_mode() {
 
     // Test Ctrl+S in Prompt mode (should NOT trigger Save)
     let event = key_event(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert!(
         matches!(result, MatchResult::NoMatch),
         "Global shortcuts should NOT be active in Prompt mode"
@@ -160,7 +160,7 @@ fn test_global_shortcuts_not_active_in_prompt_mode() {
 
     // Test Ctrl+Q in Prompt mode (should NOT trigger Quit)
     let event = key_event(KeyCode::Char('q'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert!(
         matches!(result, MatchResult::NoMatch),
         "Global shortcuts should NOT be active in Prompt mode"
@@ -176,7 +176,7 @@ fn test_insert_enter_inserts_newline() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Enter, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::InsertChar('\n'))),
@@ -189,7 +189,7 @@ fn test_insert_backspace_deletes_char() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Backspace, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::DeleteChar)),
@@ -202,7 +202,7 @@ fn test_insert_esc_switches_to_normal() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Esc, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Normal))),
@@ -215,7 +215,7 @@ fn test_insert_up_arrow_moves_cursor_up() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Up, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up))),
@@ -228,7 +228,7 @@ fn test_insert_down_arrow_moves_cursor_down() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Down, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Down))),
@@ -241,7 +241,7 @@ fn test_insert_left_arrow_moves_cursor_left() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Left, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Left))),
@@ -254,7 +254,7 @@ fn test_insert_right_arrow_moves_cursor_right() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Right, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Right))),
@@ -271,7 +271,7 @@ fn test_normal_i_switches_to_insert() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Insert))),
@@ -284,7 +284,7 @@ fn test_normal_up_arrow_moves_cursor_up() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Up, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up))),
@@ -297,7 +297,7 @@ fn test_normal_down_arrow_moves_cursor_down() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Down, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Down))),
@@ -310,7 +310,7 @@ fn test_normal_left_arrow_moves_cursor_left() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Left, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Left))),
@@ -323,7 +323,7 @@ fn test_normal_right_arrow_moves_cursor_right() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Right, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Right))),
@@ -340,7 +340,7 @@ fn test_prompt_backspace_deletes_from_prompt() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Backspace, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::PromptDeleteChar)),
@@ -353,7 +353,7 @@ fn test_prompt_enter_accepts_prompt() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Enter, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::AcceptPrompt)),
@@ -366,7 +366,7 @@ fn test_prompt_esc_cancels_prompt() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Esc, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::CancelPrompt)),
@@ -383,7 +383,7 @@ fn test_insert_enter_not_active_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Enter, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     // In Normal mode, Enter should not trigger InsertChar('\n')
     assert!(
@@ -397,7 +397,7 @@ fn test_insert_backspace_not_active_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Backspace, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     // In Normal mode, Backspace should not trigger DeleteChar
     assert!(
@@ -411,7 +411,7 @@ fn test_insert_esc_not_active_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Esc, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     // In Normal mode, Esc should not trigger mode change
     assert!(
@@ -425,7 +425,7 @@ fn test_normal_i_not_active_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     // In Insert mode, 'i' should not trigger mode change (it's a regular character)
     assert!(
@@ -439,7 +439,7 @@ fn test_normal_i_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     // In Prompt mode, 'i' should not trigger mode change
     assert!(
@@ -453,7 +453,7 @@ fn test_prompt_backspace_not_active_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Backspace, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     // In Insert mode, Backspace should trigger DeleteChar, not PromptDeleteChar
     assert!(
@@ -467,7 +467,7 @@ fn test_prompt_enter_not_active_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Enter, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     // In Insert mode, Enter should trigger InsertChar('\n'), not AcceptPrompt
     assert!(
@@ -481,7 +481,7 @@ fn test_prompt_esc_not_active_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Esc, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     // In Insert mode, Esc should trigger ChangeMode(Normal), not CancelPrompt
     assert!(
@@ -496,28 +496,28 @@ fn test_arrow_keys_not_active_in_prompt_mode() {
 
     // Arrow keys are registered for Insert and Normal modes, not Prompt
     let event = key_event(KeyCode::Up, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up))),
         "Arrow keys should not be active in Prompt mode"
     );
 
     let event = key_event(KeyCode::Down, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Down))),
         "Arrow keys should not be active in Prompt mode"
     );
 
     let event = key_event(KeyCode::Left, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Left))),
         "Arrow keys should not be active in Prompt mode"
     );
 
     let event = key_event(KeyCode::Right, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Right))),
         "Arrow keys should not be active in Prompt mode"
@@ -534,7 +534,7 @@ fn test_plain_char_not_matched_when_ctrl_expected() {
 
     // Plain 's' without Ctrl should not trigger Save
     let event = key_event(KeyCode::Char('s'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::Save)),
@@ -548,7 +548,7 @@ fn test_char_with_shift_not_matched_when_plain_expected() {
 
     // Uppercase 'I' should not trigger Insert mode (needs lowercase 'i')
     let event = key_event(KeyCode::Char('I'), KeyModifiers::SHIFT);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Insert))),
@@ -562,7 +562,7 @@ fn test_char_with_alt_not_matched() {
 
     // Alt+S should not trigger Save (needs Ctrl)
     let event = key_event(KeyCode::Char('s'), KeyModifiers::ALT);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::Save)),
@@ -576,7 +576,7 @@ fn test_arrow_with_modifiers_not_matched() {
 
     // Ctrl+Up should not trigger cursor movement (needs plain Up)
     let event = key_event(KeyCode::Up, KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up))),
@@ -594,7 +594,7 @@ fn test_all_special_keys_recognized_in_appropriate_modes() {
     let mut handler = create_handler_with_defaults();
     let event = key_event(KeyCode::Enter, KeyModifiers::NONE);
     assert!(
-        matches!(handler.process_key_event(event, EditorMode::Prompt), MatchResult::Matched(_)),
+        matches!(handler.process_key_event(event, &EditorMode::Prompt), MatchResult::Matched(_)),
         "Enter should be recognized in Insert mode"
     );
 
@@ -602,7 +602,7 @@ fn test_all_special_keys_recognized_in_appropriate_modes() {
     let mut handler = create_handler_with_defaults();
     let event = key_event(KeyCode::Backspace, KeyModifiers::NONE);
     assert!(
-        matches!(handler.process_key_event(event, EditorMode::Prompt), MatchResult::Matched(_)),
+        matches!(handler.process_key_event(event, &EditorMode::Prompt), MatchResult::Matched(_)),
         "Backspace should be recognized in Insert mode"
     );
 
@@ -610,7 +610,7 @@ fn test_all_special_keys_recognized_in_appropriate_modes() {
     let mut handler = create_handler_with_defaults();
     let event = key_event(KeyCode::Esc, KeyModifiers::NONE);
     assert!(
-        matches!(handler.process_key_event(event, EditorMode::Prompt), MatchResult::Matched(_)),
+        matches!(handler.process_key_event(event, &EditorMode::Prompt), MatchResult::Matched(_)),
         "Esc should be recognized in Insert mode"
     );
 
@@ -620,28 +620,28 @@ fn test_all_special_keys_recognized_in_appropriate_modes() {
 
         let event = key_event(KeyCode::Up, KeyModifiers::NONE);
         assert!(
-            matches!(handler.process_key_event(event, mode), MatchResult::Matched(_)),
+            matches!(handler.process_key_event(event, &mode), MatchResult::Matched(_)),
             "Up arrow should be recognized in {:?} mode",
             mode
         );
 
         let event = key_event(KeyCode::Down, KeyModifiers::NONE);
         assert!(
-            matches!(handler.process_key_event(event, mode), MatchResult::Matched(_)),
+            matches!(handler.process_key_event(event, &mode), MatchResult::Matched(_)),
             "Down arrow should be recognized in {:?} mode",
             mode
         );
 
         let event = key_event(KeyCode::Left, KeyModifiers::NONE);
         assert!(
-            matches!(handler.process_key_event(event, mode), MatchResult::Matched(_)),
+            matches!(handler.process_key_event(event, &mode), MatchResult::Matched(_)),
             "Left arrow should be recognized in {:?} mode",
             mode
         );
 
         let event = key_event(KeyCode::Right, KeyModifiers::NONE);
         assert!(
-            matches!(handler.process_key_event(event, mode), MatchResult::Matched(_)),
+            matches!(handler.process_key_event(event, &mode), MatchResult::Matched(_)),
             "Right arrow should be recognized in {:?} mode",
             mode
         );
@@ -657,7 +657,7 @@ fn test_delete_forward_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Delete, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::DeleteForward)),
@@ -670,7 +670,7 @@ fn test_delete_forward_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Delete, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::DeleteForward)),
@@ -683,7 +683,7 @@ fn test_delete_forward_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Delete, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::DeleteForward)),
@@ -696,7 +696,7 @@ fn test_home_moves_to_line_start_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Home, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveToLineStart)),
@@ -709,7 +709,7 @@ fn test_home_moves_to_line_start_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Home, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveToLineStart)),
@@ -722,7 +722,7 @@ fn test_home_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Home, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveToLineStart)),
@@ -735,7 +735,7 @@ fn test_end_moves_to_line_end_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::End, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveToLineEnd)),
@@ -748,7 +748,7 @@ fn test_end_moves_to_line_end_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::End, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::MoveToLineEnd)),
@@ -761,7 +761,7 @@ fn test_end_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::End, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::MoveToLineEnd)),
@@ -774,7 +774,7 @@ fn test_pageup_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::PageUp, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::PageUp)),
@@ -787,7 +787,7 @@ fn test_pageup_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::PageUp, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::PageUp)),
@@ -800,7 +800,7 @@ fn test_pageup_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::PageUp, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::PageUp)),
@@ -813,7 +813,7 @@ fn test_pagedown_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::PageDown, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::PageDown)),
@@ -826,7 +826,7 @@ fn test_pagedown_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::PageDown, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::PageDown)),
@@ -839,7 +839,7 @@ fn test_pagedown_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::PageDown, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::PageDown)),
@@ -852,7 +852,7 @@ fn test_tab_inserts_tab_in_insert_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Tab, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert!(
         matches!(result, MatchResult::Matched(EditorCommand::InsertTab)),
@@ -865,7 +865,7 @@ fn test_tab_not_active_in_normal_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Tab, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::InsertTab)),
@@ -878,7 +878,7 @@ fn test_tab_not_active_in_prompt_mode() {
     let mut handler = create_handler_with_defaults();
 
     let event = key_event(KeyCode::Tab, KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
 
     assert!(
         !matches!(result, MatchResult::Matched(EditorCommand::InsertTab)),
@@ -4,7 +4,7 @@ use crate::editor::EditorMode;
 use crate::input::input_handler::{InputHandler, MatchResult};
 use crate::input::keybinding::{BindingContext, KeyBinding, KeyPattern, KeySequence, Priority};
 use crate::input::EditorCommand;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use std::thread;
 use std::time::Duration;
 
@@ -70,7 +70,7 @@ fn test_single_key_matched() {
 
     // Press 'i' in Normal mode
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     match result {
         MatchResult::Matched(cmd) => {
@@ -96,7 +96,7 @@ fn test_single_key_no_match_wrong_mode() {
 
     // Press 'i' in Insert mode (wrong mode)
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
 
     assert_eq!(result, MatchResult::NoMatch);
 }
@@ -108,7 +108,7 @@ fn test_single_key_no_match_unmapped() {
     // Don't register any bindings
     // Press 'x' (unmapped)
     let event = key_event(KeyCode::Char('x'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
 
     assert_eq!(result, MatchResult::NoMatch);
 }
@@ -132,12 +132,12 @@ fn test_multi_key_sequence_partial_then_matched() {
 
     // First 'd' - partial match
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // Second 'd' - complete match
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::DeleteChar);
@@ -165,12 +165,12 @@ fn test_multi_key_sequence_partial_then_no_match() {
 
     // First 'd' - partial match
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // Press 'x' instead of second 'd' - no match
     let event = key_event(KeyCode::Char('x'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::NoMatch);
 }
 
@@ -194,17 +194,17 @@ fn test_three_key_sequence() {
 
     // 'a' - partial
     let event = key_event(KeyCode::Char('a'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // 'b' - still partial
     let event = key_event(KeyCode::Char('b'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // 'c' - complete match
     let event = key_event(KeyCode::Char('c'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::Save);
@@ -232,14 +232,14 @@ fn test_on_mode_change_clears_buffer() {
 
     // Type 'd' - partial match
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // Mode change - buffer should be cleared
     handler.on_mode_change();
 
     // Type 'd' again in Insert mode - should be NoMatch (not continuing sequence)
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
     assert_eq!(result, MatchResult::NoMatch);
 }
 
@@ -262,7 +262,7 @@ fn test_global_context_active_in_normal() {
 
     // Press Ctrl+S in Normal mode
     let event = key_event(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::Save);
@@ -290,7 +290,7 @@ fn test_global_context_active_in_insert() {
 
     // Press Ctrl+S in Insert mode
     let event = key_event(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::Save);
@@ -318,7 +318,7 @@ fn test_global_context_not_active_in_prompt() {
 
     // Press Ctrl+S in Prompt mode - should not match
     let event = key_event(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Prompt);
+    let result = handler.process_key_event(event, &EditorMode::Prompt);
     assert_eq!(result, MatchResult::NoMatch);
 }
 
@@ -349,7 +349,7 @@ fn test_priority_ordering_user_over_default() {
 
     // Press 'i' - should execute user binding (Save), not default (ChangeMode)
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::Save); // User binding wins
@@ -374,7 +374,7 @@ fn test_key_event_to_pattern_conversion() {
 
     // Test exact match: 'a' with NONE
     let event = key_event(KeyCode::Char('a'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Matched(_)));
 }
 
@@ -397,17 +397,17 @@ fn test_key_event_modifier_mismatch() {
 
     // Press 'a' with SHIFT (wrong modifier)
     let event = key_event(KeyCode::Char('a'), KeyModifiers::SHIFT);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::NoMatch);
 
     // Press 'a' with NONE (no modifier, wrong)
     let event = key_event(KeyCode::Char('a'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::NoMatch);
 
     // Press 'a' with CONTROL (correct)
     let event = key_event(KeyCode::Char('a'), KeyModifiers::CONTROL);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Matched(_)));
 }
 
@@ -430,7 +430,7 @@ fn test_check_timeout_clears_buffer() {
 
     // Type 'd' - partial match
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // Check timeout immediately - should not clear
@@ -443,7 +443,7 @@ fn test_check_timeout_clears_buffer() {
     assert!(handler.check_timeout());
 
     // Type 'd' again - should be partial, not completing the old sequence
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 }
 
@@ -463,11 +463,11 @@ fn test_match_clears_buffer() {
 
     // Press 'i' - matched
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Matched(_)));
 
     // Buffer should be cleared, so pressing 'i' again should be a fresh match
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Matched(_)));
 }
 
@@ -490,17 +490,17 @@ fn test_no_match_clears_buffer() {
 
     // Type 'd' - partial
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 
     // Type 'x' - no match, buffer should be cleared
     let event = key_event(KeyCode::Char('x'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::NoMatch);
 
     // Type 'd' again - should be partial (fresh start)
     let event = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     assert_eq!(result, MatchResult::Partial);
 }
 
@@ -531,7 +531,7 @@ fn test_multiple_bindings_different_contexts() {
 
     // Press 'i' in Normal mode
     let event = key_event(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = handler.process_key_event(event, EditorMode::Normal);
+    let result = handler.process_key_event(event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::ChangeMode(EditorMode::Insert));
@@ -540,7 +540,7 @@ fn test_multiple_bindings_different_contexts() {
     }
 
     // Press 'i' in Insert mode
-    let result = handler.process_key_event(event, EditorMode::Insert);
+    let result = handler.process_key_event(event, &EditorMode::Insert);
     match result {
         MatchResult::Matched(cmd) => {
             assert_eq!(cmd, EditorCommand::InsertChar('i'));
@@ -597,14 +597,14 @@ fn test_integration_full_workflow() {
     // Step 1: Press 'i' to enter insert mode
     let result = handler.process_key_event(
         key_event(KeyCode::Char('i'), KeyModifiers::NONE),
-        EditorMode::Normal,
+        &EditorMode::Normal,
     );
     assert!(matches!(result, MatchResult::Matched(_)));
 
     // Step 2: Switch to Insert mode, press Ctrl+S to save
     let result = handler.process_key_event(
         key_event(KeyCode::Char('s'), KeyModifiers::CONTROL),
-        EditorMode::Insert,
+        &EditorMode::Insert,
     );
     match result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Save),
@@ -617,16 +617,231 @@ fn test_integration_full_workflow() {
     // Step 4: Type "dd" to delete line
     let result = handler.process_key_event(
         key_event(KeyCode::Char('d'), KeyModifiers::NONE),
-        EditorMode::Normal,
+        &EditorMode::Normal,
     );
     assert_eq!(result, MatchResult::Partial);
 
     let result = handler.process_key_event(
         key_event(KeyCode::Char('d'), KeyModifiers::NONE),
-        EditorMode::Normal,
+        &EditorMode::Normal,
     );
     match result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::DeleteChar),
         _ => panic!("Expected DeleteChar command"),
     }
 }
+
+#[test]
+fn test_describe_key_waits_for_complete_sequence() {
+    let mut handler = InputHandler::new();
+    let dd = KeyBinding::new(
+        KeySequence::new(vec![
+            KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE),
+            KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE),
+        ])
+        .unwrap(),
+        EditorCommand::DeleteChar,
+        BindingContext::Mode(EditorMode::Normal),
+        Priority::Default,
+    );
+    handler.register_binding(dd).unwrap();
+
+    handler.describe_next_sequence();
+    assert!(handler.is_describing());
+
+    let d = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
+    assert_eq!(handler.describe_key_event(d, &EditorMode::Normal), None);
+    assert!(handler.is_describing());
+    assert_eq!(
+        handler.describe_key_event(d, &EditorMode::Normal).as_deref(),
+        Some("d d runs delete_char in normal mode (normal, default)")
+    );
+    assert!(!handler.is_describing());
+
+    // Described keys were not executed and the next key is handled normally
+    assert_eq!(handler.process_key_event(d, &EditorMode::Normal), MatchResult::Partial);
+}
+
+#[test]
+fn test_describe_key_reports_unbound_key_immediately() {
+    let mut handler = InputHandler::new();
+
+    handler.describe_next_sequence();
+    let text = handler.describe_key_event(key_event(KeyCode::F(5), KeyModifiers::NONE), &EditorMode::Insert);
+
+    assert_eq!(text.as_deref(), Some("F5 is not bound in insert mode"));
+    assert!(!handler.is_describing());
+}
+
+#[test]
+fn test_key_hints_list_winning_continuations() {
+    let mut handler = InputHandler::new();
+    let g = KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE);
+    let sequence = |second: char| {
+        KeySequence::new(vec![g, KeyPattern::new(KeyCode::Char(second), KeyModifiers::NONE)]).unwrap()
+    };
+    let bindings = [
+        (sequence('g'), EditorCommand::PageUp, BindingContext::Global, Priority::Default),
+        (sequence('e'), EditorCommand::PageDown, BindingContext::Global, Priority::Default),
+        (sequence('e'), EditorCommand::Quit, BindingContext::Global, Priority::User),
+        (sequence('x'), EditorCommand::Save, BindingContext::Mode(EditorMode::Insert), Priority::Default),
+    ];
+    for (sequence, command, context, priority) in bindings {
+        handler.register_binding(KeyBinding::new(sequence, command, context, priority)).unwrap();
+    }
+
+    // Nothing is pending yet
+    assert_eq!(handler.key_hints(&EditorMode::Normal, Duration::ZERO), None);
+
+    handler.process_key_event(key_event(KeyCode::Char('g'), KeyModifiers::NONE), &EditorMode::Normal);
+    let hints = handler.key_hints(&EditorMode::Normal, Duration::ZERO).unwrap();
+
+    assert_eq!(hints.pending, "g");
+    let entries: Vec<_> = hints.hints.iter().map(|h| (h.keys.as_str(), h.command.as_str())).collect();
+    assert_eq!(entries, vec![("e", "quit"), ("g", "page.up")]);
+}
+
+#[test]
+fn test_key_hints_wait_for_delay() {
+    let mut handler = InputHandler::new();
+    let d = KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE);
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![d, d]).unwrap(),
+            EditorCommand::DeleteChar,
+            BindingContext::Global,
+            Priority::Default,
+        ))
+        .unwrap();
+
+    handler.process_key_event(key_event(KeyCode::Char('d'), KeyModifiers::NONE), &EditorMode::Insert);
+
+    assert_eq!(handler.key_hints(&EditorMode::Insert, Duration::from_secs(60)), None);
+    thread::sleep(Duration::from_millis(20));
+    assert!(handler.key_hints(&EditorMode::Insert, Duration::from_millis(10)).is_some());
+}
+
+// Helper function to create a KeyEvent with a kind and state
+fn enhanced_key_event(code: KeyCode, kind: KeyEventKind, state: KeyEventState) -> KeyEvent {
+    KeyEvent::new_with_kind_and_state(code, KeyModifiers::NONE, kind, state)
+}
+
+#[test]
+fn test_unbound_release_is_ignored() {
+    let mut handler = InputHandler::new();
+    let d = KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE);
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![d, d]).unwrap(),
+            EditorCommand::DeleteChar,
+            BindingContext::Global,
+            Priority::Default,
+        ))
+        .unwrap();
+
+    let press = key_event(KeyCode::Char('d'), KeyModifiers::NONE);
+    let release = enhanced_key_event(KeyCode::Char('d'), KeyEventKind::Release, KeyEventState::NONE);
+
+    // Releases between the keys don't break the sequence
+    assert_eq!(handler.process_key_event(press, &EditorMode::Insert), MatchResult::Partial);
+    assert_eq!(handler.process_key_event(release, &EditorMode::Insert), MatchResult::Ignored);
+    assert_eq!(
+        handler.process_key_event(press, &EditorMode::Insert),
+        MatchResult::Matched(EditorCommand::DeleteChar)
+    );
+}
+
+#[test]
+fn test_bound_release_matches() {
+    let mut handler = InputHandler::new();
+    let release = KeyPattern::new(KeyCode::F(5), KeyModifiers::NONE).with_kind(KeyEventKind::Release);
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![release]).unwrap(),
+            EditorCommand::Save,
+            BindingContext::Global,
+            Priority::User,
+        ))
+        .unwrap();
+
+    let event = enhanced_key_event(KeyCode::F(5), KeyEventKind::Release, KeyEventState::NONE);
+    assert_eq!(
+        handler.process_key_event(event, &EditorMode::Normal),
+        MatchResult::Matched(EditorCommand::Save)
+    );
+    assert_eq!(
+        handler.process_key_event(key_event(KeyCode::F(5), KeyModifiers::NONE), &EditorMode::Normal),
+        MatchResult::NoMatch
+    );
+}
+
+#[test]
+fn test_repeat_falls_back_to_press() {
+    let mut handler = InputHandler::new();
+    let j = KeyPattern::new(KeyCode::Char('j'), KeyModifiers::NONE);
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![j]).unwrap(),
+            EditorCommand::MoveCursor(crate::input::Direction::Down),
+            BindingContext::Mode(EditorMode::Normal),
+            Priority::Default,
+        ))
+        .unwrap();
+
+    let repeat = enhanced_key_event(KeyCode::Char('j'), KeyEventKind::Repeat, KeyEventState::NONE);
+    assert_eq!(
+        handler.process_key_event(repeat, &EditorMode::Normal),
+        MatchResult::Matched(EditorCommand::MoveCursor(crate::input::Direction::Down))
+    );
+
+    // An explicit repeat binding takes over
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![j.with_kind(KeyEventKind::Repeat)]).unwrap(),
+            EditorCommand::PageDown,
+            BindingContext::Mode(EditorMode::Normal),
+            Priority::User,
+        ))
+        .unwrap();
+    assert_eq!(
+        handler.process_key_event(repeat, &EditorMode::Normal),
+        MatchResult::Matched(EditorCommand::PageDown)
+    );
+}
+
+#[test]
+fn test_keypad_key_falls_back_to_plain_key() {
+    let mut handler = InputHandler::new();
+    let enter = KeyPattern::new(KeyCode::Enter, KeyModifiers::NONE);
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![enter]).unwrap(),
+            EditorCommand::InsertChar('\n'),
+            BindingContext::Mode(EditorMode::Insert),
+            Priority::Default,
+        ))
+        .unwrap();
+
+    let kp_enter = enhanced_key_event(KeyCode::Enter, KeyEventKind::Press, KeyEventState::KEYPAD);
+    assert_eq!(
+        handler.process_key_event(kp_enter, &EditorMode::Insert),
+        MatchResult::Matched(EditorCommand::InsertChar('\n'))
+    );
+
+    handler
+        .register_binding(KeyBinding::new(
+            KeySequence::new(vec![enter.on_keypad()]).unwrap(),
+            EditorCommand::Save,
+            BindingContext::Mode(EditorMode::Insert),
+            Priority::User,
+        ))
+        .unwrap();
+    assert_eq!(
+        handler.process_key_event(kp_enter, &EditorMode::Insert),
+        MatchResult::Matched(EditorCommand::Save)
+    );
+    assert_eq!(
+        handler.process_key_event(key_event(KeyCode::Enter, KeyModifiers::NONE), &EditorMode::Insert),
+        MatchResult::Matched(EditorCommand::InsertChar('\n'))
+    );
+}
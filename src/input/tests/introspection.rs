@@ -0,0 +1,141 @@
+//! Unit tests for keybinding reports
+
+use crate::editor::EditorMode;
+use crate::input::bindings::register_default_bindings;
+use crate::input::introspection::{describe_key, keybinding_report};
+use crate::input::keybinding::{BindingContext, KeyBinding, KeyPattern, KeySequence, Priority};
+use crate::input::registry::KeyBindingRegistry;
+use crate::input::EditorCommand;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn key(ch: char) -> KeyPattern {
+    KeyPattern::new(KeyCode::Char(ch), KeyModifiers::NONE)
+}
+
+/// Registry with `g` (user, Normal mode) and `g d` (default, global)
+fn conflicting_registry() -> KeyBindingRegistry {
+    let mut registry = KeyBindingRegistry::default();
+    registry
+        .register(KeyBinding::new(
+            KeySequence::new(vec![key('g'), key('d')]).unwrap(),
+            EditorCommand::PageDown,
+            BindingContext::Global,
+            Priority::Default,
+        ))
+        .unwrap();
+    registry
+        .register(KeyBinding::new(
+            KeySequence::new(vec![key('g')]).unwrap(),
+            EditorCommand::PageUp,
+            BindingContext::Mode(EditorMode::Normal),
+            Priority::User,
+        ))
+        .unwrap();
+    registry
+}
+
+#[test]
+fn test_report_lists_every_binding() {
+    let mut registry = KeyBindingRegistry::default();
+    register_default_bindings(&mut registry).unwrap();
+
+    let report = keybinding_report(&registry);
+
+    assert!(report.starts_with(&format!("Keybindings ({})", registry.len())));
+    assert!(report.lines().any(|line| {
+        line.split_whitespace().collect::<Vec<_>>() == ["Ctrl+s", "file.save", "global", "default"]
+    }));
+    assert!(report.contains("Shift+F1"));
+    assert!(report.contains("keybindings.describe_key"));
+    assert!(report.contains("Shadowed bindings\n  none"));
+    assert!(report.contains("Prefix conflicts\n  none"));
+}
+
+#[test]
+fn test_report_includes_shadowed_and_prefix_conflicts() {
+    let mut registry = conflicting_registry();
+    registry
+        .register(KeyBinding::new(
+            KeySequence::new(vec![key('g')]).unwrap(),
+            EditorCommand::Quit,
+            BindingContext::Mode(EditorMode::Normal),
+            Priority::Plugin,
+        ))
+        .unwrap();
+
+    let report = keybinding_report(&registry);
+
+    assert!(report.contains(
+        "  g → quit (normal, plugin) is overridden by g → page.up (normal, user) in normal"
+    ));
+    assert!(report.contains(
+        "  g d → page.down (global, default) is unreachable: g → page.up (normal, user) fires first in normal"
+    ));
+}
+
+#[test]
+fn test_describe_key_bound_unbound_and_prefix() {
+    let registry = conflicting_registry();
+
+    let keys = [key('g')];
+    let description = registry.describe_sequence(&keys, &EditorMode::Normal);
+    assert_eq!(
+        describe_key(&keys, &EditorMode::Normal, &description),
+        "g runs page.up in normal mode (normal, user)"
+    );
+
+    let description = registry.describe_sequence(&keys, &EditorMode::Insert);
+    assert_eq!(
+        describe_key(&keys, &EditorMode::Insert, &description),
+        "g starts g d → page.down in insert mode"
+    );
+
+    let keys = [key('x')];
+    let description = registry.describe_sequence(&keys, &EditorMode::Insert);
+    assert_eq!(
+        describe_key(&keys, &EditorMode::Insert, &description),
+        "x is not bound in insert mode"
+    );
+}
+
+#[test]
+fn test_describe_key_mentions_overridden_bindings() {
+    let mut registry = conflicting_registry();
+    registry
+        .register(KeyBinding::new(
+            KeySequence::new(vec![key('g')]).unwrap(),
+            EditorCommand::Quit,
+            BindingContext::Global,
+            Priority::Default,
+        ))
+        .unwrap();
+    let keys = [key('g')];
+
+    let description = registry.describe_sequence(&keys, &EditorMode::Normal);
+
+    assert_eq!(
+        describe_key(&keys, &EditorMode::Normal, &description),
+        "g runs page.up in normal mode (normal, user); overrides quit (default)"
+    );
+}
+
+#[test]
+fn test_describe_key_reports_unbound_sequence() {
+    let mut registry = conflicting_registry();
+    registry
+        .register(KeyBinding::new(
+            KeySequence::new(vec![key('g')]).unwrap(),
+            EditorCommand::Noop,
+            BindingContext::Global,
+            Priority::Project,
+        ))
+        .unwrap();
+    let keys = [key('g')];
+
+    let description = registry.describe_sequence(&keys, &EditorMode::Normal);
+
+    assert_eq!(
+        describe_key(&keys, &EditorMode::Normal, &description),
+        "g is unbound in normal mode (global, project); overrides page.up (user)"
+    );
+}
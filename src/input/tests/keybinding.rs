@@ -5,7 +5,7 @@ use crate::input::keybinding::{
     BindingContext, KeyBinding, KeyPattern, KeySequence, Priority, PRIMARY_MODIFIER, ParseError,
 };
 use crate::input::EditorCommand;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MediaKeyCode};
 use std::str::FromStr;
 
 /// Helper to create a KeyEvent from code and modifiers
@@ -298,11 +298,11 @@ fn test_binding_context_global_excludes_prompt() {
     let ctx = BindingContext::Global;
 
     // Global should be active in Insert and Normal
-    assert!(ctx.is_active(EditorMode::Insert));
-    assert!(ctx.is_active(EditorMode::Normal));
+    assert!(ctx.is_active(&EditorMode::Insert));
+    assert!(ctx.is_active(&EditorMode::Normal));
 
     // Global should NOT be active in Prompt (Prompt mode should handle its own bindings)
-    assert!(!ctx.is_active(EditorMode::Prompt));
+    assert!(!ctx.is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -310,9 +310,9 @@ fn test_binding_context_mode_specific() {
     let ctx = BindingContext::Mode(EditorMode::Normal);
 
     // Should only be active in Normal mode
-    assert!(ctx.is_active(EditorMode::Normal));
-    assert!(!ctx.is_active(EditorMode::Insert));
-    assert!(!ctx.is_active(EditorMode::Prompt));
+    assert!(ctx.is_active(&EditorMode::Normal));
+    assert!(!ctx.is_active(&EditorMode::Insert));
+    assert!(!ctx.is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -320,11 +320,11 @@ fn test_binding_context_multi_mode() {
     let ctx = BindingContext::Modes(vec![EditorMode::Insert, EditorMode::Normal]);
 
     // Should be active in both Insert and Normal
-    assert!(ctx.is_active(EditorMode::Insert));
-    assert!(ctx.is_active(EditorMode::Normal));
+    assert!(ctx.is_active(&EditorMode::Insert));
+    assert!(ctx.is_active(&EditorMode::Normal));
 
     // Should NOT be active in Prompt
-    assert!(!ctx.is_active(EditorMode::Prompt));
+    assert!(!ctx.is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -335,9 +335,9 @@ fn test_binding_context_plugin_without_mode_filter() {
     };
 
     // Plugin without mode filter should be active in all modes
-    assert!(ctx.is_active(EditorMode::Insert));
-    assert!(ctx.is_active(EditorMode::Normal));
-    assert!(ctx.is_active(EditorMode::Prompt));
+    assert!(ctx.is_active(&EditorMode::Insert));
+    assert!(ctx.is_active(&EditorMode::Normal));
+    assert!(ctx.is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -348,9 +348,9 @@ fn test_binding_context_plugin_with_mode_filter() {
     };
 
     // Plugin with mode filter should only be active in specified modes
-    assert!(ctx.is_active(EditorMode::Insert));
-    assert!(ctx.is_active(EditorMode::Normal));
-    assert!(!ctx.is_active(EditorMode::Prompt));
+    assert!(ctx.is_active(&EditorMode::Insert));
+    assert!(ctx.is_active(&EditorMode::Normal));
+    assert!(!ctx.is_active(&EditorMode::Prompt));
 }
 
 // ============================================================================
@@ -415,9 +415,9 @@ fn test_key_binding_global_save() {
     );
 
     // Global binding should be active in Insert and Normal
-    assert!(binding.context().is_active(EditorMode::Insert));
-    assert!(binding.context().is_active(EditorMode::Normal));
-    assert!(!binding.context().is_active(EditorMode::Prompt));
+    assert!(binding.context().is_active(&EditorMode::Insert));
+    assert!(binding.context().is_active(&EditorMode::Normal));
+    assert!(!binding.context().is_active(&EditorMode::Prompt));
 }
 
 #[test]
@@ -436,8 +436,8 @@ fn test_key_binding_mode_specific() {
     );
 
     // Should only be active in Normal mode
-    assert!(binding.context().is_active(EditorMode::Normal));
-    assert!(!binding.context().is_active(EditorMode::Insert));
+    assert!(binding.context().is_active(&EditorMode::Normal));
+    assert!(!binding.context().is_active(&EditorMode::Insert));
 }
 
 #[test]
@@ -805,11 +805,11 @@ fn test_parse_unknown_key() {
 
 #[test]
 fn test_parse_invalid_function_key() {
-    // F13 doesn't exist
-    let result = KeySequence::from_str("F13");
+    // F25 doesn't exist
+    let result = KeySequence::from_str("F25");
     assert!(result.is_err());
     match result.unwrap_err() {
-        ParseError::UnknownKey(s) => assert_eq!(s, "F13"),
+        ParseError::UnknownKey(s) => assert_eq!(s, "F25"),
         e => panic!("Expected UnknownKey, got {:?}", e),
     }
 
@@ -878,3 +878,162 @@ fn test_parse_error_messages() {
     assert!(msg.contains("unknown key name"));
     assert!(msg.contains("BadKey"));
 }
+
+#[test]
+fn test_parse_extended_keys() {
+    let parse = |s: &str| KeySequence::from_str(s).unwrap().patterns()[0];
+
+    assert_eq!(parse("F13").code, KeyCode::F(13));
+    assert_eq!(parse("F24").code, KeyCode::F(24));
+    assert_eq!(parse("Insert").code, KeyCode::Insert);
+    assert_eq!(parse("ins").code, KeyCode::Insert);
+    assert_eq!(parse("BackTab").code, KeyCode::BackTab);
+    assert_eq!(parse("Menu").code, KeyCode::Menu);
+    assert_eq!(parse("CapsLock").code, KeyCode::CapsLock);
+    assert_eq!(parse("PrintScreen").code, KeyCode::PrintScreen);
+    assert_eq!(parse("MediaPlayPause").code, KeyCode::Media(MediaKeyCode::PlayPause));
+    assert_eq!(parse("volumeup").code, KeyCode::Media(MediaKeyCode::RaiseVolume));
+    assert_eq!(parse("Mute").code, KeyCode::Media(MediaKeyCode::MuteVolume));
+}
+
+#[test]
+fn test_parse_keypad_keys() {
+    let kp5 = KeySequence::from_str("Kp5").unwrap().patterns()[0];
+    assert_eq!(kp5, KeyPattern::new(KeyCode::Char('5'), KeyModifiers::NONE).on_keypad());
+
+    let kp_enter = KeySequence::from_str("Ctrl+kpenter").unwrap().patterns()[0];
+    assert_eq!(kp_enter, KeyPattern::new(KeyCode::Enter, KeyModifiers::CONTROL).on_keypad());
+
+    let kp_plus = KeySequence::from_str("KpPlus").unwrap().patterns()[0];
+    assert_eq!(kp_plus.code, KeyCode::Char('+'));
+    assert!(kp_plus.keypad);
+
+    // Only keys that exist on a keypad
+    for name in ["Kpa", "KpF1", "KpSpace"] {
+        assert_eq!(
+            KeySequence::from_str(name).unwrap_err(),
+            ParseError::UnknownKey(name.to_string())
+        );
+    }
+}
+
+#[test]
+fn test_parse_event_kinds() {
+    let release = KeySequence::from_str("Release+Ctrl+Space").unwrap().patterns()[0];
+    assert_eq!(release.kind, KeyEventKind::Release);
+    assert_eq!(release.modifiers, KeyModifiers::CONTROL);
+    assert_eq!(release.code, KeyCode::Char(' '));
+
+    let repeat = KeySequence::from_str("repeat+j").unwrap().patterns()[0];
+    assert_eq!(repeat.kind, KeyEventKind::Repeat);
+
+    let press = KeySequence::from_str("j").unwrap().patterns()[0];
+    assert_eq!(press.kind, KeyEventKind::Press);
+    assert!(!press.keypad);
+}
+
+#[test]
+fn test_key_pattern_matches_event_kind_and_keypad() {
+    let release = KeyEvent::new_with_kind_and_state(
+        KeyCode::Char('5'),
+        KeyModifiers::NONE,
+        KeyEventKind::Release,
+        KeyEventState::NONE,
+    );
+    let keypad = KeyEvent::new_with_kind_and_state(
+        KeyCode::Char('5'),
+        KeyModifiers::NONE,
+        KeyEventKind::Press,
+        KeyEventState::KEYPAD,
+    );
+    let five = KeyPattern::new(KeyCode::Char('5'), KeyModifiers::NONE);
+
+    assert!(!five.matches(&release));
+    assert!(five.with_kind(KeyEventKind::Release).matches(&release));
+    assert!(!five.matches(&keypad));
+    assert!(five.on_keypad().matches(&keypad));
+}
+
+#[test]
+fn test_key_pattern_from_event_drops_shift_on_backtab() {
+    let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+    assert_eq!(
+        KeyPattern::from_event(&event),
+        KeyPattern::new(KeyCode::BackTab, KeyModifiers::NONE)
+    );
+}
+
+// ============================================================================
+// Display Tests
+// ============================================================================
+
+#[test]
+fn test_key_sequence_display_uses_config_syntax() {
+    let sequence = KeySequence::from_str("Ctrl+Alt+s").unwrap();
+    assert_eq!(sequence.to_string(), "Ctrl+Alt+s");
+
+    let sequence = KeySequence::from_str("g d").unwrap();
+    assert_eq!(sequence.to_string(), "g d");
+    assert_eq!(KeySequence::from_str(&sequence.to_string()).unwrap(), sequence);
+
+    let sequence = KeySequence::new(vec![
+        KeyPattern::new(KeyCode::F(1), KeyModifiers::SHIFT),
+        KeyPattern::new(KeyCode::Char(' '), KeyModifiers::NONE),
+        KeyPattern::new(KeyCode::PageDown, KeyModifiers::NONE),
+    ])
+    .unwrap();
+    assert_eq!(sequence.to_string(), "Shift+F1 Space PageDown");
+}
+
+#[test]
+fn test_key_sequence_is_prefix_of() {
+    let g = KeySequence::from_str("g").unwrap();
+    let gd = KeySequence::from_str("g d").unwrap();
+
+    assert!(g.is_prefix_of(&gd));
+    assert!(!gd.is_prefix_of(&g));
+    assert!(!g.is_prefix_of(&g));
+    assert!(!KeySequence::from_str("d").unwrap().is_prefix_of(&gd));
+}
+
+#[test]
+fn test_context_and_priority_display() {
+    assert_eq!(BindingContext::Global.to_string(), "global");
+    assert_eq!(BindingContext::Mode(EditorMode::Normal).to_string(), "normal");
+    assert_eq!(
+        BindingContext::Modes(vec![EditorMode::Insert, EditorMode::Normal]).to_string(),
+        "insert, normal"
+    );
+    assert_eq!(
+        BindingContext::Plugin {
+            name: "lsp".to_string(),
+            modes: None
+        }
+        .to_string(),
+        "plugin lsp: all modes"
+    );
+    assert_eq!(Priority::Default.to_string(), "default");
+    assert_eq!(Priority::Project.to_string(), "project");
+}
+
+#[test]
+fn test_extended_key_display_round_trips() {
+    for text in [
+        "F24",
+        "Insert",
+        "BackTab",
+        "Menu",
+        "MediaPlayPause",
+        "VolumeDown",
+        "Kp7",
+        "KpEnter",
+        "Ctrl+KpMinus",
+        "KpBegin",
+        "Release+Ctrl+Space",
+        "Repeat+j",
+    ] {
+        let sequence = KeySequence::from_str(text).unwrap();
+        assert_eq!(sequence.to_string(), text);
+        assert_eq!(KeySequence::from_str(&sequence.to_string()).unwrap(), sequence);
+    }
+}
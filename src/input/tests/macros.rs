@@ -0,0 +1,106 @@
+//! Unit tests for keyboard macros
+
+use crate::input::macros::{repeat_commands, Macros};
+use crate::input::{Direction, EditorCommand};
+
+#[test]
+fn test_record_skips_recording_commands() {
+    let mut macros = Macros::new();
+    macros.record(&EditorCommand::Save);
+    assert_eq!(macros.recording(), None);
+
+    macros.start_recording('a');
+    assert_eq!(macros.recording(), Some('a'));
+    macros.record(&EditorCommand::InsertChar('x'));
+    macros.record(&EditorCommand::ToggleMacroRecording('a'));
+    macros.record(&EditorCommand::MoveCursor(Direction::Down));
+
+    assert_eq!(macros.stop_recording(), Some(('a', 2)));
+    assert_eq!(macros.stop_recording(), None);
+    assert_eq!(
+        macros.get('a'),
+        Some(&[EditorCommand::InsertChar('x'), EditorCommand::MoveCursor(Direction::Down)][..])
+    );
+    assert_eq!(macros.get('b'), None);
+}
+
+#[test]
+fn test_playback_rejects_recursion() {
+    let mut macros = Macros::new();
+    assert!(macros.start_playback('a'));
+    assert!(macros.start_playback('b'));
+    assert!(!macros.start_playback('a'));
+    macros.finish_playback();
+    macros.finish_playback();
+    assert!(macros.start_playback('a'));
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data/macros.toml");
+
+    let (mut macros, warnings) = Macros::load(path.clone()).unwrap();
+    assert!(warnings.is_empty());
+    let recorded = vec![
+        EditorCommand::InsertChar('"'),
+        EditorCommand::MoveCursorBy { direction: Direction::Down, count: 2 },
+        EditorCommand::Chain(vec![EditorCommand::Save, EditorCommand::Quit]),
+        EditorCommand::PromptInsertChar('\n'),
+    ];
+    macros.set('q', recorded.clone());
+    macros.set('1', vec![EditorCommand::PlayMacro { register: 'q', count: 2 }]);
+    macros.save().unwrap();
+
+    let (loaded, warnings) = Macros::load(path).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(loaded.get('q'), Some(&recorded[..]));
+    assert_eq!(loaded.get('1'), Some(&[EditorCommand::PlayMacro { register: 'q', count: 2 }][..]));
+}
+
+#[test]
+fn test_load_skips_invalid_registers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("macros.toml");
+    std::fs::write(
+        &path,
+        "[registers]\na = [\"file.save\"]\nlong = [\"quit\"]\nb = [\"explode\"]\n",
+    )
+    .unwrap();
+
+    let (macros, warnings) = Macros::load(path).unwrap();
+
+    assert_eq!(macros.get('a'), Some(&[EditorCommand::Save][..]));
+    assert_eq!(
+        warnings,
+        vec![
+            "macro register 'b': unknown command 'explode': check available commands in documentation",
+            "macro register 'long': expected a letter or digit",
+        ]
+    );
+}
+
+#[test]
+fn test_new_macros_are_not_saved() {
+    let mut macros = Macros::new();
+    macros.set('q', vec![EditorCommand::Save]);
+    assert!(macros.save().is_ok());
+}
+
+#[test]
+fn test_repeat_commands() {
+    let commands = [EditorCommand::InsertChar('a'), EditorCommand::Save];
+
+    assert_eq!(
+        repeat_commands(&commands, 2).unwrap(),
+        vec![
+            EditorCommand::InsertChar('a'),
+            EditorCommand::Save,
+            EditorCommand::InsertChar('a'),
+            EditorCommand::Save,
+        ]
+    );
+    assert!(repeat_commands(&commands, 10_000).is_ok());
+    assert!(repeat_commands(&commands, 10_001).is_err());
+    assert!(repeat_commands(&commands, usize::MAX).is_err());
+}
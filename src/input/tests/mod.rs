@@ -5,11 +5,17 @@
 
 mod bindings;
 mod command;
+mod command_line;
 mod config;
+mod config_layers;
 pub mod default_bindings_coverage;
 mod input_handler;
+mod introspection;
 mod keybinding;
+mod macros;
 pub mod mode_transitions;
 pub mod multikey_sequences;
+mod palette;
 mod plugin_registry;
 mod registry;
+mod user_commands;
@@ -62,7 +62,7 @@ mod partial_sequence_clearing {
         let mut handler = setup_handler_with_multikey();
 
         // Start "dd" sequence in Normal mode - should be Partial
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -73,7 +73,7 @@ mod partial_sequence_clearing {
         handler.on_mode_change();
 
         // Type 'd' again in Insert mode - should be NoMatch (not completing the sequence)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Insert);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Insert);
         assert_eq!(
             result,
             MatchResult::NoMatch,
@@ -86,14 +86,14 @@ mod partial_sequence_clearing {
         let mut handler = setup_handler_with_multikey();
 
         // Start "dd" sequence in Normal mode
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Simulate mode change to Prompt
         handler.on_mode_change();
 
         // Type 'd' in Prompt mode - should be NoMatch
-        let result = handler.process_key_event(char_key('d'), EditorMode::Prompt);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Prompt);
         assert_eq!(
             result,
             MatchResult::NoMatch,
@@ -106,18 +106,18 @@ mod partial_sequence_clearing {
         let mut handler = setup_handler_with_multikey();
 
         // Type 'd' in Normal mode
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Press Esc (which triggers mode change to Normal)
-        let _result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Normal);
+        let _result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Normal);
         // Esc in Normal mode might be NoMatch or trigger some command, but buffer should clear
 
         // Simulate explicit mode change (in case Esc doesn't trigger it)
         handler.on_mode_change();
 
         // Type 'd' again - should start fresh, not complete the sequence
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -130,11 +130,11 @@ mod partial_sequence_clearing {
         let mut handler = setup_handler_with_multikey();
 
         // In Insert mode, type 'd' (NoMatch - will be inserted)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Insert);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Insert);
         assert_eq!(result, MatchResult::NoMatch);
 
         // Press Esc to return to Normal mode
-        let result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Insert);
+        let result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Insert);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Normal)),
@@ -145,7 +145,7 @@ mod partial_sequence_clearing {
         handler.on_mode_change();
 
         // Now type 'd' in Normal mode - should be Partial (starting fresh)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -153,7 +153,7 @@ mod partial_sequence_clearing {
         );
 
         // Type 'd' again - should complete the sequence
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::DeleteChar),
@@ -172,7 +172,7 @@ mod all_mode_transition_paths {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Press Esc in Insert mode
-        let result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Insert);
+        let result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Insert);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Normal)),
@@ -186,7 +186,7 @@ mod all_mode_transition_paths {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Press 'i' in Normal mode
-        let result = handler.process_key_event(char_key('i'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('i'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Insert)),
@@ -200,7 +200,7 @@ mod all_mode_transition_paths {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Press Esc in Prompt mode
-        let result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Prompt);
+        let result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Prompt);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::CancelPrompt),
@@ -214,7 +214,7 @@ mod all_mode_transition_paths {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Press Enter in Prompt mode
-        let result = handler.process_key_event(key(KeyCode::Enter), EditorMode::Prompt);
+        let result = handler.process_key_event(key(KeyCode::Enter), &EditorMode::Prompt);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::AcceptPrompt),
@@ -227,29 +227,29 @@ mod all_mode_transition_paths {
         let mut handler = setup_handler_with_multikey();
 
         // Start a partial sequence in Normal
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Transition to Insert
         handler.on_mode_change();
-        let result = handler.process_key_event(char_key('d'), EditorMode::Insert);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Insert);
         assert_eq!(result, MatchResult::NoMatch, "Buffer cleared on Normal->Insert");
 
         // Transition back to Normal
         handler.on_mode_change();
 
         // Start partial sequence again
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Transition to Prompt
         handler.on_mode_change();
-        let result = handler.process_key_event(char_key('d'), EditorMode::Prompt);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Prompt);
         assert_eq!(result, MatchResult::NoMatch, "Buffer cleared on Normal->Prompt");
 
         // Transition back to Normal
         handler.on_mode_change();
 
         // Verify fresh start
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial, "Fresh sequence after Prompt->Normal");
     }
 }
@@ -263,7 +263,7 @@ mod rapid_mode_switching {
         let mut handler = setup_handler_with_multikey();
 
         // Normal mode: start 'd'
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Switch to Insert
@@ -273,7 +273,7 @@ mod rapid_mode_switching {
         handler.on_mode_change();
 
         // Type 'd' - should be fresh Partial, not completing previous
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -286,7 +286,7 @@ mod rapid_mode_switching {
         let mut handler = setup_handler_with_multikey();
 
         // Start partial in Normal
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Rapid mode changes: Normal -> Insert -> Normal -> Prompt -> Normal
         handler.on_mode_change(); // Normal -> Insert
@@ -295,7 +295,7 @@ mod rapid_mode_switching {
         handler.on_mode_change(); // Prompt -> Normal
 
         // Buffer should be completely clear
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -303,7 +303,7 @@ mod rapid_mode_switching {
         );
 
         // Complete the sequence properly
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
     }
 
@@ -312,28 +312,28 @@ mod rapid_mode_switching {
         let mut handler = setup_handler_with_multikey();
 
         // Type 'd' in Insert mode (should be NoMatch)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Insert);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Insert);
         assert_eq!(result, MatchResult::NoMatch);
 
         // Switch to Normal mode
         handler.on_mode_change();
 
         // Type 'd' once (Partial)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Switch to Insert
         handler.on_mode_change();
 
         // Type 'd' (NoMatch)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Insert);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Insert);
         assert_eq!(result, MatchResult::NoMatch);
 
         // Switch back to Normal
         handler.on_mode_change();
 
         // Type 'd' - should be fresh Partial
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -365,7 +365,7 @@ mod timeout_during_mode_transition {
         handler.register_binding(dd_binding).unwrap();
 
         // Start sequence
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Mode change before timeout (should clear immediately)
@@ -379,7 +379,7 @@ mod timeout_during_mode_transition {
         );
 
         // Type 'd' in new mode - should be fresh
-        let result = handler.process_key_event(char_key('d'), EditorMode::Insert);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Insert);
         assert_eq!(result, MatchResult::NoMatch);
     }
 
@@ -402,13 +402,13 @@ mod timeout_during_mode_transition {
         handler.register_binding(dd_binding).unwrap();
 
         // Start sequence (with long timeout)
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Immediately change mode (before timeout)
         handler.on_mode_change();
 
         // Buffer should be clear immediately, not waiting for timeout
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -427,7 +427,7 @@ mod mode_specific_behavior {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Enter in Insert mode - inserts newline
-        let result = handler.process_key_event(key(KeyCode::Enter), EditorMode::Insert);
+        let result = handler.process_key_event(key(KeyCode::Enter), &EditorMode::Insert);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::InsertChar('\n')),
@@ -435,7 +435,7 @@ mod mode_specific_behavior {
         );
 
         // Enter in Prompt mode - accepts prompt
-        let result = handler.process_key_event(key(KeyCode::Enter), EditorMode::Prompt);
+        let result = handler.process_key_event(key(KeyCode::Enter), &EditorMode::Prompt);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::AcceptPrompt),
@@ -443,7 +443,7 @@ mod mode_specific_behavior {
         );
 
         // Enter in Normal mode - NoMatch (not bound)
-        let result = handler.process_key_event(key(KeyCode::Enter), EditorMode::Normal);
+        let result = handler.process_key_event(key(KeyCode::Enter), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::NoMatch,
@@ -457,7 +457,7 @@ mod mode_specific_behavior {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Esc in Insert mode - change to Normal
-        let result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Insert);
+        let result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Insert);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Normal)),
@@ -465,7 +465,7 @@ mod mode_specific_behavior {
         );
 
         // Esc in Prompt mode - cancel prompt
-        let result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Prompt);
+        let result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Prompt);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::CancelPrompt),
@@ -473,7 +473,7 @@ mod mode_specific_behavior {
         );
 
         // Esc in Normal mode - NoMatch (not bound by default)
-        let result = handler.process_key_event(key(KeyCode::Esc), EditorMode::Normal);
+        let result = handler.process_key_event(key(KeyCode::Esc), &EditorMode::Normal);
         // Could be NoMatch or some command depending on bindings
         // Just verify it doesn't panic or error
         assert!(
@@ -488,7 +488,7 @@ mod mode_specific_behavior {
         register_default_bindings(handler.registry_mut()).unwrap();
 
         // Backspace in Insert mode - delete char
-        let result = handler.process_key_event(key(KeyCode::Backspace), EditorMode::Insert);
+        let result = handler.process_key_event(key(KeyCode::Backspace), &EditorMode::Insert);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::DeleteChar),
@@ -496,7 +496,7 @@ mod mode_specific_behavior {
         );
 
         // Backspace in Prompt mode - delete from prompt
-        let result = handler.process_key_event(key(KeyCode::Backspace), EditorMode::Prompt);
+        let result = handler.process_key_event(key(KeyCode::Backspace), &EditorMode::Prompt);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::PromptDeleteChar),
@@ -504,7 +504,7 @@ mod mode_specific_behavior {
         );
 
         // Backspace in Normal mode - NoMatch (not bound)
-        let result = handler.process_key_event(key(KeyCode::Backspace), EditorMode::Normal);
+        let result = handler.process_key_event(key(KeyCode::Backspace), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::NoMatch,
@@ -525,7 +525,7 @@ mod integration_with_global_shortcuts {
         // Ctrl+S in Insert mode
         let result = handler.process_key_event(
             char_key_mod('s', KeyModifiers::CONTROL),
-            EditorMode::Insert,
+            &EditorMode::Insert,
         );
         assert_eq!(
             result,
@@ -539,7 +539,7 @@ mod integration_with_global_shortcuts {
         // Ctrl+S in Normal mode should still work
         let result = handler.process_key_event(
             char_key_mod('s', KeyModifiers::CONTROL),
-            EditorMode::Normal,
+            &EditorMode::Normal,
         );
         assert_eq!(
             result,
@@ -553,7 +553,7 @@ mod integration_with_global_shortcuts {
         let mut handler = setup_handler_with_multikey();
 
         // Start partial sequence
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Mode change clears buffer
         handler.on_mode_change();
@@ -561,7 +561,7 @@ mod integration_with_global_shortcuts {
         // Global shortcut should still work
         let result = handler.process_key_event(
             char_key_mod('q', KeyModifiers::CONTROL),
-            EditorMode::Normal,
+            &EditorMode::Normal,
         );
         assert_eq!(
             result,
@@ -580,7 +580,7 @@ mod edge_cases {
         let mut handler = setup_handler_with_multikey();
 
         // Start partial sequence
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Multiple on_mode_change calls (shouldn't cause issues)
         handler.on_mode_change();
@@ -588,7 +588,7 @@ mod edge_cases {
         handler.on_mode_change();
 
         // Should still work normally
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
     }
 
@@ -601,7 +601,7 @@ mod edge_cases {
         handler.on_mode_change();
 
         // Should not cause any issues
-        let result = handler.process_key_event(char_key('i'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('i'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Insert)));
     }
 
@@ -610,15 +610,15 @@ mod edge_cases {
         let mut handler = setup_handler_with_multikey();
 
         // Complete a sequence
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
 
         // Buffer should already be clear, but on_mode_change shouldn't cause issues
         handler.on_mode_change();
 
         // Should work normally
-        let result = handler.process_key_event(char_key('i'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('i'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Insert)));
     }
 }
@@ -95,7 +95,7 @@ mod successful_sequence_completion {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Press 'd' - should be Partial
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -103,7 +103,7 @@ mod successful_sequence_completion {
         );
 
         // Press 'd' again within timeout - should complete
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::DeleteChar),
@@ -116,11 +116,11 @@ mod successful_sequence_completion {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Press 'g' - should be Partial
-        let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial, "First 'g' should be Partial");
 
         // Press 'g' again within timeout - should complete
-        let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up)),
@@ -133,15 +133,15 @@ mod successful_sequence_completion {
         let mut handler = setup_handler_with_three_key_sequence(Duration::from_secs(1));
 
         // Press 'd' - Partial (could be 'dd' or 'daw')
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Press 'a' - still Partial (continuing 'daw')
-        let result = handler.process_key_event(char_key('a'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('a'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Press 'w' - completes 'daw'
-        let result = handler.process_key_event(char_key('w'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('w'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::Save),
@@ -154,8 +154,8 @@ mod successful_sequence_completion {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Type 'dd' very rapidly (no delays)
-        let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         assert_eq!(result1, MatchResult::Partial);
         assert_eq!(result2, MatchResult::Matched(EditorCommand::DeleteChar));
@@ -166,21 +166,21 @@ mod successful_sequence_completion {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Complete 'dd' sequence
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
 
         // Complete 'gg' sequence
-        handler.process_key_event(char_key('g'), EditorMode::Normal);
-        let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        handler.process_key_event(char_key('g'), &EditorMode::Normal);
+        let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up))
         );
 
         // Complete 'dd' again
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
     }
 }
@@ -194,7 +194,7 @@ mod timeout_behavior {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(100));
 
         // Press 'd' - Partial
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Wait for timeout
@@ -205,7 +205,7 @@ mod timeout_behavior {
         assert!(timed_out, "Buffer should be cleared due to timeout");
 
         // Press 'd' again - should be Partial (starting fresh sequence)
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -218,14 +218,14 @@ mod timeout_behavior {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(100));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait for timeout
         thread::sleep(Duration::from_millis(150));
         handler.check_timeout();
 
         // Press 'd' again - should NOT complete 'dd'
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -253,8 +253,8 @@ mod timeout_behavior {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(100));
 
         // Complete 'dd' sequence
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait
         thread::sleep(Duration::from_millis(150));
@@ -272,7 +272,7 @@ mod timeout_behavior {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(100));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // First check before timeout - no clear
         let timed_out = handler.check_timeout();
@@ -300,11 +300,11 @@ mod invalid_key_combinations {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Press 'd' - Partial
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Press 'x' (not a valid continuation) - should be NoMatch
-        let result = handler.process_key_event(char_key('x'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('x'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::NoMatch,
@@ -312,7 +312,7 @@ mod invalid_key_combinations {
         );
 
         // Press 'd' again - should start fresh Partial
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -325,15 +325,15 @@ mod invalid_key_combinations {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Press 'd' - Partial
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Press 'i' (single-key binding for insert mode) - should be NoMatch first (clears buffer)
-        let result = handler.process_key_event(char_key('i'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('i'), &EditorMode::Normal);
         // The 'di' sequence doesn't match anything, so buffer clears and 'i' is NoMatch
         assert_eq!(result, MatchResult::NoMatch);
 
         // Now press 'i' again - should match ChangeMode(Insert)
-        let result = handler.process_key_event(char_key('i'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('i'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::ChangeMode(EditorMode::Insert))
@@ -345,19 +345,19 @@ mod invalid_key_combinations {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Start 'dd': press 'd'
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Switch to 'gg': press 'g' - invalid continuation, clears buffer
-        let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::NoMatch);
 
         // Now start fresh 'gg': press 'g'
-        let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
 
         // Complete 'gg': press 'g'
-        let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::MoveCursor(Direction::Up))
@@ -369,14 +369,14 @@ mod invalid_key_combinations {
         let mut handler = setup_handler_with_three_key_sequence(Duration::from_secs(1));
 
         // Press 'd' - Partial
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Press 'x' (invalid) - NoMatch
-        let result = handler.process_key_event(char_key('x'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('x'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::NoMatch);
 
         // Buffer should be clear, start fresh
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
     }
 }
@@ -390,13 +390,13 @@ mod timeout_reset_behavior {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(200));
 
         // Press 'd' at T=0
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 150ms (less than timeout)
         thread::sleep(Duration::from_millis(150));
 
         // Press 'd' at T=150ms - should reset timeout
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::DeleteChar),
@@ -409,19 +409,19 @@ mod timeout_reset_behavior {
         let mut handler = setup_handler_with_three_key_sequence(Duration::from_millis(200));
 
         // Press 'd' at T=0
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 150ms
         thread::sleep(Duration::from_millis(150));
 
         // Press 'a' at T=150ms - resets timeout
-        handler.process_key_event(char_key('a'), EditorMode::Normal);
+        handler.process_key_event(char_key('a'), &EditorMode::Normal);
 
         // Wait another 150ms (total 300ms from first key, but only 150ms from second)
         thread::sleep(Duration::from_millis(150));
 
         // Press 'w' at T=300ms - should complete because timeout reset at 'a'
-        let result = handler.process_key_event(char_key('w'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('w'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Matched(EditorCommand::Save),
@@ -434,16 +434,16 @@ mod timeout_reset_behavior {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(200));
 
         // Press 'd' - Partial
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 150ms
         thread::sleep(Duration::from_millis(150));
 
         // Press 'x' (invalid) - clears buffer
-        handler.process_key_event(char_key('x'), EditorMode::Normal);
+        handler.process_key_event(char_key('x'), &EditorMode::Normal);
 
         // The timeout was NOT reset, buffer was cleared. Start fresh
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -461,7 +461,7 @@ mod configurable_timeout {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(500));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 400ms (less than timeout)
         thread::sleep(Duration::from_millis(400));
@@ -471,7 +471,7 @@ mod configurable_timeout {
         assert!(!timed_out, "Should not timeout before 500ms");
 
         // Press 'd' - should complete
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
     }
 
@@ -480,7 +480,7 @@ mod configurable_timeout {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(1000));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 900ms (less than timeout)
         thread::sleep(Duration::from_millis(900));
@@ -490,7 +490,7 @@ mod configurable_timeout {
         assert!(!timed_out, "Should not timeout before 1000ms");
 
         // Press 'd' - should complete
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
     }
 
@@ -499,7 +499,7 @@ mod configurable_timeout {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(2000));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 1800ms (less than timeout)
         thread::sleep(Duration::from_millis(1800));
@@ -509,7 +509,7 @@ mod configurable_timeout {
         assert!(!timed_out, "Should not timeout before 2000ms");
 
         // Press 'd' - should complete
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
     }
 
@@ -518,7 +518,7 @@ mod configurable_timeout {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(50));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait 100ms (more than timeout)
         thread::sleep(Duration::from_millis(100));
@@ -528,7 +528,7 @@ mod configurable_timeout {
         assert!(timed_out, "Should timeout after 50ms");
 
         // Press 'd' - should be fresh Partial, not completing previous
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Partial);
     }
 }
@@ -543,8 +543,8 @@ mod stress_tests {
 
         // Type 10 'dd' sequences rapidly
         for _ in 0..10 {
-            let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
-            let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+            let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
+            let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
             assert_eq!(result1, MatchResult::Partial);
             assert_eq!(result2, MatchResult::Matched(EditorCommand::DeleteChar));
@@ -558,12 +558,12 @@ mod stress_tests {
         // Alternate between 'dd' and 'gg' 10 times
         for i in 0..10 {
             if i % 2 == 0 {
-                handler.process_key_event(char_key('d'), EditorMode::Normal);
-                let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+                handler.process_key_event(char_key('d'), &EditorMode::Normal);
+                let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
                 assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
             } else {
-                handler.process_key_event(char_key('g'), EditorMode::Normal);
-                let result = handler.process_key_event(char_key('g'), EditorMode::Normal);
+                handler.process_key_event(char_key('g'), &EditorMode::Normal);
+                let result = handler.process_key_event(char_key('g'), &EditorMode::Normal);
                 assert_eq!(
                     result,
                     MatchResult::Matched(EditorCommand::MoveCursor(
@@ -580,14 +580,14 @@ mod stress_tests {
 
         // Create 20 partial sequences that timeout
         for _ in 0..20 {
-            handler.process_key_event(char_key('d'), EditorMode::Normal);
+            handler.process_key_event(char_key('d'), &EditorMode::Normal);
             thread::sleep(Duration::from_millis(100));
             handler.check_timeout();
         }
 
         // Should still work normally after many timeouts
-        let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         assert_eq!(result1, MatchResult::Partial);
         assert_eq!(result2, MatchResult::Matched(EditorCommand::DeleteChar));
@@ -599,12 +599,12 @@ mod stress_tests {
 
         // Type many invalid sequences
         for c in ['d', 'x', 'g', 'z', 'd', 'q'].iter() {
-            handler.process_key_event(char_key(*c), EditorMode::Normal);
+            handler.process_key_event(char_key(*c), &EditorMode::Normal);
         }
 
         // Should still work normally after invalid keys
-        let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         assert_eq!(result1, MatchResult::Partial);
         assert_eq!(result2, MatchResult::Matched(EditorCommand::DeleteChar));
@@ -620,11 +620,11 @@ mod buffer_state_verification {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Complete 'dd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Verify buffer is clear by starting a new sequence
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -637,11 +637,11 @@ mod buffer_state_verification {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Start partial then invalid
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
-        handler.process_key_event(char_key('x'), EditorMode::Normal); // NoMatch, clears buffer
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        handler.process_key_event(char_key('x'), &EditorMode::Normal); // NoMatch, clears buffer
 
         // Verify buffer is clear
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -654,11 +654,11 @@ mod buffer_state_verification {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Press 'd' - Partial
-        let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result1, MatchResult::Partial);
 
         // Press 'd' again - should complete (buffer was preserved)
-        let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result2,
             MatchResult::Matched(EditorCommand::DeleteChar),
@@ -671,14 +671,14 @@ mod buffer_state_verification {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(100));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait and timeout
         thread::sleep(Duration::from_millis(150));
         handler.check_timeout();
 
         // Verify buffer is clear
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(
             result,
             MatchResult::Partial,
@@ -696,14 +696,14 @@ mod edge_cases {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // 'dd' sequence
-        let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result1, MatchResult::Partial);
         assert_eq!(result2, MatchResult::Matched(EditorCommand::DeleteChar));
 
         // 'gg' sequence
-        let result1 = handler.process_key_event(char_key('g'), EditorMode::Normal);
-        let result2 = handler.process_key_event(char_key('g'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('g'), &EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('g'), &EditorMode::Normal);
         assert_eq!(result1, MatchResult::Partial);
         assert_eq!(
             result2,
@@ -716,7 +716,7 @@ mod edge_cases {
         let mut handler = setup_handler_with_sequences(Duration::from_millis(100));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Wait exactly 100ms
         thread::sleep(Duration::from_millis(100));
@@ -734,14 +734,14 @@ mod edge_cases {
         let mut handler = setup_handler_with_sequences(Duration::from_secs(1));
 
         // Press 'd'
-        handler.process_key_event(char_key('d'), EditorMode::Normal);
+        handler.process_key_event(char_key('d'), &EditorMode::Normal);
 
         // Check timeout immediately (shouldn't timeout)
         let timed_out = handler.check_timeout();
         assert!(!timed_out);
 
         // Complete sequence - should still work
-        let result = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result, MatchResult::Matched(EditorCommand::DeleteChar));
     }
 
@@ -761,13 +761,13 @@ mod edge_cases {
         // Press Ctrl+S (global shortcut)
         let result = handler.process_key_event(
             KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
-            EditorMode::Normal,
+            &EditorMode::Normal,
         );
         assert_eq!(result, MatchResult::Matched(EditorCommand::Save));
 
         // Now start 'dd' sequence - should work normally
-        let result1 = handler.process_key_event(char_key('d'), EditorMode::Normal);
-        let result2 = handler.process_key_event(char_key('d'), EditorMode::Normal);
+        let result1 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
+        let result2 = handler.process_key_event(char_key('d'), &EditorMode::Normal);
         assert_eq!(result1, MatchResult::Partial);
         assert_eq!(result2, MatchResult::Matched(EditorCommand::DeleteChar));
     }
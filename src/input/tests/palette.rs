@@ -0,0 +1,110 @@
+//! Unit tests for command palette entries
+
+use crate::editor::EditorMode;
+use crate::input::bindings::register_default_bindings;
+use crate::input::keybinding::{BindingContext, KeyBinding, KeySequence, Priority};
+use crate::input::palette::{palette_entries, palette_list, PaletteEntry};
+use crate::input::registry::KeyBindingRegistry;
+use crate::input::user_commands::UserCommands;
+use crate::input::{EditorCommand, COMMAND_NAMES};
+use std::str::FromStr;
+
+fn plugin_command(plugin: &str, command: &str) -> EditorCommand {
+    EditorCommand::PluginCommand {
+        plugin_name: plugin.to_string(),
+        command_name: command.to_string(),
+    }
+}
+
+fn entry<'a>(entries: &'a [PaletteEntry], name: &str) -> Option<&'a PaletteEntry> {
+    entries.iter().find(|entry| entry.name == name)
+}
+
+#[test]
+fn test_entries_list_builtin_commands_by_name() {
+    let registry = KeyBindingRegistry::default();
+    let entries = palette_entries(&registry, &UserCommands::new(), &[], &EditorMode::Normal);
+
+    let record = entry(&entries, "macro.record").expect("macro.record is listed");
+    assert_eq!(record.command, EditorCommand::ToggleMacroRecording('q'));
+    assert!(record.keys.is_empty());
+    for name in ["prompt.accept", "mode.prompt", "palette.open", "noop"] {
+        assert!(entry(&entries, name).is_none(), "{}", name);
+    }
+    assert_eq!(entries.len(), COMMAND_NAMES.len() - 9);
+}
+
+#[test]
+fn test_entries_show_keys_bound_in_mode() {
+    let mut registry = KeyBindingRegistry::default();
+    register_default_bindings(&mut registry).unwrap();
+    let entries = palette_entries(&registry, &UserCommands::new(), &[], &EditorMode::Normal);
+
+    assert_eq!(entry(&entries, "keybindings.describe_key").unwrap().keys, vec!["Shift+F1"]);
+    assert_eq!(entry(&entries, "mode.insert").unwrap().keys, vec!["i"]);
+
+    let entries = palette_entries(&registry, &UserCommands::new(), &[], &EditorMode::Insert);
+    assert!(entry(&entries, "mode.insert").unwrap().keys.is_empty());
+}
+
+#[test]
+fn test_entries_leave_out_overridden_keys() {
+    let mut registry = KeyBindingRegistry::default();
+    let f5 = KeySequence::from_str("F5").unwrap();
+    for (command, priority) in [(EditorCommand::Save, Priority::Default), (EditorCommand::Quit, Priority::User)] {
+        registry
+            .register(KeyBinding::new(f5.clone(), command, BindingContext::Global, priority))
+            .unwrap();
+    }
+    let entries = palette_entries(&registry, &UserCommands::new(), &[], &EditorMode::Normal);
+
+    assert!(entry(&entries, "file.save").unwrap().keys.is_empty());
+    assert_eq!(entry(&entries, "quit").unwrap().keys, vec!["F5"]);
+}
+
+#[test]
+fn test_entries_list_user_and_plugin_commands() {
+    let mut registry = KeyBindingRegistry::default();
+    registry
+        .register(KeyBinding::new(
+            KeySequence::from_str("F9").unwrap(),
+            plugin_command("git", "blame"),
+            BindingContext::Global,
+            Priority::Plugin,
+        ))
+        .unwrap();
+    let mut user_commands = UserCommands::new();
+    user_commands.insert("save_and_quit", vec![EditorCommand::Save, EditorCommand::Quit]);
+    let declared = [plugin_command("fmt", "all"), plugin_command("git", "blame")];
+
+    let entries = palette_entries(&registry, &user_commands, &declared, &EditorMode::Normal);
+    let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(&names[names.len() - 3..], ["user.save_and_quit", "fmt.all", "git.blame"]);
+    assert_eq!(entry(&entries, "git.blame").unwrap().keys, vec!["F9"]);
+
+    // Every entry parses back into its command
+    for entry in &entries {
+        assert_eq!(EditorCommand::from_str(&entry.name).as_ref(), Ok(&entry.command), "{}", entry.name);
+    }
+}
+
+#[test]
+fn test_palette_list_shows_keys_as_details() {
+    let entries = vec![
+        PaletteEntry {
+            name: "file.save".to_string(),
+            command: EditorCommand::Save,
+            keys: vec!["Ctrl+s".to_string(), "Ctrl+S".to_string()],
+        },
+        PaletteEntry {
+            name: "quit".to_string(),
+            command: EditorCommand::Quit,
+            keys: Vec::new(),
+        },
+    ];
+    let list = palette_list(&entries);
+
+    assert_eq!(list.matches().collect::<Vec<_>>(), vec![(0, "file.save"), (1, "quit")]);
+    assert_eq!(list.detail(0), Some("Ctrl+s, Ctrl+S"));
+    assert_eq!(list.detail(1), None);
+}
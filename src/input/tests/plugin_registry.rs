@@ -3,12 +3,12 @@
 //! Tests the PluginInputExtension trait implementation for KeyBindingRegistry
 
 use crate::editor::EditorMode;
-use crate::input::keybinding::KeyPattern;
+use crate::input::keybinding::{BindingContext, KeyPattern};
 use crate::input::registry::KeyBindingRegistry;
 use crate::input::EditorCommand;
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::time::Duration;
-use termide_plugin_api::input::{PluginBindingBuilder, PluginInputExtension};
+use termide_plugin_api::input::{BindingError, EditorMode as ApiMode, PluginBindingBuilder, PluginInputExtension, PluginMode};
 
 // ============================================================================
 // Successful Plugin Binding Registration Tests
@@ -34,7 +34,7 @@ fn test_register_plugin_binding_global() {
 
     // Verify it works in Normal mode (global context includes Normal)
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
     assert!(cmd.is_some(), "should match in Normal mode");
 
     match cmd.unwrap() {
@@ -48,12 +48,12 @@ fn test_register_plugin_binding_global() {
     // Clear and test Insert mode
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
-    assert!(registry.find_match(EditorMode::Insert).is_some(), "should match in Insert mode");
+    assert!(registry.find_match(&EditorMode::Insert).is_some(), "should match in Insert mode");
 
     // Clear and test Prompt mode (global excludes Prompt)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
-    assert!(registry.find_match(EditorMode::Prompt).is_none(), "should NOT match in Prompt mode");
+    assert!(registry.find_match(&EditorMode::Prompt).is_none(), "should NOT match in Prompt mode");
 }
 
 #[test]
@@ -76,14 +76,14 @@ fn test_register_plugin_binding_single_mode() {
     // Test in Normal mode - should match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE));
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
     assert!(cmd.is_some(), "should match in Normal mode");
 
     // Clear and test in Insert mode - should NOT match
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE));
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Insert).is_none(), "should NOT match in Insert mode");
+    assert!(registry.find_match(&EditorMode::Insert).is_none(), "should NOT match in Insert mode");
 }
 
 #[test]
@@ -103,17 +103,17 @@ fn test_register_plugin_binding_multiple_modes() {
 
     // Test in Insert mode
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('/'), KeyModifiers::CONTROL));
-    assert!(registry.find_match(EditorMode::Insert).is_some(), "should match in Insert mode");
+    assert!(registry.find_match(&EditorMode::Insert).is_some(), "should match in Insert mode");
 
     // Test in Normal mode
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('/'), KeyModifiers::CONTROL));
-    assert!(registry.find_match(EditorMode::Normal).is_some(), "should match in Normal mode");
+    assert!(registry.find_match(&EditorMode::Normal).is_some(), "should match in Normal mode");
 
     // Test in Prompt mode - should NOT match
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('/'), KeyModifiers::CONTROL));
-    assert!(registry.find_match(EditorMode::Prompt).is_none(), "should NOT match in Prompt mode");
+    assert!(registry.find_match(&EditorMode::Prompt).is_none(), "should NOT match in Prompt mode");
 }
 
 #[test]
@@ -132,14 +132,14 @@ fn test_register_plugin_binding_multi_key_sequence() {
 
     // Add first 'g' - partial match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE));
-    assert!(registry.is_partial_match(EditorMode::Normal), "should be partial match");
-    assert!(registry.find_match(EditorMode::Normal).is_none(), "should not have complete match yet");
+    assert!(registry.is_partial_match(&EditorMode::Normal), "should be partial match");
+    assert!(registry.find_match(&EditorMode::Normal).is_none(), "should not have complete match yet");
 
     // Add second 'g' - complete match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE));
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
     assert!(cmd.is_some(), "should have complete match");
-    assert!(!registry.is_partial_match(EditorMode::Normal), "should not be partial anymore");
+    assert!(!registry.is_partial_match(&EditorMode::Normal), "should not be partial anymore");
 }
 
 #[test]
@@ -174,16 +174,16 @@ fn test_register_multiple_plugin_bindings() {
     // Test each binding works
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE));
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Normal).is_some());
+    assert!(registry.find_match(&EditorMode::Normal).is_some());
 
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE));
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('r'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Normal).is_some());
+    assert!(registry.find_match(&EditorMode::Normal).is_some());
 
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('h'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Normal).is_some());
+    assert!(registry.find_match(&EditorMode::Normal).is_some());
 }
 
 #[test]
@@ -222,7 +222,7 @@ fn test_plugin_binding_auto_namespacing() {
     registry.register_keybinding(binding).unwrap();
 
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
 
     match cmd.unwrap() {
         EditorCommand::PluginCommand { plugin_name, command_name } => {
@@ -461,7 +461,7 @@ fn test_user_binding_overrides_plugin_binding() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
 
     // Should match User priority binding (EditorCommand::Save), not plugin binding
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
     assert!(cmd.is_some());
     assert_eq!(cmd.unwrap(), &EditorCommand::Save, "User binding should win");
 }
@@ -498,7 +498,7 @@ fn test_plugin_binding_overrides_default_binding() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
 
     // Should match Plugin priority binding (PluginCommand), not default binding
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
     assert!(cmd.is_some());
 
     match cmd.unwrap() {
@@ -551,6 +551,134 @@ fn test_priority_ordering_plugin_between_default_and_user() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
 
     // Should match User priority (highest)
-    let cmd = registry.find_match(EditorMode::Normal);
+    let cmd = registry.find_match(&EditorMode::Normal);
     assert_eq!(cmd.unwrap(), &EditorCommand::Save, "User priority should win over Plugin and Default");
 }
+
+// ============================================================================
+// Plugin Mode Tests
+// ============================================================================
+
+#[test]
+fn test_plugin_binding_in_plugin_mode() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry
+        .register_mode(PluginMode::new("reg-blame", "blame"))
+        .expect("mode should register");
+    let binding = PluginBindingBuilder::new("reg-blame")
+        .bind("q", "close")
+        .in_modes(&["blame", "normal"])
+        .build()
+        .unwrap();
+    registry.register_keybinding(binding).unwrap();
+
+    let blame = EditorMode::Plugin(registry.plugin_mode("reg-blame.blame").unwrap());
+    assert_eq!(
+        registry.bindings()[0].context(),
+        &BindingContext::Plugin {
+            name: "reg-blame".to_string(),
+            modes: Some(vec![blame.clone(), EditorMode::Normal]),
+        }
+    );
+    for (mode, active) in [(blame, true), (EditorMode::Normal, true), (EditorMode::Insert, false)] {
+        registry.add_to_sequence(KeyPattern::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert_eq!(registry.find_match(&mode).is_some(), active, "{:?}", mode);
+        registry.clear_sequence();
+    }
+}
+
+#[test]
+fn test_plugin_binding_in_unregistered_mode() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    let binding = PluginBindingBuilder::new("reg-none")
+        .bind("q", "close")
+        .in_mode("blame")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        registry.register_keybinding(binding),
+        Err(BindingError::InvalidMode("reg-none.blame".to_string()))
+    );
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_register_mode_requires_namespace() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    let mode = PluginMode {
+        name: "blame".to_string(),
+        label: "BLAME".to_string(),
+    };
+    assert_eq!(
+        registry.register_mode(mode),
+        Err(BindingError::InvalidMode("blame".to_string()))
+    );
+    assert!(registry.plugin_mode("blame").is_none());
+}
+
+#[test]
+fn test_register_mode_again_updates_label() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry.register_mode(PluginMode::new("repl", "eval").with_label("REPL")).unwrap();
+    let old = registry.plugin_mode("repl.eval").unwrap();
+    registry.register_mode(PluginMode::new("repl", "eval").with_label("EVAL")).unwrap();
+
+    let new = registry.plugin_mode("repl.eval").unwrap();
+    assert_eq!(old, new);
+    assert_eq!(new.label(), "EVAL");
+    assert_eq!(registry.modes().len(), EditorMode::ALL.len() + 1);
+}
+
+#[test]
+fn test_registries_keep_their_own_modes() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry.register_mode(PluginMode::new("git", "blame")).unwrap();
+    let other = KeyBindingRegistry::new(Duration::from_secs(1));
+
+    assert!(registry.plugin_mode("git.blame").is_some());
+    assert!(other.plugin_mode("git.blame").is_none());
+    assert_eq!(other.modes(), EditorMode::ALL.to_vec());
+}
+
+#[test]
+fn test_unregister_plugin_removes_its_modes() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry.register_mode(PluginMode::new("gone", "a")).unwrap();
+    registry.register_mode(PluginMode::new("gone", "b")).unwrap();
+    registry.register_mode(PluginMode::new("gone-not", "c")).unwrap();
+
+    registry.unregister_plugin("gone");
+    assert!(registry.plugin_mode("gone.a").is_none());
+    assert!(registry.plugin_mode("gone.b").is_none());
+    assert!(registry.plugin_mode("gone-not.c").is_some());
+}
+
+#[test]
+fn test_parse_plugin_modes_and_mode_commands() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry.register_mode(PluginMode::new("viewer", "view").with_label("VIEW")).unwrap();
+    let view = EditorMode::Plugin(registry.plugin_mode("viewer.view").unwrap());
+
+    assert_eq!(registry.parse_mode(" viewer.view "), Ok(view.clone()));
+    assert_eq!(registry.parse_mode("insert"), Ok(EditorMode::Insert));
+    let err = registry.parse_mode("viewer.edit").unwrap_err();
+    assert!(err.to_string().contains("registered plugin modes"), "{}", err);
+
+    assert_eq!(
+        registry.parse_command("mode.viewer.view"),
+        Ok(EditorCommand::ChangeMode(view.clone()))
+    );
+    assert_eq!(registry.parse_command("mode.normal"), Ok(EditorCommand::ChangeMode(EditorMode::Normal)));
+    assert!(registry.parse_command("mode.viewer.edit").is_err());
+
+    assert_eq!(registry.resolve_mode(ApiMode::Plugin("viewer.view".to_string())), Ok(view));
+    assert_eq!(registry.resolve_mode(ApiMode::Normal), Ok(EditorMode::Normal));
+    assert_eq!(
+        registry
+            .resolve_mode(ApiMode::Plugin("viewer.none".to_string()))
+            .unwrap_err()
+            .invalid,
+        "viewer.none"
+    );
+}
@@ -1,6 +1,7 @@
 //! Unit tests for KeyBindingRegistry
 
 use crate::editor::EditorMode;
+use crate::input::introspection::keybinding_report;
 use crate::input::keybinding::{
     BindingContext, KeyBinding, KeyPattern, KeySequence, Priority,
 };
@@ -8,6 +9,7 @@ use crate::input::registry::{BindingError, KeyBindingRegistry};
 use crate::input::EditorCommand;
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::time::Duration;
+use termide_plugin_api::input::{PluginInputExtension, PluginMode};
 
 /// Helper function to create a simple binding for testing
 fn create_binding(
@@ -462,18 +464,18 @@ fn test_find_match_single_key() {
     registry.register(binding).expect("registration");
 
     // No match before adding to sequence
-    assert_eq!(registry.find_match(EditorMode::Normal), None);
+    assert_eq!(registry.find_match(&EditorMode::Normal), None);
 
     // Add 'i' to sequence
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
 
     // Should match in Normal mode
-    let result = registry.find_match(EditorMode::Normal);
+    let result = registry.find_match(&EditorMode::Normal);
     assert!(result.is_some());
     assert_eq!(result.unwrap(), &EditorCommand::ChangeMode(EditorMode::Insert));
 
     // Should not match in Insert mode (wrong context)
-    assert_eq!(registry.find_match(EditorMode::Insert), None);
+    assert_eq!(registry.find_match(&EditorMode::Insert), None);
 }
 
 #[test]
@@ -492,11 +494,11 @@ fn test_find_match_multi_key_sequence() {
 
     // Add first 'd' - no match yet
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert_eq!(registry.find_match(EditorMode::Normal), None);
+    assert_eq!(registry.find_match(&EditorMode::Normal), None);
 
     // Add second 'd' - now we have a match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    let result = registry.find_match(EditorMode::Normal);
+    let result = registry.find_match(&EditorMode::Normal);
     assert!(result.is_some());
     assert_eq!(result.unwrap(), &EditorCommand::DeleteChar);
 }
@@ -521,15 +523,15 @@ fn test_find_match_three_key_sequence() {
 
     // Add first key - no match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('a'), KeyModifiers::NONE));
-    assert_eq!(registry.find_match(EditorMode::Normal), None);
+    assert_eq!(registry.find_match(&EditorMode::Normal), None);
 
     // Add second key - no match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('b'), KeyModifiers::NONE));
-    assert_eq!(registry.find_match(EditorMode::Normal), None);
+    assert_eq!(registry.find_match(&EditorMode::Normal), None);
 
     // Add third key - match!
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('c'), KeyModifiers::NONE));
-    let result = registry.find_match(EditorMode::Normal);
+    let result = registry.find_match(&EditorMode::Normal);
     assert!(result.is_some());
     assert_eq!(result.unwrap(), &EditorCommand::Save);
 }
@@ -551,13 +553,13 @@ fn test_find_match_context_filtering() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
 
     // Should match in Normal mode
-    assert!(registry.find_match(EditorMode::Normal).is_some());
+    assert!(registry.find_match(&EditorMode::Normal).is_some());
 
     // Should not match in Insert mode (context not active)
-    assert_eq!(registry.find_match(EditorMode::Insert), None);
+    assert_eq!(registry.find_match(&EditorMode::Insert), None);
 
     // Should not match in Prompt mode (context not active)
-    assert_eq!(registry.find_match(EditorMode::Prompt), None);
+    assert_eq!(registry.find_match(&EditorMode::Prompt), None);
 }
 
 #[test]
@@ -580,13 +582,13 @@ fn test_find_match_global_context() {
     ));
 
     // Should match in Normal mode
-    assert!(registry.find_match(EditorMode::Normal).is_some());
+    assert!(registry.find_match(&EditorMode::Normal).is_some());
 
     // Should match in Insert mode
-    assert!(registry.find_match(EditorMode::Insert).is_some());
+    assert!(registry.find_match(&EditorMode::Insert).is_some());
 
     // Should NOT match in Prompt mode (Global excludes Prompt)
-    assert_eq!(registry.find_match(EditorMode::Prompt), None);
+    assert_eq!(registry.find_match(&EditorMode::Prompt), None);
 }
 
 #[test]
@@ -616,7 +618,7 @@ fn test_find_match_priority_ordering() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
 
     // Should return User priority binding (higher priority)
-    let result = registry.find_match(EditorMode::Normal);
+    let result = registry.find_match(&EditorMode::Normal);
     assert!(result.is_some());
     assert_eq!(result.unwrap(), &EditorCommand::Save);
 }
@@ -635,7 +637,7 @@ fn test_find_match_empty_buffer() {
     registry.register(binding).expect("registration");
 
     // Empty buffer should not match
-    assert_eq!(registry.find_match(EditorMode::Normal), None);
+    assert_eq!(registry.find_match(&EditorMode::Normal), None);
 }
 
 #[test]
@@ -653,15 +655,15 @@ fn test_is_partial_match_two_key_sequence() {
     registry.register(binding).expect("registration");
 
     // Empty buffer - no partial match
-    assert!(!registry.is_partial_match(EditorMode::Normal));
+    assert!(!registry.is_partial_match(&EditorMode::Normal));
 
     // Add first 'd' - this is a partial match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert!(registry.is_partial_match(EditorMode::Normal));
+    assert!(registry.is_partial_match(&EditorMode::Normal));
 
     // Add second 'd' - no longer partial (it's complete)
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert!(!registry.is_partial_match(EditorMode::Normal));
+    assert!(!registry.is_partial_match(&EditorMode::Normal));
 }
 
 #[test]
@@ -684,15 +686,15 @@ fn test_is_partial_match_three_key_sequence() {
 
     // First key - partial match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('a'), KeyModifiers::NONE));
-    assert!(registry.is_partial_match(EditorMode::Normal));
+    assert!(registry.is_partial_match(&EditorMode::Normal));
 
     // Second key - still partial
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('b'), KeyModifiers::NONE));
-    assert!(registry.is_partial_match(EditorMode::Normal));
+    assert!(registry.is_partial_match(&EditorMode::Normal));
 
     // Third key - complete (not partial)
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('c'), KeyModifiers::NONE));
-    assert!(!registry.is_partial_match(EditorMode::Normal));
+    assert!(!registry.is_partial_match(&EditorMode::Normal));
 }
 
 #[test]
@@ -711,7 +713,7 @@ fn test_is_partial_match_wrong_sequence() {
 
     // Add wrong first key - no partial match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('x'), KeyModifiers::NONE));
-    assert!(!registry.is_partial_match(EditorMode::Normal));
+    assert!(!registry.is_partial_match(&EditorMode::Normal));
 }
 
 #[test]
@@ -731,10 +733,10 @@ fn test_is_partial_match_context_filtering() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
 
     // Should be partial match in Normal mode
-    assert!(registry.is_partial_match(EditorMode::Normal));
+    assert!(registry.is_partial_match(&EditorMode::Normal));
 
     // Should NOT be partial match in Insert mode (context not active)
-    assert!(!registry.is_partial_match(EditorMode::Insert));
+    assert!(!registry.is_partial_match(&EditorMode::Insert));
 }
 
 #[test]
@@ -814,13 +816,13 @@ fn test_sequence_cleared_after_match() {
 
     // Add first 'd' - partial match
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert!(registry.is_partial_match(EditorMode::Normal));
+    assert!(registry.is_partial_match(&EditorMode::Normal));
 
     // Clear buffer
     registry.clear_sequence();
 
     // No longer a partial match (buffer is empty)
-    assert!(!registry.is_partial_match(EditorMode::Normal));
+    assert!(!registry.is_partial_match(&EditorMode::Normal));
 }
 
 #[test]
@@ -853,23 +855,23 @@ fn test_integration_complete_sequence_workflow() {
 
     // Step 1: Add 'd'
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert_eq!(registry.find_match(EditorMode::Normal), None); // No complete match
-    assert!(registry.is_partial_match(EditorMode::Normal)); // Partial match exists
+    assert_eq!(registry.find_match(&EditorMode::Normal), None); // No complete match
+    assert!(registry.is_partial_match(&EditorMode::Normal)); // Partial match exists
 
     // Step 2: Add 'd' (completing "dd")
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    let result = registry.find_match(EditorMode::Normal);
+    let result = registry.find_match(&EditorMode::Normal);
     assert!(result.is_some());
     assert_eq!(result.unwrap(), &EditorCommand::DeleteChar);
-    assert!(!registry.is_partial_match(EditorMode::Normal)); // Complete, not partial
+    assert!(!registry.is_partial_match(&EditorMode::Normal)); // Complete, not partial
 
     // Step 3: Clear and try "dw"
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('d'), KeyModifiers::NONE));
-    assert!(registry.is_partial_match(EditorMode::Normal));
+    assert!(registry.is_partial_match(&EditorMode::Normal));
 
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('w'), KeyModifiers::NONE));
-    let result = registry.find_match(EditorMode::Normal);
+    let result = registry.find_match(&EditorMode::Normal);
     assert!(result.is_some());
     assert_eq!(result.unwrap(), &EditorCommand::Save);
 }
@@ -904,7 +906,7 @@ fn test_user_priority_overrides_default_priority() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
 
     // Find match - should return User priority binding (Quit)
-    let cmd = registry.find_match(EditorMode::Normal).expect("Should find match");
+    let cmd = registry.find_match(&EditorMode::Normal).expect("Should find match");
     assert_eq!(cmd, &EditorCommand::Quit, "User priority should override Default");
 }
 
@@ -952,15 +954,15 @@ fn test_unregister_by_priority_removes_only_target_priority() {
 
     // Verify User binding is gone but others remain
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('k'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Normal).is_none(), "User binding should be removed");
+    assert!(registry.find_match(&EditorMode::Normal).is_none(), "User binding should be removed");
 
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('j'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Normal).is_some(), "Plugin binding should remain");
+    assert!(registry.find_match(&EditorMode::Normal).is_some(), "Plugin binding should remain");
 
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
-    assert!(registry.find_match(EditorMode::Normal).is_some(), "Default binding should remain");
+    assert!(registry.find_match(&EditorMode::Normal).is_some(), "Default binding should remain");
 }
 
 #[test]
@@ -1181,3 +1183,153 @@ fn test_unregister_by_priority_all_priorities() {
     assert_eq!(removed_user, 1);
     assert_eq!(registry.len(), 0);
 }
+
+#[test]
+fn test_bindings_are_in_lookup_order() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry
+        .register(create_binding('a', KeyModifiers::NONE, EditorCommand::Save, BindingContext::Global, Priority::Default))
+        .unwrap();
+    registry
+        .register(create_binding('b', KeyModifiers::NONE, EditorCommand::Quit, BindingContext::Global, Priority::User))
+        .unwrap();
+
+    let priorities: Vec<_> = registry.bindings().iter().map(|b| b.priority()).collect();
+    assert_eq!(priorities, vec![Priority::User, Priority::Default]);
+}
+
+#[test]
+fn test_describe_sequence_reports_winner_shadowed_and_continuations() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::PageUp, BindingContext::Global, Priority::Plugin))
+        .unwrap();
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::PageDown, BindingContext::Global, Priority::User))
+        .unwrap();
+    registry
+        .register(create_sequence_binding('g', 'd', EditorCommand::Save, BindingContext::Mode(EditorMode::Normal), Priority::Default))
+        .unwrap();
+    let g = [KeyPattern::new(KeyCode::Char('g'), KeyModifiers::NONE)];
+
+    let description = registry.describe_sequence(&g, &EditorMode::Normal);
+    assert_eq!(description.binding.unwrap().command(), &EditorCommand::PageDown);
+    assert_eq!(description.shadowed.len(), 1);
+    assert_eq!(description.shadowed[0].command(), &EditorCommand::PageUp);
+    assert_eq!(description.continuations.len(), 1);
+    assert_eq!(description.continuations[0].command(), &EditorCommand::Save);
+
+    // Only bindings active in the mode are considered
+    let description = registry.describe_sequence(&g, &EditorMode::Prompt);
+    assert_eq!(description.binding, None);
+    assert!(description.continuations.is_empty());
+}
+
+#[test]
+fn test_sequences_for_skips_overridden_bindings() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::PageUp, BindingContext::Global, Priority::Plugin))
+        .unwrap();
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::PageDown, BindingContext::Mode(EditorMode::Normal), Priority::User))
+        .unwrap();
+    registry
+        .register(create_binding('u', KeyModifiers::CONTROL, EditorCommand::PageUp, BindingContext::Global, Priority::Default))
+        .unwrap();
+
+    let names = |command: &EditorCommand, mode: EditorMode| -> Vec<String> {
+        registry.sequences_for(command, &mode).iter().map(ToString::to_string).collect()
+    };
+    assert_eq!(names(&EditorCommand::PageUp, EditorMode::Normal), vec!["Ctrl+u"]);
+    assert_eq!(names(&EditorCommand::PageUp, EditorMode::Insert), vec!["g", "Ctrl+u"]);
+    assert_eq!(names(&EditorCommand::PageDown, EditorMode::Normal), vec!["g"]);
+    assert!(names(&EditorCommand::PageDown, EditorMode::Prompt).is_empty());
+}
+
+#[test]
+fn test_shadowed_bindings_only_in_overlapping_modes() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry
+        .register(create_binding('s', KeyModifiers::CONTROL, EditorCommand::Save, BindingContext::Global, Priority::Default))
+        .unwrap();
+    registry
+        .register(create_binding(
+            's',
+            KeyModifiers::CONTROL,
+            EditorCommand::PluginCommand {
+                plugin_name: "fmt".to_string(),
+                command_name: "run".to_string(),
+            },
+            BindingContext::Plugin {
+                name: "fmt".to_string(),
+                modes: Some(vec![EditorMode::Normal]),
+            },
+            Priority::Plugin,
+        ))
+        .unwrap();
+
+    let shadowed = registry.shadowed_bindings();
+
+    assert_eq!(shadowed.len(), 1);
+    assert_eq!(shadowed[0].binding.command(), &EditorCommand::Save);
+    assert_eq!(shadowed[0].by.priority(), Priority::Plugin);
+    assert_eq!(shadowed[0].modes, vec![EditorMode::Normal]);
+}
+
+#[test]
+fn test_shadowed_bindings_empty_for_defaults() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    crate::input::bindings::register_default_bindings(&mut registry).unwrap();
+
+    assert!(registry.shadowed_bindings().is_empty());
+    assert!(registry.prefix_conflicts().is_empty());
+}
+
+#[test]
+fn test_prefix_conflicts_report_shortest_prefix_per_mode() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry
+        .register(create_sequence_binding('g', 'd', EditorCommand::Save, BindingContext::Global, Priority::Default))
+        .unwrap();
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::Quit, BindingContext::Mode(EditorMode::Normal), Priority::User))
+        .unwrap();
+
+    let conflicts = registry.prefix_conflicts();
+
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].prefix.command(), &EditorCommand::Quit);
+    assert_eq!(conflicts[0].unreachable.command(), &EditorCommand::Save);
+    // "g d" can still be typed in Insert mode
+    assert_eq!(conflicts[0].modes, vec![EditorMode::Normal]);
+}
+
+#[test]
+fn test_prefix_conflicts_in_plugin_modes() {
+    let mut registry = KeyBindingRegistry::new(Duration::from_secs(1));
+    registry.register_mode(PluginMode::new("conflicts", "blame")).unwrap();
+    let blame = EditorMode::Plugin(registry.plugin_mode("conflicts.blame").unwrap());
+    registry
+        .register(create_sequence_binding('g', 'd', EditorCommand::Save, BindingContext::Mode(blame.clone()), Priority::Default))
+        .unwrap();
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::Quit, BindingContext::Mode(blame.clone()), Priority::User))
+        .unwrap();
+    registry
+        .register(create_binding('g', KeyModifiers::NONE, EditorCommand::Undo, BindingContext::Mode(blame.clone()), Priority::Default))
+        .unwrap();
+
+    let conflicts = registry.prefix_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].prefix.command(), &EditorCommand::Quit);
+    assert_eq!(conflicts[0].modes, vec![blame.clone()]);
+
+    let shadowed = registry.shadowed_bindings();
+    assert_eq!(shadowed.len(), 1);
+    assert_eq!(shadowed[0].binding.command(), &EditorCommand::Undo);
+    assert_eq!(shadowed[0].modes, vec![blame.clone()]);
+
+    let report = keybinding_report(&registry);
+    assert!(report.contains("fires first in conflicts.blame"), "{}", report);
+}
@@ -0,0 +1,102 @@
+//! Unit tests for named user commands
+
+use crate::input::keybinding::{BindingContext, KeyBinding, KeySequence, Priority};
+use crate::input::user_commands::{is_valid_user_command_name, UserCommands};
+use crate::input::EditorCommand;
+use std::str::FromStr;
+
+fn user(name: &str) -> EditorCommand {
+    EditorCommand::UserCommand(name.to_string())
+}
+
+#[test]
+fn test_valid_user_command_names() {
+    assert!(is_valid_user_command_name("save-all_2"));
+    assert!(!is_valid_user_command_name("save.all"));
+    assert!(!is_valid_user_command_name("sävé"));
+}
+
+#[test]
+fn test_insert_replaces_and_ignores_case() {
+    let mut commands = UserCommands::new();
+    commands.insert("Save_Quit", vec![EditorCommand::Save]);
+    commands.insert("save_quit", vec![EditorCommand::Save, EditorCommand::Quit]);
+
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands.get("SAVE_QUIT"), Some(&[EditorCommand::Save, EditorCommand::Quit][..]));
+    assert_eq!(commands.names().collect::<Vec<_>>(), vec!["save_quit"]);
+    assert_eq!(commands.get("missing"), None);
+}
+
+#[test]
+fn test_remove_invalid_keeps_valid_commands() {
+    let mut commands = UserCommands::new();
+    commands.insert("save", vec![EditorCommand::Save]);
+    commands.insert("save_quit", vec![user("save"), EditorCommand::Quit]);
+
+    assert!(commands.remove_invalid().is_empty());
+    assert_eq!(commands.len(), 2);
+}
+
+#[test]
+fn test_remove_invalid_drops_recursive_commands() {
+    let mut commands = UserCommands::new();
+    commands.insert("self", vec![EditorCommand::Save, user("self")]);
+    commands.insert("a", vec![user("b")]);
+    commands.insert("b", vec![user("a")]);
+
+    let warnings = commands.remove_invalid();
+
+    assert_eq!(
+        warnings,
+        vec![
+            "user command 'a' calls itself through 'user.b'",
+            "user command 'b' calls undefined command 'user.a'",
+            "user command 'self' calls itself",
+        ]
+    );
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn test_remove_invalid_drops_callers_of_undefined_commands() {
+    let mut commands = UserCommands::new();
+    commands.insert("outer", vec![user("inner")]);
+    commands.insert("inner", vec![user("missing")]);
+    commands.insert("other", vec![EditorCommand::Quit]);
+
+    let warnings = commands.remove_invalid();
+
+    assert_eq!(
+        warnings,
+        vec![
+            "user command 'inner' calls undefined command 'user.missing'",
+            "user command 'outer' calls undefined command 'user.inner'",
+        ]
+    );
+    assert_eq!(commands.names().collect::<Vec<_>>(), vec!["other"]);
+}
+
+#[test]
+fn test_check_bindings_reports_undefined_commands() {
+    let mut commands = UserCommands::new();
+    commands.insert("defined", vec![EditorCommand::Save]);
+    let binding = |sequence: &str, command| {
+        KeyBinding::new(
+            KeySequence::from_str(sequence).unwrap(),
+            command,
+            BindingContext::Global,
+            Priority::User,
+        )
+    };
+    let bindings = vec![
+        binding("Ctrl+a", user("defined")),
+        binding("Ctrl+b", EditorCommand::Chain(vec![EditorCommand::Save, user("missing")])),
+        binding("Ctrl+c", EditorCommand::Quit),
+    ];
+
+    assert_eq!(
+        commands.check_bindings(&bindings),
+        vec!["keybinding 'Ctrl+b' calls undefined command 'user.missing'"]
+    );
+}
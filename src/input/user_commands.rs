@@ -0,0 +1,179 @@
+//! Named user-defined commands
+//!
+//! The `[commands]` section of a config file defines commands that run a chain
+//! of other commands. Each one is bound and called as `user.<name>`:
+//!
+//! ```toml
+//! [commands]
+//! save_and_quit = ["file.save", "quit"]
+//! scroll_down = [{ command = "move.down", args = { count = 10 } }]
+//!
+//! [[keybindings]]
+//! sequence = "Ctrl+Alt+Q"
+//! command = "user.save_and_quit"
+//! ```
+//!
+//! Names are resolved when the command runs, so a binding keeps working when
+//! the definition is reloaded. [`UserCommands::remove_invalid`] drops
+//! definitions that could never finish: ones calling an undefined command or,
+//! directly or indirectly, themselves.
+
+use crate::input::keybinding::KeyBinding;
+use crate::input::EditorCommand;
+use std::collections::BTreeMap;
+
+/// Returns `true` if `name` can name a user command
+///
+/// Names are non-empty and use ASCII letters, digits, `_` and `-`.
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::user_commands::is_valid_user_command_name;
+///
+/// assert!(is_valid_user_command_name("save_and_quit"));
+/// assert!(!is_valid_user_command_name("save and quit"));
+/// assert!(!is_valid_user_command_name(""));
+/// ```
+pub fn is_valid_user_command_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// The named commands from the `[commands]` config sections
+///
+/// # Examples
+///
+/// ```
+/// use termide::input::user_commands::UserCommands;
+/// use termide::input::EditorCommand;
+///
+/// let mut commands = UserCommands::new();
+/// commands.insert("save_and_quit", vec![EditorCommand::Save, EditorCommand::Quit]);
+///
+/// assert_eq!(
+///     commands.get("save_and_quit"),
+///     Some(&[EditorCommand::Save, EditorCommand::Quit][..])
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserCommands {
+    commands: BTreeMap<String, Vec<EditorCommand>>,
+}
+
+impl UserCommands {
+    /// Creates an empty set of user commands
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines `name` to run `commands`, replacing an earlier definition
+    ///
+    /// Names are case-insensitive like every other command name.
+    pub fn insert(&mut self, name: &str, commands: Vec<EditorCommand>) {
+        self.commands.insert(name.to_lowercase(), commands);
+    }
+
+    /// Returns the commands run by `name`
+    pub fn get(&self, name: &str) -> Option<&[EditorCommand]> {
+        self.commands.get(&name.to_lowercase()).map(Vec::as_slice)
+    }
+
+    /// Returns the defined names in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.commands.keys().map(String::as_str)
+    }
+
+    /// Returns the number of defined commands
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no commands are defined
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes commands that call an undefined or recursive user command
+    ///
+    /// Returns a warning for each removed command.
+    pub fn remove_invalid(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        // Removing a command can break the commands calling it, so repeat until stable
+        loop {
+            let invalid = self.commands.keys().find_map(|name| {
+                self.find_problem(name, &mut vec![name.as_str()])
+                    .map(|problem| (name.clone(), problem))
+            });
+            match invalid {
+                Some((name, problem)) => {
+                    self.commands.remove(&name);
+                    warnings.push(format!("user command '{}' {}", name, problem));
+                }
+                None => break,
+            }
+        }
+
+        warnings
+    }
+
+    /// Describes why the command at the end of `path` cannot run, if it cannot
+    fn find_problem<'a>(&'a self, name: &str, path: &mut Vec<&'a str>) -> Option<String> {
+        for called in self.commands.get(name).into_iter().flatten().flat_map(user_commands_in) {
+            if called == path[0] {
+                return Some(match path.get(1) {
+                    Some(through) => format!("calls itself through 'user.{}'", through),
+                    None => "calls itself".to_string(),
+                });
+            }
+            // A loop that does not lead back here is reported for its own members
+            if path.contains(&called) {
+                continue;
+            }
+            if !self.commands.contains_key(called) {
+                // Commands further down report their own undefined calls
+                if path.len() == 1 {
+                    return Some(format!("calls undefined command 'user.{}'", called));
+                }
+                continue;
+            }
+            path.push(called);
+            let problem = self.find_problem(called, path);
+            path.pop();
+            if problem.is_some() {
+                return problem;
+            }
+        }
+        None
+    }
+
+    /// Returns a warning for each binding that calls an undefined user command
+    pub fn check_bindings(&self, bindings: &[KeyBinding]) -> Vec<String> {
+        bindings
+            .iter()
+            .flat_map(|binding| {
+                user_commands_in(binding.command())
+                    .filter(|name| self.get(name).is_none())
+                    .map(move |name| {
+                        format!(
+                            "keybinding '{}' calls undefined command 'user.{}'",
+                            binding.sequence(),
+                            name
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Returns the names of the user commands `command` calls directly
+fn user_commands_in(command: &EditorCommand) -> Box<dyn Iterator<Item = &str> + '_> {
+    match command {
+        EditorCommand::UserCommand(name) => Box::new(std::iter::once(name.as_str())),
+        EditorCommand::Chain(commands) => Box::new(commands.iter().flat_map(user_commands_in)),
+        _ => Box::new(std::iter::empty()),
+    }
+}
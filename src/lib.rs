@@ -7,4 +7,5 @@ pub mod clipboard;
 pub mod editor;
 pub mod file_io;
 pub mod input;
+pub mod plugin;
 pub mod ui;
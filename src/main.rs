@@ -3,9 +3,9 @@
 //! A fast, efficient text editor built with Rust, designed for terminal environments.
 
 use std::env;
-use std::io;
+use std::io::{self, IsTerminal};
 use std::panic;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
@@ -14,16 +14,37 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode},
 };
 
-use termide::buffer::Position;
-use termide::editor::{EditorMode, EditorState};
+use termide::buffer::{Buffer, Position};
+use termide::editor::{EditorConfig, EditorMode, EditorSettings, EditorState, PromptKind};
 use termide::input::{Direction, EditorCommand};
-use termide::input::bindings::register_default_bindings;
-use termide::input::config::{get_config_path, load_user_keybindings, reload_user_keybindings};
+use termide::input::bindings::{register_default_bindings, register_keymap};
+use termide::input::command_line::{self, ExCommand};
+use termide::input::config::{
+    apply_editor_settings, get_config_path, load_keymap_options, load_save_options, load_user_commands,
+    reload_user_keybindings, ConfigError, LoadResult,
+};
+use termide::input::config_layers::{
+    language_for_path, reload_project_keybindings, resolve_settings, ConfigPaths,
+};
+use termide::input::keybinding::Priority;
+use termide::input::registry::KeyBindingRegistry;
 use termide::input::input_handler::{InputHandler, MatchResult};
+use termide::input::introspection::{is_keybinding_report, keybinding_report};
+use termide::input::macros::{get_macros_path, repeat_commands, Macros};
+use termide::input::palette::{palette_entries, palette_list};
+use termide::input::user_commands::UserCommands;
 use termide::input::watcher::ConfigWatcher;
-use termide::ui::Renderer;
+use termide::file_io::{read_pipe, write_pipe};
+use termide::plugin::{discover_plugins, get_plugins_dir, is_plugin_running, PluginHost};
+use termide::ui::{Renderer, TerminalOutput, Theme};
+use termide_plugin_api::event::EditorEvent;
+use termide_plugin_api::manifest::API_VERSION;
+use termide_plugin_api::ui::{PickerSelection, Severity};
+
+use cli::{parse_args, usage, version, CliAction, CliArgs};
 
 mod buffer;
+mod cli;
 mod editor;
 mod file_io;
 mod input;
@@ -38,84 +59,404 @@ fn main() -> Result<()> {
 
     // Parse CLI arguments
     let args: Vec<String> = env::args().collect();
-    let file_path = parse_args(&args)?;
+    let cli_args = match parse_args(&args)? {
+        CliAction::Run(cli_args) => cli_args,
+        CliAction::Help => {
+            println!("{}", usage(args.first().map(String::as_str).unwrap_or("termide")));
+            return Ok(());
+        }
+        CliAction::Version => {
+            println!("{}", version());
+            return Ok(());
+        }
+    };
+
+    if let Some(config_path) = &cli_args.config {
+        if !config_path.is_file() {
+            anyhow::bail!("Config file '{}' does not exist", config_path.display());
+        }
+    }
+    let user_config = cli_args.config.clone().or_else(get_config_path);
+
+    if cli_args.show_config {
+        let file = cli_args.files.first().map(|file| file.path.as_path());
+        for line in describe_config(user_config, file)? {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if cli_args.list_plugins {
+        for line in describe_plugins(user_config.as_deref(), get_plugins_dir().as_deref()) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
 
     // Initialize editor state
-    let mut state = if let Some(path) = file_path {
-        EditorState::from_file(Path::new(&path))
-            .with_context(|| format!("Failed to initialize editor with file: {}", path))?
-    } else {
-        EditorState::new()
-    };
+    let (mut state, mut cursor) = open_buffers(&cli_args)?;
 
-    // Initialize terminal and renderer
-    enable_raw_mode().context("Failed to enable raw terminal mode")?;
-    let mut renderer = Renderer::new().context("Failed to initialize renderer")?;
+    // Pipe mode: `cmd | termide - | other` hands the saved text to the next command
+    state.set_pipe_output(cli_args.stdout || (cli_args.stdin && !io::stdout().is_terminal()));
 
     // Initialize input handler with default bindings
-    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    let mut input_handler = InputHandler::with_timeout(EditorSettings::default().sequence_timeout);
     register_default_bindings(&mut input_handler.registry_mut())
         .context("Failed to register default keybindings")?;
 
-    // Load user config if available (after defaults so User priority takes effect)
-    let config_watcher = if let Some(config_path) = get_config_path() {
-        // Load initial config - errors during startup are silent
-        // Users can check status bar during hot reload for warnings
-        let _ = load_user_keybindings(&mut input_handler.registry_mut(), &config_path);
+    // Load plugins before the config, so its report covers their bindings
+    let mut plugins = PluginHost::new();
+    let plugins_dir = get_plugins_dir();
+    let (plugin_list, mut plugin_warnings) =
+        discover_plugins(user_config.as_deref(), plugins_dir.as_deref());
+    plugin_warnings.extend(plugins.load_all(plugin_list, input_handler.registry_mut()));
+
+    // Load user and project config (after defaults so their priorities take effect)
+    let mut config = ConfigSession::new(user_config, cli_args.theme.clone());
+    let (theme, result) = config.load(&mut state, &mut input_handler);
+    if let Some((severity, message)) = config_warning_message(&result) {
+        state.set_status(severity, message);
+    }
 
-        // Try to create config watcher for hot reload
-        // Only attempt if config file exists
-        if config_path.exists() {
-            match ConfigWatcher::new(&config_path) {
-                Ok(watcher) => Some((watcher, config_path)),
-                Err(_) => None, // Silent failure - hot reload just won't work
+    // Macros recorded in earlier sessions; config warnings take precedence
+    if let Some(path) = get_macros_path() {
+        let warning = match Macros::load(path) {
+            Ok((macros, warnings)) => {
+                input_handler.set_macros(macros);
+                warnings.into_iter().next()
             }
-        } else {
-            // Config file doesn't exist yet - no watcher needed
-            None
+            Err(e) => Some(format!("Macros not loaded: {}", e)),
+        };
+        if let (Some(warning), None) = (warning, state.status_message()) {
+            state.set_status(Severity::Warning, format!("⚠ {}", warning));
         }
-    } else {
-        None
-    };
+    }
+
+    if let (Some(warning), None) = (plugin_warnings.first(), state.status_message()) {
+        state.set_status(Severity::Warning, format!("⚠ {}", warning));
+    }
 
-    // Initialize cursor position
-    let mut cursor = Position::origin();
+    // The configured default mode only applies at startup
+    state.set_mode(state.settings().default_mode.clone());
+
+    // Initialize terminal and renderer
+    enable_raw_mode().context("Failed to enable raw terminal mode")?;
+    let mut renderer = Renderer::with_theme(theme).context("Failed to initialize renderer")?;
 
     // Main event loop
-    let result = run_event_loop(&mut state, &mut renderer, &mut cursor, &mut input_handler, config_watcher);
+    let result = run_event_loop(
+        &mut state,
+        &mut renderer,
+        &mut cursor,
+        &mut input_handler,
+        &mut plugins,
+        &mut config,
+    );
 
     // Clean up terminal
     disable_raw_mode().context("Failed to disable raw terminal mode")?;
     renderer.restore_terminal().context("Failed to restore terminal")?;
+    result?;
 
-    result
+    if let Some(content) = state.take_piped_content() {
+        write_pipe(io::stdout().lock(), &content)?;
+    }
+
+    Ok(())
 }
 
-/// Parse command-line arguments
+/// Creates the editor state with one buffer per input named on the command line
 ///
-/// Returns the file path if provided, or None for a new empty buffer.
+/// Standard input (`-`) becomes the first buffer, followed by the files in
+/// order. Returns the state together with the cursor position for the first
+/// buffer.
+fn open_buffers(cli_args: &CliArgs) -> Result<(EditorState, Position)> {
+    let mut files = cli_args.files.iter();
+
+    let (mut state, cursor) = if cli_args.stdin {
+        let content = read_pipe(io::stdin().lock())?;
+        let mut state = EditorState::new();
+        *state.buffer_mut() = Buffer::from_str(&content);
+        (state, Position::origin())
+    } else if let Some(file) = files.next() {
+        let state = EditorState::from_file(&file.path).with_context(|| {
+            format!("Failed to initialize editor with file: {}", file.path.display())
+        })?;
+        let cursor = state.buffer().clamp_position(file.cursor());
+        (state, cursor)
+    } else {
+        (EditorState::new(), Position::origin())
+    };
+
+    for file in files {
+        state
+            .open_file(&file.path, file.cursor())
+            .with_context(|| format!("Failed to open file: {}", file.path.display()))?;
+    }
+
+    if cli_args.readonly {
+        for buffer in state.buffers_mut() {
+            buffer.set_read_only(true);
+        }
+    }
+
+    Ok((state, cursor))
+}
+
+/// Applies general editor settings to the editor state and input handler
 ///
-/// # Errors
+/// Returns the theme to render with: the `--theme` command-line option wins
+/// over the configured theme.
+fn apply_settings(
+    state: &mut EditorState,
+    input_handler: &mut InputHandler,
+    settings: EditorSettings,
+    theme_override: Option<&str>,
+) -> Theme {
+    input_handler.set_timeout(settings.sequence_timeout);
+    let theme = Theme::by_name(theme_override.unwrap_or(&settings.theme)).unwrap_or_default();
+    state.set_settings(settings);
+    theme
+}
+
+/// Configuration files in effect for the active buffer, watched for changes
 ///
-/// Returns an error with usage information if invalid arguments are provided.
-fn parse_args(args: &[String]) -> Result<Option<String>> {
-    match args.len() {
-        1 => {
-            // No arguments - new empty buffer
-            Ok(None)
+/// Settings are resolved for the active buffer: its project's `.termide.toml`,
+/// the `[language.*]` sections for its file type and its `.editorconfig`
+/// properties apply on top of the user config. A project `[keymap]` or `[save]`
+/// section replaces the user's. Switching buffers, or saving the active buffer
+/// under another path, reloads them.
+struct ConfigSession {
+    /// User config file location, which may not exist yet
+    user_path: Option<PathBuf>,
+    /// `--theme` command-line option, overriding the configured theme
+    theme_override: Option<String>,
+    /// Config files found for the active buffer
+    paths: ConfigPaths,
+    /// File of the active buffer
+    file: Option<PathBuf>,
+    /// Watchers for the files in `paths`
+    watchers: Vec<ConfigWatcher>,
+}
+
+impl ConfigSession {
+    fn new(user_path: Option<PathBuf>, theme_override: Option<String>) -> Self {
+        Self {
+            user_path,
+            theme_override,
+            paths: ConfigPaths::default(),
+            file: None,
+            watchers: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the active buffer is a different file than the one the
+    /// config was loaded for, after switching buffers or saving under a new path
+    fn is_stale(&self, state: &EditorState) -> bool {
+        state.buffer().file_path() != self.file.as_ref()
+    }
+
+    /// Returns `true` if one of the config files changed since the last check
+    fn has_changes(&mut self) -> bool {
+        // Drain every watcher, not just the first that reports a change
+        let mut changed = false;
+        for watcher in &mut self.watchers {
+            changed |= watcher.check_for_changes();
         }
-        2 => {
-            // One argument - file path
-            Ok(Some(args[1].clone()))
+        changed
+    }
+
+    /// Loads the keymap, save options, keybindings and settings for the active buffer
+    ///
+    /// Returns the theme to render with and the keybinding load result, or the
+    /// first error encountered. A failing layer does not stop the others from
+    /// loading.
+    fn load(
+        &mut self,
+        state: &mut EditorState,
+        input_handler: &mut InputHandler,
+    ) -> (Theme, Result<LoadResult, ConfigError>) {
+        let file = state.buffer().file_path().cloned();
+        let paths = ConfigPaths::discover(self.user_path.clone(), file.as_deref());
+        if paths != self.paths {
+            // Silent failure - hot reload just won't work for that file
+            self.watchers = [&paths.user, &paths.project]
+                .into_iter()
+                .flatten()
+                .filter_map(|path| ConfigWatcher::new(path).ok())
+                .collect();
         }
-        _ => {
-            // Too many arguments
-            anyhow::bail!(
-                "Usage: {} [file_path]\n\nArguments:\n  file_path    Optional path to file to open or create",
-                args[0]
-            );
+        self.paths = paths;
+        self.file = file;
+
+        let mut error = None;
+        let mut result = LoadResult {
+            loaded: 0,
+            warnings: Vec::new(),
+        };
+
+        // A project [keymap] section replaces the user's; an invalid one is
+        // reported and the next layer applies
+        let mut keymap = None;
+        for path in [&self.paths.project, &self.paths.user].into_iter().flatten() {
+            match load_keymap_options(path) {
+                Ok(Some(options)) => {
+                    keymap = Some(options);
+                    break;
+                }
+                Ok(None) => {}
+                Err(e) => result.warnings.push(format!("keymap: {}", e)),
+            }
+        }
+        let registry = input_handler.registry_mut();
+        registry.unregister_by_priority(Priority::Default);
+        if let Err(e) = register_keymap(registry, keymap.unwrap_or_default()) {
+            result.warnings.push(format!("keymap: {}", e));
+        }
+
+        // Like [keymap], a project [save] section replaces the user's
+        let mut save_options = None;
+        for path in [&self.paths.project, &self.paths.user].into_iter().flatten() {
+            match load_save_options(path) {
+                Ok(Some(options)) => {
+                    save_options = Some(options);
+                    break;
+                }
+                Ok(None) => {}
+                Err(e) => result.warnings.push(format!("save: {}", e)),
+            }
         }
+        state.set_save_options(save_options.unwrap_or_default());
+
+        match &self.paths.user {
+            Some(user_path) => match reload_user_keybindings(input_handler.registry_mut(), user_path) {
+                Ok((_removed, user)) => {
+                    result.loaded += user.loaded;
+                    result.warnings.extend(user.warnings);
+                }
+                Err(e) => error = Some(e),
+            },
+            None => {
+                input_handler.registry_mut().unregister_by_priority(Priority::User);
+            }
+        }
+
+        match reload_project_keybindings(input_handler.registry_mut(), self.paths.project.as_deref()) {
+            Ok((_removed, project)) => {
+                result.loaded += project.loaded;
+                result.warnings.extend(project.warnings);
+            }
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+
+        // Project commands replace user commands of the same name; read and
+        // parse errors were already reported by the keybinding loads above
+        let mut user_commands = UserCommands::new();
+        for path in [&self.paths.user, &self.paths.project].into_iter().flatten() {
+            if let Ok(warnings) = load_user_commands(path, &mut user_commands, input_handler.registry()) {
+                result.warnings.extend(warnings);
+            }
+        }
+        result.warnings.extend(user_commands.remove_invalid());
+        result.warnings.extend(user_commands.check_bindings(input_handler.registry().bindings()));
+        input_handler.set_user_commands(user_commands);
+
+        let language = self.file.as_deref().and_then(language_for_path);
+        let theme = match resolve_settings(&self.paths, language.as_deref()) {
+            Ok(mut resolved) => {
+                if let Some(file) = &self.file {
+                    resolved.apply_editorconfig(&EditorConfig::for_file(file));
+                }
+                result.warnings.extend(resolved.warnings);
+                apply_settings(state, input_handler, resolved.settings, self.theme_override.as_deref())
+            }
+            Err(e) => {
+                error.get_or_insert(e);
+                let name = self.theme_override.as_deref().unwrap_or(&state.settings().theme);
+                Theme::by_name(name).unwrap_or_default()
+            }
+        };
+
+        (theme, error.map_or(Ok(result), Err))
+    }
+}
+
+/// Formats the outcome of a config load for the status bar when it needs
+/// attention, with its severity
+fn config_warning_message(result: &Result<LoadResult, ConfigError>) -> Option<(Severity, String)> {
+    match result {
+        Ok(result) => format_config_warnings(&result.warnings).map(|message| (Severity::Warning, message)),
+        Err(e) => Some((Severity::Error, format!("⚠ Config reload failed: {}", e))),
+    }
+}
+
+/// Describes the config files, language and settings that apply to `file`,
+/// naming the layer that set each value (`--show-config`)
+fn describe_config(user_path: Option<PathBuf>, file: Option<&Path>) -> Result<Vec<String>> {
+    let paths = ConfigPaths::discover(user_path, file);
+    let language = file.and_then(language_for_path);
+    let mut resolved = resolve_settings(&paths, language.as_deref())?;
+    if let Some(file) = file {
+        resolved.apply_editorconfig(&EditorConfig::for_file(file));
     }
+
+    let display = |path: &Option<PathBuf>| {
+        path.as_ref()
+            .map_or_else(|| "(none)".to_string(), |path| path.display().to_string())
+    };
+    let mut lines = vec![
+        format!("user config: {}", display(&paths.user)),
+        format!("project config: {}", display(&paths.project)),
+        format!("language: {}", language.as_deref().unwrap_or("(none)")),
+        String::new(),
+    ];
+    lines.extend(resolved.describe());
+    lines.extend(resolved.warnings.iter().map(|warning| format!("warning: {}", warning)));
+    Ok(lines)
+}
+
+/// Loads the plugins and describes each one, with the plugins that could not
+/// be loaded and why (`--list-plugins`)
+fn describe_plugins(config: Option<&Path>, plugins_dir: Option<&Path>) -> Vec<String> {
+    let (plugin_list, mut warnings) = discover_plugins(config, plugins_dir);
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    warnings.extend(host.load_all(plugin_list, &mut registry));
+
+    let mut lines = vec![format!("plugin API: {}", API_VERSION)];
+    for name in host.names() {
+        let Some(manifest) = host.manifest(name) else {
+            lines.push(format!("{} (no manifest)", name));
+            continue;
+        };
+        lines.push(format!(
+            "{} {} (plugin API {})",
+            name, manifest.version, manifest.api_version
+        ));
+        for command in &manifest.commands {
+            lines.push(format!("  {}.{}  {}", name, command.name, command.description));
+        }
+        if !manifest.capabilities.is_empty() {
+            lines.push(format!("  capabilities: {}", manifest.capabilities.join(", ")));
+        }
+    }
+    if host.is_empty() && warnings.is_empty() {
+        lines.push("no plugins found".to_string());
+    }
+    lines.extend(warnings.iter().map(|warning| format!("warning: {}", warning)));
+    lines
+}
+
+/// Formats config warnings for the status bar, showing the first one
+fn format_config_warnings(warnings: &[String]) -> Option<String> {
+    let first_warning = warnings.first()?;
+    Some(if warnings.len() > 1 {
+        format!("⚠ {} (+{} more)", first_warning, warnings.len() - 1)
+    } else {
+        format!("⚠ {}", first_warning)
+    })
 }
 
 /// Main event loop: read input → process → render
@@ -126,9 +467,19 @@ fn run_event_loop(
     renderer: &mut Renderer,
     cursor: &mut Position,
     input_handler: &mut InputHandler,
-    mut config_watcher: Option<(ConfigWatcher, std::path::PathBuf)>,
+    plugins: &mut PluginHost,
+    config: &mut ConfigSession,
 ) -> Result<()> {
     loop {
+        // Plugins react to what happened since the last pass before it is shown
+        for error in plugins.dispatch_events(state, cursor, input_handler.registry()) {
+            state.set_status(Severity::Error, error.to_string());
+        }
+
+        // Show the which-key popup once a multi-key sequence has been pending a while
+        state.set_key_hints(input_handler.key_hints(&state.mode(), state.settings().which_key_delay));
+        state.set_recording_macro(input_handler.macros().recording());
+
         // Render current state
         renderer.render(state, *cursor)?;
 
@@ -137,31 +488,38 @@ fn run_event_loop(
             break;
         }
 
+        // Saving through the sudo helper needs the terminal for a password prompt
+        if state.take_sudo_save_request() {
+            renderer.restore_terminal()?;
+            let result = state.save_with_sudo();
+            renderer.resume()?;
+            match result {
+                Ok(()) => *cursor = state.buffer().clamp_position(*cursor),
+                Err(e) => state.set_status(Severity::Error, format!("{:#}", e)),
+            }
+            continue;
+        }
+
+        if let Some(name) = state.take_theme_request() {
+            if let Some(theme) = Theme::by_name(&name) {
+                renderer.set_theme(theme);
+                state.set_status(Severity::Info, format!("Theme set to {}", name));
+            }
+            continue;
+        }
+
         // Check for config file changes (hot reload)
-        if let Some((ref mut watcher, ref config_path)) = config_watcher {
-            if watcher.check_for_changes() {
-                // Config file was modified - reload bindings
-                match reload_user_keybindings(input_handler.registry_mut(), config_path) {
-                    Ok((_removed, result)) => {
-                        if result.warnings.is_empty() {
-                            state.set_status_message(format!(
-                                "✓ Config reloaded: {} bindings",
-                                result.loaded
-                            ));
-                        } else {
-                            // Show first warning in status bar
-                            let first_warning = result.warnings.first().unwrap();
-                            let warning_msg = if result.warnings.len() > 1 {
-                                format!("⚠ {} (+{} more)", first_warning, result.warnings.len() - 1)
-                            } else {
-                                format!("⚠ {}", first_warning)
-                            };
-                            state.set_status_message(warning_msg);
-                        }
-                    }
-                    Err(e) => {
-                        state.set_status_message(format!("⚠ Config reload failed: {}", e));
-                    }
+        if config.has_changes() {
+            // A config file was modified - reload save options, settings and bindings
+            let (theme, result) = config.load(state, input_handler);
+            renderer.set_theme(theme);
+            state.publish_event(EditorEvent::ConfigReloaded);
+            // Show first warning in status bar
+            match config_warning_message(&result) {
+                Some((severity, message)) => state.set_status(severity, message),
+                None => {
+                    let loaded = result.map_or(0, |result| result.loaded);
+                    state.set_status(Severity::Success, format!("✓ Config reloaded: {} bindings", loaded));
                 }
             }
         }
@@ -172,7 +530,16 @@ fn run_event_loop(
         // Read input event with timeout
         if event::poll(Duration::from_millis(100))? {
             if let Event::Key(key_event) = event::read()? {
-                process_key_event(state, cursor, key_event, input_handler)?;
+                process_key_event(state, cursor, key_event, input_handler, plugins)?;
+
+                // Buffers in other projects or languages have their own settings
+                if config.is_stale(state) {
+                    let (theme, result) = config.load(state, input_handler);
+                    renderer.set_theme(theme);
+                    if let Some((severity, message)) = config_warning_message(&result) {
+                        state.set_status(severity, message);
+                    }
+                }
             }
         }
     }
@@ -186,82 +553,189 @@ fn process_key_event(
     cursor: &mut Position,
     key_event: KeyEvent,
     input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
 ) -> Result<()> {
+    // "Describe key" swallows the keys it describes
+    if input_handler.is_describing() {
+        if let Some(description) = input_handler.describe_key_event(key_event, &state.mode()) {
+            state.set_status(Severity::Info, description.to_string());
+        }
+        return Ok(());
+    }
+
     // Process key event through new input handler
-    let result = input_handler.process_key_event(key_event, state.mode());
+    let result = input_handler.process_key_event(key_event, &state.mode());
 
-    match result {
-        MatchResult::Matched(cmd) => {
-            // Complete match - execute the command
-            execute_command(state, cursor, cmd, input_handler)?;
-        }
+    let command = match result {
+        // Complete match - execute the command
+        MatchResult::Matched(cmd) => cmd,
         MatchResult::Partial => {
             // Partial match - wait for next key
             // Do nothing, buffer is preserved automatically
+            return Ok(());
         }
-        MatchResult::NoMatch => {
-            // No match - fall back to default behavior based on mode
-            handle_no_match(state, cursor, key_event)?;
-        }
-    }
+        // Key release without a binding
+        MatchResult::Ignored => return Ok(()),
+        // No match - fall back to default behavior based on mode
+        MatchResult::NoMatch => match unmatched_key_command(&state.mode(), key_event) {
+            Some(cmd) => cmd,
+            None => return Ok(()),
+        },
+    };
 
-    Ok(())
+    // Macros record the command stream, not the keys
+    input_handler.macros_mut().record(&command);
+    execute_command(state, cursor, command, input_handler, plugins)
 }
 
-/// Handles keys that don't match any binding (fallback behavior)
+/// Returns the command for a key that doesn't match any binding (fallback behavior)
 ///
 /// This implements mode-specific default behavior for unmatched keys:
 /// - Insert mode: Insert printable characters
 /// - Prompt mode: Insert printable characters into prompt
-/// - Normal mode: Ignore
-fn handle_no_match(
+/// - Normal and plugin modes: Ignore
+fn unmatched_key_command(mode: &EditorMode, key_event: KeyEvent) -> Option<EditorCommand> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    // Only printable characters, with no special modifiers except SHIFT
+    let KeyCode::Char(c) = key_event.code else {
+        return None;
+    };
+    if key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+        return None;
+    }
+
+    match mode {
+        EditorMode::Insert => Some(EditorCommand::InsertChar(c)),
+        EditorMode::Prompt => Some(EditorCommand::PromptInsertChar(c)),
+        // In Normal and plugin modes, ignore unmatched keys
+        EditorMode::Normal | EditorMode::Plugin(_) => None,
+    }
+}
+
+/// Status message shown when an edit is attempted in a read-only buffer
+const READ_ONLY_MESSAGE: &str = "Buffer is read-only. Use 'buffer.toggle_readonly' to allow editing.";
+
+/// Returns the status message if it reports an error
+fn status_error(state: &EditorState) -> Option<&str> {
+    state
+        .status_message()
+        .filter(|_| state.status_severity() == Severity::Error)
+}
+
+/// Runs `commands` in order, stopping at the first one that fails
+///
+/// A command fails when it leaves an error status message; the error is
+/// reported with the failing step. The chain also stops once a command quits
+/// or opens a prompt that needs input (e.g. "Save as" for an unnamed buffer).
+fn execute_chain(
     state: &mut EditorState,
     cursor: &mut Position,
-    key_event: KeyEvent,
+    commands: &[EditorCommand],
+    input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
 ) -> Result<()> {
-    use crossterm::event::{KeyCode, KeyModifiers};
+    // An error left over from before the chain is not a failure of its commands
+    if status_error(state).is_some() {
+        state.clear_status_message();
+    }
 
-    match state.mode() {
-        EditorMode::Insert => {
-            // Insert printable characters (only if no special modifiers except SHIFT)
-            if let KeyCode::Char(c) = key_event.code {
-                if !key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
-                    // Handle char insertion directly
-                    state.handle_char_insert(c, *cursor);
-                    // Move cursor after insertion
-                    if c == '\n' {
-                        cursor.line += 1;
-                        cursor.column = 0;
-                    } else {
-                        cursor.column += 1;
-                    }
-                    state.clear_status_message();
-                }
-            }
-        }
-        EditorMode::Prompt => {
-            // Insert printable characters into prompt (only if no special modifiers except SHIFT)
-            if let KeyCode::Char(c) = key_event.code {
-                if !key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
-                    state.prompt_insert_char(c);
-                }
-            }
+    for (step, command) in commands.iter().enumerate() {
+        let mode = state.mode();
+        execute_command(state, cursor, command.clone(), input_handler, plugins)?;
+
+        if let Some(reason) = status_error(state) {
+            let message = format!(
+                "'{}' failed (step {} of {}): {}",
+                command,
+                step + 1,
+                commands.len(),
+                reason
+            );
+            state.set_status(Severity::Error, message);
+            break;
         }
-        EditorMode::Normal => {
-            // In Normal mode, ignore unmatched keys
+        let opened_prompt = state.mode() == EditorMode::Prompt
+            && mode != EditorMode::Prompt
+            && !matches!(command, EditorCommand::ChangeMode(_));
+        if state.should_quit() || opened_prompt {
+            break;
         }
     }
 
     Ok(())
 }
 
+/// Plays the macro in `register` `count` times
+///
+/// Playback stops at the first failing command, like a command chain. Being a
+/// single command, the whole playback is one undo step.
+fn play_macro(
+    state: &mut EditorState,
+    cursor: &mut Position,
+    register: char,
+    count: usize,
+    input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
+) -> Result<()> {
+    let commands = match input_handler.macros().get(register) {
+        Some(commands) if !commands.is_empty() => commands.to_vec(),
+        _ => {
+            state.set_status(Severity::Error, format!("Macro register '{}' is empty", register));
+            return Ok(());
+        }
+    };
+    let repeated = match repeat_commands(&commands, count) {
+        Ok(repeated) => repeated,
+        Err(message) => {
+            state.set_status(Severity::Error, message);
+            return Ok(());
+        }
+    };
+    if !input_handler.macros_mut().start_playback(register) {
+        state.set_status(Severity::Error, format!("Macro '{}' cannot play itself", register));
+        return Ok(());
+    }
+
+    let result = execute_chain(state, cursor, &repeated, input_handler, plugins);
+    input_handler.macros_mut().finish_playback();
+    result
+}
+
 /// Execute an editor command, updating state and cursor position
+///
+/// Each command is a single undo step, however many edits it makes.
 fn execute_command(
     state: &mut EditorState,
     cursor: &mut Position,
     command: EditorCommand,
     input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
+) -> Result<()> {
+    state.begin_undo_group();
+    let result = run_command(state, cursor, command, input_handler, plugins);
+    state.end_undo_group();
+    result
+}
+
+/// Carries out a command for [`execute_command`]
+fn run_command(
+    state: &mut EditorState,
+    cursor: &mut Position,
+    command: EditorCommand,
+    input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
 ) -> Result<()> {
+    // Only a quit right after the unsaved-changes warning confirms it
+    if command != EditorCommand::Quit {
+        state.cancel_quit();
+    }
+
+    if state.is_read_only() && command.is_edit() {
+        state.set_status(Severity::Error, READ_ONLY_MESSAGE.to_string());
+        return Ok(());
+    }
+
     match command {
         EditorCommand::InsertChar(ch) => {
             state.handle_char_insert(ch, *cursor);
@@ -314,6 +788,14 @@ fn execute_command(
         EditorCommand::MoveCursor(direction) => {
             move_cursor(cursor, direction, state);
         }
+        EditorCommand::MoveCursorBy { direction, count } => {
+            for _ in 0..count {
+                move_cursor(cursor, direction, state);
+            }
+        }
+        EditorCommand::GotoLine(line) => {
+            *cursor = state.buffer().clamp_position(Position::new(line - 1, 0));
+        }
         EditorCommand::MoveToLineStart => {
             *cursor = state.buffer().get_line_start(*cursor);
         }
@@ -333,26 +815,165 @@ fn execute_command(
             *cursor = state.buffer().page_down(*cursor, DEFAULT_VIEWPORT_HEIGHT);
         }
         EditorCommand::InsertTab => {
-            // Insert a tab character
-            // In the future, this should respect editor config (tabs vs spaces, tab width)
-            state.handle_char_insert('\t', *cursor);
-            cursor.column += 1;
+            // Insert a tab character, or spaces up to the next tab stop with expand_tabs
+            for ch in state.settings().tab_text(cursor.column).chars() {
+                state.handle_char_insert(ch, *cursor);
+                cursor.column += 1;
+            }
+            state.clear_status_message();
+        }
+        EditorCommand::InsertText(text) => {
+            for ch in text.chars() {
+                state.handle_char_insert(ch, *cursor);
+                if ch == '\n' {
+                    cursor.line += 1;
+                    cursor.column = 0;
+                } else {
+                    cursor.column += 1;
+                }
+            }
             state.clear_status_message();
         }
+        EditorCommand::SetTheme(name) => {
+            // Applied by the event loop, which owns the renderer
+            state.request_theme(name);
+        }
         EditorCommand::Save => {
             match state.save() {
                 Ok(_saved) => {
                     // Status message already set by save() if file was saved
                     // If not saved, we're now in prompt mode
+                    // Save hooks may have rewritten the buffer, keep the cursor valid
+                    *cursor = state.buffer().clamp_position(*cursor);
                 }
                 Err(e) => {
-                    state.set_status_message(format!("Error: {:#}", e));
+                    state.set_status(Severity::Error, format!("{:#}", e));
+                }
+            }
+        }
+        EditorCommand::SaveWithSudo => {
+            // Carried out by the event loop, which can release the terminal
+            state.request_sudo_save();
+        }
+        EditorCommand::ToggleReadOnly => {
+            let read_only = !state.is_read_only();
+            state.set_read_only(read_only);
+            state.set_status(
+                Severity::Info,
+                if read_only {
+                    "Buffer is now read-only".to_string()
+                } else {
+                    "Buffer is now editable".to_string()
+                },
+            );
+        }
+        EditorCommand::NextBuffer | EditorCommand::PreviousBuffer => {
+            if state.buffer_count() > 1 {
+                *cursor = if command == EditorCommand::NextBuffer {
+                    state.next_buffer(*cursor)
+                } else {
+                    state.previous_buffer(*cursor)
+                };
+                state.clear_status_message();
+            } else {
+                state.set_status(Severity::Info, "No other buffers are open".to_string());
+            }
+        }
+        EditorCommand::ListKeybindings => {
+            let report = keybinding_report(input_handler.registry());
+            // Buffers cannot be closed, so an open report is refreshed instead
+            let index = match state.buffers().iter().position(is_keybinding_report) {
+                Some(index) => {
+                    let buffer = &mut state.buffers_mut()[index];
+                    buffer.set_content(&report);
+                    buffer.clear_dirty();
+                    index
+                }
+                None => {
+                    let mut buffer = Buffer::from_str(&report);
+                    buffer.set_read_only(true);
+                    state.add_buffer(buffer, Position::origin());
+                    state.buffer_count() - 1
+                }
+            };
+            *cursor = state.switch_buffer(index, *cursor);
+            state.set_status(Severity::Info, "Keybindings listed in a read-only buffer".to_string());
+        }
+        EditorCommand::DescribeKey => {
+            input_handler.describe_next_sequence();
+            state.set_status(Severity::Info, "Press a key sequence to describe".to_string());
+        }
+        EditorCommand::CommandPalette => {
+            let entries = palette_entries(
+                input_handler.registry(),
+                input_handler.user_commands(),
+                &plugins.commands(),
+                &state.mode(),
+            );
+            state.enter_list_prompt(PromptKind::CommandPalette, "Command: ".to_string(), palette_list(&entries));
+        }
+        EditorCommand::Noop => {}
+        EditorCommand::Undo | EditorCommand::Redo => {
+            let changed = if command == EditorCommand::Undo {
+                state.buffer_mut().undo()
+            } else {
+                state.buffer_mut().redo()
+            };
+            if changed {
+                *cursor = state.buffer().clamp_position(*cursor);
+                state.clear_status_message();
+            } else if command == EditorCommand::Undo {
+                state.set_status(Severity::Info, "Nothing to undo".to_string());
+            } else {
+                state.set_status(Severity::Info, "Nothing to redo".to_string());
+            }
+        }
+        EditorCommand::ToggleMacroRecording(register) => {
+            let macros = input_handler.macros_mut();
+            match macros.stop_recording() {
+                Some((register, count)) => {
+                    let (severity, message) = match macros.save() {
+                        Ok(()) => (
+                            Severity::Info,
+                            format!("Recorded {} commands into register '{}'", count, register),
+                        ),
+                        Err(e) => (
+                            Severity::Warning,
+                            format!("Recorded macro '{}' but could not save it: {}", register, e),
+                        ),
+                    };
+                    state.set_status(severity, message);
+                }
+                None => {
+                    macros.start_recording(register);
+                    state.set_status(Severity::Info, format!("Recording macro into register '{}'", register));
                 }
             }
         }
+        EditorCommand::PlayMacro { register, count } => {
+            play_macro(state, cursor, register, count, input_handler, plugins)?;
+        }
+        EditorCommand::Chain(commands) => {
+            execute_chain(state, cursor, &commands, input_handler, plugins)?;
+        }
+        EditorCommand::UserCommand(name) => match input_handler.user_commands().get(&name) {
+            Some(commands) => {
+                let commands = commands.to_vec();
+                execute_chain(state, cursor, &commands, input_handler, plugins)?;
+            }
+            None => {
+                state.set_status(Severity::Error, format!("Unknown command 'user.{}'", name));
+            }
+        },
         EditorCommand::Quit => {
             state.request_quit();
         }
+        EditorCommand::ForceQuit => {
+            state.force_quit();
+        }
+        EditorCommand::CommandLine => {
+            state.enter_prompt_for(PromptKind::CommandLine, ":".to_string());
+        }
         EditorCommand::ChangeMode(mode) => {
             state.set_mode(mode);
             // Clear sequence buffer on mode change
@@ -366,29 +987,70 @@ fn execute_command(
         EditorCommand::PromptDeleteChar => {
             state.prompt_delete_char();
         }
+        EditorCommand::PromptNext => {
+            state.prompt_select_next();
+        }
+        EditorCommand::PromptPrevious => {
+            state.prompt_select_previous();
+        }
+        EditorCommand::PromptComplete => {
+            let dir = env::current_dir().unwrap_or_default();
+            match state.prompt_kind() {
+                PromptKind::CommandLine => {
+                    state.prompt_complete(|input| command_line::complete(input, &dir));
+                }
+                PromptKind::SaveAs => {
+                    state.prompt_complete(|input| command_line::complete_path(input, &dir));
+                }
+                // List prompts are narrowed by typing instead
+                PromptKind::PluginPicker { .. } | PromptKind::CommandPalette => {}
+            }
+        }
         EditorCommand::AcceptPrompt => {
-            let filename = state.accept_prompt();
+            let kind = state.prompt_kind().clone();
+            let selection = state.prompt_selection();
+            let input = state.accept_prompt();
             // Clear sequence buffer on mode change (Prompt -> previous mode)
             input_handler.on_mode_change();
-            if !filename.is_empty() {
-                // Save the file with the given filename
-                match state.save_as(Path::new(&filename)) {
-                    Ok(()) => {
-                        // Status message already set by save_as()
+            match kind {
+                PromptKind::PluginPicker { plugin, id } => {
+                    let selection = selection.map(|(index, item)| PickerSelection { index, item });
+                    if let Err(e) = plugins.picker_result(&plugin, &id, selection, state, cursor, input_handler.registry()) {
+                        state.set_status(Severity::Error, e.to_string());
                     }
-                    Err(e) => {
-                        state.set_status_message(format!("Error: {:#}", e));
+                }
+                PromptKind::CommandPalette => {
+                    // Entries are named so that they parse back into their command
+                    match selection.map(|(_, name)| input_handler.registry().parse_command(&name)) {
+                        Some(Ok(command)) => execute_command(state, cursor, command, input_handler, plugins)?,
+                        Some(Err(e)) => state.set_status(Severity::Error, e.to_string()),
+                        None => state.set_status(Severity::Info, format!("No command matches '{}'", input)),
                     }
                 }
-            } else {
-                state.set_status_message("Error: Filename cannot be empty. Press Esc to cancel.".to_string());
+                PromptKind::CommandLine => match command_line::parse(&input) {
+                    Ok(command) => run_ex_command(state, cursor, command, input_handler, plugins)?,
+                    Err(e) => state.set_status(Severity::Error, e.to_string()),
+                },
+                PromptKind::SaveAs if !input.is_empty() => save_as(state, cursor, Path::new(&input)),
+                PromptKind::SaveAs => {
+                    state.set_status(Severity::Error, "Filename cannot be empty. Press Esc to cancel.".to_string());
+                }
             }
         }
         EditorCommand::CancelPrompt => {
+            let kind = state.prompt_kind().clone();
             state.cancel_prompt();
             // Clear sequence buffer on mode change (Prompt -> previous mode)
             input_handler.on_mode_change();
-            state.set_status_message("Info: Save cancelled".to_string());
+            match kind {
+                PromptKind::SaveAs => state.set_status(Severity::Info, "Save cancelled".to_string()),
+                PromptKind::CommandPalette | PromptKind::CommandLine => {}
+                PromptKind::PluginPicker { plugin, id } => {
+                    if let Err(e) = plugins.picker_result(&plugin, &id, None, state, cursor, input_handler.registry()) {
+                        state.set_status(Severity::Error, e.to_string());
+                    }
+                }
+            }
         }
         // Selection commands (placeholder - not yet implemented)
         EditorCommand::SelectLeft
@@ -398,14 +1060,14 @@ fn execute_command(
         | EditorCommand::SelectLineStart
         | EditorCommand::SelectLineEnd
         | EditorCommand::SelectAll => {
-            state.set_status_message(format!(
+            state.set_status(Severity::Info, format!(
                 "Command '{:?}' is not yet implemented. This feature is planned for a future release.",
                 command
             ));
         }
         // Clipboard commands (placeholder - not yet implemented)
         EditorCommand::Copy | EditorCommand::Cut | EditorCommand::Paste => {
-            state.set_status_message(format!(
+            state.set_status(Severity::Info, format!(
                 "Command '{:?}' is not yet implemented. This feature is planned for a future release.",
                 command
             ));
@@ -415,12 +1077,116 @@ fn execute_command(
             plugin_name,
             command_name,
         } => {
-            // Plugin system not yet initialized - provide helpful error message
-            state.set_status_message(format!(
-                "Plugin command '{}.{}' cannot be executed: plugin system not yet initialized. \
-                 Plugin support is planned for a future release.",
-                plugin_name, command_name
-            ));
+            if let Err(e) = plugins.execute(&plugin_name, &command_name, state, cursor, input_handler.registry()) {
+                state.set_status(Severity::Error, e.to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Saves the active buffer as `path`, reporting failures in the status bar
+fn save_as(state: &mut EditorState, cursor: &mut Position, path: &Path) {
+    match state.save_as(path) {
+        Ok(()) => {
+            // Status message already set by save_as()
+            *cursor = state.buffer().clamp_position(*cursor);
+        }
+        Err(e) => {
+            state.set_status(Severity::Error, format!("{:#}", e));
+        }
+    }
+}
+
+/// Carries out a command typed on the `:` command line
+fn run_ex_command(
+    state: &mut EditorState,
+    cursor: &mut Position,
+    command: ExCommand,
+    input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
+) -> Result<()> {
+    match command {
+        ExCommand::Write(None) => execute_command(state, cursor, EditorCommand::Save, input_handler, plugins)?,
+        ExCommand::Write(Some(path)) => save_as(state, cursor, &path),
+        ExCommand::Quit => execute_command(state, cursor, EditorCommand::Quit, input_handler, plugins)?,
+        ExCommand::ForceQuit => execute_command(state, cursor, EditorCommand::ForceQuit, input_handler, plugins)?,
+        ExCommand::WriteQuit => {
+            let commands = [EditorCommand::Save, EditorCommand::Quit];
+            execute_chain(state, cursor, &commands, input_handler, plugins)?;
+        }
+        ExCommand::Edit(path) => {
+            let open = state
+                .buffers()
+                .iter()
+                .position(|buffer| buffer.file_path() == Some(&path));
+            let index = match open {
+                Some(index) => index,
+                None => match state.open_file(&path, *cursor) {
+                    Ok(()) => state.buffer_count() - 1,
+                    Err(e) => {
+                        state.set_status(Severity::Error, format!("{:#}", e));
+                        return Ok(());
+                    }
+                },
+            };
+            *cursor = state.switch_buffer(index, *cursor);
+        }
+        ExCommand::Goto(address) => {
+            let line = address.resolve(cursor.line, state.buffer().line_count());
+            execute_command(state, cursor, EditorCommand::GotoLine(line + 1), input_handler, plugins)?;
+        }
+        ExCommand::Substitute(substitution) => {
+            if state.is_read_only() {
+                state.set_status(Severity::Error, READ_ONLY_MESSAGE.to_string());
+                return Ok(());
+            }
+            let (start, end) = match substitution.range.resolve(cursor.line, state.buffer().line_count()) {
+                Ok(lines) => lines,
+                Err(e) => {
+                    state.set_status(Severity::Error, e.to_string());
+                    return Ok(());
+                }
+            };
+            match substitution.apply(state.buffer_mut(), start, end) {
+                (0, _) => {
+                    state.set_status(Severity::Error, format!("Pattern not found: {}", substitution.pattern));
+                }
+                (count, lines) => {
+                    *cursor = state.buffer().clamp_position(*cursor);
+                    state.set_status(Severity::Info, format!(
+                        "{} substitution{} on {} line{}",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        lines,
+                        if lines == 1 { "" } else { "s" }
+                    ));
+                }
+            }
+        }
+        ExCommand::Set { key, value: None } => match state.settings().value(&key) {
+            Some(value) => state.set_status(Severity::Info, format!("{}={}", key, value)),
+            None => state.set_status(Severity::Error, format!("Unknown setting '{}'", key)),
+        },
+        ExCommand::Set { key, value: Some(value) } => {
+            let mut settings = state.settings().clone();
+            let mut table = toml::Table::new();
+            table.insert(key.clone(), value);
+            let mut warnings = Vec::new();
+            apply_editor_settings(&mut settings, &table, "editor", &mut warnings);
+            if let Some(warning) = warnings.first() {
+                state.set_status(Severity::Error, warning.to_string());
+                return Ok(());
+            }
+
+            input_handler.set_timeout(settings.sequence_timeout);
+            if key == "theme" {
+                state.request_theme(settings.theme.clone());
+            }
+            let shown = settings.value(&key).unwrap_or_default();
+            state.set_settings(settings);
+            state.set_status(Severity::Info, format!("{}={}", key, shown));
         }
     }
 
@@ -507,9 +1273,18 @@ fn setup_panic_handler() {
     let original_hook = panic::take_hook();
 
     panic::set_hook(Box::new(move |panic_info| {
-        // Attempt to restore terminal
+        // The plugin host catches and reports panics in plugins
+        if is_plugin_running() {
+            return;
+        }
+
+        // Attempt to restore terminal (the one drawn on, which is not stdout in pipe mode)
         let _ = disable_raw_mode();
-        let _ = crossterm::execute!(io::stdout(), crossterm::terminal::LeaveAlternateScreen);
+        if let Ok(mut output) = TerminalOutput::open() {
+            // Terminals without keyboard enhancement ignore the pop
+            let _ = crossterm::execute!(output, crossterm::event::PopKeyboardEnhancementFlags);
+            let _ = crossterm::execute!(output, crossterm::terminal::LeaveAlternateScreen);
+        }
 
         // Call the original panic hook
         original_hook(panic_info);
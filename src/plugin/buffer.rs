@@ -0,0 +1,150 @@
+//! Buffer API implementation handed to plugin commands
+
+use std::path::Path;
+
+use termide_plugin_api::buffer::{BufferApi, BufferError, TextPosition, TextRange};
+
+use crate::buffer::{Position, Selection};
+use crate::editor::EditorState;
+
+impl From<TextPosition> for Position {
+    fn from(position: TextPosition) -> Self {
+        Position::new(position.line, position.column)
+    }
+}
+
+impl From<Position> for TextPosition {
+    fn from(position: Position) -> Self {
+        TextPosition::new(position.line, position.column)
+    }
+}
+
+/// The active buffer of an [`EditorState`] and its cursor, as a [`BufferApi`]
+///
+/// Edits respect the read-only flag of the buffer. Undo grouping is up to the
+/// caller: the editor runs each plugin command in one undo group.
+///
+/// # Examples
+///
+/// ```
+/// use termide::buffer::Position;
+/// use termide::editor::EditorState;
+/// use termide::plugin::ActiveBuffer;
+/// use termide_plugin_api::buffer::{BufferApi, TextPosition};
+///
+/// let mut state = EditorState::new();
+/// let mut cursor = Position::origin();
+/// let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+///
+/// let end = buffer.insert(TextPosition::new(0, 0), "hello").unwrap();
+/// buffer.set_cursor(end).unwrap();
+/// assert_eq!(buffer.line(0), Some("hello".to_string()));
+/// assert!(buffer.is_dirty());
+/// assert_eq!(cursor, Position::new(0, 5));
+/// ```
+pub struct ActiveBuffer<'a> {
+    state: &'a mut EditorState,
+    cursor: &'a mut Position,
+}
+
+impl<'a> ActiveBuffer<'a> {
+    /// Wraps the active buffer of `state`, whose cursor is `cursor`
+    pub fn new(state: &'a mut EditorState, cursor: &'a mut Position) -> Self {
+        Self { state, cursor }
+    }
+
+    /// Returns an error unless `position` is inside the buffer
+    fn check(&self, position: TextPosition) -> Result<Position, BufferError> {
+        let converted = Position::from(position);
+        if self.state.buffer().is_valid_position(converted) {
+            Ok(converted)
+        } else {
+            Err(BufferError::InvalidPosition(position))
+        }
+    }
+
+    /// Returns an error if the buffer is read-only
+    fn check_writable(&self) -> Result<(), BufferError> {
+        if self.state.is_read_only() {
+            Err(BufferError::ReadOnly)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl BufferApi for ActiveBuffer<'_> {
+    fn line_count(&self) -> usize {
+        self.state.buffer().line_count()
+    }
+
+    fn line(&self, line: usize) -> Option<String> {
+        let text = self.state.buffer().get_line(line)?;
+        Some(text.trim_end_matches(['\n', '\r']).to_string())
+    }
+
+    fn text(&self, range: TextRange) -> Result<String, BufferError> {
+        let start = self.check(range.start)?;
+        let end = self.check(range.end)?;
+        Ok(self.state.buffer().text_range(start, end).unwrap_or_default())
+    }
+
+    fn insert(&mut self, position: TextPosition, text: &str) -> Result<TextPosition, BufferError> {
+        self.check_writable()?;
+        let position = self.check(position)?;
+        let end = self
+            .state
+            .buffer_mut()
+            .insert_text(text, position)
+            .expect("position was checked");
+        Ok(end.into())
+    }
+
+    fn delete(&mut self, range: TextRange) -> Result<(), BufferError> {
+        self.check_writable()?;
+        let start = self.check(range.start)?;
+        let end = self.check(range.end)?;
+        self.state.buffer_mut().delete_range(start, end);
+        Ok(())
+    }
+
+    fn cursor(&self) -> TextPosition {
+        (*self.cursor).into()
+    }
+
+    fn set_cursor(&mut self, position: TextPosition) -> Result<(), BufferError> {
+        *self.cursor = self.check(position)?;
+        Ok(())
+    }
+
+    fn selection(&self) -> Option<TextRange> {
+        let selection = self.state.buffer().selection()?;
+        if !selection.has_selection() {
+            return None;
+        }
+        let (start, end) = selection.range();
+        Some(TextRange::new(start.into(), end.into()))
+    }
+
+    fn set_selection(&mut self, range: Option<TextRange>) -> Result<(), BufferError> {
+        let selection = match range {
+            Some(range) => {
+                let start = self.check(range.start)?;
+                let end = self.check(range.end)?;
+                *self.cursor = end;
+                Some(Selection::with_anchor_and_cursor(start, end))
+            }
+            None => None,
+        };
+        self.state.buffer_mut().set_selection(selection);
+        Ok(())
+    }
+
+    fn file_path(&self) -> Option<&Path> {
+        self.state.buffer().file_path().map(|path| path.as_path())
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.state.buffer().is_dirty()
+    }
+}
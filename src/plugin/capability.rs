@@ -0,0 +1,180 @@
+//! Capabilities granted to sandboxed plugins
+//!
+//! Sandboxed plugins can only reach the editor through host functions, and
+//! each group of host functions needs a capability. Plugins get
+//! [`Capability::DEFAULTS`] unless the `[plugins]` config section lists their
+//! capabilities explicitly:
+//!
+//! ```toml
+//! [plugins.word-count]
+//! capabilities = ["buffer-read", "ui"]
+//!
+//! [plugins.snippets]
+//! capabilities = ["buffer-read", "buffer-edit", "ui", "filesystem"]
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::plugins_section;
+use crate::input::config::ConfigError;
+
+/// A group of host functions a sandboxed plugin may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    /// Read the active buffer and the cursor position
+    BufferRead,
+    /// Edit the active buffer and move the cursor
+    BufferEdit,
+    /// Show status messages
+    Ui,
+    /// Read files
+    Filesystem,
+}
+
+impl Capability {
+    /// Every capability, in display order
+    pub const ALL: [Capability; 4] = [
+        Capability::BufferRead,
+        Capability::BufferEdit,
+        Capability::Ui,
+        Capability::Filesystem,
+    ];
+
+    /// Capabilities of plugins without a `[plugins.<name>]` config entry
+    pub const DEFAULTS: [Capability; 3] = [
+        Capability::BufferRead,
+        Capability::BufferEdit,
+        Capability::Ui,
+    ];
+
+    /// Returns the name used in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::BufferRead => "buffer-read",
+            Capability::BufferEdit => "buffer-edit",
+            Capability::Ui => "ui",
+            Capability::Filesystem => "filesystem",
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Capability {
+    type Err = String;
+
+    /// Parses a capability name, ignoring case
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::plugin::Capability;
+    ///
+    /// assert_eq!("Filesystem".parse(), Ok(Capability::Filesystem));
+    /// assert!("network".parse::<Capability>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Capability::ALL
+            .into_iter()
+            .find(|capability| capability.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Capability::ALL.iter().map(|c| c.name()).collect();
+                format!(
+                    "unknown capability '{}' (available: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Capabilities granted to each plugin, from the `[plugins]` config section
+///
+/// # Examples
+///
+/// ```
+/// use termide::plugin::{Capability, PluginGrants};
+///
+/// let table: toml::Table = toml::from_str(r#"
+///     [word-count]
+///     capabilities = ["buffer-read"]
+/// "#).unwrap();
+/// let (grants, warnings) = PluginGrants::parse(&table);
+///
+/// assert!(warnings.is_empty());
+/// assert_eq!(grants.capabilities("word-count").len(), 1);
+/// assert!(grants.capabilities("other").contains(&Capability::BufferEdit));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginGrants {
+    plugins: BTreeMap<String, BTreeSet<Capability>>,
+}
+
+impl PluginGrants {
+    /// Parses the `[plugins]` section, returning a warning for each invalid entry
+    ///
+    /// Unknown capabilities are skipped; an entry without a valid
+    /// `capabilities` list keeps the defaults. Other keys of an entry, such as
+    /// the `command` of process plugins, are ignored.
+    pub fn parse(table: &toml::Table) -> (Self, Vec<String>) {
+        let mut grants = Self::default();
+        let mut warnings = Vec::new();
+
+        for (plugin, entry) in table {
+            let Some(entry) = entry.as_table() else {
+                warnings.push(format!("plugins.{}: expected a table", plugin));
+                continue;
+            };
+            let Some(list) = entry.get("capabilities") else {
+                continue;
+            };
+            let Some(list) = list.as_array() else {
+                warnings.push(format!("plugins.{}: 'capabilities' must be a list", plugin));
+                continue;
+            };
+
+            let mut capabilities = BTreeSet::new();
+            for value in list {
+                match value.as_str().map(Capability::from_str) {
+                    Some(Ok(capability)) => {
+                        capabilities.insert(capability);
+                    }
+                    Some(Err(e)) => warnings.push(format!("plugins.{}: {}", plugin, e)),
+                    None => {
+                        warnings.push(format!("plugins.{}: capabilities must be strings", plugin))
+                    }
+                }
+            }
+            grants.plugins.insert(plugin.clone(), capabilities);
+        }
+
+        (grants, warnings)
+    }
+
+    /// Loads the `[plugins]` section of the config file at `path`
+    ///
+    /// A missing file grants the defaults to every plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid TOML.
+    pub fn load(path: &Path) -> Result<(Self, Vec<String>), ConfigError> {
+        Ok(Self::parse(&plugins_section(path)?))
+    }
+
+    /// Returns the capabilities granted to `plugin`
+    pub fn capabilities(&self, plugin: &str) -> BTreeSet<Capability> {
+        match self.plugins.get(plugin) {
+            Some(capabilities) => capabilities.clone(),
+            None => Capability::DEFAULTS.into_iter().collect(),
+        }
+    }
+}
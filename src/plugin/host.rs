@@ -0,0 +1,605 @@
+//! Plugin host: loads plugins and runs their commands
+
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+
+use termide_plugin_api::event::{EditorEvent, EventKind};
+use termide_plugin_api::input::{BindingError, PluginBinding, PluginInputExtension, PluginMode as ApiPluginMode};
+use termide_plugin_api::manifest::{ManifestError, PluginManifest};
+use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+use termide_plugin_api::ui::{PickerSelection, Severity, UiRequest};
+use thiserror::Error;
+
+use super::{plugin_binding, ActiveBuffer};
+use crate::buffer::Position;
+use crate::editor::{EditorMode, EditorState, PromptKind};
+use crate::input::registry::KeyBindingRegistry;
+use crate::input::EditorCommand;
+
+/// Most events caused by event handlers delivered per [`PluginHost::dispatch_events`]
+pub const MAX_CASCADED_EVENTS: usize = 64;
+
+thread_local! {
+    /// Whether a plugin call is on the stack of this thread
+    static RUNNING_PLUGIN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns `true` while the current thread is running plugin code
+///
+/// The editor's panic hook uses this to leave the terminal alone for panics
+/// that the host catches and reports.
+pub fn is_plugin_running() -> bool {
+    RUNNING_PLUGIN.with(Cell::get)
+}
+
+/// Error type for loading plugins and running their commands
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PluginHostError {
+    /// The plugin name cannot be used as a command namespace
+    #[error("invalid plugin name '{0}': names may only contain letters, digits, '_' and '-'")]
+    InvalidName(String),
+
+    /// Another plugin with the same name is loaded
+    #[error("plugin '{0}' is already loaded")]
+    AlreadyLoaded(String),
+
+    /// No loaded plugin has this name
+    #[error("plugin '{0}' is not loaded")]
+    NotLoaded(String),
+
+    /// The plugin panicked earlier and no longer runs commands
+    #[error("plugin '{plugin}' is disabled after a panic: {message}")]
+    Disabled { plugin: String, message: String },
+
+    /// The plugin's manifest names a different plugin
+    #[error("plugin '{plugin}' has the manifest of plugin '{manifest}'")]
+    ManifestName { plugin: String, manifest: String },
+
+    /// The plugin's manifest is invalid or targets an incompatible API version
+    #[error("plugin '{plugin}' is incompatible: {source}")]
+    Incompatible { plugin: String, source: ManifestError },
+
+    /// The plugin's keybinding registration failed
+    #[error("plugin '{plugin}' failed to register keybindings: {source}")]
+    Registration { plugin: String, source: BindingError },
+
+    /// A plugin command returned an error
+    #[error("plugin '{plugin}': {source}")]
+    Failed { plugin: String, source: PluginError },
+
+    /// The plugin panicked
+    #[error("plugin '{plugin}' panicked: {message}")]
+    Panicked { plugin: String, message: String },
+
+    /// Event handlers caused more events than are delivered at once
+    #[error("plugin '{plugin}' caused too many events; later events were dropped")]
+    TooManyEvents { plugin: String },
+}
+
+/// A loaded plugin
+struct LoadedPlugin {
+    /// The plugin's name, read once at load time
+    name: String,
+    plugin: Box<dyn Plugin>,
+    /// The plugin's manifest, read once at load time
+    manifest: Option<PluginManifest>,
+    /// Events delivered to the plugin's event handler
+    subscriptions: Vec<EventKind>,
+    /// Panic message if the plugin has been disabled
+    panicked: Option<String>,
+}
+
+/// Loads plugins and dispatches `PluginCommand`s to them
+///
+/// Every call into a plugin is isolated: errors are returned as
+/// [`PluginHostError`] and panics are caught. A plugin that panics while
+/// running a command is disabled for the rest of the session, since its
+/// state may be inconsistent.
+///
+/// Commands get the active buffer through an [`ActiveBuffer`]. The UI
+/// requests they make (status messages, status segments, pickers, modes) are
+/// applied to the editor state when they return; plugin modes are looked up
+/// in the registry passed along.
+///
+/// Editor events are delivered by [`dispatch_events`](Self::dispatch_events),
+/// with the ordering and reentrancy rules of the
+/// [`event`](termide_plugin_api::event) module.
+///
+/// # Examples
+///
+/// ```
+/// use termide::buffer::Position;
+/// use termide::editor::EditorState;
+/// use termide::input::registry::KeyBindingRegistry;
+/// use termide::plugin::{PluginHost, PluginHostError};
+/// use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+///
+/// struct Hello;
+///
+/// impl Plugin for Hello {
+///     fn name(&self) -> &str {
+///         "hello"
+///     }
+///
+///     fn execute(&mut self, command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+///         match command {
+///             "greet" => {
+///                 let buffer = context.buffer();
+///                 buffer.insert(buffer.cursor(), "Hello!")?;
+///                 Ok(())
+///             }
+///             other => Err(PluginError::UnknownCommand(other.to_string())),
+///         }
+///     }
+/// }
+///
+/// let mut registry = KeyBindingRegistry::default();
+/// let mut host = PluginHost::new();
+/// host.load(Box::new(Hello), &mut registry).unwrap();
+///
+/// let mut state = EditorState::new();
+/// let mut cursor = Position::origin();
+/// assert!(host.execute("hello", "greet", &mut state, &mut cursor, &registry).is_ok());
+/// assert!(matches!(
+///     host.execute("hello", "wave", &mut state, &mut cursor, &registry),
+///     Err(PluginHostError::Failed { .. })
+/// ));
+/// assert_eq!(
+///     host.execute("other", "greet", &mut state, &mut cursor, &registry),
+///     Err(PluginHostError::NotLoaded("other".to_string()))
+/// );
+/// assert_eq!(state.buffer().content(), "Hello!");
+/// ```
+#[derive(Default)]
+pub struct PluginHost {
+    plugins: Vec<LoadedPlugin>,
+}
+
+impl PluginHost {
+    /// Creates a host with no plugins loaded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads `plugin`, registering its keybindings in `registry`
+    ///
+    /// A plugin with a manifest is checked first: the manifest must name the
+    /// plugin and be [valid](PluginManifest::validate) for this editor's
+    /// plugin API version. Its default bindings are registered before the
+    /// plugin's own.
+    ///
+    /// Bindings are registered with plugin priority and may only run the
+    /// plugin's own commands. If registration fails, the bindings registered
+    /// so far are removed and the plugin is not loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is invalid or taken, if the manifest is
+    /// incompatible, or if registration fails or panics.
+    pub fn load(
+        &mut self,
+        mut plugin: Box<dyn Plugin>,
+        registry: &mut KeyBindingRegistry,
+    ) -> Result<(), PluginHostError> {
+        let name = call_plugin(|| plugin.name().to_string()).map_err(|message| {
+            PluginHostError::Panicked {
+                plugin: "<unnamed>".to_string(),
+                message,
+            }
+        })?;
+        if name.is_empty()
+            || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(PluginHostError::InvalidName(name));
+        }
+        if self.get(&name).is_some() {
+            return Err(PluginHostError::AlreadyLoaded(name));
+        }
+
+        let manifest = call_plugin(|| plugin.manifest()).map_err(|message| {
+            PluginHostError::Panicked {
+                plugin: name.clone(),
+                message,
+            }
+        })?;
+        if let Some(manifest) = &manifest {
+            if manifest.name != name {
+                return Err(PluginHostError::ManifestName {
+                    plugin: name,
+                    manifest: manifest.name.clone(),
+                });
+            }
+            manifest
+                .validate()
+                .map_err(|source| PluginHostError::Incompatible {
+                    plugin: name.clone(),
+                    source,
+                })?;
+        }
+
+        let mut registrar = PluginRegistrar {
+            plugin: &name,
+            registry,
+        };
+        // The manifest's default bindings come after the plugin's own, so
+        // they can use the modes the plugin registers
+        let result = match call_plugin(|| plugin.register(&mut registrar)) {
+            Ok(Ok(())) => Ok(registrar.register_defaults(manifest.as_ref())),
+            other => other,
+        };
+        let error = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(source)) => Some(PluginHostError::Registration {
+                plugin: name.clone(),
+                source,
+            }),
+            Err(message) => Some(PluginHostError::Panicked {
+                plugin: name.clone(),
+                message,
+            }),
+        };
+        let subscriptions = match error {
+            Some(error) => Err(error),
+            None => call_plugin(|| {
+                plugin
+                    .event_subscriber()
+                    .map(|subscriber| subscriber.subscriptions())
+                    .unwrap_or_default()
+            })
+            .map_err(|message| PluginHostError::Panicked {
+                plugin: name.clone(),
+                message,
+            }),
+        };
+        let subscriptions = match subscriptions {
+            Ok(subscriptions) => subscriptions,
+            Err(error) => {
+                registry.unregister_plugin(&name);
+                return Err(error);
+            }
+        };
+
+        self.plugins.push(LoadedPlugin {
+            name,
+            plugin,
+            manifest,
+            subscriptions,
+            panicked: None,
+        });
+        Ok(())
+    }
+
+    /// Loads each of `plugins`, returning a warning for each that failed
+    pub fn load_all(
+        &mut self,
+        plugins: Vec<Box<dyn Plugin>>,
+        registry: &mut KeyBindingRegistry,
+    ) -> Vec<String> {
+        plugins
+            .into_iter()
+            .filter_map(|plugin| self.load(plugin, registry).err())
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    /// Runs `command_name` of the plugin `plugin_name` on the active buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not loaded or disabled, or if the
+    /// command fails or panics. A panic also disables the plugin.
+    pub fn execute(
+        &mut self,
+        plugin_name: &str,
+        command_name: &str,
+        state: &mut EditorState,
+        cursor: &mut Position,
+        registry: &KeyBindingRegistry,
+    ) -> Result<(), PluginHostError> {
+        self.call(plugin_name, state, cursor, registry, |plugin, context| {
+            plugin.execute(command_name, context)
+        })
+    }
+
+    /// Delivers the result of the picker `picker_id` to the plugin that opened it
+    ///
+    /// # Errors
+    ///
+    /// Same as [`execute`](Self::execute).
+    pub fn picker_result(
+        &mut self,
+        plugin_name: &str,
+        picker_id: &str,
+        selection: Option<PickerSelection>,
+        state: &mut EditorState,
+        cursor: &mut Position,
+        registry: &KeyBindingRegistry,
+    ) -> Result<(), PluginHostError> {
+        self.call(plugin_name, state, cursor, registry, |plugin, context| {
+            plugin.picker_result(picker_id, selection, context)
+        })
+    }
+
+    /// Delivers the events published to `state` to the subscribed plugins
+    ///
+    /// Called by the event loop after each command. Events are delivered in
+    /// the order they were published; each one reaches the subscribed
+    /// plugins in load order before the next one is delivered. Disabled
+    /// plugins are skipped.
+    ///
+    /// Handlers never run nested: the events a handler causes are queued
+    /// behind the others and not delivered to the plugin that caused them.
+    /// At most [`MAX_CASCADED_EVENTS`] such events are delivered per call, so
+    /// plugins reacting to each other's events cannot loop forever. Each
+    /// handler's edits are a single undo step.
+    ///
+    /// Returns the errors of failed handlers, in the order they occurred.
+    pub fn dispatch_events(
+        &mut self,
+        state: &mut EditorState,
+        cursor: &mut Position,
+        registry: &KeyBindingRegistry,
+    ) -> Vec<PluginHostError> {
+        // Each event with the plugin whose handler caused it, if any
+        let mut queue: VecDeque<(EditorEvent, Option<usize>)> =
+            state.take_events().into_iter().map(|event| (event, None)).collect();
+        let mut cascaded = 0;
+        let mut errors = Vec::new();
+
+        while let Some((event, origin)) = queue.pop_front() {
+            let kind = event.kind();
+            for index in 0..self.plugins.len() {
+                let loaded = &mut self.plugins[index];
+                if origin == Some(index)
+                    || loaded.panicked.is_some()
+                    || !loaded.subscriptions.contains(&kind)
+                {
+                    continue;
+                }
+
+                state.begin_undo_group();
+                let result = call_loaded(loaded, state, cursor, registry, |plugin, context| {
+                    match plugin.event_subscriber() {
+                        Some(subscriber) => subscriber.on_event(&event, context),
+                        None => Ok(()),
+                    }
+                });
+                state.end_undo_group();
+                errors.extend(result.err());
+
+                for caused in state.take_events() {
+                    if cascaded == MAX_CASCADED_EVENTS {
+                        errors.push(PluginHostError::TooManyEvents {
+                            plugin: self.plugins[index].name.clone(),
+                        });
+                        queue.clear();
+                        break;
+                    }
+                    cascaded += 1;
+                    queue.push_back((caused, Some(index)));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Calls into the loaded plugin `plugin_name`, see [`call_loaded`]
+    fn call(
+        &mut self,
+        plugin_name: &str,
+        state: &mut EditorState,
+        cursor: &mut Position,
+        registry: &KeyBindingRegistry,
+        f: impl FnOnce(&mut dyn Plugin, &mut CommandContext<'_>) -> Result<(), PluginError>,
+    ) -> Result<(), PluginHostError> {
+        let loaded = self
+            .plugins
+            .iter_mut()
+            .find(|loaded| loaded.name == plugin_name)
+            .ok_or_else(|| PluginHostError::NotLoaded(plugin_name.to_string()))?;
+        call_loaded(loaded, state, cursor, registry, f)
+    }
+
+    /// Returns the names of the loaded plugins in load order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.plugins.iter().map(|loaded| loaded.name.as_str())
+    }
+
+    /// Returns the number of loaded plugins
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    /// Returns `true` if no plugin is loaded
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Returns the manifest of the loaded plugin `name`, if it has one
+    pub fn manifest(&self, name: &str) -> Option<&PluginManifest> {
+        self.get(name)?.manifest.as_ref()
+    }
+
+    /// Returns the commands declared in the manifests of the plugins that are
+    /// not disabled, in load order
+    pub fn commands(&self) -> Vec<EditorCommand> {
+        self.plugins
+            .iter()
+            .filter(|loaded| loaded.panicked.is_none())
+            .flat_map(|loaded| {
+                let commands = loaded.manifest.iter().flat_map(|manifest| &manifest.commands);
+                commands.map(|command| EditorCommand::PluginCommand {
+                    plugin_name: loaded.name.clone(),
+                    command_name: command.name.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the plugin `name` was disabled after a panic
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|loaded| loaded.panicked.is_some())
+    }
+
+    fn get(&self, name: &str) -> Option<&LoadedPlugin> {
+        self.plugins.iter().find(|loaded| loaded.name == name)
+    }
+}
+
+/// Registry handed to a plugin while it registers its modes and bindings
+///
+/// Rejects bindings to commands outside the plugin's namespace, including
+/// built-in commands that happen to share it (e.g. `file.save` for a plugin
+/// named `file`), and modes outside it.
+struct PluginRegistrar<'a> {
+    plugin: &'a str,
+    registry: &'a mut KeyBindingRegistry,
+}
+
+impl PluginRegistrar<'_> {
+    /// Registers the default bindings of `manifest`
+    fn register_defaults(&mut self, manifest: Option<&PluginManifest>) -> Result<(), BindingError> {
+        for binding in manifest.iter().flat_map(|manifest| &manifest.bindings) {
+            let binding = plugin_binding(
+                self.plugin,
+                binding.keys.clone(),
+                binding.command.clone(),
+                binding.mode.as_deref(),
+            )?;
+            self.register_keybinding(binding)?;
+        }
+        Ok(())
+    }
+}
+
+impl PluginInputExtension for PluginRegistrar<'_> {
+    fn register_keybinding(&mut self, binding: PluginBinding) -> Result<(), BindingError> {
+        let owned = matches!(
+            EditorCommand::from_str(&binding.command),
+            Ok(EditorCommand::PluginCommand { plugin_name, .. }) if plugin_name == self.plugin
+        );
+        if !owned {
+            return Err(BindingError::ForeignCommand {
+                command: binding.command,
+                plugin: self.plugin.to_string(),
+            });
+        }
+        self.registry.register_keybinding(binding)
+    }
+
+    fn register_mode(&mut self, mode: ApiPluginMode) -> Result<(), BindingError> {
+        let owned = mode
+            .name
+            .split_once('.')
+            .is_some_and(|(plugin, _)| plugin == self.plugin);
+        if !owned {
+            return Err(BindingError::ForeignMode {
+                mode: mode.name,
+                plugin: self.plugin.to_string(),
+            });
+        }
+        self.registry.register_mode(mode)
+    }
+}
+
+/// Calls into `loaded` with a context for the active buffer
+///
+/// UI requests are applied even if the call fails.
+fn call_loaded(
+    loaded: &mut LoadedPlugin,
+    state: &mut EditorState,
+    cursor: &mut Position,
+    registry: &KeyBindingRegistry,
+    f: impl FnOnce(&mut dyn Plugin, &mut CommandContext<'_>) -> Result<(), PluginError>,
+) -> Result<(), PluginHostError> {
+    if let Some(message) = &loaded.panicked {
+        return Err(PluginHostError::Disabled {
+            plugin: loaded.name.clone(),
+            message: message.clone(),
+        });
+    }
+
+    let mut buffer = ActiveBuffer::new(state, cursor);
+    let mut context = CommandContext::new(&mut buffer);
+    let result = call_plugin(|| f(loaded.plugin.as_mut(), &mut context));
+    let requests = context.take_ui_requests();
+    // Edits may have removed the text under the cursor
+    *cursor = state.buffer().clamp_position(*cursor);
+    apply_ui_requests(state, &loaded.name, requests, registry);
+
+    match result {
+        Ok(result) => result.map_err(|source| PluginHostError::Failed {
+            plugin: loaded.name.clone(),
+            source,
+        }),
+        Err(message) => {
+            loaded.panicked = Some(message.clone());
+            Err(PluginHostError::Panicked {
+                plugin: loaded.name.clone(),
+                message,
+            })
+        }
+    }
+}
+
+/// Applies the UI requests of a command run by `plugin`
+fn apply_ui_requests(
+    state: &mut EditorState,
+    plugin: &str,
+    requests: Vec<UiRequest>,
+    registry: &KeyBindingRegistry,
+) {
+    for request in requests {
+        match request {
+            UiRequest::Message { severity, text } => state.set_status(severity, text),
+            UiRequest::StatusSegment { key, text } => state.set_status_segment(plugin, &key, text),
+            UiRequest::OpenPicker(picker) => {
+                let kind = PromptKind::PluginPicker {
+                    plugin: plugin.to_string(),
+                    id: picker.id,
+                };
+                state.enter_list_prompt(kind, format!("{}: ", picker.title), picker.items);
+            }
+            UiRequest::SetMode(mode) => match registry.resolve_mode(mode) {
+                Ok(EditorMode::Prompt) => state.set_status(
+                    Severity::Error,
+                    format!("Plugin '{}' cannot enter prompt mode", plugin),
+                ),
+                Ok(mode) => {
+                    // Leaving an open prompt closes it
+                    if state.mode() == EditorMode::Prompt {
+                        state.cancel_prompt();
+                    }
+                    state.set_mode(mode);
+                }
+                Err(e) => state.set_status(
+                    Severity::Error,
+                    format!("Plugin '{}' switched to unknown mode '{}'", plugin, e.invalid),
+                ),
+            },
+        }
+    }
+}
+
+/// Runs plugin code, turning a panic into its message
+fn call_plugin<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let was_running = RUNNING_PLUGIN.with(|running| running.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    RUNNING_PLUGIN.with(|running| running.set(was_running));
+    result.map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Extracts the message of a panic payload
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
@@ -0,0 +1,166 @@
+//! Reading plugin manifests
+//!
+//! WebAssembly plugins ship their manifest as a TOML file next to the
+//! module, with the same file stem (`hello.toml` for `hello.wat`). Process
+//! plugins return it in the `manifest` field of their `initialize` result.
+//! Both use the same fields:
+//!
+//! ```toml
+//! name = "hello"
+//! version = "1.0.0"
+//! api_version = "0.1"
+//! capabilities = ["buffer-read", "buffer-edit"]
+//!
+//! [[commands]]
+//! name = "greet"
+//! description = "Insert a greeting at the cursor"
+//!
+//! [[bindings]]
+//! keys = "Ctrl+Alt+h"
+//! command = "greet"
+//! mode = "insert"   # optional; bindings without a mode are global
+//! ```
+//!
+//! The capabilities a manifest lists must be granted in the config, or the
+//! plugin is not loaded. See
+//! [`PluginManifest`](termide_plugin_api::manifest::PluginManifest) for the
+//! version checks.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use termide_plugin_api::manifest::{BindingManifest, CommandManifest, PluginManifest};
+use thiserror::Error;
+
+use super::Capability;
+
+/// Error type for reading a manifest and checking its capabilities
+#[derive(Debug, Error)]
+pub enum ManifestFileError {
+    /// The manifest file could not be read
+    #[error("failed to read manifest '{path}': {source}")]
+    Read { path: String, source: io::Error },
+
+    /// The manifest is not valid TOML, or lacks required fields
+    #[error("invalid manifest '{path}': {message}")]
+    Parse { path: String, message: String },
+
+    /// The manifest lists a capability that doesn't exist
+    #[error("manifest requires unknown capability '{0}'")]
+    UnknownCapability(String),
+
+    /// The manifest lists a capability the config doesn't grant
+    #[error("manifest requires the '{0}' capability, which is not granted")]
+    NotGranted(Capability),
+}
+
+/// A manifest as written in a manifest file or protocol message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub name: String,
+    pub version: String,
+    pub api_version: String,
+    #[serde(default)]
+    pub commands: Vec<ManifestCommand>,
+    #[serde(default)]
+    pub bindings: Vec<ManifestBinding>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+/// A `[[commands]]` entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestCommand {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A `[[bindings]]` entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestBinding {
+    pub keys: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+impl From<ManifestFile> for PluginManifest {
+    fn from(file: ManifestFile) -> Self {
+        PluginManifest {
+            name: file.name,
+            version: file.version,
+            api_version: file.api_version,
+            commands: file
+                .commands
+                .into_iter()
+                .map(|command| CommandManifest::new(command.name, command.description))
+                .collect(),
+            bindings: file
+                .bindings
+                .into_iter()
+                .map(|binding| BindingManifest {
+                    keys: binding.keys,
+                    command: binding.command,
+                    mode: binding.mode,
+                })
+                .collect(),
+            capabilities: file.capabilities,
+        }
+    }
+}
+
+/// Reads the manifest file at `path`
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs;
+/// use termide::plugin::load_manifest;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("hello.toml");
+/// fs::write(&path, "name = \"hello\"\nversion = \"1.0.0\"\napi_version = \"0.1\"\n").unwrap();
+///
+/// let manifest = load_manifest(&path).unwrap();
+/// assert_eq!(manifest.name, "hello");
+/// assert!(manifest.commands.is_empty());
+/// ```
+pub fn load_manifest(path: &Path) -> Result<PluginManifest, ManifestFileError> {
+    let content = fs::read_to_string(path).map_err(|source| ManifestFileError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let file: ManifestFile = toml::from_str(&content).map_err(|e| ManifestFileError::Parse {
+        path: path.display().to_string(),
+        message: e.message().to_string(),
+    })?;
+    Ok(file.into())
+}
+
+/// Checks that every capability `manifest` requires is in `granted`
+///
+/// # Errors
+///
+/// Returns an error for the first capability that is unknown or not granted.
+pub fn check_capabilities(
+    manifest: &PluginManifest,
+    granted: &BTreeSet<Capability>,
+) -> Result<(), ManifestFileError> {
+    for name in &manifest.capabilities {
+        let capability: Capability = name
+            .parse()
+            .map_err(|_| ManifestFileError::UnknownCapability(name.clone()))?;
+        if !granted.contains(&capability) {
+            return Err(ManifestFileError::NotGranted(capability));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! Plugin module - runs plugins written against `termide-plugin-api`
+//!
+//! This module provides:
+//! - **Plugin host**: Loads plugins, registers their keybindings and dispatches
+//!   `EditorCommand::PluginCommand` to the owning plugin
+//! - **Isolation**: Plugin errors and panics are reported instead of crashing
+//!   the editor
+//! - **Buffer API**: [`ActiveBuffer`] exposes the active buffer to running
+//!   plugin commands
+//! - **Manifests**: plugins describe their version, the plugin API version
+//!   they target, commands, default bindings and capabilities; incompatible
+//!   plugins are not loaded (see [`load_manifest`])
+//! - **Events**: [`PluginHost::dispatch_events`] delivers the editor events
+//!   published to the [`EventBus`](crate::editor::EventBus) to subscribed plugins
+//! - **Sandbox**: [`WasmPlugin`] runs WebAssembly plugins with the
+//!   [`Capability`]s granted in the config
+//! - **Processes**: [`ProcessPlugin`] runs plugins written in any language as
+//!   separate processes, speaking the JSON-RPC [`protocol`]
+//!
+//! # Discovery
+//!
+//! In-process plugins are compiled into the editor and listed by
+//! [`builtin_plugins`]. They are loaded at startup, after the default
+//! bindings and before the user configuration, so user bindings override
+//! plugin bindings.
+//!
+//! WebAssembly plugins are loaded from [`get_plugins_dir`] after the builtin
+//! plugins, in file name order. See [`WasmPlugin`] for the module interface.
+//! Process plugins are started last, from the entries of the `[plugins]`
+//! config section that have a `command`. [`discover_plugins`] finds all three.
+//!
+//! # Examples
+//!
+//! ```
+//! use termide::input::registry::KeyBindingRegistry;
+//! use termide::plugin::{builtin_plugins, PluginHost};
+//!
+//! let mut registry = KeyBindingRegistry::default();
+//! let mut host = PluginHost::new();
+//! let warnings = host.load_all(builtin_plugins(), &mut registry);
+//! assert!(warnings.is_empty());
+//! ```
+
+mod buffer;
+mod capability;
+mod host;
+mod manifest;
+mod process;
+pub mod protocol;
+mod wasm;
+
+pub use buffer::ActiveBuffer;
+pub use capability::{Capability, PluginGrants};
+pub use host::{is_plugin_running, PluginHost, PluginHostError, MAX_CASCADED_EVENTS};
+pub use manifest::{
+    check_capabilities, load_manifest, ManifestBinding, ManifestCommand, ManifestFile,
+    ManifestFileError,
+};
+pub use process::{
+    ProcessPlugin, ProcessPluginError, ProcessSpec, MAX_RESTART_DELAY, RESPONSE_TIMEOUT,
+    RESTART_DELAY,
+};
+pub use wasm::{load_wasm_plugins, WasmPlugin, WasmPluginError, FUEL_PER_CALL, MEMORY_LIMIT};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use termide_plugin_api::input::{BindingError, EditorMode, PluginBinding, PluginBindingBuilder};
+use termide_plugin_api::plugin::Plugin;
+
+use crate::input::config::{ConfigError, KeybindingConfig};
+
+/// Returns the plugins compiled into the editor
+///
+/// New in-process plugins are added to this list.
+pub fn builtin_plugins() -> Vec<Box<dyn Plugin>> {
+    Vec::new()
+}
+
+/// Returns the directory WebAssembly plugins are loaded from
+///
+/// This is `termide/plugins` in the platform config directory, e.g.
+/// `~/.config/termide/plugins` on Linux.
+pub fn get_plugins_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|mut path| {
+        path.push("termide");
+        path.push("plugins");
+        path
+    })
+}
+
+/// Finds the builtin plugins, the WebAssembly plugins in `plugins_dir` and the
+/// process plugins configured in the config file at `config`
+///
+/// Returns the plugins in load order, and a warning for each plugin or config
+/// entry that could not be used.
+pub fn discover_plugins(
+    config: Option<&Path>,
+    plugins_dir: Option<&Path>,
+) -> (Vec<Box<dyn Plugin>>, Vec<String>) {
+    let mut plugins = builtin_plugins();
+    let mut warnings = Vec::new();
+
+    let section = match config.map(plugins_section) {
+        Some(Ok(section)) => section,
+        Some(Err(e)) => {
+            warnings.push(e.to_string());
+            toml::Table::new()
+        }
+        None => toml::Table::new(),
+    };
+    let (grants, grant_warnings) = PluginGrants::parse(&section);
+    warnings.extend(grant_warnings);
+
+    if let Some(dir) = plugins_dir {
+        let (wasm_plugins, wasm_warnings) = load_wasm_plugins(dir, &grants);
+        plugins.extend(wasm_plugins);
+        warnings.extend(wasm_warnings);
+    }
+
+    let (specs, spec_warnings) = ProcessSpec::parse_all(&section);
+    warnings.extend(spec_warnings);
+    for spec in specs {
+        let name = spec.name.clone();
+        match ProcessPlugin::spawn(spec, grants.capabilities(&name)) {
+            Ok(plugin) => plugins.push(Box::new(plugin)),
+            Err(e) => warnings.push(format!("Plugin '{}' not loaded: {}", name, e)),
+        }
+    }
+
+    (plugins, warnings)
+}
+
+/// Reads the `[plugins]` section of the config file at `path`
+///
+/// A missing file has an empty section.
+fn plugins_section(path: &Path) -> Result<toml::Table, ConfigError> {
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+    let content = fs::read_to_string(path).map_err(|source| ConfigError::ReadError {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let config: KeybindingConfig =
+        toml::from_str(&content).map_err(|source| ConfigError::TomlParseError {
+            path: path.display().to_string(),
+            source,
+        })?;
+    Ok(config.plugins)
+}
+
+/// Builds a binding of `plugin` from keys, command and mode names
+///
+/// `None` binds globally. Plugin mode names without a dot are namespaced with
+/// the plugin name. Unlike [`PluginBindingBuilder::in_mode`], which falls back
+/// to Normal mode, invalid mode names are an error; modes that are not
+/// registered are rejected when the binding is registered.
+fn plugin_binding(
+    plugin: &str,
+    keys: String,
+    command: String,
+    mode: Option<&str>,
+) -> Result<PluginBinding, BindingError> {
+    let builder = PluginBindingBuilder::new(plugin).bind(keys, command);
+    match mode {
+        Some(mode) => EditorMode::from_str(mode)
+            .or_else(|e| EditorMode::from_str(&format!("{}.{}", plugin, mode.trim())).map_err(|_| e))
+            .and_then(|_| builder.in_mode(mode).build()),
+        None => builder.global().build(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
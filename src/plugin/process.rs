@@ -0,0 +1,765 @@
+//! Out-of-process plugins speaking the JSON-RPC protocol
+//!
+//! Process plugins are configured in the `[plugins]` section of the config
+//! file, with the program to run and its arguments:
+//!
+//! ```toml
+//! [plugins.shout]
+//! command = ["python3", "/home/me/termide/shout.py"]
+//! capabilities = ["buffer-read", "buffer-edit"]
+//! ```
+//!
+//! See [`protocol`](super::protocol) for the messages they exchange with the
+//! editor.
+
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use termide_plugin_api::event::{EditorEvent, EventKind, EventSubscriber};
+use termide_plugin_api::input::{BindingError, EditorMode, PluginBinding, PluginInputExtension, PluginMode};
+use termide_plugin_api::manifest::PluginManifest;
+use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+use termide_plugin_api::ui::{Picker, PickerSelection};
+use thiserror::Error;
+
+use super::protocol::{
+    event_data, required_capability, InitializeResult, Message, RpcError, RpcPosition, RpcRange, RpcSeverity,
+    CAPABILITY_DENIED, INVALID_PARAMS, METHOD_NOT_FOUND, NOT_RUNNING, PROTOCOL_VERSION,
+    UNKNOWN_COMMAND,
+};
+use super::{check_capabilities, plugin_binding, Capability, ManifestFileError};
+
+/// How long the editor waits for the response to a request
+pub const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Messages queued for a process that isn't reading its input before it is
+/// treated as crashed
+const QUEUED_MESSAGES: usize = 256;
+
+/// Delay before restarting a plugin after its first crash
+pub const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between restarts of a plugin that keeps crashing
+pub const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// A process plugin from the `[plugins]` config section
+///
+/// # Examples
+///
+/// ```
+/// use termide::plugin::ProcessSpec;
+///
+/// let table: toml::Table = toml::from_str(r#"
+///     [shout]
+///     command = ["python3", "shout.py"]
+///
+///     [word-count]
+///     capabilities = ["buffer-read"]
+/// "#).unwrap();
+/// let (specs, warnings) = ProcessSpec::parse_all(&table);
+///
+/// assert!(warnings.is_empty());
+/// assert_eq!(specs, vec![ProcessSpec::new("shout", vec!["python3".into(), "shout.py".into()])]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessSpec {
+    /// Plugin name, the key of the config entry
+    pub name: String,
+    /// Program to run, followed by its arguments
+    pub command: Vec<String>,
+}
+
+impl ProcessSpec {
+    /// Creates a spec running `command`
+    pub fn new(name: impl Into<String>, command: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            command,
+        }
+    }
+
+    /// Returns the entries of the `[plugins]` section that have a `command`
+    ///
+    /// `command` is a program name or a list of the program and its
+    /// arguments. Invalid commands are skipped with a warning.
+    pub fn parse_all(table: &toml::Table) -> (Vec<Self>, Vec<String>) {
+        let mut specs = Vec::new();
+        let mut warnings = Vec::new();
+
+        for (name, entry) in table {
+            let Some(command) = entry.as_table().and_then(|entry| entry.get("command")) else {
+                continue;
+            };
+            let command: Option<Vec<String>> = match command {
+                toml::Value::String(program) => Some(vec![program.clone()]),
+                toml::Value::Array(items) => items
+                    .iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect(),
+                _ => None,
+            };
+            match command {
+                Some(command) if !command.is_empty() && !command[0].is_empty() => {
+                    specs.push(Self::new(name.clone(), command))
+                }
+                _ => warnings.push(format!(
+                    "plugins.{}: 'command' must be a program or a non-empty list of strings",
+                    name
+                )),
+            }
+        }
+
+        (specs, warnings)
+    }
+}
+
+/// Error type for process plugins
+#[derive(Debug, Error)]
+pub enum ProcessPluginError {
+    /// The program could not be started
+    #[error("failed to start '{program}': {source}")]
+    Spawn { program: String, source: io::Error },
+
+    /// The process exited or closed its output
+    #[error("plugin process exited")]
+    Exited,
+
+    /// The process didn't answer a request in time
+    #[error("plugin process did not answer within {} ms", .0.as_millis())]
+    Timeout(Duration),
+
+    /// The process stopped reading the messages sent to it
+    #[error("plugin process stopped reading its input")]
+    Stalled,
+
+    /// The process sent something that isn't a valid protocol message
+    #[error("protocol error: {0}")]
+    Protocol(String),
+
+    /// The process answered a request with an error
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+
+    /// The process crashed recently and is waiting to be restarted
+    #[error("plugin process crashed; restarting in {} ms", .0.as_millis())]
+    Restarting(Duration),
+
+    /// A binding from `initialize` is invalid
+    #[error(transparent)]
+    Binding(#[from] BindingError),
+
+    /// The manifest from `initialize` requires capabilities that aren't granted
+    #[error(transparent)]
+    Manifest(#[from] ManifestFileError),
+}
+
+impl ProcessPluginError {
+    /// Returns `true` if the process has to be restarted after this error
+    fn is_crash(&self) -> bool {
+        matches!(
+            self,
+            ProcessPluginError::Exited
+                | ProcessPluginError::Timeout(_)
+                | ProcessPluginError::Stalled
+                | ProcessPluginError::Protocol(_)
+        )
+    }
+}
+
+/// Delay before restarting a crashed process, doubling with each crash in a row
+#[derive(Debug, Clone)]
+pub(crate) struct Backoff {
+    initial: Duration,
+    max: Duration,
+    crashes: u32,
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    pub(crate) fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            crashes: 0,
+            retry_at: None,
+        }
+    }
+
+    /// Records a crash at `now` and returns the delay before the next restart
+    pub(crate) fn crashed(&mut self, now: Instant) -> Duration {
+        let delay = self
+            .initial
+            .saturating_mul(2u32.saturating_pow(self.crashes))
+            .min(self.max);
+        self.crashes = self.crashes.saturating_add(1);
+        self.retry_at = Some(now + delay);
+        delay
+    }
+
+    /// Returns how long to wait at `now` before restarting, if at all
+    pub(crate) fn remaining(&self, now: Instant) -> Option<Duration> {
+        let remaining = self.retry_at?.checked_duration_since(now)?;
+        (!remaining.is_zero()).then_some(remaining)
+    }
+
+    /// Forgets earlier crashes once the process works again
+    pub(crate) fn reset(&mut self) {
+        self.crashes = 0;
+        self.retry_at = None;
+    }
+}
+
+/// A running plugin process
+struct Connection {
+    child: Child,
+    /// Lines written to the process input by a writer thread
+    input: SyncSender<String>,
+    /// Messages read from the process output by a reader thread
+    messages: Receiver<Result<Message, String>>,
+    next_id: u64,
+}
+
+impl Connection {
+    /// Starts `command` with piped standard input and output
+    fn spawn(command: &[String]) -> Result<Self, ProcessPluginError> {
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| ProcessPluginError::Spawn {
+                program: String::new(),
+                source: io::Error::new(io::ErrorKind::InvalidInput, "empty command"),
+            })?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|source| ProcessPluginError::Spawn {
+                program: program.clone(),
+                source,
+            })?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        // Writing to a process that doesn't read would block the editor, so
+        // the writes happen on their own thread
+        let (input, lines) = mpsc::sync_channel(QUEUED_MESSAGES);
+        thread::spawn(move || write_lines(stdin, lines));
+
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let message = serde_json::from_str(&line)
+                    .map_err(|e| format!("invalid message '{}': {}", line, e));
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            input,
+            messages,
+            next_id: 1,
+        })
+    }
+
+    fn send(&mut self, message: &Message) -> Result<(), ProcessPluginError> {
+        let mut line = serde_json::to_string(message).expect("messages serialize to JSON");
+        line.push('\n');
+        self.input.try_send(line).map_err(|e| match e {
+            TrySendError::Full(_) => ProcessPluginError::Stalled,
+            TrySendError::Disconnected(_) => ProcessPluginError::Exited,
+        })
+    }
+
+    /// Handles a request or notification from the process with `serve`
+    fn answer(
+        &mut self,
+        message: Message,
+        serve: &mut dyn FnMut(&str, Value) -> Result<Value, RpcError>,
+    ) -> Result<(), ProcessPluginError> {
+        let method = message.method.as_deref().unwrap_or_default();
+        let result = serve(method, message.params.unwrap_or(Value::Null));
+        match message.id {
+            Some(id) => self.send(&Message::response(id, result)),
+            None => Ok(()),
+        }
+    }
+
+    /// Answers the messages the process sent while no command was running
+    fn drain(&mut self) -> Result<(), ProcessPluginError> {
+        while let Ok(message) = self.messages.try_recv() {
+            let message = message.map_err(ProcessPluginError::Protocol)?;
+            if message.method.is_some() {
+                self.answer(message, &mut |_, _| Err(not_running()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a request and waits at most `timeout` for its response, handling
+    /// the requests the process makes in the meantime with `serve`
+    fn request(
+        &mut self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+        serve: &mut dyn FnMut(&str, Value) -> Result<Value, RpcError>,
+    ) -> Result<Value, ProcessPluginError> {
+        self.drain()?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&Message::request(id, method, params))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let message = match self.messages.recv_timeout(remaining) {
+                Ok(message) => message.map_err(ProcessPluginError::Protocol)?,
+                Err(RecvTimeoutError::Timeout) => return Err(ProcessPluginError::Timeout(timeout)),
+                Err(RecvTimeoutError::Disconnected) => return Err(ProcessPluginError::Exited),
+            };
+            if message.method.is_some() {
+                self.answer(message, serve)?;
+            } else if message.id == Some(id.into()) {
+                return match message.error {
+                    Some(error) => Err(error.into()),
+                    None => Ok(message.result.unwrap_or(Value::Null)),
+                };
+            }
+        }
+    }
+}
+
+/// Writes each line from `lines` to `stdin` until the process exits
+fn write_lines(mut stdin: ChildStdin, lines: Receiver<String>) {
+    for line in lines {
+        if stdin
+            .write_all(line.as_bytes())
+            .and_then(|()| stdin.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A plugin running as a separate process
+///
+/// The process is started when the plugin is created. If it crashes, it is
+/// restarted the next time a command runs, after a delay that doubles with
+/// each crash in a row. Keybindings, modes and the manifest are read once,
+/// from the first `initialize`; a restarted process keeps them.
+pub struct ProcessPlugin {
+    spec: ProcessSpec,
+    capabilities: BTreeSet<Capability>,
+    connection: Option<Connection>,
+    bindings: Vec<PluginBinding>,
+    modes: Vec<PluginMode>,
+    manifest: Option<PluginManifest>,
+    /// Events the process subscribed to in its last `initialize`
+    events: BTreeSet<String>,
+    timeout: Duration,
+    backoff: Backoff,
+}
+
+impl ProcessPlugin {
+    /// Starts the plugin process and reads its keybindings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be started, doesn't answer
+    /// `initialize` correctly, returns invalid bindings, or its manifest
+    /// requires capabilities that aren't granted.
+    pub fn spawn(
+        spec: ProcessSpec,
+        capabilities: BTreeSet<Capability>,
+    ) -> Result<Self, ProcessPluginError> {
+        Self::with_timeout(spec, capabilities, RESPONSE_TIMEOUT)
+    }
+
+    /// Starts the plugin process, waiting at most `timeout` for the response to
+    /// each request
+    ///
+    /// # Errors
+    ///
+    /// Returns an error as [`ProcessPlugin::spawn`].
+    pub fn with_timeout(
+        spec: ProcessSpec,
+        capabilities: BTreeSet<Capability>,
+        timeout: Duration,
+    ) -> Result<Self, ProcessPluginError> {
+        let mut plugin = Self {
+            spec,
+            capabilities,
+            connection: None,
+            bindings: Vec::new(),
+            modes: Vec::new(),
+            manifest: None,
+            events: BTreeSet::new(),
+            timeout,
+            backoff: Backoff::new(RESTART_DELAY, MAX_RESTART_DELAY),
+        };
+        let result = plugin.start()?;
+        if let Some(manifest) = result.manifest {
+            let manifest = PluginManifest::from(manifest);
+            check_capabilities(&manifest, &plugin.capabilities)?;
+            plugin.manifest = Some(manifest);
+        }
+        plugin.modes = result
+            .modes
+            .into_iter()
+            .map(|mode| {
+                let plugin_mode = PluginMode::new(&plugin.spec.name, &mode.name);
+                match mode.label {
+                    Some(label) => plugin_mode.with_label(label),
+                    None => plugin_mode,
+                }
+            })
+            .collect();
+        plugin.bindings = result
+            .bindings
+            .into_iter()
+            .map(|binding| {
+                plugin_binding(
+                    &plugin.spec.name,
+                    binding.keys,
+                    binding.command,
+                    binding.mode.as_deref(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(plugin)
+    }
+
+    /// Sets the delay before the first restart and the longest delay
+    pub fn set_restart_delays(&mut self, initial: Duration, max: Duration) {
+        self.backoff = Backoff::new(initial, max);
+    }
+
+    /// Returns `true` if the process is running
+    pub fn is_running(&mut self) -> bool {
+        self.connection
+            .as_mut()
+            .is_some_and(|connection| matches!(connection.child.try_wait(), Ok(None)))
+    }
+
+    /// Returns the process id, if the process is running
+    pub fn process_id(&self) -> Option<u32> {
+        self.connection
+            .as_ref()
+            .map(|connection| connection.child.id())
+    }
+
+    /// Returns `true` if the process subscribed to the event `name`
+    pub fn is_subscribed(&self, name: &str) -> bool {
+        self.events.contains(name)
+    }
+
+    /// Sends the event `name` with `data` if the process subscribed to it
+    ///
+    /// Events for a crashed process are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process has exited or stopped reading its
+    /// input.
+    pub fn notify_event(&mut self, name: &str, data: Value) -> Result<(), ProcessPluginError> {
+        if !self.is_subscribed(name) {
+            return Ok(());
+        }
+        let Some(connection) = self.connection.as_mut() else {
+            return Ok(());
+        };
+        let result = connection.send(&Message::notification(
+            "event",
+            json!({ "name": name, "data": data }),
+        ));
+        self.check(result)
+    }
+
+    /// Starts the process and sends `initialize`
+    fn start(&mut self) -> Result<InitializeResult, ProcessPluginError> {
+        let mut connection = Connection::spawn(&self.spec.command)?;
+        let params = json!({ "name": self.spec.name, "protocol_version": PROTOCOL_VERSION });
+        let result = connection.request("initialize", params, self.timeout, &mut |_, _| {
+            Err(not_running())
+        })?;
+        let result: InitializeResult = serde_json::from_value(result).map_err(|e| {
+            ProcessPluginError::Protocol(format!("invalid initialize result: {}", e))
+        })?;
+        self.events = result.events.iter().cloned().collect();
+        self.connection = Some(connection);
+        Ok(result)
+    }
+
+    /// Stops a process that crashed and schedules its restart
+    fn check<T>(&mut self, result: Result<T, ProcessPluginError>) -> Result<T, ProcessPluginError> {
+        match &result {
+            Err(e) if e.is_crash() => {
+                self.connection = None;
+                self.backoff.crashed(Instant::now());
+            }
+            Ok(_) => self.backoff.reset(),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Sends a request, serving the process's buffer and UI requests with
+    /// `context`, and restarting the process first if it crashed
+    fn call(
+        &mut self,
+        method: &str,
+        params: Value,
+        context: &mut CommandContext<'_>,
+    ) -> Result<Value, ProcessPluginError> {
+        if self.connection.is_none() {
+            if let Some(remaining) = self.backoff.remaining(Instant::now()) {
+                return Err(ProcessPluginError::Restarting(remaining));
+            }
+            let started = self.start();
+            self.check(started)?;
+        }
+
+        let plugin = &self.spec.name;
+        let capabilities = &self.capabilities;
+        let connection = self.connection.as_mut().expect("process is running");
+        let result = connection.request(method, params, self.timeout, &mut |method, params| {
+            serve(plugin, method, params, capabilities, context)
+        });
+        self.check(result)
+    }
+
+    /// Converts the result of a call into the result of a plugin command
+    fn command_result(
+        command: &str,
+        result: Result<Value, ProcessPluginError>,
+    ) -> Result<(), PluginError> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(ProcessPluginError::Rpc(error)) if error.code == UNKNOWN_COMMAND => {
+                Err(PluginError::UnknownCommand(command.to_string()))
+            }
+            Err(ProcessPluginError::Rpc(error)) => Err(PluginError::Failed(error.message)),
+            Err(e) => Err(PluginError::Failed(e.to_string())),
+        }
+    }
+}
+
+impl Plugin for ProcessPlugin {
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn manifest(&self) -> Option<PluginManifest> {
+        self.manifest.clone()
+    }
+
+    fn register(&mut self, registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+        for mode in &self.modes {
+            registry.register_mode(mode.clone())?;
+        }
+        for binding in &self.bindings {
+            registry.register_keybinding(binding.clone())?;
+        }
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        command: &str,
+        context: &mut CommandContext<'_>,
+    ) -> Result<(), PluginError> {
+        let result = self.call("execute", json!({ "command": command }), context);
+        Self::command_result(command, result)
+    }
+
+    fn picker_result(
+        &mut self,
+        picker: &str,
+        selection: Option<PickerSelection>,
+        context: &mut CommandContext<'_>,
+    ) -> Result<(), PluginError> {
+        let selection =
+            selection.map(|selection| json!({ "index": selection.index, "item": selection.item }));
+        let params = json!({ "picker": picker, "selection": selection });
+        let result = self.call("picker_result", params, context);
+        Self::command_result("picker_result", result)
+    }
+
+    fn event_subscriber(&mut self) -> Option<&mut dyn EventSubscriber> {
+        Some(self)
+    }
+}
+
+/// Forwards editor events as `event` notifications
+///
+/// A restarted process may subscribe to different events, so every kind is
+/// forwarded here and [`notify_event`](ProcessPlugin::notify_event) drops the
+/// ones the running process didn't ask for.
+impl EventSubscriber for ProcessPlugin {
+    fn subscriptions(&self) -> Vec<EventKind> {
+        EventKind::ALL.to_vec()
+    }
+
+    fn on_event(
+        &mut self,
+        event: &EditorEvent,
+        _context: &mut CommandContext<'_>,
+    ) -> Result<(), PluginError> {
+        self.notify_event(event.kind().as_str(), event_data(event))
+            .map_err(|e| PluginError::Failed(e.to_string()))
+    }
+}
+
+/// Error for requests made while no command is running
+fn not_running() -> RpcError {
+    RpcError::new(
+        NOT_RUNNING,
+        "buffer and UI requests are only available while a command runs",
+    )
+}
+
+fn parse<T: DeserializeOwned>(method: &str, params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| {
+        RpcError::new(
+            INVALID_PARAMS,
+            format!("invalid params for '{}': {}", method, e),
+        )
+    })
+}
+
+#[derive(Deserialize)]
+struct LineParams {
+    line: usize,
+}
+
+#[derive(Deserialize)]
+struct InsertParams {
+    position: RpcPosition,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct MessageParams {
+    severity: RpcSeverity,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct StatusSegmentParams {
+    key: String,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PickerParams {
+    id: String,
+    title: String,
+    items: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ModeParams {
+    mode: String,
+}
+
+/// Handles a request of the process of `plugin` while a command runs
+fn serve(
+    plugin: &str,
+    method: &str,
+    params: Value,
+    capabilities: &BTreeSet<Capability>,
+    context: &mut CommandContext<'_>,
+) -> Result<Value, RpcError> {
+    let capability = required_capability(method)
+        .ok_or_else(|| RpcError::new(METHOD_NOT_FOUND, format!("unknown method '{}'", method)))?;
+    if !capabilities.contains(&capability) {
+        return Err(RpcError::new(
+            CAPABILITY_DENIED,
+            format!(
+                "'{}' needs the '{}' capability, which is not granted",
+                method, capability
+            ),
+        ));
+    }
+
+    let buffer = context.buffer();
+    let result = match method {
+        "buffer.line_count" => json!(buffer.line_count()),
+        "buffer.line" => json!(buffer.line(parse::<LineParams>(method, params)?.line)),
+        "buffer.text" => json!(buffer.text(parse::<RpcRange>(method, params)?.into())?),
+        "buffer.cursor" => json!(RpcPosition::from(buffer.cursor())),
+        "buffer.selection" => json!(buffer.selection().map(RpcRange::from)),
+        "buffer.file_path" => json!(buffer.file_path().map(|path| path.display().to_string())),
+        "buffer.is_dirty" => json!(buffer.is_dirty()),
+        "buffer.insert" => {
+            let params: InsertParams = parse(method, params)?;
+            json!(RpcPosition::from(
+                buffer.insert(params.position.into(), &params.text)?
+            ))
+        }
+        "buffer.delete" => {
+            buffer.delete(parse::<RpcRange>(method, params)?.into())?;
+            Value::Null
+        }
+        "buffer.set_cursor" => {
+            buffer.set_cursor(parse::<RpcPosition>(method, params)?.into())?;
+            Value::Null
+        }
+        "buffer.set_selection" => {
+            let range: Option<RpcRange> = parse(method, params)?;
+            buffer.set_selection(range.map(Into::into))?;
+            Value::Null
+        }
+        "ui.message" => {
+            let params: MessageParams = parse(method, params)?;
+            context.show_message(params.severity.into(), params.text);
+            Value::Null
+        }
+        "ui.status_segment" => {
+            let params: StatusSegmentParams = parse(method, params)?;
+            context.set_status_segment(params.key, params.text);
+            Value::Null
+        }
+        "ui.open_picker" => {
+            let params: PickerParams = parse(method, params)?;
+            context.open_picker(Picker::new(params.id, params.title, params.items));
+            Value::Null
+        }
+        "ui.set_mode" => {
+            let params: ModeParams = parse(method, params)?;
+            // Mode names without a namespace are the plugin's own
+            let mode = EditorMode::from_str(&params.mode)
+                .or_else(|_| EditorMode::from_str(&format!("{}.{}", plugin, params.mode.trim())))
+                .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+            context.set_mode(mode);
+            Value::Null
+        }
+        _ => unreachable!("every method has a capability"),
+    };
+    Ok(result)
+}
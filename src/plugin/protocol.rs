@@ -0,0 +1,387 @@
+//! JSON-RPC protocol spoken with out-of-process plugins
+//!
+//! A process plugin is a program, written in any language, that speaks
+//! [JSON-RPC 2.0](https://www.jsonrpc.org/specification) on its standard input
+//! and output, one JSON message per line. Its standard error is discarded.
+//! The editor starts the program when it loads the plugin and kills it when
+//! the editor exits.
+//!
+//! # Lifecycle
+//!
+//! 1. The editor sends `initialize`. The result lists the plugin's keybindings
+//!    and the editor events it subscribes to.
+//! 2. When one of the bindings is pressed, the editor sends `execute`. Until
+//!    it answers, the plugin may query and edit the active buffer with
+//!    `buffer.*` requests and change the UI with `ui.*` requests. All edits of
+//!    one command are a single undo step.
+//! 3. Subscribed events arrive as `event` notifications.
+//!
+//! A plugin that exits, stops answering or writes invalid JSON is killed and
+//! restarted the next time it is needed, waiting longer after each crash in a
+//! row.
+//!
+//! # Editor to plugin
+//!
+//! | Method | Params | Result |
+//! |---|---|---|
+//! | `initialize` | `{"name", "protocol_version"}` | `{"bindings": [{"keys", "command", "mode"}], "modes": [{"name", "label"}], "events": [name], "manifest"}` |
+//! | `execute` | `{"command"}` | `null` |
+//! | `picker_result` | `{"picker", "selection": {"index", "item"} or null}` | `null` |
+//! | `event` (notification) | `{"name", "data"}` | |
+//!
+//! Events are sent for the
+//! [`EventKind`](termide_plugin_api::event::EventKind)s named in `events`,
+//! without waiting for the plugin, so buffer and UI requests fail while
+//! handling them. Their `data`:
+//!
+//! | Event | Data |
+//! |---|---|
+//! | `buffer_opened` | `{"path"}` |
+//! | `buffer_saved` | `{"path"}`, `path` is `null` for standard output |
+//! | `buffer_changed` | `{"path"}`, `path` is `null` for unnamed buffers |
+//! | `mode_changed` | `{"from", "to"}`, mode names such as `"insert"` |
+//! | `config_reloaded` | `{}` |
+//!
+//! `manifest` is optional and has the fields of a
+//! [manifest file](super::load_manifest), e.g. `{"name", "version",
+//! "api_version", "commands": [{"name", "description"}], "capabilities"}`.
+//! A plugin whose manifest targets an incompatible plugin API version or
+//! requires capabilities that aren't granted is not loaded.
+//!
+//! Binding commands are namespaced with the plugin name like
+//! [`PluginBinding`](termide_plugin_api::input::PluginBinding)s; `mode` is
+//! optional, and bindings without it are global. A plugin answers `execute`
+//! for a command it doesn't have with the error code [`UNKNOWN_COMMAND`].
+//!
+//! `modes` are the plugin's own [modes](termide_plugin_api::input::PluginMode),
+//! namespaced like commands; `label` is optional and defaults to the
+//! uppercased name. Bindings and `ui.set_mode` may name them without the
+//! plugin namespace.
+//!
+//! # Plugin to editor
+//!
+//! Positions are `{"line", "column"}`, zero-based; ranges are
+//! `{"start", "end"}`. Each method needs a
+//! [`Capability`](super::Capability), and all of them fail with
+//! [`NOT_RUNNING`] unless the editor is waiting for an `execute` or
+//! `picker_result` answer.
+//!
+//! | Method | Capability | Params | Result |
+//! |---|---|---|---|
+//! | `buffer.line_count` | `buffer-read` | | count |
+//! | `buffer.line` | `buffer-read` | `{"line"}` | text, or `null` past the end |
+//! | `buffer.text` | `buffer-read` | range | text |
+//! | `buffer.cursor` | `buffer-read` | | position |
+//! | `buffer.selection` | `buffer-read` | | range or `null` |
+//! | `buffer.file_path` | `buffer-read` | | path or `null` |
+//! | `buffer.is_dirty` | `buffer-read` | | boolean |
+//! | `buffer.insert` | `buffer-edit` | `{"position", "text"}` | end position |
+//! | `buffer.delete` | `buffer-edit` | range | `null` |
+//! | `buffer.set_cursor` | `buffer-edit` | position | `null` |
+//! | `buffer.set_selection` | `buffer-edit` | range or `null` | `null` |
+//! | `ui.message` | `ui` | `{"severity", "text"}` | `null` |
+//! | `ui.status_segment` | `ui` | `{"key", "text"}`, `text` may be `null` | `null` |
+//! | `ui.open_picker` | `ui` | `{"id", "title", "items"}` | `null` |
+//! | `ui.set_mode` | `ui` | `{"mode"}`, e.g. `"normal"` or a plugin mode | `null` |
+//!
+//! Severities are `"info"`, `"success"`, `"warning"` and `"error"`. The `ui.*`
+//! methods may also be sent as notifications.
+//!
+//! # Example
+//!
+//! ```text
+//! -> {"jsonrpc":"2.0","id":1,"method":"initialize","params":{"name":"shout","protocol_version":1}}
+//! <- {"jsonrpc":"2.0","id":1,"result":{"bindings":[{"keys":"Ctrl+Alt+u","command":"upper"}]}}
+//! -> {"jsonrpc":"2.0","id":2,"method":"execute","params":{"command":"upper"}}
+//! <- {"jsonrpc":"2.0","id":"a","method":"buffer.line","params":{"line":0}}
+//! -> {"jsonrpc":"2.0","id":"a","result":"hello"}
+//! <- {"jsonrpc":"2.0","method":"ui.message","params":{"severity":"info","text":"HELLO"}}
+//! <- {"jsonrpc":"2.0","id":2,"result":null}
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use termide_plugin_api::buffer::{BufferError, TextPosition, TextRange};
+use termide_plugin_api::event::EditorEvent;
+use termide_plugin_api::ui::Severity;
+use thiserror::Error;
+
+use super::{Capability, ManifestFile};
+
+/// Version sent in `initialize`, incremented on incompatible changes
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The method does not exist
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The params do not match the method
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// The plugin has no command with the requested name
+pub const UNKNOWN_COMMAND: i64 = -32001;
+
+/// A buffer operation failed, e.g. because a position is outside the buffer
+pub const BUFFER_ERROR: i64 = -32002;
+
+/// The plugin has not been granted the capability the method needs
+pub const CAPABILITY_DENIED: i64 = -32003;
+
+/// The method was called while no command is running
+pub const NOT_RUNNING: i64 = -32004;
+
+/// Methods plugins may call, and the capability each one needs
+pub const METHODS: [(&str, Capability); 15] = [
+    ("buffer.line_count", Capability::BufferRead),
+    ("buffer.line", Capability::BufferRead),
+    ("buffer.text", Capability::BufferRead),
+    ("buffer.cursor", Capability::BufferRead),
+    ("buffer.selection", Capability::BufferRead),
+    ("buffer.file_path", Capability::BufferRead),
+    ("buffer.is_dirty", Capability::BufferRead),
+    ("buffer.insert", Capability::BufferEdit),
+    ("buffer.delete", Capability::BufferEdit),
+    ("buffer.set_cursor", Capability::BufferEdit),
+    ("buffer.set_selection", Capability::BufferEdit),
+    ("ui.message", Capability::Ui),
+    ("ui.status_segment", Capability::Ui),
+    ("ui.open_picker", Capability::Ui),
+    ("ui.set_mode", Capability::Ui),
+];
+
+/// Returns the capability needed by the plugin method `method`
+pub fn required_capability(method: &str) -> Option<Capability> {
+    METHODS
+        .iter()
+        .find(|(name, _)| *name == method)
+        .map(|(_, capability)| *capability)
+}
+
+/// A JSON-RPC request, notification or response
+///
+/// # Examples
+///
+/// ```
+/// use termide::plugin::protocol::Message;
+/// use serde_json::json;
+///
+/// let message: Message = serde_json::from_str(r#"{"jsonrpc":"2.0","id":3,"result":null}"#).unwrap();
+/// assert!(message.method.is_none());
+/// assert_eq!(message.id, Some(json!(3)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    /// Always `"2.0"`
+    pub jsonrpc: String,
+    /// Request id; absent for notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    /// Method of a request or notification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    /// Result of a successful response; `null` results deserialize to `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl Message {
+    fn new() -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: None,
+            method: None,
+            params: None,
+            result: None,
+            error: None,
+        }
+    }
+
+    /// Creates a request
+    pub fn request(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            id: Some(id.into()),
+            method: Some(method.to_string()),
+            params: Some(params),
+            ..Self::new()
+        }
+    }
+
+    /// Creates a notification, which gets no response
+    pub fn notification(method: &str, params: Value) -> Self {
+        Self {
+            method: Some(method.to_string()),
+            params: Some(params),
+            ..Self::new()
+        }
+    }
+
+    /// Creates the response to the request `id`
+    pub fn response(id: Value, result: Result<Value, RpcError>) -> Self {
+        match result {
+            // `null` results are serialized explicitly, as JSON-RPC requires
+            Ok(result) => Self {
+                id: Some(id),
+                result: Some(result),
+                ..Self::new()
+            },
+            Err(error) => Self {
+                id: Some(id),
+                error: Some(error),
+                ..Self::new()
+            },
+        }
+    }
+}
+
+/// The error of a failed JSON-RPC request
+#[derive(Debug, Clone, PartialEq, Error, Serialize, Deserialize)]
+#[error("{message}")]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    /// Creates an error
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<BufferError> for RpcError {
+    fn from(error: BufferError) -> Self {
+        Self::new(BUFFER_ERROR, error.to_string())
+    }
+}
+
+/// A position on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<TextPosition> for RpcPosition {
+    fn from(position: TextPosition) -> Self {
+        Self {
+            line: position.line,
+            column: position.column,
+        }
+    }
+}
+
+impl From<RpcPosition> for TextPosition {
+    fn from(position: RpcPosition) -> Self {
+        TextPosition::new(position.line, position.column)
+    }
+}
+
+/// A range on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcRange {
+    pub start: RpcPosition,
+    pub end: RpcPosition,
+}
+
+impl From<TextRange> for RpcRange {
+    fn from(range: TextRange) -> Self {
+        Self {
+            start: range.start.into(),
+            end: range.end.into(),
+        }
+    }
+}
+
+impl From<RpcRange> for TextRange {
+    fn from(range: RpcRange) -> Self {
+        TextRange::new(range.start.into(), range.end.into())
+    }
+}
+
+/// A severity on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RpcSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl From<RpcSeverity> for Severity {
+    fn from(severity: RpcSeverity) -> Self {
+        match severity {
+            RpcSeverity::Info => Severity::Info,
+            RpcSeverity::Success => Severity::Success,
+            RpcSeverity::Warning => Severity::Warning,
+            RpcSeverity::Error => Severity::Error,
+        }
+    }
+}
+
+/// A keybinding in the `initialize` result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcBinding {
+    pub keys: String,
+    pub command: String,
+    /// Mode name; bindings without a mode are global
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+/// A plugin mode in the `initialize` result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcMode {
+    /// Mode name without the plugin namespace
+    pub name: String,
+    /// Status line label; defaults to the uppercased name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Result of `initialize`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InitializeResult {
+    #[serde(default)]
+    pub bindings: Vec<RpcBinding>,
+    #[serde(default)]
+    pub modes: Vec<RpcMode>,
+    /// Names of the events the plugin subscribes to
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// The plugin's manifest, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<ManifestFile>,
+}
+
+/// Returns the `data` of the `event` notification for `event`
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use serde_json::json;
+/// use termide::plugin::protocol::event_data;
+/// use termide_plugin_api::event::EditorEvent;
+///
+/// let event = EditorEvent::BufferSaved { path: Some(PathBuf::from("notes.txt")) };
+/// assert_eq!(event_data(&event), json!({ "path": "notes.txt" }));
+/// ```
+pub fn event_data(event: &EditorEvent) -> Value {
+    match event {
+        EditorEvent::BufferOpened { path } => json!({ "path": path }),
+        EditorEvent::BufferSaved { path } | EditorEvent::BufferChanged { path } => {
+            json!({ "path": path })
+        }
+        EditorEvent::ModeChanged { from, to } => {
+            json!({ "from": from.as_str(), "to": to.as_str() })
+        }
+        EditorEvent::ConfigReloaded => json!({}),
+    }
+}
@@ -0,0 +1,100 @@
+//! Unit tests for ActiveBuffer
+
+use crate::buffer::Position;
+use crate::editor::EditorState;
+use crate::plugin::ActiveBuffer;
+use termide_plugin_api::buffer::{BufferApi, BufferError, TextPosition, TextRange, BUFFER_API_VERSION};
+
+fn state_with(content: &str) -> EditorState {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content(content);
+    state
+}
+
+#[test]
+fn test_read_lines_and_ranges() {
+    let mut state = state_with("fn main() {\r\n}\n");
+    let mut cursor = Position::new(1, 1);
+    let buffer = ActiveBuffer::new(&mut state, &mut cursor);
+
+    assert_eq!(buffer.version(), BUFFER_API_VERSION);
+    assert_eq!(buffer.line_count(), 3);
+    assert_eq!(buffer.line(0), Some("fn main() {".to_string()));
+    assert_eq!(buffer.line(2), Some(String::new()));
+    assert_eq!(buffer.line(3), None);
+    assert_eq!(
+        buffer.text(TextRange::new(TextPosition::new(0, 3), TextPosition::new(0, 7))),
+        Ok("main".to_string())
+    );
+    assert_eq!(buffer.cursor(), TextPosition::new(1, 1));
+    assert_eq!(buffer.file_path(), None);
+}
+
+#[test]
+fn test_edits() {
+    let mut state = state_with("let x = 1;");
+    let mut cursor = Position::origin();
+    let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+
+    let end = buffer.insert(TextPosition::new(0, 10), "\nlet y = 2;").unwrap();
+    assert_eq!(end, TextPosition::new(1, 10));
+    buffer
+        .delete(TextRange::new(TextPosition::new(0, 4), TextPosition::new(0, 5)))
+        .unwrap();
+    buffer.set_cursor(end).unwrap();
+    assert!(buffer.is_dirty());
+
+    assert_eq!(state.buffer().content(), "let  = 1;\nlet y = 2;");
+    assert_eq!(cursor, Position::new(1, 10));
+}
+
+#[test]
+fn test_invalid_positions_are_rejected() {
+    let mut state = state_with("abc");
+    let mut cursor = Position::origin();
+    let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+    let outside = TextPosition::new(0, 4);
+
+    assert_eq!(buffer.insert(outside, "x"), Err(BufferError::InvalidPosition(outside)));
+    assert_eq!(
+        buffer.delete(TextRange::new(TextPosition::new(0, 0), outside)),
+        Err(BufferError::InvalidPosition(outside))
+    );
+    assert_eq!(buffer.set_cursor(outside), Err(BufferError::InvalidPosition(outside)));
+    assert_eq!(state.buffer().content(), "abc");
+    assert_eq!(cursor, Position::origin());
+}
+
+#[test]
+fn test_read_only_buffer_rejects_edits() {
+    let mut state = state_with("abc");
+    state.set_read_only(true);
+    let mut cursor = Position::origin();
+    let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+
+    assert_eq!(buffer.insert(TextPosition::new(0, 0), "x"), Err(BufferError::ReadOnly));
+    assert_eq!(
+        buffer.delete(TextRange::new(TextPosition::new(0, 0), TextPosition::new(0, 1))),
+        Err(BufferError::ReadOnly)
+    );
+    // Reading and moving still work
+    assert_eq!(buffer.line(0), Some("abc".to_string()));
+    assert_eq!(buffer.set_cursor(TextPosition::new(0, 2)), Ok(()));
+}
+
+#[test]
+fn test_selection() {
+    let mut state = state_with("hello world");
+    let mut cursor = Position::origin();
+    let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+    assert_eq!(buffer.selection(), None);
+
+    let range = TextRange::new(TextPosition::new(0, 6), TextPosition::new(0, 11));
+    buffer.set_selection(Some(range)).unwrap();
+    assert_eq!(buffer.selection(), Some(range));
+    assert_eq!(buffer.cursor(), TextPosition::new(0, 11));
+    assert_eq!(buffer.text(range), Ok("world".to_string()));
+
+    buffer.set_selection(None).unwrap();
+    assert_eq!(buffer.selection(), None);
+}
@@ -0,0 +1,128 @@
+//! Unit tests for Capability and PluginGrants
+
+use crate::plugin::{Capability, PluginGrants};
+use std::collections::BTreeSet;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+fn table(toml: &str) -> toml::Table {
+    toml::from_str(toml).unwrap()
+}
+
+#[test]
+fn test_capability_names_round_trip() {
+    for capability in Capability::ALL {
+        assert_eq!(capability.name().parse(), Ok(capability));
+        assert_eq!(capability.to_string(), capability.name());
+    }
+}
+
+#[test]
+fn test_capability_parse_ignores_case_and_whitespace() {
+    assert_eq!(" Buffer-Read ".parse(), Ok(Capability::BufferRead));
+}
+
+#[test]
+fn test_capability_parse_unknown_lists_available() {
+    let err = "network".parse::<Capability>().unwrap_err();
+    assert!(err.contains("unknown capability 'network'"));
+    assert!(err.contains("filesystem"));
+}
+
+#[test]
+fn test_defaults_exclude_filesystem() {
+    let grants = PluginGrants::default();
+    let capabilities = grants.capabilities("any");
+    assert_eq!(
+        capabilities,
+        Capability::DEFAULTS.into_iter().collect::<BTreeSet<_>>()
+    );
+    assert!(!capabilities.contains(&Capability::Filesystem));
+}
+
+#[test]
+fn test_explicit_list_replaces_defaults() {
+    let (grants, warnings) = PluginGrants::parse(&table(
+        r#"
+        [snippets]
+        capabilities = ["filesystem", "ui"]
+
+        [viewer]
+        capabilities = []
+        "#,
+    ));
+    assert!(warnings.is_empty());
+    assert_eq!(
+        grants.capabilities("snippets"),
+        BTreeSet::from([Capability::Ui, Capability::Filesystem])
+    );
+    assert!(grants.capabilities("viewer").is_empty());
+}
+
+#[test]
+fn test_parse_warns_about_invalid_entries() {
+    let (grants, warnings) = PluginGrants::parse(&table(
+        r#"
+        broken = "ui"
+
+        [mixed]
+        capabilities = ["ui", "network", 3]
+        "#,
+    ));
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0].starts_with("plugins.broken:"));
+    assert!(warnings[1].contains("unknown capability 'network'"));
+    assert!(warnings[2].contains("must be strings"));
+    assert_eq!(
+        grants.capabilities("mixed"),
+        BTreeSet::from([Capability::Ui])
+    );
+    assert_eq!(
+        grants.capabilities("broken").len(),
+        Capability::DEFAULTS.len()
+    );
+}
+
+#[test]
+fn test_entries_without_capabilities_keep_defaults() {
+    let (grants, warnings) = PluginGrants::parse(&table(
+        r#"
+        [shout]
+        command = ["python3", "shout.py"]
+        "#,
+    ));
+    assert!(warnings.is_empty());
+    assert_eq!(grants.capabilities("shout"), PluginGrants::default().capabilities("shout"));
+}
+
+#[test]
+fn test_load_reads_plugins_section() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(
+        file,
+        "[plugins.word-count]\ncapabilities = [\"buffer-read\"]"
+    )
+    .unwrap();
+
+    let (grants, warnings) = PluginGrants::load(file.path()).unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(
+        grants.capabilities("word-count"),
+        BTreeSet::from([Capability::BufferRead])
+    );
+}
+
+#[test]
+fn test_load_missing_file_grants_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let (grants, warnings) = PluginGrants::load(&dir.path().join("config.toml")).unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(grants, PluginGrants::default());
+}
+
+#[test]
+fn test_load_invalid_toml_is_an_error() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "[plugins.x").unwrap();
+    assert!(PluginGrants::load(file.path()).is_err());
+}
@@ -0,0 +1,648 @@
+//! Unit tests for PluginHost
+
+use crate::buffer::Position;
+use crate::editor::{EditorMode, EditorState, PromptKind};
+use crate::input::keybinding::KeySequence;
+use crate::input::registry::KeyBindingRegistry;
+use crate::input::EditorCommand;
+use crate::plugin::{is_plugin_running, PluginHost, PluginHostError, MAX_CASCADED_EVENTS};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::str::FromStr;
+use termide_plugin_api::buffer::TextPosition;
+use termide_plugin_api::event::{EditorEvent, EventKind, EventSubscriber};
+use termide_plugin_api::input::{BindingError, EditorMode as ApiMode, PluginBindingBuilder, PluginInputExtension, PluginMode};
+use termide_plugin_api::manifest::{BindingManifest, CommandManifest, ManifestError, PluginManifest, Version};
+use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+use termide_plugin_api::ui::{Picker, PickerSelection, Severity};
+
+/// Test plugin binding `Ctrl+k` to `<name>.<command>` for each of `commands`
+struct TestPlugin {
+    name: &'static str,
+    commands: Vec<&'static str>,
+    executed: Vec<String>,
+}
+
+impl TestPlugin {
+    fn new(name: &'static str, commands: Vec<&'static str>) -> Self {
+        Self {
+            name,
+            commands,
+            executed: Vec::new(),
+        }
+    }
+}
+
+impl Plugin for TestPlugin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn register(&mut self, registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+        for (i, command) in self.commands.iter().enumerate() {
+            let sequence = format!("Ctrl+k {}", i);
+            registry.register_keybinding(PluginBindingBuilder::new(self.name).bind(sequence, *command).build()?)?;
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        assert!(is_plugin_running());
+        match command {
+            "fail" => Err(PluginError::Failed("no selection".to_string())),
+            "crash" => panic!("index out of bounds"),
+            "run" => {
+                self.executed.push(command.to_string());
+                let buffer = context.buffer();
+                buffer.insert(buffer.cursor(), self.name)?;
+                Ok(())
+            }
+            other => Err(PluginError::UnknownCommand(other.to_string())),
+        }
+    }
+}
+
+fn sequence(s: &str) -> Vec<crate::input::keybinding::KeyPattern> {
+    KeySequence::from_str(s).unwrap().patterns().to_vec()
+}
+
+#[test]
+fn test_load_registers_plugin_bindings() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+
+    host.load(Box::new(TestPlugin::new("lint", vec!["run"])), &mut registry).unwrap();
+
+    assert_eq!(host.names().collect::<Vec<_>>(), vec!["lint"]);
+    let description = registry.describe_sequence(&sequence("Ctrl+k 0"), &EditorMode::Normal);
+    assert_eq!(
+        description.binding.map(|binding| binding.command()),
+        Some(&EditorCommand::PluginCommand {
+            plugin_name: "lint".to_string(),
+            command_name: "run".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_execute_dispatches_to_owning_plugin() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(TestPlugin::new("lint", vec![])), &mut registry).unwrap();
+    host.load(Box::new(TestPlugin::new("fmt", vec![])), &mut registry).unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    assert_eq!(host.execute("fmt", "run", &mut state, &mut cursor, &registry), Ok(()));
+    assert_eq!(
+        host.execute("lint", "fail", &mut state, &mut cursor, &registry).unwrap_err().to_string(),
+        "plugin 'lint': no selection"
+    );
+    assert_eq!(
+        host.execute("lint", "nope", &mut state, &mut cursor, &registry).unwrap_err().to_string(),
+        "plugin 'lint': unknown command 'nope'"
+    );
+    assert_eq!(host.execute("git", "run", &mut state, &mut cursor, &registry), Err(PluginHostError::NotLoaded("git".to_string())));
+    assert_eq!(state.buffer().content(), "fmt");
+}
+
+#[test]
+fn test_panicking_command_disables_plugin() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(TestPlugin::new("lint", vec![])), &mut registry).unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    assert_eq!(
+        host.execute("lint", "crash", &mut state, &mut cursor, &registry),
+        Err(PluginHostError::Panicked {
+            plugin: "lint".to_string(),
+            message: "index out of bounds".to_string(),
+        })
+    );
+    assert!(!is_plugin_running());
+    assert!(host.is_disabled("lint"));
+    assert_eq!(
+        host.execute("lint", "run", &mut state, &mut cursor, &registry).unwrap_err().to_string(),
+        "plugin 'lint' is disabled after a panic: index out of bounds"
+    );
+}
+
+#[test]
+fn test_plugin_may_only_bind_own_commands() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+
+    // The first binding is removed again when the second is rejected
+    let result = host.load(Box::new(TestPlugin::new("lint", vec!["run", "fmt.run"])), &mut registry);
+
+    assert_eq!(
+        result,
+        Err(PluginHostError::Registration {
+            plugin: "lint".to_string(),
+            source: BindingError::ForeignCommand {
+                command: "fmt.run".to_string(),
+                plugin: "lint".to_string(),
+            },
+        })
+    );
+    assert!(registry.is_empty());
+    assert!(host.is_empty());
+
+    // Built-in commands are not in a plugin's namespace either
+    let result = host.load(Box::new(TestPlugin::new("file", vec!["save"])), &mut registry);
+    assert!(matches!(result, Err(PluginHostError::Registration { .. })));
+}
+
+#[test]
+fn test_load_rejects_invalid_and_duplicate_names() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+
+    assert_eq!(
+        host.load(Box::new(TestPlugin::new("my.plugin", vec![])), &mut registry),
+        Err(PluginHostError::InvalidName("my.plugin".to_string()))
+    );
+    host.load(Box::new(TestPlugin::new("lint", vec![])), &mut registry).unwrap();
+    assert_eq!(
+        host.load(Box::new(TestPlugin::new("lint", vec![])), &mut registry),
+        Err(PluginHostError::AlreadyLoaded("lint".to_string()))
+    );
+}
+
+#[test]
+fn test_load_all_reports_failures() {
+    /// Plugin whose registration panics
+    struct Broken;
+
+    impl Plugin for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn register(&mut self, _registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+            panic!("bad config")
+        }
+
+        fn execute(&mut self, _command: &str, _context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+            Ok(())
+        }
+    }
+
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    let warnings = host.load_all(
+        vec![Box::new(Broken), Box::new(TestPlugin::new("lint", vec!["run"]))],
+        &mut registry,
+    );
+
+    assert_eq!(warnings, vec!["plugin 'broken' panicked: bad config".to_string()]);
+    assert_eq!(host.names().collect::<Vec<_>>(), vec!["lint"]);
+    assert_eq!(registry.len(), 1);
+}
+
+/// Plugin described by a manifest, binding nothing itself
+struct ManifestPlugin(PluginManifest);
+
+impl ManifestPlugin {
+    /// Manifest for plugin `fmt` with command `all`, bound to `Ctrl+k f` in Normal mode
+    fn new() -> Self {
+        let mut manifest = PluginManifest::new("fmt", "1.2.0");
+        manifest.commands.push(CommandManifest::new("all", "Format the buffer"));
+        manifest.bindings.push(BindingManifest::new("Ctrl+k f", "all").in_mode("normal"));
+        Self(manifest)
+    }
+}
+
+impl Plugin for ManifestPlugin {
+    fn name(&self) -> &str {
+        "fmt"
+    }
+
+    fn manifest(&self) -> Option<PluginManifest> {
+        Some(self.0.clone())
+    }
+
+    fn register(&mut self, _registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+        Ok(())
+    }
+
+    fn execute(&mut self, _command: &str, _context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_load_registers_manifest_bindings() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(ManifestPlugin::new()), &mut registry).unwrap();
+
+    assert_eq!(host.manifest("fmt").map(|manifest| manifest.version.as_str()), Some("1.2.0"));
+    assert_eq!(host.manifest("lint"), None);
+    let description = registry.describe_sequence(&sequence("Ctrl+k f"), &EditorMode::Normal);
+    assert_eq!(
+        description.binding.map(|binding| binding.command()),
+        Some(&EditorCommand::PluginCommand {
+            plugin_name: "fmt".to_string(),
+            command_name: "all".to_string(),
+        })
+    );
+    let description = registry.describe_sequence(&sequence("Ctrl+k f"), &EditorMode::Insert);
+    assert!(description.binding.is_none());
+}
+
+#[test]
+fn test_commands_lists_manifest_commands() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(TestPlugin::new("lint", vec!["check"])), &mut registry).unwrap();
+    host.load(Box::new(ManifestPlugin::new()), &mut registry).unwrap();
+
+    assert_eq!(
+        host.commands(),
+        vec![EditorCommand::PluginCommand {
+            plugin_name: "fmt".to_string(),
+            command_name: "all".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_load_rejects_incompatible_manifest() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    let mut plugin = ManifestPlugin::new();
+    plugin.0.api_version = "99.0".to_string();
+
+    let err = host.load(Box::new(plugin), &mut registry).unwrap_err();
+    assert_eq!(
+        err,
+        PluginHostError::Incompatible {
+            plugin: "fmt".to_string(),
+            source: ManifestError::IncompatibleApi {
+                plugin: "99.0".to_string(),
+                host: Version::current().to_string(),
+            },
+        }
+    );
+    assert!(err.to_string().starts_with("plugin 'fmt' is incompatible: written for plugin API 99.0"));
+    assert!(host.is_empty());
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_load_rejects_manifest_of_other_plugin() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    let mut plugin = ManifestPlugin::new();
+    plugin.0.name = "lint".to_string();
+
+    assert_eq!(
+        host.load(Box::new(plugin), &mut registry),
+        Err(PluginHostError::ManifestName {
+            plugin: "fmt".to_string(),
+            manifest: "lint".to_string(),
+        })
+    );
+    assert!(host.is_empty());
+}
+
+/// Plugin that asks for UI changes and inserts the picked item
+struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn name(&self) -> &str {
+        "ui"
+    }
+
+    fn execute(&mut self, command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        context.show_message(Severity::Warning, "2 lints");
+        context.set_status_segment("lints", Some("L:2".to_string()));
+        match command {
+            "pick" => {
+                let items = vec!["alpha".to_string(), "beta".to_string()];
+                context.open_picker(Picker::new("letters", "Letter", items));
+                Ok(())
+            }
+            _ => Err(PluginError::Failed("lint crashed".to_string())),
+        }
+    }
+
+    fn picker_result(
+        &mut self,
+        picker: &str,
+        selection: Option<PickerSelection>,
+        context: &mut CommandContext<'_>,
+    ) -> Result<(), PluginError> {
+        assert_eq!(picker, "letters");
+        if let Some(selection) = selection {
+            let buffer = context.buffer();
+            buffer.insert(buffer.cursor(), &selection.item)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_ui_requests_are_applied() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(UiPlugin), &mut registry).unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    host.execute("ui", "pick", &mut state, &mut cursor, &registry).unwrap();
+
+    assert_eq!(state.status_message(), Some("2 lints"));
+    assert_eq!(state.status_severity(), Severity::Warning);
+    assert_eq!(state.status_segments().collect::<Vec<_>>(), vec!["L:2"]);
+    assert_eq!(state.mode(), EditorMode::Prompt);
+    assert_eq!(state.prompt_message(), "Letter: ");
+    assert_eq!(
+        state.prompt_kind(),
+        &PromptKind::PluginPicker {
+            plugin: "ui".to_string(),
+            id: "letters".to_string(),
+        }
+    );
+    assert_eq!(state.prompt_list().map(|list| list.len()), Some(2));
+}
+
+#[test]
+fn test_ui_requests_are_applied_when_command_fails() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(UiPlugin), &mut registry).unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    assert!(host.execute("ui", "lint", &mut state, &mut cursor, &registry).is_err());
+    assert_eq!(state.status_segments().collect::<Vec<_>>(), vec!["L:2"]);
+    assert_ne!(state.mode(), EditorMode::Prompt);
+}
+
+#[test]
+fn test_picker_result_is_delivered() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(UiPlugin), &mut registry).unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    let selection = PickerSelection {
+        index: 1,
+        item: "beta".to_string(),
+    };
+    host.picker_result("ui", "letters", Some(selection), &mut state, &mut cursor, &registry).unwrap();
+    host.picker_result("ui", "letters", None, &mut state, &mut cursor, &registry).unwrap();
+
+    assert_eq!(state.buffer().content(), "beta");
+    assert_eq!(
+        host.picker_result("git", "letters", None, &mut state, &mut cursor, &registry),
+        Err(PluginHostError::NotLoaded("git".to_string()))
+    );
+}
+
+/// Plugin `viewer` with a `view` mode, entered and left by its commands
+struct ModePlugin {
+    foreign: bool,
+}
+
+impl Plugin for ModePlugin {
+    fn name(&self) -> &str {
+        "viewer"
+    }
+
+    fn register(&mut self, registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+        let owner = if self.foreign { "other" } else { "viewer" };
+        registry.register_mode(PluginMode::new(owner, "view").with_label("VIEW"))?;
+        registry.register_keybinding(
+            PluginBindingBuilder::new("viewer")
+                .bind("q", "leave")
+                .in_mode("view")
+                .build()?,
+        )
+    }
+
+    fn execute(&mut self, command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        let mode = match command {
+            "enter" => ApiMode::Plugin("viewer.view".to_string()),
+            "leave" => ApiMode::Normal,
+            "prompt" => ApiMode::Prompt,
+            _ => ApiMode::Plugin("viewer.missing".to_string()),
+        };
+        context.set_mode(mode);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_plugin_modes_are_registered_and_entered() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    host.load(Box::new(ModePlugin { foreign: false }), &mut registry).unwrap();
+    let view = EditorMode::Plugin(registry.plugin_mode("viewer.view").expect("mode should be registered"));
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    host.execute("viewer", "enter", &mut state, &mut cursor, &registry).unwrap();
+    assert_eq!(state.mode(), view);
+    assert_eq!(state.mode().to_string(), "VIEW");
+    let description = registry.describe_sequence(&sequence("q"), &view);
+    assert_eq!(
+        description.binding.map(|binding| binding.command()),
+        Some(&EditorCommand::PluginCommand {
+            plugin_name: "viewer".to_string(),
+            command_name: "leave".to_string(),
+        })
+    );
+
+    host.execute("viewer", "prompt", &mut state, &mut cursor, &registry).unwrap();
+    assert_eq!(state.mode(), view);
+    assert_eq!(state.status_message(), Some("Plugin 'viewer' cannot enter prompt mode"));
+
+    host.execute("viewer", "missing", &mut state, &mut cursor, &registry).unwrap();
+    assert_eq!(state.mode(), view);
+    assert_eq!(
+        state.status_message(),
+        Some("Plugin 'viewer' switched to unknown mode 'viewer.missing'")
+    );
+
+    host.execute("viewer", "leave", &mut state, &mut cursor, &registry).unwrap();
+    assert_eq!(state.mode(), EditorMode::Normal);
+}
+
+#[test]
+fn test_plugin_may_only_register_own_modes() {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+
+    let err = host.load(Box::new(ModePlugin { foreign: true }), &mut registry).unwrap_err();
+    assert!(err.to_string().contains("other.view"), "{}", err);
+    assert!(registry.plugin_mode("other.view").is_none());
+    assert!(host.is_empty());
+    assert!(registry.is_empty());
+}
+
+/// Test plugin logging the events it receives to a log shared between plugins
+struct EventPlugin {
+    name: &'static str,
+    subscriptions: Vec<EventKind>,
+    log: Rc<RefCell<Vec<String>>>,
+    /// Text inserted twice at the start of the buffer for every event
+    insert: Option<&'static str>,
+}
+
+impl EventPlugin {
+    fn new(name: &'static str, subscriptions: Vec<EventKind>, log: &Rc<RefCell<Vec<String>>>) -> Self {
+        Self {
+            name,
+            subscriptions,
+            log: Rc::clone(log),
+            insert: None,
+        }
+    }
+
+    fn inserting(mut self, text: &'static str) -> Self {
+        self.insert = Some(text);
+        self
+    }
+}
+
+impl Plugin for EventPlugin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn execute(&mut self, command: &str, _context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        Err(PluginError::UnknownCommand(command.to_string()))
+    }
+
+    fn event_subscriber(&mut self) -> Option<&mut dyn EventSubscriber> {
+        Some(self)
+    }
+}
+
+impl EventSubscriber for EventPlugin {
+    fn subscriptions(&self) -> Vec<EventKind> {
+        self.subscriptions.clone()
+    }
+
+    fn on_event(&mut self, event: &EditorEvent, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        assert!(is_plugin_running());
+        self.log.borrow_mut().push(format!("{}: {}", self.name, event.kind().as_str()));
+        match self.name {
+            "crash" => panic!("bad event"),
+            "fail" => return Err(PluginError::Failed("cannot handle".to_string())),
+            _ => {}
+        }
+        if let Some(text) = self.insert {
+            context.buffer().insert(TextPosition::new(0, 0), text)?;
+            context.buffer().insert(TextPosition::new(0, 0), text)?;
+        }
+        Ok(())
+    }
+}
+
+fn event_host(plugins: Vec<EventPlugin>) -> PluginHost {
+    let mut registry = KeyBindingRegistry::default();
+    let mut host = PluginHost::new();
+    for plugin in plugins {
+        host.load(Box::new(plugin), &mut registry).unwrap();
+    }
+    host
+}
+
+#[test]
+fn test_events_are_delivered_in_order() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut host = event_host(vec![
+        EventPlugin::new("a", vec![EventKind::BufferSaved, EventKind::ModeChanged], &log),
+        EventPlugin::new("b", vec![EventKind::ModeChanged], &log),
+    ]);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    state.set_mode(EditorMode::Normal);
+    state.publish_event(EditorEvent::BufferSaved { path: None });
+    state.publish_event(EditorEvent::ConfigReloaded);
+    assert!(host.dispatch_events(&mut state, &mut cursor, &KeyBindingRegistry::default()).is_empty());
+
+    assert_eq!(*log.borrow(), vec!["a: mode_changed", "b: mode_changed", "a: buffer_saved"]);
+    assert!(host.dispatch_events(&mut state, &mut cursor, &KeyBindingRegistry::default()).is_empty());
+    assert_eq!(log.borrow().len(), 3);
+}
+
+#[test]
+fn test_caused_events_skip_their_origin() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut host = event_host(vec![
+        EventPlugin::new("writer", vec![EventKind::ConfigReloaded, EventKind::BufferChanged], &log)
+            .inserting("x"),
+        EventPlugin::new("watcher", vec![EventKind::BufferChanged], &log),
+    ]);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    state.publish_event(EditorEvent::ConfigReloaded);
+    assert!(host.dispatch_events(&mut state, &mut cursor, &KeyBindingRegistry::default()).is_empty());
+
+    assert_eq!(*log.borrow(), vec!["writer: config_reloaded", "watcher: buffer_changed"]);
+    // Both inserts of the handler are one undo step
+    assert_eq!(state.buffer().content(), "xx");
+    assert!(state.buffer_mut().undo());
+    assert_eq!(state.buffer().content(), "");
+}
+
+#[test]
+fn test_event_loops_are_cut_off() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut host = event_host(vec![
+        EventPlugin::new("ping", vec![EventKind::BufferChanged], &log).inserting("i"),
+        EventPlugin::new("pong", vec![EventKind::BufferChanged], &log).inserting("o"),
+    ]);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    state.publish_event(EditorEvent::BufferChanged { path: None });
+    let errors = host.dispatch_events(&mut state, &mut cursor, &KeyBindingRegistry::default());
+
+    assert!(matches!(errors.as_slice(), [PluginHostError::TooManyEvents { .. }]));
+    assert!(log.borrow().len() <= MAX_CASCADED_EVENTS + 2);
+    assert!(state.take_events().is_empty());
+}
+
+#[test]
+fn test_failing_handlers_do_not_stop_delivery() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut host = event_host(vec![
+        EventPlugin::new("crash", vec![EventKind::ConfigReloaded], &log),
+        EventPlugin::new("fail", vec![EventKind::ConfigReloaded], &log),
+        EventPlugin::new("ok", vec![EventKind::ConfigReloaded], &log),
+    ]);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    state.publish_event(EditorEvent::ConfigReloaded);
+    let errors = host.dispatch_events(&mut state, &mut cursor, &KeyBindingRegistry::default());
+    assert!(matches!(errors[0], PluginHostError::Panicked { .. }));
+    assert!(matches!(errors[1], PluginHostError::Failed { .. }));
+    assert_eq!(errors.len(), 2);
+    assert!(host.is_disabled("crash"));
+
+    // The disabled plugin is skipped from now on
+    state.publish_event(EditorEvent::ConfigReloaded);
+    assert_eq!(host.dispatch_events(&mut state, &mut cursor, &KeyBindingRegistry::default()).len(), 1);
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "crash: config_reloaded",
+            "fail: config_reloaded",
+            "ok: config_reloaded",
+            "fail: config_reloaded",
+            "ok: config_reloaded",
+        ]
+    );
+}
@@ -0,0 +1,120 @@
+//! Unit tests for manifest files
+
+use crate::plugin::{
+    check_capabilities, load_manifest, Capability, ManifestFile, ManifestFileError,
+};
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use tempfile::NamedTempFile;
+use termide_plugin_api::manifest::{BindingManifest, CommandManifest, PluginManifest};
+
+fn manifest_file(content: &str) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_load_manifest() {
+    let file = manifest_file(
+        r#"
+name = "fmt"
+version = "2.1.0"
+api_version = "0.1"
+capabilities = ["buffer-edit"]
+
+[[commands]]
+name = "all"
+description = "Format the buffer"
+
+[[commands]]
+name = "line"
+
+[[bindings]]
+keys = "Ctrl+Alt+f"
+command = "all"
+
+[[bindings]]
+keys = "g f"
+command = "line"
+mode = "normal"
+"#,
+    );
+
+    let manifest = load_manifest(file.path()).unwrap();
+    assert_eq!(manifest.name, "fmt");
+    assert_eq!(manifest.version, "2.1.0");
+    assert_eq!(manifest.api_version, "0.1");
+    assert_eq!(
+        manifest.commands,
+        vec![
+            CommandManifest::new("all", "Format the buffer"),
+            CommandManifest::new("line", ""),
+        ]
+    );
+    assert_eq!(
+        manifest.bindings,
+        vec![
+            BindingManifest::new("Ctrl+Alt+f", "all"),
+            BindingManifest::new("g f", "line").in_mode("normal"),
+        ]
+    );
+    assert_eq!(manifest.capabilities, vec!["buffer-edit"]);
+}
+
+#[test]
+fn test_load_manifest_errors() {
+    let err = load_manifest(Path::new("/nonexistent/fmt.toml")).unwrap_err();
+    assert!(matches!(err, ManifestFileError::Read { .. }));
+    assert!(err.to_string().contains("/nonexistent/fmt.toml"));
+
+    // `api_version` is required
+    let file = manifest_file("name = \"fmt\"\nversion = \"1.0.0\"\n");
+    let err = load_manifest(file.path()).unwrap_err();
+    assert!(matches!(err, ManifestFileError::Parse { .. }));
+    assert!(err.to_string().contains("api_version"), "{}", err);
+}
+
+#[test]
+fn test_manifest_file_from_json() {
+    // Process plugins send their manifest in the `initialize` result
+    let file: ManifestFile = serde_json::from_value(serde_json::json!({
+        "name": "fmt",
+        "version": "1.0.0",
+        "api_version": "0.1",
+        "commands": [{ "name": "all" }],
+    }))
+    .unwrap();
+
+    let manifest = PluginManifest::from(file);
+    assert_eq!(manifest.commands, vec![CommandManifest::new("all", "")]);
+    assert!(manifest.bindings.is_empty());
+    assert!(manifest.capabilities.is_empty());
+}
+
+#[test]
+fn test_check_capabilities() {
+    let granted: BTreeSet<Capability> = Capability::DEFAULTS.into_iter().collect();
+    let mut manifest = PluginManifest::new("fmt", "1.0.0");
+    manifest.capabilities = vec!["buffer-read".to_string(), "ui".to_string()];
+    assert!(check_capabilities(&manifest, &granted).is_ok());
+
+    manifest.capabilities.push("filesystem".to_string());
+    let err = check_capabilities(&manifest, &granted).unwrap_err();
+    assert!(matches!(
+        err,
+        ManifestFileError::NotGranted(Capability::Filesystem)
+    ));
+    assert_eq!(
+        err.to_string(),
+        "manifest requires the 'filesystem' capability, which is not granted"
+    );
+
+    manifest.capabilities = vec!["network".to_string()];
+    let err = check_capabilities(&manifest, &granted).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "manifest requires unknown capability 'network'"
+    );
+}
@@ -0,0 +1,18 @@
+//! Unit tests for plugin module
+//!
+//! Tests are organized by struct:
+//! - buffer.rs: Tests for ActiveBuffer
+//! - capability.rs: Tests for Capability and PluginGrants
+//! - host.rs: Tests for PluginHost
+//! - manifest.rs: Tests for manifest files
+//! - process.rs: Tests for ProcessSpec and restart backoff
+//! - protocol.rs: Tests for process plugin protocol messages
+//! - wasm.rs: Tests for WasmPlugin
+
+mod buffer;
+mod capability;
+mod host;
+mod manifest;
+mod process;
+mod protocol;
+mod wasm;
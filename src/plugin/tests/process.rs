@@ -0,0 +1,105 @@
+//! Unit tests for ProcessSpec and the restart backoff
+
+use crate::plugin::process::Backoff;
+use crate::plugin::ProcessSpec;
+use std::time::{Duration, Instant};
+
+fn parse(toml: &str) -> (Vec<ProcessSpec>, Vec<String>) {
+    ProcessSpec::parse_all(&toml::from_str(toml).unwrap())
+}
+
+#[test]
+fn test_parse_command_forms() {
+    let (specs, warnings) = parse(
+        r#"
+        [list]
+        command = ["node", "plugin.js", "--verbose"]
+
+        [program]
+        command = "termide-git"
+        "#,
+    );
+    assert!(warnings.is_empty());
+    assert_eq!(
+        specs,
+        vec![
+            ProcessSpec::new(
+                "list",
+                vec!["node".into(), "plugin.js".into(), "--verbose".into()]
+            ),
+            ProcessSpec::new("program", vec!["termide-git".into()]),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_skips_entries_without_command() {
+    let (specs, warnings) = parse(
+        r#"
+        other = "value"
+
+        [word-count]
+        capabilities = ["buffer-read"]
+        "#,
+    );
+    assert!(specs.is_empty());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_parse_warns_about_invalid_commands() {
+    let (specs, warnings) = parse(
+        r#"
+        [empty]
+        command = []
+
+        [numbers]
+        command = ["run", 3]
+
+        [blank]
+        command = ""
+
+        [table]
+        command = { program = "x" }
+        "#,
+    );
+    assert!(specs.is_empty());
+    assert_eq!(warnings.len(), 4);
+    assert!(warnings
+        .iter()
+        .all(|warning| warning.contains("'command' must be")));
+}
+
+#[test]
+fn test_backoff_doubles_up_to_max() {
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+    let now = Instant::now();
+    assert_eq!(backoff.remaining(now), None);
+
+    let delays: Vec<u64> = (0..5).map(|_| backoff.crashed(now).as_secs()).collect();
+    assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+    assert_eq!(backoff.remaining(now), Some(Duration::from_secs(5)));
+    assert_eq!(backoff.remaining(now + Duration::from_secs(5)), None);
+}
+
+#[test]
+fn test_backoff_reset_forgets_crashes() {
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+    let now = Instant::now();
+    backoff.crashed(now);
+    backoff.crashed(now);
+    backoff.reset();
+
+    assert_eq!(backoff.remaining(now), None);
+    assert_eq!(backoff.crashed(now), Duration::from_secs(1));
+}
+
+#[test]
+fn test_backoff_survives_many_crashes() {
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+    let now = Instant::now();
+    for _ in 0..100 {
+        backoff.crashed(now);
+    }
+    assert_eq!(backoff.crashed(now), Duration::from_secs(60));
+}
@@ -0,0 +1,144 @@
+//! Unit tests for the process plugin protocol messages
+
+use crate::plugin::protocol::{
+    event_data, required_capability, InitializeResult, Message, RpcBinding, RpcError, RpcMode, RpcPosition, RpcRange,
+    BUFFER_ERROR, METHODS,
+};
+use crate::plugin::Capability;
+use serde_json::json;
+use std::path::PathBuf;
+use termide_plugin_api::buffer::{BufferError, TextPosition, TextRange};
+use termide_plugin_api::event::EditorEvent;
+use termide_plugin_api::input::EditorMode;
+
+#[test]
+fn test_request_serialization() {
+    let message = Message::request(7, "execute", json!({ "command": "upper" }));
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({ "jsonrpc": "2.0", "id": 7, "method": "execute", "params": { "command": "upper" } })
+    );
+}
+
+#[test]
+fn test_notification_has_no_id() {
+    let message = Message::notification("event", json!({ "name": "buffer_saved" }));
+    let value = serde_json::to_value(&message).unwrap();
+    assert!(value.get("id").is_none());
+    assert_eq!(value["method"], "event");
+}
+
+#[test]
+fn test_null_result_is_serialized() {
+    let message = Message::response(json!("a"), Ok(json!(null)));
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"jsonrpc":"2.0","id":"a","result":null}"#
+    );
+}
+
+#[test]
+fn test_error_response() {
+    let message = Message::response(json!(1), Err(BufferError::ReadOnly.into()));
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(value["error"]["code"], BUFFER_ERROR);
+    assert!(value.get("result").is_none());
+
+    let parsed: Message = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed.error.unwrap().code, BUFFER_ERROR);
+}
+
+#[test]
+fn test_rpc_error_displays_message() {
+    assert_eq!(
+        RpcError::new(-1, "no selection").to_string(),
+        "no selection"
+    );
+}
+
+#[test]
+fn test_positions_and_ranges_convert() {
+    let range = TextRange::new(TextPosition::new(2, 4), TextPosition::new(1, 0));
+    let wire = RpcRange::from(range);
+    assert_eq!(wire.start, RpcPosition { line: 1, column: 0 });
+    assert_eq!(TextRange::from(wire), range);
+    assert_eq!(
+        serde_json::to_value(wire).unwrap(),
+        json!({ "start": { "line": 1, "column": 0 }, "end": { "line": 2, "column": 4 } })
+    );
+}
+
+#[test]
+fn test_initialize_result_defaults() {
+    let result: InitializeResult = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(result, InitializeResult::default());
+
+    let result: InitializeResult = serde_json::from_value(json!({
+        "bindings": [{ "keys": "Ctrl+k", "command": "run", "mode": "insert" }],
+        "events": ["buffer_saved"],
+    }))
+    .unwrap();
+    assert_eq!(
+        result.bindings,
+        vec![RpcBinding {
+            keys: "Ctrl+k".to_string(),
+            command: "run".to_string(),
+            mode: Some("insert".to_string()),
+        }]
+    );
+    assert!(result.modes.is_empty());
+
+    let result: InitializeResult = serde_json::from_value(json!({
+        "modes": [{ "name": "view", "label": "VIEW" }, { "name": "edit" }],
+    }))
+    .unwrap();
+    assert_eq!(
+        result.modes,
+        vec![
+            RpcMode {
+                name: "view".to_string(),
+                label: Some("VIEW".to_string()),
+            },
+            RpcMode {
+                name: "edit".to_string(),
+                label: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_required_capability() {
+    assert_eq!(
+        required_capability("buffer.line"),
+        Some(Capability::BufferRead)
+    );
+    assert_eq!(
+        required_capability("buffer.insert"),
+        Some(Capability::BufferEdit)
+    );
+    assert_eq!(required_capability("ui.open_picker"), Some(Capability::Ui));
+    assert_eq!(required_capability("ui.set_mode"), Some(Capability::Ui));
+    assert_eq!(required_capability("fs.read"), None);
+    assert!(METHODS
+        .iter()
+        .all(|(_, capability)| *capability != Capability::Filesystem));
+}
+
+#[test]
+fn test_event_data() {
+    let opened = EditorEvent::BufferOpened {
+        path: PathBuf::from("a.txt"),
+    };
+    assert_eq!(event_data(&opened), json!({ "path": "a.txt" }));
+    assert_eq!(
+        event_data(&EditorEvent::BufferChanged { path: None }),
+        json!({ "path": null })
+    );
+    let mode = EditorEvent::ModeChanged {
+        from: EditorMode::Normal,
+        to: EditorMode::Insert,
+    };
+    assert_eq!(event_data(&mode), json!({ "from": "normal", "to": "insert" }));
+    assert_eq!(event_data(&EditorEvent::ConfigReloaded), json!({}));
+}
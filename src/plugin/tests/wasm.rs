@@ -0,0 +1,520 @@
+//! Unit tests for WasmPlugin
+
+use crate::buffer::Position;
+use crate::editor::EditorState;
+use crate::plugin::{
+    load_wasm_plugins, ActiveBuffer, Capability, PluginGrants, WasmPlugin, WasmPluginError,
+};
+use std::collections::BTreeSet;
+use std::fs;
+use termide_plugin_api::buffer::BufferError;
+use termide_plugin_api::input::{
+    BindingContext, BindingError, PluginBinding, PluginInputExtension, PluginMode,
+};
+use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+use termide_plugin_api::ui::{Severity, UiRequest};
+
+/// Registry recording the bindings registered by a plugin
+#[derive(Default)]
+struct Recorder {
+    bindings: Vec<PluginBinding>,
+}
+
+impl PluginInputExtension for Recorder {
+    fn register_keybinding(&mut self, binding: PluginBinding) -> Result<(), BindingError> {
+        self.bindings.push(binding);
+        Ok(())
+    }
+
+    fn register_mode(&mut self, mode: PluginMode) -> Result<(), BindingError> {
+        Err(BindingError::InvalidMode(mode.name))
+    }
+}
+
+fn defaults() -> BTreeSet<Capability> {
+    Capability::DEFAULTS.into_iter().collect()
+}
+
+/// Builds a module with `imports` and a `termide_execute` running `body`
+fn module(imports: &str, body: &str) -> String {
+    format!(
+        r#"(module
+            {}
+            (memory (export "memory") 1)
+            (data (i32.const 0) "hi")
+            (func (export "termide_execute") (result i32) {}))"#,
+        imports, body
+    )
+}
+
+fn load(wat: &str) -> Result<WasmPlugin, WasmPluginError> {
+    WasmPlugin::new("test", wat.as_bytes(), &defaults())
+}
+
+/// Runs `command` on a buffer containing `content` with the cursor at `cursor`
+fn run(
+    plugin: &mut WasmPlugin,
+    command: &str,
+    content: &str,
+    cursor: Position,
+) -> (
+    Result<(), PluginError>,
+    EditorState,
+    Position,
+    Vec<UiRequest>,
+) {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content(content);
+    let mut cursor = cursor;
+    let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+    let mut context = CommandContext::new(&mut buffer);
+    let result = plugin.execute(command, &mut context);
+    let requests = context.take_ui_requests();
+    (result, state, cursor, requests)
+}
+
+#[test]
+fn test_new_accepts_minimal_module() {
+    let plugin = load(&module("", "(i32.const 0)")).unwrap();
+    assert_eq!(plugin.name(), "test");
+}
+
+#[test]
+fn test_new_rejects_invalid_module() {
+    let err = load("not a module").err().unwrap();
+    assert!(matches!(err, WasmPluginError::InvalidModule(_)));
+}
+
+#[test]
+fn test_new_requires_memory_export() {
+    let wat = r#"(module (func (export "termide_execute") (result i32) (i32.const 0)))"#;
+    let err = load(wat).err().unwrap();
+    assert!(matches!(err, WasmPluginError::MissingExport("'memory'")));
+}
+
+#[test]
+fn test_new_requires_execute_export() {
+    let err = load(r#"(module (memory (export "memory") 1))"#)
+        .err()
+        .unwrap();
+    assert!(matches!(err, WasmPluginError::MissingExport(_)));
+    assert!(err.to_string().contains("termide_execute"));
+}
+
+#[test]
+fn test_new_rejects_unknown_imports() {
+    let wasi = r#"(import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))"#;
+    let err = load(&module(wasi, "(i32.const 0)")).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "unknown import 'wasi_snapshot_preview1.fd_write'"
+    );
+
+    let unknown = r#"(import "termide" "spawn" (func))"#;
+    assert!(matches!(
+        load(&module(unknown, "(i32.const 0)")),
+        Err(WasmPluginError::UnknownImport { .. })
+    ));
+}
+
+#[test]
+fn test_filesystem_needs_grant() {
+    let import = r#"(import "termide" "read_file" (func (param i32 i32 i32 i32) (result i32)))"#;
+    let wat = module(import, "(i32.const 0)");
+
+    let err = load(&wat).err().unwrap();
+    match err {
+        WasmPluginError::CapabilityDenied {
+            function,
+            capability,
+        } => {
+            assert_eq!(function, "read_file");
+            assert_eq!(capability, Capability::Filesystem);
+        }
+        other => panic!("expected CapabilityDenied, got {:?}", other),
+    }
+
+    let granted: BTreeSet<_> = [Capability::Filesystem].into();
+    assert!(WasmPlugin::new("test", wat.as_bytes(), &granted).is_ok());
+}
+
+#[test]
+fn test_buffer_edit_needs_grant() {
+    let import = r#"(import "termide" "insert" (func (param i32 i32 i32 i32) (result i32)))"#;
+    let read_only: BTreeSet<_> = [Capability::BufferRead, Capability::Ui].into();
+    let err = WasmPlugin::new(
+        "test",
+        module(import, "(i32.const 0)").as_bytes(),
+        &read_only,
+    )
+    .err()
+    .unwrap();
+    assert!(err.to_string().contains("'buffer-edit' capability"));
+}
+
+#[test]
+fn test_register_collects_bindings() {
+    let wat = r#"(module
+        (import "termide" "bind" (func $bind (param i32 i32 i32 i32 i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "Ctrl+k")
+        (data (i32.const 8) "run")
+        (data (i32.const 16) "insert")
+        (func (export "termide_register")
+            (drop (call $bind (i32.const 0) (i32.const 6) (i32.const 8) (i32.const 3) (i32.const 0) (i32.const 0)))
+            (drop (call $bind (i32.const 0) (i32.const 6) (i32.const 8) (i32.const 3) (i32.const 16) (i32.const 6))))
+        (func (export "termide_execute") (result i32) (i32.const 0)))"#;
+    let mut plugin = load(wat).unwrap();
+    let mut recorder = Recorder::default();
+    plugin.register(&mut recorder).unwrap();
+
+    assert_eq!(recorder.bindings.len(), 2);
+    assert_eq!(recorder.bindings[0].sequence, "Ctrl+k");
+    assert_eq!(recorder.bindings[0].command, "test.run");
+    assert_eq!(recorder.bindings[0].context, BindingContext::Global);
+    assert_ne!(recorder.bindings[1].context, BindingContext::Global);
+}
+
+#[test]
+fn test_register_rejects_invalid_binding() {
+    let wat = r#"(module
+        (import "termide" "bind" (func $bind (param i32 i32 i32 i32 i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "Ctrl+k")
+        (data (i32.const 8) "run")
+        (data (i32.const 16) "no where")
+        (func (export "termide_register")
+            (drop (call $bind (i32.const 0) (i32.const 6) (i32.const 8) (i32.const 3) (i32.const 16) (i32.const 8))))
+        (func (export "termide_execute") (result i32) (i32.const 0)))"#;
+    assert!(matches!(load(wat), Err(WasmPluginError::Binding(_))));
+}
+
+#[test]
+fn test_register_cannot_use_buffer() {
+    let wat = r#"(module
+        (import "termide" "line_count" (func $line_count (result i32)))
+        (memory (export "memory") 1)
+        (func (export "termide_register") (drop (call $line_count)))
+        (func (export "termide_execute") (result i32) (i32.const 0)))"#;
+    let err = load(wat).err().unwrap();
+    assert!(matches!(err, WasmPluginError::Registration(_)));
+    assert!(err
+        .to_string()
+        .contains("only available while a command runs"));
+}
+
+#[test]
+fn test_execute_cannot_bind() {
+    let import =
+        r#"(import "termide" "bind" (func $bind (param i32 i32 i32 i32 i32 i32) (result i32)))"#;
+    let body = "(call $bind (i32.const 0) (i32.const 2) (i32.const 0) (i32.const 2) (i32.const 0) (i32.const 0))";
+    let mut plugin = load(&module(import, body)).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    let message = result.unwrap_err().to_string();
+    assert!(
+        message.contains("only available in termide_register"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_execute_edits_buffer_and_moves_cursor() {
+    let imports = r#"
+        (import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))
+        (import "termide" "set_cursor" (func $set_cursor (param i32 i32) (result i32)))
+        (import "termide" "message" (func $message (param i32 i32 i32)))"#;
+    let body = r#"
+        (drop (call $insert (i32.const 1) (i32.const 0) (i32.const 0) (i32.const 2)))
+        (drop (call $set_cursor (i32.const 1) (i32.const 2)))
+        (call $message (i32.const 2) (i32.const 0) (i32.const 2))
+        (i32.const 0)"#;
+    let mut plugin = load(&module(imports, body)).unwrap();
+    let (result, state, cursor, requests) = run(&mut plugin, "run", "one\ntwo", Position::origin());
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(state.buffer().content(), "one\nhitwo");
+    assert_eq!(cursor, Position::new(1, 2));
+    assert_eq!(
+        requests,
+        vec![UiRequest::Message {
+            severity: Severity::Warning,
+            text: "hi".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_execute_reads_buffer() {
+    let imports = r#"
+        (import "termide" "command" (func $command (param i32 i32) (result i32)))
+        (import "termide" "line_count" (func $line_count (result i32)))
+        (import "termide" "line" (func $line (param i32 i32 i32) (result i32)))
+        (import "termide" "cursor_line" (func $cursor_line (result i32)))
+        (import "termide" "cursor_column" (func $cursor_column (result i32)))
+        (import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))"#;
+    // Appends "<line count><cursor line><cursor column>" digits and the
+    // length of the command name to the end of the last line, followed by a
+    // copy of the first line
+    let body = r#"
+        (i32.store8 (i32.const 100) (i32.add (i32.const 48) (call $line_count)))
+        (i32.store8 (i32.const 101) (i32.add (i32.const 48) (call $cursor_line)))
+        (i32.store8 (i32.const 102) (i32.add (i32.const 48) (call $cursor_column)))
+        (i32.store8 (i32.const 103) (i32.add (i32.const 48) (call $command (i32.const 200) (i32.const 16))))
+        (drop (call $line (i32.const 0) (i32.const 104) (i32.const 3)))
+        (drop (call $insert (i32.const 1) (i32.const 3) (i32.const 100) (i32.const 7)))
+        (i32.const 0)"#;
+    let mut plugin = load(&module(imports, body)).unwrap();
+    let (result, state, ..) = run(&mut plugin, "stats", "one\ntwo", Position::new(1, 2));
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(state.buffer().content(), "one\ntwo2125one");
+}
+
+#[test]
+fn test_line_reports_length_without_writing_when_too_small() {
+    let imports = r#"
+        (import "termide" "line" (func $line (param i32 i32 i32) (result i32)))
+        (import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))"#;
+    // Line 0 is 5 bytes but only 2 fit; "hi" at 0 is left untouched
+    let body = r#"
+        (if (i32.ne (call $line (i32.const 0) (i32.const 0) (i32.const 2)) (i32.const 5))
+            (then (return (i32.const 1))))
+        (if (i32.ne (call $line (i32.const 9) (i32.const 0) (i32.const 2)) (i32.const -1))
+            (then (return (i32.const 2))))
+        (drop (call $insert (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 2)))
+        (i32.const 0)"#;
+    let mut plugin = load(&module(imports, body)).unwrap();
+    let (result, state, ..) = run(&mut plugin, "run", "hello", Position::origin());
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(state.buffer().content(), "hihello");
+}
+
+#[test]
+fn test_invalid_position_returns_status() {
+    let import =
+        r#"(import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))"#;
+    let body = "(call $insert (i32.const 5) (i32.const 0) (i32.const 0) (i32.const 2))";
+    let mut plugin = load(&module(import, body)).unwrap();
+    let (result, state, ..) = run(&mut plugin, "run", "one", Position::origin());
+
+    assert_eq!(
+        result,
+        Err(PluginError::Failed(
+            "command 'run' failed with code -1".to_string()
+        ))
+    );
+    assert_eq!(state.buffer().content(), "one");
+}
+
+#[test]
+fn test_delete_orders_positions() {
+    let import =
+        r#"(import "termide" "delete" (func $delete (param i32 i32 i32 i32) (result i32)))"#;
+    let body = "(call $delete (i32.const 1) (i32.const 1) (i32.const 0) (i32.const 1))";
+    let mut plugin = load(&module(import, body)).unwrap();
+    let (result, state, ..) = run(&mut plugin, "run", "one\ntwo", Position::origin());
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(state.buffer().content(), "owo");
+}
+
+#[test]
+fn test_failure_message_is_reported() {
+    let import = r#"(import "termide" "fail" (func $fail (param i32 i32)))"#;
+    let body = "(call $fail (i32.const 0) (i32.const 2)) (i32.const 3)";
+    let mut plugin = load(&module(import, body)).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    assert_eq!(result, Err(PluginError::Failed("hi".to_string())));
+
+    // The failure message does not leak into the next command
+    let mut plugin = load(&module(import, "(i32.const 3)")).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    assert_eq!(
+        result,
+        Err(PluginError::Failed(
+            "command 'run' failed with code 3".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_trap_is_reported() {
+    let mut plugin = load(&module("", "(unreachable)")).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    let message = result.unwrap_err().to_string();
+    assert!(message.starts_with("command 'run' trapped:"), "{}", message);
+
+    // The plugin keeps working after a trap
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_failed_command_discards_edits() {
+    let import =
+        r#"(import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))"#;
+    let insert = "(drop (call $insert (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 2)))";
+    for end in ["(unreachable)", "(loop $forever (br $forever)) (i32.const 0)", "(i32.const 1)"] {
+        let mut plugin = load(&module(import, &format!("{} {}", insert, end))).unwrap();
+        let (result, state, ..) = run(&mut plugin, "run", "text", Position::origin());
+        assert!(result.is_err(), "{}", end);
+        assert_eq!(state.buffer().content(), "text", "{}", end);
+    }
+}
+
+#[test]
+fn test_infinite_loop_runs_out_of_fuel() {
+    let body = "(loop $forever (br $forever)) (i32.const 0)";
+    let mut plugin = load(&module("", body)).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    assert_eq!(
+        result,
+        Err(PluginError::Failed(
+            "command 'run' trapped: instruction budget exceeded".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_infinite_loop_in_start_fails_load() {
+    let wat = r#"(module
+        (memory (export "memory") 1)
+        (func $start (loop $forever (br $forever)))
+        (start $start)
+        (func (export "termide_execute") (result i32) (i32.const 0)))"#;
+    let err = load(wat).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid module: instruction budget exceeded"
+    );
+}
+
+#[test]
+fn test_memory_growth_is_limited() {
+    // 2048 pages are 128 MiB, above the limit; memory.grow returns -1
+    let body = "(i32.ne (memory.grow (i32.const 2048)) (i32.const -1))";
+    let mut plugin = load(&module("", body)).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn test_out_of_bounds_string_traps() {
+    let import = r#"(import "termide" "fail" (func $fail (param i32 i32)))"#;
+    let body = "(call $fail (i32.const 65530) (i32.const 100)) (i32.const 1)";
+    let mut plugin = load(&module(import, body)).unwrap();
+    let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+    assert!(result.unwrap_err().to_string().contains("out of bounds"));
+}
+
+#[test]
+fn test_hostile_string_length_traps() {
+    let import = r#"(import "termide" "fail" (func $fail (param i32 i32)))"#;
+    for len in ["-1", "2147483647"] {
+        let body = format!("(call $fail (i32.const 0) (i32.const {})) (i32.const 1)", len);
+        let mut plugin = load(&module(import, &body)).unwrap();
+        let (result, ..) = run(&mut plugin, "run", "", Position::origin());
+        assert!(result.unwrap_err().to_string().contains("out of bounds"), "{}", len);
+    }
+}
+
+#[test]
+fn test_edits_respect_read_only_buffer() {
+    let import =
+        r#"(import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))"#;
+    let body = "(call $insert (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 2))";
+    let mut plugin = load(&module(import, body)).unwrap();
+
+    let mut state = EditorState::new();
+    state.set_read_only(true);
+    let mut cursor = Position::origin();
+    let mut buffer = ActiveBuffer::new(&mut state, &mut cursor);
+    let mut context = CommandContext::new(&mut buffer);
+    assert_eq!(
+        plugin.execute("run", &mut context),
+        Err(PluginError::Buffer(BufferError::ReadOnly))
+    );
+    assert_eq!(state.buffer().content(), "");
+}
+
+#[test]
+fn test_read_file_with_filesystem_capability() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snippet.txt");
+    fs::write(&path, "snippet").unwrap();
+    let path = path.to_string_lossy().into_owned();
+
+    let imports = r#"
+        (import "termide" "read_file" (func $read_file (param i32 i32 i32 i32) (result i32)))
+        (import "termide" "insert" (func $insert (param i32 i32 i32 i32) (result i32)))"#;
+    let body = format!(
+        r#"(local $len i32)
+        (local.set $len (call $read_file (i32.const 1024) (i32.const {}) (i32.const 4096) (i32.const 1024)))
+        (drop (call $insert (i32.const 0) (i32.const 0) (i32.const 4096) (local.get $len)))
+        (if (i32.ne (call $read_file (i32.const 0) (i32.const 2) (i32.const 4096) (i32.const 1024)) (i32.const -1))
+            (then (unreachable)))
+        (i32.const 0)"#,
+        path.len()
+    );
+    let wat = module(imports, &body).replace(
+        r#"(data (i32.const 0) "hi")"#,
+        &format!(
+            r#"(data (i32.const 0) "hi") (data (i32.const 1024) "{}")"#,
+            path
+        ),
+    );
+    let granted: BTreeSet<_> = [Capability::BufferEdit, Capability::Filesystem].into();
+    let mut plugin = WasmPlugin::new("test", wat.as_bytes(), &granted).unwrap();
+    let (result, state, ..) = run(&mut plugin, "run", "", Position::origin());
+
+    // Reading the missing file "hi" returns -1, otherwise the command traps
+    assert_eq!(result, Ok(()));
+    assert_eq!(state.buffer().content(), "snippet");
+}
+
+#[test]
+fn test_load_wasm_plugins_from_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("b-second.wat"), module("", "(i32.const 0)")).unwrap();
+    fs::write(dir.path().join("a-first.wat"), module("", "(i32.const 0)")).unwrap();
+    fs::write(dir.path().join("broken.wasm"), "garbage").unwrap();
+    fs::write(dir.path().join("notes.txt"), "not a plugin").unwrap();
+
+    let (plugins, warnings) = load_wasm_plugins(dir.path(), &PluginGrants::default());
+    let names: Vec<&str> = plugins.iter().map(|plugin| plugin.name()).collect();
+    assert_eq!(names, vec!["a-first", "b-second"]);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("broken.wasm"));
+}
+
+#[test]
+fn test_load_wasm_plugins_applies_grants() {
+    let dir = tempfile::tempdir().unwrap();
+    let import = r#"(import "termide" "read_file" (func (param i32 i32 i32 i32) (result i32)))"#;
+    fs::write(
+        dir.path().join("reader.wat"),
+        module(import, "(i32.const 0)"),
+    )
+    .unwrap();
+
+    let (plugins, warnings) = load_wasm_plugins(dir.path(), &PluginGrants::default());
+    assert!(plugins.is_empty());
+    assert!(warnings[0].contains("'filesystem' capability"));
+
+    let table: toml::Table = toml::from_str("[reader]\ncapabilities = [\"filesystem\"]").unwrap();
+    let (grants, _) = PluginGrants::parse(&table);
+    let (plugins, warnings) = load_wasm_plugins(dir.path(), &grants);
+    assert_eq!(plugins.len(), 1);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_load_wasm_plugins_missing_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let (plugins, warnings) =
+        load_wasm_plugins(&dir.path().join("plugins"), &PluginGrants::default());
+    assert!(plugins.is_empty());
+    assert!(warnings.is_empty());
+}
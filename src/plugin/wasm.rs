@@ -0,0 +1,723 @@
+//! Sandboxed WebAssembly plugins
+//!
+//! A WebAssembly plugin is a module in the plugins directory, compiled
+//! (`.wasm`) or in text format (`.wat`); the file stem is the plugin name.
+//! Modules run in an interpreter with a memory limit and an instruction budget
+//! per call. They reach the editor only through the host functions they import
+//! from the `termide` module, and each group of host functions needs a
+//! [`Capability`]. A module that imports a function it isn't granted is not
+//! loaded.
+//!
+//! # Module interface
+//!
+//! A module exports its `memory`, `termide_execute() -> i32`, which runs the
+//! command returned by `command`, and optionally `termide_register()`, which
+//! binds keys with `bind`. `termide_execute` returns 0 on success; any other
+//! value fails the command with the message passed to `fail`, if any.
+//!
+//! Strings are UTF-8, passed as pointer and length. Functions returning a
+//! string write it to a buffer given by pointer and capacity and return its
+//! length; nothing is written if it doesn't fit. Positions are zero-based line
+//! and column. Functions returning a status return 0 on success and -1 if a
+//! position is outside the buffer.
+//!
+//! | Function | Capability | Signature |
+//! |---|---|---|
+//! | `command` | | `(ptr, cap) -> len` |
+//! | `fail` | | `(ptr, len)` |
+//! | `bind` | | `(keys_ptr, keys_len, command_ptr, command_len, mode_ptr, mode_len) -> status`; an empty mode binds globally |
+//! | `line_count` | `buffer-read` | `() -> count` |
+//! | `line` | `buffer-read` | `(line, ptr, cap) -> len`, or -1 past the last line |
+//! | `cursor_line`, `cursor_column` | `buffer-read` | `() -> index` |
+//! | `insert` | `buffer-edit` | `(line, column, ptr, len) -> status` |
+//! | `delete` | `buffer-edit` | `(start_line, start_column, end_line, end_column) -> status` |
+//! | `set_cursor` | `buffer-edit` | `(line, column) -> status` |
+//! | `message` | `ui` | `(severity, ptr, len)`; 0 info, 1 success, 2 warning, 3 error |
+//! | `read_file` | `filesystem` | `(path_ptr, path_len, ptr, cap) -> len`, or -1 if unreadable |
+//!
+//! `bind` only works in `termide_register`, and the buffer functions only
+//! while a command runs. Commands edit a copy of the active buffer; the edits
+//! are applied to the buffer once `termide_execute` returns 0, and dropped if
+//! it fails or traps.
+//!
+//! A manifest file with the module's file stem (`hello.toml` for
+//! `hello.wat`) declares the plugin's version, commands and required
+//! capabilities; see [`load_manifest`](super::load_manifest).
+//!
+//! See `examples/plugins/hello.wat` for a complete plugin.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use termide_plugin_api::buffer::{BufferApi, TextPosition, TextRange};
+use termide_plugin_api::input::{BindingError, PluginBinding, PluginInputExtension};
+use termide_plugin_api::manifest::PluginManifest;
+use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+use termide_plugin_api::ui::Severity;
+use thiserror::Error;
+use wasmi::{
+    Caller, Config, Engine, Extern, Instance, Linker, Module, Store, StoreLimits,
+    StoreLimitsBuilder, TrapCode,
+};
+
+use super::{
+    check_capabilities, load_manifest, plugin_binding, Capability, ManifestFileError, PluginGrants,
+};
+use crate::buffer::{Buffer, Position};
+
+/// Module the host functions are imported from
+const HOST_MODULE: &str = "termide";
+
+/// Instructions a plugin may run per call before it is stopped
+pub const FUEL_PER_CALL: u64 = 50_000_000;
+
+/// Largest linear memory a plugin may use, in bytes
+pub const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Status returned by host functions on success
+const OK: i32 = 0;
+
+/// Status returned by host functions on failure
+const FAILED: i32 = -1;
+
+/// Host functions and the capability each one needs
+const HOST_FUNCTIONS: [(&str, Option<Capability>); 12] = [
+    ("command", None),
+    ("fail", None),
+    ("bind", None),
+    ("line_count", Some(Capability::BufferRead)),
+    ("line", Some(Capability::BufferRead)),
+    ("cursor_line", Some(Capability::BufferRead)),
+    ("cursor_column", Some(Capability::BufferRead)),
+    ("insert", Some(Capability::BufferEdit)),
+    ("delete", Some(Capability::BufferEdit)),
+    ("set_cursor", Some(Capability::BufferEdit)),
+    ("message", Some(Capability::Ui)),
+    ("read_file", Some(Capability::Filesystem)),
+];
+
+/// Error type for loading WebAssembly plugins
+#[derive(Debug, Error)]
+pub enum WasmPluginError {
+    /// The module file could not be read
+    #[error("failed to read '{path}': {source}")]
+    Read { path: String, source: io::Error },
+
+    /// The module is not valid WebAssembly, or failed to start
+    #[error("invalid module: {0}")]
+    InvalidModule(String),
+
+    /// The module imports something the host doesn't provide
+    #[error("unknown import '{module}.{name}'")]
+    UnknownImport { module: String, name: String },
+
+    /// The module imports a host function it hasn't been granted
+    #[error("'{function}' needs the '{capability}' capability, which is not granted")]
+    CapabilityDenied {
+        function: String,
+        capability: Capability,
+    },
+
+    /// A required export is missing or has the wrong type
+    #[error("module must export {0}")]
+    MissingExport(&'static str),
+
+    /// `termide_register` trapped
+    #[error("registration failed: {0}")]
+    Registration(String),
+
+    /// A binding made with `bind` is invalid
+    #[error(transparent)]
+    Binding(#[from] BindingError),
+
+    /// The manifest file is invalid or requires capabilities that aren't granted
+    #[error(transparent)]
+    Manifest(#[from] ManifestFileError),
+}
+
+/// An edit made by a running command, replayed on the active buffer
+enum Edit {
+    Insert(TextPosition, String),
+    Delete(TextRange),
+    SetCursor(TextPosition),
+}
+
+/// The active buffer as seen by a running command
+struct Session {
+    /// Copy of the active buffer, kept up to date with the command's edits
+    buffer: Buffer,
+    cursor: Position,
+    edits: Vec<Edit>,
+    messages: Vec<(Severity, String)>,
+}
+
+impl Session {
+    /// Copies the content and cursor of `buffer`
+    fn new(buffer: &dyn BufferApi) -> Self {
+        let lines: Vec<String> = (0..buffer.line_count())
+            .map(|line| buffer.line(line).unwrap_or_default())
+            .collect();
+        Self {
+            buffer: Buffer::from_str(&lines.join("\n")),
+            cursor: buffer.cursor().into(),
+            edits: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Converts a position passed by the module, if it is inside the buffer
+    fn position(&self, line: i32, column: i32) -> Option<Position> {
+        let position = Position::new(usize::try_from(line).ok()?, usize::try_from(column).ok()?);
+        self.buffer.is_valid_position(position).then_some(position)
+    }
+
+    fn line(&self, line: i32) -> Option<String> {
+        let text = self.buffer.get_line(usize::try_from(line).ok()?)?;
+        Some(text.trim_end_matches('\n').to_string())
+    }
+
+    fn insert(&mut self, line: i32, column: i32, text: String) -> i32 {
+        let Some(position) = self.position(line, column) else {
+            return FAILED;
+        };
+        self.buffer.insert_text(&text, position);
+        self.edits.push(Edit::Insert(position.into(), text));
+        OK
+    }
+
+    fn delete(&mut self, start: (i32, i32), end: (i32, i32)) -> i32 {
+        let (Some(start), Some(end)) =
+            (self.position(start.0, start.1), self.position(end.0, end.1))
+        else {
+            return FAILED;
+        };
+        let range = TextRange::new(start.into(), end.into());
+        self.buffer
+            .delete_range(range.start.into(), range.end.into());
+        self.edits.push(Edit::Delete(range));
+        OK
+    }
+
+    fn set_cursor(&mut self, line: i32, column: i32) -> i32 {
+        let Some(position) = self.position(line, column) else {
+            return FAILED;
+        };
+        self.cursor = position;
+        self.edits.push(Edit::SetCursor(position.into()));
+        OK
+    }
+
+    /// Replays the edits and messages of the command on `context`
+    fn apply(self, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+        for (severity, text) in self.messages {
+            context.show_message(severity, text);
+        }
+        let buffer = context.buffer();
+        for edit in self.edits {
+            match edit {
+                Edit::Insert(position, text) => {
+                    buffer.insert(position, &text)?;
+                }
+                Edit::Delete(range) => buffer.delete(range)?,
+                Edit::SetCursor(position) => buffer.set_cursor(position)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Data the host functions of a plugin work on
+struct HostState {
+    limits: StoreLimits,
+    plugin: String,
+    /// Bindings made with `bind`; `None` outside `termide_register`
+    bindings: Option<Vec<Result<PluginBinding, BindingError>>>,
+    /// Name of the running command
+    command: String,
+    /// Message passed to `fail` by the running command
+    failure: Option<String>,
+    /// The active buffer while a command runs
+    session: Option<Session>,
+}
+
+/// A plugin running in a WebAssembly sandbox
+///
+/// # Examples
+///
+/// ```
+/// use termide::plugin::{Capability, WasmPlugin};
+/// use termide_plugin_api::plugin::Plugin;
+///
+/// let module = r#"(module
+///     (memory (export "memory") 1)
+///     (func (export "termide_execute") (result i32) (i32.const 0)))"#;
+/// let plugin = WasmPlugin::new("noop", module.as_bytes(), &Capability::DEFAULTS.into()).unwrap();
+/// assert_eq!(plugin.name(), "noop");
+/// ```
+pub struct WasmPlugin {
+    name: String,
+    store: Store<HostState>,
+    instance: Instance,
+    /// Bindings made by `termide_register` at load time
+    bindings: Vec<PluginBinding>,
+    manifest: Option<PluginManifest>,
+}
+
+impl WasmPlugin {
+    /// Loads a module, in binary or text format, granting it `capabilities`
+    ///
+    /// Runs the module's `termide_register` export, if any, to collect its
+    /// keybindings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module is invalid, imports functions that are
+    /// unknown or not granted, lacks a required export, or fails to register.
+    pub fn new(
+        name: &str,
+        wasm: &[u8],
+        capabilities: &BTreeSet<Capability>,
+    ) -> Result<Self, WasmPluginError> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)
+            .map_err(|e| WasmPluginError::InvalidModule(e.to_string()))?;
+        check_imports(&module, capabilities)?;
+
+        let state = HostState {
+            limits: StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build(),
+            plugin: name.to_string(),
+            bindings: None,
+            command: String::new(),
+            failure: None,
+            session: None,
+        };
+        let mut store = Store::new(&engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(FUEL_PER_CALL)
+            .expect("fuel metering is enabled");
+        let instance = linker(&engine)
+            .instantiate_and_start(&mut store, &module)
+            .map_err(|e| WasmPluginError::InvalidModule(describe(&e)))?;
+
+        if instance.get_memory(&store, "memory").is_none() {
+            return Err(WasmPluginError::MissingExport("'memory'"));
+        }
+        if instance
+            .get_typed_func::<(), i32>(&store, "termide_execute")
+            .is_err()
+        {
+            return Err(WasmPluginError::MissingExport("'termide_execute() -> i32'"));
+        }
+
+        let mut plugin = Self {
+            name: name.to_string(),
+            store,
+            instance,
+            bindings: Vec::new(),
+            manifest: None,
+        };
+        plugin.bindings = plugin.collect_bindings()?;
+        Ok(plugin)
+    }
+
+    /// Loads the module at `path`, named after its file stem, with the
+    /// manifest next to it, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module or manifest cannot be read, if the
+    /// manifest requires capabilities that aren't granted, or as
+    /// [`WasmPlugin::new`].
+    pub fn from_file(path: &Path, grants: &PluginGrants) -> Result<Self, WasmPluginError> {
+        let wasm = fs::read(path).map_err(|source| WasmPluginError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let capabilities = grants.capabilities(&name);
+
+        let manifest_path = path.with_extension("toml");
+        let manifest = if manifest_path.is_file() {
+            let manifest = load_manifest(&manifest_path)?;
+            check_capabilities(&manifest, &capabilities)?;
+            Some(manifest)
+        } else {
+            None
+        };
+
+        Ok(Self::new(&name, &wasm, &capabilities)?.with_manifest(manifest))
+    }
+
+    /// Attaches `manifest`, which the plugin host checks when loading the plugin
+    pub fn with_manifest(mut self, manifest: Option<PluginManifest>) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    /// Runs `termide_register`, if exported, and returns the bindings it made
+    fn collect_bindings(&mut self) -> Result<Vec<PluginBinding>, WasmPluginError> {
+        if self
+            .instance
+            .get_export(&self.store, "termide_register")
+            .is_none()
+        {
+            return Ok(Vec::new());
+        }
+        let register = self
+            .instance
+            .get_typed_func::<(), ()>(&self.store, "termide_register")
+            .map_err(|_| WasmPluginError::MissingExport("'termide_register()'"))?;
+
+        self.store.data_mut().bindings = Some(Vec::new());
+        self.refuel();
+        let result = register.call(&mut self.store, ());
+        let bindings = self.store.data_mut().bindings.take().unwrap_or_default();
+
+        result.map_err(|e| WasmPluginError::Registration(describe(&e)))?;
+        Ok(bindings.into_iter().collect::<Result<_, _>>()?)
+    }
+
+    /// Resets the instruction budget for the next call
+    fn refuel(&mut self) {
+        self.store
+            .set_fuel(FUEL_PER_CALL)
+            .expect("fuel metering is enabled");
+    }
+}
+
+impl Plugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn manifest(&self) -> Option<PluginManifest> {
+        self.manifest.clone()
+    }
+
+    fn register(&mut self, registry: &mut dyn PluginInputExtension) -> Result<(), BindingError> {
+        for binding in &self.bindings {
+            registry.register_keybinding(binding.clone())?;
+        }
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        command: &str,
+        context: &mut CommandContext<'_>,
+    ) -> Result<(), PluginError> {
+        let state = self.store.data_mut();
+        state.command = command.to_string();
+        state.failure = None;
+        state.session = Some(Session::new(context.buffer()));
+
+        self.refuel();
+        let result = self
+            .instance
+            .get_typed_func::<(), i32>(&self.store, "termide_execute")
+            .and_then(|execute| execute.call(&mut self.store, ()));
+
+        let state = self.store.data_mut();
+        let failure = state.failure.take();
+        let mut session = state
+            .session
+            .take()
+            .expect("session is set while a command runs");
+        // A command that fails or traps leaves the buffer as it was; its
+        // messages are still shown
+        if !matches!(result, Ok(OK)) {
+            session.edits.clear();
+        }
+        session.apply(context)?;
+
+        match result {
+            Ok(OK) => Ok(()),
+            Ok(code) => Err(PluginError::Failed(failure.unwrap_or_else(|| {
+                format!("command '{}' failed with code {}", command, code)
+            }))),
+            Err(e) => Err(PluginError::Failed(format!(
+                "command '{}' trapped: {}",
+                command,
+                describe(&e)
+            ))),
+        }
+    }
+}
+
+/// Loads the WebAssembly plugins in `dir`, in file name order
+///
+/// Returns the plugins and a warning for each module that could not be
+/// loaded. A missing directory holds no plugins.
+pub fn load_wasm_plugins(dir: &Path, grants: &PluginGrants) -> (Vec<Box<dyn Plugin>>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "wasm" || extension == "wat")
+        })
+        .collect();
+    paths.sort();
+
+    let mut plugins: Vec<Box<dyn Plugin>> = Vec::new();
+    let mut warnings = Vec::new();
+    for path in paths {
+        match WasmPlugin::from_file(&path, grants) {
+            Ok(plugin) => plugins.push(Box::new(plugin)),
+            Err(e) => warnings.push(format!("Plugin '{}' not loaded: {}", path.display(), e)),
+        }
+    }
+    (plugins, warnings)
+}
+
+/// Returns an error unless every import of `module` is a granted host function
+fn check_imports(
+    module: &Module,
+    capabilities: &BTreeSet<Capability>,
+) -> Result<(), WasmPluginError> {
+    for import in module.imports() {
+        let known = HOST_FUNCTIONS
+            .iter()
+            .find(|(name, _)| import.module() == HOST_MODULE && *name == import.name());
+        match known {
+            None => {
+                return Err(WasmPluginError::UnknownImport {
+                    module: import.module().to_string(),
+                    name: import.name().to_string(),
+                })
+            }
+            Some((name, Some(capability))) if !capabilities.contains(capability) => {
+                return Err(WasmPluginError::CapabilityDenied {
+                    function: name.to_string(),
+                    capability: *capability,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Describes a trap or host error for the user
+fn describe(error: &wasmi::Error) -> String {
+    match error.as_trap_code() {
+        Some(TrapCode::OutOfFuel) => "instruction budget exceeded".to_string(),
+        _ => error.to_string(),
+    }
+}
+
+/// Reads `len` bytes at `ptr` from the module's memory as a string
+fn read_string(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Result<String, wasmi::Error> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmi::Error::new("module does not export its memory"))?;
+    // Check the range before copying, so a hostile length cannot make the
+    // host allocate more than the module's memory
+    let start = ptr as u32 as usize;
+    let bytes = usize::try_from(len)
+        .ok()
+        .and_then(|len| memory.data(caller).get(start..)?.get(..len))
+        .ok_or_else(|| wasmi::Error::new("string out of bounds"))?;
+    String::from_utf8(bytes.to_vec()).map_err(|_| wasmi::Error::new("string is not valid UTF-8"))
+}
+
+/// Writes `text` to the buffer at `ptr` if it fits in `cap` bytes, and
+/// returns its length
+fn write_string(
+    caller: &mut Caller<'_, HostState>,
+    ptr: i32,
+    cap: i32,
+    text: &str,
+) -> Result<i32, wasmi::Error> {
+    let len = i32::try_from(text.len()).map_err(|_| wasmi::Error::new("string too long"))?;
+    if len <= cap {
+        let memory = caller
+            .get_export("memory")
+            .and_then(Extern::into_memory)
+            .ok_or_else(|| wasmi::Error::new("module does not export its memory"))?;
+        memory
+            .write(&mut *caller, ptr as u32 as usize, text.as_bytes())
+            .map_err(|_| wasmi::Error::new("buffer out of bounds"))?;
+    }
+    Ok(len)
+}
+
+/// Returns the session of the running command
+fn session<'a>(caller: &'a mut Caller<'_, HostState>) -> Result<&'a mut Session, wasmi::Error> {
+    caller.data_mut().session.as_mut().ok_or_else(|| {
+        wasmi::Error::new("buffer functions are only available while a command runs")
+    })
+}
+
+/// Defines the host functions
+fn linker(engine: &Engine) -> Linker<HostState> {
+    let mut linker = Linker::new(engine);
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "command",
+            |mut caller: Caller<'_, HostState>, ptr: i32, cap: i32| {
+                let command = caller.data().command.clone();
+                write_string(&mut caller, ptr, cap, &command)
+            },
+        )
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "fail",
+                |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                    let message = read_string(&caller, ptr, len)?;
+                    caller.data_mut().failure = Some(message);
+                    Ok(())
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "bind",
+                |mut caller: Caller<'_, HostState>,
+                 keys_ptr: i32,
+                 keys_len: i32,
+                 command_ptr: i32,
+                 command_len: i32,
+                 mode_ptr: i32,
+                 mode_len: i32| {
+                    let keys = read_string(&caller, keys_ptr, keys_len)?;
+                    let command = read_string(&caller, command_ptr, command_len)?;
+                    let mode = read_string(&caller, mode_ptr, mode_len)?;
+                    let state = caller.data_mut();
+                    let mode = (!mode.is_empty()).then_some(mode.as_str());
+                    let binding = plugin_binding(&state.plugin, keys, command, mode);
+                    let bindings = state.bindings.as_mut().ok_or_else(|| {
+                        wasmi::Error::new("bind is only available in termide_register")
+                    })?;
+                    let status = if binding.is_ok() { OK } else { FAILED };
+                    bindings.push(binding);
+                    Ok(status)
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "line_count",
+                |mut caller: Caller<'_, HostState>| {
+                    Ok(session(&mut caller)?.buffer.line_count() as i32)
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "line",
+                |mut caller: Caller<'_, HostState>, line: i32, ptr: i32, cap: i32| match session(
+                    &mut caller,
+                )?
+                .line(line)
+                {
+                    Some(text) => write_string(&mut caller, ptr, cap, &text),
+                    None => Ok(FAILED),
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "cursor_line",
+                |mut caller: Caller<'_, HostState>| Ok(session(&mut caller)?.cursor.line as i32),
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "cursor_column",
+                |mut caller: Caller<'_, HostState>| Ok(session(&mut caller)?.cursor.column as i32),
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "insert",
+                |mut caller: Caller<'_, HostState>, line: i32, column: i32, ptr: i32, len: i32| {
+                    let text = read_string(&caller, ptr, len)?;
+                    Ok(session(&mut caller)?.insert(line, column, text))
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "delete",
+                |mut caller: Caller<'_, HostState>,
+                 start_line: i32,
+                 start_column: i32,
+                 end_line: i32,
+                 end_column: i32| {
+                    Ok(session(&mut caller)?
+                        .delete((start_line, start_column), (end_line, end_column)))
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "set_cursor",
+                |mut caller: Caller<'_, HostState>, line: i32, column: i32| {
+                    Ok(session(&mut caller)?.set_cursor(line, column))
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "message",
+                |mut caller: Caller<'_, HostState>, severity: i32, ptr: i32, len: i32| {
+                    let severity = match severity {
+                        0 => Severity::Info,
+                        1 => Severity::Success,
+                        2 => Severity::Warning,
+                        3 => Severity::Error,
+                        other => {
+                            return Err(wasmi::Error::new(format!("invalid severity {}", other)))
+                        }
+                    };
+                    let text = read_string(&caller, ptr, len)?;
+                    session(&mut caller)?.messages.push((severity, text));
+                    Ok(())
+                },
+            )
+        })
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "read_file",
+                |mut caller: Caller<'_, HostState>,
+                 path_ptr: i32,
+                 path_len: i32,
+                 ptr: i32,
+                 cap: i32| {
+                    let path = read_string(&caller, path_ptr, path_len)?;
+                    match fs::read_to_string(path) {
+                        Ok(content) => write_string(&mut caller, ptr, cap, &content),
+                        Err(_) => Ok(FAILED),
+                    }
+                },
+            )
+        })
+        .expect("host function names are unique");
+    linker
+}
@@ -2,37 +2,314 @@
 
 use super::*;
 use std::io::Write;
+use std::path::PathBuf;
 use tempfile::NamedTempFile;
 use termide::buffer::Buffer;
 use termide::buffer::Position;
+use termide::file_io::BackupMode;
 use termide::input::bindings::register_default_bindings;
 use termide::input::config::load_user_keybindings;
 use termide::input::input_handler::InputHandler;
 
+/// Builds an argument list with the program name prepended
+fn cli(args: &[&str]) -> Vec<String> {
+    std::iter::once("termide")
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect()
+}
+
+/// Parses arguments that are expected to start the editor
+fn run_args(args: &[&str]) -> CliArgs {
+    match parse_args(&cli(args)).unwrap() {
+        CliAction::Run(cli_args) => cli_args,
+        other => panic!("expected Run, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_parse_args_no_file() {
-    let args = vec!["termide".to_string()];
-    let result = parse_args(&args).unwrap();
-    assert_eq!(result, None);
+    let result = run_args(&[]);
+    assert!(result.files.is_empty());
+    assert!(!result.readonly);
+    assert!(!result.stdin);
 }
 
 #[test]
 fn test_parse_args_with_file() {
-    let args = vec!["termide".to_string(), "test.txt".to_string()];
-    let result = parse_args(&args).unwrap();
-    assert_eq!(result, Some("test.txt".to_string()));
+    let result = run_args(&["test.txt"]);
+    assert_eq!(result.files, vec![cli::FileArg::new("test.txt")]);
 }
 
 #[test]
-fn test_parse_args_too_many() {
-    let args = vec![
-        "termide".to_string(),
-        "file1.txt".to_string(),
-        "file2.txt".to_string(),
-    ];
-    let result = parse_args(&args);
-    assert!(result.is_err());
-    assert!(result.unwrap_err().to_string().contains("Usage:"));
+fn test_parse_args_multiple_files() {
+    let result = run_args(&["file1.txt", "file2.txt", "file3.txt"]);
+    let paths: Vec<_> = result.files.iter().map(|f| f.path.to_str().unwrap()).collect();
+    assert_eq!(paths, vec!["file1.txt", "file2.txt", "file3.txt"]);
+}
+
+#[test]
+fn test_parse_args_readonly() {
+    let result = run_args(&["--readonly", "test.txt"]);
+    assert_eq!(result.files, vec![cli::FileArg::new("test.txt")]);
+    assert!(result.readonly);
+
+    assert!(run_args(&["test.txt", "-R"]).readonly);
+}
+
+#[test]
+fn test_parse_args_unknown_option() {
+    let err = parse_args(&cli(&["--bogus"])).unwrap_err().to_string();
+    assert!(err.contains("Unknown option '--bogus'"));
+    assert!(err.contains("Usage:"));
+}
+
+#[test]
+fn test_parse_args_suggests_similar_option() {
+    let err = parse_args(&cli(&["--readonyl"])).unwrap_err().to_string();
+    assert!(err.contains("Did you mean '--readonly'?"));
+
+    let err = parse_args(&cli(&["-theme", "dark"])).unwrap_err().to_string();
+    assert!(err.contains("Did you mean '--theme'?"));
+}
+
+#[test]
+fn test_parse_args_help_and_version() {
+    assert_eq!(parse_args(&cli(&["--help"])).unwrap(), CliAction::Help);
+    assert_eq!(parse_args(&cli(&["file.txt", "-h"])).unwrap(), CliAction::Help);
+    assert_eq!(parse_args(&cli(&["--version"])).unwrap(), CliAction::Version);
+    assert_eq!(parse_args(&cli(&["-V"])).unwrap(), CliAction::Version);
+
+    assert!(version().starts_with("termide "));
+    assert!(usage("termide").contains("--config <path>"));
+
+    let err = parse_args(&cli(&["--help=yes"])).unwrap_err().to_string();
+    assert!(err.contains("does not take a value"));
+}
+
+#[test]
+fn test_parse_args_line_and_column_suffix() {
+    let result = run_args(&["/nonexistent/src/main.rs:12:5", "/nonexistent/lib.rs:7"]);
+    assert_eq!(result.files[0].path, PathBuf::from("/nonexistent/src/main.rs"));
+    assert_eq!(result.files[0].cursor(), Position::new(11, 4));
+    assert_eq!(result.files[1].path, PathBuf::from("/nonexistent/lib.rs"));
+    assert_eq!(result.files[1].line, Some(7));
+    assert_eq!(result.files[1].column, None);
+}
+
+#[test]
+fn test_parse_args_suffix_ignored_for_existing_file_or_non_numeric() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let literal = dir.path().join("notes:10");
+    std::fs::write(&literal, "text").unwrap();
+
+    let result = run_args(&[literal.to_str().unwrap(), "/nonexistent/a:b"]);
+    assert_eq!(result.files[0], cli::FileArg::new(&literal));
+    assert_eq!(result.files[1], cli::FileArg::new("/nonexistent/a:b"));
+}
+
+#[test]
+fn test_parse_args_plus_line() {
+    let result = run_args(&["+42", "a.txt", "b.txt"]);
+    assert_eq!(result.files[0].line, Some(42));
+    assert_eq!(result.files[1].line, None);
+
+    let result = run_args(&["+", "a.txt"]);
+    assert_eq!(result.files[0].line, Some(usize::MAX));
+
+    let err = parse_args(&cli(&["+abc", "a.txt"])).unwrap_err().to_string();
+    assert!(err.contains("Invalid line number '+abc'"));
+
+    let err = parse_args(&cli(&["a.txt", "+3"])).unwrap_err().to_string();
+    assert!(err.contains("must be followed by a file name"));
+}
+
+#[test]
+fn test_parse_args_config_and_theme() {
+    let result = run_args(&["--config", "/tmp/custom.toml", "--theme=Light"]);
+    assert_eq!(result.config, Some(PathBuf::from("/tmp/custom.toml")));
+    assert_eq!(result.theme, Some("light".to_string()));
+
+    let err = parse_args(&cli(&["--config"])).unwrap_err().to_string();
+    assert!(err.contains("Option '--config' requires a value"));
+
+    let err = parse_args(&cli(&["--theme", "neon"])).unwrap_err().to_string();
+    assert!(err.contains("Invalid theme 'neon'"));
+    assert!(err.contains("dark, light"));
+}
+
+#[test]
+fn test_parse_args_stdin_and_double_dash() {
+    let result = run_args(&["-", "--", "-R", "+5"]);
+    assert!(result.stdin);
+    assert!(!result.readonly);
+    let paths: Vec<_> = result.files.iter().map(|f| f.path.to_str().unwrap()).collect();
+    assert_eq!(paths, vec!["-R", "+5"]);
+
+    let err = parse_args(&cli(&["-", "-"])).unwrap_err().to_string();
+    assert!(err.contains("only be read once"));
+}
+
+#[test]
+fn test_parse_args_stdout() {
+    let result = run_args(&["-", "--stdout"]);
+    assert!(result.stdin);
+    assert!(result.stdout);
+    assert!(!run_args(&["-"]).stdout);
+
+    let err = parse_args(&cli(&["--stdout=file"])).unwrap_err().to_string();
+    assert!(err.contains("does not take a value"));
+}
+
+#[test]
+fn test_open_buffers_opens_every_file_at_its_position() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let first = dir.path().join("first.txt");
+    let second = dir.path().join("second.txt");
+    std::fs::write(&first, "one\ntwo\nthree\n").unwrap();
+    std::fs::write(&second, "alpha\nbeta\n").unwrap();
+
+    let cli_args = run_args(&[
+        &format!("{}:2:3", first.display()),
+        "+99",
+        second.to_str().unwrap(),
+        "--readonly",
+    ]);
+    let (mut state, cursor) = open_buffers(&cli_args).unwrap();
+
+    assert_eq!(state.buffer_count(), 2);
+    assert_eq!(cursor, Position::new(1, 2));
+    assert!(state.buffers().iter().all(|b| b.is_read_only()));
+
+    // The jump target past the end is clamped to the last line
+    let cursor = state.next_buffer(cursor);
+    assert_eq!(state.buffer().file_path(), Some(&second));
+    assert_eq!(cursor, Position::new(2, 0));
+}
+
+#[test]
+fn test_execute_command_switches_buffers() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let first = dir.path().join("first.txt");
+    let second = dir.path().join("second.txt");
+    std::fs::write(&first, "first file\n").unwrap();
+    std::fs::write(&second, "second\n").unwrap();
+
+    let mut state = EditorState::from_file(&first).unwrap();
+    state.open_file(&second, Position::origin()).unwrap();
+    let mut cursor = Position::new(0, 5);
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::NextBuffer,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.active_buffer_index(), 1);
+    assert_eq!(cursor, Position::origin());
+
+    // Switching back restores the cursor of the first buffer
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::PreviousBuffer,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.active_buffer_index(), 0);
+    assert_eq!(cursor, Position::new(0, 5));
+}
+
+#[test]
+fn test_execute_command_blocks_edits_when_read_only() {
+    let mut state = EditorState::new();
+    state.set_read_only(true);
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::InsertChar('x'),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+
+    assert_eq!(state.buffer().content(), "");
+    assert_eq!(cursor, Position::origin());
+    assert_eq!(state.status_message(), Some(READ_ONLY_MESSAGE));
+
+    // Navigation still works
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::MoveToLineEnd,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.status_message(), Some(READ_ONLY_MESSAGE));
+}
+
+#[test]
+fn test_read_only_blocks_chained_edits_and_writing_elsewhere() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("copy.txt");
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("text");
+    state.set_read_only(true);
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let chain = EditorCommand::Chain(vec![EditorCommand::MoveToLineEnd, EditorCommand::InsertChar('!')]);
+    execute_command(&mut state, &mut cursor, chain, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.buffer().content(), "text");
+    assert!(state.status_message().is_some_and(|message| message.contains("read-only")));
+
+    state.enter_prompt("Save as: ".to_string());
+    for ch in path.display().to_string().chars() {
+        state.prompt_insert_char(ch);
+    }
+    execute_command(&mut state, &mut cursor, EditorCommand::AcceptPrompt, &mut input_handler, &mut plugins).unwrap();
+    assert!(!path.exists());
+    assert!(state.status_message().is_some_and(|message| message.contains("read-only")));
+}
+
+#[test]
+fn test_toggle_read_only_allows_editing() {
+    let mut state = EditorState::new();
+    state.set_read_only(true);
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::ToggleReadOnly,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert!(!state.is_read_only());
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::InsertChar('x'),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.buffer().content(), "x");
 }
 
 #[test]
@@ -156,3 +433,1150 @@ mod config_integration {
         // The actual priority resolution is tested in registry tests
     }
 }
+
+#[test]
+fn test_insert_tab_uses_tab_character_by_default() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::InsertTab,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+
+    assert_eq!(state.buffer().content(), "\t");
+    assert_eq!(cursor, Position::new(0, 1));
+}
+
+#[test]
+fn test_insert_tab_expands_to_spaces() {
+    let mut state = EditorState::new();
+    state.set_settings(EditorSettings {
+        expand_tabs: true,
+        tab_width: 4,
+        ..EditorSettings::default()
+    });
+    state.handle_char_insert('a', Position::origin());
+    let mut cursor = Position::new(0, 1);
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::InsertTab,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+
+    assert_eq!(state.buffer().content(), "a   ");
+    assert_eq!(cursor, Position::new(0, 4));
+}
+
+#[test]
+fn test_apply_settings_updates_state_and_theme() {
+    let mut state = EditorState::new();
+    let mut input_handler = InputHandler::new();
+    let settings = EditorSettings {
+        theme: "light".to_string(),
+        line_numbers: true,
+        ..EditorSettings::default()
+    };
+
+    let theme = apply_settings(&mut state, &mut input_handler, settings.clone(), None);
+    assert_eq!(theme, Theme::light());
+    assert_eq!(state.settings(), &settings);
+
+    // The command-line theme wins over the configured one
+    let theme = apply_settings(&mut state, &mut input_handler, settings, Some("dark"));
+    assert_eq!(theme, Theme::dark());
+}
+
+#[test]
+fn test_format_config_warnings() {
+    assert_eq!(format_config_warnings(&[]), None);
+    assert_eq!(
+        format_config_warnings(&["editor.tab_width: bad".to_string()]),
+        Some("⚠ editor.tab_width: bad".to_string())
+    );
+    assert_eq!(
+        format_config_warnings(&["first".to_string(), "second".to_string()]),
+        Some("⚠ first (+1 more)".to_string())
+    );
+}
+
+#[test]
+fn test_parse_args_show_config() {
+    let cli_args = run_args(&["--show-config", "src/main.rs"]);
+    assert!(cli_args.show_config);
+    assert_eq!(cli_args.files, vec![cli::FileArg::new("src/main.rs")]);
+
+    assert!(parse_args(&cli(&["--show-config=yes"])).is_err());
+}
+
+#[test]
+fn test_parse_args_list_plugins() {
+    assert!(run_args(&["--list-plugins"]).list_plugins);
+    assert!(!run_args(&[]).list_plugins);
+    assert!(parse_args(&cli(&["--list-plugins=all"])).is_err());
+}
+
+#[test]
+fn test_describe_plugins() {
+    let dir = tempfile::tempdir().unwrap();
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/plugins");
+    for file in ["hello.wat", "hello.toml"] {
+        std::fs::copy(examples.join(file), dir.path().join(file)).unwrap();
+    }
+    // Written against a future API version
+    std::fs::copy(examples.join("hello.wat"), dir.path().join("future.wat")).unwrap();
+    std::fs::write(
+        dir.path().join("future.toml"),
+        "name = \"future\"\nversion = \"1.0.0\"\napi_version = \"99.0\"\n",
+    )
+    .unwrap();
+
+    let lines = describe_plugins(None, Some(dir.path()));
+    assert_eq!(
+        lines,
+        vec![
+            format!("plugin API: {}", API_VERSION),
+            "hello 1.0.0 (plugin API 0.1)".to_string(),
+            "  hello.greet  Insert a greeting at the cursor".to_string(),
+            "  hello.duplicate_line  Copy the cursor line below it".to_string(),
+            "  capabilities: buffer-read, buffer-edit, ui".to_string(),
+            format!(
+                "warning: plugin 'future' is incompatible: written for plugin API 99.0, but this editor provides {}",
+                termide_plugin_api::manifest::Version::current()
+            ),
+        ]
+    );
+
+    let empty = tempfile::tempdir().unwrap();
+    assert_eq!(
+        describe_plugins(None, Some(empty.path())),
+        vec![
+            format!("plugin API: {}", API_VERSION),
+            "no plugins found".to_string(),
+        ]
+    );
+}
+
+/// Creates a user config and a project directory containing `.termide.toml`
+fn create_config_layers(user: &str, project: &str) -> (tempfile::TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let user_path = dir.path().join("config.toml");
+    std::fs::write(&user_path, user).unwrap();
+    std::fs::create_dir(dir.path().join("project")).unwrap();
+    std::fs::write(dir.path().join("project/.termide.toml"), project).unwrap();
+    (dir, user_path)
+}
+
+#[test]
+fn test_describe_config_names_layers() {
+    let (dir, user_path) = create_config_layers(
+        "[editor]\nline_numbers = true\n",
+        "[editor]\ntab_width = 2\n[language.rust]\nexpand_tabs = true\n",
+    );
+    let file = dir.path().join("project/lib.rs");
+
+    let lines = describe_config(Some(user_path.clone()), Some(&file)).unwrap();
+
+    assert_eq!(lines[0], format!("user config: {}", user_path.display()));
+    assert_eq!(
+        lines[1],
+        format!("project config: {}", dir.path().join("project/.termide.toml").display())
+    );
+    assert_eq!(lines[2], "language: rust");
+    assert!(lines.contains(&"tab_width = 2 (project config)".to_string()));
+    assert!(lines.contains(&"expand_tabs = true (project config [language.rust])".to_string()));
+    assert!(lines.contains(&"line_numbers = true (user config)".to_string()));
+    assert!(lines.contains(&"scroll_margin = 0 (default)".to_string()));
+}
+
+#[test]
+fn test_config_session_follows_active_buffer() {
+    let (dir, user_path) = create_config_layers(
+        "[editor]\ntab_width = 8\n",
+        "[editor]\ntab_width = 2\n\n[[keybindings]]\nsequence = \"Ctrl+b\"\ncommand = \"quit\"\n",
+    );
+    let project_file = dir.path().join("project/main.rs");
+    let outside_file = dir.path().join("notes.txt");
+    std::fs::write(&project_file, "").unwrap();
+    std::fs::write(&outside_file, "").unwrap();
+
+    let mut state = EditorState::from_file(&project_file).unwrap();
+    state.open_file(&outside_file, Position::new(0, 0)).unwrap();
+    state.switch_buffer(0, Position::new(0, 0));
+    let mut input_handler = InputHandler::new();
+    let mut config = ConfigSession::new(Some(user_path), None);
+
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    assert_eq!(result.unwrap().loaded, 1);
+    assert_eq!(state.settings().tab_width, 2);
+    assert!(!config.is_stale(&state));
+
+    // The other buffer is outside the project: only the user config applies
+    state.switch_buffer(1, Position::new(0, 0));
+    assert!(config.is_stale(&state));
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    assert_eq!(result.unwrap().loaded, 0);
+    assert_eq!(state.settings().tab_width, 8);
+    assert_eq!(
+        input_handler.registry_mut().unregister_by_priority(Priority::Project),
+        0
+    );
+}
+
+#[test]
+fn test_config_session_follows_save_as_to_another_project() {
+    let (dir, user_path) = create_config_layers("[editor]\ntab_width = 8\n", "[editor]\ntab_width = 2\n");
+    let outside_file = dir.path().join("notes.txt");
+    std::fs::write(&outside_file, "").unwrap();
+
+    let mut state = EditorState::from_file(&outside_file).unwrap();
+    let mut input_handler = InputHandler::new();
+    let mut config = ConfigSession::new(Some(user_path), None);
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    result.unwrap();
+    assert_eq!(state.settings().tab_width, 8);
+
+    // Same buffer, new path inside the project
+    state.save_as(&dir.path().join("project/notes.txt")).unwrap();
+    assert!(config.is_stale(&state));
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    result.unwrap();
+    assert_eq!(state.settings().tab_width, 2);
+    assert!(!config.is_stale(&state));
+}
+
+#[test]
+fn test_config_warning_message_reports_errors() {
+    let ok = Ok(LoadResult {
+        loaded: 3,
+        warnings: Vec::new(),
+    });
+    assert_eq!(config_warning_message(&ok), None);
+
+    let failed: Result<LoadResult, ConfigError> = Err(ConfigError::ReadError {
+        path: "config.toml".to_string(),
+        source: io::Error::from(io::ErrorKind::NotFound),
+    });
+    let (severity, message) = config_warning_message(&failed).unwrap();
+    assert_eq!(severity, Severity::Error);
+    assert!(message.starts_with("⚠ Config reload failed:"));
+}
+
+#[test]
+fn test_list_keybindings_opens_read_only_report() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::new(0, 0);
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::ListKeybindings,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+
+    assert_eq!(state.buffer_count(), 2);
+    assert_eq!(state.active_buffer_index(), 1);
+    assert!(state.is_read_only());
+    assert!(state.buffer().content().contains("keybindings.list"));
+    assert_eq!(cursor, Position::origin());
+}
+
+#[test]
+fn test_list_keybindings_refreshes_open_report() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::new(0, 0);
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+
+    execute_command(&mut state, &mut cursor, EditorCommand::ListKeybindings, &mut input_handler, &mut plugins).unwrap();
+    execute_command(&mut state, &mut cursor, EditorCommand::PreviousBuffer, &mut input_handler, &mut plugins).unwrap();
+    input_handler
+        .registry_mut()
+        .register(termide::input::keybinding::KeyBinding::new(
+            "Ctrl+b".parse().unwrap(),
+            EditorCommand::UserCommand("refreshed".to_string()),
+            termide::input::keybinding::BindingContext::Global,
+            Priority::User,
+        ))
+        .unwrap();
+    execute_command(&mut state, &mut cursor, EditorCommand::ListKeybindings, &mut input_handler, &mut plugins).unwrap();
+
+    assert_eq!(state.buffer_count(), 2);
+    assert_eq!(state.active_buffer_index(), 1);
+    assert!(state.buffer().content().contains("user.refreshed"));
+    assert!(!state.buffer().is_dirty());
+}
+
+#[test]
+fn test_describe_key_shows_binding_without_executing() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+
+    process_key_event(
+        &mut state,
+        &mut cursor,
+        KeyEvent::new(KeyCode::F(1), KeyModifiers::SHIFT),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    process_key_event(
+        &mut state,
+        &mut cursor,
+        KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+
+    assert!(!state.should_quit());
+    assert_eq!(
+        state.status_message(),
+        Some("Ctrl+q runs quit in insert mode (global, default)")
+    );
+    assert_eq!(state.status_severity(), Severity::Info);
+}
+
+#[test]
+fn test_config_session_applies_project_keymap() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let (dir, user_path) = create_config_layers(
+        "[keymap]\npreset = \"emacs\"\n",
+        "[keymap]\npreset = \"vim\"\n\n[[keybindings]]\nsequence = \"l\"\nunbind = true\nmode = \"normal\"\n",
+    );
+    let project_file = dir.path().join("project/main.rs");
+    std::fs::write(&project_file, "").unwrap();
+    let mut state = EditorState::from_file(&project_file).unwrap();
+    let mut input_handler = InputHandler::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+    let mut config = ConfigSession::new(Some(user_path), None);
+
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    assert!(result.is_ok());
+
+    let key = |ch| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
+    assert_eq!(
+        input_handler.process_key_event(key('j'), &EditorMode::Normal),
+        MatchResult::Matched(EditorCommand::MoveCursor(Direction::Down))
+    );
+    assert_eq!(
+        input_handler.process_key_event(key('l'), &EditorMode::Normal),
+        MatchResult::Matched(EditorCommand::Noop)
+    );
+    // The user's emacs preset is replaced, not merged
+    assert_eq!(
+        input_handler.process_key_event(
+            KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL),
+            &EditorMode::Insert
+        ),
+        MatchResult::NoMatch
+    );
+}
+
+#[test]
+fn test_config_session_warns_about_invalid_project_keymap() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let (dir, user_path) = create_config_layers("[keymap]\npreset = \"vim\"\n", "[keymap]\npreset = \"nano\"\n");
+    let project_file = dir.path().join("project/main.rs");
+    std::fs::write(&project_file, "").unwrap();
+    let mut state = EditorState::from_file(&project_file).unwrap();
+    let mut input_handler = InputHandler::new();
+    let mut config = ConfigSession::new(Some(user_path), None);
+
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    let (_severity, message) = config_warning_message(&result).expect("the invalid keymap is reported");
+    assert!(message.contains(".termide.toml") && message.contains("nano"), "{}", message);
+    // The user's keymap applies instead
+    assert_eq!(
+        input_handler.process_key_event(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE), &EditorMode::Normal),
+        MatchResult::Matched(EditorCommand::MoveCursor(Direction::Down))
+    );
+}
+
+#[test]
+fn test_config_session_layers_save_options() {
+    let (dir, user_path) = create_config_layers(
+        "[save.backup]\nmode = \"simple\"\n",
+        "[save]\ntrim_trailing_whitespace = true\n",
+    );
+    let project_file = dir.path().join("project/main.rs");
+    let other_file = dir.path().join("other.rs");
+    std::fs::write(&project_file, "").unwrap();
+    std::fs::write(&other_file, "").unwrap();
+    let mut input_handler = InputHandler::new();
+    let mut config = ConfigSession::new(Some(user_path.clone()), None);
+
+    // The project section replaces the user's
+    let mut state = EditorState::from_file(&project_file).unwrap();
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    assert!(result.unwrap().warnings.is_empty());
+    assert!(state.save_options().hooks.trim_trailing_whitespace);
+    assert_eq!(state.save_options().backup.mode, BackupMode::None);
+
+    // Outside the project the user's options apply
+    let mut state = EditorState::from_file(&other_file).unwrap();
+    config.load(&mut state, &mut input_handler);
+    assert_eq!(state.save_options().backup.mode, BackupMode::Simple);
+
+    // A malformed section is reported and does not leave stale options behind
+    std::fs::write(&user_path, "[save.backup]\nmode = \"sometimes\"\n").unwrap();
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+    let (_severity, message) = config_warning_message(&result).expect("the invalid [save] is reported");
+    assert!(message.contains("sometimes"), "{}", message);
+    assert_eq!(state.save_options().backup.mode, BackupMode::None);
+}
+
+#[test]
+fn test_execute_parameterized_commands() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let snippet = EditorCommand::InsertText("one\ntwo\nthree".to_string());
+    execute_command(&mut state, &mut cursor, snippet, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.buffer().content(), "one\ntwo\nthree");
+    assert_eq!(cursor, Position::new(2, 5));
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::GotoLine(2),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(cursor, Position::new(1, 0));
+
+    // Lines past the end go to the last line
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::GotoLine(100),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(cursor, Position::new(2, 0));
+
+    let right = EditorCommand::MoveCursorBy { direction: Direction::Right, count: 3 };
+    execute_command(&mut state, &mut cursor, right, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(cursor, Position::new(2, 3));
+
+    let set_theme = EditorCommand::SetTheme("light".to_string());
+    execute_command(&mut state, &mut cursor, set_theme, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.take_theme_request().as_deref(), Some("light"));
+    assert_eq!(state.take_theme_request(), None);
+}
+
+#[test]
+fn test_command_chain_stops_at_first_failure() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let chain = EditorCommand::Chain(vec![
+        EditorCommand::InsertText("ab".to_string()),
+        EditorCommand::ToggleReadOnly,
+        EditorCommand::InsertChar('c'),
+        EditorCommand::ToggleReadOnly,
+    ]);
+    execute_command(&mut state, &mut cursor, chain, &mut input_handler, &mut plugins).unwrap();
+
+    assert_eq!(state.buffer().content(), "ab");
+    assert!(state.is_read_only(), "commands after the failure must not run");
+    assert_eq!(
+        state.status_message(),
+        Some(
+            "'insert_char 'c'' failed (step 3 of 4): Buffer is read-only. \
+             Use 'buffer.toggle_readonly' to allow editing."
+        )
+    );
+    assert_eq!(state.status_severity(), Severity::Error);
+}
+
+#[test]
+fn test_command_chain_stops_at_save_prompt() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let chain = EditorCommand::Chain(vec![
+        EditorCommand::InsertChar('x'),
+        EditorCommand::Save,
+        EditorCommand::Quit,
+    ]);
+    execute_command(&mut state, &mut cursor, chain, &mut input_handler, &mut plugins).unwrap();
+
+    assert_eq!(state.mode(), EditorMode::Prompt);
+    assert!(!state.should_quit());
+}
+
+#[test]
+fn test_execute_user_command() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    let mut commands = UserCommands::new();
+    commands.insert("greet", vec![EditorCommand::InsertText("hi".to_string())]);
+    commands.insert("greet_twice", vec![EditorCommand::UserCommand("greet".to_string()); 2]);
+    input_handler.set_user_commands(commands);
+
+    let greet_twice = EditorCommand::UserCommand("greet_twice".to_string());
+    execute_command(
+        &mut state,
+        &mut cursor,
+        greet_twice,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.buffer().content(), "hihi");
+
+    let missing = EditorCommand::UserCommand("missing".to_string());
+    execute_command(&mut state, &mut cursor, missing, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.status_message(), Some("Unknown command 'user.missing'"));
+    assert_eq!(state.status_severity(), Severity::Error);
+}
+
+#[test]
+fn test_config_session_loads_user_commands() {
+    let (dir, user_path) = create_config_layers(
+        "[commands]\ngreet = [\"file.save\"]\nshared = [\"quit\"]\n\n\
+         [[keybindings]]\nsequence = \"Ctrl+g\"\ncommand = \"user.nowhere\"\n",
+        "[commands]\nshared = [\"user.greet\", \"quit\"]\n",
+    );
+    let project_file = dir.path().join("project/main.rs");
+    std::fs::write(&project_file, "").unwrap();
+    let mut state = EditorState::from_file(&project_file).unwrap();
+    let mut input_handler = InputHandler::new();
+    let mut config = ConfigSession::new(Some(user_path), None);
+
+    let (_theme, result) = config.load(&mut state, &mut input_handler);
+
+    assert_eq!(
+        result.unwrap().warnings,
+        vec!["keybinding 'Ctrl+g' calls undefined command 'user.nowhere'"]
+    );
+    let commands = input_handler.user_commands();
+    assert_eq!(commands.names().collect::<Vec<_>>(), vec!["greet", "shared"]);
+    assert_eq!(
+        commands.get("shared"),
+        Some(&[EditorCommand::UserCommand("greet".to_string()), EditorCommand::Quit][..])
+    );
+}
+
+#[test]
+fn test_record_and_play_macro() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("a\nb\nc\nd\n");
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    // Record "insert '-' then go to the start of the next line"
+    for code in [KeyCode::F(3), KeyCode::Char('-'), KeyCode::Down, KeyCode::Home, KeyCode::F(3)] {
+        process_key_event(
+            &mut state,
+            &mut cursor,
+            key(code),
+            &mut input_handler,
+            &mut plugins,
+        )
+        .unwrap();
+    }
+    assert_eq!(state.status_message(), Some("Recorded 3 commands into register 'q'"));
+    assert_eq!(state.status_severity(), Severity::Info);
+    assert_eq!(
+        input_handler.macros().get('q'),
+        Some(
+            &[
+                EditorCommand::InsertChar('-'),
+                EditorCommand::MoveCursor(Direction::Down),
+                EditorCommand::MoveToLineStart,
+            ][..]
+        )
+    );
+
+    let play = EditorCommand::PlayMacro { register: 'q', count: 2 };
+    execute_command(&mut state, &mut cursor, play, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.buffer().content(), "-a\n-b\n-c\nd\n");
+    assert_eq!(cursor, Position::new(3, 0));
+
+    // The whole playback is one undo step
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::Undo,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.buffer().content(), "-a\nb\nc\nd\n");
+}
+
+#[test]
+fn test_play_macro_errors() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let play = EditorCommand::PlayMacro { register: 'z', count: 1 };
+    execute_command(
+        &mut state,
+        &mut cursor,
+        play.clone(),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.status_message(), Some("Macro register 'z' is empty"));
+    assert_eq!(state.status_severity(), Severity::Error);
+
+    input_handler.macros_mut().set('z', vec![EditorCommand::InsertChar('x'), play.clone()]);
+    execute_command(&mut state, &mut cursor, play, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.buffer().content(), "x");
+    assert_eq!(
+        state.status_message(),
+        Some("'macro.play { register = \"z\", count = 1 }' failed (step 2 of 2): Macro 'z' cannot play itself")
+    );
+    assert_eq!(state.status_severity(), Severity::Error);
+}
+
+#[test]
+fn test_play_macro_rejects_huge_counts() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    input_handler.macros_mut().set('q', vec![EditorCommand::InsertChar('x')]);
+
+    for count in [10_001, usize::MAX] {
+        let play = EditorCommand::PlayMacro { register: 'q', count };
+        execute_command(&mut state, &mut cursor, play, &mut input_handler, &mut plugins).unwrap();
+        assert_eq!(state.buffer().content(), "");
+        assert_eq!(state.status_severity(), Severity::Error);
+    }
+
+    // The macro can still be played afterwards
+    let play = EditorCommand::PlayMacro { register: 'q', count: 3 };
+    execute_command(&mut state, &mut cursor, play, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.buffer().content(), "xxx");
+}
+
+#[test]
+fn test_undo_redo_commands() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::Undo,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.status_message(), Some("Nothing to undo"));
+    assert_eq!(state.status_severity(), Severity::Info);
+
+    let text = EditorCommand::InsertText("hi".to_string());
+    execute_command(&mut state, &mut cursor, text, &mut input_handler, &mut plugins).unwrap();
+    // A command is a single undo step
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::Undo,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.buffer().content(), "");
+    assert_eq!(cursor, Position::origin());
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::Redo,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.buffer().content(), "hi");
+}
+
+#[test]
+fn test_execute_plugin_command() {
+    use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+
+    /// Plugin that fails or panics on demand
+    struct Flaky;
+
+    impl Plugin for Flaky {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn execute(&mut self, command: &str, _context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+            match command {
+                "fail" => Err(PluginError::Failed("nothing to do".to_string())),
+                _ => panic!("boom"),
+            }
+        }
+    }
+
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    plugins.load(Box::new(Flaky), input_handler.registry_mut()).unwrap();
+    let command = |name: &str| EditorCommand::PluginCommand {
+        plugin_name: "flaky".to_string(),
+        command_name: name.to_string(),
+    };
+
+    execute_command(&mut state, &mut cursor, command("fail"), &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.status_message(), Some("plugin 'flaky': nothing to do"));
+    assert_eq!(state.status_severity(), Severity::Error);
+
+    // A panic is reported instead of crashing the editor
+    execute_command(&mut state, &mut cursor, command("crash"), &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.status_message(), Some("plugin 'flaky' panicked: boom"));
+    assert_eq!(state.status_severity(), Severity::Error);
+
+    let missing = EditorCommand::PluginCommand {
+        plugin_name: "lsp".to_string(),
+        command_name: "hover".to_string(),
+    };
+    execute_command(&mut state, &mut cursor, missing, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.status_message(), Some("plugin 'lsp' is not loaded"));
+    assert_eq!(state.status_severity(), Severity::Error);
+}
+
+#[test]
+fn test_plugin_command_edits_are_one_undo_step() {
+    use termide_plugin_api::buffer::{TextPosition, TextRange};
+    use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+
+    /// Plugin that wraps the first line in brackets
+    struct Wrap;
+
+    impl Plugin for Wrap {
+        fn name(&self) -> &str {
+            "wrap"
+        }
+
+        fn execute(&mut self, _command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+            let buffer = context.buffer();
+            let len = buffer.line(0).unwrap_or_default().chars().count();
+            buffer.insert(TextPosition::new(0, len), "]")?;
+            buffer.insert(TextPosition::new(0, 0), "[")?;
+            buffer.delete(TextRange::new(TextPosition::new(0, 1), TextPosition::new(0, 2)))?;
+            buffer.set_cursor(TextPosition::new(0, len + 1))?;
+            Ok(())
+        }
+    }
+
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("hello");
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    plugins.load(Box::new(Wrap), input_handler.registry_mut()).unwrap();
+    let command = EditorCommand::PluginCommand {
+        plugin_name: "wrap".to_string(),
+        command_name: "line".to_string(),
+    };
+
+    execute_command(&mut state, &mut cursor, command, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.buffer().content(), "[ello]");
+    assert_eq!(cursor, Position::new(0, 6));
+
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::Undo,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.buffer().content(), "hello");
+}
+
+#[test]
+fn test_plugin_picker_round_trip() {
+    use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+    use termide_plugin_api::ui::{Picker, PickerSelection, Severity};
+
+    /// Plugin that picks a word to insert
+    struct Words;
+
+    impl Plugin for Words {
+        fn name(&self) -> &str {
+            "words"
+        }
+
+        fn execute(&mut self, _command: &str, context: &mut CommandContext<'_>) -> Result<(), PluginError> {
+            let items = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+            context.open_picker(Picker::new("insert", "Word", items));
+            Ok(())
+        }
+
+        fn picker_result(
+            &mut self,
+            _picker: &str,
+            selection: Option<PickerSelection>,
+            context: &mut CommandContext<'_>,
+        ) -> Result<(), PluginError> {
+            match selection {
+                Some(selection) => {
+                    let buffer = context.buffer();
+                    let end = buffer.insert(buffer.cursor(), &selection.item)?;
+                    buffer.set_cursor(end)?;
+                }
+                None => context.show_message(Severity::Info, "nothing picked"),
+            }
+            Ok(())
+        }
+    }
+
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    plugins.load(Box::new(Words), input_handler.registry_mut()).unwrap();
+    let open = EditorCommand::PluginCommand {
+        plugin_name: "words".to_string(),
+        command_name: "insert".to_string(),
+    };
+
+    let commands = [
+        open.clone(),
+        EditorCommand::PromptInsertChar('a'),
+        EditorCommand::PromptNext,
+        EditorCommand::AcceptPrompt,
+    ];
+    for command in commands {
+        execute_command(&mut state, &mut cursor, command, &mut input_handler, &mut plugins).unwrap();
+    }
+    assert_eq!(state.mode(), EditorMode::Insert);
+    assert_eq!(state.buffer().content(), "baz");
+    assert_eq!(cursor, Position::new(0, 3));
+
+    // Cancelling delivers no selection instead of reporting a cancelled save
+    execute_command(&mut state, &mut cursor, open, &mut input_handler, &mut plugins).unwrap();
+    execute_command(
+        &mut state,
+        &mut cursor,
+        EditorCommand::CancelPrompt,
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.status_message(), Some("nothing picked"));
+    assert_eq!(state.buffer().content(), "baz");
+}
+
+#[test]
+fn test_command_palette_runs_picked_command() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("one\ntwo");
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+
+    process_key_event(
+        &mut state,
+        &mut cursor,
+        KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE),
+        &mut input_handler,
+        &mut plugins,
+    )
+    .unwrap();
+    assert_eq!(state.mode(), EditorMode::Prompt);
+    assert_eq!(state.prompt_kind(), &PromptKind::CommandPalette);
+    let list = state.prompt_list().expect("the palette shows a list");
+    assert!(list.matches().any(|(_, name)| name == "file.save"));
+
+    for ch in "mvdn".chars() {
+        execute_command(
+            &mut state,
+            &mut cursor,
+            EditorCommand::PromptInsertChar(ch),
+            &mut input_handler,
+            &mut plugins,
+        )
+        .unwrap();
+    }
+    assert_eq!(state.prompt_selection().map(|(_, name)| name), Some("move.down".to_string()));
+    execute_command(&mut state, &mut cursor, EditorCommand::AcceptPrompt, &mut input_handler, &mut plugins).unwrap();
+
+    assert_eq!(state.mode(), EditorMode::Insert);
+    assert_eq!(cursor, Position::new(1, 0));
+}
+
+#[test]
+fn test_command_palette_without_match() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let commands = [
+        EditorCommand::CommandPalette,
+        EditorCommand::PromptInsertChar('#'),
+        EditorCommand::AcceptPrompt,
+    ];
+    for command in commands {
+        execute_command(&mut state, &mut cursor, command, &mut input_handler, &mut plugins).unwrap();
+    }
+    assert_eq!(state.mode(), EditorMode::Insert);
+    assert_eq!(state.status_message(), Some("No command matches '#'"));
+    assert_eq!(state.status_severity(), Severity::Info);
+}
+
+/// Opens the command line, types `line` and accepts it
+fn run_command_line(
+    state: &mut EditorState,
+    cursor: &mut Position,
+    line: &str,
+    input_handler: &mut InputHandler,
+    plugins: &mut PluginHost,
+) {
+    let commands = std::iter::once(EditorCommand::CommandLine)
+        .chain(line.chars().map(EditorCommand::PromptInsertChar))
+        .chain(std::iter::once(EditorCommand::AcceptPrompt));
+    for command in commands {
+        execute_command(state, cursor, command, input_handler, plugins).unwrap();
+    }
+}
+
+#[test]
+fn test_colon_opens_command_line_with_completion() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut state = EditorState::new();
+    state.set_mode(EditorMode::Normal);
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+    register_default_bindings(input_handler.registry_mut()).unwrap();
+
+    let keys = [KeyCode::Char(':'), KeyCode::Char('w'), KeyCode::Char('r'), KeyCode::Tab];
+    for code in keys {
+        process_key_event(
+            &mut state,
+            &mut cursor,
+            KeyEvent::new(code, KeyModifiers::NONE),
+            &mut input_handler,
+            &mut plugins,
+        )
+        .unwrap();
+    }
+    assert_eq!(state.mode(), EditorMode::Prompt);
+    assert_eq!(state.prompt_kind(), &PromptKind::CommandLine);
+    assert_eq!(state.prompt_message(), ":");
+    assert_eq!(state.prompt_input(), "write");
+
+    execute_command(&mut state, &mut cursor, EditorCommand::CancelPrompt, &mut input_handler, &mut plugins).unwrap();
+    assert_eq!(state.mode(), EditorMode::Normal);
+    assert_eq!(state.status_message(), None);
+}
+
+#[test]
+fn test_command_line_write_and_quit() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.txt");
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("hello");
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let line = format!("w {}", path.display());
+    run_command_line(&mut state, &mut cursor, &line, &mut input_handler, &mut plugins);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello");
+    assert_eq!(state.buffer().file_path(), Some(&path));
+
+    state.buffer_mut().set_content("bye");
+    run_command_line(&mut state, &mut cursor, "wq", &mut input_handler, &mut plugins);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "bye");
+    assert!(state.should_quit());
+}
+
+#[test]
+fn test_quit_after_saving_another_buffer_still_warns() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("saved.txt");
+    std::fs::write(&path, "").unwrap();
+    let mut state = EditorState::from_file(&path).unwrap();
+    state.buffer_mut().set_content("saved");
+    let mut dirty = Buffer::new();
+    dirty.set_content("unsaved");
+    state.add_buffer(dirty, Position::origin());
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    // "Saved successfully" is left in the status bar
+    execute_command(&mut state, &mut cursor, EditorCommand::Save, &mut input_handler, &mut plugins).unwrap();
+    assert!(state.status_message().is_some());
+
+    execute_command(&mut state, &mut cursor, EditorCommand::Quit, &mut input_handler, &mut plugins).unwrap();
+    assert!(!state.should_quit(), "the other buffer still has unsaved changes");
+    assert_eq!(state.status_severity(), Severity::Warning);
+
+    // Any other command cancels the confirmation
+    execute_command(&mut state, &mut cursor, EditorCommand::MoveCursor(Direction::Right), &mut input_handler, &mut plugins).unwrap();
+    execute_command(&mut state, &mut cursor, EditorCommand::Quit, &mut input_handler, &mut plugins).unwrap();
+    assert!(!state.should_quit());
+
+    execute_command(&mut state, &mut cursor, EditorCommand::Quit, &mut input_handler, &mut plugins).unwrap();
+    assert!(state.should_quit());
+}
+
+#[test]
+fn test_command_line_force_quit_discards_changes() {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("unsaved");
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    run_command_line(&mut state, &mut cursor, "q", &mut input_handler, &mut plugins);
+    assert!(!state.should_quit());
+    assert!(state.status_message().is_some_and(|message| message.starts_with("Unsaved changes")));
+    assert_eq!(state.status_severity(), Severity::Warning);
+
+    state.clear_status_message();
+    run_command_line(&mut state, &mut cursor, "q!", &mut input_handler, &mut plugins);
+    assert!(state.should_quit());
+}
+
+#[test]
+fn test_command_line_edit_opens_or_switches() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "notes").unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    let line = format!("e {}", path.display());
+    run_command_line(&mut state, &mut cursor, &line, &mut input_handler, &mut plugins);
+    assert_eq!(state.buffer_count(), 2);
+    assert_eq!(state.buffer().content(), "notes");
+
+    execute_command(&mut state, &mut cursor, EditorCommand::NextBuffer, &mut input_handler, &mut plugins).unwrap();
+    run_command_line(&mut state, &mut cursor, &line, &mut input_handler, &mut plugins);
+    assert_eq!(state.buffer_count(), 2);
+    assert_eq!(state.active_buffer_index(), 1);
+}
+
+#[test]
+fn test_command_line_goto_and_substitute() {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("a.a\nb.b\nc.c");
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    run_command_line(&mut state, &mut cursor, "2", &mut input_handler, &mut plugins);
+    assert_eq!(cursor, Position::new(1, 0));
+    run_command_line(&mut state, &mut cursor, "$", &mut input_handler, &mut plugins);
+    assert_eq!(cursor, Position::new(2, 0));
+
+    run_command_line(&mut state, &mut cursor, "%s/./-/g", &mut input_handler, &mut plugins);
+    assert_eq!(state.buffer().content(), "a-a\nb-b\nc-c");
+    assert_eq!(state.status_message(), Some("3 substitutions on 3 lines"));
+    assert_eq!(state.status_severity(), Severity::Info);
+
+    run_command_line(&mut state, &mut cursor, "1,2s/-/+", &mut input_handler, &mut plugins);
+    assert_eq!(state.buffer().content(), "a+a\nb+b\nc-c");
+
+    run_command_line(&mut state, &mut cursor, "s/x/y/", &mut input_handler, &mut plugins);
+    assert_eq!(state.status_message(), Some("Pattern not found: x"));
+    assert_eq!(state.status_severity(), Severity::Error);
+
+    state.set_read_only(true);
+    run_command_line(&mut state, &mut cursor, "%s/a/b/", &mut input_handler, &mut plugins);
+    assert_eq!(state.buffer().content(), "a+a\nb+b\nc-c");
+    assert_eq!(state.status_message(), Some(READ_ONLY_MESSAGE));
+}
+
+#[test]
+fn test_command_line_set_option() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    run_command_line(&mut state, &mut cursor, "set tab_width=2", &mut input_handler, &mut plugins);
+    assert_eq!(state.settings().tab_width, 2);
+    assert_eq!(state.status_message(), Some("tab_width=2"));
+    assert_eq!(state.status_severity(), Severity::Info);
+
+    run_command_line(&mut state, &mut cursor, "set theme=light", &mut input_handler, &mut plugins);
+    assert_eq!(state.take_theme_request(), Some("light".to_string()));
+
+    run_command_line(&mut state, &mut cursor, "set tab_width=99", &mut input_handler, &mut plugins);
+    assert_eq!(state.settings().tab_width, 2);
+    assert!(state.status_message().is_some_and(|message| message.starts_with("editor.tab_width:")));
+    assert_eq!(state.status_severity(), Severity::Error);
+
+    run_command_line(&mut state, &mut cursor, "set expand_tabs", &mut input_handler, &mut plugins);
+    assert_eq!(state.status_message(), Some("expand_tabs=false"));
+    assert_eq!(state.status_severity(), Severity::Info);
+}
+
+#[test]
+fn test_command_line_reports_parse_errors() {
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    let mut input_handler = InputHandler::new();
+    let mut plugins = PluginHost::new();
+
+    run_command_line(&mut state, &mut cursor, "frob", &mut input_handler, &mut plugins);
+    assert_eq!(state.mode(), EditorMode::Insert);
+    assert_eq!(state.status_message(), Some("Not an editor command: frob"));
+    assert_eq!(state.status_severity(), Severity::Error);
+}
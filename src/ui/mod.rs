@@ -11,7 +11,9 @@
 //! - Automatic scrolling: keeps cursor in view
 //! - Status bar with file info, mode, and position
 //! - Status messages with color coding
+//! - Which-key popup listing the continuations of a pending key sequence
 //! - Theming support with customizable colors
+//! - Drawing on `/dev/tty` when standard output is redirected (pipe mode)
 //!
 //! # Theme System
 //!
@@ -42,9 +44,11 @@
 //! # }
 //! ```
 
+mod output;
 mod renderer;
 mod theme;
 
+pub use output::TerminalOutput;
 pub use renderer::Renderer;
 pub use theme::Theme;
 
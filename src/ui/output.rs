@@ -0,0 +1,87 @@
+//! Terminal output device selection
+
+use std::fs::File;
+use std::io::{self, IsTerminal, Stdout, Write};
+
+use anyhow::{Context, Result};
+
+/// Where the editor interface is drawn
+///
+/// Normally this is standard output. When standard output is redirected (for
+/// example `cmd | termide - | other`), the interface is drawn on the
+/// controlling terminal (`/dev/tty`) instead so the pipe only receives the
+/// edited text.
+#[derive(Debug)]
+pub enum TerminalOutput {
+    /// Standard output, which is a terminal
+    Stdout(Stdout),
+    /// The controlling terminal, opened because standard output is redirected
+    Tty(File),
+}
+
+impl TerminalOutput {
+    /// Opens the device the interface should be drawn on
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if standard output is redirected and the controlling
+    /// terminal cannot be opened (e.g. when running without a terminal).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use termide::ui::TerminalOutput;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let output = TerminalOutput::open()?;
+    /// if output.is_tty_device() {
+    ///     eprintln!("Drawing on /dev/tty");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open() -> Result<Self> {
+        let stdout = io::stdout();
+        if stdout.is_terminal() {
+            return Ok(Self::Stdout(stdout));
+        }
+
+        #[cfg(unix)]
+        {
+            let tty = File::options()
+                .read(true)
+                .write(true)
+                .open("/dev/tty")
+                .context(
+                    "Standard output is not a terminal and the controlling terminal \
+                     (/dev/tty) could not be opened.",
+                )?;
+            Ok(Self::Tty(tty))
+        }
+        #[cfg(not(unix))]
+        {
+            Ok(Self::Stdout(stdout))
+        }
+    }
+
+    /// Returns `true` if drawing goes to `/dev/tty` rather than standard output
+    pub fn is_tty_device(&self) -> bool {
+        matches!(self, Self::Tty(_))
+    }
+}
+
+impl Write for TerminalOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout(stdout) => stdout.write(buf),
+            Self::Tty(tty) => tty.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout(stdout) => stdout.flush(),
+            Self::Tty(tty) => tty.flush(),
+        }
+    }
+}
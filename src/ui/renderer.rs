@@ -1,23 +1,38 @@
 //! Terminal renderer for the editor using Ratatui
 
-use std::io::{self, Stdout};
-
 use anyhow::{Context, Result};
 use crossterm::{
+    event::{KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::Line,
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame, Terminal,
 };
 
-use crate::{buffer::Position, editor::EditorState};
-use super::Theme;
+use termide_plugin_api::ui::Severity;
+
+use crate::{
+    buffer::Position,
+    editor::{EditorState, KeyHints, PromptList},
+};
+use super::{TerminalOutput, Theme};
+
+/// Keyboard protocol features requested from terminals that support them
+///
+/// Disambiguating escape codes tells apart keys such as Ctrl+I and Tab, and
+/// event types report key repeats and releases.
+const KEYBOARD_ENHANCEMENT_FLAGS: KeyboardEnhancementFlags =
+    KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+        .union(KeyboardEnhancementFlags::REPORT_EVENT_TYPES);
 
 /// Renders a single frame (standalone function to avoid borrow checker issues)
 fn render_frame_impl(
@@ -40,12 +55,38 @@ fn render_frame_impl(
         ])
         .split(size);
 
+    // Reserve a gutter for line numbers when enabled
+    let gutter = if state.settings().line_numbers {
+        gutter_width(state.buffer().line_count()).min(chunks[0].width)
+    } else {
+        0
+    };
+    let text_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(gutter), Constraint::Min(1)])
+        .split(chunks[0]);
+    let text_area = text_chunks[1];
+
+    if gutter > 0 {
+        render_line_numbers(frame, text_chunks[0], state, scroll_offset, theme);
+    }
+
     // Render text area
-    render_text_area(frame, chunks[0], state, scroll_offset);
+    render_text_area(frame, text_area, state, scroll_offset);
 
     // Render status bar
     render_status_bar(frame, chunks[1], state, cursor_pos, theme);
 
+    // Continuations of a pending key sequence, drawn over the bottom of the text
+    if let Some(hints) = state.key_hints() {
+        render_key_hints(frame, chunks[0], hints, theme);
+    }
+
+    // Items of a picker prompt, drawn over the bottom of the text
+    if let Some(list) = state.prompt_list() {
+        render_prompt_list(frame, chunks[0], list, theme);
+    }
+
     // Set cursor position
     if state.mode() == EditorMode::Prompt {
         // In prompt mode, cursor is in the prompt input
@@ -54,7 +95,7 @@ fn render_frame_impl(
         frame.set_cursor_position((prompt_cursor_x, prompt_cursor_y));
     } else {
         // Normal/Insert mode, cursor is in the text area
-        let cursor_screen_pos = calculate_cursor_screen_position(cursor_pos, chunks[0], scroll_offset);
+        let cursor_screen_pos = calculate_cursor_screen_position(cursor_pos, text_area, scroll_offset);
         if let Some((x, y)) = cursor_screen_pos {
             frame.set_cursor_position((x, y));
         }
@@ -94,6 +135,159 @@ fn render_text_area(
     frame.render_widget(paragraph, area);
 }
 
+/// Returns the width of the line number gutter for a buffer with `line_count` lines
+///
+/// Fits the largest line number (at least three digits) plus one column of padding.
+pub(super) fn gutter_width(line_count: usize) -> u16 {
+    let digits = line_count.max(1).to_string().len().max(3);
+    digits as u16 + 1
+}
+
+/// Renders right-aligned line numbers for the visible lines
+fn render_line_numbers(
+    frame: &mut Frame,
+    area: Rect,
+    state: &EditorState,
+    scroll_offset: usize,
+    theme: &Theme,
+) {
+    let line_count = state.buffer().line_count();
+    let end_line = (scroll_offset + area.height as usize).min(line_count);
+    let width = area.width.saturating_sub(1) as usize;
+
+    let lines: Vec<Line> = (scroll_offset..end_line)
+        .map(|line_idx| Line::from(format!("{:>width$} ", line_idx + 1, width = width)))
+        .collect();
+
+    let gutter = Paragraph::new(lines).style(Style::default().fg(theme.line_number));
+    frame.render_widget(gutter, area);
+}
+
+/// Renders the which-key popup at the bottom of `area`, just above the status bar
+fn render_key_hints(frame: &mut Frame, area: Rect, hints: &KeyHints, theme: &Theme) {
+    let lines = key_hint_lines(hints, area.width.saturating_sub(2));
+    let height = (lines.len() as u16 + 2).min(area.height);
+    if height < 3 {
+        return;
+    }
+
+    let popup = Rect {
+        x: area.x,
+        y: area.y + area.height - height,
+        width: area.width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.info))
+        .title(format!(" {} ", hints.pending));
+    let widget = Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+        .block(block)
+        .style(Style::default().fg(theme.text_fg).bg(theme.text_bg));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
+
+/// Lays out key hints as `keys  command` entries in as many columns as fit `width`
+///
+/// Entries fill the columns top to bottom, so alphabetically sorted hints read
+/// down each column.
+pub(super) fn key_hint_lines(hints: &KeyHints, width: u16) -> Vec<String> {
+    let keys_width = hints.hints.iter().map(|hint| hint.keys.chars().count()).max().unwrap_or(0);
+    let entries: Vec<String> = hints
+        .hints
+        .iter()
+        .map(|hint| format!("{:<keys_width$}  {}", hint.keys, hint.command))
+        .collect();
+    let entry_width = entries.iter().map(|entry| entry.chars().count()).max().unwrap_or(0);
+
+    // Three spaces between columns
+    let columns = ((width as usize + 3) / (entry_width + 3)).clamp(1, entries.len().max(1));
+    let rows = entries.len().div_ceil(columns);
+
+    (0..rows)
+        .map(|row| {
+            let line = (0..columns)
+                .filter_map(|column| entries.get(column * rows + row))
+                .map(|entry| format!("{:<entry_width$}", entry))
+                .collect::<Vec<_>>()
+                .join("   ");
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Renders the items of a picker prompt at the bottom of `area`, just above the status bar
+fn render_prompt_list(frame: &mut Frame, area: Rect, list: &PromptList, theme: &Theme) {
+    // At most half the text area, and at least one row for "no matches"
+    let max_rows = (area.height / 2).saturating_sub(2).max(1) as usize;
+    let (lines, highlighted) = prompt_list_lines(list, max_rows, area.width.saturating_sub(2) as usize);
+    let height = (lines.len().max(1) as u16 + 2).min(area.height);
+    if height < 3 {
+        return;
+    }
+
+    let popup = Rect {
+        x: area.x,
+        y: area.y + area.height - height,
+        width: area.width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.info))
+        .title(format!(" {} ", list.len()));
+    let lines: Vec<Line> = if lines.is_empty() {
+        vec![Line::styled("no matches", Style::default().fg(theme.info))]
+    } else {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(row, item)| {
+                if Some(row) == highlighted {
+                    Line::styled(item, Style::default().bg(theme.selection))
+                } else {
+                    Line::from(item)
+                }
+            })
+            .collect()
+    };
+    let widget = Paragraph::new(lines)
+        .block(block)
+        .style(Style::default().fg(theme.text_fg).bg(theme.text_bg));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
+
+/// Returns the matching items shown in a picker popup of `max_rows` rows
+/// and `width` columns, and the row of the highlighted item
+///
+/// The rows scroll with the highlight, which stays on the last row once it
+/// moves past the first page. Item details are right-aligned, and left out
+/// where they don't fit next to the item.
+pub(super) fn prompt_list_lines(list: &PromptList, max_rows: usize, width: usize) -> (Vec<String>, Option<usize>) {
+    let selected = list.selected_position();
+    let first = selected.map_or(0, |selected| (selected + 1).saturating_sub(max_rows));
+    let lines = list
+        .matches()
+        .skip(first)
+        .take(max_rows)
+        .map(|(index, item)| {
+            let Some(detail) = list.detail(index) else {
+                return item.to_string();
+            };
+            // At least two spaces between the item and its detail
+            match width.checked_sub(item.chars().count() + detail.chars().count()) {
+                Some(gap) if gap >= 2 => format!("{}{}{}", item, " ".repeat(gap), detail),
+                _ => item.to_string(),
+            }
+        })
+        .collect();
+    (lines, selected.map(|selected| selected - first))
+}
+
 /// Renders the status bar and status message
 fn render_status_bar(
     frame: &mut Frame,
@@ -121,14 +315,31 @@ fn render_status_bar(
         .and_then(|n| n.to_str())
         .unwrap_or("[No Name]");
 
+    // Show which buffer is active when several are open
+    let buffer_indicator = if state.buffer_count() > 1 {
+        format!("[{}/{}] ", state.active_buffer_index() + 1, state.buffer_count())
+    } else {
+        String::new()
+    };
+    let read_only_indicator = if state.is_read_only() { " [RO]" } else { "" };
     let dirty_indicator = if state.buffer().is_dirty() { " *" } else { "" };
-    let mode_str = state.mode().to_string();
+    let mode = state.mode();
+    let mode = mode.to_string();
+    let mode_str = match state.recording_macro() {
+        Some(register) => format!("{} recording @{}", mode, register),
+        None => mode.to_string(),
+    };
     let position_str = format!("{}:{}", cursor_pos.line + 1, cursor_pos.column + 1);
 
-    let status_line = format!(
-        " {}{} | {} | {}",
-        filename, dirty_indicator, mode_str, position_str
+    let mut status_line = format!(
+        " {}{}{}{} | {} | {}",
+        buffer_indicator, filename, read_only_indicator, dirty_indicator, mode_str, position_str
     );
+    // Segments contributed by plugins
+    for segment in state.status_segments() {
+        status_line.push_str(" | ");
+        status_line.push_str(segment);
+    }
 
     let status_bar = Paragraph::new(status_line).style(
         Style::default()
@@ -150,21 +361,14 @@ fn render_status_bar(
         );
         frame.render_widget(prompt_widget, chunks[1]);
     } else if let Some(message) = state.status_message() {
-        // Render status message with appropriate color based on type
-        let message_style = if message.starts_with("Error:") {
-            // Errors in error color
-            Style::default().fg(theme.error).add_modifier(Modifier::BOLD)
-        } else if message.starts_with("Warning:") {
-            // Warnings in warning color
-            Style::default()
+        // Render status message with the color of its severity
+        let message_style = match state.status_severity() {
+            Severity::Error => Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            Severity::Warning => Style::default()
                 .fg(theme.warning)
-                .add_modifier(Modifier::BOLD)
-        } else if message.starts_with("Info:") {
-            // Informational messages in info color
-            Style::default().fg(theme.info)
-        } else {
-            // Success messages in success color
-            Style::default().fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+            Severity::Info => Style::default().fg(theme.info),
+            Severity::Success => Style::default().fg(theme.success),
         };
 
         let status_message = Paragraph::new(message).style(message_style);
@@ -196,6 +400,43 @@ fn calculate_cursor_screen_position(
     Some((x, y))
 }
 
+/// Returns the scroll offset that keeps `cursor_line` visible
+///
+/// At least `margin` lines stay visible above and below the cursor where the
+/// buffer allows it (the margin is reduced on small screens so the cursor can
+/// still move). Scrolling only happens when the cursor leaves that region, and
+/// never scrolls past the point where the last line reaches the bottom of the
+/// screen because of the margin.
+pub(super) fn scroll_offset_for(
+    cursor_line: usize,
+    scroll_offset: usize,
+    visible_height: usize,
+    line_count: usize,
+    margin: usize,
+) -> usize {
+    if visible_height == 0 {
+        return scroll_offset;
+    }
+
+    let margin = margin.min(visible_height.saturating_sub(1) / 2);
+    let mut offset = scroll_offset;
+
+    // Scroll down if cursor is below visible area (minus the margin)
+    if cursor_line + margin >= offset + visible_height {
+        let last_offset = line_count
+            .saturating_sub(visible_height)
+            .max((cursor_line + 1).saturating_sub(visible_height));
+        offset = (cursor_line + margin + 1 - visible_height).min(last_offset);
+    }
+
+    // Scroll up if cursor is above visible area (plus the margin)
+    if cursor_line < offset + margin {
+        offset = cursor_line.saturating_sub(margin);
+    }
+
+    offset
+}
+
 /// The terminal renderer managing UI display
 ///
 /// `Renderer` handles all terminal UI rendering using Ratatui, including:
@@ -233,20 +474,24 @@ fn calculate_cursor_screen_position(
 #[derive(Debug)]
 pub struct Renderer {
     /// The Ratatui terminal instance
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    terminal: Terminal<CrosstermBackend<TerminalOutput>>,
     /// Viewport scroll offset (top line visible)
     scroll_offset: usize,
     /// Previous frame hash for dirty checking
     last_frame_hash: u64,
     /// Theme for UI styling
     theme: Theme,
+    /// Whether the terminal reports keys with the keyboard enhancement protocol
+    keyboard_enhanced: bool,
 }
 
 impl Renderer {
     /// Creates a new renderer and initializes the terminal with the default theme
     ///
     /// This sets up the terminal in raw mode and alternate screen with the
-    /// default dark theme.
+    /// default dark theme. The interface is drawn on standard output, or on the
+    /// controlling terminal when standard output is redirected (see
+    /// [`TerminalOutput`]).
     ///
     /// # Errors
     ///
@@ -296,10 +541,19 @@ impl Renderer {
     /// ```
     pub fn with_theme(theme: Theme) -> Result<Self> {
         enable_raw_mode().context("Failed to enable raw mode")?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen).context("Failed to enter alternate screen")?;
+        let mut output = TerminalOutput::open()?;
+        execute!(output, EnterAlternateScreen).context("Failed to enter alternate screen")?;
+
+        // The support query is answered on standard output, so it is only
+        // asked when that is the terminal
+        let keyboard_enhanced =
+            !output.is_tty_device() && supports_keyboard_enhancement().unwrap_or(false);
+        if keyboard_enhanced {
+            execute!(output, PushKeyboardEnhancementFlags(KEYBOARD_ENHANCEMENT_FLAGS))
+                .context("Failed to enable keyboard enhancement")?;
+        }
 
-        let backend = CrosstermBackend::new(stdout);
+        let backend = CrosstermBackend::new(output);
         let terminal = Terminal::new(backend).context("Failed to create terminal")?;
 
         Ok(Self {
@@ -307,6 +561,7 @@ impl Renderer {
             scroll_offset: 0,
             last_frame_hash: 0,
             theme,
+            keyboard_enhanced,
         })
     }
 
@@ -318,6 +573,10 @@ impl Renderer {
     ///
     /// Returns an error if terminal restoration fails.
     pub fn restore_terminal(&mut self) -> Result<()> {
+        if self.keyboard_enhanced {
+            execute!(self.terminal.backend_mut(), PopKeyboardEnhancementFlags)
+                .context("Failed to disable keyboard enhancement")?;
+        }
         disable_raw_mode().context("Failed to disable raw mode")?;
         execute!(self.terminal.backend_mut(), LeaveAlternateScreen)
             .context("Failed to leave alternate screen")?;
@@ -327,6 +586,30 @@ impl Renderer {
         Ok(())
     }
 
+    /// Takes the terminal back after [`restore_terminal`](Self::restore_terminal)
+    ///
+    /// Used after handing the terminal to an external process (such as a sudo
+    /// password prompt). The screen is cleared and the next frame is fully redrawn.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the terminal cannot be re-initialized.
+    pub fn resume(&mut self) -> Result<()> {
+        enable_raw_mode().context("Failed to enable raw mode")?;
+        execute!(self.terminal.backend_mut(), EnterAlternateScreen)
+            .context("Failed to enter alternate screen")?;
+        if self.keyboard_enhanced {
+            execute!(
+                self.terminal.backend_mut(),
+                PushKeyboardEnhancementFlags(KEYBOARD_ENHANCEMENT_FLAGS)
+            )
+            .context("Failed to enable keyboard enhancement")?;
+        }
+        self.terminal.clear().context("Failed to clear terminal")?;
+        self.last_frame_hash = 0;
+        Ok(())
+    }
+
     /// Renders the editor state to the terminal
     ///
     /// This method handles viewport calculation, scrolling, and renders:
@@ -369,7 +652,15 @@ impl Renderer {
 
         // Adjust scroll offset based on cursor position
         let terminal_height = self.terminal.size()?.height as usize;
-        self.adjust_scroll(cursor_pos, terminal_height);
+        // Reserve space for status bar (2 lines)
+        let visible_height = terminal_height.saturating_sub(2);
+        self.scroll_offset = scroll_offset_for(
+            cursor_pos.line,
+            self.scroll_offset,
+            visible_height,
+            state.buffer().line_count(),
+            state.settings().scroll_margin,
+        );
 
         // Capture scroll_offset and theme before the closure
         let scroll_offset = self.scroll_offset;
@@ -385,26 +676,6 @@ impl Renderer {
     }
 
 
-    /// Adjusts scroll offset to keep cursor visible
-    fn adjust_scroll(&mut self, cursor_pos: Position, terminal_height: usize) {
-        // Reserve space for status bar (2 lines)
-        let visible_height = terminal_height.saturating_sub(2);
-
-        if visible_height == 0 {
-            return;
-        }
-
-        // Scroll down if cursor is below visible area
-        if cursor_pos.line >= self.scroll_offset + visible_height {
-            self.scroll_offset = cursor_pos.line.saturating_sub(visible_height - 1);
-        }
-
-        // Scroll up if cursor is above visible area
-        if cursor_pos.line < self.scroll_offset {
-            self.scroll_offset = cursor_pos.line;
-        }
-    }
-
     /// Calculates a simple hash of the current frame state for dirty checking
     ///
     /// This is a simple hash based on buffer content length, cursor position,
@@ -420,6 +691,9 @@ impl Renderer {
         hash ^= state.buffer().len_chars() as u64;
         hash ^= (state.buffer().line_count() as u64) << 16;
         hash ^= if state.buffer().is_dirty() { 1 } else { 0 } << 32;
+        hash ^= if state.is_read_only() { 1 } else { 0 } << 33;
+        hash ^= (state.active_buffer_index() as u64) << 34;
+        hash ^= if state.settings().line_numbers { 1 } else { 0 } << 60;
 
         // Include cursor position
         hash ^= (cursor_pos.line as u64) << 8;
@@ -430,7 +704,13 @@ impl Renderer {
             crate::editor::EditorMode::Insert => 1,
             crate::editor::EditorMode::Normal => 2,
             crate::editor::EditorMode::Prompt => 3,
+            crate::editor::EditorMode::Plugin(_) => 4,
         } << 40;
+        if let crate::editor::EditorMode::Plugin(mode) = state.mode() {
+            let mut hasher = DefaultHasher::new();
+            mode.label().hash(&mut hasher);
+            hash ^= hasher.finish().rotate_left(3);
+        }
 
         // Include status message presence
         hash ^= if state.status_message().is_some() {
@@ -442,14 +722,34 @@ impl Renderer {
         // Include scroll offset
         hash ^= (self.scroll_offset as u64) << 56;
 
+        // Include the macro recording indicator
+        if let Some(register) = state.recording_macro() {
+            hash ^= (register as u64).rotate_left(44);
+        }
+
+        // Include the which-key popup
+        if let Some(hints) = state.key_hints() {
+            let mut hasher = DefaultHasher::new();
+            hints.hash(&mut hasher);
+            hash ^= hasher.finish().rotate_left(1);
+        }
+
         // Include prompt input if in prompt mode
         if state.mode() == crate::editor::EditorMode::Prompt {
             let mut hasher = DefaultHasher::new();
             state.prompt_input().hash(&mut hasher);
             state.prompt_message().hash(&mut hasher);
+            state.prompt_list().hash(&mut hasher);
             hash ^= hasher.finish();
         }
 
+        // Include the status line segments of plugins
+        let mut hasher = DefaultHasher::new();
+        for segment in state.status_segments() {
+            segment.hash(&mut hasher);
+        }
+        hash ^= hasher.finish().rotate_left(2);
+
         hash
     }
 
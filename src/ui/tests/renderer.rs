@@ -1,7 +1,8 @@
 //! Unit tests for Renderer struct
 
 use crate::buffer::Position;
-use crate::editor::{EditorMode, EditorState};
+use crate::editor::{EditorMode, EditorState, KeyHint, KeyHints, PromptList};
+use crate::ui::renderer::{gutter_width, key_hint_lines, prompt_list_lines, scroll_offset_for};
 
 // Note: Many Renderer tests require a terminal environment and are difficult to unit test
 // in isolation. The most important logic (scroll adjustment, hash calculation) is tested here.
@@ -265,6 +266,49 @@ fn test_status_bar_with_no_filename() {
     assert_eq!(status, " [No Name] * | INSERT | 11:26");
 }
 
+#[test]
+fn test_gutter_width_fits_largest_line_number() {
+    // At least three digits plus padding
+    assert_eq!(gutter_width(0), 4);
+    assert_eq!(gutter_width(9), 4);
+    assert_eq!(gutter_width(999), 4);
+    assert_eq!(gutter_width(1000), 5);
+    assert_eq!(gutter_width(123_456), 7);
+}
+
+#[test]
+fn test_scroll_offset_without_margin_matches_previous_behaviour() {
+    // Cursor below visible area
+    assert_eq!(scroll_offset_for(25, 0, 20, 100, 0), 6);
+    // Cursor above visible area
+    assert_eq!(scroll_offset_for(5, 10, 20, 100, 0), 5);
+    // Cursor visible - no change
+    assert_eq!(scroll_offset_for(15, 10, 20, 100, 0), 10);
+}
+
+#[test]
+fn test_scroll_offset_keeps_margin_around_cursor() {
+    // Moving down to line 17 of a 20-line view keeps 3 lines below the cursor
+    assert_eq!(scroll_offset_for(17, 0, 20, 100, 3), 1);
+    // Moving up to line 12 with offset 10 keeps 3 lines above the cursor
+    assert_eq!(scroll_offset_for(12, 10, 20, 100, 3), 9);
+    // Near the top of the buffer the margin cannot be kept
+    assert_eq!(scroll_offset_for(1, 5, 20, 100, 3), 0);
+}
+
+#[test]
+fn test_scroll_offset_margin_does_not_scroll_past_end() {
+    // 25 lines in a 20-line view: the last useful offset is 5
+    assert_eq!(scroll_offset_for(24, 0, 20, 25, 3), 5);
+}
+
+#[test]
+fn test_scroll_offset_margin_limited_on_small_screens() {
+    // A margin larger than half the view is reduced so the cursor can move
+    assert_eq!(scroll_offset_for(3, 0, 5, 100, 10), 1);
+    assert_eq!(scroll_offset_for(7, 0, 0, 100, 3), 0);
+}
+
 // Helper function to simulate frame hash calculation
 // This mirrors the logic in renderer.rs
 fn calculate_test_hash(state: &EditorState, cursor_pos: Position) -> u64 {
@@ -285,6 +329,7 @@ fn calculate_test_hash(state: &EditorState, cursor_pos: Position) -> u64 {
         EditorMode::Insert => 1,
         EditorMode::Normal => 2,
         EditorMode::Prompt => 3,
+        EditorMode::Plugin(_) => 4,
     } << 40;
 
     // Include status message presence
@@ -299,3 +344,92 @@ fn calculate_test_hash(state: &EditorState, cursor_pos: Position) -> u64 {
 
     hash
 }
+
+fn hints(entries: &[(&str, &str)]) -> KeyHints {
+    KeyHints {
+        pending: "g".to_string(),
+        hints: entries
+            .iter()
+            .map(|(keys, command)| KeyHint {
+                keys: keys.to_string(),
+                command: command.to_string(),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_key_hint_lines_align_commands() {
+    let hints = hints(&[("d", "lsp.definition"), ("Ctrl+r", "lsp.references")]);
+
+    let lines = key_hint_lines(&hints, 20);
+
+    assert_eq!(lines, vec!["d       lsp.definition", "Ctrl+r  lsp.references"]);
+}
+
+#[test]
+fn test_key_hint_lines_fill_columns_top_to_bottom() {
+    let hints = hints(&[("a", "one"), ("b", "two"), ("c", "six")]);
+
+    // Each entry is 6 wide; 2 columns need 6 + 3 + 6 = 15
+    let lines = key_hint_lines(&hints, 15);
+
+    assert_eq!(lines, vec!["a  one   c  six", "b  two"]);
+}
+
+#[test]
+fn test_key_hint_lines_narrow_width_uses_one_column() {
+    let hints = hints(&[("a", "one"), ("b", "two")]);
+
+    assert_eq!(key_hint_lines(&hints, 2).len(), 2);
+    assert!(key_hint_lines(&KeyHints { pending: "g".to_string(), hints: Vec::new() }, 40).is_empty());
+}
+
+fn list(count: usize) -> PromptList {
+    PromptList::new((0..count).map(|i| format!("item {}", i)).collect())
+}
+
+#[test]
+fn test_prompt_list_lines_show_first_page() {
+    let (lines, highlighted) = prompt_list_lines(&list(10), 3, 20);
+    assert_eq!(lines, vec!["item 0", "item 1", "item 2"]);
+    assert_eq!(highlighted, Some(0));
+}
+
+#[test]
+fn test_prompt_list_lines_scroll_with_highlight() {
+    let mut list = list(10);
+    for _ in 0..4 {
+        list.select_next();
+    }
+    let (lines, highlighted) = prompt_list_lines(&list, 3, 20);
+    assert_eq!(lines, vec!["item 2", "item 3", "item 4"]);
+    assert_eq!(highlighted, Some(2));
+
+    // Wrapping around to the end shows the last page
+    let mut list = self::list(10);
+    list.select_previous();
+    let (lines, highlighted) = prompt_list_lines(&list, 3, 20);
+    assert_eq!(lines, vec!["item 7", "item 8", "item 9"]);
+    assert_eq!(highlighted, Some(2));
+}
+
+#[test]
+fn test_prompt_list_lines_align_details() {
+    let details = vec!["Ctrl+s".to_string(), String::new(), "F2, Ctrl+Shift+P".to_string()];
+    let list = PromptList::new(vec!["save".to_string(), "quit".to_string(), "palette".to_string()])
+        .with_details(details);
+
+    let (lines, _) = prompt_list_lines(&list, 3, 20);
+    assert_eq!(lines, vec!["save          Ctrl+s", "quit", "palette"]);
+
+    let (lines, _) = prompt_list_lines(&list, 3, 25);
+    assert_eq!(lines[2], "palette  F2, Ctrl+Shift+P");
+}
+
+#[test]
+fn test_prompt_list_lines_without_matches() {
+    let mut list = list(3);
+    list.filter("none");
+    assert_eq!(prompt_list_lines(&list, 3, 20), (Vec::new(), None));
+}
@@ -289,3 +289,14 @@ fn test_theme_memory_layout() {
     // With 11 Color fields, we expect ~44 bytes plus padding
     assert!(size < 200, "Theme struct is unexpectedly large: {} bytes", size);
 }
+
+#[test]
+fn test_theme_by_name() {
+    assert_eq!(Theme::by_name("dark"), Some(Theme::dark()));
+    assert_eq!(Theme::by_name("LIGHT"), Some(Theme::light()));
+    assert_eq!(Theme::by_name("solarized"), None);
+
+    for name in Theme::NAMES {
+        assert!(Theme::by_name(name).is_some(), "{} should be a theme", name);
+    }
+}
@@ -79,13 +79,13 @@ pub struct Theme {
     pub status_bar_fg: Color,
 
     // Status message colors
-    /// Color for error messages (e.g., "Error: File not found")
+    /// Color for error messages (e.g., a failed save)
     pub error: Color,
 
-    /// Color for warning messages (e.g., "Warning: Unsaved changes")
+    /// Color for warning messages (e.g., unsaved changes on quit)
     pub warning: Color,
 
-    /// Color for informational messages (e.g., "Info: File saved")
+    /// Color for informational messages (e.g., "Buffer is now editable")
     pub info: Color,
 
     /// Color for success messages (e.g., "Saved to file.txt")
@@ -101,6 +101,9 @@ pub struct Theme {
 
     /// Default background color for text area
     pub text_bg: Color,
+
+    /// Foreground color for line numbers in the gutter
+    pub line_number: Color,
 }
 
 impl Default for Theme {
@@ -178,6 +181,9 @@ impl Theme {
             // Text
             text_fg: Color::Reset, // Use terminal default
             text_bg: Color::Reset, // Use terminal default
+
+            // Gutter
+            line_number: Color::DarkGray,
         }
     }
 
@@ -227,6 +233,32 @@ impl Theme {
             // Text
             text_fg: Color::Reset,
             text_bg: Color::Reset,
+
+            // Gutter
+            line_number: Color::Gray,
+        }
+    }
+
+    /// Names accepted by [`Theme::by_name`]
+    pub const NAMES: &'static [&'static str] = &["dark", "light"];
+
+    /// Looks up a built-in theme by name (case-insensitive)
+    ///
+    /// Returns `None` for unknown names; see [`Theme::NAMES`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use termide::ui::Theme;
+    ///
+    /// assert_eq!(Theme::by_name("Light"), Some(Theme::light()));
+    /// assert!(Theme::by_name("solarized").is_none());
+    /// ```
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            _ => None,
         }
     }
 
@@ -42,7 +42,7 @@ fn test_ctrl_s_accessible_in_insert_mode() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), modifier));
 
     // Assert: Find match returns Save command
-    let command = registry.find_match(EditorMode::Insert);
+    let command = registry.find_match(&EditorMode::Insert);
     assert_eq!(command, Some(&EditorCommand::Save));
 }
 
@@ -62,7 +62,7 @@ fn test_ctrl_s_accessible_in_normal_mode() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), modifier));
 
     // Assert: Find match returns Save command
-    let command = registry.find_match(EditorMode::Normal);
+    let command = registry.find_match(&EditorMode::Normal);
     assert_eq!(command, Some(&EditorCommand::Save));
 }
 
@@ -82,7 +82,7 @@ fn test_ctrl_s_not_accessible_in_prompt_mode() {
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('s'), modifier));
 
     // Assert: No match in Prompt mode
-    let command = registry.find_match(EditorMode::Prompt);
+    let command = registry.find_match(&EditorMode::Prompt);
     assert_eq!(command, None, "Global bindings should not be active in Prompt mode");
 }
 
@@ -96,7 +96,7 @@ fn test_i_key_only_in_normal_mode() {
     // Act & Assert: 'i' in Normal mode triggers Insert mode
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Normal);
+    let command = registry.find_match(&EditorMode::Normal);
     assert_eq!(
         command,
         Some(&EditorCommand::ChangeMode(EditorMode::Insert))
@@ -105,7 +105,7 @@ fn test_i_key_only_in_normal_mode() {
     // Act & Assert: 'i' in Insert mode does nothing (no binding)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Insert);
+    let command = registry.find_match(&EditorMode::Insert);
     assert_eq!(command, None, "'i' should not match in Insert mode");
 }
 
@@ -119,7 +119,7 @@ fn test_arrow_keys_in_insert_and_normal_modes() {
     // Test Up arrow in Insert mode
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Up, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Insert);
+    let command = registry.find_match(&EditorMode::Insert);
     assert_eq!(
         command,
         Some(&EditorCommand::MoveCursor(Direction::Up)),
@@ -129,21 +129,21 @@ fn test_arrow_keys_in_insert_and_normal_modes() {
     // Test Up arrow in Normal mode
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Up, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Normal);
+    let command = registry.find_match(&EditorMode::Normal);
     assert_eq!(
         command,
         Some(&EditorCommand::MoveCursor(Direction::Up)),
         "Up arrow should work in Normal mode"
     );
 
-    // Test Up arrow NOT in Prompt mode
+    // Test Up arrow moves the prompt list highlight, not the cursor, in Prompt mode
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Up, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Prompt);
+    let command = registry.find_match(&EditorMode::Prompt);
     assert_eq!(
         command,
-        None,
-        "Arrow keys should not be bound in Prompt mode"
+        Some(&EditorCommand::PromptPrevious),
+        "Up arrow should move the prompt list highlight in Prompt mode"
     );
 }
 
@@ -157,7 +157,7 @@ fn test_enter_key_mode_specific_behavior() {
     // Test Enter in Insert mode (inserts newline)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Enter, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Insert);
+    let command = registry.find_match(&EditorMode::Insert);
     assert_eq!(
         command,
         Some(&EditorCommand::InsertChar('\n')),
@@ -167,7 +167,7 @@ fn test_enter_key_mode_specific_behavior() {
     // Test Enter in Prompt mode (accepts prompt)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Enter, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Prompt);
+    let command = registry.find_match(&EditorMode::Prompt);
     assert_eq!(
         command,
         Some(&EditorCommand::AcceptPrompt),
@@ -177,7 +177,7 @@ fn test_enter_key_mode_specific_behavior() {
     // Test Enter in Normal mode (no binding)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Enter, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Normal);
+    let command = registry.find_match(&EditorMode::Normal);
     assert_eq!(
         command,
         None,
@@ -195,7 +195,7 @@ fn test_escape_key_mode_specific_behavior() {
     // Test Esc in Insert mode (switch to Normal)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Esc, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Insert);
+    let command = registry.find_match(&EditorMode::Insert);
     assert_eq!(
         command,
         Some(&EditorCommand::ChangeMode(EditorMode::Normal)),
@@ -205,7 +205,7 @@ fn test_escape_key_mode_specific_behavior() {
     // Test Esc in Prompt mode (cancel prompt)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Esc, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Prompt);
+    let command = registry.find_match(&EditorMode::Prompt);
     assert_eq!(
         command,
         Some(&EditorCommand::CancelPrompt),
@@ -215,7 +215,7 @@ fn test_escape_key_mode_specific_behavior() {
     // Test Esc in Normal mode (no binding)
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Esc, KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Normal);
+    let command = registry.find_match(&EditorMode::Normal);
     assert_eq!(
         command,
         None,
@@ -261,7 +261,7 @@ fn test_user_binding_can_override_default() {
     // Assert: User binding takes precedence
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
-    let command = registry.find_match(EditorMode::Normal);
+    let command = registry.find_match(&EditorMode::Normal);
     assert_eq!(
         command,
         Some(&EditorCommand::Quit),
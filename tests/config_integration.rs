@@ -43,7 +43,7 @@ fn test_user_binding_overrides_default() {
 
     // Process Ctrl+S key event
     let key_event = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     // Should match user binding (Quit) instead of default (Save)
     match match_result {
@@ -76,7 +76,7 @@ fn test_user_binding_adds_new_binding() {
 
     // Process Ctrl+K key event
     let key_event = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     // Should match user binding
     match match_result {
@@ -110,7 +110,7 @@ fn test_mode_specific_user_binding() {
 
     // Process 'x' in Normal mode - should match
     let key_event = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => {
@@ -120,7 +120,7 @@ fn test_mode_specific_user_binding() {
     }
 
     // Process 'x' in Insert mode - should NOT match (mode-specific binding)
-    let match_result_insert = input_handler.process_key_event(key_event, EditorMode::Insert);
+    let match_result_insert = input_handler.process_key_event(key_event, &EditorMode::Insert);
     match match_result_insert {
         MatchResult::NoMatch => {
             // Expected - binding is specific to Normal mode
@@ -160,7 +160,7 @@ fn test_multiple_user_bindings() {
 
     // Test Ctrl+S -> Quit
     let key_event = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Quit),
         _ => panic!("Expected Quit command"),
@@ -168,7 +168,7 @@ fn test_multiple_user_bindings() {
 
     // Test Ctrl+Q -> Save
     let key_event = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Save),
         _ => panic!("Expected Save command"),
@@ -176,10 +176,10 @@ fn test_multiple_user_bindings() {
 
     // Test d d -> DeleteChar (multi-key sequence)
     let key_event = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
-    let match_result1 = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result1 = input_handler.process_key_event(key_event, &EditorMode::Normal);
     assert!(matches!(match_result1, MatchResult::Partial));
 
-    let match_result2 = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result2 = input_handler.process_key_event(key_event, &EditorMode::Normal);
     match match_result2 {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::DeleteChar),
         _ => panic!("Expected DeleteChar command for 'd d' sequence"),
@@ -208,7 +208,7 @@ fn test_plugin_command_in_config() {
 
     // Process Ctrl+F key event
     let key_event = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     // Should match plugin command
     match match_result {
@@ -282,14 +282,14 @@ fn test_global_binding_works_in_all_modes() {
     let key_event = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
 
     // Test in Normal mode
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Quit),
         _ => panic!("Expected Quit in Normal mode"),
     }
 
     // Test in Insert mode
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Insert);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Insert);
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Quit),
         _ => panic!("Expected Quit in Insert mode"),
@@ -317,7 +317,7 @@ fn test_empty_config_file_no_errors() {
 
     // Verify default bindings still work (Ctrl+S -> Save)
     let key_event = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => {
@@ -352,7 +352,7 @@ fn test_config_with_invalid_sequence() {
 
     // Verify the valid binding works
     let key_event = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Save),
@@ -385,7 +385,7 @@ fn test_config_with_invalid_command() {
 
     // Verify the valid binding works
     let key_event = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Quit),
@@ -420,7 +420,7 @@ fn test_config_with_invalid_mode() {
 
     // Verify the valid binding works
     let key_event = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => assert_eq!(cmd, EditorCommand::Save),
@@ -452,7 +452,7 @@ fn test_user_binding_overrides_plugin_binding() {
 
     // Verify plugin binding works
     let key_event = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => match cmd {
@@ -477,7 +477,7 @@ fn test_user_binding_overrides_plugin_binding() {
     assert_eq!(result.unwrap().loaded, 1);
 
     // User binding should now override plugin binding
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => {
@@ -525,7 +525,7 @@ fn test_plugin_then_config_user_wins() {
 
     // Process Ctrl+T in Normal mode
     let key_event = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     // Should match user binding (DeleteChar), not plugin binding
     match match_result {
@@ -565,7 +565,7 @@ fn test_all_three_priority_levels() {
 
     // At this point, plugin binding should override default
     let key_event = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(EditorCommand::PluginCommand { plugin_name, .. }) => {
@@ -585,7 +585,7 @@ fn test_all_three_priority_levels() {
     load_user_keybindings(&mut input_handler.registry_mut(), config_file.path()).unwrap();
 
     // User binding should override both plugin and default
-    let match_result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let match_result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match match_result {
         MatchResult::Matched(cmd) => {
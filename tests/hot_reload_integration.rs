@@ -110,13 +110,13 @@ command = "file.new"
 
     // Verify old Ctrl+q binding is gone
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('q'), KeyModifiers::CONTROL));
-    let cmd = registry.find_match(termide::editor::EditorMode::Normal);
+    let cmd = registry.find_match(&termide::editor::EditorMode::Normal);
     assert!(cmd.is_none(), "Old Ctrl+q binding should be removed");
 
     // Verify new Ctrl+n binding exists
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
-    let cmd = registry.find_match(termide::editor::EditorMode::Normal);
+    let cmd = registry.find_match(&termide::editor::EditorMode::Normal);
     assert!(cmd.is_some(), "New Ctrl+n binding should exist");
 }
 
@@ -177,13 +177,13 @@ command = "file.save"
 
     // Verify default binding still works
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('i'), KeyModifiers::NONE));
-    let cmd = registry.find_match(termide::editor::EditorMode::Normal);
+    let cmd = registry.find_match(&termide::editor::EditorMode::Normal);
     assert!(cmd.is_some(), "Default binding should still exist");
 
     // Verify plugin binding still works
     registry.clear_sequence();
     registry.add_to_sequence(KeyPattern::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
-    let cmd = registry.find_match(termide::editor::EditorMode::Normal);
+    let cmd = registry.find_match(&termide::editor::EditorMode::Normal);
     assert!(cmd.is_some(), "Plugin binding should still exist");
 }
 
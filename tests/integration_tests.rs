@@ -411,7 +411,7 @@ fn test_register_and_trigger_plugin_command() {
     );
 
     // Process the key event
-    let result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     // Verify the command was matched
     use termide::input::input_handler::MatchResult;
@@ -493,7 +493,7 @@ fn test_multiple_plugin_commands() {
         KeyCode::F(5),
         KeyModifiers::NONE,
     );
-    let result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     use termide::input::input_handler::MatchResult;
     match result {
         MatchResult::Matched(EditorCommand::PluginCommand {
@@ -511,7 +511,7 @@ fn test_multiple_plugin_commands() {
         KeyCode::F(6),
         KeyModifiers::NONE,
     );
-    let result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(EditorCommand::PluginCommand {
             plugin_name,
@@ -528,7 +528,7 @@ fn test_multiple_plugin_commands() {
         KeyCode::F(7),
         KeyModifiers::NONE,
     );
-    let result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     match result {
         MatchResult::Matched(EditorCommand::PluginCommand {
             plugin_name,
@@ -567,7 +567,7 @@ fn test_plugin_command_mode_specific() {
     let key_event = KeyEvent::new(KeyCode::F(8), KeyModifiers::NONE);
 
     // Should match in Normal mode
-    let result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Normal);
     use termide::input::input_handler::MatchResult;
     match result {
         MatchResult::Matched(EditorCommand::PluginCommand { .. }) => {}
@@ -575,7 +575,7 @@ fn test_plugin_command_mode_specific() {
     }
 
     // Should NOT match in Insert mode
-    let result = input_handler.process_key_event(key_event, EditorMode::Insert);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Insert);
     match result {
         MatchResult::NoMatch => {}
         _ => panic!("Expected NoMatch in Insert mode"),
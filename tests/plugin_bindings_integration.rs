@@ -29,7 +29,7 @@ fn test_plugin_binding_end_to_end_global() {
 
     // Test the binding works in Normal mode
     let key_event = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL);
-    let result = input_handler.process_key_event(key_event, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Normal);
 
     match result {
         MatchResult::Matched(cmd) => match cmd {
@@ -46,11 +46,11 @@ fn test_plugin_binding_end_to_end_global() {
     }
 
     // Test the binding works in Insert mode
-    let result = input_handler.process_key_event(key_event, EditorMode::Insert);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Insert);
     assert!(matches!(result, MatchResult::Matched(_)));
 
     // Test the binding does NOT work in Prompt mode (global excludes Prompt)
-    let result = input_handler.process_key_event(key_event, EditorMode::Prompt);
+    let result = input_handler.process_key_event(key_event, &EditorMode::Prompt);
     assert!(matches!(result, MatchResult::NoMatch));
 }
 
@@ -75,11 +75,11 @@ fn test_plugin_binding_end_to_end_mode_specific() {
     let key_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
 
     // First key - should be partial match
-    let result = input_handler.process_key_event(key_g, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_g, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Partial));
 
     // Second key - should match
-    let result = input_handler.process_key_event(key_d, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_d, &EditorMode::Normal);
     match result {
         MatchResult::Matched(cmd) => match cmd {
             EditorCommand::PluginCommand {
@@ -95,7 +95,7 @@ fn test_plugin_binding_end_to_end_mode_specific() {
     }
 
     // Test same sequence in Insert mode - should NOT match
-    let result = input_handler.process_key_event(key_g, EditorMode::Insert);
+    let result = input_handler.process_key_event(key_g, &EditorMode::Insert);
     // Should not be partial in Insert mode
     assert!(matches!(result, MatchResult::NoMatch));
 }
@@ -124,7 +124,7 @@ fn test_plugin_binding_priority_over_defaults() {
 
     // Press 'i' in Normal mode - should trigger plugin command, not default
     let key_i = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
-    let result = input_handler.process_key_event(key_i, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_i, &EditorMode::Normal);
 
     match result {
         MatchResult::Matched(cmd) => match cmd {
@@ -184,8 +184,8 @@ fn test_multiple_plugins_coexist() {
     // Test LSP binding
     let key_g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
     let key_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
-    input_handler.process_key_event(key_g, EditorMode::Normal);
-    let result = input_handler.process_key_event(key_d, EditorMode::Normal);
+    input_handler.process_key_event(key_g, &EditorMode::Normal);
+    let result = input_handler.process_key_event(key_d, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Matched(_)));
 
     // Test formatter binding (Ctrl+Shift+F)
@@ -193,16 +193,16 @@ fn test_multiple_plugins_coexist() {
         KeyCode::Char('f'),
         KeyModifiers::CONTROL | KeyModifiers::SHIFT,
     );
-    let result = input_handler.process_key_event(key_f, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_f, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::Matched(_)));
 
     // Test autocomplete binding (Ctrl+Space in Insert mode)
     let key_space = KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL);
-    let result = input_handler.process_key_event(key_space, EditorMode::Insert);
+    let result = input_handler.process_key_event(key_space, &EditorMode::Insert);
     assert!(matches!(result, MatchResult::Matched(_)));
 
     // Autocomplete should NOT work in Normal mode
-    let result = input_handler.process_key_event(key_space, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_space, &EditorMode::Normal);
     assert!(matches!(result, MatchResult::NoMatch));
 }
 
@@ -234,7 +234,7 @@ fn test_plugin_binding_command_routing() {
 
     // Test Ctrl+A routes to plugin-a
     let key_a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
-    let result = input_handler.process_key_event(key_a, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_a, &EditorMode::Normal);
 
     match result {
         MatchResult::Matched(EditorCommand::PluginCommand { plugin_name, .. }) => {
@@ -245,7 +245,7 @@ fn test_plugin_binding_command_routing() {
 
     // Test Ctrl+B routes to plugin-b
     let key_b = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL);
-    let result = input_handler.process_key_event(key_b, EditorMode::Normal);
+    let result = input_handler.process_key_event(key_b, &EditorMode::Normal);
 
     match result {
         MatchResult::Matched(EditorCommand::PluginCommand { plugin_name, .. }) => {
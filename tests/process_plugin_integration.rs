@@ -0,0 +1,428 @@
+//! Integration tests for process plugins
+//!
+//! These tests run the stub plugin `examples/stub_plugin.rs` as a separate
+//! process and cover the JSON-RPC protocol end-to-end: registering bindings,
+//! running commands that query and edit the buffer, UI requests, events and
+//! restarts after crashes.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use termide::buffer::Position;
+use termide::editor::{EditorMode, EditorState, PromptKind};
+use termide::input::input_handler::{InputHandler, MatchResult};
+use termide::input::EditorCommand;
+use termide::plugin::{
+    discover_plugins, ActiveBuffer, Capability, PluginHost, ProcessPlugin, ProcessPluginError,
+    ProcessSpec,
+};
+use termide_plugin_api::plugin::{CommandContext, Plugin, PluginError};
+use termide_plugin_api::ui::PickerSelection;
+
+/// Builds the stub plugin example once and returns its path
+fn stub_path() -> &'static PathBuf {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+    PATH.get_or_init(|| {
+        let mut build = Command::new(env!("CARGO"));
+        build
+            .args(["build", "--quiet", "--example", "stub_plugin"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"));
+        if !cfg!(debug_assertions) {
+            build.arg("--release");
+        }
+        assert!(build.status().expect("cargo should run").success());
+
+        // Examples are built next to the `deps` directory of this test
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("examples");
+        path.push(format!("stub_plugin{}", std::env::consts::EXE_SUFFIX));
+        path
+    })
+}
+
+fn stub_spec(args: &[&str]) -> ProcessSpec {
+    let mut command = vec![stub_path().display().to_string()];
+    command.extend(args.iter().map(|arg| arg.to_string()));
+    ProcessSpec::new("stub", command)
+}
+
+fn all_capabilities() -> BTreeSet<Capability> {
+    Capability::ALL.into_iter().collect()
+}
+
+fn spawn_stub() -> ProcessPlugin {
+    ProcessPlugin::spawn(stub_spec(&[]), all_capabilities()).expect("stub should start")
+}
+
+/// Runs `command` of `plugin` on the active buffer of `state`
+fn run(
+    plugin: &mut ProcessPlugin,
+    command: &str,
+    state: &mut EditorState,
+    cursor: &mut Position,
+) -> Result<(), PluginError> {
+    let mut buffer = ActiveBuffer::new(state, cursor);
+    let mut context = CommandContext::new(&mut buffer);
+    plugin.execute(command, &mut context)
+}
+
+fn state_with(content: &str) -> EditorState {
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content(content);
+    state
+}
+
+#[test]
+fn test_bindings_run_commands_through_host() {
+    let mut host = PluginHost::new();
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    let warnings = host.load_all(vec![Box::new(spawn_stub())], input_handler.registry_mut());
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let key = KeyEvent::new(
+        KeyCode::Char('u'),
+        KeyModifiers::CONTROL | KeyModifiers::ALT,
+    );
+    let MatchResult::Matched(EditorCommand::PluginCommand {
+        plugin_name,
+        command_name,
+    }) = input_handler.process_key_event(key, &EditorMode::Normal)
+    else {
+        panic!("Ctrl+Alt+u should run a plugin command");
+    };
+    assert_eq!(
+        (plugin_name.as_str(), command_name.as_str()),
+        ("stub", "upper")
+    );
+
+    let mut state = state_with("first\nsecond");
+    let mut cursor = Position::new(1, 2);
+    host.execute(&plugin_name, &command_name, &mut state, &mut cursor, input_handler.registry())
+        .unwrap();
+
+    assert_eq!(state.buffer().content(), "first\nSECOND");
+    assert_eq!(cursor, Position::new(1, 2));
+    assert_eq!(state.status_message(), Some("Uppercased"));
+}
+
+#[test]
+fn test_binding_modes_are_mapped() {
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    let mut stub = spawn_stub();
+    stub.register(input_handler.registry_mut()).unwrap();
+
+    let key = KeyEvent::new(
+        KeyCode::Char('p'),
+        KeyModifiers::CONTROL | KeyModifiers::ALT,
+    );
+    assert!(matches!(
+        input_handler.process_key_event(key, &EditorMode::Insert),
+        MatchResult::Matched(EditorCommand::PluginCommand { .. })
+    ));
+    assert!(!matches!(
+        input_handler.process_key_event(key, &EditorMode::Normal),
+        MatchResult::Matched(_)
+    ));
+}
+
+#[test]
+fn test_unknown_command() {
+    let mut stub = spawn_stub();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    assert_eq!(
+        run(&mut stub, "nope", &mut state, &mut cursor),
+        Err(PluginError::UnknownCommand("nope".to_string()))
+    );
+}
+
+#[test]
+fn test_capabilities_limit_requests() {
+    let read_only: BTreeSet<_> = [Capability::BufferRead].into();
+    let mut stub = ProcessPlugin::spawn(stub_spec(&[]), read_only).unwrap();
+    let mut state = state_with("quiet");
+    let mut cursor = Position::origin();
+
+    let err = run(&mut stub, "upper", &mut state, &mut cursor).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("'buffer.delete' needs the 'buffer-edit' capability"),
+        "{}",
+        err
+    );
+    assert_eq!(state.buffer().content(), "quiet");
+}
+
+#[test]
+fn test_manifest_is_read_at_startup() {
+    let stub = ProcessPlugin::spawn(stub_spec(&["--manifest"]), all_capabilities()).unwrap();
+    let manifest = stub.manifest().expect("the stub sends a manifest");
+    assert_eq!(manifest.name, "stub");
+    assert_eq!(manifest.version, "0.3.0");
+    assert_eq!(manifest.command("upper").unwrap().description, "Uppercase the cursor line");
+
+    // The manifest asks for buffer-edit, which is not granted
+    let read_only: BTreeSet<_> = [Capability::BufferRead].into();
+    match ProcessPlugin::spawn(stub_spec(&["--manifest"]), read_only) {
+        Err(err @ ProcessPluginError::Manifest(_)) => {
+            assert!(err.to_string().contains("'buffer-edit' capability"), "{}", err)
+        }
+        Err(err) => panic!("expected a manifest error, got {}", err),
+        Ok(_) => panic!("the stub should not start"),
+    }
+}
+
+#[test]
+fn test_plugin_mode_round_trip() {
+    let mut host = PluginHost::new();
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    assert!(host
+        .load_all(vec![Box::new(spawn_stub())], input_handler.registry_mut())
+        .is_empty());
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    state.set_mode(EditorMode::Normal);
+
+    host.execute("stub", "view", &mut state, &mut cursor, input_handler.registry())
+        .unwrap();
+    let view = input_handler
+        .registry()
+        .plugin_mode("stub.view")
+        .expect("the mode is registered");
+    assert_eq!(state.mode(), EditorMode::Plugin(view));
+    assert_eq!(state.mode().to_string(), "VIEW");
+
+    // `q` is only bound in the plugin's mode
+    let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+    assert_eq!(
+        input_handler.process_key_event(q, &state.mode()),
+        MatchResult::Matched(EditorCommand::PluginCommand {
+            plugin_name: "stub".to_string(),
+            command_name: "leave".to_string(),
+        })
+    );
+    assert_eq!(
+        input_handler.process_key_event(q, &EditorMode::Normal),
+        MatchResult::NoMatch
+    );
+
+    host.execute("stub", "leave", &mut state, &mut cursor, input_handler.registry())
+        .unwrap();
+    assert_eq!(state.mode(), EditorMode::Normal);
+}
+
+#[test]
+fn test_picker_round_trip() {
+    let mut host = PluginHost::new();
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    host.load_all(vec![Box::new(spawn_stub())], input_handler.registry_mut());
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    host.execute("stub", "pick", &mut state, &mut cursor, input_handler.registry())
+        .unwrap();
+    assert_eq!(
+        state.prompt_kind(),
+        &PromptKind::PluginPicker {
+            plugin: "stub".to_string(),
+            id: "color".to_string()
+        }
+    );
+
+    let selection = PickerSelection {
+        index: 1,
+        item: "green".to_string(),
+    };
+    host.picker_result("stub", "color", Some(selection), &mut state, &mut cursor, input_handler.registry())
+        .unwrap();
+    assert_eq!(state.status_message(), Some("Picked green"));
+}
+
+#[test]
+fn test_events_reach_subscribed_plugin() {
+    let mut stub = spawn_stub();
+    assert!(stub.is_subscribed("buffer_saved"));
+    assert!(!stub.is_subscribed("mode_changed"));
+
+    stub.notify_event("mode_changed", json!({ "mode": "insert" }))
+        .unwrap();
+    stub.notify_event("buffer_saved", json!({ "path": "notes.txt" }))
+        .unwrap();
+
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    run(&mut stub, "last_event", &mut state, &mut cursor).unwrap();
+    assert_eq!(state.buffer().content(), "buffer_saved");
+}
+
+#[test]
+fn test_host_forwards_published_events() {
+    let mut host = PluginHost::new();
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    host.load_all(vec![Box::new(spawn_stub())], input_handler.registry_mut());
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    state.save_as(&dir.path().join("notes.txt")).unwrap();
+    // Not subscribed, so it doesn't replace the save
+    state.set_mode(EditorMode::Normal);
+    assert!(host.dispatch_events(&mut state, &mut cursor, input_handler.registry()).is_empty());
+
+    host.execute("stub", "last_event", &mut state, &mut cursor, input_handler.registry())
+        .unwrap();
+    assert_eq!(state.buffer().content(), "buffer_saved");
+}
+
+#[test]
+fn test_crashed_process_is_restarted() {
+    let mut stub = spawn_stub();
+    stub.set_restart_delays(Duration::ZERO, Duration::ZERO);
+    let first = stub.process_id().unwrap();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    let err = run(&mut stub, "crash", &mut state, &mut cursor).unwrap_err();
+    assert_eq!(
+        err,
+        PluginError::Failed("plugin process exited".to_string())
+    );
+    assert!(!stub.is_running());
+
+    run(&mut stub, "pid", &mut state, &mut cursor).unwrap();
+    let second = stub.process_id().unwrap();
+    assert_ne!(first, second);
+    assert_eq!(state.buffer().content(), second.to_string());
+}
+
+#[test]
+fn test_restart_waits_for_backoff() {
+    let mut stub = spawn_stub();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    run(&mut stub, "crash", &mut state, &mut cursor).unwrap_err();
+    let err = run(&mut stub, "pid", &mut state, &mut cursor).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("plugin process crashed; restarting in"),
+        "{}",
+        err
+    );
+    assert_eq!(state.buffer().content(), "");
+}
+
+#[test]
+fn test_hanging_process_times_out() {
+    let mut stub = ProcessPlugin::with_timeout(
+        stub_spec(&[]),
+        all_capabilities(),
+        Duration::from_millis(300),
+    )
+    .unwrap();
+    stub.set_restart_delays(Duration::ZERO, Duration::ZERO);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    let err = run(&mut stub, "hang", &mut state, &mut cursor).unwrap_err();
+    assert_eq!(
+        err,
+        PluginError::Failed("plugin process did not answer within 300 ms".to_string())
+    );
+    assert!(run(&mut stub, "pid", &mut state, &mut cursor).is_ok());
+}
+
+#[test]
+fn test_chatty_process_times_out() {
+    let mut stub = ProcessPlugin::with_timeout(
+        stub_spec(&[]),
+        all_capabilities(),
+        Duration::from_millis(300),
+    )
+    .unwrap();
+    stub.set_restart_delays(Duration::ZERO, Duration::ZERO);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    // Messages without the response don't extend the deadline
+    let started = Instant::now();
+    let err = run(&mut stub, "chatter", &mut state, &mut cursor).unwrap_err();
+    assert_eq!(
+        err,
+        PluginError::Failed("plugin process did not answer within 300 ms".to_string())
+    );
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert!(run(&mut stub, "pid", &mut state, &mut cursor).is_ok());
+}
+
+#[test]
+fn test_process_not_reading_input_is_a_crash() {
+    let mut stub = spawn_stub();
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    run(&mut stub, "deaf", &mut state, &mut cursor).unwrap();
+
+    // Events fill the pipe and then the queue, but never block the editor
+    let data = json!({ "path": "x".repeat(64 * 1024) });
+    let err = (0..1000)
+        .find_map(|_| stub.notify_event("buffer_saved", data.clone()).err())
+        .expect("the queue should fill up");
+    assert!(matches!(err, ProcessPluginError::Stalled));
+    assert!(!stub.is_running());
+}
+
+#[test]
+fn test_invalid_output_is_a_crash() {
+    let mut stub = spawn_stub();
+    stub.set_restart_delays(Duration::ZERO, Duration::ZERO);
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    let err = run(&mut stub, "garbage", &mut state, &mut cursor).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("protocol error: invalid message"),
+        "{}",
+        err
+    );
+    assert!(run(&mut stub, "pid", &mut state, &mut cursor).is_ok());
+}
+
+#[test]
+fn test_missing_program_fails_to_spawn() {
+    let spec = ProcessSpec::new("ghost", vec!["/nonexistent/termide-plugin".to_string()]);
+    let err = ProcessPlugin::spawn(spec, all_capabilities())
+        .err()
+        .unwrap();
+    assert!(matches!(err, ProcessPluginError::Spawn { .. }));
+    assert!(err
+        .to_string()
+        .starts_with("failed to start '/nonexistent/termide-plugin'"));
+}
+
+#[test]
+fn test_discover_plugins_starts_configured_processes() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    fs::write(
+        &config,
+        format!(
+            "[plugins.stub]\ncommand = [{:?}, \"--no-bindings\"]\n\n[plugins.ghost]\ncommand = []\n",
+            stub_path().display().to_string()
+        ),
+    )
+    .unwrap();
+
+    let (plugins, warnings) = discover_plugins(Some(&config), None);
+    let names: Vec<&str> = plugins.iter().map(|plugin| plugin.name()).collect();
+    assert_eq!(names, vec!["stub"]);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("plugins.ghost:"), "{}", warnings[0]);
+}
@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use tempfile::TempDir;
 use termide::buffer::Position;
 use termide::editor::{EditorMode, EditorState};
+use termide_plugin_api::ui::Severity;
 
 #[test]
 fn test_render_empty_buffer() {
@@ -106,10 +107,11 @@ fn test_render_warning_message() {
     // Trigger quit warning
     let should_quit = state.request_quit();
     assert!(!should_quit);
+    assert_eq!(state.status_severity(), Severity::Warning);
     assert!(state
         .status_message()
         .unwrap()
-        .starts_with("Warning:"));
+        .starts_with("Unsaved changes!"));
 }
 
 #[test]
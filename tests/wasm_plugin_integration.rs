@@ -0,0 +1,163 @@
+//! Integration tests for WebAssembly plugins
+//!
+//! These tests load the bundled example plugin `examples/plugins/hello.wat`
+//! through the plugin host and run its commands from keybindings.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use termide::buffer::Position;
+use termide::editor::{EditorMode, EditorState};
+use termide::input::input_handler::{InputHandler, MatchResult};
+use termide::input::EditorCommand;
+use termide::plugin::{load_wasm_plugins, PluginGrants, PluginHost};
+
+fn example_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/plugins"))
+}
+
+/// Loads the example plugins into a host and an input handler
+fn load_examples(grants: &PluginGrants) -> (PluginHost, InputHandler, Vec<String>) {
+    let (plugins, mut warnings) = load_wasm_plugins(example_dir(), grants);
+    let mut host = PluginHost::new();
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    warnings.extend(host.load_all(plugins, input_handler.registry_mut()));
+    (host, input_handler, warnings)
+}
+
+fn ctrl_alt(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL | KeyModifiers::ALT)
+}
+
+/// Runs the plugin command bound to `key` in Normal mode
+fn press(
+    key: KeyEvent,
+    host: &mut PluginHost,
+    input_handler: &mut InputHandler,
+    state: &mut EditorState,
+    cursor: &mut Position,
+) {
+    match input_handler.process_key_event(key, &EditorMode::Normal) {
+        MatchResult::Matched(EditorCommand::PluginCommand {
+            plugin_name,
+            command_name,
+        }) => host
+            .execute(&plugin_name, &command_name, state, cursor, input_handler.registry())
+            .expect("command should succeed"),
+        other => panic!("expected a plugin command, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_example_plugin_loads_and_binds_keys() {
+    let (host, _, warnings) = load_examples(&PluginGrants::default());
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(host.names().collect::<Vec<_>>(), vec!["hello"]);
+}
+
+#[test]
+fn test_example_plugin_manifest() {
+    let (host, _, _) = load_examples(&PluginGrants::default());
+    let manifest = host.manifest("hello").expect("hello.toml should be loaded");
+    assert_eq!(manifest.version, "1.0.0");
+    let commands: Vec<&str> = manifest.commands.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(commands, vec!["greet", "duplicate_line"]);
+    assert!(manifest.validate().is_ok());
+}
+
+#[test]
+fn test_example_plugin_greets_at_cursor() {
+    let (mut host, mut input_handler, _) = load_examples(&PluginGrants::default());
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("say: ");
+    let mut cursor = Position::new(0, 5);
+
+    press(
+        ctrl_alt('h'),
+        &mut host,
+        &mut input_handler,
+        &mut state,
+        &mut cursor,
+    );
+
+    assert_eq!(state.buffer().content(), "say: Hello from WASM!");
+    assert_eq!(cursor, Position::new(0, 21));
+    assert_eq!(state.status_message(), Some("Greeted"));
+}
+
+#[test]
+fn test_example_plugin_duplicates_line() {
+    let (mut host, mut input_handler, _) = load_examples(&PluginGrants::default());
+    let mut state = EditorState::new();
+    state.buffer_mut().set_content("first\nsecond\nthird");
+    let mut cursor = Position::new(1, 3);
+
+    press(
+        ctrl_alt('d'),
+        &mut host,
+        &mut input_handler,
+        &mut state,
+        &mut cursor,
+    );
+
+    assert_eq!(state.buffer().content(), "first\nsecond\nsecond\nthird");
+    assert_eq!(cursor, Position::new(2, 3));
+}
+
+#[test]
+fn test_example_plugin_rejects_unknown_command() {
+    let (mut host, input_handler, _) = load_examples(&PluginGrants::default());
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+
+    let err = host
+        .execute("hello", "shout", &mut state, &mut cursor, input_handler.registry())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "plugin 'hello': unknown command");
+}
+
+#[test]
+fn test_example_plugin_needs_its_capabilities() {
+    let table: toml::Table = toml::from_str("[hello]\ncapabilities = [\"buffer-read\"]").unwrap();
+    let (grants, _) = PluginGrants::parse(&table);
+    let (host, _, warnings) = load_examples(&grants);
+
+    assert!(host.is_empty());
+    assert_eq!(warnings.len(), 1);
+    assert!(
+        warnings[0].contains("'buffer-edit' capability"),
+        "{}",
+        warnings[0]
+    );
+}
+
+#[test]
+fn test_plugin_loaded_from_config_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::copy(
+        example_dir().join("hello.wat"),
+        dir.path().join("greeter.wat"),
+    )
+    .unwrap();
+
+    let (plugins, warnings) = load_wasm_plugins(dir.path(), &PluginGrants::default());
+    let mut host = PluginHost::new();
+    let mut input_handler = InputHandler::with_timeout(Duration::from_millis(1000));
+    assert!(warnings.is_empty());
+    assert!(host
+        .load_all(plugins, input_handler.registry_mut())
+        .is_empty());
+
+    // Commands are namespaced by the file stem
+    let mut state = EditorState::new();
+    let mut cursor = Position::origin();
+    press(
+        ctrl_alt('h'),
+        &mut host,
+        &mut input_handler,
+        &mut state,
+        &mut cursor,
+    );
+    assert_eq!(state.buffer().content(), "Hello from WASM!");
+}